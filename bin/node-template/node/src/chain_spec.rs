@@ -2,7 +2,7 @@ use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
 	SudoConfig, SystemConfig, WASM_BINARY, Signature, SessionConfig, opaque::SessionKeys,
-	ValidatorSetConfig, AccountSetConfig
+	ValidatorSetConfig, AccountSetConfig, ImOnlineConfig, ImOnlineId
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -38,16 +38,18 @@ pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId where
 fn session_keys(
 	aura: AuraId,
 	grandpa: GrandpaId,
+	im_online: ImOnlineId,
 ) -> SessionKeys {
-	SessionKeys { aura, grandpa }
+	SessionKeys { aura, grandpa, im_online }
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+/// Generate the session keys of an authority.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId, ImOnlineId) {
 	(
 		get_account_id_from_seed::<sr25519::Public>(s),
 		get_from_seed::<AuraId>(s),
 		get_from_seed::<GrandpaId>(s),
+		get_from_seed::<ImOnlineId>(s),
 	)
 }
 
@@ -141,7 +143,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId, ImOnlineId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
 	enable_println: bool,
@@ -173,9 +175,13 @@ fn testnet_genesis(
 		}),
 		pallet_session: Some(SessionConfig {
 			keys: initial_authorities.iter().map(|x| {
-				(x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone()))
+				(x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone(), x.3.clone()))
 			}).collect::<Vec<_>>(),
 		}),
+		pallet_im_online: Some(ImOnlineConfig {
+			// Taken from the session keys.
+			keys: vec![],
+		}),
 		pallet_contracts: Some(ContractsConfig {
             current_schedule: pallet_contracts::Schedule {
                     enable_println,
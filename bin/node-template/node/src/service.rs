@@ -117,6 +117,12 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		);
 	}
 
+	// Keep the nodes authorized by `pallet_node_authorization` in sync with the network, which
+	// only enforces them when started with `--permissioned`.
+	sc_service::build_network_authorization(
+		task_manager.spawn_handle(), client.clone(), network.clone(),
+	);
+
 	let role = config.role.clone();
 	let force_authoring = config.force_authoring;
 	let name = config.network.node_name.clone();
@@ -7,6 +7,7 @@
 
 use sp_std::prelude::*;
 use codec::{Decode, Encode};
+use frame_support::type_info::TypeInfo;
 use sp_std::marker::PhantomData;
 use sp_std::fmt::Debug;
 use frame_support::{
@@ -94,7 +95,7 @@ decl_event!(
 ///  and the extrinsics are filtered out before they hit the pallet logic.
 
 /// The `AllowAccount` struct.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, Default)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, TypeInfo)]
 pub struct AllowAccount<T: Trait + Send + Sync>(PhantomData<T>);
 
 impl<T: Trait + Send + Sync> AllowAccount<T> {
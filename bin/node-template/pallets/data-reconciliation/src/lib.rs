@@ -29,6 +29,7 @@ use frame_support::{
 	sp_runtime::{RuntimeDebug, FixedU128},
 	dispatch::DispatchResult
 };
+use frame_support::type_info::TypeInfo;
 
 use frame_system::{self as system, ensure_signed};
 
@@ -67,7 +68,7 @@ pub type DateCampaign = Vec<u8>;
 pub type ErrorMessage = Vec<u8>;
 pub type Failed = bool;
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct Campaign {
     name: Vec<u8>,
 	total_budget: u128,
@@ -86,7 +87,7 @@ pub struct Campaign {
 	timezone: Vec<u8>
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct AggregatedData {
 	campaign_id: CampaignId,
 	platform: Platform,
@@ -98,7 +99,7 @@ pub struct AggregatedData {
 	conversions: u128,
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct Kpis {
 	final_count: u128,
 	cost: u128,
@@ -108,7 +109,7 @@ pub struct Kpis {
 	client: u128
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct ReconciledData {
 	amount_spent: u128,
 	budget_utilisation: u128,
@@ -29,6 +29,7 @@ use frame_support::{
 	traits::{Get},
 	dispatch::DispatchResult,
 };
+use frame_support::type_info::TypeInfo;
 
 use frame_system::{self as system, ensure_signed};
 
@@ -59,7 +60,7 @@ type OrderDate = Vec<u8>;
 type ErrorMessage = Vec<u8>;
 type Failed = bool;
 
-#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 struct BillboardData {
 	id: BillboardId,
 	spot_duration: u32,
@@ -68,7 +69,7 @@ struct BillboardData {
 	imp_multiplier_per_day: u32
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct Billboard {
 	spot_duration: u32,
 	spots_per_hour: u32,
@@ -76,7 +77,7 @@ pub struct Billboard {
 	imp_multiplier_per_day: u32
 }
 
-#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct OrderData {
 	start_date: i64,
 	end_date: i64,
@@ -86,7 +87,7 @@ pub struct OrderData {
 	target_inventory: Vec<BillboardData>
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct Order {
 	start_date: i64,
 	end_date: i64,
@@ -95,7 +96,7 @@ pub struct Order {
 	creative_list: Vec<CreativeId>
 }
 
-#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct SessionData {
 	id: SessionId,
 	order_id: OrderId,
@@ -106,7 +107,7 @@ pub struct SessionData {
 	duration: u32
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct VerifedSpot {
 	verified_audience: u32
 }
@@ -41,6 +41,9 @@ impl system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -2,30 +2,65 @@
 //!
 //! The Validator Set Pallet provides functionality to add/remove validators through extrinsics, in a Substrate-based
 //! PoA network.
-//! 
-//! The pallet is based on the Substrate session pallet and implements related traits for session 
+//!
+//! The pallet is based on the Substrate session pallet and implements related traits for session
 //! management when validators are added or removed.
+//!
+//! Validators are added and removed by `AddRemoveOrigin`. In addition, the pallet implements
+//! `OnOffenceHandler`, so when it is used as the offence handler of `pallet_offences` any
+//! validator reported for an offence (e.g. by `pallet_im_online` for being offline) is removed
+//! from the set at the next session. The set never shrinks below `MinAuthorities`.
+//!
+//! Besides validator set changes, the session is rotated every `SessionPeriod` blocks so that
+//! `pallet_im_online` gets to report unresponsive validators. Offences committed in a session cut
+//! short by a validator set change are ignored, validators had no chance to send a heartbeat.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 use sp_std::prelude::*;
 use frame_support::{
-    StorageValue,
+	StorageValue,
 	decl_event, decl_storage, decl_module, decl_error,
-	dispatch
+	dispatch, ensure,
+	traits::{Get, EnsureOrigin},
+	weights::Weight,
+};
+use frame_system::{self as system};
+use sp_runtime::{Perbill, traits::{Convert, Saturating, Zero}};
+use sp_staking::{
+	SessionIndex,
+	offence::{OffenceDetails, OnOffenceHandler},
 };
-use frame_system::{self as system, ensure_root};
-use sp_runtime::traits::Convert;
-
 
 pub trait Trait: system::Trait + pallet_session::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// Origin allowed to add or remove validators.
+	type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The minimum number of validators that have to stay in the set. Neither `remove_validator`
+	/// nor an offence report can shrink the set below this number.
+	type MinAuthorities: Get<u32>;
+
+	/// Number of blocks after which the session is rotated even if the validator set did not
+	/// change. Zero disables periodic rotation.
+	type SessionPeriod: Get<Self::BlockNumber>;
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as ValidatorSet {
 		pub Validators get(fn validators) config(): Option<Vec<T::AccountId>>;
 		Flag get(fn flag): bool;
+		/// Block number at which the current session started.
+		SessionStart get(fn session_start): T::BlockNumber;
+		/// Validators reported for an offence, removed from the set at the next session.
+		pub OfflineValidators get(fn offline_validators): Vec<T::AccountId>;
 	}
 }
 
@@ -39,27 +74,47 @@ decl_event!(
 
 		// Validator removed.
 		ValidatorRemoved(AccountId),
+
+		// Validator reported for an offence, it will be removed at the next session.
+		ValidatorReported(AccountId),
 	}
 );
 
 decl_error! {
 	/// Errors for the module.
 	pub enum Error for Module<T: Trait> {
+		/// The validator set is not initialized.
 		NoValidators,
+		/// The account is already a validator.
+		Duplicate,
+		/// The account is not a validator.
+		NotAValidator,
+		/// Removing the validator would leave fewer than `MinAuthorities` validators.
+		TooFewValidators,
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The minimum number of validators that have to stay in the set.
+		const MinAuthorities: u32 = T::MinAuthorities::get();
+
+		/// Number of blocks after which the session is rotated even if the validator set did
+		/// not change.
+		const SessionPeriod: T::BlockNumber = T::SessionPeriod::get();
+
 		fn deposit_event() = default;
 
-		/// Add a new validator using root/sudo privileges.
+		/// Add a new validator, dispatched by `AddRemoveOrigin`.
 		///
 		/// New validator's session keys should be set in session module before calling this.
-		#[weight = 0]
+		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(2, 2)]
 		pub fn add_validator(origin, validator_id: T::AccountId) -> dispatch::DispatchResult {
-			ensure_root(origin)?;
+			T::AddRemoveOrigin::ensure_origin(origin)?;
 			let mut validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
+			ensure!(!validators.contains(&validator_id), Error::<T>::Duplicate);
 			validators.push(validator_id.clone());
 			<Validators<T>>::put(validators);
 			// Calling rotate_session to queue the new session keys.
@@ -71,19 +126,18 @@ decl_module! {
 			Ok(())
 		}
 
-		/// Remove a validator using root/sudo privileges.
-		#[weight = 0]
+		/// Remove a validator, dispatched by `AddRemoveOrigin`.
+		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(2, 2)]
 		pub fn remove_validator(origin, validator_id: T::AccountId) -> dispatch::DispatchResult {
-			ensure_root(origin)?;
+			T::AddRemoveOrigin::ensure_origin(origin)?;
 			let mut validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
-			// Assuming that this will be a PoA network for enterprise use-cases, 
-			// the validator count may not be too big; the for loop shouldn't be too heavy.
-			// In case the validator count is large, we need to find another way.
-			for (i, v) in validators.clone().into_iter().enumerate() {
-				if v == validator_id {
-					validators.swap_remove(i);
-				}
-			}
+			let index = validators.iter().position(|v| *v == validator_id)
+				.ok_or(Error::<T>::NotAValidator)?;
+			ensure!(
+				validators.len() as u32 > T::MinAuthorities::get(),
+				Error::<T>::TooFewValidators,
+			);
+			validators.swap_remove(index);
 			<Validators<T>>::put(validators);
 			// Calling rotate_session to queue the new session keys.
 			<pallet_session::Module<T>>::rotate_session();
@@ -96,36 +150,145 @@ decl_module! {
 	}
 }
 
+impl<T: Trait> Module<T> {
+	/// Queue a validator reported for an offence for removal at the next session.
+	/// Returns whether the validator was newly queued.
+	fn mark_for_removal(validator_id: T::AccountId) -> bool {
+		let is_validator = Self::validators().map_or(false, |v| v.contains(&validator_id));
+		let mut offline = Self::offline_validators();
+		if !is_validator || offline.contains(&validator_id) {
+			return false;
+		}
+
+		offline.push(validator_id.clone());
+		<OfflineValidators<T>>::put(offline);
+		Self::deposit_event(RawEvent::ValidatorReported(validator_id));
+		true
+	}
+
+	/// Remove the validators queued in `OfflineValidators` from the set, keeping at least
+	/// `MinAuthorities` of them. Returns whether the set changed.
+	fn remove_offline_validators() -> bool {
+		let offline = <OfflineValidators<T>>::take();
+		let mut validators = match Self::validators() {
+			Some(validators) if !offline.is_empty() => validators,
+			_ => return false,
+		};
+
+		let mut removed = false;
+		for validator_id in offline {
+			if validators.len() as u32 <= T::MinAuthorities::get() {
+				break;
+			}
+			if let Some(index) = validators.iter().position(|v| *v == validator_id) {
+				validators.swap_remove(index);
+				removed = true;
+				Self::deposit_event(RawEvent::ValidatorRemoved(validator_id));
+			}
+		}
+
+		if removed {
+			<Validators<T>>::put(validators);
+		}
+		removed
+	}
+}
+
 /// Indicates to the session module if the session should be rotated.
-/// We set this flag to true when we add/remove a validator.
+/// We set this flag to true when we add/remove a validator. The session is also rotated every
+/// `SessionPeriod` blocks.
 impl<T: Trait> pallet_session::ShouldEndSession<T::BlockNumber> for Module<T> {
-	fn should_end_session(_now: T::BlockNumber) -> bool {
-		Self::flag()
+	fn should_end_session(now: T::BlockNumber) -> bool {
+		let period = T::SessionPeriod::get();
+		Self::flag() || (!period.is_zero() && now.saturating_sub(Self::session_start()) >= period)
 	}
 }
 
 /// Provides the new set of validators to the session module when session is being rotated.
 impl<T: Trait> pallet_session::SessionManager<T::AccountId> for Module<T> {
 	fn new_session(_new_index: u32) -> Option<Vec<T::AccountId>> {
-		// Flag is set to false so that the session doesn't keep rotating.
-		Flag::put(false);
+		// The set computed here is only queued, so if offline validators were removed
+		// the session is rotated once more for the new set to take effect. Otherwise the
+		// flag is set to false so that the session doesn't keep rotating.
+		Flag::put(Self::remove_offline_validators());
 
 		Self::validators()
 	}
 
 	fn end_session(_end_index: u32) {}
 
-	fn start_session(_start_index: u32) {}
+	fn start_session(_start_index: u32) {
+		<SessionStart<T>>::put(<system::Module<T>>::block_number());
+	}
+}
+
+/// Allows the pallet to be wrapped in `pallet_session::historical::NoteHistoricalRoot`, which
+/// `pallet_im_online` and `pallet_offences` rely on. The full identification of a validator is
+/// its account id.
+impl<T: Trait> pallet_session::historical::SessionManager<T::AccountId, T::AccountId> for Module<T> {
+	fn new_session(new_index: u32) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+		<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+	}
+
+	fn end_session(end_index: u32) {
+		<Self as pallet_session::SessionManager<_>>::end_session(end_index)
+	}
+
+	fn start_session(start_index: u32) {
+		<Self as pallet_session::SessionManager<_>>::start_session(start_index)
+	}
+}
+
+/// Removes reported offenders from the validator set at the next session. The slash fraction
+/// is ignored, any offence is enough for a validator to be removed.
+impl<T: Trait, FullIdentification> OnOffenceHandler<T::AccountId, (T::AccountId, FullIdentification), Weight>
+	for Module<T>
+{
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, FullIdentification)>],
+		_slash_fraction: &[Perbill],
+		session: SessionIndex,
+	) -> Result<Weight, ()> {
+		let session_length = <system::Module<T>>::block_number().saturating_sub(Self::session_start());
+		if session == <pallet_session::Module<T>>::current_index()
+			&& session_length < T::SessionPeriod::get()
+		{
+			// The session was cut short by a validator set change.
+			return Ok(T::DbWeight::get().reads(3));
+		}
+
+		let mut reported = false;
+		for details in offenders {
+			reported |= Self::mark_for_removal(details.offender.0.clone());
+		}
+
+		if reported {
+			// Rotate the session so that the offenders are removed.
+			Flag::put(true);
+		}
+
+		Ok(T::DbWeight::get().reads_writes(2, 2).saturating_mul(offenders.len() as Weight))
+	}
+
+	fn can_report() -> bool {
+		true
+	}
 }
 
 impl<T: Trait> frame_support::traits::EstimateNextSessionRotation<T::BlockNumber> for Module<T> {
 	fn estimate_next_session_rotation(_now: T::BlockNumber) -> Option<T::BlockNumber> {
-		None
+		let period = T::SessionPeriod::get();
+		if period.is_zero() {
+			None
+		} else {
+			Some(Self::session_start().saturating_add(period))
+		}
 	}
 
 	// The validity of this weight depends on the implementation of `estimate_next_session_rotation`
 	fn weight(_now: T::BlockNumber) -> u64 {
-		0
+		T::DbWeight::get().reads(1)
 	}
 }
 
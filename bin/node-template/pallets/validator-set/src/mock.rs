@@ -0,0 +1,104 @@
+// Creating mock runtime here
+
+use crate::{Module, Trait, GenesisConfig};
+use sp_core::H256;
+use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, ConvertInto}, testing::{Header, UintAuthorityId}, Perbill,
+	BuildStorage,
+};
+use frame_system::{self as system, EnsureRoot};
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl pallet_session::Trait for Test {
+	type ShouldEndSession = ValidatorSet;
+	type SessionManager = ValidatorSet;
+	type SessionHandler = pallet_session::TestSessionHandler;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type Keys = UintAuthorityId;
+	type Event = ();
+	type DisabledValidatorsThreshold = ();
+	type NextSessionRotation = ValidatorSet;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinAuthorities: u32 = 2;
+	pub const SessionPeriod: u64 = 10;
+}
+
+impl Trait for Test {
+	type Event = ();
+	type AddRemoveOrigin = EnsureRoot<u64>;
+	type MinAuthorities = MinAuthorities;
+	type SessionPeriod = SessionPeriod;
+}
+
+pub type System = system::Module<Test>;
+pub type Session = pallet_session::Module<Test>;
+pub type ValidatorSet = Module<Test>;
+
+// Build genesis storage with validators 1, 2 and 3. Account 4 has session keys
+// but is not a validator.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisConfig::<Test> {
+		validators: vec![1, 2, 3],
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: (1..=4).map(|i| (i, i, UintAuthorityId(i))).collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
+
+pub fn run_to_block(n: u64) {
+	use frame_support::traits::OnInitialize;
+
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Session::on_initialize(System::block_number());
+	}
+}
@@ -0,0 +1,130 @@
+use crate::{Error, mock::*};
+use frame_support::{assert_ok, assert_noop, weights::Weight};
+use sp_runtime::{DispatchError, Perbill};
+use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+
+fn report(offenders: &[u64]) {
+	let offenders: Vec<_> = offenders.iter()
+		.map(|v| OffenceDetails { offender: (*v, *v), reporters: vec![] })
+		.collect();
+	let slash_fraction = vec![Perbill::zero(); offenders.len()];
+	assert_ok!(
+		<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+			&offenders,
+			&slash_fraction,
+			Session::current_index(),
+		)
+	);
+}
+
+#[test]
+fn add_validator_updates_the_session_validators() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_eq!(ValidatorSet::validators(), Some(vec![1, 2, 3, 4]));
+
+		run_to_block(1);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+		assert!(!ValidatorSet::flag());
+	});
+}
+
+#[test]
+fn add_remove_validator_requires_the_configured_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(ValidatorSet::remove_validator(Origin::signed(1), 3), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn add_validator_rejects_duplicates() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::root(), 1), Error::<Test>::Duplicate);
+	});
+}
+
+#[test]
+fn remove_validator_keeps_min_authorities() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::remove_validator(Origin::root(), 5), Error::<Test>::NotAValidator);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_eq!(ValidatorSet::validators(), Some(vec![1, 2]));
+
+		assert_noop!(ValidatorSet::remove_validator(Origin::root(), 1), Error::<Test>::TooFewValidators);
+
+		run_to_block(1);
+		assert_eq!(Session::validators(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn session_is_rotated_every_session_period() {
+	new_test_ext().execute_with(|| {
+		run_to_block(9);
+		assert_eq!(Session::current_index(), 0);
+
+		run_to_block(10);
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(ValidatorSet::session_start(), 10);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn reported_validators_are_removed_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		// A full session has elapsed.
+		System::set_block_number(10);
+		report(&[1]);
+		assert_eq!(ValidatorSet::offline_validators(), vec![1]);
+		assert!(ValidatorSet::flag());
+
+		// The new set is queued in the first rotation and enacted in the second.
+		run_to_block(11);
+		assert_eq!(ValidatorSet::validators(), Some(vec![3, 2]));
+		assert!(ValidatorSet::offline_validators().is_empty());
+		assert!(ValidatorSet::flag());
+
+		run_to_block(12);
+		assert_eq!(Session::validators(), vec![3, 2]);
+		assert!(!ValidatorSet::flag());
+	});
+}
+
+#[test]
+fn offences_in_a_session_cut_short_are_ignored() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		report(&[1]);
+		assert!(ValidatorSet::offline_validators().is_empty());
+		assert!(!ValidatorSet::flag());
+	});
+}
+
+#[test]
+fn reports_of_non_validators_are_ignored() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		report(&[4]);
+		assert!(ValidatorSet::offline_validators().is_empty());
+		assert!(!ValidatorSet::flag());
+	});
+}
+
+#[test]
+fn reported_validators_are_not_removed_below_min_authorities() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		report(&[1, 2, 3]);
+		assert_eq!(ValidatorSet::offline_validators(), vec![1, 2, 3]);
+
+		run_to_block(12);
+		assert_eq!(ValidatorSet::validators(), Some(vec![3, 2]));
+		assert_eq!(Session::validators(), vec![3, 2]);
+		assert!(ValidatorSet::offline_validators().is_empty());
+	});
+}
@@ -10,7 +10,7 @@ use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata, Encode};
 use sp_runtime::{
 	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority},
 };
 use sp_runtime::traits::{
 	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount,
@@ -23,6 +23,7 @@ use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthority
 use pallet_grandpa::fg_primitives;
 use sp_version::RuntimeVersion;
 use pallet_session;
+use pallet_session::historical as pallet_session_historical;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use pallet_contracts_rpc_runtime_api::ContractExecResult;
@@ -32,12 +33,13 @@ use pallet_contracts_rpc_runtime_api::ContractExecResult;
 pub use sp_runtime::BuildStorage;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
+pub use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue, debug,
 	traits::{KeyOwnerProofSystem, Randomness},
 	weights::{
-		Weight, IdentityFee, RuntimeDbWeight,
+		Weight, ProofSize, IdentityFee, RuntimeDbWeight,
 		// constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		constants::{WEIGHT_PER_SECOND, WEIGHT_PER_MILLIS, WEIGHT_PER_MICROS},
 	},
@@ -105,6 +107,7 @@ pub mod opaque {
 		pub struct SessionKeys {
 			pub aura: Aura,
 			pub grandpa: Grandpa,
+			pub im_online: ImOnline,
 		}
 	}
 }
@@ -151,6 +154,7 @@ parameter_types! {
 	pub MaximumExtrinsicWeight: Weight = AvailableBlockRatio::get()
 		.saturating_sub(Perbill::from_percent(10)) * MaximumBlockWeight::get();
 	pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
+	pub const MaximumBlockProofSize: ProofSize = 5 * 1024 * 1024;
 	pub const Version: RuntimeVersion = VERSION;
 }
 
@@ -208,6 +212,9 @@ impl frame_system::Trait for Runtime {
 	/// idependent of the logic of that extrinsics. (Roughly max block weight - average on
 	/// initialize cost).
 	type MaximumExtrinsicWeight = MaximumExtrinsicWeight;
+	type MaximumBlockProofSize = MaximumBlockProofSize;
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	/// Maximum size of all encoded transactions (in bytes) that are allowed in one block.
 	type MaximumBlockLength = MaximumBlockLength;
 	/// Portion of the block weight that is available to all normal transactions.
@@ -290,6 +297,9 @@ impl pallet_transaction_payment::Trait for Runtime {
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
+	type SponsorSignature = Signature;
+	type SponsorPublic = <Signature as Verify>::Signer;
+	type WeightInfo = ();
 }
 
 impl pallet_sudo::Trait for Runtime {
@@ -298,7 +308,7 @@ impl pallet_sudo::Trait for Runtime {
 }
 
 /// Configure the pallet template in pallets/template.
-impl template::Trait for Runtime {
+impl template::Config for Runtime {
 	type Event = Event;
 }
 
@@ -388,14 +398,22 @@ impl pallet_node_authorization::Trait for Runtime {
 // 	pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(17);
 // }
 
+parameter_types! {
+	pub const MinAuthorities: u32 = 2;
+	pub const SessionPeriod: BlockNumber = HOURS;
+}
+
 impl validatorset::Trait for Runtime {
 	type Event = Event;
+	type AddRemoveOrigin = EnsureRoot<AccountId>;
+	type MinAuthorities = MinAuthorities;
+	type SessionPeriod = SessionPeriod;
 }
 
 impl pallet_session::Trait for Runtime {
     type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
     type ShouldEndSession = ValidatorSet;
-    type SessionManager = ValidatorSet;
+    type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
     type Event = Event;
     type Keys = opaque::SessionKeys;
     type NextSessionRotation = ValidatorSet;
@@ -405,6 +423,39 @@ impl pallet_session::Trait for Runtime {
     type WeightInfo = ();
 }
 
+impl pallet_session::historical::Trait for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = validatorset::ValidatorOf<Self>;
+}
+
+parameter_types! {
+	pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const MaxHeartbeats: u32 = 2;
+}
+
+impl pallet_im_online::Trait for Runtime {
+	type AuthorityId = ImOnlineId;
+	type Event = Event;
+	type SessionDuration = SessionPeriod;
+	type ReportUnresponsiveness = Offences;
+	type UnsignedPriority = ImOnlineUnsignedPriority;
+	type MaxHeartbeats = MaxHeartbeats;
+	type UnresponsivenessPolicy = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
+}
+
+// Offenders are removed from the validator set at the next session.
+impl pallet_offences::Trait for Runtime {
+	type Event = Event;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
+	type WeightSoftLimit = OffencesWeightSoftLimit;
+}
+
 /// Payload data to be signed when making signed transaction from off-chain workers,
 ///   inside `create_transaction` function.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -485,6 +536,9 @@ construct_runtime!(
 		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
 		Session: pallet_session::{Module, Call, Storage, Event, Config<T>},
 		ValidatorSet: validatorset::{Module, Call, Storage, Event<T>, Config<T>},
+		Historical: pallet_session_historical::{Module},
+		ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
+		Offences: pallet_offences::{Module, Call, Storage, Event},
 		Aura: pallet_aura::{Module, Config<T>, Inherent},
 		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
@@ -599,6 +653,14 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_node_authorization::NodeAuthorizationApi<Block> for Runtime {
+		fn authorized_nodes(
+			node: sp_node_authorization::OpaquePeerId,
+		) -> Vec<sp_node_authorization::OpaquePeerId> {
+			NodeAuthorization::get_authorized_nodes(&node)
+		}
+	}
+
 	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
 		fn slot_duration() -> u64 {
 			Aura::slot_duration()
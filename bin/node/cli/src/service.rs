@@ -638,20 +638,22 @@ mod tests {
 				let check_era = frame_system::CheckEra::from(Era::Immortal);
 				let check_nonce = frame_system::CheckNonce::from(index);
 				let check_weight = frame_system::CheckWeight::new();
-				let payment = pallet_transaction_payment::ChargeTransactionPayment::from(0);
+				let check_feeless_rate_limit = frame_system::CheckFeelessRateLimit::new();
+				let payment = pallet_transaction_payment::ChargeSponsoredTransactionPayment::from(0);
 				let extra = (
 					check_spec_version,
 					check_tx_version,
 					check_genesis,
 					check_era,
+					payment,
+					check_feeless_rate_limit,
 					check_nonce,
 					check_weight,
-					payment,
 				);
 				let raw_payload = SignedPayload::from_raw(
 					function,
 					extra,
-					(spec_version, transaction_version, genesis_hash, genesis_hash, (), (), ())
+					(spec_version, transaction_version, genesis_hash, genesis_hash, (), (), (), ())
 				);
 				let signature = raw_payload.using_encoded(|payload|	{
 					signer.sign(payload)
@@ -17,10 +17,11 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use node_primitives::Balance;
+use codec::Decode;
+use node_primitives::{Balance, BlockNumber};
 use sp_runtime::traits::Convert;
 use frame_support::traits::{OnUnbalanced, Currency};
-use crate::{Balances, Authorship, NegativeImbalance};
+use crate::{Balances, Authorship, NegativeImbalance, Call};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -45,6 +46,67 @@ impl Convert<u128, Balance> for CurrencyToVoteHandler {
 	fn convert(x: u128) -> Balance { x * Self::factor() }
 }
 
+/// Values calls by the amount of balance they transfer out of, or lock in, the dispatching
+/// account. This is used to enforce the spending limits of scoped proxies.
+///
+/// Calls dispatching other calls are valued at the total of the calls they dispatch, and calls
+/// whose value is unknown, e.g. multisig approvals of a call hash, at the maximum balance.
+pub struct ProxyCallValue;
+
+impl ProxyCallValue {
+	fn total<'a>(calls: impl IntoIterator<Item=&'a Call>) -> Balance {
+		calls.into_iter().fold(0, |total, c| total.saturating_add(Self::value(c)))
+	}
+
+	fn opaque(call: &[u8]) -> Balance {
+		Call::decode(&mut &call[..]).map_or(Balance::max_value(), |c| Self::value(&c))
+	}
+
+	fn scheduled(maybe_periodic: &Option<(BlockNumber, u32)>, call: &Call) -> Balance {
+		let count = maybe_periodic.map_or(1, |(_, count)| count.max(1));
+		Self::value(call).saturating_mul(count.into())
+	}
+}
+
+impl pallet_proxy::CallValue<Call, Balance> for ProxyCallValue {
+	fn value(call: &Call) -> Balance {
+		match call {
+			Call::Balances(pallet_balances::Call::transfer(_, value)) |
+			Call::Balances(pallet_balances::Call::transfer_keep_alive(_, value)) |
+			Call::Balances(pallet_balances::Call::force_transfer(_, _, value)) => *value,
+			Call::Vesting(pallet_vesting::Call::vested_transfer(_, schedule)) => schedule.locked,
+			Call::Contracts(pallet_contracts::Call::call(_, value, _, _)) => *value,
+			Call::Contracts(pallet_contracts::Call::instantiate(endowment, _, _, _)) => *endowment,
+			Call::Staking(pallet_staking::Call::bond(_, value, _)) |
+			Call::Staking(pallet_staking::Call::bond_extra(value)) => *value,
+			Call::Democracy(pallet_democracy::Call::propose(_, value)) => *value,
+			Call::Utility(pallet_utility::Call::batch(calls)) => Self::total(calls),
+			Call::Utility(pallet_utility::Call::as_derivative(_, call)) |
+			Call::Proxy(pallet_proxy::Call::proxy(_, _, call)) |
+			Call::Proxy(pallet_proxy::Call::proxy_announced(_, _, _, call)) |
+			Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(_, call)) |
+			Call::Recovery(pallet_recovery::Call::as_recovered(_, call)) |
+			Call::Sudo(pallet_sudo::Call::sudo(call)) |
+			Call::Sudo(pallet_sudo::Call::sudo_unchecked_weight(call, _)) |
+			Call::Sudo(pallet_sudo::Call::sudo_as(_, call)) => Self::value(call),
+			Call::Multisig(pallet_multisig::Call::as_multi(_, _, _, call, _, _)) |
+			Call::Multisig(pallet_multisig::Call::as_weighted_multi(_, _, call, _, _)) =>
+				Self::opaque(call),
+			Call::Multisig(pallet_multisig::Call::approve_as_multi(..)) |
+			Call::Multisig(pallet_multisig::Call::approve_as_weighted_multi(..)) =>
+				Balance::max_value(),
+			Call::Scheduler(pallet_scheduler::Call::schedule(_, maybe_periodic, _, call)) |
+			Call::Scheduler(pallet_scheduler::Call::schedule_named(_, _, maybe_periodic, _, call)) |
+			Call::Scheduler(pallet_scheduler::Call::schedule_after(_, maybe_periodic, _, call)) |
+			Call::Scheduler(
+				pallet_scheduler::Call::schedule_named_after(_, _, maybe_periodic, _, call)
+			) =>
+				Self::scheduled(maybe_periodic, call),
+			_ => 0,
+		}
+	}
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use super::*;
@@ -364,3 +426,43 @@ mod multiplier_tests {
 			});
 	}
 }
+
+#[cfg(test)]
+mod proxy_call_value_tests {
+	use super::*;
+	use codec::Encode;
+	use node_primitives::AccountId;
+	use pallet_proxy::CallValue;
+
+	fn transfer(value: Balance) -> Call {
+		Call::Balances(pallet_balances::Call::transfer(AccountId::default().into(), value))
+	}
+
+	#[test]
+	fn wrapped_calls_are_valued() {
+		let batch = Call::Utility(pallet_utility::Call::batch(vec![transfer(1), transfer(2)]));
+		let proxy = Call::Proxy(
+			pallet_proxy::Call::proxy(AccountId::default(), None, Box::new(batch))
+		);
+		assert_eq!(ProxyCallValue::value(&proxy), 3);
+
+		let derivative = Call::Utility(
+			pallet_utility::Call::as_derivative(0, Box::new(transfer(5)))
+		);
+		assert_eq!(ProxyCallValue::value(&derivative), 5);
+
+		let bond_extra = Call::Staking(pallet_staking::Call::bond_extra(7));
+		assert_eq!(ProxyCallValue::value(&bond_extra), 7);
+
+		let as_multi = |call: Vec<u8>| Call::Multisig(
+			pallet_multisig::Call::as_multi(2, vec![], None, call, false, 0)
+		);
+		assert_eq!(ProxyCallValue::value(&as_multi(transfer(4).encode())), 4);
+		assert_eq!(ProxyCallValue::value(&as_multi(vec![0xff])), Balance::max_value());
+
+		let approve = Call::Multisig(
+			pallet_multisig::Call::approve_as_multi(2, vec![], None, [0; 32], 0)
+		);
+		assert_eq!(ProxyCallValue::value(&approve), Balance::max_value());
+	}
+}
@@ -27,7 +27,7 @@ use sp_std::prelude::*;
 use frame_support::{
 	construct_runtime, parameter_types, debug, RuntimeDebug,
 	weights::{
-		Weight, IdentityFee,
+		Weight, ProofSize, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
 	traits::{Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, LockIdentifier},
@@ -35,6 +35,7 @@ use frame_support::{
 use frame_system::{EnsureRoot, EnsureOneOf};
 use frame_support::traits::InstanceFilter;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
@@ -44,7 +45,7 @@ pub use node_primitives::{AccountId, Signature};
 use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
 use sp_api::impl_runtime_apis;
 use sp_runtime::{
-	Permill, Perbill, Perquintill, Percent, ApplyExtrinsicResult,
+	Permill, Perbill, Perquintill, Percent, ApplyExtrinsicResult, DispatchResult,
 	impl_opaque_keys, generic, create_runtime_str, ModuleId, FixedPointNumber,
 };
 use sp_runtime::curve::PiecewiseLinear;
@@ -60,7 +61,7 @@ use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthority
 use pallet_grandpa::fg_primitives;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
-use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+use pallet_transaction_payment_rpc_runtime_api::{RuntimeDispatchInfo, SponsoredDispatchInfo};
 pub use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 use pallet_contracts_rpc_runtime_api::ContractExecResult;
 use pallet_session::{historical as pallet_session_historical};
@@ -78,7 +79,7 @@ pub use pallet_staking::StakerStatus;
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::{CurrencyToVoteHandler, Author};
+use impls::{CurrencyToVoteHandler, Author, ProxyCallValue};
 
 /// Constant values used within the runtime.
 pub mod constants;
@@ -109,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 259,
+	spec_version: 287,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 6,
 };
 
 /// Native version.
@@ -153,6 +154,10 @@ parameter_types! {
 		AvailableBlockRatio::get().saturating_sub(AVERAGE_ON_INITIALIZE_WEIGHT)
 		* MaximumBlockWeight::get();
 	pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
+	pub const MaximumBlockProofSize: ProofSize = 5 * 1024 * 1024;
+	/// Allow a few feeless transactions per account and hour.
+	pub const MaxFeelessTransactions: u32 = 4;
+	pub const FeelessRateLimitPeriod: BlockNumber = HOURS;
 	pub const Version: RuntimeVersion = VERSION;
 }
 
@@ -176,6 +181,9 @@ impl frame_system::Trait for Runtime {
 	type BlockExecutionWeight = BlockExecutionWeight;
 	type ExtrinsicBaseWeight = ExtrinsicBaseWeight;
 	type MaximumExtrinsicWeight = MaximumExtrinsicWeight;
+	type MaximumBlockProofSize = MaximumBlockProofSize;
+	type MaxFeelessTransactions = MaxFeelessTransactions;
+	type FeelessRateLimitPeriod = FeelessRateLimitPeriod;
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = Version;
@@ -222,7 +230,7 @@ parameter_types! {
 }
 
 /// The type used to represent the kinds of proxying allowed.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum ProxyType {
 	Any,
 	NonTransfer,
@@ -276,6 +284,7 @@ impl pallet_proxy::Trait for Runtime {
 	type CallHasher = BlakeTwo256;
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+	type CallValue = ProxyCallValue;
 }
 
 parameter_types! {
@@ -365,6 +374,9 @@ impl pallet_transaction_payment::Trait for Runtime {
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate =
 		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+	type SponsorSignature = Signature;
+	type SponsorPublic = <Signature as traits::Verify>::Signer;
+	type WeightInfo = weights::pallet_transaction_payment::WeightInfo;
 }
 
 parameter_types! {
@@ -441,6 +453,7 @@ parameter_types! {
 	pub const MaxIterations: u32 = 10;
 	// 0.05%. The higher the value, the more strict solution acceptance becomes.
 	pub MinSolutionScoreBump: Perbill = Perbill::from_rational_approximation(5u32, 10_000);
+	pub const MaxUnsignedSolutions: u32 = 16;
 }
 
 impl pallet_staking::Trait for Runtime {
@@ -467,6 +480,7 @@ impl pallet_staking::Trait for Runtime {
 	type Call = Call;
 	type MaxIterations = MaxIterations;
 	type MinSolutionScoreBump = MinSolutionScoreBump;
+	type MaxUnsignedSolutions = MaxUnsignedSolutions;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type UnsignedPriority = StakingUnsignedPriority;
 	type WeightInfo = weights::pallet_staking::WeightInfo;
@@ -621,6 +635,9 @@ parameter_types! {
 	pub const MaximumReasonLength: u32 = 16384;
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: Balance = 5 * DOLLARS;
+	pub const ChildBountyValueMinimum: Balance = 1 * DOLLARS;
+	pub const MaxActiveChildBountyCount: u32 = 100;
+	pub const MaxAssetPaymentFailures: u32 = 3;
 }
 
 impl pallet_treasury::Trait for Runtime {
@@ -653,10 +670,20 @@ impl pallet_treasury::Trait for Runtime {
 	type BountyCuratorDeposit = BountyCuratorDeposit;
 	type BountyValueMinimum = BountyValueMinimum;
 	type MaximumReasonLength = MaximumReasonLength;
+	type ChildBountyValueMinimum = ChildBountyValueMinimum;
+	type MaxActiveChildBountyCount = MaxActiveChildBountyCount;
 	type BurnDestination = ();
+	type Assets = Assets;
+	type MaxAssetPaymentFailures = MaxAssetPaymentFailures;
 	type WeightInfo = weights::pallet_treasury::WeightInfo;
 }
 
+impl pallet_assets::Trait for Runtime {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+}
+
 parameter_types! {
 	pub const TombstoneDeposit: Balance = 16 * MILLICENTS;
 	pub const RentByteFee: Balance = 4 * MILLICENTS;
@@ -664,9 +691,22 @@ parameter_types! {
 	pub const SurchargeReward: Balance = 150 * MILLICENTS;
 }
 
+/// The randomness of the previous BABE epoch, with the current block number mixed into the
+/// subject so that `seal_random` returns a different value in each block.
+///
+/// The value is still known from the start of the epoch, so contracts must not rely on it being
+/// unpredictable to anyone before the block.
+pub struct ContractsRandomness;
+impl Randomness<Hash> for ContractsRandomness {
+	fn random(subject: &[u8]) -> Hash {
+		let subject = (subject, System::block_number()).encode();
+		pallet_babe::RandomnessFromOneEpochAgo::<Runtime>::random(&subject)
+	}
+}
+
 impl pallet_contracts::Trait for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = ContractsRandomness;
 	type Currency = Balances;
 	type Event = Event;
 	type DetermineContractAddress = pallet_contracts::SimpleAddressDeterminer<Runtime>;
@@ -691,6 +731,7 @@ impl pallet_sudo::Trait for Runtime {
 parameter_types! {
 	pub const SessionDuration: BlockNumber = EPOCH_DURATION_IN_SLOTS as _;
 	pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const MaxHeartbeats: u32 = 2;
 	/// We prioritize im-online heartbeats over election solution submission.
 	pub const StakingUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 }
@@ -722,9 +763,10 @@ impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for R
 			frame_system::CheckTxVersion::<Runtime>::new(),
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::<Runtime>::from(era),
+			pallet_transaction_payment::ChargeSponsoredTransactionPayment::<Runtime>::from(tip),
+			frame_system::CheckFeelessRateLimit::<Runtime>::new(),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -759,6 +801,8 @@ impl pallet_im_online::Trait for Runtime {
 	type SessionDuration = SessionDuration;
 	type ReportUnresponsiveness = Offences;
 	type UnsignedPriority = ImOnlineUnsignedPriority;
+	type MaxHeartbeats = MaxHeartbeats;
+	type UnresponsivenessPolicy = ();
 	type WeightInfo = weights::pallet_im_online::WeightInfo;
 }
 
@@ -835,6 +879,19 @@ parameter_types! {
 	pub const FriendDepositFactor: Balance = 50 * CENTS;
 	pub const MaxFriends: u16 = 9;
 	pub const RecoveryDeposit: Balance = 5 * DOLLARS;
+	pub const MinRecoveryDelayPeriod: BlockNumber = 1 * DAYS;
+}
+
+/// Makes the rescuer of a recovered account an `Any` proxy of it in `pallet_proxy`.
+pub struct RecoveredProxy;
+impl pallet_recovery::RecoveredAccountProxy<AccountId> for RecoveredProxy {
+	fn add_proxy(recovered: &AccountId, rescuer: &AccountId) -> DispatchResult {
+		Proxy::add_proxy_delegate(recovered, rescuer.clone(), ProxyType::Any, 0)
+	}
+
+	fn remove_proxy(recovered: &AccountId, rescuer: &AccountId) -> DispatchResult {
+		Proxy::remove_proxy_delegate(recovered, rescuer.clone(), ProxyType::Any, 0)
+	}
 }
 
 impl pallet_recovery::Trait for Runtime {
@@ -845,6 +902,9 @@ impl pallet_recovery::Trait for Runtime {
 	type FriendDepositFactor = FriendDepositFactor;
 	type MaxFriends = MaxFriends;
 	type RecoveryDeposit = RecoveryDeposit;
+	type MinDelayPeriod = MinRecoveryDelayPeriod;
+	type Notify = pallet_recovery::OffchainNotify;
+	type RecoveredProxy = RecoveredProxy;
 }
 
 parameter_types! {
@@ -862,7 +922,7 @@ impl pallet_society::Trait for Runtime {
 	type Event = Event;
 	type ModuleId = SocietyModuleId;
 	type Currency = Balances;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type CandidateDeposit = CandidateDeposit;
 	type WrongSideDeduction = WrongSideDeduction;
 	type MaxStrikes = MaxStrikes;
@@ -900,7 +960,7 @@ construct_runtime!(
 		Authorship: pallet_authorship::{Module, Call, Storage, Inherent},
 		Indices: pallet_indices::{Module, Call, Storage, Config<T>, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-		TransactionPayment: pallet_transaction_payment::{Module, Storage},
+		TransactionPayment: pallet_transaction_payment::{Module, Call, Storage},
 		Staking: pallet_staking::{Module, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
 		Session: pallet_session::{Module, Call, Storage, Event, Config<T>},
 		Democracy: pallet_democracy::{Module, Call, Storage, Config, Event<T>},
@@ -917,7 +977,6 @@ construct_runtime!(
 		AuthorityDiscovery: pallet_authority_discovery::{Module, Call, Config},
 		Offences: pallet_offences::{Module, Call, Storage, Event},
 		Historical: pallet_session_historical::{Module},
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
 		Identity: pallet_identity::{Module, Call, Storage, Event<T>},
 		Society: pallet_society::{Module, Call, Storage, Event<T>, Config<T>},
 		Recovery: pallet_recovery::{Module, Call, Storage, Event<T>},
@@ -925,6 +984,7 @@ construct_runtime!(
 		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
 		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
 		Multisig: pallet_multisig::{Module, Call, Storage, Event<T>},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -943,14 +1003,19 @@ pub type BlockId = generic::BlockId<Block>;
 /// When you change this, you **MUST** modify [`sign`] in `bin/node/testing/src/keyring.rs`!
 ///
 /// [`sign`]: <../../testing/src/keyring.rs.html>
+///
+/// The fee payment comes before `CheckNonce`, as a sponsor approval is bound to the nonce of the
+/// transactor before it is incremented. `CheckFeelessRateLimit` comes before `CheckNonce` too, as
+/// it only admits feeless transactions of accounts which existed before the nonce was bumped.
 pub type SignedExtra = (
 	frame_system::CheckSpecVersion<Runtime>,
 	frame_system::CheckTxVersion<Runtime>,
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
+	pallet_transaction_payment::ChargeSponsoredTransactionPayment<Runtime>,
+	frame_system::CheckFeelessRateLimit<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -959,7 +1024,28 @@ pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
-pub type Executive = frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllModules>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllModules,
+	RemoveCollectiveFlip,
+>;
+
+/// Remove the random material left behind by the `RandomnessCollectiveFlip` module, which was
+/// replaced by the BABE randomness.
+pub struct RemoveCollectiveFlip;
+impl frame_support::traits::OnRuntimeUpgrade for RemoveCollectiveFlip {
+	fn on_runtime_upgrade() -> Weight {
+		frame_support::storage::migration::remove_storage_prefix(
+			b"RandomnessCollectiveFlip",
+			b"RandomMaterial",
+			&[],
+		);
+		RocksDbWeight::get().writes(1)
+	}
+}
 
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
@@ -1000,7 +1086,7 @@ impl_runtime_apis! {
 		}
 
 		fn random_seed() -> <Block as BlockT>::Hash {
-			RandomnessCollectiveFlip::random_seed()
+			pallet_babe::RandomnessFromOneEpochAgo::<Runtime>::random_seed()
 		}
 	}
 
@@ -1153,6 +1239,21 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_transaction_payment_rpc_runtime_api::SponsoredTransactionPaymentApi<
+		Block,
+		Balance,
+		AccountId,
+	> for Runtime {
+		fn query_sponsored_info(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			sponsor: AccountId,
+			who: AccountId,
+		) -> SponsoredDispatchInfo<Balance> {
+			TransactionPayment::query_sponsored_info(uxt, len, sponsor, who)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -1165,6 +1266,18 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+			let weight = Executive::try_runtime_upgrade()?;
+			Ok((weight, MaximumBlockWeight::get()))
+		}
+
+		fn execute_block_no_check(block: Block) -> Weight {
+			Executive::execute_block_no_check(block)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -1218,6 +1331,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_staking, Staking);
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_transaction_payment, TransactionPayment);
 			add_benchmark!(params, batches, pallet_treasury, Treasury);
 			add_benchmark!(params, batches, pallet_utility, Utility);
 			add_benchmark!(params, batches, pallet_vesting, Vesting);
@@ -29,6 +29,7 @@ pub mod pallet_scheduler;
 pub mod pallet_session;
 pub mod pallet_staking;
 pub mod pallet_timestamp;
+pub mod pallet_transaction_payment;
 pub mod pallet_utility;
 pub mod pallet_vesting;
 pub mod pallet_elections_phragmen;
@@ -87,4 +87,21 @@ impl pallet_multisig::WeightInfo for WeightInfo {
 			.saturating_add(DbWeight::get().reads(2 as Weight))
 			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
+	fn create_weighted(s: u32, ) -> Weight {
+		(62_718_000 as Weight)
+			.saturating_add((148_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_weighted_signatories(s: u32, ) -> Weight {
+		(24_503_000 as Weight)
+			.saturating_add((152_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn dissolve_weighted() -> Weight {
+		(45_372_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
 }
@@ -22,14 +22,15 @@ impl pallet_proxy::WeightInfo for WeightInfo {
 	fn proxy(p: u32, ) -> Weight {
 		(26127000 as Weight)
 			.saturating_add((214000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
 	}
 	fn proxy_announced(a: u32, p: u32, ) -> Weight {
 		(55405000 as Weight)
 			.saturating_add((774000 as Weight).saturating_mul(a as Weight))
 			.saturating_add((209000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(DbWeight::get().reads(3 as Weight))
-			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
 	fn remove_announcement(a: u32, p: u32, ) -> Weight {
 		(35879000 as Weight)
@@ -82,4 +83,10 @@ impl pallet_proxy::WeightInfo for WeightInfo {
 			.saturating_add(DbWeight::get().reads(1 as Weight))
 			.saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn add_scoped_proxy(p: u32, ) -> Weight {
+		(39254000 as Weight)
+			.saturating_add((231000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
 }
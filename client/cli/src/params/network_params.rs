@@ -43,6 +43,14 @@ pub struct NetworkParams {
 	#[structopt(long = "reserved-only")]
 	pub reserved_only: bool,
 
+	/// Run in permissioned mode, only allowing connections to/from nodes authorized by the
+	/// runtime.
+	///
+	/// Until the node learns which nodes are authorized, e.g. from the runtime state at the best
+	/// block, it connects to no one, not even to boot nodes or reserved nodes.
+	#[structopt(long = "permissioned")]
+	pub permissioned: bool,
+
 	/// The public address that other nodes will use to connect to it.
 	/// This can be used if there's a proxy in front of this node.
 	#[structopt(long, value_name = "PUBLIC_ADDR")]
@@ -145,6 +153,7 @@ impl NetworkParams {
 			} else {
 				NonReservedPeerMode::Accept
 			},
+			permissioned: self.permissioned,
 			listen_addresses,
 			public_addresses,
 			notifications_protocols: Vec::new(),
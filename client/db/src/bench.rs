@@ -31,7 +31,8 @@ use sp_core::{
 use sp_runtime::traits::{Block as BlockT, HashFor};
 use sp_runtime::Storage;
 use sp_state_machine::{
-	DBValue, backend::Backend as StateBackend, StorageCollection, ChildStorageCollection
+	DBValue, backend::Backend as StateBackend, StorageCollection, ChildStorageCollection,
+	ProofRecorder,
 };
 use kvdb::{KeyValueDB, DBTransaction};
 use crate::storage_cache::{CachingState, SharedCache, new_shared_cache};
@@ -44,14 +45,20 @@ type State<B> = CachingState<DbState<B>, B>;
 
 struct StorageDb<Block: BlockT> {
 	db: Arc<dyn KeyValueDB>,
+	proof_recorder: ProofRecorder<HashFor<Block>>,
 	_block: std::marker::PhantomData<Block>,
 }
 
 impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for StorageDb<Block> {
 	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
-		let key = prefixed_key::<HashFor<Block>>(key, prefix);
-		self.db.get(0, &key)
-			.map_err(|e| format!("Database backend error: {:?}", e))
+		if let Some(value) = self.proof_recorder.read().get(key) {
+			return Ok(value.clone());
+		}
+		let prefixed_key = prefixed_key::<HashFor<Block>>(key, prefix);
+		let value = self.db.get(0, &prefixed_key)
+			.map_err(|e| format!("Database backend error: {:?}", e))?;
+		self.proof_recorder.write().insert(key.clone(), value.clone());
+		Ok(value)
 	}
 }
 
@@ -105,6 +112,8 @@ pub struct BenchmarkingState<B: BlockT> {
 	child_key_tracker: RefCell<HashMap<Vec<u8>, HashMap<Vec<u8>, KeyTracker>>>,
 	read_write_tracker: RefCell<ReadWriteTracker>,
 	whitelist: RefCell<Vec<TrackedStorageKey>>,
+	/// Records all trie nodes fetched from the database, i.e. the storage proof of the execution.
+	proof_recorder: ProofRecorder<HashFor<B>>,
 }
 
 impl<B: BlockT> BenchmarkingState<B> {
@@ -126,6 +135,7 @@ impl<B: BlockT> BenchmarkingState<B> {
 			child_key_tracker: Default::default(),
 			read_write_tracker: Default::default(),
 			whitelist: Default::default(),
+			proof_recorder: Default::default(),
 		};
 
 		state.add_whitelist_to_tracker();
@@ -153,7 +163,11 @@ impl<B: BlockT> BenchmarkingState<B> {
 			None => Arc::new(::kvdb_memorydb::create(1)),
 		};
 		self.db.set(Some(db.clone()));
-		let storage_db = Arc::new(StorageDb::<B> { db, _block: Default::default() });
+		let storage_db = Arc::new(StorageDb::<B> {
+			db,
+			proof_recorder: self.proof_recorder.clone(),
+			_block: Default::default(),
+		});
 		*self.state.borrow_mut() = Some(State::new(
 			DbState::<B>::new(storage_db, self.root.get()),
 			self.shared_cache.clone(),
@@ -480,6 +494,27 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		self.wipe_tracker()
 	}
 
+	/// Get the size of the storage proof recorded since the last reset.
+	fn proof_size(&self) -> u32 {
+		self.proof_recorder.read()
+			.values()
+			.filter_map(|v| v.as_ref().map(|v| v.len() as u32))
+			.sum()
+	}
+
+	/// Get the number of unique trie nodes in the storage proof recorded since the last reset.
+	fn proof_nodes(&self) -> u32 {
+		self.proof_recorder.read()
+			.values()
+			.filter(|v| v.is_some())
+			.count() as u32
+	}
+
+	/// Reset the recorded storage proof.
+	fn reset_proof_size(&self) {
+		self.proof_recorder.write().clear()
+	}
+
 	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
 		self.whitelist.borrow().to_vec()
 	}
@@ -413,6 +413,10 @@ pub struct NetworkConfiguration {
 	pub reserved_nodes: Vec<MultiaddrWithPeerId>,
 	/// The non-reserved peer mode.
 	pub non_reserved_mode: NonReservedPeerMode,
+	/// If true, the node runs in permissioned mode: it only connects to and accepts the peers
+	/// set with `NetworkService::set_authorized_peers`, which is empty at start. This also
+	/// applies to boot nodes and reserved nodes.
+	pub permissioned: bool,
 	/// Client identifier. Sent over the wire for debugging purposes.
 	pub client_version: String,
 	/// Name of the node. Sent over the wire for debugging purposes.
@@ -445,6 +449,7 @@ impl NetworkConfiguration {
 			out_peers: 75,
 			reserved_nodes: Vec::new(),
 			non_reserved_mode: NonReservedPeerMode::Accept,
+			permissioned: false,
 			client_version: client_version.into(),
 			node_name: node_name.into(),
 			transport: TransportConfig::Normal {
@@ -1490,6 +1490,7 @@ mod tests {
 			out_peers: 128,
 			bootnodes: Vec::new(),
 			reserved_only: false,
+			authorized_only: false,
 			priority_groups: Vec::new(),
 		};
 		sc_peerset::Peerset::from_config(cfg)
@@ -74,6 +74,7 @@ fn build_nodes() -> (Swarm<CustomProtoWithAddr>, Swarm<CustomProtoWithAddr>) {
 				vec![]
 			},
 			reserved_only: false,
+			authorized_only: false,
 			priority_groups: Vec::new(),
 		});
 
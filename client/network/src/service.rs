@@ -226,6 +226,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			out_peers: params.network_config.out_peers,
 			bootnodes,
 			reserved_only: params.network_config.non_reserved_mode == NonReservedPeerMode::Deny,
+			authorized_only: params.network_config.permissioned,
 			priority_groups,
 		};
 
@@ -605,20 +606,19 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
 		&self.local_peer_id
 	}
 
-	/// Set authorized peers.
+	/// Set the peers we are allowed to be connected to in permissioned mode.
 	///
-	/// Need a better solution to manage authorized peers, but now just use reserved peers for
-	/// prototyping.
+	/// Connections with peers that are not part of the new set are dropped right away if the
+	/// permissioned mode is enabled. See
+	/// [`NetworkConfiguration::permissioned`](crate::config::NetworkConfiguration::permissioned).
 	pub fn set_authorized_peers(&self, peers: HashSet<PeerId>) {
-		self.peerset.set_reserved_peers(peers)
+		self.peerset.set_authorized_peers(peers)
 	}
 
-	/// Set authorized_only flag.
-	///
-	/// Need a better solution to decide authorized_only, but now just use reserved_only flag for
-	/// prototyping.
-	pub fn set_authorized_only(&self, reserved_only: bool) {
-		self.peerset.set_reserved_only(reserved_only)
+	/// Enable or disable the permissioned mode, i.e. only connect to and accept peers set with
+	/// [`NetworkService::set_authorized_peers`].
+	pub fn set_authorized_only(&self, authorized_only: bool) {
+		self.peerset.set_authorized_only(authorized_only)
 	}
 
 	/// Appends a notification to the buffer of pending outgoing notifications with the given peer.
@@ -183,13 +183,14 @@ impl<Storage: OffchainStorage> OffchainExt for Api<Storage> {
 		self.http.response_read_body(request_id, buffer, deadline)
 	}
 
-	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
+	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, _authorized_only: bool) {
 		let peer_ids: HashSet<PeerId> = nodes.into_iter()
 			.filter_map(|node| PeerId::from_bytes(node.0).ok())
 			.collect();
-		
+
+		// The permissioned mode is only ever switched by `--permissioned`, the runtime
+		// merely decides which nodes are authorized.
 		self.network_provider.set_authorized_peers(peer_ids);
-		self.network_provider.set_authorized_only(authorized_only);
 	}
 }
 
@@ -311,10 +312,6 @@ mod tests {
 		fn set_authorized_peers(&self, _peers: HashSet<PeerId>) {
 			unimplemented!()
 		}
-
-		fn set_authorized_only(&self, _reserved_only: bool) {
-			unimplemented!()
-		}
 	}
 
 	impl NetworkStateInfo for TestNetwork {
@@ -58,9 +58,6 @@ pub use sp_offchain::{OffchainWorkerApi, STORAGE_PREFIX};
 pub trait NetworkProvider: NetworkStateInfo {
 	/// Set the authorized peers.
 	fn set_authorized_peers(&self, peers: HashSet<PeerId>);
-	
-	/// Set the authorized only flag.
-	fn set_authorized_only(&self, reserved_only: bool);
 }
 
 impl<B, H> NetworkProvider for NetworkService<B, H>
@@ -71,10 +68,6 @@ where
 	fn set_authorized_peers(&self, peers: HashSet<PeerId>) {
 		self.set_authorized_peers(peers)
 	}
-
-	fn set_authorized_only(&self, reserved_only: bool) {
-		self.set_authorized_only(reserved_only)
-	}
 }
 
 /// An offchain workers manager.
@@ -256,10 +249,6 @@ mod tests {
 		fn set_authorized_peers(&self, _peers: HashSet<PeerId>) {
 			unimplemented!()
 		}
-
-		fn set_authorized_only(&self, _reserved_only: bool) {
-			unimplemented!()
-		}
 	}
 
 	struct TestPool(
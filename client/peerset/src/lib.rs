@@ -47,6 +47,8 @@ enum Action {
 	RemoveReservedPeer(PeerId),
 	SetReservedPeers(HashSet<PeerId>),
 	SetReservedOnly(bool),
+	SetAuthorizedPeers(HashSet<PeerId>),
+	SetAuthorizedOnly(bool),
 	ReportPeer(PeerId, ReputationChange),
 	SetPriorityGroup(String, HashSet<PeerId>),
 	AddToPriorityGroup(String, PeerId),
@@ -109,6 +111,16 @@ impl PeersetHandle {
 		let _ = self.tx.unbounded_send(Action::SetReservedPeers(peer_ids));
 	}
 
+	/// Set the peers we are allowed to be connected to when in authorized-only mode.
+	pub fn set_authorized_peers(&self, peer_ids: HashSet<PeerId>) {
+		let _ = self.tx.unbounded_send(Action::SetAuthorizedPeers(peer_ids));
+	}
+
+	/// Sets whether or not the peerset only has connections with authorized peers.
+	pub fn set_authorized_only(&self, authorized_only: bool) {
+		let _ = self.tx.unbounded_send(Action::SetAuthorizedOnly(authorized_only));
+	}
+
 	/// Reports an adjustment to the reputation of the given peer.
 	pub fn report_peer(&self, peer_id: PeerId, score_diff: ReputationChange) {
 		let _ = self.tx.unbounded_send(Action::ReportPeer(peer_id, score_diff));
@@ -175,6 +187,11 @@ pub struct PeersetConfig {
 	/// If true, we only accept nodes in [`PeersetConfig::priority_groups`].
 	pub reserved_only: bool,
 
+	/// If true, we only connect to and accept nodes set with
+	/// [`PeersetHandle::set_authorized_peers`], which is empty at start. This applies to every
+	/// node, including bootnodes and reserved nodes.
+	pub authorized_only: bool,
+
 	/// Lists of nodes we should always be connected to.
 	///
 	/// > **Note**: Keep in mind that the networking has to know an address for these nodes,
@@ -192,6 +209,10 @@ pub struct Peerset {
 	data: peersstate::PeersState,
 	/// If true, we only accept reserved nodes.
 	reserved_only: bool,
+	/// If true, we only connect to and accept nodes in `authorized_peers`.
+	authorized_only: bool,
+	/// Nodes we are allowed to be connected to in authorized-only mode.
+	authorized_peers: HashSet<PeerId>,
 	/// Lists of nodes that don't occupy slots and that we should try to always be connected to.
 	/// Is kept in sync with the list of reserved nodes in [`Peerset::data`].
 	priority_groups: HashMap<String, HashSet<PeerId>>,
@@ -223,6 +244,8 @@ impl Peerset {
 			tx,
 			rx,
 			reserved_only: config.reserved_only,
+			authorized_only: config.authorized_only,
+			authorized_peers: HashSet::new(),
 			priority_groups: config.priority_groups.clone().into_iter().collect(),
 			message_queue: VecDeque::new(),
 			created: now,
@@ -278,6 +301,42 @@ impl Peerset {
 		}
 	}
 
+	fn on_set_authorized_peers(&mut self, peer_ids: HashSet<PeerId>) {
+		self.authorized_peers = peer_ids;
+		self.disconnect_unauthorized();
+		self.alloc_slots();
+	}
+
+	fn on_set_authorized_only(&mut self, authorized_only: bool) {
+		self.authorized_only = authorized_only;
+		self.disconnect_unauthorized();
+		self.alloc_slots();
+	}
+
+	/// Returns true if we are allowed to be connected to the given node.
+	fn is_authorized(&self, peer_id: &PeerId) -> bool {
+		!self.authorized_only || self.authorized_peers.contains(peer_id)
+	}
+
+	/// Disconnect all the nodes that we are no longer allowed to be connected to.
+	fn disconnect_unauthorized(&mut self) {
+		if !self.authorized_only {
+			return;
+		}
+
+		for peer_id in self.data.connected_peers().cloned().collect::<Vec<_>>().into_iter() {
+			if self.authorized_peers.contains(&peer_id) {
+				continue;
+			}
+
+			debug!(target: "peerset", "Disconnecting unauthorized node {}", peer_id);
+			let peer = self.data.peer(&peer_id).into_connected()
+				.expect("We are enumerating connected peers, therefore the peer is connected; qed");
+			peer.disconnect();
+			self.message_queue.push_back(Message::Drop(peer_id));
+		}
+	}
+
 	fn on_set_priority_group(&mut self, group_id: &str, peers: HashSet<PeerId>) {
 		// Determine the difference between the current group and the new list.
 		let (to_insert, to_remove) = {
@@ -422,16 +481,20 @@ impl Peerset {
 	fn alloc_slots(&mut self) {
 		self.update_time();
 
+		let authorized_only = self.authorized_only;
+
 		// Try to connect to all the reserved nodes that we are not connected to.
 		loop {
 			let next = {
 				let data = &mut self.data;
+				let authorized_peers = &self.authorized_peers;
 				self.priority_groups
 					.get(RESERVED_NODES)
 					.into_iter()
 					.flatten()
 					.filter(move |n| {
-						data.peer(n).into_connected().is_none()
+						(!authorized_only || authorized_peers.contains(n)) &&
+							data.peer(n).into_connected().is_none()
 					})
 					.next()
 					.cloned()
@@ -466,11 +529,13 @@ impl Peerset {
 		loop {
 			let next = {
 				let data = &mut self.data;
+				let authorized_peers = &self.authorized_peers;
 				self.priority_groups
 					.values()
 					.flatten()
 					.filter(move |n| {
-						data.peer(n).into_connected().is_none()
+						(!authorized_only || authorized_peers.contains(n)) &&
+							data.peer(n).into_connected().is_none()
 					})
 					.next()
 					.cloned()
@@ -499,7 +564,10 @@ impl Peerset {
 		// Now, we try to connect to non-priority nodes.
 		loop {
 			// Try to grab the next node to attempt to connect to.
-			let next = match self.data.highest_not_connected_peer() {
+			let authorized_peers = &self.authorized_peers;
+			let next = match self.data.highest_not_connected_peer(|n| {
+				!authorized_only || authorized_peers.contains(n)
+			}) {
 				Some(p) => p,
 				None => break,	// No known node to add.
 			};
@@ -530,6 +598,12 @@ impl Peerset {
 		trace!(target: "peerset", "Incoming {:?}", peer_id);
 		self.update_time();
 
+		if !self.is_authorized(&peer_id) {
+			debug!(target: "peerset", "Rejecting unauthorized node {}", peer_id);
+			self.message_queue.push_back(Message::Reject(index));
+			return;
+		}
+
 		if self.reserved_only {
 			if !self.priority_groups.get(RESERVED_NODES).map_or(false, |n| n.contains(&peer_id)) {
 				self.message_queue.push_back(Message::Reject(index));
@@ -630,6 +704,7 @@ impl Peerset {
 				(peer_id.to_base58(), state)
 			}).collect::<HashMap<_, _>>(),
 			"reserved_only": self.reserved_only,
+			"authorized_only": self.authorized_only,
 			"message_queue": self.message_queue.len(),
 		})
 	}
@@ -669,6 +744,10 @@ impl Stream for Peerset {
 					self.on_set_reserved_peers(peer_ids),
 				Action::SetReservedOnly(reserved) =>
 					self.on_set_reserved_only(reserved),
+				Action::SetAuthorizedPeers(peer_ids) =>
+					self.on_set_authorized_peers(peer_ids),
+				Action::SetAuthorizedOnly(authorized_only) =>
+					self.on_set_authorized_only(authorized_only),
 				Action::ReportPeer(peer_id, score_diff) =>
 					self.on_report_peer(peer_id, score_diff),
 				Action::SetPriorityGroup(group_id, peers) =>
@@ -714,6 +793,7 @@ mod tests {
 			out_peers: 2,
 			bootnodes: vec![bootnode],
 			reserved_only: true,
+			authorized_only: false,
 			priority_groups: Vec::new(),
 		};
 
@@ -742,6 +822,7 @@ mod tests {
 			out_peers: 1,
 			bootnodes: vec![bootnode.clone()],
 			reserved_only: false,
+			authorized_only: false,
 			priority_groups: Vec::new(),
 		};
 
@@ -768,6 +849,7 @@ mod tests {
 			out_peers: 50,
 			bootnodes: vec![],
 			reserved_only: true,
+			authorized_only: false,
 			priority_groups: vec![],
 		};
 
@@ -779,6 +861,68 @@ mod tests {
 		]);
 	}
 
+	#[test]
+	fn test_peerset_reject_incoming_in_authorized_only() {
+		let authorized = PeerId::random();
+		let unauthorized = PeerId::random();
+		let ii = IncomingIndex(1);
+		let ii2 = IncomingIndex(2);
+		let config = PeersetConfig {
+			in_peers: 50,
+			out_peers: 50,
+			bootnodes: vec![],
+			reserved_only: false,
+			authorized_only: true,
+			priority_groups: vec![],
+		};
+
+		let (mut peerset, handle) = Peerset::from_config(config);
+		handle.set_authorized_peers(vec![authorized.clone()].into_iter().collect());
+
+		let fut = futures::future::poll_fn(move |cx| {
+			// We need one polling for the message to be processed.
+			assert_eq!(Stream::poll_next(Pin::new(&mut peerset), cx), Poll::Pending);
+
+			peerset.incoming(unauthorized.clone(), ii);
+			peerset.incoming(authorized.clone(), ii2);
+			assert_eq!(
+				Stream::poll_next(Pin::new(&mut peerset), cx),
+				Poll::Ready(Some(Message::Reject(ii))),
+			);
+			assert_eq!(
+				Stream::poll_next(Pin::new(&mut peerset), cx),
+				Poll::Ready(Some(Message::Accept(ii2))),
+			);
+
+			Poll::Ready(())
+		});
+
+		futures::executor::block_on(fut);
+	}
+
+	#[test]
+	fn test_peerset_authorized_peers_update() {
+		let bootnode = PeerId::random();
+		let config = PeersetConfig {
+			in_peers: 0,
+			out_peers: 2,
+			bootnodes: vec![bootnode.clone()],
+			reserved_only: false,
+			authorized_only: true,
+			priority_groups: vec![],
+		};
+
+		// The bootnode isn't authorized yet, so we don't connect to it.
+		let (peerset, handle) = Peerset::from_config(config);
+		handle.set_authorized_peers(vec![bootnode.clone()].into_iter().collect());
+		handle.set_authorized_peers(Default::default());
+
+		assert_messages(peerset, vec![
+			Message::Connect(bootnode.clone()),
+			Message::Drop(bootnode),
+		]);
+	}
+
 	#[test]
 	fn test_peerset_discovered() {
 		let bootnode = PeerId::random();
@@ -789,6 +933,7 @@ mod tests {
 			out_peers: 2,
 			bootnodes: vec![bootnode.clone()],
 			reserved_only: false,
+			authorized_only: false,
 			priority_groups: vec![],
 		};
 
@@ -810,6 +955,7 @@ mod tests {
 			out_peers: 25,
 			bootnodes: vec![],
 			reserved_only: false,
+			authorized_only: false,
 			priority_groups: vec![],
 		});
 
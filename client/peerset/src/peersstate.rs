@@ -166,13 +166,18 @@ impl PeersState {
 			.map(|(p, _)| p)
 	}
 
-	/// Returns the peer with the highest reputation and that we are not connected to.
+	/// Returns the peer with the highest reputation and that we are not connected to, only
+	/// considering the peers for which `filter` returns true.
 	///
 	/// If multiple nodes have the same reputation, which one is returned is unspecified.
-	pub fn highest_not_connected_peer(&mut self) -> Option<NotConnectedPeer> {
+	pub fn highest_not_connected_peer(
+		&mut self,
+		filter: impl Fn(&PeerId) -> bool,
+	) -> Option<NotConnectedPeer> {
 		let outcome = self.nodes
 			.iter_mut()
 			.filter(|(_, Node { connection_state, .. })| !connection_state.is_connected())
+			.filter(|(peer_id, _)| filter(peer_id))
 			.fold(None::<(&PeerId, &mut Node)>, |mut cur_node, to_try| {
 				if let Some(cur_node) = cur_node.take() {
 					if cur_node.1.reputation >= to_try.1.reputation {
@@ -573,19 +578,19 @@ mod tests {
 		let id1 = PeerId::random();
 		let id2 = PeerId::random();
 
-		assert!(peers_state.highest_not_connected_peer().is_none());
+		assert!(peers_state.highest_not_connected_peer(|_| true).is_none());
 		peers_state.peer(&id1).into_unknown().unwrap().discover().set_reputation(50);
 		peers_state.peer(&id2).into_unknown().unwrap().discover().set_reputation(25);
-		assert_eq!(peers_state.highest_not_connected_peer().map(|p| p.into_peer_id()), Some(id1.clone()));
+		assert_eq!(peers_state.highest_not_connected_peer(|_| true).map(|p| p.into_peer_id()), Some(id1.clone()));
 		peers_state.peer(&id2).into_not_connected().unwrap().set_reputation(75);
-		assert_eq!(peers_state.highest_not_connected_peer().map(|p| p.into_peer_id()), Some(id2.clone()));
+		assert_eq!(peers_state.highest_not_connected_peer(|_| true).map(|p| p.into_peer_id()), Some(id2.clone()));
 		peers_state.peer(&id2).into_not_connected().unwrap().try_accept_incoming().unwrap();
-		assert_eq!(peers_state.highest_not_connected_peer().map(|p| p.into_peer_id()), Some(id1.clone()));
+		assert_eq!(peers_state.highest_not_connected_peer(|_| true).map(|p| p.into_peer_id()), Some(id1.clone()));
 		peers_state.peer(&id1).into_not_connected().unwrap().set_reputation(100);
 		peers_state.peer(&id2).into_connected().unwrap().disconnect();
-		assert_eq!(peers_state.highest_not_connected_peer().map(|p| p.into_peer_id()), Some(id1.clone()));
+		assert_eq!(peers_state.highest_not_connected_peer(|_| true).map(|p| p.into_peer_id()), Some(id1.clone()));
 		peers_state.peer(&id1).into_not_connected().unwrap().set_reputation(-100);
-		assert_eq!(peers_state.highest_not_connected_peer().map(|p| p.into_peer_id()), Some(id2.clone()));
+		assert_eq!(peers_state.highest_not_connected_peer(|_| true).map(|p| p.into_peer_id()), Some(id2.clone()));
 	}
 
 	#[test]
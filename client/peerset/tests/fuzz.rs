@@ -55,6 +55,7 @@ fn test_once() {
 			vec![("foo".to_string(), nodes)]
 		},
 		reserved_only: Uniform::new_inclusive(0, 10).sample(&mut rng) == 0,
+		authorized_only: false,
 		in_peers: Uniform::new_inclusive(0, 25).sample(&mut rng),
 		out_peers: Uniform::new_inclusive(0, 25).sample(&mut rng),
 	});
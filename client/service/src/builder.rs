@@ -451,6 +451,54 @@ pub fn build_offchain_workers<TBl, TBackend, TCl>(
 	offchain_workers
 }
 
+/// Keep the nodes the runtime authorizes us to be connected to in sync with the network.
+///
+/// The authorized nodes are retrieved through the `NodeAuthorizationApi` at the best block, on
+/// startup and on every new best block, and handed to the network. They are only enforced if the
+/// network runs in permissioned mode, see `NetworkConfiguration::permissioned`.
+pub fn build_network_authorization<TBl, TCl>(
+	spawn_handle: SpawnTaskHandle,
+	client: Arc<TCl>,
+	network: Arc<NetworkService<TBl, <TBl as BlockT>::Hash>>,
+) where
+	TBl: BlockT,
+	TCl: ProvideRuntimeApi<TBl> + HeaderBackend<TBl> + BlockchainEvents<TBl> + Send + Sync + 'static,
+	<TCl as ProvideRuntimeApi<TBl>>::Api: sp_node_authorization::NodeAuthorizationApi<TBl>,
+{
+	update_authorized_peers(&*client, &network, client.info().best_hash);
+
+	let future = client.import_notification_stream()
+		.filter(|notification| ready(notification.is_new_best))
+		.for_each(move |notification| {
+			update_authorized_peers(&*client, &network, notification.hash);
+			ready(())
+		});
+
+	spawn_handle.spawn("network-authorization", future);
+}
+
+fn update_authorized_peers<TBl, TCl>(
+	client: &TCl,
+	network: &NetworkService<TBl, <TBl as BlockT>::Hash>,
+	at: TBl::Hash,
+) where
+	TBl: BlockT,
+	TCl: ProvideRuntimeApi<TBl>,
+	<TCl as ProvideRuntimeApi<TBl>>::Api: sp_node_authorization::NodeAuthorizationApi<TBl>,
+{
+	use sp_node_authorization::{NodeAuthorizationApi, OpaquePeerId};
+
+	let local_peer_id = OpaquePeerId(network.local_peer_id().clone().into_bytes());
+	match client.runtime_api().authorized_nodes(&BlockId::Hash(at), local_peer_id) {
+		Ok(nodes) => network.set_authorized_peers(
+			nodes.into_iter()
+				.filter_map(|node| sc_network::PeerId::from_bytes(node.0).ok())
+				.collect()
+		),
+		Err(e) => warn!("Failed to retrieve the authorized nodes at {}: {:?}", at, e),
+	}
+}
+
 /// Spawn the tasks that are required to run a node.
 pub fn spawn_tasks<TBl, TBackend, TExPool, TRpc, TCl>(
 	params: SpawnTasksParams<TBl, TCl, TExPool, TRpc, TBackend>,
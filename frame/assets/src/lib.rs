@@ -134,6 +134,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{Parameter, decl_module, decl_event, decl_storage, decl_error, ensure};
+use frame_support::traits::MultiAsset;
+use sp_runtime::DispatchResult;
 use sp_runtime::traits::{Member, AtLeast32Bit, AtLeast32BitUnsigned, Zero, StaticLookup};
 use frame_system::ensure_signed;
 use sp_runtime::traits::One;
@@ -193,15 +195,8 @@ decl_module! {
 			#[compact] amount: T::Balance
 		) {
 			let origin = ensure_signed(origin)?;
-			let origin_account = (id, origin.clone());
-			let origin_balance = <Balances<T>>::get(&origin_account);
 			let target = T::Lookup::lookup(target)?;
-			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
-			ensure!(origin_balance >= amount, Error::<T>::BalanceLow);
-
-			Self::deposit_event(RawEvent::Transferred(id, origin, target.clone(), amount));
-			<Balances<T>>::insert(origin_account, origin_balance - amount);
-			<Balances<T>>::mutate((id, target), |balance| *balance += amount);
+			Self::do_transfer(id, origin, target, amount)?;
 		}
 
 		/// Destroy any assets of `id` owned by `origin`.
@@ -276,6 +271,44 @@ impl<T: Trait> Module<T> {
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
 		<TotalSupply<T>>::get(id)
 	}
+
+	// Private mutables
+
+	/// Move `amount` of asset `id` from `origin` to `target`.
+	fn do_transfer(
+		id: T::AssetId,
+		origin: T::AccountId,
+		target: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let origin_account = (id, origin.clone());
+		let origin_balance = <Balances<T>>::get(&origin_account);
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+		ensure!(origin_balance >= amount, Error::<T>::BalanceLow);
+
+		Self::deposit_event(RawEvent::Transferred(id, origin, target.clone(), amount));
+		<Balances<T>>::insert(origin_account, origin_balance - amount);
+		<Balances<T>>::mutate((id, target), |balance| *balance += amount);
+		Ok(())
+	}
+}
+
+impl<T: Trait> MultiAsset<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn balance(id: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<Balances<T>>::get((id, who))
+	}
+
+	fn transfer(
+		id: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Self::do_transfer(id, source.clone(), dest.clone(), amount)
+	}
 }
 
 #[cfg(test)]
@@ -316,6 +349,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = ();
@@ -386,6 +422,17 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn multi_asset_transfer_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(<Assets as MultiAsset<u64>>::transfer(0, &1, &2, 40));
+			assert_eq!(<Assets as MultiAsset<u64>>::balance(0, &1), 60);
+			assert_eq!(<Assets as MultiAsset<u64>>::balance(0, &2), 40);
+			assert_noop!(<Assets as MultiAsset<u64>>::transfer(0, &2, &3, 41), Error::<Test>::BalanceLow);
+		});
+	}
+
 	#[test]
 	fn transferring_less_than_one_unit_should_not_work() {
 		new_test_ext().execute_with(|| {
@@ -161,6 +161,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = ();
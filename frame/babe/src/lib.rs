@@ -25,7 +25,9 @@ use codec::{Decode, Encode};
 use frame_support::{
 	decl_error, decl_module, decl_storage,
 	dispatch::DispatchResultWithPostInfo,
-	traits::{FindAuthor, Get, KeyOwnerProofSystem, Randomness as RandomnessT},
+	traits::{
+		FindAuthor, Get, KeyOwnerProofSystem, Randomness as RandomnessT, RandomnessWithFreshness,
+	},
 	weights::{Pays, Weight},
 	Parameter,
 };
@@ -190,7 +192,7 @@ decl_storage! {
 		NextEpochConfig: Option<NextConfigDescriptor>;
 
 		/// Next epoch randomness.
-		NextRandomness: schnorrkel::Randomness;
+		NextRandomness get(fn next_randomness): schnorrkel::Randomness;
 
 		/// Randomness under construction.
 		///
@@ -216,6 +218,16 @@ decl_storage! {
 		/// on block finalization. Querying this storage entry outside of block
 		/// execution context should always yield zero.
 		Lateness get(fn lateness): T::BlockNumber;
+
+		/// The VRF output of the author of the current block, if it was authored in a primary slot.
+		///
+		/// Set on every block initialization and exposed through [`CurrentBlockRandomness`].
+		AuthorVrfRandomness get(fn author_vrf_randomness): MaybeRandomness;
+
+		/// The block numbers at which the previous and the current epoch started, respectively.
+		///
+		/// Used to tell since when the epoch randomness is known.
+		EpochStart get(fn epoch_start): (T::BlockNumber, T::BlockNumber);
 	}
 	add_extra_genesis {
 		config(authorities): Vec<(AuthorityId, BabeAuthorityWeight)>;
@@ -320,11 +332,77 @@ impl<T: Trait> RandomnessT<<T as frame_system::Trait>::Hash> for Module<T> {
 	///   output of this random function. This reduces the ability of a cabal of block producers
 	///   from conspiring against individuals.
 	fn random(subject: &[u8]) -> T::Hash {
-		let mut subject = subject.to_vec();
-		subject.reserve(VRF_OUTPUT_LENGTH);
-		subject.extend_from_slice(&Self::randomness()[..]);
+		hash_with_randomness::<T>(subject, &Self::randomness())
+	}
+}
+
+fn hash_with_randomness<T: Trait>(subject: &[u8], randomness: &schnorrkel::Randomness) -> T::Hash {
+	let mut subject = subject.to_vec();
+	subject.reserve(VRF_OUTPUT_LENGTH);
+	subject.extend_from_slice(&randomness[..]);
+
+	<T as frame_system::Trait>::Hashing::hash(&subject[..])
+}
+
+/// Randomness derived from the VRF outputs of the epoch before the previous one, i.e. the
+/// randomness of the current epoch.
+///
+/// It is known since the start of the previous epoch, so it changes only once per epoch and is
+/// the same value used by [`Module`]'s own [`Randomness`](RandomnessT) implementation.
+pub struct RandomnessFromTwoEpochsAgo<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> RandomnessT<T::Hash> for RandomnessFromTwoEpochsAgo<T> {
+	fn random(subject: &[u8]) -> T::Hash {
+		Self::random_with_freshness(subject).0
+	}
+}
+
+impl<T: Trait> RandomnessWithFreshness<T::Hash, T::BlockNumber> for RandomnessFromTwoEpochsAgo<T> {
+	fn random_with_freshness(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		(hash_with_randomness::<T>(subject, &Randomness::get()), EpochStart::<T>::get().0)
+	}
+}
+
+/// Randomness derived from the VRF outputs of the previous epoch, i.e. the randomness that will
+/// be used by the next epoch.
+///
+/// It is known since the start of the current epoch. A block producer could only influence it by
+/// withholding their blocks during the previous epoch, each one of them having exactly one bit of
+/// influence per block.
+pub struct RandomnessFromOneEpochAgo<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> RandomnessT<T::Hash> for RandomnessFromOneEpochAgo<T> {
+	fn random(subject: &[u8]) -> T::Hash {
+		Self::random_with_freshness(subject).0
+	}
+}
+
+impl<T: Trait> RandomnessWithFreshness<T::Hash, T::BlockNumber> for RandomnessFromOneEpochAgo<T> {
+	fn random_with_freshness(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		(hash_with_randomness::<T>(subject, &NextRandomness::get()), EpochStart::<T>::get().1)
+	}
+}
+
+/// Randomness derived from the VRF output of the author of the current block.
+///
+/// This is `None` for blocks authored in secondary slots, which carry no VRF output. The author
+/// can compute the value as soon as the parent block is known, so it is reported as known since
+/// the parent block.
+pub struct CurrentBlockRandomness<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> RandomnessT<Option<T::Hash>> for CurrentBlockRandomness<T> {
+	fn random(subject: &[u8]) -> Option<T::Hash> {
+		Self::random_with_freshness(subject).0
+	}
+}
+
+impl<T: Trait> RandomnessWithFreshness<Option<T::Hash>, T::BlockNumber> for CurrentBlockRandomness<T> {
+	fn random_with_freshness(subject: &[u8]) -> (Option<T::Hash>, T::BlockNumber) {
+		let random = AuthorVrfRandomness::get()
+			.map(|randomness| hash_with_randomness::<T>(subject, &randomness));
+		let known_since = <frame_system::Module<T>>::block_number().saturating_sub(One::one());
 
-		<T as frame_system::Trait>::Hashing::hash(&subject[..])
+		(random, known_since)
 	}
 }
 
@@ -446,6 +524,11 @@ impl<T: Trait> Module<T> {
 		EpochIndex::put(epoch_index);
 		Authorities::put(authorities);
 
+		EpochStart::<T>::mutate(|(previous_epoch_start, current_epoch_start)| {
+			*previous_epoch_start = *current_epoch_start;
+			*current_epoch_start = <frame_system::Module<T>>::block_number();
+		});
+
 		// Update epoch randomness.
 		let next_epoch_index = epoch_index
 			.checked_add(1)
@@ -577,6 +660,7 @@ impl<T: Trait> Module<T> {
 			}
 		});
 
+		AuthorVrfRandomness::put(maybe_randomness);
 		Initialized::put(maybe_randomness);
 
 		// enact epoch change, if necessary.
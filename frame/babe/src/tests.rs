@@ -101,6 +101,13 @@ fn first_block_epoch_zero_start() {
 		assert_eq!(Babe::current_slot(), genesis_slot);
 		assert_eq!(Babe::epoch_index(), 0);
 
+		// the author's VRF output is exposed as per-block randomness right away.
+		assert_eq!(Babe::author_vrf_randomness(), Some(vrf_randomness));
+		assert_eq!(
+			CurrentBlockRandomness::<Test>::random_with_freshness(&b"subject"[..]),
+			(Some(hash_with_randomness::<Test>(&b"subject"[..], &vrf_randomness)), 0),
+		);
+
 		Babe::on_finalize(1);
 		let header = System::finalize();
 
@@ -156,6 +163,41 @@ fn can_predict_next_epoch_change() {
 	})
 }
 
+#[test]
+fn randomness_reports_since_when_it_is_known() {
+	new_test_ext(1).execute_with(|| {
+		assert_eq!(<Test as Trait>::EpochDuration::get(), 3);
+		// this sets the genesis slot to 6;
+		go_to_block(1, 6);
+		assert_eq!(Babe::epoch_start(), (0, 0));
+
+		// epochs change at slot 9 (block 4) and slot 12 (block 7).
+		progress_to_block(4);
+		assert_eq!(Babe::epoch_start(), (0, 4));
+		progress_to_block(8);
+		assert_eq!(Babe::epoch_index(), 2);
+		assert_eq!(Babe::epoch_start(), (4, 7));
+
+		let subject = &b"subject"[..];
+		assert_eq!(
+			RandomnessFromTwoEpochsAgo::<Test>::random_with_freshness(subject),
+			(hash_with_randomness::<Test>(subject, &Babe::randomness()), 4),
+		);
+		assert_eq!(
+			RandomnessFromTwoEpochsAgo::<Test>::random(subject),
+			<Babe as RandomnessT<_>>::random(subject),
+		);
+		assert_eq!(
+			RandomnessFromOneEpochAgo::<Test>::random_with_freshness(subject),
+			(hash_with_randomness::<Test>(subject, &Babe::next_randomness()), 7),
+		);
+
+		// blocks authored in secondary slots carry no VRF output.
+		assert_eq!(Babe::author_vrf_randomness(), None);
+		assert_eq!(CurrentBlockRandomness::<Test>::random_with_freshness(subject), (None, 7));
+	})
+}
+
 #[test]
 fn can_enact_next_config() {
 	new_test_ext(1).execute_with(|| {
@@ -159,9 +159,10 @@ mod default_weight;
 use sp_std::prelude::*;
 use sp_std::{cmp, result, mem, fmt::Debug, ops::BitOr, convert::Infallible};
 use codec::{Codec, Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use frame_support::{
 	StorageValue, Parameter, decl_event, decl_storage, decl_module, decl_error, ensure,
-	weights::{Weight, Pays},
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, Pays},
 	traits::{
 		Currency, OnKilledAccount, OnUnbalanced, TryDrop, StoredMap,
 		WithdrawReason, WithdrawReasons, LockIdentifier, LockableCurrency, ExistenceRequirement,
@@ -181,10 +182,25 @@ pub use self::imbalances::{PositiveImbalance, NegativeImbalance};
 
 pub trait WeightInfo {
 	fn transfer() -> Weight;
+	fn transfer_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn transfer_keep_alive() -> Weight;
+	fn transfer_keep_alive_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_balance_creating() -> Weight;
+	fn set_balance_creating_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_balance_killing() -> Weight;
+	fn set_balance_killing_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_transfer() -> Weight;
+	fn force_transfer_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Subtrait<I: Instance = DefaultInstance>: frame_system::Trait {
@@ -288,7 +304,7 @@ decl_error! {
 }
 
 /// Simplified reasons for withdrawing balance.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub enum Reasons {
 	/// Paying system transaction fees.
 	Fee = 0,
@@ -320,7 +336,7 @@ impl BitOr for Reasons {
 
 /// A single lock on a balance. There can be many of these on an account and they "overlap", so the
 /// same balance is frozen by multiple locks.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct BalanceLock<Balance> {
 	/// An identifier for this lock. Only one lock may be in existence for each identifier.
 	pub id: LockIdentifier,
@@ -331,7 +347,7 @@ pub struct BalanceLock<Balance> {
 }
 
 /// All balance information for an account.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
 pub struct AccountData<Balance> {
 	/// Non-reserved part of the balance. There may still be restrictions on this, but it is the
 	/// total pool what may in principle be transferred, reserved and used for tipping.
@@ -377,7 +393,7 @@ impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
 // A value placed in storage that represents the current version of the Balances storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
 // storage migration logic. This should match directly with the semantic versions of the Rust crate.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	V1_0_0,
 	V2_0_0,
@@ -463,7 +479,10 @@ decl_module! {
 		/// - DB Weight: 1 Read and 1 Write to destination account
 		/// - Origin account is already in memory, so no DB operations for them.
 		/// # </weight>
-		#[weight = (T::WeightInfo::transfer(), Pays::No)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::transfer(), Pays::No),
+			T::WeightInfo::transfer_proof_size(),
+		)]
 		pub fn transfer(
 			origin,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -492,9 +511,12 @@ decl_module! {
 		///     - Killing: 35.11 µs
 		/// - DB Weight: 1 Read, 1 Write to `who`
 		/// # </weight>
-		#[weight = (T::WeightInfo::set_balance_creating() // Creates a new account.
-			.max(T::WeightInfo::set_balance_killing()), Pays::No) // Kills an existing account.
-		]
+		#[weight = WithProofSize(
+			(T::WeightInfo::set_balance_creating() // Creates a new account.
+				.max(T::WeightInfo::set_balance_killing()), Pays::No), // Kills an existing account.
+			T::WeightInfo::set_balance_creating_proof_size()
+				.max(T::WeightInfo::set_balance_killing_proof_size()),
+		)]
 		fn set_balance(
 			origin,
 			who: <T::Lookup as StaticLookup>::Source,
@@ -536,7 +558,10 @@ decl_module! {
 		/// - Same as transfer, but additional read and write because the source account is
 		///   not assumed to be in the overlay.
 		/// # </weight>
-		#[weight = (T::WeightInfo::force_transfer(), Pays::No)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::force_transfer(), Pays::No),
+			T::WeightInfo::force_transfer_proof_size(),
+		)]
 		pub fn force_transfer(
 			origin,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -560,7 +585,10 @@ decl_module! {
 		/// - Base Weight: 51.4 µs
 		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
 		/// #</weight>
-		#[weight = (T::WeightInfo::transfer_keep_alive(), Pays::No)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::transfer_keep_alive(), Pays::No),
+			T::WeightInfo::transfer_keep_alive_proof_size(),
+		)]
 		pub fn transfer_keep_alive(
 			origin,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -899,6 +927,9 @@ impl<T: Subtrait<I>, I: Instance> frame_system::Trait for ElevatedTrait<T, I> {
 	type BlockExecutionWeight = T::BlockExecutionWeight;
 	type ExtrinsicBaseWeight = T::ExtrinsicBaseWeight;
 	type MaximumExtrinsicWeight = T::MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = T::MaximumBlockLength;
 	type AvailableBlockRatio = T::AvailableBlockRatio;
 	type Version = T::Version;
@@ -22,7 +22,7 @@
 use sp_runtime::{
 	Perbill,
 	traits::IdentityLookup,
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 };
 use sp_core::H256;
 use sp_io;
@@ -84,6 +84,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -102,6 +105,9 @@ impl pallet_transaction_payment::Trait for Test {
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<u64>;
 	type FeeMultiplierUpdate = ();
+	type SponsorSignature = TestSignature;
+	type SponsorPublic = UintAuthorityId;
+	type WeightInfo = ();
 }
 
 impl Trait for Test {
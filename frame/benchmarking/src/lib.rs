@@ -829,6 +829,8 @@ macro_rules! impl_benchmark {
 
 						// Reset the read/write counter so we don't count operations in the setup process.
 						$crate::benchmarking::reset_read_write_count();
+						// Likewise, only record the storage proof of the benchmarked logic.
+						$crate::benchmarking::reset_proof_size();
 
 						if verify {
 							closure_to_benchmark()?;
@@ -856,6 +858,12 @@ macro_rules! impl_benchmark {
 								target: "benchmark",
 								"Read/Write Count {:?}", read_write_count
 							);
+							let proof_size = $crate::benchmarking::proof_size();
+							let proof_nodes = $crate::benchmarking::proof_nodes();
+							frame_support::debug::trace!(
+								target: "benchmark",
+								"Proof Size {} bytes, {} trie nodes", proof_size, proof_nodes
+							);
 
 							// Time the storage root recalculation.
 							let start_storage_root = $crate::benchmarking::current_time();
@@ -871,6 +879,8 @@ macro_rules! impl_benchmark {
 								repeat_reads: read_write_count.1,
 								writes: read_write_count.2,
 								repeat_writes: read_write_count.3,
+								proof_size,
+								proof_nodes,
 							});
 						}
 
@@ -1052,6 +1062,9 @@ macro_rules! impl_benchmark_test {
 /// ```
 ///
 /// At the end of `dispatch_benchmark`, you should return this batches object.
+///
+/// Both the pallet and the benchmark of the `BenchmarkConfig` may contain `*` wildcards, e.g.
+/// `pallet_*` or `*`, to run the benchmarks of several pallets at once.
 #[macro_export]
 macro_rules! add_benchmark {
 	( $params:ident, $batches:ident, $name:ident, $( $location:tt )* ) => (
@@ -1067,9 +1080,11 @@ macro_rules! add_benchmark {
 			verify,
 			extra,
 		} = config;
-		if &pallet[..] == &name_string[..] || &pallet[..] == &b"*"[..] {
-			if &pallet[..] == &b"*"[..] || &benchmark[..] == &b"*"[..] {
-				for benchmark in $( $location )*::benchmarks(*extra).into_iter() {
+		if $crate::pattern_matches(&pallet[..], &name_string[..]) {
+			if benchmark.contains(&b'*') {
+				let benchmarks = $( $location )*::benchmarks(*extra).into_iter()
+					.filter(|name| $crate::pattern_matches(&benchmark[..], name));
+				for benchmark in benchmarks {
 					$batches.push($crate::BenchmarkBatch {
 						results: $( $location )*::run_benchmark(
 							benchmark,
@@ -1084,7 +1099,10 @@ macro_rules! add_benchmark {
 						benchmark: benchmark.to_vec(),
 					});
 				}
-			} else {
+			// A pallet selected by a wildcard is skipped if it doesn't have the benchmark.
+			} else if !pallet.contains(&b'*') ||
+				$( $location )*::benchmarks(true).iter().any(|name| *name == &benchmark[..])
+			{
 				$batches.push($crate::BenchmarkBatch {
 					results: $( $location )*::run_benchmark(
 						&benchmark[..],
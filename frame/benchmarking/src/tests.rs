@@ -90,6 +90,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = ();
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = ();
 	type AvailableBlockRatio = ();
 	type Version = ();
@@ -258,3 +261,17 @@ fn benchmarks_generate_unit_tests() {
 		assert_ok!(test_benchmark_no_components::<Test>());
 	});
 }
+
+#[test]
+fn pattern_matches_works() {
+	assert!(pattern_matches(b"*", b"pallet_balances"));
+	assert!(pattern_matches(b"*", b""));
+	assert!(pattern_matches(b"pallet_balances", b"pallet_balances"));
+	assert!(!pattern_matches(b"pallet_balances", b"pallet_balances2"));
+	assert!(pattern_matches(b"pallet_*", b"pallet_balances"));
+	assert!(!pattern_matches(b"pallet_*", b"frame_system"));
+	assert!(pattern_matches(b"*_keep_alive", b"transfer_keep_alive"));
+	assert!(!pattern_matches(b"*_keep_alive", b"transfer"));
+	assert!(pattern_matches(b"set_*_*", b"set_balance_creating"));
+	assert!(!pattern_matches(b"set_*_*", b"set_balance"));
+}
@@ -61,6 +61,8 @@ pub struct BenchmarkResults {
 	pub repeat_reads: u32,
 	pub writes: u32,
 	pub repeat_writes: u32,
+	pub proof_size: u32,
+	pub proof_nodes: u32,
 }
 
 /// Configuration used to setup and run runtime benchmarks.
@@ -125,6 +127,21 @@ pub trait Benchmarking {
 		self.reset_read_write_count()
 	}
 
+	/// Get the size of the storage proof recorded since the last reset.
+	fn proof_size(&self) -> u32 {
+		self.proof_size()
+	}
+
+	/// Get the number of unique trie nodes in the storage proof recorded since the last reset.
+	fn proof_nodes(&self) -> u32 {
+		self.proof_nodes()
+	}
+
+	/// Reset the recorded storage proof.
+	fn reset_proof_size(&mut self) {
+		self.reset_proof_size()
+	}
+
 	/// Get the DB whitelist.
 	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
 		self.get_whitelist()
@@ -209,6 +226,19 @@ pub trait BenchmarkingSetup<T, I = ()> {
 	) -> Result<Box<dyn FnOnce() -> Result<(), &'static str>>, &'static str>;
 }
 
+/// Whether the pallet or benchmark `name` is selected by `pattern`, in which `*` matches any
+/// sequence of characters, e.g. `pallet_*` or `*`.
+pub fn pattern_matches(pattern: &[u8], name: &[u8]) -> bool {
+	match pattern.iter().position(|c| *c == b'*') {
+		None => pattern == name,
+		Some(wildcard) => {
+			let (prefix, rest) = (&pattern[..wildcard], &pattern[wildcard + 1..]);
+			name.starts_with(prefix) &&
+				(prefix.len()..=name.len()).any(|start| pattern_matches(rest, &name[start..]))
+		},
+	}
+}
+
 /// Grab an account, seeded by a name and index.
 pub fn account<AccountId: Decode + Default>(name: &'static str, index: u32, seed: u32) -> AccountId {
 	let entropy = (name, index, seed).using_encoded(blake2_256);
@@ -56,8 +56,11 @@ use frame_support::{
 	},
 	ensure,
 	traits::{ChangeMembers, EnsureOrigin, Get, InitializeMembers},
-	weights::{DispatchClass, GetDispatchInfo, Weight},
+	weights::{
+		DispatchClass, GetDispatchInfo, Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize,
+	},
 };
+use frame_support::type_info::TypeInfo;
 use frame_system::{self as system, ensure_signed, ensure_root};
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -122,15 +125,45 @@ impl DefaultVote for MoreThanMajorityThenPrimeDefaultVote {
 
 pub trait WeightInfo {
 	fn set_members(m: u32, n: u32, p: u32, ) -> Weight;
+	fn set_members_proof_size(_m: u32, _n: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn execute(b: u32, m: u32, ) -> Weight;
+	fn execute_proof_size(_b: u32, _m: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn propose_execute(b: u32, m: u32, ) -> Weight;
+	fn propose_execute_proof_size(_b: u32, _m: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn propose_proposed(b: u32, m: u32, p: u32, ) -> Weight;
+	fn propose_proposed_proof_size(_b: u32, _m: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vote(m: u32, ) -> Weight;
+	fn vote_proof_size(_m: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_early_disapproved(m: u32, p: u32, ) -> Weight;
+	fn close_early_disapproved_proof_size(_m: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_early_approved(b: u32, m: u32, p: u32, ) -> Weight;
+	fn close_early_approved_proof_size(_b: u32, _m: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_disapproved(m: u32, p: u32, ) -> Weight;
+	fn close_disapproved_proof_size(_m: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_approved(b: u32, m: u32, p: u32, ) -> Weight;
+	fn close_approved_proof_size(_b: u32, _m: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn disapprove_proposal(p: u32, ) -> Weight;
+	fn disapprove_proposal_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait<I: Instance=DefaultInstance>: frame_system::Trait {
@@ -167,7 +200,7 @@ pub trait Trait<I: Instance=DefaultInstance>: frame_system::Trait {
 }
 
 /// Origin for the collective module.
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub enum RawOrigin<AccountId, I> {
 	/// It has been condoned by a given number of members of the collective from a given total.
 	Members(MemberCount, MemberCount),
@@ -180,7 +213,7 @@ pub enum RawOrigin<AccountId, I> {
 /// Origin for the collective module.
 pub type Origin<T, I=DefaultInstance> = RawOrigin<<T as frame_system::Trait>::AccountId, I>;
 
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 /// Info for keeping track of a motion being voted on.
 pub struct Votes<AccountId, BlockNumber> {
 	/// The proposal's unique index.
@@ -317,13 +350,20 @@ decl_module! {
 		///   - `P` storage mutations (codec `O(M)`) for updating the votes for each proposal
 		///   - 1 storage write (codec `O(1)`) for deleting the old `prime` and setting the new one
 		/// # </weight>
-		#[weight = (
-			T::WeightInfo::set_members(
-				*old_count, // M
-				new_members.len() as u32, // N
-				T::MaxProposals::get() // P
+		#[weight = WithProofSize(
+			(
+				T::WeightInfo::set_members(
+					*old_count, // M
+					new_members.len() as u32, // N
+					T::MaxProposals::get() // P
+				),
+				DispatchClass::Operational
+			),
+			T::WeightInfo::set_members_proof_size(
+				*old_count,
+				new_members.len() as u32,
+				T::MaxProposals::get(),
 			),
-			DispatchClass::Operational
 		)]
 		fn set_members(origin,
 			new_members: Vec<T::AccountId>,
@@ -369,12 +409,16 @@ decl_module! {
 		/// - DB: 1 read (codec `O(M)`) + DB access of `proposal`
 		/// - 1 event
 		/// # </weight>
-		#[weight = (
-			T::WeightInfo::execute(
-				*length_bound, // B
-				T::MaxMembers::get(), // M
-			).saturating_add(proposal.get_dispatch_info().weight), // P
-			DispatchClass::Operational
+		#[weight = WithProofSize(
+			(
+				T::WeightInfo::execute(
+					*length_bound, // B
+					T::MaxMembers::get(), // M
+				).saturating_add(proposal.get_dispatch_info().weight), // P
+				DispatchClass::Operational
+			),
+			T::WeightInfo::execute_proof_size(*length_bound, T::MaxMembers::get())
+				.saturating_add(proposal.get_dispatch_info().proof_size),
 		)]
 		fn execute(origin,
 			proposal: Box<<T as Trait<I>>::Proposal>,
@@ -427,20 +471,32 @@ decl_module! {
 		///       - 1 storage write `Voting` (codec `O(M)`)
 		///   - 1 event
 		/// # </weight>
-		#[weight = (
+		#[weight = WithProofSize(
+			(
+				if *threshold < 2 {
+					T::WeightInfo::propose_execute(
+						*length_bound, // B
+						T::MaxMembers::get(), // M
+					).saturating_add(proposal.get_dispatch_info().weight) // P1
+				} else {
+					T::WeightInfo::propose_proposed(
+						*length_bound, // B
+						T::MaxMembers::get(), // M
+						T::MaxProposals::get(), // P2
+					)
+				},
+				DispatchClass::Operational
+			),
 			if *threshold < 2 {
-				T::WeightInfo::propose_execute(
-					*length_bound, // B
-					T::MaxMembers::get(), // M
-				).saturating_add(proposal.get_dispatch_info().weight) // P1
+				T::WeightInfo::propose_execute_proof_size(*length_bound, T::MaxMembers::get())
+					.saturating_add(proposal.get_dispatch_info().proof_size)
 			} else {
-				T::WeightInfo::propose_proposed(
-					*length_bound, // B
-					T::MaxMembers::get(), // M
-					T::MaxProposals::get(), // P2
+				T::WeightInfo::propose_proposed_proof_size(
+					*length_bound,
+					T::MaxMembers::get(),
+					T::MaxProposals::get(),
 				)
 			},
-			DispatchClass::Operational
 		)]
 		fn propose(origin,
 			#[compact] threshold: MemberCount,
@@ -508,9 +564,9 @@ decl_module! {
 		///   - 1 storage mutation `Voting` (codec `O(M)`)
 		/// - 1 event
 		/// # </weight>
-		#[weight = (
-			T::WeightInfo::vote(T::MaxMembers::get()),
-			DispatchClass::Operational
+		#[weight = WithProofSize(
+			(T::WeightInfo::vote(T::MaxMembers::get()), DispatchClass::Operational),
+			T::WeightInfo::vote_proof_size(T::MaxMembers::get()),
 		)]
 		fn vote(origin,
 			proposal: T::Hash,
@@ -583,20 +639,26 @@ decl_module! {
 		///  - any mutations done while executing `proposal` (`P1`)
 		/// - up to 3 events
 		/// # </weight>
-		#[weight = (
-			{
-				let b = *length_bound;
-				let m = T::MaxMembers::get();
-				let p1 = *proposal_weight_bound;
-				let p2 = T::MaxProposals::get();
-				T::WeightInfo::close_early_approved(b, m, p2)
-					.max(T::WeightInfo::close_early_disapproved(m, p2))
-					.max(T::WeightInfo::close_approved(b, m, p2))
-					.max(T::WeightInfo::close_disapproved(m, p2))
-					.saturating_add(p1)
-			},
-			DispatchClass::Operational
-		)]
+		#[weight = {
+			let b = *length_bound;
+			let m = T::MaxMembers::get();
+			let p1 = *proposal_weight_bound;
+			let p2 = T::MaxProposals::get();
+			WithProofSize(
+				(
+					T::WeightInfo::close_early_approved(b, m, p2)
+						.max(T::WeightInfo::close_early_disapproved(m, p2))
+						.max(T::WeightInfo::close_approved(b, m, p2))
+						.max(T::WeightInfo::close_disapproved(m, p2))
+						.saturating_add(p1),
+					DispatchClass::Operational
+				),
+				T::WeightInfo::close_early_approved_proof_size(b, m, p2)
+					.max(T::WeightInfo::close_early_disapproved_proof_size(m, p2))
+					.max(T::WeightInfo::close_approved_proof_size(b, m, p2))
+					.max(T::WeightInfo::close_disapproved_proof_size(m, p2)),
+			)
+		}]
 		fn close(origin,
 			proposal_hash: T::Hash,
 			#[compact] index: ProposalIndex,
@@ -685,7 +747,10 @@ decl_module! {
 		/// * Reads: Proposals
 		/// * Writes: Voting, Proposals, ProposalOf
 		/// # </weight>
-		#[weight = T::WeightInfo::disapprove_proposal(T::MaxProposals::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::disapprove_proposal(T::MaxProposals::get()),
+			T::WeightInfo::disapprove_proposal_proof_size(T::MaxProposals::get()),
+		)]
 		fn disapprove_proposal(origin, proposal_hash: T::Hash) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			let proposal_count = Self::do_disapprove_proposal(proposal_hash);
@@ -982,6 +1047,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type MaximumBlockLength = MaximumBlockLength;
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
@@ -158,9 +158,10 @@ use sp_runtime::{
 	traits::{Zero, Hash, Dispatchable, Saturating},
 };
 use codec::{Encode, Decode, Input};
+use frame_support::type_info::TypeInfo;
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
-	weights::{Weight, DispatchClass, Pays},
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, DispatchClass, Pays},
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
 		OnUnbalanced, BalanceStatus, schedule::{Named as ScheduleNamed, DispatchTime}, EnsureOrigin
@@ -175,7 +176,7 @@ mod conviction;
 mod types;
 mod default_weight;
 pub use vote_threshold::{Approved, VoteThreshold};
-pub use vote::{Vote, AccountVote, Voting};
+pub use vote::{Vote, AccountVote, Voting, PriorLock};
 pub use conviction::Conviction;
 pub use types::{ReferendumInfo, ReferendumStatus, Tally, UnvoteScope, Delegations};
 
@@ -204,28 +205,97 @@ type NegativeImbalanceOf<T> =
 
 pub trait WeightInfo {
 	fn propose() -> Weight;
+	fn propose_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn second(s: u32, ) -> Weight;
+	fn second_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vote_new(r: u32, ) -> Weight;
+	fn vote_new_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vote_existing(r: u32, ) -> Weight;
+	fn vote_existing_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn emergency_cancel() -> Weight;
+	fn emergency_cancel_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn external_propose(v: u32, ) -> Weight;
+	fn external_propose_proof_size(_v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn external_propose_majority() -> Weight;
+	fn external_propose_majority_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn external_propose_default() -> Weight;
+	fn external_propose_default_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn fast_track() -> Weight;
+	fn fast_track_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn veto_external(v: u32, ) -> Weight;
+	fn veto_external_proof_size(_v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel_referendum() -> Weight;
+	fn cancel_referendum_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel_queued(r: u32, ) -> Weight;
+	fn cancel_queued_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn on_initialize_base(r: u32, ) -> Weight;
+	fn on_initialize_base_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn delegate(r: u32, ) -> Weight;
+	fn delegate_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn undelegate(r: u32, ) -> Weight;
+	fn undelegate_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn clear_public_proposals() -> Weight;
+	fn clear_public_proposals_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn note_preimage(b: u32, ) -> Weight;
+	fn note_preimage_proof_size(_b: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn note_imminent_preimage(b: u32, ) -> Weight;
+	fn note_imminent_preimage_proof_size(_b: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn reap_preimage(b: u32, ) -> Weight;
+	fn reap_preimage_proof_size(_b: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn unlock_remove(r: u32, ) -> Weight;
+	fn unlock_remove_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn unlock_set(r: u32, ) -> Weight;
+	fn unlock_set_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_vote(r: u32, ) -> Weight;
+	fn remove_vote_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_other_vote(r: u32, ) -> Weight;
+	fn remove_other_vote_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: frame_system::Trait + Sized {
@@ -321,7 +391,7 @@ pub trait Trait: frame_system::Trait + Sized {
 	type WeightInfo: WeightInfo;
 }
 
-#[derive(Clone, Encode, Decode, RuntimeDebug)]
+#[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum PreimageStatus<AccountId, Balance, BlockNumber> {
 	/// The preimage is imminently needed at the argument.
 	Missing(BlockNumber),
@@ -348,7 +418,7 @@ impl<AccountId, Balance, BlockNumber> PreimageStatus<AccountId, Balance, BlockNu
 // A value placed in storage that represents the current version of the Democracy storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
 // storage migration logic.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	V1,
 }
@@ -596,7 +666,7 @@ decl_module! {
 		/// - Db reads: `PublicPropCount`, `PublicProps`
 		/// - Db writes: `PublicPropCount`, `PublicProps`, `DepositOf`
 		/// # </weight>
-		#[weight = T::WeightInfo::propose()]
+		#[weight = WithProofSize(T::WeightInfo::propose(), T::WeightInfo::propose_proof_size())]
 		fn propose(origin, proposal_hash: T::Hash, #[compact] value: BalanceOf<T>) {
 			let who = ensure_signed(origin)?;
 			ensure!(value >= T::MinimumDeposit::get(), Error::<T>::ValueLow);
@@ -626,7 +696,10 @@ decl_module! {
 		/// - Db reads: `DepositOf`
 		/// - Db writes: `DepositOf`
 		/// # </weight>
-		#[weight = T::WeightInfo::second(*seconds_upper_bound)]
+		#[weight = WithProofSize(
+			T::WeightInfo::second(*seconds_upper_bound),
+			T::WeightInfo::second_proof_size(*seconds_upper_bound),
+		)]
 		fn second(origin, #[compact] proposal: PropIndex, #[compact] seconds_upper_bound: u32) {
 			let who = ensure_signed(origin)?;
 
@@ -654,8 +727,12 @@ decl_module! {
 		/// - Db reads: `ReferendumInfoOf`, `VotingOf`, `balances locks`
 		/// - Db writes: `ReferendumInfoOf`, `VotingOf`, `balances locks`
 		/// # </weight>
-		#[weight = T::WeightInfo::vote_new(T::MaxVotes::get())
-			.max(T::WeightInfo::vote_existing(T::MaxVotes::get()))]
+		#[weight = WithProofSize(
+			T::WeightInfo::vote_new(T::MaxVotes::get())
+				.max(T::WeightInfo::vote_existing(T::MaxVotes::get())),
+			T::WeightInfo::vote_new_proof_size(T::MaxVotes::get())
+				.max(T::WeightInfo::vote_existing_proof_size(T::MaxVotes::get())),
+		)]
 		fn vote(origin,
 			#[compact] ref_index: ReferendumIndex,
 			vote: AccountVote<BalanceOf<T>>,
@@ -676,7 +753,10 @@ decl_module! {
 		/// - Db reads: `ReferendumInfoOf`, `Cancellations`
 		/// - Db writes: `ReferendumInfoOf`, `Cancellations`
 		/// # </weight>
-		#[weight = (T::WeightInfo::emergency_cancel(), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::emergency_cancel(), DispatchClass::Operational),
+			T::WeightInfo::emergency_cancel_proof_size(),
+		)]
 		fn emergency_cancel(origin, ref_index: ReferendumIndex) {
 			T::CancellationOrigin::ensure_origin(origin)?;
 
@@ -701,7 +781,10 @@ decl_module! {
 		/// - Db reads: `NextExternal`, `Blacklist`
 		/// - Db writes: `NextExternal`
 		/// # </weight>
-		#[weight = T::WeightInfo::external_propose(MAX_VETOERS)]
+		#[weight = WithProofSize(
+			T::WeightInfo::external_propose(MAX_VETOERS),
+			T::WeightInfo::external_propose_proof_size(MAX_VETOERS),
+		)]
 		fn external_propose(origin, proposal_hash: T::Hash) {
 			T::ExternalOrigin::ensure_origin(origin)?;
 			ensure!(!<NextExternal<T>>::exists(), Error::<T>::DuplicateProposal);
@@ -728,7 +811,10 @@ decl_module! {
 		/// - Complexity: `O(1)`
 		/// - Db write: `NextExternal`
 		/// # </weight>
-		#[weight = T::WeightInfo::external_propose_majority()]
+		#[weight = WithProofSize(
+			T::WeightInfo::external_propose_majority(),
+			T::WeightInfo::external_propose_majority_proof_size(),
+		)]
 		fn external_propose_majority(origin, proposal_hash: T::Hash) {
 			T::ExternalMajorityOrigin::ensure_origin(origin)?;
 			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SimpleMajority));
@@ -748,7 +834,10 @@ decl_module! {
 		/// - Complexity: `O(1)`
 		/// - Db write: `NextExternal`
 		/// # </weight>
-		#[weight = T::WeightInfo::external_propose_default()]
+		#[weight = WithProofSize(
+			T::WeightInfo::external_propose_default(),
+			T::WeightInfo::external_propose_default_proof_size(),
+		)]
 		fn external_propose_default(origin, proposal_hash: T::Hash) {
 			T::ExternalDefaultOrigin::ensure_origin(origin)?;
 			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityAgainst));
@@ -774,7 +863,10 @@ decl_module! {
 		/// - Db writes: `NextExternal`, `ReferendumCount`, `ReferendumInfoOf`
 		/// - Base Weight: 30.1 µs
 		/// # </weight>
-		#[weight = T::WeightInfo::fast_track()]
+		#[weight = WithProofSize(
+			T::WeightInfo::fast_track(),
+			T::WeightInfo::fast_track_proof_size(),
+		)]
 		fn fast_track(origin,
 			proposal_hash: T::Hash,
 			voting_period: T::BlockNumber,
@@ -824,7 +916,10 @@ decl_module! {
 		/// - Db reads: `NextExternal`, `Blacklist`
 		/// - Db writes: `NextExternal`, `Blacklist`
 		/// # </weight>
-		#[weight = T::WeightInfo::veto_external(MAX_VETOERS)]
+		#[weight = WithProofSize(
+			T::WeightInfo::veto_external(MAX_VETOERS),
+			T::WeightInfo::veto_external_proof_size(MAX_VETOERS),
+		)]
 		fn veto_external(origin, proposal_hash: T::Hash) {
 			let who = T::VetoOrigin::ensure_origin(origin)?;
 
@@ -858,7 +953,10 @@ decl_module! {
 		/// - Complexity: `O(1)`.
 		/// - Db writes: `ReferendumInfoOf`
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel_referendum()]
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel_referendum(),
+			T::WeightInfo::cancel_referendum_proof_size(),
+		)]
 		fn cancel_referendum(origin, #[compact] ref_index: ReferendumIndex) {
 			ensure_root(origin)?;
 			Self::internal_cancel_referendum(ref_index);
@@ -875,7 +973,10 @@ decl_module! {
 		/// - Db reads: `scheduler lookup`, scheduler agenda`
 		/// - Db writes: `scheduler lookup`, scheduler agenda`
 		/// # </weight>
-		#[weight = (T::WeightInfo::cancel_queued(10), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::cancel_queued(10), DispatchClass::Operational),
+			T::WeightInfo::cancel_queued_proof_size(10),
+		)]
 		fn cancel_queued(origin, which: ReferendumIndex) {
 			ensure_root(origin)?;
 			T::Scheduler::cancel_named((DEMOCRACY_ID, which).encode())
@@ -918,7 +1019,10 @@ decl_module! {
 		// NOTE: weight must cover an incorrect voting of origin with max votes, this is ensure
 		// because a valid delegation cover decoding a direct voting with max votes.
 		/// # </weight>
-		#[weight = T::WeightInfo::delegate(T::MaxVotes::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::delegate(T::MaxVotes::get()),
+			T::WeightInfo::delegate_proof_size(T::MaxVotes::get()),
+		)]
 		pub fn delegate(
 			origin,
 			to: T::AccountId,
@@ -951,7 +1055,10 @@ decl_module! {
 		// NOTE: weight must cover an incorrect voting of origin with max votes, this is ensure
 		// because a valid delegation cover decoding a direct voting with max votes.
 		/// # </weight>
-		#[weight = T::WeightInfo::undelegate(T::MaxVotes::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::undelegate(T::MaxVotes::get().into()),
+			T::WeightInfo::undelegate_proof_size(T::MaxVotes::get().into()),
+		)]
 		fn undelegate(origin) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let votes = Self::try_undelegate(who)?;
@@ -966,7 +1073,10 @@ decl_module! {
 		/// - `O(1)`.
 		/// - Db writes: `PublicProps`
 		/// # </weight>
-		#[weight = T::WeightInfo::clear_public_proposals()]
+		#[weight = WithProofSize(
+			T::WeightInfo::clear_public_proposals(),
+			T::WeightInfo::clear_public_proposals_proof_size(),
+		)]
 		fn clear_public_proposals(origin) {
 			ensure_root(origin)?;
 			<PublicProps<T>>::kill();
@@ -986,15 +1096,18 @@ decl_module! {
 		/// - Db reads: `Preimages`
 		/// - Db writes: `Preimages`
 		/// # </weight>
-		#[weight = T::WeightInfo::note_preimage(encoded_proposal.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::note_preimage(encoded_proposal.len() as u32),
+			T::WeightInfo::note_preimage_proof_size(encoded_proposal.len() as u32),
+		)]
 		fn note_preimage(origin, encoded_proposal: Vec<u8>) {
 			Self::note_preimage_inner(ensure_signed(origin)?, encoded_proposal)?;
 		}
 
 		/// Same as `note_preimage` but origin is `OperationalPreimageOrigin`.
-		#[weight = (
-			T::WeightInfo::note_preimage(encoded_proposal.len() as u32),
-			DispatchClass::Operational,
+		#[weight = WithProofSize(
+			(T::WeightInfo::note_preimage(encoded_proposal.len() as u32), DispatchClass::Operational),
+			T::WeightInfo::note_preimage_proof_size(encoded_proposal.len() as u32),
 		)]
 		fn note_preimage_operational(origin, encoded_proposal: Vec<u8>) {
 			let who = T::OperationalPreimageOrigin::ensure_origin(origin)?;
@@ -1017,7 +1130,10 @@ decl_module! {
 		/// - Db reads: `Preimages`
 		/// - Db writes: `Preimages`
 		/// # </weight>
-		#[weight = T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32),
+			T::WeightInfo::note_imminent_preimage_proof_size(encoded_proposal.len() as u32),
+		)]
 		fn note_imminent_preimage(origin, encoded_proposal: Vec<u8>) -> DispatchResultWithPostInfo {
 			Self::note_imminent_preimage_inner(ensure_signed(origin)?, encoded_proposal)?;
 			// We check that this preimage was not uploaded before in `note_imminent_preimage_inner`,
@@ -1026,9 +1142,9 @@ decl_module! {
 		}
 
 		/// Same as `note_imminent_preimage` but origin is `OperationalPreimageOrigin`.
-		#[weight = (
-			T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32),
-			DispatchClass::Operational,
+		#[weight = WithProofSize(
+			(T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32), DispatchClass::Operational),
+			T::WeightInfo::note_imminent_preimage_proof_size(encoded_proposal.len() as u32),
 		)]
 		fn note_imminent_preimage_operational(origin, encoded_proposal: Vec<u8>) -> DispatchResultWithPostInfo {
 			let who = T::OperationalPreimageOrigin::ensure_origin(origin)?;
@@ -1057,7 +1173,10 @@ decl_module! {
 		/// - Db reads: `Preimages`, provider account data
 		/// - Db writes: `Preimages` provider account data
 		/// # </weight>
-		#[weight = T::WeightInfo::reap_preimage(*proposal_len_upper_bound)]
+		#[weight = WithProofSize(
+			T::WeightInfo::reap_preimage(*proposal_len_upper_bound),
+			T::WeightInfo::reap_preimage_proof_size(*proposal_len_upper_bound),
+		)]
 		fn reap_preimage(origin, proposal_hash: T::Hash, #[compact] proposal_len_upper_bound: u32) {
 			let who = ensure_signed(origin)?;
 
@@ -1095,8 +1214,12 @@ decl_module! {
 		/// - Db reads: `VotingOf`, `balances locks`, `target account`
 		/// - Db writes: `VotingOf`, `balances locks`, `target account`
 		/// # </weight>
-		#[weight = T::WeightInfo::unlock_set(T::MaxVotes::get())
-			.max(T::WeightInfo::unlock_remove(T::MaxVotes::get()))]
+		#[weight = WithProofSize(
+			T::WeightInfo::unlock_set(T::MaxVotes::get())
+				.max(T::WeightInfo::unlock_remove(T::MaxVotes::get())),
+			T::WeightInfo::unlock_set_proof_size(T::MaxVotes::get())
+				.max(T::WeightInfo::unlock_remove_proof_size(T::MaxVotes::get())),
+		)]
 		fn unlock(origin, target: T::AccountId) {
 			ensure_signed(origin)?;
 			Self::update_lock(&target);
@@ -1133,7 +1256,10 @@ decl_module! {
 		/// - Db reads: `ReferendumInfoOf`, `VotingOf`
 		/// - Db writes: `ReferendumInfoOf`, `VotingOf`
 		/// # </weight>
-		#[weight = T::WeightInfo::remove_vote(T::MaxVotes::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_vote(T::MaxVotes::get()),
+			T::WeightInfo::remove_vote_proof_size(T::MaxVotes::get()),
+		)]
 		fn remove_vote(origin, index: ReferendumIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::try_remove_vote(&who, index, UnvoteScope::Any)
@@ -1158,7 +1284,10 @@ decl_module! {
 		/// - Db reads: `ReferendumInfoOf`, `VotingOf`
 		/// - Db writes: `ReferendumInfoOf`, `VotingOf`
 		/// # </weight>
-		#[weight = T::WeightInfo::remove_other_vote(T::MaxVotes::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_other_vote(T::MaxVotes::get()),
+			T::WeightInfo::remove_other_vote_proof_size(T::MaxVotes::get()),
+		)]
 		fn remove_other_vote(origin, target: T::AccountId, index: ReferendumIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let scope = if target == who { UnvoteScope::Any } else { UnvoteScope::OnlyExpired };
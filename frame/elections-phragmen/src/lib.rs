@@ -84,6 +84,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_std::prelude::*;
 use sp_runtime::{
 	DispatchError, RuntimeDebug, Perbill,
@@ -91,7 +92,7 @@ use sp_runtime::{
 };
 use frame_support::{
 	decl_storage, decl_event, ensure, decl_module, decl_error,
-	weights::Weight,
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
 	storage::{StorageMap, IterableStorageMap},
 	dispatch::{DispatchResultWithPostInfo, WithPostDispatchInfo},
 	traits::{
@@ -115,7 +116,7 @@ type NegativeImbalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
 /// An indication that the renouncing account currently has which of the below roles.
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum Renouncing {
 	/// A member is renouncing.
 	Member,
@@ -126,7 +127,7 @@ pub enum Renouncing {
 }
 
 /// Information needed to prove the defunct-ness of a voter.
-#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct DefunctVoter<AccountId> {
 	/// the voter's who's being challenged for being defunct
 	pub who: AccountId,
@@ -140,16 +141,49 @@ pub struct DefunctVoter<AccountId> {
 
 pub trait WeightInfo {
 	fn vote(v: u32, ) -> Weight;
+	fn vote_proof_size(_v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vote_update(v: u32, ) -> Weight;
+	fn vote_update_proof_size(_v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_voter() -> Weight;
+	fn remove_voter_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn report_defunct_voter_correct(c: u32, v: u32, ) -> Weight;
+	fn report_defunct_voter_correct_proof_size(_c: u32, _v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn report_defunct_voter_incorrect(c: u32, v: u32, ) -> Weight;
+	fn report_defunct_voter_incorrect_proof_size(_c: u32, _v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn submit_candidacy(c: u32, ) -> Weight;
+	fn submit_candidacy_proof_size(_c: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn renounce_candidacy_candidate(c: u32, ) -> Weight;
+	fn renounce_candidacy_candidate_proof_size(_c: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn renounce_candidacy_members() -> Weight;
+	fn renounce_candidacy_members_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn renounce_candidacy_runners_up() -> Weight;
+	fn renounce_candidacy_runners_up_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_member_with_replacement() -> Weight;
+	fn remove_member_with_replacement_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_member_wrong_refund() -> Weight;
+	fn remove_member_wrong_refund_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: frame_system::Trait {
@@ -340,7 +374,10 @@ decl_module! {
 		/// 	- Lock
 		/// 	- [AccountBalance(who) (unreserve -- only when creating a new voter)]
 		/// # </weight>
-		#[weight = T::WeightInfo::vote(votes.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::vote(votes.len() as u32),
+			T::WeightInfo::vote_proof_size(votes.len() as u32),
+		)]
 		fn vote(
 			origin,
 			votes: Vec<T::AccountId>,
@@ -396,7 +433,10 @@ decl_module! {
 		/// 	- Locks
 		/// 	- [AccountData(who)]
 		/// # </weight>
-		#[weight = T::WeightInfo::remove_voter()]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_voter(),
+			T::WeightInfo::remove_voter_proof_size(),
+		)]
 		fn remove_voter(origin) {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::is_voter(&who), Error::<T>::MustBeVoter);
@@ -431,9 +471,15 @@ decl_module! {
 		/// 	- Voting(reporter || target)
 		/// Note: the db access is worse with respect to db, which is when the report is correct.
 		/// # </weight>
-		#[weight = T::WeightInfo::report_defunct_voter_correct(
-			defunct.candidate_count,
-			defunct.vote_count,
+		#[weight = WithProofSize(
+			T::WeightInfo::report_defunct_voter_correct(
+				defunct.candidate_count,
+				defunct.vote_count,
+			),
+			T::WeightInfo::report_defunct_voter_correct_proof_size(
+				defunct.candidate_count,
+				defunct.vote_count,
+			),
 		)]
 		fn report_defunct_voter(
 			origin,
@@ -506,7 +552,10 @@ decl_module! {
 		/// 	- [AccountBalance(who)]
 		/// 	- Candidates
 		/// # </weight>
-		#[weight = T::WeightInfo::submit_candidacy(*candidate_count)]
+		#[weight = WithProofSize(
+			T::WeightInfo::submit_candidacy(*candidate_count),
+			T::WeightInfo::submit_candidacy_proof_size(*candidate_count),
+		)]
 		fn submit_candidacy(origin, #[compact] candidate_count: u32) {
 			let who = ensure_signed(origin)?;
 
@@ -568,9 +617,18 @@ decl_module! {
 		/// 		- [AccountData(who) (unreserve)]
 		/// </weight>
 		#[weight =  match *renouncing {
-			Renouncing::Candidate(count) => T::WeightInfo::renounce_candidacy_candidate(count),
-			Renouncing::Member => T::WeightInfo::renounce_candidacy_members(),
-			Renouncing::RunnerUp => T::WeightInfo::renounce_candidacy_runners_up(),
+			Renouncing::Candidate(count) => WithProofSize(
+				T::WeightInfo::renounce_candidacy_candidate(count),
+				T::WeightInfo::renounce_candidacy_candidate_proof_size(count),
+			),
+			Renouncing::Member => WithProofSize(
+				T::WeightInfo::renounce_candidacy_members(),
+				T::WeightInfo::renounce_candidacy_members_proof_size(),
+			),
+			Renouncing::RunnerUp => WithProofSize(
+				T::WeightInfo::renounce_candidacy_runners_up(),
+				T::WeightInfo::renounce_candidacy_runners_up_proof_size(),
+			),
 		}]
 		fn renounce_candidacy(origin, renouncing: Renouncing) {
 			let who = ensure_signed(origin)?;
@@ -631,9 +689,12 @@ decl_module! {
 		/// Else, since this is a root call and will go into phragmen, we assume full block for now.
 		/// # </weight>
 		#[weight = if *has_replacement {
-			T::WeightInfo::remove_member_with_replacement()
+			WithProofSize(
+				T::WeightInfo::remove_member_with_replacement(),
+				T::WeightInfo::remove_member_with_replacement_proof_size(),
+			)
 		} else {
-			T::MaximumBlockWeight::get()
+			WithProofSize(T::MaximumBlockWeight::get(), T::MaximumBlockProofSize::get())
 		}]
 		fn remove_member(
 			origin,
@@ -1110,6 +1171,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type MaximumBlockLength = MaximumBlockLength;
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
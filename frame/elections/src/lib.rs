@@ -45,6 +45,7 @@ use frame_support::{
 	}
 };
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use frame_system::{ensure_signed, ensure_root};
 
 mod mock;
@@ -108,7 +109,7 @@ mod tests;
 // entries before they increase the capacity.
 
 /// The activity status of a voter.
-#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct VoterInfo<Balance> {
 	/// Last VoteIndex in which this voter assigned (or initialized) approvals.
 	last_active: VoteIndex,
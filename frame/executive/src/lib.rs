@@ -112,13 +112,27 @@
 //!
 //! pub type Executive = executive::Executive<Runtime, Block, Context, Runtime, AllModules, CustomOnRuntimeUpgrade>;
 //! ```
+//!
+//! ### Multi-block migrations
+//!
+//! Migrations which are too heavy for a single block can be given as the last generic parameter,
+//! as a tuple of [`SteppedMigration`](../frame_support/traits/trait.SteppedMigration.html). They
+//! are started after a runtime upgrade, once all `OnRuntimeUpgrade` logic has been executed, and
+//! are stepped right after `on_initialize` in every block, using the weight available to normal
+//! dispatches, until they are complete.
+//!
+//! While the migrations are ongoing, transactions of the `Normal` dispatch class are rejected
+//! with `InvalidTransaction::ExhaustsResources` and `on_idle` is not called. Inherents and
+//! operational transactions are still applied.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::{prelude::*, marker::PhantomData};
 use frame_support::{
 	storage::StorageValue, weights::{GetDispatchInfo, DispatchInfo, DispatchClass},
-	traits::{OnInitialize, OnFinalize, OnRuntimeUpgrade, OffchainWorker},
+	traits::{
+		Get, OnInitialize, OnIdle, OnFinalize, OnRuntimeUpgrade, OffchainWorker, SteppedMigration,
+	},
 	dispatch::PostDispatchInfo,
 };
 use sp_runtime::{
@@ -127,7 +141,8 @@ use sp_runtime::{
 		self, Header, Zero, One, Checkable, Applyable, CheckEqual, ValidateUnsigned, NumberFor,
 		Block as BlockT, Dispatchable, Saturating,
 	},
-	transaction_validity::{TransactionValidity, TransactionSource},
+	transaction_validity::{TransactionValidity, TransactionSource, InvalidTransaction},
+	Perbill,
 };
 use codec::{Codec, Encode};
 use frame_system::{extrinsics_root, DigestOf};
@@ -153,8 +168,18 @@ pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::Origin;
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///                       already called by `AllModules`. It will be called before all modules will
 ///                       be called.
-pub struct Executive<System, Block, Context, UnsignedValidator, AllModules, OnRuntimeUpgrade = ()>(
-	PhantomData<(System, Block, Context, UnsignedValidator, AllModules, OnRuntimeUpgrade)>
+/// - `Migrations`: Migrations that are started after a runtime upgrade and executed over as many
+///                 blocks as needed. No normal transactions are accepted while they are ongoing.
+pub struct Executive<
+	System,
+	Block,
+	Context,
+	UnsignedValidator,
+	AllModules,
+	OnRuntimeUpgrade = (),
+	Migrations = (),
+>(
+	PhantomData<(System, Block, Context, UnsignedValidator, AllModules, OnRuntimeUpgrade, Migrations)>
 );
 
 impl<
@@ -165,11 +190,13 @@ impl<
 	AllModules:
 		OnRuntimeUpgrade +
 		OnInitialize<System::BlockNumber> +
+		OnIdle<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
 	COnRuntimeUpgrade: OnRuntimeUpgrade,
+	Migrations: SteppedMigration,
 > ExecuteBlock<Block> for
-	Executive<System, Block, Context, UnsignedValidator, AllModules, COnRuntimeUpgrade>
+	Executive<System, Block, Context, UnsignedValidator, AllModules, COnRuntimeUpgrade, Migrations>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>:
@@ -180,7 +207,15 @@ where
 	UnsignedValidator: ValidateUnsigned<Call=CallOf<Block::Extrinsic, Context>>,
 {
 	fn execute_block(block: Block) {
-		Executive::<System, Block, Context, UnsignedValidator, AllModules>::execute_block(block);
+		Executive::<
+			System,
+			Block,
+			Context,
+			UnsignedValidator,
+			AllModules,
+			COnRuntimeUpgrade,
+			Migrations,
+		>::execute_block(block);
 	}
 }
 
@@ -192,10 +227,12 @@ impl<
 	AllModules:
 		OnRuntimeUpgrade +
 		OnInitialize<System::BlockNumber> +
+		OnIdle<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
 	COnRuntimeUpgrade: OnRuntimeUpgrade,
-> Executive<System, Block, Context, UnsignedValidator, AllModules, COnRuntimeUpgrade>
+	Migrations: SteppedMigration,
+> Executive<System, Block, Context, UnsignedValidator, AllModules, COnRuntimeUpgrade, Migrations>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>:
@@ -205,6 +242,37 @@ where
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call=CallOf<Block::Extrinsic, Context>>,
 {
+	/// Execute all `OnRuntimeUpgrade` of this runtime, and return the aggregate weight.
+	pub fn execute_on_runtime_upgrade() -> frame_support::weights::Weight {
+		// System is not part of `AllModules`, so we need to call this manually.
+		let mut weight = <frame_system::Module::<System> as OnRuntimeUpgrade>::on_runtime_upgrade();
+		weight = weight.saturating_add(COnRuntimeUpgrade::on_runtime_upgrade());
+		weight = weight.saturating_add(<AllModules as OnRuntimeUpgrade>::on_runtime_upgrade());
+		weight
+	}
+
+	/// Execute all `OnRuntimeUpgrade` of this runtime, including the pre and post migration checks.
+	///
+	/// This should only be used for testing, as the checks are never executed on-chain.
+	#[cfg(feature = "try-runtime")]
+	pub fn try_runtime_upgrade() -> Result<frame_support::weights::Weight, &'static str> {
+		<
+			(frame_system::Module::<System>, COnRuntimeUpgrade, AllModules)
+			as
+			OnRuntimeUpgrade
+		>::pre_upgrade()?;
+
+		let weight = Self::execute_on_runtime_upgrade();
+
+		<
+			(frame_system::Module::<System>, COnRuntimeUpgrade, AllModules)
+			as
+			OnRuntimeUpgrade
+		>::post_upgrade()?;
+
+		Ok(weight)
+	}
+
 	/// Start the execution of a particular block.
 	pub fn initialize_block(header: &System::Header) {
 		sp_io::init_tracing();
@@ -235,11 +303,10 @@ where
 		digest: &Digest<System::Hash>,
 	) {
 		if Self::runtime_upgraded() {
-			// System is not part of `AllModules`, so we need to call this manually.
-			let mut weight = <frame_system::Module::<System> as OnRuntimeUpgrade>::on_runtime_upgrade();
-			weight = weight.saturating_add(COnRuntimeUpgrade::on_runtime_upgrade());
-			weight = weight.saturating_add(<AllModules as OnRuntimeUpgrade>::on_runtime_upgrade());
+			let weight = Self::execute_on_runtime_upgrade();
 			<frame_system::Module<System>>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+			// (Re)start the multi-block migrations from scratch.
+			frame_system::MigrationCursor::put(Option::<Vec<u8>>::None);
 		}
 		<frame_system::Module<System>>::initialize(
 			block_number,
@@ -253,9 +320,49 @@ where
 			.saturating_add(<System::BlockExecutionWeight as frame_support::traits::Get<_>>::get());
 		<frame_system::Module::<System>>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
 
+		Self::step_migrations();
+
 		frame_system::Module::<System>::note_finished_initialize();
 	}
 
+	/// Step the ongoing multi-block migrations, if any, with the weight left to normal dispatches.
+	fn step_migrations() {
+		let mut cursor = match frame_system::MigrationCursor::get() {
+			Some(cursor) => cursor,
+			None => return,
+		};
+
+		let max_weight = <System::MaximumBlockWeight as Get<_>>::get();
+		let limit = <System::AvailableBlockRatio as Get<Perbill>>::get() * max_weight;
+		let mut remaining_weight = limit
+			.saturating_sub(<frame_system::Module<System>>::block_weight().total());
+
+		loop {
+			let (next, used_weight) = Migrations::step(cursor.clone(), remaining_weight);
+			<frame_system::Module<System>>::register_extra_weight_unchecked(
+				used_weight,
+				DispatchClass::Mandatory,
+			);
+			remaining_weight = remaining_weight.saturating_sub(used_weight);
+
+			let stalled = next == cursor;
+			match next {
+				None => {
+					frame_system::MigrationCursor::kill();
+					return;
+				},
+				Some(_) => cursor = next,
+			}
+
+			// Continue in the next block if the block is full or no progress was made.
+			if stalled || remaining_weight.is_zero() {
+				break;
+			}
+		}
+
+		frame_system::MigrationCursor::put(cursor);
+	}
+
 	/// Returns if the runtime was upgraded since the last time this function was called.
 	fn runtime_upgraded() -> bool {
 		let last = frame_system::LastRuntimeUpgrade::get();
@@ -315,14 +422,38 @@ where
 		} };
 	}
 
+	/// Execute `block` like `execute_block`, but without checking the state root, and return the
+	/// weight it consumed.
+	///
+	/// This allows to execute an existing block with a runtime other than the one it was authored
+	/// with, which runs its runtime upgrade first. This should only be used for testing.
+	#[cfg(feature = "try-runtime")]
+	pub fn execute_block_no_check(block: Block) -> frame_support::weights::Weight {
+		Self::initialize_block(block.header());
+		Self::initial_checks(&block);
+
+		let signature_batching = sp_runtime::SignatureBatching::start();
+
+		let (header, extrinsics) = block.deconstruct();
+		Self::execute_extrinsics_with_book_keeping(extrinsics, *header.number());
+
+		if !signature_batching.verify() {
+			panic!("Signature verification failed.");
+		}
+
+		let weight = <frame_system::Module<System>>::block_weight().total();
+		let new_header = <frame_system::Module<System>>::finalize();
+		Self::check_digest(&header, &new_header);
+		weight
+	}
+
 	/// Execute given extrinsics and take care of post-extrinsics book-keeping.
 	fn execute_extrinsics_with_book_keeping(extrinsics: Vec<Block::Extrinsic>, block_number: NumberFor<Block>) {
 		extrinsics.into_iter().for_each(Self::apply_extrinsic_no_note);
 
 		// post-extrinsics book-keeping
 		<frame_system::Module<System>>::note_finished_extrinsics();
-		<frame_system::Module<System> as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
-		<AllModules as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
+		Self::idle_and_finalize_hook(block_number);
 	}
 
 	/// Finalize the block - it is up the caller to ensure that all header fields are valid
@@ -332,14 +463,35 @@ where
 		sp_tracing::enter_span!( sp_tracing::Level::TRACE, "finalize_block" );
 		<frame_system::Module<System>>::note_finished_extrinsics();
 		let block_number = <frame_system::Module<System>>::block_number();
-		<frame_system::Module<System> as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
-		<AllModules as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
+		Self::idle_and_finalize_hook(block_number);
 
 		// set up extrinsics
 		<frame_system::Module<System>>::derive_extrinsics();
 		<frame_system::Module<System>>::finalize()
 	}
 
+	/// Call `on_idle` with the weight left in the block, unless migrations are ongoing, and then
+	/// `on_finalize`.
+	fn idle_and_finalize_hook(block_number: System::BlockNumber) {
+		let max_weight = <System::MaximumBlockWeight as Get<_>>::get();
+		let remaining_weight = max_weight
+			.saturating_sub(<frame_system::Module<System>>::block_weight().total());
+
+		if !remaining_weight.is_zero() && !<frame_system::Module<System>>::migrations_ongoing() {
+			let used_weight = <AllModules as OnIdle<System::BlockNumber>>::on_idle(
+				block_number,
+				remaining_weight,
+			);
+			<frame_system::Module<System>>::register_extra_weight_unchecked(
+				used_weight,
+				DispatchClass::Mandatory,
+			);
+		}
+
+		<frame_system::Module<System> as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
+		<AllModules as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
+	}
+
 	/// Apply extrinsic outside of the block execution function.
 	///
 	/// This doesn't attempt to validate anything regarding the block, but it builds a list of uxt
@@ -373,6 +525,9 @@ where
 		// Verify that the signature is good.
 		let xt = uxt.check(&Default::default())?;
 
+		let dispatch_info = xt.get_dispatch_info();
+		Self::ensure_not_paused(&dispatch_info)?;
+
 		// We don't need to make sure to `note_extrinsic` only after we know it's going to be
 		// executed to prevent it from leaking in storage since at this point, it will either
 		// execute or panic (and revert storage changes).
@@ -383,7 +538,6 @@ where
 		// AUDIT: Under no circumstances may this function panic from here onwards.
 
 		// Decode parameters and dispatch
-		let dispatch_info = xt.get_dispatch_info();
 		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 		<frame_system::Module<System>>::note_applied_extrinsic(&r, dispatch_info);
@@ -391,12 +545,30 @@ where
 		Ok(r.map(|_| ()).map_err(|e| e.error))
 	}
 
+	/// Reject normal transactions while multi-block migrations are ongoing.
+	fn ensure_not_paused(info: &DispatchInfo) -> Result<(), InvalidTransaction> {
+		if info.class == DispatchClass::Normal && <frame_system::Module<System>>::migrations_ongoing() {
+			Err(InvalidTransaction::ExhaustsResources)
+		} else {
+			Ok(())
+		}
+	}
+
 	fn final_checks(header: &System::Header) {
 		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "final_checks");
 		// remove temporaries
 		let new_header = <frame_system::Module<System>>::finalize();
 
-		// check digest
+		Self::check_digest(header, &new_header);
+
+		// check storage root.
+		let storage_root = new_header.state_root();
+		header.state_root().check_equal(&storage_root);
+		assert!(header.state_root() == storage_root, "Storage root must match that calculated.");
+	}
+
+	/// Check that the digest of `header` matches the one calculated in `new_header`.
+	fn check_digest(header: &System::Header, new_header: &System::Header) {
 		assert_eq!(
 			header.digest().logs().len(),
 			new_header.digest().logs().len(),
@@ -407,11 +579,6 @@ where
 			header_item.check_equal(&computed_item);
 			assert!(header_item == computed_item, "Digest item must match that calculated.");
 		}
-
-		// check storage root.
-		let storage_root = new_header.state_root();
-		header.state_root().check_equal(&storage_root);
-		assert!(header.state_root() == storage_root, "Storage root must match that calculated.");
 	}
 
 	/// Check a given signed transaction for validity. This doesn't execute any
@@ -439,6 +606,8 @@ where
 			xt.get_dispatch_info()
 		};
 
+		Self::ensure_not_paused(&dispatch_info)?;
+
 		within_span! {
 			sp_tracing::Level::TRACE, "validate";
 			xt.validate::<UnsignedValidator>(source, &dispatch_info, encoded_len)
@@ -479,17 +648,19 @@ mod tests {
 	use super::*;
 	use sp_core::H256;
 	use sp_runtime::{
-		generic::Era, Perbill, DispatchError, testing::{Digest, Header, Block},
+		generic::Era, Perbill, DispatchError,
+		testing::{Digest, Header, Block, TestSignature, UintAuthorityId},
 		traits::{Header as HeaderT, BlakeTwo256, IdentityLookup},
 		transaction_validity::{InvalidTransaction, UnknownTransaction, TransactionValidityError},
 	};
 	use frame_support::{
 		impl_outer_event, impl_outer_origin, parameter_types, impl_outer_dispatch,
-		weights::{Weight, RuntimeDbWeight, IdentityFee, WeightToFeePolynomial},
+		weights::{Weight, ProofSize, RuntimeDbWeight, IdentityFee, WeightToFeePolynomial},
 		traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons, WithdrawReason},
 	};
 	use frame_system::{self as system, Call as SystemCall, ChainContext, LastRuntimeUpgradeInfo};
 	use pallet_balances::Call as BalancesCall;
+	use codec::Decode;
 	use hex_literal::hex;
 	const TEST_KEY: &[u8] = &*b":test:key:";
 
@@ -521,6 +692,12 @@ mod tests {
 					175
 				}
 
+				fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+					println!("on_idle({}, {})", n, remaining_weight);
+					super::IDLE_WEIGHT.with(|w| *w.borrow_mut() = Some(remaining_weight));
+					0
+				}
+
 				fn on_finalize() {
 					println!("on_finalize(?)");
 				}
@@ -562,6 +739,7 @@ mod tests {
 		pub const BlockHashCount: u64 = 250;
 		pub const MaximumBlockWeight: Weight = 1024;
 		pub const MaximumBlockLength: u32 = 2 * 1024;
+		pub const MaximumBlockProofSize: ProofSize = 1024 * 1024;
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 		pub const BlockExecutionWeight: Weight = 10;
 		pub const ExtrinsicBaseWeight: Weight = 5;
@@ -588,6 +766,9 @@ mod tests {
 		type BlockExecutionWeight = BlockExecutionWeight;
 		type ExtrinsicBaseWeight = ExtrinsicBaseWeight;
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = MaximumBlockProofSize;
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = RuntimeVersion;
@@ -621,6 +802,9 @@ mod tests {
 		type TransactionByteFee = TransactionByteFee;
 		type WeightToFee = IdentityFee<Balance>;
 		type FeeMultiplierUpdate = ();
+		type SponsorSignature = TestSignature;
+		type SponsorPublic = UintAuthorityId;
+		type WeightInfo = ();
 	}
 	impl custom::Trait for Runtime {}
 
@@ -652,6 +836,7 @@ mod tests {
 	thread_local! {
 		pub static RUNTIME_VERSION: std::cell::RefCell<sp_version::RuntimeVersion> =
 			Default::default();
+		pub static IDLE_WEIGHT: std::cell::RefCell<Option<Weight>> = Default::default();
 	}
 
 	type SignedExtra = (
@@ -673,6 +858,24 @@ mod tests {
 			sp_io::storage::set(CUSTOM_ON_RUNTIME_KEY, &true.encode());
 			0
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				sp_io::storage::get(CUSTOM_ON_RUNTIME_KEY).is_none(),
+				"custom upgrade already executed",
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				sp_io::storage::get(CUSTOM_ON_RUNTIME_KEY) == Some(true.encode()),
+				"custom upgrade not executed",
+			);
+			Ok(())
+		}
 	}
 
 	type Executive = super::Executive<
@@ -684,6 +887,43 @@ mod tests {
 		CustomOnRuntimeUpgrade
 	>;
 
+	// Migrates `MIGRATION_ITEMS` items, each step using `MIGRATION_ITEM_WEIGHT` per item.
+	const MIGRATION_ITEMS: u32 = 5;
+	const MIGRATION_ITEM_WEIGHT: Weight = 300;
+
+	fn migrated_key(item: u32) -> Vec<u8> {
+		(b":migrated:", item).encode()
+	}
+
+	struct TestMigration;
+	impl SteppedMigration for TestMigration {
+		fn step(cursor: Option<Vec<u8>>, limit: Weight) -> (Option<Vec<u8>>, Weight) {
+			let mut next = cursor.map(|c| u32::decode(&mut &c[..]).unwrap()).unwrap_or(0);
+			let mut weight = 0;
+			while next < MIGRATION_ITEMS && weight + MIGRATION_ITEM_WEIGHT <= limit {
+				sp_io::storage::set(&migrated_key(next), &[1]);
+				next += 1;
+				weight += MIGRATION_ITEM_WEIGHT;
+			}
+
+			if next == MIGRATION_ITEMS {
+				(None, weight)
+			} else {
+				(Some(next.encode()), weight)
+			}
+		}
+	}
+
+	type MigratingExecutive = super::Executive<
+		Runtime,
+		Block<TestXt>,
+		ChainContext<Runtime>,
+		Runtime,
+		AllModules,
+		CustomOnRuntimeUpgrade,
+		(TestMigration,),
+	>;
+
 	fn extra(nonce: u64, fee: Balance) -> SignedExtra {
 		(
 			frame_system::CheckEra::from(Era::Immortal),
@@ -764,6 +1004,24 @@ mod tests {
 		});
 	}
 
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn block_import_without_checks_ignores_the_state_root() {
+		new_test_ext(1).execute_with(|| {
+			let weight = Executive::execute_block_no_check(Block {
+				header: Header {
+					parent_hash: [69u8; 32].into(),
+					number: 1,
+					state_root: [0u8; 32].into(),
+					extrinsics_root: hex!("03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314").into(),
+					digest: Digest { logs: vec![], },
+				},
+				extrinsics: vec![],
+			});
+			assert!(weight > 0);
+		});
+	}
+
 	#[test]
 	#[should_panic]
 	fn block_import_of_bad_extrinsic_root_fails() {
@@ -1058,4 +1316,79 @@ mod tests {
 			assert_eq!(sp_io::storage::get(CUSTOM_ON_RUNTIME_KEY).unwrap(), true.encode());
 		});
 	}
+
+	#[test]
+	fn on_idle_is_called_with_remaining_weight() {
+		new_test_ext(1).execute_with(|| {
+			IDLE_WEIGHT.with(|w| *w.borrow_mut() = None);
+			Executive::initialize_block(&Header::new_from_number(1));
+			Executive::finalize_block();
+
+			// Everything but the block execution and `on_initialize` weight is left.
+			assert_eq!(IDLE_WEIGHT.with(|w| *w.borrow()), Some(1024 - 175 - 10));
+		});
+	}
+
+	#[test]
+	fn multi_block_migrations_work() {
+		let xt = TestXt::new(Call::Balances(BalancesCall::transfer(33, 0)), sign_extra(1, 0, 0));
+		let migrated = || (0..MIGRATION_ITEMS)
+			.filter(|i| sp_io::storage::get(&migrated_key(*i)).is_some())
+			.count();
+
+		new_test_ext(1).execute_with(|| {
+			RUNTIME_VERSION.with(|v| *v.borrow_mut() = sp_version::RuntimeVersion {
+				spec_version: 1,
+				..Default::default()
+			});
+			IDLE_WEIGHT.with(|w| *w.borrow_mut() = None);
+
+			// 839 weight is left after `on_initialize`, enough for two items per block.
+			MigratingExecutive::initialize_block(&Header::new_from_number(1));
+			assert_eq!(migrated(), 2);
+			assert!(System::migrations_ongoing());
+			assert_eq!(
+				<frame_system::Module<Runtime>>::block_weight().total(),
+				175 + 10 + 2 * MIGRATION_ITEM_WEIGHT,
+			);
+
+			// Normal transactions are paused.
+			assert_eq!(
+				MigratingExecutive::validate_transaction(TransactionSource::External, xt.clone()),
+				Err(InvalidTransaction::ExhaustsResources.into()),
+			);
+			assert_eq!(
+				MigratingExecutive::apply_extrinsic(xt.clone()),
+				Err(InvalidTransaction::ExhaustsResources.into()),
+			);
+
+			// `on_idle` is not called while migrating.
+			MigratingExecutive::finalize_block();
+			assert_eq!(IDLE_WEIGHT.with(|w| *w.borrow()), None);
+
+			MigratingExecutive::initialize_block(&Header::new_from_number(2));
+			assert_eq!(migrated(), 4);
+			assert!(System::migrations_ongoing());
+			MigratingExecutive::finalize_block();
+
+			MigratingExecutive::initialize_block(&Header::new_from_number(3));
+			assert_eq!(migrated(), 5);
+			assert!(!System::migrations_ongoing());
+			assert!(MigratingExecutive::apply_extrinsic(xt).unwrap().is_ok());
+			MigratingExecutive::finalize_block();
+			assert!(IDLE_WEIGHT.with(|w| w.borrow().is_some()));
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_runtime_upgrade_runs_checks() {
+		new_test_ext(1).execute_with(|| {
+			assert!(Executive::try_runtime_upgrade().is_ok());
+			assert_eq!(sp_io::storage::get(CUSTOM_ON_RUNTIME_KEY).unwrap(), true.encode());
+
+			// The custom upgrade refuses to be executed twice.
+			assert_eq!(Executive::try_runtime_upgrade(), Err("custom upgrade already executed"));
+		});
+	}
 }
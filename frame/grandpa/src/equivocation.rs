@@ -40,6 +40,7 @@
 use sp_std::prelude::*;
 
 use codec::{self as codec, Decode, Encode};
+use frame_support::type_info::TypeInfo;
 use frame_support::{debug, traits::KeyOwnerProofSystem};
 use sp_finality_grandpa::{EquivocationProof, RoundNumber, SetId};
 use sp_runtime::{
@@ -175,7 +176,7 @@ where
 }
 
 /// A round number and set id which point on the time of an offence.
-#[derive(Copy, Clone, PartialOrd, Ord, Eq, PartialEq, Encode, Decode)]
+#[derive(Copy, Clone, PartialOrd, Ord, Eq, PartialEq, Encode, Decode, TypeInfo)]
 pub struct GrandpaTimeSlot {
 	// The order of these matters for `derive(Ord)`.
 	/// Grandpa Set ID.
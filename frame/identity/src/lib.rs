@@ -76,13 +76,14 @@ use sp_std::prelude::*;
 use sp_std::{fmt::Debug, ops::Add, iter::once};
 use enumflags2::BitFlags;
 use codec::{Encode, Decode};
+use frame_support::type_info::{TypeInfo, Type, TypeDef, Path, Field, Variant, MetaType};
 use sp_runtime::{DispatchError, RuntimeDebug, DispatchResult};
 use sp_runtime::traits::{StaticLookup, Zero, AppendZerosInput, Saturating};
 use frame_support::{
 	decl_module, decl_event, decl_storage, ensure, decl_error,
 	dispatch::DispatchResultWithPostInfo,
 	traits::{Currency, ReservableCurrency, OnUnbalanced, Get, BalanceStatus, EnsureOrigin},
-	weights::Weight,
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
 };
 use frame_system::ensure_signed;
 
@@ -96,21 +97,69 @@ type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_sy
 
 pub trait WeightInfo {
 	fn add_registrar(r: u32, ) -> Weight;
+	fn add_registrar_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_identity(r: u32, x: u32, ) -> Weight;
+	fn set_identity_proof_size(_r: u32, _x: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_subs_new(s: u32, ) -> Weight;
+	fn set_subs_new_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_subs_old(p: u32, ) -> Weight;
+	fn set_subs_old_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn add_sub(p: u32, ) -> Weight;
+	fn add_sub_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn rename_sub(p: u32, ) -> Weight;
+	fn rename_sub_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_sub(p: u32, ) -> Weight;
+	fn remove_sub_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn quit_sub(p: u32, ) -> Weight;
+	fn quit_sub_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn clear_identity(r: u32, s: u32, x: u32, ) -> Weight;
+	fn clear_identity_proof_size(_r: u32, _s: u32, _x: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn request_judgement(r: u32, x: u32, ) -> Weight;
+	fn request_judgement_proof_size(_r: u32, _x: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel_request(r: u32, x: u32, ) -> Weight;
+	fn cancel_request_proof_size(_r: u32, _x: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_fee(r: u32, ) -> Weight;
+	fn set_fee_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_account_id(r: u32, ) -> Weight;
+	fn set_account_id_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_fields(r: u32, ) -> Weight;
+	fn set_fields_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn provide_judgement(r: u32, x: u32, ) -> Weight;
+	fn provide_judgement_proof_size(_r: u32, _x: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn kill_identity(r: u32, s: u32, x: u32, ) -> Weight;
+	fn kill_identity_proof_size(_r: u32, _s: u32, _x: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: frame_system::Trait {
@@ -217,6 +266,42 @@ impl Encode for Data {
 }
 impl codec::EncodeLike for Data {}
 
+impl TypeInfo for Data {
+	fn type_info() -> Type {
+		// Raw data is encoded without a length prefix, the length is given by the variant index.
+		macro_rules! raw_variants {
+			( $( $len:literal ),* ) => {
+				vec![ $(
+					Variant::new(
+						concat!("Raw", $len),
+						vec![Field::unnamed(MetaType::new::<[u8; $len]>(), concat!("[u8; ", $len, "]"))],
+						$len + 1,
+					),
+				)* ]
+			}
+		}
+		let hash = |name, index| Variant::new(
+			name,
+			vec![Field::unnamed(MetaType::new::<[u8; 32]>(), "[u8; 32]")],
+			index,
+		);
+
+		let mut variants = vec![Variant::new("None", Vec::new(), 0)];
+		variants.extend(raw_variants!(
+			0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+			17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+		));
+		variants.extend(vec![
+			hash("BlakeTwo256", 34),
+			hash("Sha256", 35),
+			hash("Keccak256", 36),
+			hash("ShaThree256", 37),
+		]);
+
+		Type::new(Path::new("Data", module_path!()), TypeDef::Variant(variants))
+	}
+}
+
 impl Default for Data {
 	fn default() -> Self {
 		Self::None
@@ -230,7 +315,7 @@ pub type RegistrarIndex = u32;
 ///
 /// NOTE: Registrars may pay little attention to some fields. Registrars may want to make clear
 /// which fields their attestation is relevant for by off-chain means.
-#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub enum Judgement<
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq
 > {
@@ -303,6 +388,16 @@ impl Encode for IdentityFields {
 		self.0.bits().using_encoded(f)
 	}
 }
+impl TypeInfo for IdentityFields {
+	fn type_info() -> Type {
+		Type::new(
+			Path::new("IdentityFields", module_path!()),
+			TypeDef::Composite(vec![
+				Field::unnamed(MetaType::new::<u64>(), "BitFlags<IdentityField>"),
+			]),
+		)
+	}
+}
 impl Decode for IdentityFields {
 	fn decode<I: codec::Input>(input: &mut I) -> sp_std::result::Result<Self, codec::Error> {
 		let field = u64::decode(input)?;
@@ -314,7 +409,7 @@ impl Decode for IdentityFields {
 ///
 /// NOTE: This should be stored at the end of the storage item to facilitate the addition of extra
 /// fields in a backwards compatible way through a specialized `Decode` impl.
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(test, derive(Default))]
 pub struct IdentityInfo {
 	/// Additional fields of the identity that are not catered for with the struct's explicit
@@ -366,7 +461,7 @@ pub struct IdentityInfo {
 ///
 /// NOTE: This is stored separately primarily to facilitate the addition of extra fields in a
 /// backwards compatible way through a specialized `Decode` impl.
-#[derive(Clone, Encode, Eq, PartialEq, RuntimeDebug)]
+#[derive(Clone, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct Registration<
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq
 > {
@@ -401,7 +496,7 @@ impl<
 }
 
 /// Information concerning a registrar.
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct RegistrarInfo<
 	Balance: Encode + Decode + Clone + Debug + Eq + PartialEq,
 	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq
@@ -553,7 +648,10 @@ decl_module! {
 		/// - One storage mutation (codec `O(R)`).
 		/// - One event.
 		/// # </weight>
-		#[weight = T::WeightInfo::add_registrar(T::MaxRegistrars::get()) ]
+		#[weight = WithProofSize(
+			T::WeightInfo::add_registrar(T::MaxRegistrars::get()),
+			T::WeightInfo::add_registrar_proof_size(T::MaxRegistrars::get()),
+		)]
 		fn add_registrar(origin, account: T::AccountId) -> DispatchResultWithPostInfo {
 			T::RegistrarOrigin::ensure_origin(origin)?;
 
@@ -591,9 +689,15 @@ decl_module! {
 		/// - One storage mutation (codec-read `O(X' + R)`, codec-write `O(X + R)`).
 		/// - One event.
 		/// # </weight>
-		#[weight =  T::WeightInfo::set_identity(
-			T::MaxRegistrars::get().into(), // R
-			T::MaxAdditionalFields::get().into(), // X
+		#[weight = WithProofSize(
+			T::WeightInfo::set_identity(
+				T::MaxRegistrars::get().into(), // R
+				T::MaxAdditionalFields::get().into(), // X
+			),
+			T::WeightInfo::set_identity_proof_size(
+				T::MaxRegistrars::get().into(),
+				T::MaxAdditionalFields::get().into(),
+			),
 		)]
 		fn set_identity(origin, info: IdentityInfo) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
@@ -657,9 +761,12 @@ decl_module! {
 		// N storage items for N sub accounts. Right now the weight on this function
 		// is a large overestimate due to the fact that it could potentially write
 		// to 2 x T::MaxSubAccounts::get().
-		#[weight = T::WeightInfo::set_subs_old(T::MaxSubAccounts::get()) // P: Assume max sub accounts removed.
-			.saturating_add(T::WeightInfo::set_subs_new(subs.len() as u32)) // S: Assume all subs are new.
-		]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_subs_old(T::MaxSubAccounts::get()) // P: Assume max sub accounts removed.
+				.saturating_add(T::WeightInfo::set_subs_new(subs.len() as u32)), // S: Assume all subs are new.
+			T::WeightInfo::set_subs_old_proof_size(T::MaxSubAccounts::get())
+				.saturating_add(T::WeightInfo::set_subs_new_proof_size(subs.len() as u32)),
+		)]
 		fn set_subs(origin, subs: Vec<(T::AccountId, Data)>) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 			ensure!(<IdentityOf<T>>::contains_key(&sender), Error::<T>::NotFound);
@@ -717,10 +824,17 @@ decl_module! {
 		/// - `2` storage reads and `S + 2` storage deletions.
 		/// - One event.
 		/// # </weight>
-		#[weight = T::WeightInfo::clear_identity(
-			T::MaxRegistrars::get().into(), // R
-			T::MaxSubAccounts::get().into(), // S
-			T::MaxAdditionalFields::get().into(), // X
+		#[weight = WithProofSize(
+			T::WeightInfo::clear_identity(
+				T::MaxRegistrars::get().into(), // R
+				T::MaxSubAccounts::get().into(), // S
+				T::MaxAdditionalFields::get().into(), // X
+			),
+			T::WeightInfo::clear_identity_proof_size(
+				T::MaxRegistrars::get().into(),
+				T::MaxSubAccounts::get().into(),
+				T::MaxAdditionalFields::get().into(),
+			),
 		)]
 		fn clear_identity(origin) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
@@ -766,9 +880,15 @@ decl_module! {
 		/// - Storage: 1 read `O(R)`, 1 mutate `O(X + R)`.
 		/// - One event.
 		/// # </weight>
-		#[weight = T::WeightInfo::request_judgement(
-			T::MaxRegistrars::get().into(), // R
-			T::MaxAdditionalFields::get().into(), // X
+		#[weight = WithProofSize(
+			T::WeightInfo::request_judgement(
+				T::MaxRegistrars::get().into(), // R
+				T::MaxAdditionalFields::get().into(), // X
+			),
+			T::WeightInfo::request_judgement_proof_size(
+				T::MaxRegistrars::get().into(),
+				T::MaxAdditionalFields::get().into(),
+			),
 		)]
 		fn request_judgement(origin,
 			#[compact] reg_index: RegistrarIndex,
@@ -822,9 +942,15 @@ decl_module! {
 		/// - One storage mutation `O(R + X)`.
 		/// - One event
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel_request(
-			T::MaxRegistrars::get().into(), // R
-			T::MaxAdditionalFields::get().into(), // X
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel_request(
+				T::MaxRegistrars::get().into(), // R
+				T::MaxAdditionalFields::get().into(), // X
+			),
+			T::WeightInfo::cancel_request_proof_size(
+				T::MaxRegistrars::get().into(),
+				T::MaxAdditionalFields::get().into(),
+			),
 		)]
 		fn cancel_request(origin, reg_index: RegistrarIndex) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
@@ -864,7 +990,10 @@ decl_module! {
 		/// - One storage mutation `O(R)`.
 		/// - Benchmark: 7.315 + R * 0.329 µs (min squares analysis)
 		/// # </weight>
-		#[weight = T::WeightInfo::set_fee(T::MaxRegistrars::get())] // R
+		#[weight = WithProofSize(
+			T::WeightInfo::set_fee(T::MaxRegistrars::get()),
+			T::WeightInfo::set_fee_proof_size(T::MaxRegistrars::get()),
+		)] // R
 		fn set_fee(origin,
 			#[compact] index: RegistrarIndex,
 			#[compact] fee: BalanceOf<T>,
@@ -894,7 +1023,10 @@ decl_module! {
 		/// - One storage mutation `O(R)`.
 		/// - Benchmark: 8.823 + R * 0.32 µs (min squares analysis)
 		/// # </weight>
-		#[weight = T::WeightInfo::set_account_id(T::MaxRegistrars::get())] // R
+		#[weight = WithProofSize(
+			T::WeightInfo::set_account_id(T::MaxRegistrars::get()),
+			T::WeightInfo::set_account_id_proof_size(T::MaxRegistrars::get()),
+		)] // R
 		fn set_account_id(origin,
 			#[compact] index: RegistrarIndex,
 			new: T::AccountId,
@@ -924,7 +1056,10 @@ decl_module! {
 		/// - One storage mutation `O(R)`.
 		/// - Benchmark: 7.464 + R * 0.325 µs (min squares analysis)
 		/// # </weight>
-		#[weight = T::WeightInfo::set_fields(T::MaxRegistrars::get())] // R
+		#[weight = WithProofSize(
+			T::WeightInfo::set_fields(T::MaxRegistrars::get()),
+			T::WeightInfo::set_fields_proof_size(T::MaxRegistrars::get()),
+		)] // R
 		fn set_fields(origin,
 			#[compact] index: RegistrarIndex,
 			fields: IdentityFields,
@@ -962,9 +1097,15 @@ decl_module! {
 		/// - Storage: 1 read `O(R)`, 1 mutate `O(R + X)`.
 		/// - One event.
 		/// # </weight>
-		#[weight = T::WeightInfo::provide_judgement(
-			T::MaxRegistrars::get().into(), // R
-			T::MaxAdditionalFields::get().into(), // X
+		#[weight = WithProofSize(
+			T::WeightInfo::provide_judgement(
+				T::MaxRegistrars::get().into(), // R
+				T::MaxAdditionalFields::get().into(), // X
+			),
+			T::WeightInfo::provide_judgement_proof_size(
+				T::MaxRegistrars::get().into(),
+				T::MaxAdditionalFields::get().into(),
+			),
 		)]
 		fn provide_judgement(origin,
 			#[compact] reg_index: RegistrarIndex,
@@ -1022,10 +1163,17 @@ decl_module! {
 		/// - `S + 2` storage mutations.
 		/// - One event.
 		/// # </weight>
-		#[weight = T::WeightInfo::kill_identity(
-			T::MaxRegistrars::get().into(), // R
-			T::MaxSubAccounts::get().into(), // S
-			T::MaxAdditionalFields::get().into(), // X
+		#[weight = WithProofSize(
+			T::WeightInfo::kill_identity(
+				T::MaxRegistrars::get().into(), // R
+				T::MaxSubAccounts::get().into(), // S
+				T::MaxAdditionalFields::get().into(), // X
+			),
+			T::WeightInfo::kill_identity_proof_size(
+				T::MaxRegistrars::get().into(),
+				T::MaxSubAccounts::get().into(),
+				T::MaxAdditionalFields::get().into(),
+			),
 		)]
 		fn kill_identity(origin, target: <T::Lookup as StaticLookup>::Source) -> DispatchResultWithPostInfo {
 			T::ForceOrigin::ensure_origin(origin)?;
@@ -1058,7 +1206,10 @@ decl_module! {
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// sub identity of `sub`.
-		#[weight = T::WeightInfo::add_sub(T::MaxSubAccounts::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::add_sub(T::MaxSubAccounts::get()),
+			T::WeightInfo::add_sub_proof_size(T::MaxSubAccounts::get()),
+		)]
 		fn add_sub(origin, sub: <T::Lookup as StaticLookup>::Source, data: Data) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			let sub = T::Lookup::lookup(sub)?;
@@ -1086,7 +1237,10 @@ decl_module! {
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// sub identity of `sub`.
-		#[weight = T::WeightInfo::rename_sub(T::MaxSubAccounts::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::rename_sub(T::MaxSubAccounts::get()),
+			T::WeightInfo::rename_sub_proof_size(T::MaxSubAccounts::get()),
+		)]
 		fn rename_sub(origin, sub: <T::Lookup as StaticLookup>::Source, data: Data) {
 			let sender = ensure_signed(origin)?;
 			let sub = T::Lookup::lookup(sub)?;
@@ -1102,7 +1256,10 @@ decl_module! {
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// sub identity of `sub`.
-		#[weight = T::WeightInfo::remove_sub(T::MaxSubAccounts::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_sub(T::MaxSubAccounts::get()),
+			T::WeightInfo::remove_sub_proof_size(T::MaxSubAccounts::get()),
+		)]
 		fn remove_sub(origin, sub: <T::Lookup as StaticLookup>::Source) {
 			let sender = ensure_signed(origin)?;
 			ensure!(IdentityOf::<T>::contains_key(&sender), Error::<T>::NoIdentity);
@@ -1129,7 +1286,10 @@ decl_module! {
 		///
 		/// NOTE: This should not normally be used, but is provided in the case that the non-
 		/// controller of an account is maliciously registered as a sub-account.
-		#[weight = T::WeightInfo::quit_sub(T::MaxSubAccounts::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::quit_sub(T::MaxSubAccounts::get()),
+			T::WeightInfo::quit_sub_proof_size(T::MaxSubAccounts::get()),
+		)]
 		fn quit_sub(origin) {
 			let sender = ensure_signed(origin)?;
 			let (sup, _) = SuperOf::<T>::take(&sender).ok_or(Error::<T>::NotSub)?;
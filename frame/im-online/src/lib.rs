@@ -40,6 +40,15 @@
 //!
 //! - `is_online` - True if the validator sent a heartbeat in the current session.
 //!
+//! ### Unresponsiveness policy
+//!
+//! At the end of each session the validators that neither sent a heartbeat nor authored a
+//! block are dealt with according to the [`UnresponsivenessPolicy`](./trait.UnresponsivenessPolicy.html)
+//! of the runtime. It decides whether an `UnresponsivenessOffence` is reported for them, for how
+//! many sessions they are kicked out of the validator set and how long after a session change
+//! unresponsiveness goes unpunished. Kicking requires the session manager to be wrapped in
+//! [`FilterKicked`](./struct.FilterKicked.html).
+//!
 //! ## Usage
 //!
 //! ```
@@ -76,16 +85,16 @@ mod default_weight;
 
 use sp_application_crypto::RuntimeAppPublic;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_core::offchain::OpaqueNetworkState;
 use sp_std::prelude::*;
-use sp_std::convert::TryInto;
 use pallet_session::historical::IdentificationTuple;
 use sp_runtime::{
 	offchain::storage::StorageValueRef,
 	RuntimeDebug,
-	traits::{Convert, Member, Saturating, AtLeast32BitUnsigned}, Perbill,
+	traits::{Convert, Member, Saturating, AtLeast32BitUnsigned, Zero}, Perbill,
 	transaction_validity::{
-		TransactionValidity, ValidTransaction, InvalidTransaction, TransactionSource,
+		TransactionValidity, TransactionValidityError, InvalidTransaction, TransactionSource,
 		TransactionPriority,
 	},
 };
@@ -96,7 +105,8 @@ use sp_staking::{
 use frame_support::{
 	decl_module, decl_event, decl_storage, Parameter, debug, decl_error,
 	traits::Get,
-	weights::Weight,
+	unsigned::{RateLimit, UnsignedValidity},
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
 };
 use frame_system::ensure_none;
 use frame_system::offchain::{
@@ -150,7 +160,7 @@ const INCLUDE_THRESHOLD: u32 = 3;
 /// This stores the block number at which heartbeat was requested and when the worker
 /// has actually managed to produce it.
 /// Note we store such status for every `authority_index` separately.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 struct HeartbeatStatus<BlockNumber> {
 	/// An index of the session that we are supposed to send heartbeat for.
 	pub session_index: SessionIndex,
@@ -211,7 +221,7 @@ impl<BlockNumber: sp_std::fmt::Debug> sp_std::fmt::Debug for OffchainErr<BlockNu
 pub type AuthIndex = u32;
 
 /// Heartbeat which is sent/received.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct Heartbeat<BlockNumber>
 	where BlockNumber: PartialEq + Eq + Decode + Encode,
 {
@@ -229,6 +239,88 @@ pub struct Heartbeat<BlockNumber>
 
 pub trait WeightInfo {
 	fn validate_unsigned_and_then_heartbeat(k: u32, e: u32, ) -> Weight;
+	fn validate_unsigned_and_then_heartbeat_proof_size(_k: u32, _e: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+}
+
+/// Decides how validators found unresponsive at the end of a session are dealt with.
+///
+/// `consecutive` is the number of consecutive sessions, among those the validator was part of,
+/// in which it was found unresponsive, including the ending one.
+pub trait UnresponsivenessPolicy<BlockNumber> {
+	/// Number of blocks after a session change during which unresponsive validators are not
+	/// punished if the session ends.
+	fn grace_period() -> BlockNumber;
+
+	/// Whether an `UnresponsivenessOffence` is reported for the validator.
+	fn report(consecutive: u32) -> bool;
+
+	/// Number of sessions the validator is kicked out of the validator set for. Zero doesn't
+	/// kick it.
+	fn kick_sessions(consecutive: u32) -> SessionIndex;
+}
+
+/// Reports every unresponsive validator, without kicking it.
+impl<BlockNumber: Zero> UnresponsivenessPolicy<BlockNumber> for () {
+	fn grace_period() -> BlockNumber {
+		Zero::zero()
+	}
+
+	fn report(_consecutive: u32) -> bool {
+		true
+	}
+
+	fn kick_sessions(_consecutive: u32) -> SessionIndex {
+		0
+	}
+}
+
+/// Reports unresponsive validators and kicks them out of the next session, unless the session
+/// ended less than `GracePeriod` blocks after it started.
+pub struct KickUnresponsive<GracePeriod>(sp_std::marker::PhantomData<GracePeriod>);
+
+impl<BlockNumber, GracePeriod: Get<BlockNumber>> UnresponsivenessPolicy<BlockNumber>
+	for KickUnresponsive<GracePeriod>
+{
+	fn grace_period() -> BlockNumber {
+		GracePeriod::get()
+	}
+
+	fn report(_consecutive: u32) -> bool {
+		true
+	}
+
+	fn kick_sessions(_consecutive: u32) -> SessionIndex {
+		1
+	}
+}
+
+/// Like `KickUnresponsive`, but a validator found unresponsive in `n` consecutive sessions is
+/// kicked out for `2^(n - 1)` sessions, and at most `MaxSessions`.
+pub struct ExponentialBackOff<GracePeriod, MaxSessions>(
+	sp_std::marker::PhantomData<(GracePeriod, MaxSessions)>
+);
+
+impl<BlockNumber, GracePeriod, MaxSessions> UnresponsivenessPolicy<BlockNumber>
+	for ExponentialBackOff<GracePeriod, MaxSessions>
+where
+	GracePeriod: Get<BlockNumber>,
+	MaxSessions: Get<SessionIndex>,
+{
+	fn grace_period() -> BlockNumber {
+		GracePeriod::get()
+	}
+
+	fn report(_consecutive: u32) -> bool {
+		true
+	}
+
+	fn kick_sessions(consecutive: u32) -> SessionIndex {
+		1u32.checked_shl(consecutive.saturating_sub(1))
+			.unwrap_or(SessionIndex::max_value())
+			.min(MaxSessions::get())
+	}
 }
 
 pub trait Trait: SendTransactionTypes<Call<Self>> + pallet_session::historical::Trait {
@@ -260,6 +352,16 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + pallet_session::historical::
 	/// multiple pallets send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
 
+	/// The maximum number of heartbeats accepted from an authority index within
+	/// `SessionDuration` blocks.
+	///
+	/// The window starts at the first heartbeat and is not aligned to sessions, so this should
+	/// be at least two to accept the heartbeats of two consecutive sessions.
+	type MaxHeartbeats: Get<u32>;
+
+	/// How validators found unresponsive at the end of a session are dealt with.
+	type UnresponsivenessPolicy: UnresponsivenessPolicy<Self::BlockNumber>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -268,6 +370,7 @@ decl_event!(
 	pub enum Event<T> where
 		<T as Trait>::AuthorityId,
 		IdentificationTuple = IdentificationTuple<T>,
+		ValidatorId = <T as pallet_session::Trait>::ValidatorId,
 	{
 		/// A new heartbeat was received from `AuthorityId` \[authority_id\]
 		HeartbeatReceived(AuthorityId),
@@ -275,6 +378,9 @@ decl_event!(
 		AllGood,
 		/// At the end of the session, at least one validator was found to be \[offline\].
 		SomeOffline(Vec<IdentificationTuple>),
+		/// An unresponsive validator was kicked out of the validator set until the given
+		/// session. \[validator, session_index\]
+		ValidatorKicked(ValidatorId, SessionIndex),
 	}
 );
 
@@ -302,6 +408,39 @@ decl_storage! {
 		AuthoredBlocks get(fn authored_blocks):
 			double_map hasher(twox_64_concat) SessionIndex, hasher(twox_64_concat) T::ValidatorId
 			=> u32;
+
+		/// The block number at which the current session started.
+		SessionStart get(fn session_start): T::BlockNumber;
+
+		/// For each validator, the number of consecutive sessions it was part of and found
+		/// unresponsive in.
+		///
+		/// The entries of validators which are neither part of the current session nor kicked out
+		/// are removed at the end of every session, so there is at most one per validator of the
+		/// last two sessions or kicked validator.
+		ConsecutiveOffences get(fn consecutive_offences):
+			map hasher(twox_64_concat) T::ValidatorId => u32;
+
+		/// Validators kicked out by the `UnresponsivenessPolicy`, with the index of the first
+		/// session they can be part of again.
+		Kicked get(fn kicked_until):
+			map hasher(twox_64_concat) T::ValidatorId => Option<SessionIndex>;
+
+		/// The validators whose kick ends at a given session index. A validator may be listed
+		/// under the end of a previous kick too, if it was kicked again.
+		KickExpiries: map hasher(twox_64_concat) SessionIndex => Vec<T::ValidatorId>;
+
+		/// Whether a validator was kicked since the last validator set was planned.
+		KickedChanged: bool;
+
+		/// The last validator set planned by the session manager wrapped in `FilterKicked`,
+		/// kicked validators included.
+		PlannedValidators get(fn planned_validators): Vec<T::ValidatorId>;
+
+		/// The start of the current rate limit window and the number of heartbeats accepted
+		/// within it, for each authority index.
+		HeartbeatCounts get(fn heartbeat_counts):
+			map hasher(twox_64_concat) AuthIndex => Option<(T::BlockNumber, u32)>;
 	}
 	add_extra_genesis {
 		config(keys): Vec<T::AuthorityId>;
@@ -323,6 +462,10 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 
+		/// The maximum number of heartbeats accepted from an authority index within
+		/// `SessionDuration` blocks.
+		const MaxHeartbeats: u32 = T::MaxHeartbeats::get();
+
 		fn deposit_event() = default;
 
 		/// # <weight>
@@ -331,14 +474,20 @@ decl_module! {
 		///   - `O(K)`: decoding of length `K`
 		///   - `O(E)`: decoding/encoding of length `E`
 		/// - DbReads: pallet_session `Validators`, pallet_session `CurrentIndex`, `Keys`,
-		///   `ReceivedHeartbeats`
-		/// - DbWrites: `ReceivedHeartbeats`
+		///   `ReceivedHeartbeats`, `HeartbeatCounts`
+		/// - DbWrites: `ReceivedHeartbeats`, `HeartbeatCounts`
 		/// # </weight>
 		// NOTE: the weight includes the cost of validate_unsigned as it is part of the cost to
 		// import block with such an extrinsic.
-		#[weight = <T as Trait>::WeightInfo::validate_unsigned_and_then_heartbeat(
-			heartbeat.validators_len as u32,
-			heartbeat.network_state.external_addresses.len() as u32,
+		#[weight = WithProofSize(
+			<T as Trait>::WeightInfo::validate_unsigned_and_then_heartbeat(
+				heartbeat.validators_len as u32,
+				heartbeat.network_state.external_addresses.len() as u32,
+			),
+			<T as Trait>::WeightInfo::validate_unsigned_and_then_heartbeat_proof_size(
+				heartbeat.validators_len as u32,
+				heartbeat.network_state.external_addresses.len() as u32,
+			),
 		)]
 		fn heartbeat(
 			origin,
@@ -608,6 +757,68 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// Apply the `UnresponsivenessPolicy` to a validator found unresponsive at the end of
+	/// `session_index`. Returns whether an offence should be reported.
+	fn punish_unresponsive(id: &T::ValidatorId, session_index: SessionIndex) -> bool {
+		let consecutive = <ConsecutiveOffences<T>>::mutate(id, |n| {
+			*n = n.saturating_add(1);
+			*n
+		});
+
+		let sessions = T::UnresponsivenessPolicy::kick_sessions(consecutive);
+		if sessions > 0 {
+			// The validator set of the next session is already queued, so the validator is
+			// kicked out starting from the one after.
+			let until = session_index.saturating_add(2).saturating_add(sessions);
+			<Kicked<T>>::insert(id, until);
+			<KickExpiries<T>>::append(until, id);
+			KickedChanged::put(true);
+			Self::deposit_event(RawEvent::ValidatorKicked(id.clone(), until));
+		}
+
+		T::UnresponsivenessPolicy::report(consecutive)
+	}
+
+	/// Note the validator set planned for session `new_index` by the session manager wrapped
+	/// in `FilterKicked`, if it changed, and expire the kicks ending at `new_index`. Returns
+	/// whether the validator set has to be planned anew without the kicked validators.
+	fn note_planned_session(new_index: SessionIndex, planned: Option<&[T::ValidatorId]>) -> bool {
+		if let Some(planned) = planned {
+			<PlannedValidators<T>>::put(planned);
+		}
+
+		// Only the kicks ending now are looked at, skipping those which were extended since.
+		let expired = <KickExpiries<T>>::take(new_index)
+			.into_iter()
+			.filter(|id| Self::kicked_until(id) == Some(new_index))
+			.collect::<Vec<_>>();
+		for id in &expired {
+			<Kicked<T>>::remove(id);
+		}
+
+		KickedChanged::take() || planned.is_some() || !expired.is_empty()
+	}
+
+	/// Forget the consecutive offences of the validators which left the validator set, i.e. are
+	/// neither part of the `current_validators` nor kicked out.
+	///
+	/// Entries are only added for the validators of the current session, so this iterates over
+	/// at most the validators of this and the previous session, and the kicked validators.
+	fn forget_left_validators(current_validators: &[T::ValidatorId]) {
+		let left = <ConsecutiveOffences<T>>::iter()
+			.map(|(id, _)| id)
+			.filter(|id| !current_validators.contains(id) && !Self::is_kicked(id))
+			.collect::<Vec<_>>();
+		for id in &left {
+			<ConsecutiveOffences<T>>::remove(id);
+		}
+	}
+
+	/// Whether the validator is currently kicked out of the validator set.
+	pub fn is_kicked(id: &T::ValidatorId) -> bool {
+		<Kicked<T>>::contains_key(id)
+	}
+
 	#[cfg(test)]
 	fn set_keys(keys: Vec<T::AuthorityId>) {
 		Keys::<T>::put(&keys)
@@ -637,6 +848,7 @@ impl<T: Trait> pallet_session::OneSessionHandler<T::AccountId> for Module<T> {
 		let block_number = <frame_system::Module<T>>::block_number();
 		let half_session = T::SessionDuration::get() / 2.into();
 		<HeartbeatAfter<T>>::put(block_number + half_session);
+		<SessionStart<T>>::put(block_number);
 
 		// Remember who the authorities are for the new session.
 		Keys::<T>::put(validators.map(|x| x.1).collect::<Vec<_>>());
@@ -647,12 +859,28 @@ impl<T: Trait> pallet_session::OneSessionHandler<T::AccountId> for Module<T> {
 		let keys = Keys::<T>::get();
 		let current_validators = <pallet_session::Module<T>>::validators();
 
-		let offenders = current_validators.into_iter().enumerate()
-			.filter(|(index, id)|
-				!Self::is_online_aux(*index as u32, id)
-			).filter_map(|(_, id)|
-				T::FullIdentificationOf::convert(id.clone()).map(|full_id| (id, full_id))
-			).collect::<Vec<IdentificationTuple<T>>>();
+		let now = <frame_system::Module<T>>::block_number();
+		let grace_period_end = Self::session_start()
+			.saturating_add(T::UnresponsivenessPolicy::grace_period());
+
+		let mut offenders = Vec::<IdentificationTuple<T>>::new();
+		if now >= grace_period_end {
+			for (index, id) in current_validators.iter().enumerate() {
+				if Self::is_online_aux(index as u32, id) {
+					<ConsecutiveOffences<T>>::remove(id);
+					continue;
+				}
+
+				if let Some(full_id) = T::FullIdentificationOf::convert(id.clone()) {
+					if Self::punish_unresponsive(id, session_index) {
+						offenders.push((id.clone(), full_id));
+					}
+				}
+			}
+		}
+		// This is bounded by the validator set sizes, and accounted for by the session rotation
+		// taking the whole block.
+		Self::forget_left_validators(&current_validators);
 
 		// Remove all received heartbeats and number of authored blocks from the
 		// current session, they have already been processed and won't be needed
@@ -678,6 +906,83 @@ impl<T: Trait> pallet_session::OneSessionHandler<T::AccountId> for Module<T> {
 	}
 }
 
+/// A `SessionManager` that wraps an inner `I` and removes the validators kicked out by the
+/// `UnresponsivenessPolicy` from the validator sets it plans.
+///
+/// The whole set is kept if all of its validators are kicked out.
+pub struct FilterKicked<T, I>(sp_std::marker::PhantomData<(T, I)>);
+
+impl<T: Trait, I> pallet_session::SessionManager<T::ValidatorId> for FilterKicked<T, I>
+	where I: pallet_session::SessionManager<T::ValidatorId>
+{
+	fn new_session(new_index: SessionIndex) -> Option<Vec<T::ValidatorId>> {
+		let planned = I::new_session(new_index);
+		if !<Module<T>>::note_planned_session(new_index, planned.as_deref()) {
+			return None;
+		}
+
+		let planned = planned.unwrap_or_else(<Module<T>>::planned_validators);
+		let remaining = planned.iter()
+			.filter(|id| !<Module<T>>::is_kicked(id))
+			.cloned()
+			.collect::<Vec<_>>();
+
+		match (remaining.is_empty(), planned.is_empty()) {
+			(_, true) => None,
+			(true, false) => Some(planned),
+			(false, false) => Some(remaining),
+		}
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		I::start_session(start_index)
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		I::end_session(end_index)
+	}
+}
+
+impl<T: Trait, I> pallet_session::historical::SessionManager<T::ValidatorId, T::FullIdentification>
+	for FilterKicked<T, I>
+	where I: pallet_session::historical::SessionManager<T::ValidatorId, T::FullIdentification>
+{
+	fn new_session(new_index: SessionIndex) -> Option<Vec<IdentificationTuple<T>>> {
+		let planned = <I as pallet_session::historical::SessionManager<_, _>>::new_session(new_index);
+		let planned_ids = planned.as_ref()
+			.map(|planned| planned.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>());
+		if !<Module<T>>::note_planned_session(new_index, planned_ids.as_deref()) {
+			return None;
+		}
+
+		let planned = planned.unwrap_or_else(|| <Module<T>>::planned_validators()
+			.into_iter()
+			.filter_map(|id|
+				T::FullIdentificationOf::convert(id.clone()).map(|full_id| (id, full_id))
+			)
+			.collect()
+		);
+		let remaining = planned.iter()
+			.filter(|(id, _)| !<Module<T>>::is_kicked(id))
+			.cloned()
+			.collect::<Vec<_>>();
+
+		match (remaining.is_empty(), planned.is_empty()) {
+			(_, true) => None,
+			(true, false) => Some(planned),
+			(false, false) => Some(remaining),
+		}
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		<I as pallet_session::historical::SessionManager<_, _>>::start_session(start_index)
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		<I as pallet_session::historical::SessionManager<_, _>>::end_session(end_index)
+	}
+}
+
 /// Invalid transaction custom error. Returned when validators_len field in heartbeat is incorrect.
 const INVALID_VALIDATORS_LEN: u8 = 10;
 
@@ -710,6 +1015,11 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				None => return InvalidTransaction::BadProof.into(),
 			};
 
+			HeartbeatsLimit::<T>::check(
+				&heartbeat.authority_index,
+				<frame_system::Module<T>>::block_number(),
+			)?;
+
 			// check signature (this is expensive so we do it last).
 			let signature_valid = heartbeat.using_encoded(|encoded_heartbeat| {
 				authority_id.verify(&encoded_heartbeat, &signature)
@@ -719,20 +1029,36 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				return InvalidTransaction::BadProof.into();
 			}
 
-			ValidTransaction::with_tag_prefix("ImOnline")
+			UnsignedValidity::new("ImOnline")
 				.priority(T::UnsignedPriority::get())
 				.and_provides((current_session, authority_id))
-				.longevity(TryInto::<u64>::try_into(
-					T::SessionDuration::get() / 2.into()
-				).unwrap_or(64_u64))
-				.propagate(true)
+				.longevity(T::SessionDuration::get() / 2.into(), 64)
 				.build()
 		} else {
 			InvalidTransaction::Call.into()
 		}
 	}
+
+	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+		Self::validate_unsigned(TransactionSource::InBlock, call)?;
+		if let Call::heartbeat(heartbeat, _) = call {
+			HeartbeatsLimit::<T>::note(
+				&heartbeat.authority_index,
+				<frame_system::Module<T>>::block_number(),
+			)?;
+		}
+		Ok(())
+	}
 }
 
+/// Rate limit of the heartbeats, at most `MaxHeartbeats` of them are accepted per authority
+/// index within `SessionDuration` blocks.
+type HeartbeatsLimit<T> = RateLimit<
+	HeartbeatCounts<T>,
+	<T as Trait>::MaxHeartbeats,
+	<T as Trait>::SessionDuration,
+>;
+
 /// An offence that is filed if a validator didn't send a heartbeat message.
 #[derive(RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Clone, PartialEq, Eq))]
@@ -21,13 +21,13 @@
 
 use std::cell::RefCell;
 
-use crate::{Module, Trait};
+use crate::{Module, Trait, UnresponsivenessPolicy, KickUnresponsive, ExponentialBackOff, FilterKicked};
 use sp_runtime::Perbill;
 use sp_staking::{SessionIndex, offence::{ReportOffence, OffenceError}};
 use sp_runtime::testing::{Header, UintAuthorityId, TestXt};
 use sp_runtime::traits::{IdentityLookup, BlakeTwo256, ConvertInto};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, impl_outer_dispatch, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, impl_outer_dispatch, parameter_types, weights::Weight, traits::Get};
 
 impl_outer_origin!{
 	pub enum Origin for Runtime {}
@@ -127,6 +127,9 @@ impl frame_system::Trait for Runtime {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -148,7 +151,10 @@ parameter_types! {
 
 impl pallet_session::Trait for Runtime {
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
-	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Runtime, TestSessionManager>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<
+		Runtime,
+		FilterKicked<Runtime, TestSessionManager>,
+	>;
 	type SessionHandler = (ImOnline, );
 	type ValidatorId = u64;
 	type ValidatorIdOf = ConvertInto;
@@ -177,6 +183,60 @@ impl pallet_authorship::Trait for Runtime {
 
 parameter_types! {
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const MaxHeartbeats: u32 = 1;
+	pub const MaxKickSessions: SessionIndex = 4;
+}
+
+/// The unresponsiveness policies that can be used in tests.
+#[derive(Clone, Copy)]
+pub enum Policy {
+	Report,
+	Kick,
+	BackOff,
+}
+
+thread_local! {
+	pub static POLICY: RefCell<Policy> = RefCell::new(Policy::Report);
+	pub static GRACE_PERIOD: RefCell<u64> = RefCell::new(0);
+}
+
+pub struct GracePeriod;
+impl Get<u64> for GracePeriod {
+	fn get() -> u64 {
+		GRACE_PERIOD.with(|v| *v.borrow())
+	}
+}
+
+/// Dispatches to the policy set in `POLICY`.
+pub struct TestPolicy;
+impl UnresponsivenessPolicy<u64> for TestPolicy {
+	fn grace_period() -> u64 {
+		match POLICY.with(|p| *p.borrow()) {
+			Policy::Report => <() as UnresponsivenessPolicy<u64>>::grace_period(),
+			Policy::Kick => <KickUnresponsive<GracePeriod> as UnresponsivenessPolicy<u64>>::grace_period(),
+			Policy::BackOff =>
+				<ExponentialBackOff<GracePeriod, MaxKickSessions> as UnresponsivenessPolicy<u64>>::grace_period(),
+		}
+	}
+
+	fn report(consecutive: u32) -> bool {
+		match POLICY.with(|p| *p.borrow()) {
+			Policy::Report => <() as UnresponsivenessPolicy<u64>>::report(consecutive),
+			Policy::Kick => <KickUnresponsive<GracePeriod> as UnresponsivenessPolicy<u64>>::report(consecutive),
+			Policy::BackOff =>
+				<ExponentialBackOff<GracePeriod, MaxKickSessions> as UnresponsivenessPolicy<u64>>::report(consecutive),
+		}
+	}
+
+	fn kick_sessions(consecutive: u32) -> SessionIndex {
+		match POLICY.with(|p| *p.borrow()) {
+			Policy::Report => <() as UnresponsivenessPolicy<u64>>::kick_sessions(consecutive),
+			Policy::Kick =>
+				<KickUnresponsive<GracePeriod> as UnresponsivenessPolicy<u64>>::kick_sessions(consecutive),
+			Policy::BackOff =>
+				<ExponentialBackOff<GracePeriod, MaxKickSessions> as UnresponsivenessPolicy<u64>>::kick_sessions(consecutive),
+		}
+	}
 }
 
 impl Trait for Runtime {
@@ -185,6 +245,8 @@ impl Trait for Runtime {
 	type ReportUnresponsiveness = OffenceHandler;
 	type SessionDuration = Period;
 	type UnsignedPriority = UnsignedPriority;
+	type MaxHeartbeats = MaxHeartbeats;
+	type UnresponsivenessPolicy = TestPolicy;
 	type WeightInfo = ();
 }
 
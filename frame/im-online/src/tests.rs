@@ -27,7 +27,7 @@ use sp_core::offchain::{
 	TransactionPoolExt,
 	testing::{TestOffchainExt, TestTransactionPoolExt},
 };
-use frame_support::{dispatch, assert_noop};
+use frame_support::{dispatch, assert_noop, assert_ok};
 use sp_runtime::{testing::UintAuthorityId, transaction_validity::TransactionValidityError};
 
 #[test]
@@ -198,6 +198,35 @@ fn late_heartbeat_and_invalid_keys_len_should_fail() {
 	});
 }
 
+#[test]
+fn should_rate_limit_heartbeats_of_an_authority_index() {
+	new_test_ext().execute_with(|| {
+		advance_session();
+		VALIDATORS.with(|l| *l.borrow_mut() = Some(vec![1, 2, 3]));
+		advance_session();
+		assert_eq!(Session::current_index(), 2);
+		let block = System::block_number();
+
+		assert_ok!(heartbeat(1, 2, 0, 1.into(), Session::validators()));
+		assert_eq!(ImOnline::heartbeat_counts(0), Some((block, 1)));
+
+		// a session that ends within `SessionDuration` blocks uses up the allowance.
+		Session::rotate_session();
+		ImOnline::set_keys(Session::validators().into_iter().map(UintAuthorityId).collect());
+		assert_eq!(Session::current_index(), 3);
+		assert_noop!(
+			heartbeat(1, 3, 0, 1.into(), Session::validators()),
+			"Transaction would exhaust the block limits",
+		);
+		assert_ok!(heartbeat(1, 3, 1, 2.into(), Session::validators()));
+
+		// the allowance is renewed after `SessionDuration` blocks.
+		advance_session();
+		assert_ok!(heartbeat(1, 4, 0, 1.into(), Session::validators()));
+		assert_eq!(ImOnline::heartbeat_counts(0), Some((block + 1, 1)));
+	});
+}
+
 #[test]
 fn should_generate_heartbeats() {
 	use frame_support::traits::OffchainWorker;
@@ -354,3 +383,123 @@ fn should_not_send_a_report_if_already_online() {
 		});
 	});
 }
+
+fn queued_validators() -> Vec<u64> {
+	Session::queued_keys().into_iter().map(|(id, _)| id).collect()
+}
+
+fn unresponsive_in_session_two() {
+	System::set_block_number(1);
+	advance_session();
+	VALIDATORS.with(|l| *l.borrow_mut() = Some(vec![1, 2, 3, 4, 5, 6]));
+	advance_session();
+	assert_eq!(Session::validators(), vec![1, 2, 3]);
+
+	// none of the validators of session 2 sends a heartbeat.
+	advance_session();
+	assert_eq!(Session::current_index(), 3);
+}
+
+#[test]
+fn should_kick_unresponsive_validators_out_of_the_next_session() {
+	new_test_ext().execute_with(|| {
+		POLICY.with(|p| *p.borrow_mut() = Policy::Kick);
+		unresponsive_in_session_two();
+
+		// the offence is still reported.
+		let offences = OFFENCES.with(|l| l.replace(vec![]));
+		assert_eq!(offences, vec![
+			(vec![], UnresponsivenessOffence {
+				session_index: 2,
+				validator_set_count: 3,
+				offenders: vec![(1, 1), (2, 2), (3, 3)],
+			})
+		]);
+
+		// session 3 was already queued, the validators are kicked out of session 4.
+		for v in 1..=3 {
+			assert_eq!(ImOnline::kicked_until(v), Some(5));
+			assert_eq!(ImOnline::consecutive_offences(v), 1);
+		}
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4, 5, 6]);
+		assert_eq!(queued_validators(), vec![4, 5, 6]);
+
+		let validators = Session::validators();
+		for (idx, v) in validators.iter().enumerate() {
+			let _ = heartbeat(1, 3, idx as u32, (*v).into(), validators.clone()).unwrap();
+		}
+		advance_session();
+
+		// the kick has expired and the validators are back in session 5.
+		assert_eq!(Session::validators(), vec![4, 5, 6]);
+		assert_eq!(queued_validators(), vec![1, 2, 3, 4, 5, 6]);
+		for v in 1..=3 {
+			assert_eq!(ImOnline::kicked_until(v), None);
+			assert_eq!(ImOnline::consecutive_offences(v), 0);
+		}
+		assert!(OFFENCES.with(|l| l.borrow().is_empty()));
+	});
+}
+
+#[test]
+fn should_forget_consecutive_offences_of_validators_leaving_the_set() {
+	new_test_ext().execute_with(|| {
+		// nobody sends a heartbeat from session 2 on, and validator 1 is not planned anymore.
+		unresponsive_in_session_two();
+		VALIDATORS.with(|l| *l.borrow_mut() = Some(vec![2, 3, 4, 5, 6]));
+		advance_session();
+		advance_session();
+		assert_eq!(Session::validators(), vec![2, 3, 4, 5, 6]);
+		assert_eq!(ImOnline::consecutive_offences(1), 3);
+
+		// validator 1 is forgotten at the end of the first session it is not part of.
+		advance_session();
+		assert!(!<ConsecutiveOffences<Runtime>>::contains_key(1));
+		assert_eq!(ImOnline::consecutive_offences(2), 4);
+		assert_eq!(ImOnline::consecutive_offences(4), 3);
+	});
+}
+
+#[test]
+fn should_back_off_exponentially_for_repeat_offenders() {
+	type BackOff = ExponentialBackOff<GracePeriod, MaxKickSessions>;
+
+	assert_eq!(<BackOff as UnresponsivenessPolicy<u64>>::kick_sessions(1), 1);
+	assert_eq!(<BackOff as UnresponsivenessPolicy<u64>>::kick_sessions(2), 2);
+	assert_eq!(<BackOff as UnresponsivenessPolicy<u64>>::kick_sessions(3), 4);
+	assert_eq!(<BackOff as UnresponsivenessPolicy<u64>>::kick_sessions(4), 4);
+	assert_eq!(<BackOff as UnresponsivenessPolicy<u64>>::kick_sessions(40), 4);
+
+	new_test_ext().execute_with(|| {
+		POLICY.with(|p| *p.borrow_mut() = Policy::BackOff);
+		unresponsive_in_session_two();
+		assert_eq!(ImOnline::kicked_until(1), Some(5));
+
+		// validator 1 is unresponsive again in session 3.
+		let validators = Session::validators();
+		for (idx, v) in validators.iter().enumerate().skip(1) {
+			let _ = heartbeat(1, 3, idx as u32, (*v).into(), validators.clone()).unwrap();
+		}
+		advance_session();
+
+		assert_eq!(ImOnline::consecutive_offences(1), 2);
+		assert_eq!(ImOnline::kicked_until(1), Some(7));
+		assert_eq!(ImOnline::consecutive_offences(2), 0);
+		assert_eq!(ImOnline::kicked_until(2), None);
+		assert_eq!(queued_validators(), vec![2, 3, 4, 5, 6]);
+	});
+}
+
+#[test]
+fn should_not_punish_unresponsive_validators_during_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		POLICY.with(|p| *p.borrow_mut() = Policy::Kick);
+		GRACE_PERIOD.with(|p| *p.borrow_mut() = 10);
+		unresponsive_in_session_two();
+
+		assert!(OFFENCES.with(|l| l.borrow().is_empty()));
+		assert_eq!(ImOnline::kicked_until(1), None);
+		assert_eq!(ImOnline::consecutive_offences(1), 0);
+		assert_eq!(queued_validators(), vec![1, 2, 3, 4, 5, 6]);
+	});
+}
@@ -22,6 +22,7 @@ use std::fmt;
 use sp_std::convert::TryInto;
 use crate::Member;
 use codec::{Encode, Decode, Input, Output, Error};
+use frame_support::type_info::{TypeInfo, Type, TypeDef, Path, Field, Variant, MetaType};
 
 /// An indices-aware address, which can be either a direct `AccountId` or
 /// an index.
@@ -121,6 +122,36 @@ impl<AccountId, AccountIndex> codec::EncodeLike for Address<AccountId, AccountIn
 	AccountIndex: Member + Encode + PartialOrd<AccountIndex> + Ord + Copy + From<u32> + TryInto<u32>,
 {}
 
+impl<AccountId, AccountIndex> TypeInfo for Address<AccountId, AccountIndex> where
+	AccountId: Member + TypeInfo + 'static,
+	AccountIndex: Member + TypeInfo + 'static,
+{
+	fn type_info() -> Type {
+		Type::new(
+			Path::new("Address", module_path!()),
+			TypeDef::Variant(sp_std::vec![
+				Variant::new("Index16", sp_std::vec![Field::unnamed(MetaType::new::<u16>(), "u16")], 0xfc),
+				Variant::new("Index32", sp_std::vec![Field::unnamed(MetaType::new::<u32>(), "u32")], 0xfd),
+				Variant::new(
+					"Index",
+					sp_std::vec![Field::unnamed(MetaType::new::<AccountIndex>(), "AccountIndex")],
+					0xfe,
+				),
+				Variant::new(
+					"Id",
+					sp_std::vec![Field::unnamed(MetaType::new::<AccountId>(), "AccountId")],
+					0xff,
+				),
+			]),
+		).docs(&[
+			" An indices-aware address, which can be either a direct `AccountId` or",
+			" an index.",
+			"",
+			" Indices below `0xf0` are encoded as a single byte holding the index itself.",
+		])
+	}
+}
+
 impl<AccountId, AccountIndex> Default for Address<AccountId, AccountIndex> where
 	AccountId: Member + Default,
 	AccountIndex: Member,
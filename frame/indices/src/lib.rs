@@ -28,7 +28,7 @@ use sp_runtime::traits::{
 use frame_support::{Parameter, decl_module, decl_error, decl_event, decl_storage, ensure};
 use frame_support::dispatch::DispatchResult;
 use frame_support::traits::{Currency, ReservableCurrency, Get, BalanceStatus::Reserved};
-use frame_support::weights::Weight;
+use frame_support::weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize};
 use frame_system::{ensure_signed, ensure_root};
 use self::address::Address as RawAddress;
 
@@ -43,10 +43,25 @@ type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trai
 
 pub trait WeightInfo {
 	fn claim() -> Weight;
+	fn claim_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn transfer() -> Weight;
+	fn transfer_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn free() -> Weight;
+	fn free_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_transfer() -> Weight;
+	fn force_transfer_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn freeze() -> Weight;
+	fn freeze_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 /// The module's config trait.
@@ -137,7 +152,7 @@ decl_module! {
 		/// -------------------
 		/// - DB Weight: 1 Read/Write (Accounts)
 		/// # </weight>
-		#[weight = T::WeightInfo::claim()]
+		#[weight = WithProofSize(T::WeightInfo::claim(), T::WeightInfo::claim_proof_size())]
 		fn claim(origin, index: T::AccountIndex) {
 			let who = ensure_signed(origin)?;
 
@@ -169,7 +184,7 @@ decl_module! {
 		///    - Reads: Indices Accounts, System Account (recipient)
 		///    - Writes: Indices Accounts, System Account (recipient)
 		/// # </weight>
-		#[weight = T::WeightInfo::transfer()]
+		#[weight = WithProofSize(T::WeightInfo::transfer(), T::WeightInfo::transfer_proof_size())]
 		fn transfer(origin, new: T::AccountId, index: T::AccountIndex) {
 			let who = ensure_signed(origin)?;
 			ensure!(who != new, Error::<T>::NotTransfer);
@@ -203,7 +218,7 @@ decl_module! {
 		/// -------------------
 		/// - DB Weight: 1 Read/Write (Accounts)
 		/// # </weight>
-		#[weight = T::WeightInfo::free()]
+		#[weight = WithProofSize(T::WeightInfo::free(), T::WeightInfo::free_proof_size())]
 		fn free(origin, index: T::AccountIndex) {
 			let who = ensure_signed(origin)?;
 
@@ -238,7 +253,10 @@ decl_module! {
 		///    - Reads: Indices Accounts, System Account (original owner)
 		///    - Writes: Indices Accounts, System Account (original owner)
 		/// # </weight>
-		#[weight = T::WeightInfo::force_transfer()]
+		#[weight = WithProofSize(
+			T::WeightInfo::force_transfer(),
+			T::WeightInfo::force_transfer_proof_size(),
+		)]
 		fn force_transfer(origin, new: T::AccountId, index: T::AccountIndex, freeze: bool) {
 			ensure_root(origin)?;
 
@@ -268,7 +286,7 @@ decl_module! {
 		/// -------------------
 		/// - DB Weight: 1 Read/Write (Accounts)
 		/// # </weight>
-		#[weight = T::WeightInfo::freeze()]
+		#[weight = WithProofSize(T::WeightInfo::freeze(), T::WeightInfo::freeze_proof_size())]
 		fn freeze(origin, index: T::AccountIndex) {
 			let who = ensure_signed(origin)?;
 
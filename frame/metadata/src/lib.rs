@@ -30,6 +30,7 @@ use codec::{Decode, Input, Error};
 use codec::{Encode, Output};
 use sp_std::vec::Vec;
 use sp_core::RuntimeDebug;
+use sp_type_info::{Form, MetaForm, MetaType, PortableForm, PortableRegistry, Registry, IntoPortable};
 
 #[cfg(feature = "std")]
 type StringBuf = String;
@@ -217,6 +218,11 @@ pub struct ErrorMetadata {
 /// All the metadata about errors in a module.
 pub trait ModuleErrorMetadata {
 	fn metadata() -> &'static [ErrorMetadata];
+
+	/// The type of the error enum, `None` if the module doesn't declare one.
+	fn error_type() -> Option<MetaType> {
+		None
+	}
 }
 
 impl ModuleErrorMetadata for &'static str {
@@ -300,6 +306,11 @@ pub enum StorageEntryType {
 		value: DecodeDifferentStr,
 		key2_hasher: StorageHasher,
 	},
+	NMap {
+		keys: DecodeDifferentArray<&'static str, StringBuf>,
+		hashers: DecodeDifferentArray<StorageHasher>,
+		value: DecodeDifferentStr,
+	},
 }
 
 /// A storage entry modifier.
@@ -364,8 +375,12 @@ pub enum RuntimeMetadata {
 	V10(RuntimeMetadataDeprecated),
 	/// Version 11 for runtime metadata. No longer used.
 	V11(RuntimeMetadataDeprecated),
-	/// Version 12 for runtime metadata.
-	V12(RuntimeMetadataV12),
+	/// Version 12 for runtime metadata. No longer used.
+	V12(RuntimeMetadataDeprecated),
+	/// Version 13 for runtime metadata.
+	V13(RuntimeMetadataV13),
+	/// Version 14 for runtime metadata.
+	V14(RuntimeMetadataV14),
 }
 
 /// Enum that should fail.
@@ -389,7 +404,7 @@ impl Decode for RuntimeMetadataDeprecated {
 /// The metadata of a runtime.
 #[derive(Eq, Encode, PartialEq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct RuntimeMetadataV12 {
+pub struct RuntimeMetadataV13 {
 	/// Metadata of all the modules.
 	pub modules: DecodeDifferentArray<ModuleMetadata>,
 	/// Metadata of the extrinsic.
@@ -397,7 +412,7 @@ pub struct RuntimeMetadataV12 {
 }
 
 /// The latest version of the metadata.
-pub type RuntimeMetadataLastVersion = RuntimeMetadataV12;
+pub type RuntimeMetadataLastVersion = RuntimeMetadataV14;
 
 /// All metadata about an runtime module.
 #[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
@@ -423,8 +438,198 @@ impl Into<sp_core::OpaqueMetadata> for RuntimeMetadataPrefixed {
 	}
 }
 
-impl Into<RuntimeMetadataPrefixed> for RuntimeMetadataLastVersion {
+impl Into<RuntimeMetadataPrefixed> for RuntimeMetadataV13 {
+	fn into(self) -> RuntimeMetadataPrefixed {
+		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V13(self))
+	}
+}
+
+impl Into<RuntimeMetadataPrefixed> for RuntimeMetadataV14 {
 	fn into(self) -> RuntimeMetadataPrefixed {
-		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V12(self))
+		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(self))
+	}
+}
+
+/// The metadata of a runtime, describing all its types in a portable registry.
+///
+/// In contrast to the previous versions, types are not described by their name but are
+/// referenced by their identifier in [`RuntimeMetadataV14::types`], whose entries describe
+/// the full SCALE encoding of the type.
+#[derive(Eq, Encode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+pub struct RuntimeMetadataV14 {
+	/// The registry of all types referenced by the metadata.
+	pub types: PortableRegistry,
+	/// Metadata of all the modules.
+	pub modules: Vec<ModuleMetadataV14<PortableForm>>,
+	/// Metadata of the extrinsic.
+	pub extrinsic: ExtrinsicMetadata,
+}
+
+impl RuntimeMetadataV14 {
+	/// Create the metadata of the given modules, registering all their types.
+	pub fn new(modules: Vec<ModuleMetadataV14>, extrinsic: ExtrinsicMetadata) -> Self {
+		let mut registry = Registry::new();
+		let modules = registry.map_into_portable(modules);
+		Self { types: registry.into(), modules, extrinsic }
+	}
+}
+
+/// All metadata about a runtime module.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct ModuleMetadataV14<F: Form = MetaForm> {
+	pub name: F::String,
+	pub storage: Option<StorageMetadataV14<F>>,
+	/// The `Call` enum of the module, each variant is a dispatchable.
+	pub calls: Option<F::Type>,
+	/// The `Event` enum of the module.
+	pub event: Option<F::Type>,
+	pub constants: Vec<ModuleConstantMetadataV14<F>>,
+	/// The `Error` enum of the module.
+	pub error: Option<F::Type>,
+	/// Define the index of the module, this index will be used for the encoding of module event,
+	/// call and origin variants.
+	pub index: u8,
+}
+
+impl IntoPortable for ModuleMetadataV14 {
+	type Output = ModuleMetadataV14<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		ModuleMetadataV14 {
+			name: self.name.into_portable(registry),
+			storage: self.storage.map(|storage| storage.into_portable(registry)),
+			calls: self.calls.map(|ty| registry.register_type(&ty)),
+			event: self.event.map(|ty| registry.register_type(&ty)),
+			constants: registry.map_into_portable(self.constants),
+			error: self.error.map(|ty| registry.register_type(&ty)),
+			index: self.index,
+		}
+	}
+}
+
+/// All metadata of the storage.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct StorageMetadataV14<F: Form = MetaForm> {
+	/// The common prefix used by all storage entries.
+	pub prefix: F::String,
+	pub entries: Vec<StorageEntryMetadataV14<F>>,
+}
+
+impl IntoPortable for StorageMetadataV14 {
+	type Output = StorageMetadataV14<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		StorageMetadataV14 {
+			prefix: self.prefix.into_portable(registry),
+			entries: registry.map_into_portable(self.entries),
+		}
+	}
+}
+
+/// All the metadata about one storage entry.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct StorageEntryMetadataV14<F: Form = MetaForm> {
+	pub name: F::String,
+	pub modifier: StorageEntryModifier,
+	pub ty: StorageEntryTypeV14<F>,
+	pub default: Vec<u8>,
+	pub documentation: Vec<F::String>,
+}
+
+impl IntoPortable for StorageEntryMetadataV14 {
+	type Output = StorageEntryMetadataV14<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		StorageEntryMetadataV14 {
+			name: self.name.into_portable(registry),
+			modifier: self.modifier,
+			ty: self.ty.into_portable(registry),
+			default: self.default,
+			documentation: registry.map_into_portable(self.documentation),
+		}
+	}
+}
+
+/// A storage entry type.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize")))]
+pub enum StorageEntryTypeV14<F: Form = MetaForm> {
+	Plain(F::Type),
+	Map {
+		hasher: StorageHasher,
+		key: F::Type,
+		value: F::Type,
+	},
+	DoubleMap {
+		hasher: StorageHasher,
+		key1: F::Type,
+		key2: F::Type,
+		value: F::Type,
+		key2_hasher: StorageHasher,
+	},
+	NMap {
+		keys: Vec<F::Type>,
+		hashers: Vec<StorageHasher>,
+		value: F::Type,
+	},
+}
+
+impl IntoPortable for StorageEntryTypeV14 {
+	type Output = StorageEntryTypeV14<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		match self {
+			StorageEntryTypeV14::Plain(value) => StorageEntryTypeV14::Plain(registry.register_type(&value)),
+			StorageEntryTypeV14::Map { hasher, key, value } => StorageEntryTypeV14::Map {
+				hasher,
+				key: registry.register_type(&key),
+				value: registry.register_type(&value),
+			},
+			StorageEntryTypeV14::DoubleMap { hasher, key1, key2, value, key2_hasher } =>
+				StorageEntryTypeV14::DoubleMap {
+					hasher,
+					key1: registry.register_type(&key1),
+					key2: registry.register_type(&key2),
+					value: registry.register_type(&value),
+					key2_hasher,
+				},
+			StorageEntryTypeV14::NMap { keys, hashers, value } => StorageEntryTypeV14::NMap {
+				keys: registry.register_types(keys),
+				hashers,
+				value: registry.register_type(&value),
+			},
+		}
+	}
+}
+
+/// All the metadata about one module constant.
+#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct ModuleConstantMetadataV14<F: Form = MetaForm> {
+	pub name: F::String,
+	pub ty: F::Type,
+	pub value: Vec<u8>,
+	pub documentation: Vec<F::String>,
+}
+
+impl IntoPortable for ModuleConstantMetadataV14 {
+	type Output = ModuleConstantMetadataV14<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		ModuleConstantMetadataV14 {
+			name: self.name.into_portable(registry),
+			ty: registry.register_type(&self.ty),
+			value: self.value,
+			documentation: registry.map_into_portable(self.documentation),
+		}
 	}
 }
@@ -298,6 +298,50 @@ benchmarks! {
 		assert!(!Multisigs::<T>::contains_key(multi_account_id, call_hash));
 		assert!(!Calls::<T>::contains_key(call_hash));
 	}
+
+	create_weighted {
+		// Signatories, need at least 1 person
+		let s in 1 .. T::MaxSignatories::get() as u32;
+		let (signatories, _) = setup_multi::<T>(s, 0)?;
+		let signatories = signatories.into_iter().map(|a| (a, 1)).collect::<Vec<_>>();
+		let caller = account("caller", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let multi_account_id = Multisig::<T>::weighted_account_id(&caller, 0, None);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), signatories, s, 0)
+	verify {
+		assert!(WeightedMultisigs::<T>::contains_key(multi_account_id));
+	}
+
+	set_weighted_signatories {
+		// Signatories, need at least 1 person
+		let s in 1 .. T::MaxSignatories::get() as u32;
+		let (signatories, _) = setup_multi::<T>(s, 0)?;
+		let signatories = signatories.into_iter().map(|a| (a, 1)).collect::<Vec<_>>();
+		let caller = account("caller", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let multi_account_id = Multisig::<T>::weighted_account_id(&caller, 0, None);
+		let o = RawOrigin::Signed(caller).into();
+		Multisig::<T>::create_weighted(o, signatories.clone(), s, 0)?;
+	}: _(RawOrigin::Signed(multi_account_id.clone()), signatories, 1)
+	verify {
+		assert_eq!(WeightedMultisigs::<T>::get(multi_account_id).map(|m| m.threshold), Some(1));
+	}
+
+	dissolve_weighted {
+		let (signatories, _) = setup_multi::<T>(T::MaxSignatories::get().into(), 0)?;
+		let signatories = signatories.into_iter().map(|a| (a, 1)).collect::<Vec<_>>();
+		let caller = account("caller", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let multi_account_id = Multisig::<T>::weighted_account_id(&caller, 0, None);
+		let o = RawOrigin::Signed(caller).into();
+		Multisig::<T>::create_weighted(o, signatories, 1, 0)?;
+	}: _(RawOrigin::Signed(multi_account_id.clone()))
+	verify {
+		assert!(!WeightedMultisigs::<T>::contains_key(multi_account_id));
+	}
 }
 
 #[cfg(test)]
@@ -319,6 +363,9 @@ mod tests {
 			assert_ok!(test_benchmark_approve_as_multi_approve::<Test>());
 			assert_ok!(test_benchmark_approve_as_multi_complete::<Test>());
 			assert_ok!(test_benchmark_cancel_as_multi::<Test>());
+			assert_ok!(test_benchmark_create_weighted::<Test>());
+			assert_ok!(test_benchmark_set_weighted_signatories::<Test>());
+			assert_ok!(test_benchmark_dissolve_weighted::<Test>());
 		});
 	}
 }
@@ -31,6 +31,12 @@
 //! operation. This is useful for multisig wallets where cryptographic threshold signatures are
 //! not available or desired.
 //!
+//! Alternatively, a weighted multisig account may be created. Its account ID does not depend on
+//! its signatories, each signatory carries an approval weight and a call is dispatched once the
+//! approvals reach the account's threshold weight. A signatory whose weight reaches the threshold
+//! on its own dispatches a call right away. The signatories and the threshold are kept in storage
+//! and may be changed by the multisig account itself, without moving any funds.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -39,6 +45,13 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `create_weighted` - Create a weighted multisig account.
+//! * `as_weighted_multi` - Approve and if possible dispatch a call from a weighted multisig account.
+//! * `approve_as_weighted_multi` - Approve a call from a weighted multisig account.
+//! * `cancel_as_weighted_multi` - Cancel a call from a weighted multisig account.
+//! * `set_weighted_signatories` - Change the signatories and threshold of a weighted multisig
+//!   account, called by the account itself.
+//! * `dissolve_weighted` - Remove a weighted multisig account, called by the account itself.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
@@ -48,10 +61,11 @@
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_io::hashing::blake2_256;
 use frame_support::{decl_module, decl_event, decl_error, decl_storage, Parameter, ensure, RuntimeDebug};
 use frame_support::{traits::{Get, ReservableCurrency, Currency},
-	weights::{Weight, GetDispatchInfo},
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, GetDispatchInfo},
 	dispatch::{DispatchResultWithPostInfo, DispatchErrorWithPostInfo, PostDispatchInfo},
 };
 use frame_system::{self as system, ensure_signed, RawOrigin};
@@ -67,15 +81,57 @@ pub type OpaqueCall = Vec<u8>;
 
 pub trait WeightInfo {
 	fn as_multi_threshold_1(z: u32, ) -> Weight;
+	fn as_multi_threshold_1_proof_size(_z: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn as_multi_create(s: u32, z: u32, ) -> Weight;
+	fn as_multi_create_proof_size(_s: u32, _z: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn as_multi_create_store(s: u32, z: u32, ) -> Weight;
+	fn as_multi_create_store_proof_size(_s: u32, _z: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn as_multi_approve(s: u32, z: u32, ) -> Weight;
+	fn as_multi_approve_proof_size(_s: u32, _z: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn as_multi_approve_store(s: u32, z: u32, ) -> Weight;
+	fn as_multi_approve_store_proof_size(_s: u32, _z: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn as_multi_complete(s: u32, z: u32, ) -> Weight;
+	fn as_multi_complete_proof_size(_s: u32, _z: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn approve_as_multi_create(s: u32, ) -> Weight;
+	fn approve_as_multi_create_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn approve_as_multi_approve(s: u32, ) -> Weight;
+	fn approve_as_multi_approve_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn approve_as_multi_complete(s: u32, ) -> Weight;
+	fn approve_as_multi_complete_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel_as_multi(s: u32, ) -> Weight;
+	fn cancel_as_multi_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn create_weighted(s: u32, ) -> Weight;
+	fn create_weighted_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn set_weighted_signatories(s: u32, ) -> Weight;
+	fn set_weighted_signatories_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn dissolve_weighted() -> Weight;
+	fn dissolve_weighted_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 /// Configuration trait.
@@ -113,7 +169,7 @@ pub trait Trait: frame_system::Trait {
 /// A global extrinsic index, formed as the extrinsic index within a block, together with that
 /// block's height. This allows a transaction in which a multisig operation of a particular
 /// composite was created to be uniquely identified.
-#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct Timepoint<BlockNumber> {
 	/// The height of the chain at the point in time.
 	height: BlockNumber,
@@ -122,7 +178,7 @@ pub struct Timepoint<BlockNumber> {
 }
 
 /// An open multisig operation.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct Multisig<BlockNumber, Balance, AccountId> {
 	/// The extrinsic when the multisig operation was opened.
 	when: Timepoint<BlockNumber>,
@@ -134,6 +190,48 @@ pub struct Multisig<BlockNumber, Balance, AccountId> {
 	approvals: Vec<AccountId>,
 }
 
+/// A weighted multisig account.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct WeightedMultisig<Balance, AccountId> {
+	/// The signatories together with the weight of their approval. Always sorted by account.
+	signatories: Vec<(AccountId, u32)>,
+	/// The total weight of approvals needed before a call is dispatched.
+	threshold: u32,
+	/// The amount held in reserve of the `depositor`, to be returned once the account is
+	/// dissolved.
+	deposit: Balance,
+	/// The account who created it.
+	depositor: AccountId,
+}
+
+/// A composite account on whose behalf operations are approved, together with the rules of their
+/// approval.
+///
+/// The signatories of a deterministic multisig account all carry a weight of one.
+struct Composite<AccountId> {
+	/// The composite account.
+	id: AccountId,
+	/// The signatories together with the weight of their approval. Always sorted by account.
+	signatories: Vec<(AccountId, u32)>,
+	/// The total weight of approvals needed before a call is dispatched.
+	threshold: u32,
+	/// The number of `DepositFactor`s to reserve for an operation.
+	deposit_factor: u32,
+}
+
+impl<AccountId: Ord> Composite<AccountId> {
+	/// The weight of the approval of `who`; zero if `who` is not a signatory.
+	fn weight_of(&self, who: &AccountId) -> u32 {
+		self.signatories.binary_search_by(|(s, _)| s.cmp(who))
+			.map_or(0, |i| self.signatories[i].1)
+	}
+
+	/// The total weight of the given approvals.
+	fn approval(&self, approvals: &[AccountId]) -> u32 {
+		approvals.iter().fold(0, |total, who| total.saturating_add(self.weight_of(who)))
+	}
+}
+
 decl_storage! {
 	trait Store for Module<T: Trait> as Multisig {
 		/// The set of open multisig operations.
@@ -142,12 +240,16 @@ decl_storage! {
 			=> Option<Multisig<T::BlockNumber, BalanceOf<T>, T::AccountId>>;
 
 		pub Calls: map hasher(identity) [u8; 32] => Option<(OpaqueCall, T::AccountId, BalanceOf<T>)>;
+
+		/// The weighted multisig accounts.
+		pub WeightedMultisigs: map hasher(twox_64_concat) T::AccountId
+			=> Option<WeightedMultisig<BalanceOf<T>, T::AccountId>>;
 	}
 }
 
 decl_error! {
 	pub enum Error for Module<T: Trait> {
-		/// Threshold must be 2 or greater.
+		/// Threshold must be 2 or greater, or non-zero for a weighted multisig.
 		MinimumThreshold,
 		/// Call is already approved by this signatory.
 		AlreadyApproved,
@@ -175,6 +277,14 @@ decl_error! {
 		WeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The sender is not a signatory of the multisig account.
+		NotSignatory,
+		/// A signatory was given a weight of zero.
+		ZeroWeight,
+		/// The total weight of the signatories is below the threshold.
+		UnreachableThreshold,
+		/// The weighted multisig account already exists.
+		AlreadyExists,
 	}
 }
 
@@ -194,6 +304,12 @@ decl_event! {
 		MultisigExecuted(AccountId, Timepoint<BlockNumber>, AccountId, CallHash, DispatchResult),
 		/// A multisig operation has been cancelled. \[cancelling, timepoint, multisig, call_hash\]
 		MultisigCancelled(AccountId, Timepoint<BlockNumber>, AccountId, CallHash),
+		/// A weighted multisig account has been created. \[creator, multisig\]
+		WeightedMultisigCreated(AccountId, AccountId),
+		/// The signatories or threshold of a weighted multisig account have changed. \[multisig\]
+		WeightedSignatoriesChanged(AccountId),
+		/// A weighted multisig account has been dissolved. \[multisig\]
+		WeightedMultisigDissolved(AccountId),
 	}
 }
 
@@ -235,12 +351,15 @@ decl_module! {
 		/// - DB Weight: None
 		/// - Plus Call Weight
 		/// # </weight>
-		#[weight = (
-			T::WeightInfo::as_multi_threshold_1(call.using_encoded(|c| c.len() as u32))
-				.saturating_add(call.get_dispatch_info().weight
-			),
-			call.get_dispatch_info().class,
-		)]
+		#[weight = {
+			let z = call.using_encoded(|c| c.len() as u32);
+			let di = call.get_dispatch_info();
+
+			WithProofSize(
+				(T::WeightInfo::as_multi_threshold_1(z).saturating_add(di.weight), di.class),
+				T::WeightInfo::as_multi_threshold_1_proof_size(z).saturating_add(di.proof_size),
+			)
+		}]
 		fn as_multi_threshold_1(origin,
 			other_signatories: Vec<T::AccountId>,
 			call: Box<<T as Trait>::Call>,
@@ -324,11 +443,17 @@ decl_module! {
 			let s = other_signatories.len() as u32;
 			let z = call.len() as u32;
 
-			T::WeightInfo::as_multi_create(s, z)
-			.max(T::WeightInfo::as_multi_create_store(s, z))
-			.max(T::WeightInfo::as_multi_approve(s, z))
-			.max(T::WeightInfo::as_multi_complete(s, z))
-			.saturating_add(*max_weight)
+			WithProofSize(
+				T::WeightInfo::as_multi_create(s, z)
+				.max(T::WeightInfo::as_multi_create_store(s, z))
+				.max(T::WeightInfo::as_multi_approve(s, z))
+				.max(T::WeightInfo::as_multi_complete(s, z))
+				.saturating_add(*max_weight),
+				T::WeightInfo::as_multi_create_proof_size(s, z)
+				.max(T::WeightInfo::as_multi_create_store_proof_size(s, z))
+				.max(T::WeightInfo::as_multi_approve_proof_size(s, z))
+				.max(T::WeightInfo::as_multi_complete_proof_size(s, z)),
+			)
 		}]
 		fn as_multi(origin,
 			threshold: u16,
@@ -339,7 +464,8 @@ decl_module! {
 			max_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::operate(who, threshold, other_signatories, maybe_timepoint, CallOrHash::Call(call, store_call), max_weight)
+			let composite = Self::composite(who.clone(), threshold, other_signatories)?;
+			Self::operate(who, composite, maybe_timepoint, CallOrHash::Call(call, store_call), max_weight)
 		}
 
 		/// Register approval for a dispatch to be made from a deterministic composite account if
@@ -381,10 +507,15 @@ decl_module! {
 		#[weight = {
 			let s = other_signatories.len() as u32;
 
-			T::WeightInfo::approve_as_multi_create(s)
-			.max(T::WeightInfo::approve_as_multi_approve(s))
-			.max(T::WeightInfo::approve_as_multi_complete(s))
-			.saturating_add(*max_weight)
+			WithProofSize(
+				T::WeightInfo::approve_as_multi_create(s)
+				.max(T::WeightInfo::approve_as_multi_approve(s))
+				.max(T::WeightInfo::approve_as_multi_complete(s))
+				.saturating_add(*max_weight),
+				T::WeightInfo::approve_as_multi_create_proof_size(s)
+				.max(T::WeightInfo::approve_as_multi_approve_proof_size(s))
+				.max(T::WeightInfo::approve_as_multi_complete_proof_size(s)),
+			)
 		}]
 		fn approve_as_multi(origin,
 			threshold: u16,
@@ -394,7 +525,8 @@ decl_module! {
 			max_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::operate(who, threshold, other_signatories, maybe_timepoint, CallOrHash::Hash(call_hash), max_weight)
+			let composite = Self::composite(who.clone(), threshold, other_signatories)?;
+			Self::operate(who, composite, maybe_timepoint, CallOrHash::Hash(call_hash), max_weight)
 		}
 
 		/// Cancel a pre-existing, on-going multisig transaction. Any deposit reserved previously
@@ -423,7 +555,10 @@ decl_module! {
 		///     - Read: Multisig Storage, [Caller Account], Refund Account, Calls
 		///     - Write: Multisig Storage, [Caller Account], Refund Account, Calls
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel_as_multi(other_signatories.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel_as_multi(other_signatories.len() as u32),
+			T::WeightInfo::cancel_as_multi_proof_size(other_signatories.len() as u32),
+		)]
 		fn cancel_as_multi(origin,
 			threshold: u16,
 			other_signatories: Vec<T::AccountId>,
@@ -431,25 +566,257 @@ decl_module! {
 			call_hash: [u8; 32],
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
-			let max_sigs = T::MaxSignatories::get() as usize;
-			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
-			ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
-			let signatories = Self::ensure_sorted_and_insert(other_signatories, who.clone())?;
+			let composite = Self::composite(who.clone(), threshold, other_signatories)?;
+			Self::cancel(who, composite.id, timepoint, call_hash)
+		}
 
-			let id = Self::multi_account_id(&signatories, threshold);
+		/// Create a weighted multisig account, whose account ID does not depend on its
+		/// signatories.
+		///
+		/// Payment: `DepositBase` plus `MaxSignatories` times `DepositFactor` will be reserved.
+		/// It is returned once the account is dissolved.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `signatories`: The accounts who can approve dispatches of the multisig account,
+		/// together with the weight of their approval. Must be ordered by account and may not be
+		/// empty. The sender need not be part of it.
+		/// - `threshold`: The total weight of approvals needed before a dispatch is executed.
+		/// - `index`: A disambiguation index, in case this is called multiple times in the same
+		/// transaction (e.g. with `utility::batch`). Unless you're using `batch` you probably just
+		/// want to use `0`.
+		///
+		/// # <weight>
+		/// - `O(S)`.
+		/// - One balance-reserve operation.
+		/// - One encode & hash, one insert `O(S)` where `S` is the number of signatories.
+		/// - One event.
+		/// -------------------------------
+		/// - DB Weight:
+		///     - Read: Weighted Multisig Storage, [Caller Account]
+		///     - Write: Weighted Multisig Storage, [Caller Account]
+		/// # </weight>
+		#[weight = WithProofSize(
+			T::WeightInfo::create_weighted(signatories.len() as u32),
+			T::WeightInfo::create_weighted_proof_size(signatories.len() as u32),
+		)]
+		fn create_weighted(origin,
+			signatories: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+			index: u16,
+		) {
+			let who = ensure_signed(origin)?;
+			Self::ensure_weighted_signatories(&signatories, threshold)?;
 
-			let m = <Multisigs<T>>::get(&id, call_hash)
-				.ok_or(Error::<T>::NotFound)?;
-			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
-			ensure!(m.depositor == who, Error::<T>::NotOwner);
+			let id = Self::weighted_account_id(&who, index, None);
+			ensure!(!WeightedMultisigs::<T>::contains_key(&id), Error::<T>::AlreadyExists);
+			let deposit = T::DepositBase::get()
+				+ T::DepositFactor::get() * u32::from(T::MaxSignatories::get()).into();
+			T::Currency::reserve(&who, deposit)?;
+
+			WeightedMultisigs::<T>::insert(&id, WeightedMultisig {
+				signatories,
+				threshold,
+				deposit,
+				depositor: who.clone(),
+			});
+			Self::deposit_event(RawEvent::WeightedMultisigCreated(who, id));
+		}
+
+		/// Register approval for a dispatch to be made from a weighted multisig account.
+		///
+		/// If the approvals, weighed by the current signatories of the account, reach its
+		/// threshold, then dispatch the call. Approvals of accounts which are no longer signatories
+		/// do not count. If the weight of the sender alone reaches the threshold, the call is
+		/// dispatched right away, without recording the operation.
+		///
+		/// Payment: `DepositBase` will be reserved if this is the first approval and the call is
+		/// not dispatched right away, plus the number of signatories times `DepositFactor`. It is
+		/// returned once this dispatch happens or is cancelled.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
+		/// not the first approval, then it must be `Some`, with the timepoint (block number and
+		/// transaction index) of the first approval transaction.
+		/// - `call`: The call to be executed.
+		///
+		/// NOTE: Unless this is the final approval, you will generally want to use
+		/// `approve_as_weighted_multi` instead, since it only requires a hash of the call.
+		///
+		/// # <weight>
+		/// Same as `as_multi`, plus one read of the weighted multisig account.
+		/// # </weight>
+		#[weight = {
+			let s = T::MaxSignatories::get() as u32 - 1;
+			let z = call.len() as u32;
+
+			WithProofSize(
+				T::WeightInfo::as_multi_create(s, z)
+				.max(T::WeightInfo::as_multi_create_store(s, z))
+				.max(T::WeightInfo::as_multi_approve(s, z))
+				.max(T::WeightInfo::as_multi_complete(s, z))
+				.saturating_add(T::DbWeight::get().reads(1))
+				.saturating_add(*max_weight),
+				T::WeightInfo::as_multi_create_proof_size(s, z)
+				.max(T::WeightInfo::as_multi_create_store_proof_size(s, z))
+				.max(T::WeightInfo::as_multi_approve_proof_size(s, z))
+				.max(T::WeightInfo::as_multi_complete_proof_size(s, z)),
+			)
+		}]
+		fn as_weighted_multi(origin,
+			multisig: T::AccountId,
+			maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
+			call: OpaqueCall,
+			store_call: bool,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let composite = Self::weighted_composite(multisig)?;
+			Self::operate(who, composite, maybe_timepoint, CallOrHash::Call(call, store_call), max_weight)
+		}
+
+		/// Register approval for a dispatch to be made from a weighted multisig account.
+		///
+		/// Payment: `DepositBase` will be reserved if this is the first approval, plus the number of
+		/// signatories times `DepositFactor`. It is returned once this dispatch happens or is
+		/// cancelled.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
+		/// not the first approval, then it must be `Some`, with the timepoint (block number and
+		/// transaction index) of the first approval transaction.
+		/// - `call_hash`: The hash of the call to be executed.
+		///
+		/// NOTE: If this is the final approval, you will want to use `as_weighted_multi` instead.
+		///
+		/// # <weight>
+		/// Same as `approve_as_multi`, plus one read of the weighted multisig account.
+		/// # </weight>
+		#[weight = {
+			let s = T::MaxSignatories::get() as u32 - 1;
+
+			WithProofSize(
+				T::WeightInfo::approve_as_multi_create(s)
+				.max(T::WeightInfo::approve_as_multi_approve(s))
+				.max(T::WeightInfo::approve_as_multi_complete(s))
+				.saturating_add(T::DbWeight::get().reads(1))
+				.saturating_add(*max_weight),
+				T::WeightInfo::approve_as_multi_create_proof_size(s)
+				.max(T::WeightInfo::approve_as_multi_approve_proof_size(s))
+				.max(T::WeightInfo::approve_as_multi_complete_proof_size(s)),
+			)
+		}]
+		fn approve_as_weighted_multi(origin,
+			multisig: T::AccountId,
+			maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
+			call_hash: [u8; 32],
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let composite = Self::weighted_composite(multisig)?;
+			Self::operate(who, composite, maybe_timepoint, CallOrHash::Hash(call_hash), max_weight)
+		}
 
-			let _ = T::Currency::unreserve(&m.depositor, m.deposit);
-			<Multisigs<T>>::remove(&id, &call_hash);
-			Self::clear_call(&call_hash);
+		/// Cancel a pre-existing, on-going dispatch of a weighted multisig account. Any deposit
+		/// reserved previously for this operation will be unreserved on success.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account which made the first
+		/// approval.
+		///
+		/// - `multisig`: The weighted multisig account.
+		/// - `timepoint`: The timepoint (block number and transaction index) of the first approval
+		/// transaction for this dispatch.
+		/// - `call_hash`: The hash of the call to be executed.
+		///
+		/// # <weight>
+		/// Same as `cancel_as_multi`.
+		/// # </weight>
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel_as_multi(T::MaxSignatories::get() as u32 - 1),
+			T::WeightInfo::cancel_as_multi_proof_size(T::MaxSignatories::get() as u32 - 1),
+		)]
+		fn cancel_as_weighted_multi(origin,
+			multisig: T::AccountId,
+			timepoint: Timepoint<T::BlockNumber>,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::cancel(who, multisig, timepoint, call_hash)
+		}
 
-			Self::deposit_event(RawEvent::MultisigCancelled(who, timepoint, id, call_hash));
-			Ok(())
+		/// Replace the signatories and threshold of a weighted multisig account.
+		///
+		/// Operations which are underway are unaffected, except that their approvals are weighed
+		/// by the new signatories.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the weighted multisig account,
+		/// i.e. it is dispatched through `as_weighted_multi`.
+		///
+		/// - `signatories`: The new signatories together with the weight of their approval. Must
+		/// be ordered by account and may not be empty.
+		/// - `threshold`: The new total weight of approvals needed before a dispatch is executed.
+		///
+		/// # <weight>
+		/// - `O(S)`.
+		/// - One mutate `O(S)` where `S` is the number of signatories.
+		/// - One event.
+		/// -------------------------------
+		/// - DB Weight:
+		///     - Read: Weighted Multisig Storage
+		///     - Write: Weighted Multisig Storage
+		/// # </weight>
+		#[weight = WithProofSize(
+			T::WeightInfo::set_weighted_signatories(signatories.len() as u32),
+			T::WeightInfo::set_weighted_signatories_proof_size(signatories.len() as u32),
+		)]
+		fn set_weighted_signatories(origin,
+			signatories: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) {
+			let who = ensure_signed(origin)?;
+			Self::ensure_weighted_signatories(&signatories, threshold)?;
+			WeightedMultisigs::<T>::try_mutate(&who, |maybe_multisig| -> DispatchResult {
+				let multisig = maybe_multisig.as_mut().ok_or(Error::<T>::NotFound)?;
+				multisig.signatories = signatories;
+				multisig.threshold = threshold;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::WeightedSignatoriesChanged(who));
+		}
+
+		/// Remove a weighted multisig account, returning its deposit to the account which created
+		/// it.
+		///
+		/// WARNING: **All access to this account will be lost.** Any funds held in it will be
+		/// inaccessible. Operations which are underway may still be cancelled through
+		/// `cancel_as_weighted_multi`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the weighted multisig account,
+		/// i.e. it is dispatched through `as_weighted_multi`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One balance-unreserve operation.
+		/// - One remove.
+		/// - One event.
+		/// -------------------------------
+		/// - DB Weight:
+		///     - Read: Weighted Multisig Storage, Depositor Account
+		///     - Write: Weighted Multisig Storage, Depositor Account
+		/// # </weight>
+		#[weight = WithProofSize(
+			T::WeightInfo::dissolve_weighted(),
+			T::WeightInfo::dissolve_weighted_proof_size(),
+		)]
+		fn dissolve_weighted(origin) {
+			let who = ensure_signed(origin)?;
+			let multisig = WeightedMultisigs::<T>::take(&who).ok_or(Error::<T>::NotFound)?;
+			T::Currency::unreserve(&multisig.depositor, multisig.deposit);
+			Self::deposit_event(RawEvent::WeightedMultisigDissolved(who));
 		}
 	}
 }
@@ -464,22 +831,81 @@ impl<T: Trait> Module<T> {
 		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
 	}
 
-	fn operate(
+	/// Derive the account ID of a weighted multisig account created by `who`.
+	///
+	/// If `maybe_when` is `None`, the current timepoint is used.
+	pub fn weighted_account_id(
+		who: &T::AccountId,
+		index: u16,
+		maybe_when: Option<Timepoint<T::BlockNumber>>,
+	) -> T::AccountId {
+		let when = maybe_when.unwrap_or_else(Self::timepoint);
+		let entropy = (b"modlpy/weighted_", who, when, index).using_encoded(blake2_256);
+		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+	}
+
+	/// The deterministic multisig account of `who` and `other_signatories` with `threshold`.
+	fn composite(
 		who: T::AccountId,
 		threshold: u16,
 		other_signatories: Vec<T::AccountId>,
-		maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
-		call_or_hash: CallOrHash,
-		max_weight: Weight,
-	) -> DispatchResultWithPostInfo {
+	) -> Result<Composite<T::AccountId>, DispatchError> {
 		ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 		let max_sigs = T::MaxSignatories::get() as usize;
 		ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
-		let other_signatories_len = other_signatories.len();
-		ensure!(other_signatories_len < max_sigs, Error::<T>::TooManySignatories);
-		let signatories = Self::ensure_sorted_and_insert(other_signatories, who.clone())?;
+		ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
+		let signatories = Self::ensure_sorted_and_insert(other_signatories, who)?;
+
+		Ok(Composite {
+			id: Self::multi_account_id(&signatories, threshold),
+			signatories: signatories.into_iter().map(|s| (s, 1)).collect(),
+			threshold: threshold.into(),
+			deposit_factor: threshold.into(),
+		})
+	}
 
-		let id = Self::multi_account_id(&signatories, threshold);
+	/// The weighted multisig account `id`.
+	fn weighted_composite(id: T::AccountId) -> Result<Composite<T::AccountId>, DispatchError> {
+		let multisig = WeightedMultisigs::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+		Ok(Composite {
+			id,
+			deposit_factor: multisig.signatories.len() as u32,
+			signatories: multisig.signatories,
+			threshold: multisig.threshold,
+		})
+	}
+
+	/// Check that weighted `signatories` are sorted, have non-zero weights and can reach
+	/// `threshold`.
+	fn ensure_weighted_signatories(signatories: &[(T::AccountId, u32)], threshold: u32) -> DispatchResult {
+		ensure!(threshold >= 1, Error::<T>::MinimumThreshold);
+		ensure!(!signatories.is_empty(), Error::<T>::TooFewSignatories);
+		ensure!(
+			signatories.len() <= T::MaxSignatories::get() as usize,
+			Error::<T>::TooManySignatories,
+		);
+		ensure!(
+			signatories.windows(2).all(|w| w[0].0 < w[1].0),
+			Error::<T>::SignatoriesOutOfOrder,
+		);
+		ensure!(signatories.iter().all(|(_, w)| *w > 0), Error::<T>::ZeroWeight);
+		let total = signatories.iter().fold(0u32, |total, (_, w)| total.saturating_add(*w));
+		ensure!(total >= threshold, Error::<T>::UnreachableThreshold);
+		Ok(())
+	}
+
+	fn operate(
+		who: T::AccountId,
+		composite: Composite<T::AccountId>,
+		maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
+		call_or_hash: CallOrHash,
+		max_weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let weight = composite.weight_of(&who);
+		ensure!(weight > 0, Error::<T>::NotSignatory);
+		let other_signatories_len = composite.signatories.len() - 1;
+		let threshold = composite.threshold;
+		let id = composite.id.clone();
 
 		// Threshold > 1; this means it's a multi-step operation. We extract the `call_hash`.
 		let (call_hash, call_len, maybe_call, store) = match call_or_hash {
@@ -498,11 +924,11 @@ impl<T: Trait> Module<T> {
 			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
 
 			// Ensure that either we have not yet signed or that it is at threshold.
-			let mut approvals = m.approvals.len() as u16;
+			let mut approvals = composite.approval(&m.approvals);
 			// We only bother with the approval if we're below threshold.
 			let maybe_pos = m.approvals.binary_search(&who).err().filter(|_| approvals < threshold);
 			// Bump approvals if not yet voted and the vote is needed.
-			if maybe_pos.is_some() { approvals += 1; }
+			if maybe_pos.is_some() { approvals = approvals.saturating_add(weight); }
 
 			// We only bother fetching/decoding call if we know that we're ready to execute.
 			let maybe_approved_call = if approvals >= threshold {
@@ -570,8 +996,32 @@ impl<T: Trait> Module<T> {
 			// Not yet started; there should be no timepoint given.
 			ensure!(maybe_timepoint.is_none(), Error::<T>::UnexpectedTimepoint);
 
+			// The approval of a weighty enough signatory of a weighted multisig reaches the
+			// threshold on its own, so the call is dispatched right away if it's available.
+			let maybe_approved_call = if weight >= threshold {
+				Self::get_call(&call_hash, maybe_call.as_ref().map(|c| c.as_ref()))
+			} else { None };
+
+			if let Some((call, call_len)) = maybe_approved_call {
+				// verify weight
+				ensure!(call.get_dispatch_info().weight <= max_weight, Error::<T>::WeightTooLow);
+
+				// A call stored by an earlier operation is left for it.
+				let result = call.dispatch(RawOrigin::Signed(id.clone()).into());
+				Self::deposit_event(RawEvent::MultisigExecuted(
+					who, Self::timepoint(), id, call_hash, result.map(|_| ()).map_err(|e| e.error)
+				));
+				return Ok(get_result_weight(result).map(|actual_weight|
+					T::WeightInfo::as_multi_complete(
+						other_signatories_len as u32,
+						call_len as u32
+					).saturating_add(actual_weight)
+				).into())
+			}
+
 			// Just start the operation by recording it in storage.
-			let deposit = T::DepositBase::get() + T::DepositFactor::get() * threshold.into();
+			let deposit = T::DepositBase::get()
+				+ T::DepositFactor::get() * composite.deposit_factor.into();
 
 			// Store the call if desired.
 			let stored = if let Some(data) = maybe_call.filter(|_| store) {
@@ -606,6 +1056,26 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// Cancel the operation of `id` on `call_hash`, which was begun by `who` at `timepoint`.
+	fn cancel(
+		who: T::AccountId,
+		id: T::AccountId,
+		timepoint: Timepoint<T::BlockNumber>,
+		call_hash: [u8; 32],
+	) -> DispatchResult {
+		let m = <Multisigs<T>>::get(&id, call_hash)
+			.ok_or(Error::<T>::NotFound)?;
+		ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
+		ensure!(m.depositor == who, Error::<T>::NotOwner);
+
+		let _ = T::Currency::unreserve(&m.depositor, m.deposit);
+		<Multisigs<T>>::remove(&id, &call_hash);
+		Self::clear_call(&call_hash);
+
+		Self::deposit_event(RawEvent::MultisigCancelled(who, timepoint, id, call_hash));
+		Ok(())
+	}
+
 	/// Place a call's encoded data in storage, reserving funds as appropriate.
 	///
 	/// We store `data` here because storing `call` would result in needing another `.encode`.
@@ -77,6 +77,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -108,6 +111,7 @@ impl Filter<Call> for TestBaseCallFilter {
 	fn filter(c: &Call) -> bool {
 		match *c {
 			Call::Balances(_) => true,
+			Call::Multisig(_) => true,
 			// Needed for benchmarking
 			Call::System(frame_system::Call::remark(_)) => true,
 			_ => false,
@@ -576,3 +580,159 @@ fn multisig_handles_no_preimage_after_all_approve() {
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
+
+#[test]
+fn create_weighted_checks_signatories() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1)], 0, 0),
+			Error::<Test>::MinimumThreshold,
+		);
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![], 1, 0),
+			Error::<Test>::TooFewSignatories,
+		);
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1), (3, 1), (5, 1)], 1, 0),
+			Error::<Test>::TooManySignatories,
+		);
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![(2, 1), (1, 1)], 1, 0),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 0)], 1, 0),
+			Error::<Test>::ZeroWeight,
+		);
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1)], 3, 0),
+			Error::<Test>::UnreachableThreshold,
+		);
+
+		assert_ok!(Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1)], 2, 0));
+		let multi = Multisig::weighted_account_id(&4, 0, None);
+		expect_event(RawEvent::WeightedMultisigCreated(4, multi));
+		assert_eq!(Balances::reserved_balance(4), 4);
+		assert_noop!(
+			Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1)], 2, 0),
+			Error::<Test>::AlreadyExists,
+		);
+	});
+}
+
+#[test]
+fn weighted_multisig_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Multisig::create_weighted(Origin::signed(4), vec![(1, 2), (2, 1), (3, 1)], 3, 0));
+		let multi = Multisig::weighted_account_id(&4, 0, None);
+		assert_ok!(Balances::transfer(Origin::signed(4), multi, 5));
+
+		let call = Call::Balances(BalancesCall::transfer(6, 4));
+		let call_weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+		let hash = blake2_256(&data);
+		assert_noop!(
+			Multisig::approve_as_weighted_multi(Origin::signed(5), multi, None, hash.clone(), 0),
+			Error::<Test>::NotSignatory,
+		);
+		assert_ok!(Multisig::approve_as_weighted_multi(Origin::signed(2), multi, None, hash.clone(), 0));
+		assert_eq!(Balances::reserved_balance(2), 4);
+		assert_ok!(Multisig::approve_as_weighted_multi(Origin::signed(3), multi, Some(now()), hash.clone(), 0));
+		assert_eq!(Balances::free_balance(6), 0);
+
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(1), multi, Some(now()), data, false, call_weight));
+		assert_eq!(Balances::free_balance(6), 4);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn cancel_weighted_multisig_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1)], 2, 0));
+		let multi = Multisig::weighted_account_id(&4, 0, None);
+
+		let hash = blake2_256(&Call::Balances(BalancesCall::transfer(6, 4)).encode());
+		assert_ok!(Multisig::approve_as_weighted_multi(Origin::signed(1), multi, None, hash.clone(), 0));
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_noop!(
+			Multisig::cancel_as_weighted_multi(Origin::signed(2), multi, now(), hash.clone()),
+			Error::<Test>::NotOwner,
+		);
+		assert_ok!(Multisig::cancel_as_weighted_multi(Origin::signed(1), multi, now(), hash.clone()));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!Multisigs::<Test>::contains_key(multi, hash));
+	});
+}
+
+#[test]
+fn weighted_multisig_can_change_signatories() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Multisig::create_weighted(Origin::signed(4), vec![(1, 1), (2, 1)], 2, 0));
+		let multi = Multisig::weighted_account_id(&4, 0, None);
+		assert_ok!(Balances::transfer(Origin::signed(4), multi, 5));
+		assert_noop!(
+			Multisig::set_weighted_signatories(Origin::signed(1), vec![(1, 1)], 1),
+			Error::<Test>::NotFound,
+		);
+
+		let call = Call::Multisig(crate::Call::set_weighted_signatories(vec![(1, 1), (3, 1)], 1));
+		let call_weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(1), multi, None, data.clone(), false, 0));
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(2), multi, Some(now()), data, false, call_weight));
+		expect_event(RawEvent::MultisigExecuted(2, now(), multi, blake2_256(&call.encode()), Ok(())));
+		assert_eq!(WeightedMultisigs::<Test>::get(multi).map(|m| m.threshold), Some(1));
+
+		// The account is unchanged and only the new signatories may approve.
+		let call = Call::Balances(BalancesCall::transfer(6, 4));
+		let call_weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+		assert_noop!(
+			Multisig::as_weighted_multi(Origin::signed(2), multi, None, data.clone(), false, 0),
+			Error::<Test>::NotSignatory,
+		);
+		// With a threshold of one, a single approval dispatches the call right away.
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(3), multi, None, data, false, call_weight));
+		assert_eq!(Balances::free_balance(6), 4);
+
+		let call = Call::Multisig(crate::Call::dissolve_weighted());
+		let call_weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(3), multi, None, data, false, call_weight));
+		assert!(!WeightedMultisigs::<Test>::contains_key(multi));
+		assert_eq!(Balances::reserved_balance(4), 0);
+	});
+}
+
+#[test]
+fn weighty_signatory_dispatches_weighted_multisig_call_right_away() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Multisig::create_weighted(Origin::signed(4), vec![(1, 3), (2, 1), (3, 1)], 3, 0));
+		let multi = Multisig::weighted_account_id(&4, 0, None);
+		assert_ok!(Balances::transfer(Origin::signed(4), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
+
+		let call = Call::Balances(BalancesCall::transfer(6, 4));
+		let call_weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+		let hash = blake2_256(&data);
+		assert_noop!(
+			Multisig::as_weighted_multi(Origin::signed(1), multi, None, data.clone(), false, 0),
+			Error::<Test>::WeightTooLow,
+		);
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(1), multi, None, data.clone(), true, call_weight));
+		expect_event(RawEvent::MultisigExecuted(1, now(), multi, hash, Ok(())));
+		assert_eq!(Balances::free_balance(6), 4);
+		// Nothing was recorded nor reserved.
+		assert!(!Multisigs::<Test>::contains_key(multi, hash));
+		assert!(!Calls::<Test>::contains_key(hash));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// Without the call, the approval is recorded as usual.
+		assert_ok!(Multisig::approve_as_weighted_multi(Origin::signed(1), multi, None, hash.clone(), 0));
+		assert!(Multisigs::<Test>::contains_key(multi, hash));
+		assert_ok!(Multisig::as_weighted_multi(Origin::signed(2), multi, Some(now()), data, false, call_weight));
+		assert_eq!(Balances::free_balance(6), 8);
+	});
+}
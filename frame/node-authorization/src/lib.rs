@@ -29,10 +29,15 @@
 //! A node must have an owner. The owner can additionally change the connections
 //! for the node. Only one user is allowed to claim a specific node. To eliminate
 //! false claim, the maintainer of the node should claim it before even starting the
-//! node. This pallet uses offchain worker to set reserved nodes, if the node is not
-//! an authority, make sure to enable offchain worker with the right CLI flag. The
-//! node can be lagged with the latest block, in this case you need to disable offchain
-//! worker and manually set reserved nodes when starting it.
+//! node.
+//!
+//! The connection policy is enforced by starting the node in permissioned mode
+//! (`--permissioned`), in which the networking layer only connects to and accepts the
+//! authorized nodes. The client keeps them in sync with the runtime state at the best
+//! block through the `NodeAuthorizationApi` runtime api, which should be implemented
+//! with [`Module::get_authorized_nodes`]. This pallet's offchain worker additionally
+//! pushes the authorized nodes to the network on every block it runs for, without
+//! touching the mode itself: `--permissioned` is the only switch for it.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -40,13 +45,15 @@
 use sp_core::OpaquePeerId as PeerId;
 use sp_std::{
 	collections::btree_set::BTreeSet,
+	convert::TryFrom,
 	iter::FromIterator,
 	prelude::*,
 };
 use codec::Decode;
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error,
-	debug, ensure,
+	debug, ensure, BoundedBTreeSet,
+	storage::migration,
 	weights::{DispatchClass, Weight},
 	traits::{Get, EnsureOrigin},
 };
@@ -104,8 +111,10 @@ pub trait Trait: frame_system::Trait {
 
 decl_storage! {
 	trait Store for Module<T: Trait> as NodeAuthorization {
-		/// The set of well known nodes. This is stored sorted (just by value).
-		pub WellKnownNodes get(fn well_known_nodes): BTreeSet<PeerId>;
+		/// The set of well known nodes. This is stored sorted (just by value), and holds at most
+		/// `MaxWellKnownNodes` nodes.
+		pub WellKnownNodes get(fn well_known_nodes):
+			BoundedBTreeSet<PeerId, T::MaxWellKnownNodes>;
 		/// A map that maintains the ownership of each node.
 		pub Owners get(fn owners):
 			map hasher(blake2_128_concat) PeerId => T::AccountId;
@@ -117,6 +126,7 @@ decl_storage! {
 		config(nodes): Vec<(PeerId, T::AccountId)>;
 		build(|config: &GenesisConfig<T>| {
 			<Module<T>>::initialize_nodes(&config.nodes)
+				.expect("genesis well known nodes exceed `MaxWellKnownNodes`")
 		})
 	}
 }
@@ -181,6 +191,11 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			Self::truncate_well_known_nodes();
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
 		/// Add a node to the set of well known nodes. If the node is already claimed, the owner
 		/// will be updated and keep the existing additional connection unchanged.
 		///
@@ -192,13 +207,12 @@ decl_module! {
 			T::AddOrigin::ensure_origin(origin)?;
 			ensure!(node.0.len() < T::MaxPeerIdLength::get() as usize, Error::<T>::PeerIdTooLong);
 
-			let mut nodes = WellKnownNodes::get();
-			ensure!(nodes.len() < T::MaxWellKnownNodes::get() as usize, Error::<T>::TooManyNodes);
+			let mut nodes = WellKnownNodes::<T>::get();
 			ensure!(!nodes.contains(&node), Error::<T>::AlreadyJoined);
 
-			nodes.insert(node.clone());
+			nodes.try_insert(node.clone()).map_err(|_| Error::<T>::TooManyNodes)?;
 
-			WellKnownNodes::put(&nodes);
+			WellKnownNodes::<T>::put(&nodes);
 			<Owners<T>>::insert(&node, &owner);
 
 			Self::deposit_event(RawEvent::NodeAdded(node, owner));
@@ -215,12 +229,12 @@ decl_module! {
 			T::RemoveOrigin::ensure_origin(origin)?;
 			ensure!(node.0.len() < T::MaxPeerIdLength::get() as usize, Error::<T>::PeerIdTooLong);
 
-			let mut nodes = WellKnownNodes::get();
+			let mut nodes = WellKnownNodes::<T>::get();
 			ensure!(nodes.contains(&node), Error::<T>::NotExist);
 
 			nodes.remove(&node);
 
-			WellKnownNodes::put(&nodes);
+			WellKnownNodes::<T>::put(&nodes);
 			<Owners<T>>::remove(&node);
 			AdditionalConnections::remove(&node);
 
@@ -242,14 +256,14 @@ decl_module! {
 
 			if remove == add { return Ok(()) }
 
-			let mut nodes = WellKnownNodes::get();
+			let mut nodes = WellKnownNodes::<T>::get();
 			ensure!(nodes.contains(&remove), Error::<T>::NotExist);
 			ensure!(!nodes.contains(&add), Error::<T>::AlreadyJoined);
 
 			nodes.remove(&remove);
-			nodes.insert(add.clone());
+			nodes.try_insert(add.clone()).map_err(|_| Error::<T>::TooManyNodes)?;
 
-			WellKnownNodes::put(&nodes);
+			WellKnownNodes::<T>::put(&nodes);
 			Owners::<T>::swap(&remove, &add);
 			AdditionalConnections::swap(&remove, &add);
 
@@ -266,9 +280,9 @@ decl_module! {
 		#[weight = (T::WeightInfo::reset_well_known_nodes(), DispatchClass::Operational)]
 		pub fn reset_well_known_nodes(origin, nodes: Vec<(PeerId, T::AccountId)>) {
 			T::ResetOrigin::ensure_origin(origin)?;
-			ensure!(nodes.len() < T::MaxWellKnownNodes::get() as usize, Error::<T>::TooManyNodes);
-	
-			Self::initialize_nodes(&nodes);
+			ensure!(nodes.len() <= T::MaxWellKnownNodes::get() as usize, Error::<T>::TooManyNodes);
+
+			Self::initialize_nodes(&nodes)?;
 
 			Self::deposit_event(RawEvent::NodesReset(nodes));
 		}
@@ -300,7 +314,7 @@ decl_module! {
 			ensure!(node.0.len() < T::MaxPeerIdLength::get() as usize, Error::<T>::PeerIdTooLong);
 			ensure!(Owners::<T>::contains_key(&node), Error::<T>::NotClaimed);
 			ensure!(Owners::<T>::get(&node) == sender, Error::<T>::NotOwner);
-			ensure!(!WellKnownNodes::get().contains(&node), Error::<T>::PermissionDenied);
+			ensure!(!WellKnownNodes::<T>::get().contains(&node), Error::<T>::PermissionDenied);
 
 			Owners::<T>::remove(&node);
 			AdditionalConnections::remove(&node);
@@ -382,8 +396,11 @@ decl_module! {
 			Self::deposit_event(RawEvent::ConnectionsRemoved(node, connections));
 		}
 
-		/// Set reserved node every block. It may not be enabled depends on the offchain
+		/// Set the authorized nodes every block. It may not be enabled depends on the offchain
 		/// worker settings when starting the node.
+		///
+		/// This only updates which nodes are authorized, whether the node connects to them
+		/// exclusively is decided by `--permissioned` alone.
 		fn offchain_worker(now: T::BlockNumber) {
 			let network_state = sp_io::offchain::network_state();
 			match network_state {
@@ -394,7 +411,7 @@ decl_module! {
 						Err(_) => debug::error!("Error: failed to decode PeerId at {:?}", now),
 						Ok(node) => sp_io::offchain::set_authorized_nodes(
 							Self::get_authorized_nodes(&PeerId(node)),
-							true
+							false
 						)
 					}
 				}
@@ -404,21 +421,53 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
-	fn initialize_nodes(nodes: &Vec<(PeerId, T::AccountId)>) {
+	/// Keep at most `MaxWellKnownNodes` of the well known nodes, which were stored unbounded
+	/// before. Above the bound, the set would no longer decode and all the nodes would be lost.
+	///
+	/// The nodes dropped are the greatest ones. They keep their owners and additional
+	/// connections, and can be added back once others are removed.
+	fn truncate_well_known_nodes() {
+		let nodes = match migration::get_storage_value::<BTreeSet<PeerId>>(
+			b"NodeAuthorization", b"WellKnownNodes", &[],
+		) {
+			Some(nodes) => nodes,
+			None => return,
+		};
+		let max = T::MaxWellKnownNodes::get() as usize;
+		if nodes.len() > max {
+			debug::warn!(
+				"Dropping {} well known nodes above `MaxWellKnownNodes`",
+				nodes.len() - max,
+			);
+			let nodes = nodes.into_iter().take(max).collect::<BTreeSet<_>>();
+			migration::put_storage_value(b"NodeAuthorization", b"WellKnownNodes", &[], nodes);
+		}
+	}
+
+	fn initialize_nodes(nodes: &Vec<(PeerId, T::AccountId)>) -> Result<(), Error<T>> {
 		let peer_ids = nodes.iter()
 			.map(|item| item.0.clone())
 			.collect::<BTreeSet<PeerId>>();
-		WellKnownNodes::put(&peer_ids);
+		let peer_ids = BoundedBTreeSet::<PeerId, T::MaxWellKnownNodes>::try_from(peer_ids)
+			.map_err(|_| Error::<T>::TooManyNodes)?;
+		WellKnownNodes::<T>::put(&peer_ids);
 
 		for (node, who) in nodes.iter() {
 			Owners::<T>::insert(node, who);
 		}
+
+		Ok(())
 	}
 
-	fn get_authorized_nodes(node: &PeerId) -> Vec<PeerId> {
+	/// The nodes the given node is allowed to be connected to: its additional connections, plus
+	/// all the other well known nodes if it is a well known node itself.
+	///
+	/// Meant to back the `NodeAuthorizationApi` runtime api, which the client uses to enforce
+	/// the connection policy in the networking layer.
+	pub fn get_authorized_nodes(node: &PeerId) -> Vec<PeerId> {
 		let mut nodes = AdditionalConnections::get(node);
 
-		let mut well_known_nodes = WellKnownNodes::get();
+		let mut well_known_nodes = WellKnownNodes::<T>::get().into_inner();
 		if well_known_nodes.contains(node) {
 			well_known_nodes.remove(node);
 			nodes.extend(well_known_nodes);
@@ -434,7 +483,7 @@ mod tests {
 
 	use frame_support::{
 		assert_ok, assert_noop, impl_outer_origin, weights::Weight,
-		parameter_types, ord_parameter_types,
+		parameter_types, ord_parameter_types, traits::OnRuntimeUpgrade,
 	};
 	use frame_system::EnsureSignedBy;
 	use sp_core::H256;
@@ -471,6 +520,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type MaximumBlockLength = MaximumBlockLength;
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
@@ -536,7 +588,7 @@ mod tests {
 				NodeAuthorization::add_well_known_node(Origin::signed(1), test_node(15), 15)
 			);
 			assert_eq!(
-				WellKnownNodes::get(),
+				WellKnownNodes::<Test>::get(),
 				BTreeSet::from_iter(vec![test_node(10), test_node(15), test_node(20), test_node(30)])
 			);
 			assert_eq!(Owners::<Test>::get(test_node(10)), 10);
@@ -577,7 +629,7 @@ mod tests {
 				NodeAuthorization::remove_well_known_node(Origin::signed(2), test_node(20))
 			);
 			assert_eq!(
-				WellKnownNodes::get(),
+				WellKnownNodes::<Test>::get(),
 				BTreeSet::from_iter(vec![test_node(10), test_node(30)])
 			);
 			assert!(!Owners::<Test>::contains_key(test_node(20)));
@@ -613,7 +665,7 @@ mod tests {
 				)
 			);
 			assert_eq!(
-				WellKnownNodes::get(),
+				WellKnownNodes::<Test>::get(),
 				BTreeSet::from_iter(vec![test_node(10), test_node(20), test_node(30)])
 			);
 
@@ -640,7 +692,7 @@ mod tests {
 				)
 			);
 			assert_eq!(
-				WellKnownNodes::get(),
+				WellKnownNodes::<Test>::get(),
 				BTreeSet::from_iter(vec![test_node(5), test_node(10), test_node(30)])
 			);
 			assert!(!Owners::<Test>::contains_key(test_node(20)));
@@ -671,6 +723,7 @@ mod tests {
 						(test_node(5), 5),
 						(test_node(20), 20),
 						(test_node(25), 25),
+						(test_node(35), 35),
 					]
 				),
 				Error::<Test>::TooManyNodes
@@ -679,16 +732,41 @@ mod tests {
 			assert_ok!(
 				NodeAuthorization::reset_well_known_nodes(
 					Origin::signed(4),
-					vec![(test_node(15), 15), (test_node(5), 5), (test_node(20), 20)]
+					vec![
+						(test_node(15), 15),
+						(test_node(5), 5),
+						(test_node(20), 20),
+						(test_node(25), 25),
+					]
 				)
 			);
 			assert_eq!(
-				WellKnownNodes::get(),
-				BTreeSet::from_iter(vec![test_node(5), test_node(15), test_node(20)])
+				WellKnownNodes::<Test>::get(),
+				BTreeSet::from_iter(vec![test_node(5), test_node(15), test_node(20), test_node(25)])
 			);
 			assert_eq!(Owners::<Test>::get(test_node(5)), 5);
 			assert_eq!(Owners::<Test>::get(test_node(15)), 15);
 			assert_eq!(Owners::<Test>::get(test_node(20)), 20);
+			assert_eq!(Owners::<Test>::get(test_node(25)), 25);
+		});
+	}
+
+	#[test]
+	fn runtime_upgrade_truncates_well_known_nodes() {
+		new_test_ext().execute_with(|| {
+			// Stored unbounded before.
+			let nodes = (1..=6).map(test_node).collect::<BTreeSet<_>>();
+			migration::put_storage_value(b"NodeAuthorization", b"WellKnownNodes", &[], nodes);
+
+			NodeAuthorization::on_runtime_upgrade();
+			assert_eq!(
+				WellKnownNodes::<Test>::get(),
+				BTreeSet::from_iter(vec![test_node(1), test_node(2), test_node(3), test_node(4)])
+			);
+
+			// A set within the bound is left as is.
+			NodeAuthorization::on_runtime_upgrade();
+			assert_eq!(WellKnownNodes::<Test>::get().len(), 4);
 		});
 	}
 
@@ -66,6 +66,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = ();
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type SystemWeightInfo = ();
 }
 parameter_types! {
@@ -183,6 +186,7 @@ impl pallet_staking::Trait for Test {
 	type UnsignedPriority = ();
 	type MaxIterations = ();
 	type MinSolutionScoreBump = ();
+	type MaxUnsignedSolutions = ();
 	type WeightInfo = ();
 }
 
@@ -192,6 +196,8 @@ impl pallet_im_online::Trait for Test {
 	type SessionDuration = Period;
 	type ReportUnresponsiveness = Offences;
 	type UnsignedPriority = ();
+	type MaxHeartbeats = ();
+	type UnresponsivenessPolicy = ();
 	type WeightInfo = ();
 }
 
@@ -22,7 +22,7 @@
 use super::*;
 use frame_system::{RawOrigin, EventRecord};
 use frame_benchmarking::{benchmarks, account, whitelisted_caller};
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::{Bounded, One};
 use crate::Module as Proxy;
 
 const SEED: u32 = 0;
@@ -79,6 +79,27 @@ fn add_announcements<T: Trait>(
 	Ok(())
 }
 
+// Scope the proxy of `real` by `delegate` with an expiry and a spend limit, so that proxying
+// through it reads and writes its scope.
+fn add_scope<T: Trait>(real: &T::AccountId, delegate: T::AccountId) {
+	let def = ProxyDefinition {
+		delegate,
+		proxy_type: T::ProxyType::default(),
+		delay: T::BlockNumber::zero(),
+	};
+	let limit = SpendLimit {
+		allowance: BalanceOf::<T>::max_value(),
+		period: One::one(),
+		period_start: Zero::zero(),
+		spent: Zero::zero(),
+	};
+	Scopes::<T>::insert(
+		real,
+		Proxy::<T>::scope_key(&def),
+		ProxyScope { expiry: Some(T::BlockNumber::max_value()), limit: Some(limit) },
+	);
+}
+
 benchmarks! {
 	_ {
 		let p in 1 .. (T::MaxProxies::get() - 1).into() => add_proxies::<T>(p, None)?;
@@ -91,6 +112,7 @@ benchmarks! {
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		// ... and "real" is the traditional caller. This is not a typo.
 		let real: T::AccountId = whitelisted_caller();
+		add_scope::<T>(&real, caller.clone());
 		let call: <T as Trait>::Call = frame_system::Call::<T>::remark(vec![]).into();
 	}: _(RawOrigin::Signed(caller), real, Some(T::ProxyType::default()), Box::new(call))
 	verify {
@@ -113,6 +135,7 @@ benchmarks! {
 			T::CallHasher::hash_of(&call),
 		)?;
 		add_announcements::<T>(a, Some(delegate.clone()), None)?;
+		add_scope::<T>(&real, delegate.clone());
 	}: _(RawOrigin::Signed(caller), delegate, real, Some(T::ProxyType::default()), Box::new(call))
 	verify {
 		assert_last_event::<T>(RawEvent::ProxyExecuted(Ok(())).into())
@@ -190,6 +213,23 @@ benchmarks! {
 		assert_eq!(proxies.len() as u32, p + 1);
 	}
 
+	add_scoped_proxy {
+		let p in ...;
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		account("target", T::MaxProxies::get().into(), SEED),
+		T::ProxyType::default(),
+		T::BlockNumber::zero(),
+		Some(T::BlockNumber::max_value()),
+		Some((BalanceOf::<T>::max_value(), One::one()))
+	)
+	verify {
+		let (proxies, _) = Proxies::<T>::get(&caller);
+		assert_eq!(proxies.len() as u32, p + 1);
+		assert_eq!(Scopes::<T>::iter_prefix_values(&caller).count(), 1);
+	}
+
 	remove_proxy {
 		let p in ...;
 		let caller: T::AccountId = whitelisted_caller();
@@ -270,6 +310,7 @@ mod tests {
 			assert_ok!(test_benchmark_reject_announcement::<Test>());
 			assert_ok!(test_benchmark_announce::<Test>());
 			assert_ok!(test_benchmark_add_proxy::<Test>());
+			assert_ok!(test_benchmark_add_scoped_proxy::<Test>());
 			assert_ok!(test_benchmark_remove_proxy::<Test>());
 			assert_ok!(test_benchmark_remove_proxies::<Test>());
 			assert_ok!(test_benchmark_anonymous::<Test>());
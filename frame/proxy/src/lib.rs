@@ -40,12 +40,13 @@
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_io::hashing::blake2_256;
 use sp_runtime::{DispatchResult, traits::{Dispatchable, Zero, Hash, Member, Saturating}};
 use frame_support::{
 	decl_module, decl_event, decl_error, decl_storage, Parameter, ensure, RuntimeDebug, traits::{
 		Get, ReservableCurrency, Currency, InstanceFilter, OriginTrait, IsType,
-	}, weights::{Weight, GetDispatchInfo},
+	}, weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, GetDispatchInfo},
 	dispatch::{PostDispatchInfo, IsSubType}, storage::IterableStorageMap,
 };
 use frame_system::{self as system, ensure_signed};
@@ -59,15 +60,65 @@ type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trai
 
 pub trait WeightInfo {
 	fn proxy_announced(a: u32, p: u32, ) -> Weight;
+	fn proxy_announced_proof_size(_a: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_announcement(a: u32, p: u32, ) -> Weight;
+	fn remove_announcement_proof_size(_a: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn reject_announcement(a: u32, p: u32, ) -> Weight;
+	fn reject_announcement_proof_size(_a: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn announce(a: u32, p: u32, ) -> Weight;
+	fn announce_proof_size(_a: u32, _p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn proxy(p: u32, ) -> Weight;
+	fn proxy_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn add_proxy(p: u32, ) -> Weight;
+	fn add_proxy_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_proxy(p: u32, ) -> Weight;
+	fn remove_proxy_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn remove_proxies(p: u32, ) -> Weight;
+	fn remove_proxies_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn anonymous(p: u32, ) -> Weight;
+	fn anonymous_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn kill_anonymous(p: u32, ) -> Weight;
+	fn kill_anonymous_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn add_scoped_proxy(p: u32, ) -> Weight;
+	fn add_scoped_proxy_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+}
+
+/// Something which can tell how much value a call would move out of the account which dispatches
+/// it.
+///
+/// This is used to enforce the spending limits of scoped proxies. Calls which do not move any
+/// value should be reported as spending zero. Calls which dispatch other calls should be valued
+/// at the total of the calls they dispatch, and calls whose value can't be determined should be
+/// valued at the maximum balance, so that no proxy with a spending limit can dispatch them.
+pub trait CallValue<Call, Balance> {
+	/// The amount of value which `call` would spend if it were dispatched.
+	fn value(call: &Call) -> Balance;
+}
+
+impl<Call, Balance: Zero> CallValue<Call, Balance> for () {
+	fn value(_: &Call) -> Balance { Zero::zero() }
 }
 
 /// Configuration trait.
@@ -124,11 +175,14 @@ pub trait Trait: frame_system::Trait {
 	/// This is held for adding an `AccountId`, `Hash` and `BlockNumber` (typically 68 bytes)
 	/// into a pre-existing storage value.
 	type AnnouncementDepositFactor: Get<BalanceOf<Self>>;
+
+	/// The means of valuing a call for the purpose of enforcing spending limits of scoped proxies.
+	type CallValue: CallValue<<Self as Trait>::Call, BalanceOf<Self>>;
 }
 
 /// The parameters under which a particular account has a proxy relationship with some other
 /// account.
-#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
 pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
 	/// The account which may act on behalf of another.
 	delegate: AccountId,
@@ -140,7 +194,7 @@ pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
 }
 
 /// Details surrounding a specific instance of an announcement to make a call.
-#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct Announcement<AccountId, Hash, BlockNumber> {
 	/// The account which made the announcement.
 	real: AccountId,
@@ -150,8 +204,38 @@ pub struct Announcement<AccountId, Hash, BlockNumber> {
 	height: BlockNumber,
 }
 
+/// The spending allowance of a scoped proxy, renewed every `period` blocks.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct SpendLimit<BlockNumber, Balance> {
+	/// The maximum value which may be spent within a single period.
+	allowance: Balance,
+	/// The length of a period in blocks.
+	period: BlockNumber,
+	/// The block at which the current period began.
+	period_start: BlockNumber,
+	/// The value which has been spent so far within the current period.
+	spent: Balance,
+}
+
+/// Restrictions placed on a proxy relationship in addition to its `ProxyType`.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ProxyScope<BlockNumber, Balance> {
+	/// The block from which the proxy may no longer be used, if any.
+	expiry: Option<BlockNumber>,
+	/// The limit on the value that the proxy may spend, if any.
+	limit: Option<SpendLimit<BlockNumber, Balance>>,
+}
+
 type CallHashOf<T> = <<T as Trait>::CallHasher as Hash>::Output;
 
+type ProxyDefinitionOf<T> = ProxyDefinition<
+	<T as frame_system::Trait>::AccountId,
+	<T as Trait>::ProxyType,
+	<T as frame_system::Trait>::BlockNumber,
+>;
+
+type ProxyScopeOf<T> = ProxyScope<<T as frame_system::Trait>::BlockNumber, BalanceOf<T>>;
+
 decl_storage! {
 	trait Store for Module<T: Trait> as Proxy {
 		/// The set of account proxies. Maps the account which has delegated to the accounts
@@ -162,6 +246,13 @@ decl_storage! {
 		/// The announcements made by the proxy (key).
 		pub Announcements: map hasher(twox_64_concat) T::AccountId
 			=> (Vec<Announcement<T::AccountId, CallHashOf<T>, T::BlockNumber>>, BalanceOf<T>);
+
+		/// The scopes of the proxies which were registered through `add_scoped_proxy`. Maps the
+		/// account which has delegated and the `(delegate, proxy_type, delay)` of the proxy to its
+		/// expiry and spending limit.
+		pub Scopes: double_map hasher(twox_64_concat) T::AccountId,
+			hasher(blake2_128_concat) (T::AccountId, T::ProxyType, T::BlockNumber)
+			=> Option<ProxyScopeOf<T>>;
 	}
 }
 
@@ -181,6 +272,12 @@ decl_error! {
 		NoPermission,
 		/// Announcement, if made at all, was made too recently.
 		Unannounced,
+		/// The proxy has expired.
+		Expired,
+		/// The call would exceed the spending limit of the proxy for the current period.
+		SpendLimitExceeded,
+		/// The scope is invalid; the expiry is not in the future or the spending period is zero.
+		BadScope,
 	}
 }
 
@@ -242,6 +339,24 @@ decl_module! {
 			T::MaximumBlockWeight::get()
 		}
 
+		fn pre_upgrade() -> Result<(), &'static str> {
+			sp_io::storage::set(PROXIES_COUNT_KEY, &Self::raw_proxies_count().encode());
+			Ok(())
+		}
+
+		fn post_upgrade() -> Result<(), &'static str> {
+			let count = sp_io::storage::get(PROXIES_COUNT_KEY)
+				.and_then(|count| u32::decode(&mut &count[..]).ok())
+				.ok_or("the proxies were not counted before the upgrade")?;
+			sp_io::storage::clear(PROXIES_COUNT_KEY);
+			ensure!(Self::raw_proxies_count() == count, "proxies were removed by the upgrade");
+			ensure!(
+				Proxies::<T>::iter().count() as u32 == count,
+				"proxies don't decode as proxy definitions after the upgrade",
+			);
+			Ok(())
+		}
+
 		/// Dispatch the given `call` from an account that the sender is authorised for through
 		/// `add_proxy`.
 		///
@@ -259,9 +374,13 @@ decl_module! {
 		/// # </weight>
 		#[weight = {
 			let di = call.get_dispatch_info();
-			(T::WeightInfo::proxy(T::MaxProxies::get().into())
-				.saturating_add(di.weight),
-			di.class)
+			WithProofSize(
+				(T::WeightInfo::proxy(T::MaxProxies::get().into())
+					.saturating_add(di.weight),
+				di.class),
+				T::WeightInfo::proxy_proof_size(T::MaxProxies::get().into())
+					.saturating_add(di.proof_size),
+			)
 		}]
 		fn proxy(origin,
 			real: T::AccountId,
@@ -271,8 +390,9 @@ decl_module! {
 			let who = ensure_signed(origin)?;
 			let def = Self::find_proxy(&real, &who, force_proxy_type)?;
 			ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
+			let scope = Self::check_scope(&real, &def, &call)?;
 
-			Self::do_proxy(def, real, *call);
+			Self::do_proxy(def, scope, real, *call);
 		}
 
 		/// Register a proxy account for the sender that is able to make calls on its behalf.
@@ -286,28 +406,64 @@ decl_module! {
 		/// # <weight>
 		/// Weight is a function of the number of proxies the user has (P).
 		/// # </weight>
-		#[weight = T::WeightInfo::add_proxy(T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::add_proxy(T::MaxProxies::get().into()),
+			T::WeightInfo::add_proxy_proof_size(T::MaxProxies::get().into()),
+		)]
 		fn add_proxy(origin,
 			delegate: T::AccountId,
 			proxy_type: T::ProxyType,
 			delay: T::BlockNumber,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Proxies::<T>::try_mutate(&who, |(ref mut proxies, ref mut deposit)| {
-				ensure!(proxies.len() < T::MaxProxies::get() as usize, Error::<T>::TooMany);
-				let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
-				let i = proxies.binary_search(&proxy_def).err().ok_or(Error::<T>::Duplicate)?;
-				proxies.insert(i, proxy_def);
-				let new_deposit = T::ProxyDepositBase::get()
-					+ T::ProxyDepositFactor::get() * (proxies.len() as u32).into();
-				if new_deposit > *deposit {
-					T::Currency::reserve(&who, new_deposit - *deposit)?;
-				} else if new_deposit < *deposit {
-					T::Currency::unreserve(&who, *deposit - new_deposit);
-				}
-				*deposit = new_deposit;
-				Ok(())
-			})
+			Self::do_add_proxy(&who, ProxyDefinition { delegate, proxy_type, delay })
+		}
+
+		/// Register a proxy account for the sender which may only be used until a given block and
+		/// which may only spend a limited amount of value in every period.
+		///
+		/// The value spent by a call is determined by the `CallValue` of the runtime. A scoped
+		/// proxy may not register further proxies for the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `proxy`: The account that the `caller` would like to make a proxy.
+		/// - `proxy_type`: The permissions allowed for this proxy account.
+		/// - `delay`: The announcement period required of the proxy.
+		/// - `expiry`: The block from which the proxy may no longer be used, if any.
+		/// - `spend_limit`: The value that the proxy may spend and the length of the period, in
+		/// blocks, after which this allowance is renewed, if any.
+		///
+		/// # <weight>
+		/// Weight is a function of the number of proxies the user has (P).
+		/// # </weight>
+		#[weight = WithProofSize(
+			T::WeightInfo::add_scoped_proxy(T::MaxProxies::get().into()),
+			T::WeightInfo::add_scoped_proxy_proof_size(T::MaxProxies::get().into()),
+		)]
+		fn add_scoped_proxy(origin,
+			delegate: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: T::BlockNumber,
+			expiry: Option<T::BlockNumber>,
+			spend_limit: Option<(BalanceOf<T>, T::BlockNumber)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = system::Module::<T>::block_number();
+			ensure!(expiry.map_or(true, |e| e > now), Error::<T>::BadScope);
+			ensure!(spend_limit.map_or(true, |(_, p)| !p.is_zero()), Error::<T>::BadScope);
+
+			let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
+			Self::do_add_proxy(&who, proxy_def.clone())?;
+			let limit = spend_limit.map(|(allowance, period)| SpendLimit {
+				allowance,
+				period,
+				period_start: now,
+				spent: Zero::zero(),
+			});
+			Scopes::<T>::insert(&who, Self::scope_key(&proxy_def), ProxyScope { expiry, limit });
+			Ok(())
 		}
 
 		/// Unregister a proxy account for the sender.
@@ -321,33 +477,17 @@ decl_module! {
 		/// # <weight>
 		/// Weight is a function of the number of proxies the user has (P).
 		/// # </weight>
-		#[weight = T::WeightInfo::remove_proxy(T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_proxy(T::MaxProxies::get().into()),
+			T::WeightInfo::remove_proxy_proof_size(T::MaxProxies::get().into()),
+		)]
 		fn remove_proxy(origin,
 			delegate: T::AccountId,
 			proxy_type: T::ProxyType,
 			delay: T::BlockNumber,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Proxies::<T>::try_mutate_exists(&who, |x| {
-				let (mut proxies, old_deposit) = x.take().ok_or(Error::<T>::NotFound)?;
-				let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
-				let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
-				proxies.remove(i);
-				let new_deposit = if proxies.is_empty() {
-					BalanceOf::<T>::zero()
-				} else {
-					T::ProxyDepositBase::get() + T::ProxyDepositFactor::get() * (proxies.len() as u32).into()
-				};
-				if new_deposit > old_deposit {
-					T::Currency::reserve(&who, new_deposit - old_deposit)?;
-				} else if new_deposit < old_deposit {
-					T::Currency::unreserve(&who, old_deposit - new_deposit);
-				}
-				if !proxies.is_empty() {
-					*x = Some((proxies, new_deposit))
-				}
-				Ok(())
-			})
+			Self::do_remove_proxy(&who, ProxyDefinition { delegate, proxy_type, delay })
 		}
 
 		/// Unregister all proxy accounts for the sender.
@@ -360,10 +500,14 @@ decl_module! {
 		/// # <weight>
 		/// Weight is a function of the number of proxies the user has (P).
 		/// # </weight>
-		#[weight = T::WeightInfo::remove_proxies(T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_proxies(T::MaxProxies::get().into()),
+			T::WeightInfo::remove_proxies_proof_size(T::MaxProxies::get().into()),
+		)]
 		fn remove_proxies(origin) {
 			let who = ensure_signed(origin)?;
 			let (_, old_deposit) = Proxies::<T>::take(&who);
+			Scopes::<T>::remove_prefix(&who);
 			T::Currency::unreserve(&who, old_deposit);
 		}
 
@@ -390,7 +534,10 @@ decl_module! {
 		/// Weight is a function of the number of proxies the user has (P).
 		/// # </weight>
 		/// TODO: Might be over counting 1 read
-		#[weight = T::WeightInfo::anonymous(T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::anonymous(T::MaxProxies::get().into()),
+			T::WeightInfo::anonymous_proof_size(T::MaxProxies::get().into()),
+		)]
 		fn anonymous(origin, proxy_type: T::ProxyType, delay: T::BlockNumber, index: u16) {
 			let who = ensure_signed(origin)?;
 
@@ -427,7 +574,10 @@ decl_module! {
 		/// # <weight>
 		/// Weight is a function of the number of proxies the user has (P).
 		/// # </weight>
-		#[weight = T::WeightInfo::kill_anonymous(T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::kill_anonymous(T::MaxProxies::get().into()),
+			T::WeightInfo::kill_anonymous_proof_size(T::MaxProxies::get().into()),
+		)]
 		fn kill_anonymous(origin,
 			spawner: T::AccountId,
 			proxy_type: T::ProxyType,
@@ -442,6 +592,7 @@ decl_module! {
 			ensure!(proxy == who, Error::<T>::NoPermission);
 
 			let (_, deposit) = Proxies::<T>::take(&who);
+			Scopes::<T>::remove_prefix(&who);
 			T::Currency::unreserve(&spawner, deposit);
 		}
 
@@ -466,7 +617,10 @@ decl_module! {
 		/// - A: the number of announcements made.
 		/// - P: the number of proxies the user has.
 		/// # </weight>
-		#[weight = T::WeightInfo::announce(T::MaxPending::get(), T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::announce(T::MaxPending::get(), T::MaxProxies::get().into()),
+			T::WeightInfo::announce_proof_size(T::MaxPending::get(), T::MaxProxies::get().into()),
+		)]
 		fn announce(origin, real: T::AccountId, call_hash: CallHashOf<T>) {
 			let who = ensure_signed(origin)?;
 			Proxies::<T>::get(&real).0.into_iter()
@@ -510,7 +664,13 @@ decl_module! {
 		/// - A: the number of announcements made.
 		/// - P: the number of proxies the user has.
 		/// # </weight>
-		#[weight = T::WeightInfo::remove_announcement(T::MaxPending::get(), T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_announcement(T::MaxPending::get(), T::MaxProxies::get().into()),
+			T::WeightInfo::remove_announcement_proof_size(
+				T::MaxPending::get(),
+				T::MaxProxies::get().into(),
+			),
+		)]
 		fn remove_announcement(origin, real: T::AccountId, call_hash: CallHashOf<T>) {
 			let who = ensure_signed(origin)?;
 			Self::edit_announcements(&who, |ann| ann.real != real || ann.call_hash != call_hash)?;
@@ -532,7 +692,13 @@ decl_module! {
 		/// - A: the number of announcements made.
 		/// - P: the number of proxies the user has.
 		/// # </weight>
-		#[weight = T::WeightInfo::reject_announcement(T::MaxPending::get(), T::MaxProxies::get().into())]
+		#[weight = WithProofSize(
+			T::WeightInfo::reject_announcement(T::MaxPending::get(), T::MaxProxies::get().into()),
+			T::WeightInfo::reject_announcement_proof_size(
+				T::MaxPending::get(),
+				T::MaxProxies::get().into(),
+			),
+		)]
 		fn reject_announcement(origin, delegate: T::AccountId, call_hash: CallHashOf<T>) {
 			let who = ensure_signed(origin)?;
 			Self::edit_announcements(&delegate, |ann| ann.real != who || ann.call_hash != call_hash)?;
@@ -557,9 +723,15 @@ decl_module! {
 		/// # </weight>
 		#[weight = {
 			let di = call.get_dispatch_info();
-			(T::WeightInfo::proxy_announced(T::MaxPending::get(), T::MaxProxies::get().into())
-				.saturating_add(di.weight),
-			di.class)
+			WithProofSize(
+				(T::WeightInfo::proxy_announced(T::MaxPending::get(), T::MaxProxies::get().into())
+					.saturating_add(di.weight),
+				di.class),
+				T::WeightInfo::proxy_announced_proof_size(
+					T::MaxPending::get(),
+					T::MaxProxies::get().into(),
+				).saturating_add(di.proof_size),
+			)
 		}]
 		fn proxy_announced(origin,
 			delegate: T::AccountId,
@@ -569,6 +741,7 @@ decl_module! {
 		) {
 			ensure_signed(origin)?;
 			let def = Self::find_proxy(&real, &delegate, force_proxy_type)?;
+			let scope = Self::check_scope(&real, &def, &call)?;
 
 			let call_hash = T::CallHasher::hash_of(&call);
 			let now = system::Module::<T>::block_number();
@@ -576,11 +749,24 @@ decl_module! {
 				ann.real != real || ann.call_hash != call_hash || now.saturating_sub(ann.height) < def.delay
 			).map_err(|_| Error::<T>::Unannounced)?;
 
-			Self::do_proxy(def, real, *call);
+			Self::do_proxy(def, scope, real, *call);
 		}
 	}
 }
 
+/// Where `pre_upgrade` keeps the number of proxied accounts for `post_upgrade`.
+#[cfg(feature = "try-runtime")]
+const PROXIES_COUNT_KEY: &[u8] = b":try-runtime:proxy:proxies_count";
+
+#[cfg(feature = "try-runtime")]
+impl<T: Trait> Module<T> {
+	/// The number of entries of `Proxies`, including those that don't decode.
+	fn raw_proxies_count() -> u32 {
+		use frame_support::storage::{migration::count_keys_with_prefix, StoragePrefixedMap};
+		count_keys_with_prefix(&Proxies::<T>::final_prefix())
+	}
+}
+
 impl<T: Trait> Module<T> {
 	pub fn anonymous_account(
 		who: &T::AccountId,
@@ -640,30 +826,139 @@ impl<T: Trait> Module<T> {
 		})
 	}
 
+	/// Register `delegate` as a proxy of `delegator`, as if `delegator` had called `add_proxy`.
+	///
+	/// This allows other pallets to hand control of an account over to a proxy, the deposit is
+	/// reserved from `delegator`.
+	pub fn add_proxy_delegate(
+		delegator: &T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		Self::do_add_proxy(delegator, ProxyDefinition { delegate, proxy_type, delay })
+	}
+
+	/// Unregister `delegate` as a proxy of `delegator`, as if `delegator` had called
+	/// `remove_proxy`.
+	pub fn remove_proxy_delegate(
+		delegator: &T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		Self::do_remove_proxy(delegator, ProxyDefinition { delegate, proxy_type, delay })
+	}
+
+	fn do_remove_proxy(who: &T::AccountId, proxy_def: ProxyDefinitionOf<T>) -> DispatchResult {
+		Proxies::<T>::try_mutate_exists(who, |x| {
+			let (mut proxies, old_deposit) = x.take().ok_or(Error::<T>::NotFound)?;
+			let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
+			proxies.remove(i);
+			Scopes::<T>::remove(who, Self::scope_key(&proxy_def));
+			let new_deposit = if proxies.is_empty() {
+				BalanceOf::<T>::zero()
+			} else {
+				T::ProxyDepositBase::get() + T::ProxyDepositFactor::get() * (proxies.len() as u32).into()
+			};
+			if new_deposit > old_deposit {
+				T::Currency::reserve(who, new_deposit - old_deposit)?;
+			} else if new_deposit < old_deposit {
+				T::Currency::unreserve(who, old_deposit - new_deposit);
+			}
+			if !proxies.is_empty() {
+				*x = Some((proxies, new_deposit))
+			}
+			Ok(())
+		})
+	}
+
+	fn do_add_proxy(who: &T::AccountId, proxy_def: ProxyDefinitionOf<T>) -> DispatchResult {
+		Proxies::<T>::try_mutate(who, |(ref mut proxies, ref mut deposit)| {
+			ensure!(proxies.len() < T::MaxProxies::get() as usize, Error::<T>::TooMany);
+			let i = proxies.binary_search(&proxy_def).err().ok_or(Error::<T>::Duplicate)?;
+			proxies.insert(i, proxy_def);
+			let new_deposit = T::ProxyDepositBase::get()
+				+ T::ProxyDepositFactor::get() * (proxies.len() as u32).into();
+			if new_deposit > *deposit {
+				T::Currency::reserve(who, new_deposit - *deposit)?;
+			} else if new_deposit < *deposit {
+				T::Currency::unreserve(who, *deposit - new_deposit);
+			}
+			*deposit = new_deposit;
+			Ok(())
+		})
+	}
+
+	fn scope_key(def: &ProxyDefinitionOf<T>) -> (T::AccountId, T::ProxyType, T::BlockNumber) {
+		(def.delegate.clone(), def.proxy_type.clone(), def.delay)
+	}
+
+	/// Check the scope of the proxy `def` of `real`, if it has one, against the current block and
+	/// the value spent by `call`.
+	///
+	/// Returns the scope as it should be stored once `call` is dispatched. Nothing is written.
+	fn check_scope(
+		real: &T::AccountId,
+		def: &ProxyDefinitionOf<T>,
+		call: &<T as Trait>::Call,
+	) -> Result<Option<ProxyScopeOf<T>>, DispatchError> {
+		let mut scope = match Scopes::<T>::get(real, Self::scope_key(def)) {
+			Some(scope) => scope,
+			None => return Ok(None),
+		};
+		let now = system::Module::<T>::block_number();
+		if let Some(expiry) = scope.expiry {
+			ensure!(now < expiry, Error::<T>::Expired);
+		}
+		if let Some(ref mut limit) = scope.limit {
+			let elapsed = now.saturating_sub(limit.period_start);
+			if elapsed >= limit.period {
+				// Start the period that `now` falls in, forgetting anything spent before it.
+				limit.period_start = now - elapsed % limit.period;
+				limit.spent = Zero::zero();
+			}
+			let spent = limit.spent.saturating_add(T::CallValue::value(call));
+			ensure!(spent <= limit.allowance, Error::<T>::SpendLimitExceeded);
+			limit.spent = spent;
+		}
+		Ok(Some(scope))
+	}
+
 	fn find_proxy(
 		real: &T::AccountId,
 		delegate: &T::AccountId,
 		force_proxy_type: Option<T::ProxyType>,
-	) -> Result<ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>, DispatchError> {
-		let f = |x: &ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>| -> bool {
+	) -> Result<ProxyDefinitionOf<T>, DispatchError> {
+		let f = |x: &ProxyDefinitionOf<T>| -> bool {
 			&x.delegate == delegate && force_proxy_type.as_ref().map_or(true, |y| &x.proxy_type == y)
 		};
 		Ok(Proxies::<T>::get(real).0.into_iter().find(f).ok_or(Error::<T>::NotProxy)?)
 	}
 
+	/// Dispatch `call` from `real` through the proxy `def`.
+	///
+	/// The `scope` returned by `check_scope` is only stored if the call succeeds, so that failed
+	/// calls don't use up the allowance of the proxy.
 	fn do_proxy(
-		def: ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>,
+		def: ProxyDefinitionOf<T>,
+		scope: Option<ProxyScopeOf<T>>,
 		real: T::AccountId,
 		call: <T as Trait>::Call,
 	) {
+		let scoped = scope.is_some();
+		let scope_key = Self::scope_key(&def);
 		// This is a freshly authenticated new account, the origin restrictions doesn't apply.
-		let mut origin: T::Origin = frame_system::RawOrigin::Signed(real).into();
+		let mut origin: T::Origin = frame_system::RawOrigin::Signed(real.clone()).into();
 		origin.add_filter(move |c: &<T as frame_system::Trait>::Call| {
 			let c = <T as Trait>::Call::from_ref(c);
 			// We make sure the proxy call does access this pallet to change modify proxies.
 			match c.is_sub_type() {
+				// Scoped proxy call cannot add a proxy, which would escape its scope.
+				Some(Call::add_proxy(..)) | Some(Call::add_scoped_proxy(..)) if scoped => false,
 				// Proxy call cannot add or remove a proxy with more permissions than it already has.
-				Some(Call::add_proxy(_, ref pt, _)) | Some(Call::remove_proxy(_, ref pt, _))
+				Some(Call::add_proxy(_, ref pt, _)) | Some(Call::remove_proxy(_, ref pt, _)) |
+				Some(Call::add_scoped_proxy(_, ref pt, ..))
 					if !def.proxy_type.is_superset(&pt) => false,
 				// Proxy call cannot remove all proxies or kill anonymous proxies unless it has full permissions.
 				Some(Call::remove_proxies(..)) | Some(Call::kill_anonymous(..))
@@ -672,6 +967,10 @@ impl<T: Trait> Module<T> {
 			}
 		});
 		let e = call.dispatch(origin);
+		if let (Ok(_), Some(scope)) = (&e, scope) {
+			// The call may have removed the proxy, together with its scope.
+			Scopes::<T>::mutate(&real, &scope_key, |s| if s.is_some() { *s = Some(scope) });
+		}
 		Self::deposit_event(RawEvent::ProxyExecuted(e.map(|_| ()).map_err(|e| e.error)));
 	}
 }
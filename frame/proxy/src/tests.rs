@@ -79,6 +79,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -132,6 +135,17 @@ impl InstanceFilter<Call> for ProxyType {
 		self == &ProxyType::Any || self == o
 	}
 }
+pub struct TransferValue;
+impl CallValue<Call, u64> for TransferValue {
+	fn value(c: &Call) -> u64 {
+		match c {
+			Call::Balances(BalancesCall::transfer(_, value)) => *value,
+			Call::Balances(BalancesCall::transfer_keep_alive(_, value)) => *value,
+			Call::Utility(UtilityCall::batch(calls)) => calls.iter().map(Self::value).sum(),
+			_ => 0,
+		}
+	}
+}
 pub struct BaseFilter;
 impl Filter<Call> for BaseFilter {
 	fn filter(c: &Call) -> bool {
@@ -156,6 +170,7 @@ impl Trait for Test {
 	type MaxPending = MaxPending;
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+	type CallValue = TransferValue;
 }
 
 type System = frame_system::Module<Test>;
@@ -404,6 +419,21 @@ fn add_remove_proxies_works() {
 	});
 }
 
+#[test]
+fn proxy_delegates_can_be_managed_by_other_pallets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy_delegate(&1, 2, ProxyType::Any, 0));
+		assert_eq!(Balances::reserved_balance(1), 2);
+		let call = Box::new(Call::Balances(BalancesCall::transfer(6, 1)));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call));
+		assert_eq!(Balances::free_balance(6), 1);
+
+		assert_ok!(Proxy::remove_proxy_delegate(&1, 2, ProxyType::Any, 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(Proxy::remove_proxy_delegate(&1, 2, ProxyType::Any, 0), Error::<Test>::NotFound);
+	});
+}
+
 #[test]
 fn cannot_add_proxy_without_balance() {
 	new_test_ext().execute_with(|| {
@@ -484,3 +514,120 @@ fn anonymous_works() {
 		assert_noop!(Proxy::proxy(Origin::signed(1), anon, None, call.clone()), Error::<Test>::NotProxy);
 	});
 }
+
+#[test]
+fn scoped_proxy_expires() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Proxy::add_scoped_proxy(Origin::signed(1), 2, ProxyType::Any, 0, Some(1), None),
+			Error::<Test>::BadScope
+		);
+		assert_ok!(Proxy::add_scoped_proxy(Origin::signed(1), 2, ProxyType::Any, 0, Some(3), None));
+		assert_eq!(Balances::reserved_balance(1), 2);
+
+		let call = Box::new(Call::Balances(BalancesCall::transfer(6, 1)));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+		System::set_block_number(3);
+		assert_noop!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()), Error::<Test>::Expired);
+
+		assert_ok!(Proxy::remove_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		assert_eq!(Scopes::<Test>::get(1, (2, ProxyType::Any, 0)), None);
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+	});
+}
+
+#[test]
+fn scoped_proxy_spend_limit_works() {
+	new_test_ext().execute_with(|| {
+		Balances::mutate_account(&1, |a| a.free = 1000);
+		assert_noop!(
+			Proxy::add_scoped_proxy(Origin::signed(1), 2, ProxyType::Any, 0, None, Some((5, 0))),
+			Error::<Test>::BadScope
+		);
+		assert_ok!(Proxy::add_scoped_proxy(Origin::signed(1), 2, ProxyType::Any, 0, None, Some((5, 10))));
+
+		let transfer = |value: u64| Call::Balances(BalancesCall::transfer(6, value));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, Box::new(transfer(3))));
+		assert_noop!(
+			Proxy::proxy(Origin::signed(2), 1, None, Box::new(transfer(3))),
+			Error::<Test>::SpendLimitExceeded
+		);
+		let batch = Call::Utility(UtilityCall::batch(vec![transfer(1), transfer(2)]));
+		assert_noop!(
+			Proxy::proxy(Origin::signed(2), 1, None, Box::new(batch)),
+			Error::<Test>::SpendLimitExceeded
+		);
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, Box::new(transfer(2))));
+		assert_eq!(Balances::free_balance(6), 5);
+
+		// The period started at block 1, so block 25 falls in the period starting at block 21.
+		System::set_block_number(25);
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, Box::new(transfer(5))));
+		assert_eq!(Balances::free_balance(6), 10);
+		System::set_block_number(30);
+		assert_noop!(
+			Proxy::proxy(Origin::signed(2), 1, None, Box::new(transfer(1))),
+			Error::<Test>::SpendLimitExceeded
+		);
+		System::set_block_number(31);
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, Box::new(transfer(1))));
+		assert_eq!(Balances::free_balance(6), 11);
+	});
+}
+
+#[test]
+fn failed_calls_do_not_use_the_allowance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_scoped_proxy(Origin::signed(1), 2, ProxyType::Any, 0, None, Some((5, 10))));
+
+		let transfer = Box::new(Call::Balances(BalancesCall::transfer(6, 5)));
+		Balances::mutate_account(&1, |a| a.free = 4);
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, transfer.clone()));
+		let insufficient = BalancesError::<Test, _>::InsufficientBalance.into();
+		expect_event(RawEvent::ProxyExecuted(Err(insufficient)));
+
+		Balances::mutate_account(&1, |a| a.free = 1000);
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, transfer.clone()));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+		assert_eq!(Balances::free_balance(6), 5);
+	});
+}
+
+#[test]
+fn scoped_proxy_cannot_add_proxies() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_scoped_proxy(Origin::signed(1), 2, ProxyType::Any, 0, Some(10), None));
+
+		let call = Box::new(Call::Proxy(ProxyCall::add_proxy(2, ProxyType::Any, 1)));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()));
+		expect_event(RawEvent::ProxyExecuted(Err(DispatchError::BadOrigin)));
+		let call = Box::new(Call::Proxy(ProxyCall::add_scoped_proxy(3, ProxyType::Any, 0, None, None)));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()));
+		expect_event(RawEvent::ProxyExecuted(Err(DispatchError::BadOrigin)));
+
+		let call = Box::new(Call::Proxy(ProxyCall::remove_proxies()));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+		assert_eq!(Scopes::<Test>::iter_prefix_values(1).count(), 0);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn upgrade_checks_find_migrated_proxies() {
+	use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+
+	new_test_ext().execute_with(|| {
+		let old = (vec![(2u64, ProxyType::Any)], 1u64);
+		unhashed::put(&Proxies::<Test>::hashed_key_for(1), &old);
+		assert_ok!(<Proxy as OnRuntimeUpgrade>::pre_upgrade());
+		<Proxy as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_ok!(<Proxy as OnRuntimeUpgrade>::post_upgrade());
+
+		// checks after an upgrade without checks before fail.
+		assert!(<Proxy as OnRuntimeUpgrade>::post_upgrade().is_err());
+	});
+}
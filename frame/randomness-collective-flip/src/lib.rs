@@ -23,6 +23,10 @@
 //! adversaries. Using this pallet as a randomness source is advisable primarily in low-security
 //! situations like testing.
 //!
+//! Chains running BABE should rather use the randomness sources of `pallet_babe`, e.g.
+//! `RandomnessFromOneEpochAgo`, which are derived from VRF outputs and tell since which block their
+//! value is known.
+//!
 //! ## Public Functions
 //!
 //! See the [`Module`](./struct.Module.html) struct for details of publicly available functions.
@@ -175,6 +179,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = ();
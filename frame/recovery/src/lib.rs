@@ -38,7 +38,12 @@
 //! * `threshold` - The number of friends that need to approve a recovery process for
 //!   the account to be successfully recovered.
 //! * `delay_period` - The minimum number of blocks after the beginning of the recovery
-//!   process that need to pass before the account can be successfully recovered.
+//!   process that need to pass before the account can be successfully recovered. It can
+//!   be no shorter than the `MinDelayPeriod` of the runtime.
+//!
+//! By default every friend counts as one approval. The account owner can give some friends
+//! more weight with `set_friend_weights`, in which case the `threshold` is compared to the total
+//! weight of the friends who vouched.
 //!
 //! There is a configurable deposit that all users need to pay to create a recovery
 //! configuration. This deposit is composed of a base deposit plus a multiplier for
@@ -58,13 +63,15 @@
 //! 4. Then the account owner would contact their configured friends to vouch for
 //!    the recovery attempt. The account owner would provide their old account id
 //!    and the new account id, and friends would call `vouch_recovery` with those
-//!    parameters.
+//!    parameters. Vouching for an active recovery process is free, so that friends
+//!    don't need to hold more than an existing account to vouch.
 //! 5. Once a threshold number of friends have vouched for the recovery attempt,
 //!    the account owner needs to wait until the delay period has passed, starting
 //!    when they initiated the recovery process.
 //! 6. Now the account owner is able to call `claim_recovery`, which subsequently
 //!    allows them to call `as_recovered` and directly make calls on-behalf-of the lost
-//!    account.
+//!    account. If the runtime configures a `RecoveredProxy`, the new account is also
+//!    registered as a proxy of the lost account (e.g. in `pallet_proxy`), if possible.
 //! 7. Using the now recovered account, the account owner can call `close_recovery`
 //!    on the recovery process they opened, reclaiming the recovery deposit they
 //!    placed.
@@ -95,6 +102,15 @@
 //! this pallet makes it near-zero cost to re-configure the recovery settings and
 //! remove/replace friends who are acting inappropriately.
 //!
+//! ### Notifications
+//!
+//! The account owner is meant to close malicious recovery attempts before their delay period
+//! ends. Besides the `RecoveryInitiated` event, a `RecoveryClaimable` event is deposited once
+//! enough friends have vouched for a recovery attempt, along with the block from which it can be
+//! claimed. The runtime is also notified through its `Notify` handler, e.g. `OffchainNotify`
+//! which writes the recovery attempts of an account to the offchain database so that an
+//! off-chain service can alert the owner.
+//!
 //! ### Safety Considerations
 //!
 //! It is important to note that this is a powerful pallet that can compromise the
@@ -107,6 +123,9 @@
 //!   against your account. Using off-chain notification systems can help with this,
 //!   but ultimately, setting a large `delay_period` means that even the most skilled
 //!   attacker will need to wait this long before they can access your account.
+//! * Give your most trusted friends more weight: With `set_friend_weights`, a single
+//!   trusted friend can count as several approvals while other friends only contribute
+//!   to the threshold together.
 //! * Use a high threshold of approvals: Setting a value of 1 for the threshold means
 //!   that any of your friends would be able to recover your account. They would
 //!   simply need to start a recovery process and approve their own process. Similarly,
@@ -142,6 +161,7 @@
 //!
 //! * `close_recovery` - Close an active recovery process for your account and reclaim the recovery deposit.
 //! * `remove_recovery` - Remove the recovery configuration from the account, making it un-recoverable.
+//! * `set_friend_weights` - Set the weight each friend counts for towards the threshold.
 //!
 //! #### For Super Users
 //!
@@ -153,14 +173,15 @@
 
 use sp_std::prelude::*;
 use sp_runtime::{
-	traits::{Dispatchable, SaturatedConversion, CheckedAdd, CheckedMul},
-	DispatchResult
+	traits::{Dispatchable, SaturatedConversion, CheckedAdd, CheckedMul, Saturating},
+	DispatchResult, DispatchError,
 };
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 
 use frame_support::{
 	decl_module, decl_event, decl_storage, decl_error, ensure,
-	Parameter, RuntimeDebug, weights::GetDispatchInfo,
+	Parameter, RuntimeDebug, weights::{GetDispatchInfo, FeelessIf},
 	traits::{Currency, ReservableCurrency, Get, BalanceStatus},
 	dispatch::PostDispatchInfo,
 };
@@ -207,10 +228,92 @@ pub trait Trait: frame_system::Trait {
 	/// `sizeof(BlockNumber, Balance + T * AccountId)` bytes. Where T is a configurable
 	/// threshold.
 	type RecoveryDeposit: Get<BalanceOf<Self>>;
+
+	/// The minimum delay period of a recovery configuration.
+	type MinDelayPeriod: Get<Self::BlockNumber>;
+
+	/// Handler notified of the recovery attempts of an account, e.g. to alert its owner.
+	type Notify: NotifyRecovery<Self::AccountId, Self::BlockNumber>;
+
+	/// Gives the rescuer control of a recovered account through a proxy.
+	type RecoveredProxy: RecoveredAccountProxy<Self::AccountId>;
+}
+
+/// Handler notified of the recovery attempts of an account.
+///
+/// This allows the owner of the account to be alerted, and to close a malicious attempt, before
+/// it can be claimed.
+pub trait NotifyRecovery<AccountId, BlockNumber> {
+	/// The recovery of `lost` by `rescuer` was initiated. It can be claimed from `claimable_at`
+	/// if enough friends vouch for it.
+	fn on_initiated(lost: &AccountId, rescuer: &AccountId, claimable_at: BlockNumber);
+
+	/// Enough friends vouched for the recovery of `lost` by `rescuer`. It can be claimed from
+	/// `claimable_at`.
+	fn on_vouched(lost: &AccountId, rescuer: &AccountId, claimable_at: BlockNumber);
+
+	/// The recovery of `lost` by `rescuer` was closed.
+	fn on_closed(lost: &AccountId, rescuer: &AccountId);
+}
+
+impl<AccountId, BlockNumber> NotifyRecovery<AccountId, BlockNumber> for () {
+	fn on_initiated(_: &AccountId, _: &AccountId, _: BlockNumber) {}
+	fn on_vouched(_: &AccountId, _: &AccountId, _: BlockNumber) {}
+	fn on_closed(_: &AccountId, _: &AccountId) {}
+}
+
+/// Prefix of the offchain database keys written by `OffchainNotify`.
+pub const OFFCHAIN_NOTIFY_PREFIX: &[u8] = b"recovery/";
+
+/// Writes the recovery attempts to the offchain database, for off-chain services to alert the
+/// owners of the accounts.
+///
+/// An attempt is stored under `OFFCHAIN_NOTIFY_PREFIX ++ (lost, rescuer).encode()` as the
+/// encoded `(claimable_at, vouched)`, until it is closed.
+pub struct OffchainNotify;
+
+impl OffchainNotify {
+	fn key<AccountId: Encode>(lost: &AccountId, rescuer: &AccountId) -> Vec<u8> {
+		let mut key = OFFCHAIN_NOTIFY_PREFIX.to_vec();
+		(lost, rescuer).using_encoded(|encoded| key.extend_from_slice(encoded));
+		key
+	}
+}
+
+impl<AccountId: Encode, BlockNumber: Encode> NotifyRecovery<AccountId, BlockNumber> for OffchainNotify {
+	fn on_initiated(lost: &AccountId, rescuer: &AccountId, claimable_at: BlockNumber) {
+		sp_io::offchain_index::set(&Self::key(lost, rescuer), &(claimable_at, false).encode());
+	}
+
+	fn on_vouched(lost: &AccountId, rescuer: &AccountId, claimable_at: BlockNumber) {
+		sp_io::offchain_index::set(&Self::key(lost, rescuer), &(claimable_at, true).encode());
+	}
+
+	fn on_closed(lost: &AccountId, rescuer: &AccountId) {
+		sp_io::offchain_index::clear(&Self::key(lost, rescuer));
+	}
+}
+
+/// Gives the rescuer control of a recovered account through a proxy, in addition to
+/// `as_recovered`.
+pub trait RecoveredAccountProxy<AccountId> {
+	/// Make `rescuer` a proxy of the `recovered` account.
+	///
+	/// This is best-effort: the recovery is claimed even if it fails, e.g. because the
+	/// `recovered` account has too many proxies or can't afford their deposit.
+	fn add_proxy(recovered: &AccountId, rescuer: &AccountId) -> DispatchResult;
+
+	/// Remove `rescuer` from the proxies of the `recovered` account.
+	fn remove_proxy(recovered: &AccountId, rescuer: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId> RecoveredAccountProxy<AccountId> for () {
+	fn add_proxy(_: &AccountId, _: &AccountId) -> DispatchResult { Ok(()) }
+	fn remove_proxy(_: &AccountId, _: &AccountId) -> DispatchResult { Ok(()) }
 }
 
 /// An active recovery process.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct ActiveRecovery<BlockNumber, Balance, AccountId> {
 	/// The block number when the recovery process started.
 	created: BlockNumber,
@@ -222,7 +325,7 @@ pub struct ActiveRecovery<BlockNumber, Balance, AccountId> {
 }
 
 /// Configuration for recovering an account.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct RecoveryConfig<BlockNumber, Balance, AccountId> {
 	/// The minimum number of blocks since the start of the recovery process before the account
 	/// can be recovered.
@@ -256,6 +359,19 @@ decl_storage! {
 		/// Map from the user who can access it to the recovered account.
 		pub Proxy get(fn proxy):
 			map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
+
+		/// The weights of the friends of a recoverable account, in the order of its friends.
+		///
+		/// Friends weigh one if not set.
+		pub FriendWeights get(fn friend_weights):
+			map hasher(twox_64_concat) T::AccountId => Option<Vec<u16>>;
+
+		/// The recovered accounts the recovery added a proxy to.
+		///
+		/// Map from the user who can access it to the recovered account. Only these proxies are
+		/// removed by `cancel_recovered`.
+		pub ProxyAdded get(fn proxy_added):
+			map hasher(blake2_128_concat) T::AccountId => Option<T::AccountId>;
 	}
 }
 
@@ -263,6 +379,7 @@ decl_event! {
 	/// Events type.
 	pub enum Event<T> where
 		AccountId = <T as system::Trait>::AccountId,
+		BlockNumber = <T as system::Trait>::BlockNumber,
 	{
 		/// A recovery process has been set up for an \[account\].
 		RecoveryCreated(AccountId),
@@ -280,6 +397,15 @@ decl_event! {
 		AccountRecovered(AccountId, AccountId),
 		/// A recovery process has been removed for an \[account\].
 		RecoveryRemoved(AccountId),
+		/// Enough friends vouched for the recovery process of lost account by rescuer account,
+		/// it can be claimed from the given block.
+		/// \[lost, rescuer, claimable_at\]
+		RecoveryClaimable(AccountId, AccountId, BlockNumber),
+		/// The friend weights of an \[account\] have been set.
+		FriendWeightsSet(AccountId),
+		/// The rescuer account could not be made a proxy of the lost account, it can still
+		/// control it with `as_recovered`. \[lost, rescuer, error\]
+		RecoveredProxyFailed(AccountId, AccountId, DispatchError),
 	}
 }
 
@@ -289,7 +415,7 @@ decl_error! {
 		NotAllowed,
 		/// Threshold must be greater than zero
 		ZeroThreshold,
-		/// Friends list must be greater than zero and threshold
+		/// Friends list must be greater than zero
 		NotEnoughFriends,
 		/// Friends list must be less than max friends
 		MaxFriends,
@@ -317,6 +443,12 @@ decl_error! {
 		Overflow,
 		/// This account is already set up for recovery
 		AlreadyProxy,
+		/// The delay period is shorter than the minimum delay period
+		DelayTooShort,
+		/// There must be one weight of at least one for each friend
+		BadWeights,
+		/// The total weight of the friends must be at least the threshold
+		NotEnoughWeight,
 	}
 }
 
@@ -336,6 +468,9 @@ decl_module! {
 		/// The base amount of currency needed to reserve for starting a recovery.
 		const RecoveryDeposit: BalanceOf<T> = T::RecoveryDeposit::get();
 
+		/// The minimum delay period of a recovery configuration.
+		const MinDelayPeriod: T::BlockNumber = T::MinDelayPeriod::get();
+
 		/// Deposit one of this module's events by using the default implementation.
 		fn deposit_event() = default;
 
@@ -398,10 +533,12 @@ decl_module! {
 		/// - `friends`: A list of friends you trust to vouch for recovery attempts.
 		///   Should be ordered and contain no duplicate values.
 		/// - `threshold`: The number of friends that must vouch for a recovery attempt
-		///   before the account can be recovered. Should be less than or equal to
-		///   the length of the list of friends.
+		///   before the account can be recovered. If greater than the length of the list of
+		///   friends, the account can only be recovered once `set_friend_weights` gives them
+		///   enough weight.
 		/// - `delay_period`: The number of blocks after a recovery attempt is initialized
-		///   that needs to pass before the account can be recovered.
+		///   that needs to pass before the account can be recovered. Must be at least
+		///   `MinDelayPeriod`.
 		///
 		/// # <weight>
 		/// - Key: F (len of friends)
@@ -425,10 +562,10 @@ decl_module! {
 			// Check user input is valid
 			ensure!(threshold >= 1, Error::<T>::ZeroThreshold);
 			ensure!(!friends.is_empty(), Error::<T>::NotEnoughFriends);
-			ensure!(threshold as usize <= friends.len(), Error::<T>::NotEnoughFriends);
 			let max_friends = T::MaxFriends::get() as usize;
 			ensure!(friends.len() <= max_friends, Error::<T>::MaxFriends);
 			ensure!(Self::is_sorted_and_unique(&friends), Error::<T>::NotSorted);
+			ensure!(delay_period >= T::MinDelayPeriod::get(), Error::<T>::DelayTooShort);
 			// Total deposit is base fee + number of friends * factor fee
 			let friend_deposit = T::FriendDepositFactor::get()
 				.checked_mul(&friends.len().saturated_into())
@@ -477,7 +614,7 @@ decl_module! {
 		fn initiate_recovery(origin, account: T::AccountId) {
 			let who = ensure_signed(origin)?;
 			// Check that the account is recoverable
-			ensure!(<Recoverable<T>>::contains_key(&account), Error::<T>::NotRecoverable);
+			let recovery_config = Self::recovery_config(&account).ok_or(Error::<T>::NotRecoverable)?;
 			// Check that the recovery process has not already been started
 			ensure!(!<ActiveRecoveries<T>>::contains_key(&account, &who), Error::<T>::AlreadyStarted);
 			// Take recovery deposit
@@ -489,8 +626,10 @@ decl_module! {
 				deposit: recovery_deposit,
 				friends: vec![],
 			};
+			let claimable_at = recovery_status.created.saturating_add(recovery_config.delay_period);
 			// Create the active recovery storage item
 			<ActiveRecoveries<T>>::insert(&account, &who, recovery_status);
+			T::Notify::on_initiated(&account, &who, claimable_at);
 			Self::deposit_event(RawEvent::RecoveryInitiated(account, who));
 		}
 
@@ -508,6 +647,12 @@ decl_module! {
 		/// The combination of these two parameters must point to an active recovery
 		/// process.
 		///
+		/// Once the vouching friends weigh at least the threshold, `RecoveryClaimable` is
+		/// deposited to warn the owner of the lost account.
+		///
+		/// No fee is charged if the recovery process is active. The runtime should rate limit
+		/// such feeless calls, e.g. with `frame_system::CheckFeelessRateLimit`.
+		///
 		/// # <weight>
 		/// Key: F (len of friends in config), V (len of vouching friends)
 		/// - One storage read to get the recovery configuration. O(1), Codec O(F)
@@ -519,7 +664,12 @@ decl_module! {
 		///
 		/// Total Complexity: O(F + logF + V + logV)
 		/// # </weight>
-		#[weight = 100_000_000]
+		#[weight = FeelessIf(
+			100_000_000,
+			|(lost, rescuer): (&T::AccountId, &T::AccountId)| {
+				<ActiveRecoveries<T>>::contains_key(lost, rescuer)
+			},
+		)]
 		fn vouch_recovery(origin, lost: T::AccountId, rescuer: T::AccountId) {
 			let who = ensure_signed(origin)?;
 			// Get the recovery configuration for the lost account.
@@ -529,13 +679,22 @@ decl_module! {
 			// Make sure the voter is a friend
 			ensure!(Self::is_friend(&recovery_config.friends, &who), Error::<T>::NotFriend);
 			// Either insert the vouch, or return an error that the user already vouched.
+			let weight_before = Self::vouched_weight(&lost, &recovery_config, &active_recovery.friends);
 			match active_recovery.friends.binary_search(&who) {
 				Ok(_pos) => Err(Error::<T>::AlreadyVouched)?,
 				Err(pos) => active_recovery.friends.insert(pos, who.clone()),
 			}
+			let weight = Self::vouched_weight(&lost, &recovery_config, &active_recovery.friends);
+			let threshold = recovery_config.threshold as u32;
+			let claimable_at = active_recovery.created.saturating_add(recovery_config.delay_period);
 			// Update storage with the latest details
 			<ActiveRecoveries<T>>::insert(&lost, &rescuer, active_recovery);
-			Self::deposit_event(RawEvent::RecoveryVouched(lost, rescuer, who));
+			Self::deposit_event(RawEvent::RecoveryVouched(lost.clone(), rescuer.clone(), who));
+			// Warn the owner once the threshold is met.
+			if weight_before < threshold && weight >= threshold {
+				T::Notify::on_vouched(&lost, &rescuer, claimable_at);
+				Self::deposit_event(RawEvent::RecoveryClaimable(lost, rescuer, claimable_at));
+			}
 		}
 
 		/// Allow a successful rescuer to claim their recovered account.
@@ -553,12 +712,15 @@ decl_module! {
 		/// - One storage read to get the recovery configuration. O(1), Codec O(F)
 		/// - One storage read to get the active recovery process. O(1), Codec O(V)
 		/// - One storage read to get the current block number. O(1)
-		/// - One storage write. O(1), Codec O(V).
+		/// - One storage read to get the friend weights. O(1), Codec O(F)
+		/// - One call to the recovered proxy handler, e.g. one storage read and write of the
+		///   proxies and one deposit reserve. O(1)
+		/// - Two storage writes. O(1), Codec O(V).
 		/// - One event.
 		///
 		/// Total Complexity: O(F + V)
 		/// # </weight>
-		#[weight = 100_000_000]
+		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(2, 3)]
 		fn claim_recovery(origin, account: T::AccountId) {
 			let who = ensure_signed(origin)?;
 			// Get the recovery configuration for the lost account
@@ -574,9 +736,17 @@ decl_module! {
 			ensure!(recoverable_block_number <= current_block_number, Error::<T>::DelayPeriod);
 			// Make sure the threshold is met
 			ensure!(
-				recovery_config.threshold as usize <= active_recovery.friends.len(),
+				recovery_config.threshold as u32
+					<= Self::vouched_weight(&account, &recovery_config, &active_recovery.friends),
 				Error::<T>::Threshold
 			);
+			// Hand control of the account over to the rescuer, `as_recovered` works regardless.
+			match T::RecoveredProxy::add_proxy(&account, &who) {
+				Ok(()) => ProxyAdded::<T>::insert(&who, &account),
+				Err(e) => Self::deposit_event(
+					RawEvent::RecoveredProxyFailed(account.clone(), who.clone(), e)
+				),
+			}
 			// Create the recovery storage item
 			Proxy::<T>::insert(&who, &account);
 			system::Module::<T>::inc_ref(&who);
@@ -611,6 +781,7 @@ decl_module! {
 			// Move the reserved funds from the rescuer to the rescued account.
 			// Acts like a slashing mechanism for those who try to maliciously recover accounts.
 			let _ = T::Currency::repatriate_reserved(&rescuer, &who, active_recovery.deposit, BalanceStatus::Free);
+			T::Notify::on_closed(&who, &rescuer);
 			Self::deposit_event(RawEvent::RecoveryClosed(who, rescuer));
 		}
 
@@ -644,11 +815,44 @@ decl_module! {
 			// Take the recovery configuration for this account.
 			let recovery_config = <Recoverable<T>>::take(&who).ok_or(Error::<T>::NotRecoverable)?;
 
+			<FriendWeights<T>>::remove(&who);
+
 			// Unreserve the initial deposit for the recovery configuration.
 			T::Currency::unreserve(&who, recovery_config.deposit);
 			Self::deposit_event(RawEvent::RecoveryRemoved(who));
 		}
 
+		/// Set the weight each friend counts for towards the threshold of your recovery
+		/// configuration.
+		///
+		/// The dispatch origin for this call must be _Signed_ and must be a
+		/// recoverable account (i.e. has a recovery configuration).
+		///
+		/// Parameters:
+		/// - `weights`: The weight of each friend, in the order of the friends list. Each
+		///   weight must be at least one, and together they must weigh at least the threshold.
+		///
+		/// # <weight>
+		/// Key: F (len of friends)
+		/// - One storage read to get the recovery configuration. O(1), Codec O(F)
+		/// - A sum of the weights. O(F)
+		/// - One storage write. O(1), Codec O(F).
+		/// - One event.
+		///
+		/// Total Complexity: O(F)
+		/// # </weight>
+		#[weight = 30_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn set_friend_weights(origin, weights: Vec<u16>) {
+			let who = ensure_signed(origin)?;
+			let recovery_config = Self::recovery_config(&who).ok_or(Error::<T>::NotRecoverable)?;
+			ensure!(weights.len() == recovery_config.friends.len(), Error::<T>::BadWeights);
+			ensure!(weights.iter().all(|w| *w >= 1), Error::<T>::BadWeights);
+			let total_weight: u32 = weights.iter().map(|w| *w as u32).sum();
+			ensure!(recovery_config.threshold as u32 <= total_weight, Error::<T>::NotEnoughWeight);
+			<FriendWeights<T>>::insert(&who, weights);
+			Self::deposit_event(RawEvent::FriendWeightsSet(who));
+		}
+
 		/// Cancel the ability to use `as_recovered` for `account`.
 		///
 		/// The dispatch origin for this call must be _Signed_ and registered to
@@ -659,12 +863,20 @@ decl_module! {
 		///
 		/// # <weight>
 		/// - One storage mutation to check account is recovered by `who`. O(1)
+		/// - One storage take to check whether the recovery added the proxy. O(1)
+		/// - One call to the recovered proxy handler if it did, e.g. one storage read and
+		///   write of the proxies and one deposit unreserve. O(1)
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::DbWeight::get().reads_writes(3, 3)]
 		fn cancel_recovered(origin, account: T::AccountId) {
 			let who = ensure_signed(origin)?;
 			// Check `who` is allowed to make a call on behalf of `account`
-			ensure!(Self::proxy(&who) == Some(account), Error::<T>::NotAllowed);
+			ensure!(Self::proxy(&who) == Some(account.clone()), Error::<T>::NotAllowed);
+			// Leave a proxy the recovery didn't add alone. The recovered account may have
+			// removed the proxy itself already.
+			if ProxyAdded::<T>::take(&who).as_ref() == Some(&account) {
+				let _ = T::RecoveredProxy::remove_proxy(&account, &who);
+			}
 			Proxy::<T>::remove(&who);
 			system::Module::<T>::dec_ref(&who);
 		}
@@ -681,4 +893,19 @@ impl<T: Trait> Module<T> {
 	fn is_friend(friends: &Vec<T::AccountId>, friend: &T::AccountId) -> bool {
 		friends.binary_search(&friend).is_ok()
 	}
+
+	/// The total weight of the friends of `account` who vouched.
+	fn vouched_weight(
+		account: &T::AccountId,
+		recovery_config: &RecoveryConfig<T::BlockNumber, BalanceOf<T>, T::AccountId>,
+		vouched: &[T::AccountId],
+	) -> u32 {
+		match Self::friend_weights(account) {
+			Some(weights) => vouched.iter()
+				.filter_map(|v| recovery_config.friends.binary_search(v).ok())
+				.map(|i| weights.get(i).copied().unwrap_or(1) as u32)
+				.sum(),
+			None => vouched.len() as u32,
+		}
+	}
 }
@@ -25,6 +25,7 @@ use frame_support::{
 	traits::{OnInitialize, OnFinalize},
 };
 use sp_core::H256;
+use std::cell::RefCell;
 use sp_runtime::{
 	Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header,
 };
@@ -76,6 +77,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -105,6 +109,63 @@ parameter_types! {
 	pub const FriendDepositFactor: u64 = 1;
 	pub const MaxFriends: u16 = 3;
 	pub const RecoveryDeposit: u64 = 10;
+	pub const MinDelayPeriod: u64 = 5;
+}
+
+thread_local! {
+	pub static NOTIFICATIONS: RefCell<Vec<Notification>> = RefCell::new(vec![]);
+	pub static PROXIES: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![]);
+}
+
+/// A notification received by `TestNotify`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Notification {
+	Initiated(u64, u64, u64),
+	Vouched(u64, u64, u64),
+	Closed(u64, u64),
+}
+
+pub struct TestNotify;
+impl NotifyRecovery<u64, u64> for TestNotify {
+	fn on_initiated(lost: &u64, rescuer: &u64, claimable_at: u64) {
+		NOTIFICATIONS.with(|n| n.borrow_mut().push(Notification::Initiated(*lost, *rescuer, claimable_at)));
+	}
+
+	fn on_vouched(lost: &u64, rescuer: &u64, claimable_at: u64) {
+		NOTIFICATIONS.with(|n| n.borrow_mut().push(Notification::Vouched(*lost, *rescuer, claimable_at)));
+	}
+
+	fn on_closed(lost: &u64, rescuer: &u64) {
+		NOTIFICATIONS.with(|n| n.borrow_mut().push(Notification::Closed(*lost, *rescuer)));
+	}
+}
+
+pub fn notifications() -> Vec<Notification> {
+	NOTIFICATIONS.with(|n| n.borrow().clone())
+}
+
+/// Keeps the (recovered, rescuer) proxies in `PROXIES`.
+pub struct TestProxy;
+impl RecoveredAccountProxy<u64> for TestProxy {
+	fn add_proxy(recovered: &u64, rescuer: &u64) -> DispatchResult {
+		PROXIES.with(|p| {
+			let mut proxies = p.borrow_mut();
+			if proxies.contains(&(*recovered, *rescuer)) {
+				return Err(DispatchError::Other("Duplicate"));
+			}
+			proxies.push((*recovered, *rescuer));
+			Ok(())
+		})
+	}
+
+	fn remove_proxy(recovered: &u64, rescuer: &u64) -> DispatchResult {
+		PROXIES.with(|p| p.borrow_mut().retain(|x| *x != (*recovered, *rescuer)));
+		Ok(())
+	}
+}
+
+pub fn proxies() -> Vec<(u64, u64)> {
+	PROXIES.with(|p| p.borrow().clone())
 }
 
 impl Trait for Test {
@@ -115,6 +176,9 @@ impl Trait for Test {
 	type FriendDepositFactor = FriendDepositFactor;
 	type MaxFriends = MaxFriends;
 	type RecoveryDeposit = RecoveryDeposit;
+	type MinDelayPeriod = MinDelayPeriod;
+	type Notify = TestNotify;
+	type RecoveredProxy = TestProxy;
 }
 
 pub type Recovery = Module<Test>;
@@ -125,6 +189,8 @@ pub type BalancesCall = pallet_balances::Call<Test>;
 pub type RecoveryCall = super::Call<Test>;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	NOTIFICATIONS.with(|n| n.borrow_mut().clear());
+	PROXIES.with(|p| p.borrow_mut().clear());
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
 		balances: vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)],
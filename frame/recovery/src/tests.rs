@@ -19,13 +19,14 @@
 
 use super::*;
 use mock::{
-	Recovery, Balances, Test, Origin, Call, BalancesCall, RecoveryCall,
-	new_test_ext, run_to_block
+	Recovery, Balances, System, Test, TestEvent, Origin, Call, BalancesCall, RecoveryCall,
+	Notification, new_test_ext, run_to_block, notifications, proxies, PROXIES,
 };
 use sp_runtime::traits::{BadOrigin};
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency},
+	weights::Pays,
 };
 
 #[test]
@@ -153,11 +154,6 @@ fn create_recovery_handles_basic_errors() {
 			Recovery::create_recovery(Origin::signed(5), vec![2], 0, 0),
 			Error::<Test>::ZeroThreshold
 		);
-		// Threshold greater than friends length
-		assert_noop!(
-			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 4, 0),
-			Error::<Test>::NotEnoughFriends
-		);
 		// Too many friends
 		assert_noop!(
 			Recovery::create_recovery(Origin::signed(5), vec![1, 2, 3, 4], 4, 0),
@@ -297,6 +293,20 @@ fn vouch_recovery_works() {
 	});
 }
 
+#[test]
+fn vouching_for_active_recoveries_is_feeless() {
+	new_test_ext().execute_with(|| {
+		let pays_fee = || RecoveryCall::vouch_recovery(5, 1).get_dispatch_info().pays_fee;
+		assert_eq!(pays_fee(), Pays::Yes);
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10));
+		assert_eq!(pays_fee(), Pays::Yes);
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_eq!(pays_fee(), Pays::No);
+		assert_ok!(Recovery::close_recovery(Origin::signed(5), 1));
+		assert_eq!(pays_fee(), Pays::Yes);
+	});
+}
+
 #[test]
 fn claim_recovery_handles_basic_errors() {
 	new_test_ext().execute_with(|| {
@@ -385,3 +395,128 @@ fn remove_recovery_works() {
 		assert_ok!(Recovery::remove_recovery(Origin::signed(5)));
 	});
 }
+
+#[test]
+fn create_recovery_requires_min_delay_period() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 4),
+			Error::<Test>::DelayTooShort
+		);
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 5));
+	});
+}
+
+#[test]
+fn owner_is_notified_of_recovery_attempts() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 2, 10));
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_eq!(notifications(), vec![Notification::Initiated(5, 1, 11)]);
+
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
+		assert_eq!(notifications().len(), 1);
+		// The threshold is met, the owner is warned once.
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(3), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(4), 5, 1));
+		assert_eq!(notifications().len(), 2);
+		assert_eq!(notifications()[1], Notification::Vouched(5, 1, 11));
+		let claimable = TestEvent::recovery(RawEvent::RecoveryClaimable(5, 1, 11));
+		assert_eq!(System::events().iter().filter(|r| r.event == claimable).count(), 1);
+
+		// The owner closes the malicious recovery before it can be claimed.
+		assert_ok!(Recovery::close_recovery(Origin::signed(5), 1));
+		assert_eq!(notifications()[2], Notification::Closed(5, 1));
+		run_to_block(11);
+		assert_noop!(Recovery::claim_recovery(Origin::signed(1), 5), Error::<Test>::NotStarted);
+	});
+}
+
+#[test]
+fn set_friend_weights_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Recovery::set_friend_weights(Origin::signed(5), vec![1, 1, 1]), Error::<Test>::NotRecoverable);
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10));
+		assert_noop!(Recovery::set_friend_weights(Origin::signed(5), vec![1, 1]), Error::<Test>::BadWeights);
+		assert_noop!(Recovery::set_friend_weights(Origin::signed(5), vec![3, 0, 1]), Error::<Test>::BadWeights);
+		assert_ok!(Recovery::set_friend_weights(Origin::signed(5), vec![3, 1, 1]));
+		assert_eq!(Recovery::friend_weights(&5), Some(vec![3, 1, 1]));
+		// The weights are removed with the recovery configuration.
+		assert_ok!(Recovery::remove_recovery(Origin::signed(5)));
+		assert_eq!(Recovery::friend_weights(&5), None);
+	});
+}
+
+#[test]
+fn weighted_friends_reach_threshold() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 3, 10));
+		assert_ok!(Recovery::set_friend_weights(Origin::signed(5), vec![3, 1, 1]));
+		// Friends 3 and 4 only weigh 2 together.
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(3), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(4), 5, 1));
+		run_to_block(11);
+		assert_noop!(Recovery::claim_recovery(Origin::signed(1), 5), Error::<Test>::Threshold);
+		// Friend 2 alone meets the threshold.
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(2), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 2));
+		run_to_block(21);
+		assert_ok!(Recovery::claim_recovery(Origin::signed(2), 5));
+	});
+}
+
+#[test]
+fn recovered_account_is_controlled_through_proxy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 1, 10));
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
+		run_to_block(11);
+		assert_ok!(Recovery::claim_recovery(Origin::signed(1), 5));
+		assert_eq!(proxies(), vec![(5, 1)]);
+
+		assert_ok!(Recovery::cancel_recovered(Origin::signed(1), 5));
+		assert!(proxies().is_empty());
+	});
+}
+
+#[test]
+fn recovery_is_claimed_even_if_the_proxy_cannot_be_added() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 1, 10));
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
+		run_to_block(11);
+		// 1 is already a proxy of 5.
+		PROXIES.with(|p| p.borrow_mut().push((5, 1)));
+		assert_ok!(Recovery::claim_recovery(Origin::signed(1), 5));
+		assert_eq!(Recovery::proxy(&1), Some(5));
+
+		let failed = TestEvent::recovery(
+			RawEvent::RecoveredProxyFailed(5, 1, DispatchError::Other("Duplicate"))
+		);
+		assert_eq!(System::events().iter().filter(|r| r.event == failed).count(), 1);
+
+		// The proxy was not added by the recovery, so it is left alone.
+		assert_ok!(Recovery::cancel_recovered(Origin::signed(1), 5));
+		assert_eq!(proxies(), vec![(5, 1)]);
+	});
+}
+
+#[test]
+fn threshold_can_exceed_the_number_of_friends_with_weights() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(Origin::signed(5), vec![2, 3, 4], 5, 10));
+		assert_noop!(Recovery::set_friend_weights(Origin::signed(5), vec![2, 1, 1]), Error::<Test>::NotEnoughWeight);
+		assert_ok!(Recovery::set_friend_weights(Origin::signed(5), vec![3, 1, 1]));
+		assert_ok!(Recovery::initiate_recovery(Origin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(2), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(3), 5, 1));
+		run_to_block(11);
+		assert_noop!(Recovery::claim_recovery(Origin::signed(1), 5), Error::<Test>::Threshold);
+		assert_ok!(Recovery::vouch_recovery(Origin::signed(4), 5, 1));
+		assert_ok!(Recovery::claim_recovery(Origin::signed(1), 5));
+	});
+}
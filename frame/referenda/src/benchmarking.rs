@@ -0,0 +1,195 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Referenda pallet benchmarking.
+//!
+//! The benchmarks submit their referenda as proposals to be dispatched from the root origin, so the
+//! runtime must have a track for it.
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_support::traits::{Currency, Get, EnsureOrigin, OnInitialize, UnfilteredDispatchable};
+use frame_system::{RawOrigin, Module as System, EventRecord};
+use sp_runtime::traits::Bounded;
+use pallet_democracy::{Vote, Conviction};
+
+use crate::Module as Referenda;
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Trait>(generic_event: <T as Trait>::Event) {
+	let events = System::<T>::events();
+	let system_event: <T as frame_system::Trait>::Event = generic_event.into();
+	// compare to the last event record
+	let EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2.into());
+	caller
+}
+
+fn submit<T: Trait>(n: u32) -> Result<ReferendumIndex, &'static str>
+	where T::Proposal: From<frame_system::Call<T>>
+{
+	let caller = funded_account::<T>("submitter", n);
+	let proposal: T::Proposal = frame_system::Call::<T>::remark(n.encode()).into();
+	Referenda::<T>::submit(
+		RawOrigin::Signed(caller).into(),
+		Box::new(RawOrigin::Root.into()),
+		Box::new(proposal),
+		0.into(),
+	)?;
+	Ok(ReferendumCount::get() - 1)
+}
+
+fn place_deposit<T: Trait>(index: ReferendumIndex) -> Result<(), &'static str> {
+	let caller = funded_account::<T>("depositor", index);
+	Referenda::<T>::place_decision_deposit(RawOrigin::Signed(caller).into(), index)?;
+	Ok(())
+}
+
+fn account_vote<T: Trait>(b: BalanceOf<T>) -> AccountVote<BalanceOf<T>> {
+	let vote = Vote { aye: true, conviction: Conviction::Locked1x };
+	AccountVote::Standard { vote, balance: b }
+}
+
+fn track_id<T: Trait>() -> Result<TrackId, &'static str> {
+	T::Tracks::track_for(&RawOrigin::Root.into()).map_err(|_| "no root track")
+}
+
+fn root_track<T: Trait>() -> Result<TrackInfoOf<T>, &'static str> {
+	T::Tracks::info(track_id::<T>()?).ok_or("no root track")
+}
+
+benchmarks! {
+	where_clause { where T::Proposal: From<frame_system::Call<T>> }
+
+	_ { }
+
+	submit {
+		let caller = funded_account::<T>("caller", 0);
+		let proposal: T::Proposal = frame_system::Call::<T>::remark(vec![]).into();
+	}: _(RawOrigin::Signed(caller.clone()), Box::new(RawOrigin::Root.into()), Box::new(proposal), 0.into())
+	verify {
+		assert!(matches!(Referenda::<T>::referendum_info(0), Some(ReferendumInfo::Ongoing(_))));
+	}
+
+	place_decision_deposit {
+		let caller = funded_account::<T>("caller", 0);
+		let index = submit::<T>(0)?;
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		let amount = root_track::<T>()?.decision_deposit;
+		assert_last_event::<T>(RawEvent::DecisionDepositPlaced(index, caller, amount).into());
+	}
+
+	vote {
+		let r in 1 .. T::MaxVotes::get();
+
+		let caller = funded_account::<T>("caller", 0);
+		for i in 0 .. r - 1 {
+			let index = submit::<T>(i)?;
+			Referenda::<T>::vote(RawOrigin::Signed(caller.clone()).into(), index, account_vote::<T>(100.into()))?;
+		}
+		let index = submit::<T>(r)?;
+	}: _(RawOrigin::Signed(caller.clone()), index, account_vote::<T>(100.into()))
+	verify {
+		assert_eq!(VotingFor::<T>::iter_prefix_values(&caller).count(), r as usize);
+	}
+
+	remove_vote {
+		let caller = funded_account::<T>("caller", 0);
+		let index = submit::<T>(0)?;
+		Referenda::<T>::vote(RawOrigin::Signed(caller.clone()).into(), index, account_vote::<T>(100.into()))?;
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		assert!(VotingFor::<T>::get(&caller, index).is_none());
+	}
+
+	unlock {
+		let r in 1 .. T::MaxVotes::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2.into());
+		for i in 0 .. r {
+			let index = submit::<T>(i)?;
+			Referenda::<T>::vote(RawOrigin::Signed(caller.clone()).into(), index, account_vote::<T>(100.into()))?;
+		}
+		PriorLocks::<T>::mutate(&caller, |prior| prior.accumulate(1.into(), 200.into()));
+		System::<T>::set_block_number(1.into());
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(!PriorLocks::<T>::contains_key(&caller));
+	}
+
+	cancel {
+		let origin = T::CancelOrigin::successful_origin();
+		let index = submit::<T>(0)?;
+		place_deposit::<T>(index)?;
+		let call = Call::<T>::cancel(index);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T>(RawEvent::Cancelled(index).into());
+	}
+
+	on_initialize {
+		// Alarms going off.
+		let a in 0 .. 100;
+		// Referenda being decided upon.
+		let d in 0 .. root_track::<T>()?.max_deciding;
+
+		let track = root_track::<T>()?;
+		for i in 0 .. d {
+			let index = submit::<T>(i)?;
+			place_deposit::<T>(index)?;
+		}
+		System::<T>::set_block_number(track.prepare_period);
+		Referenda::<T>::on_initialize(track.prepare_period);
+		assert_eq!(Deciding::get(track_id::<T>()?).len(), d as usize);
+
+		// These referenda time out without their decision deposit being placed.
+		for i in 0 .. a {
+			submit::<T>(d + i)?;
+		}
+		let now = System::<T>::block_number() + T::UndecidingTimeout::get();
+		System::<T>::set_block_number(now);
+	}: { Referenda::<T>::on_initialize(now) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_submit::<Test>());
+			assert_ok!(test_benchmark_place_decision_deposit::<Test>());
+			assert_ok!(test_benchmark_vote::<Test>());
+			assert_ok!(test_benchmark_remove_vote::<Test>());
+			assert_ok!(test_benchmark_unlock::<Test>());
+			assert_ok!(test_benchmark_cancel::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+		});
+	}
+}
@@ -0,0 +1,688 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Referenda Module
+//!
+//! - [`referenda::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Referenda pallet handles the decision making of proposals through referenda which are
+//! decided upon on one of several tracks, alongside one another.
+//!
+//! Every proposal is submitted together with the origin from which it is to be dispatched. The
+//! origin determines the track of the referendum, and each track has its own parameters: the
+//! deposit needed for a referendum to be decided upon, the number of referenda that may be
+//! decided upon at once, the lengths of its preparation, decision and confirmation periods, the
+//! minimum delay before enactment and the curves of the minimum approval and support needed over
+//! the decision period.
+//!
+//! Voting uses the conviction voting of the Democracy pallet. Votes are locked until the voter
+//! removes them and, for votes on the winning side, for the conviction's multiple of
+//! `VoteLockingPeriod` after the referendum concluded.
+//!
+//! ### Lifecycle
+//!
+//! - A referendum is submitted, taking the `SubmissionDeposit` of the submitter.
+//! - Once the decision deposit of its track has been placed and the track's preparation period has
+//!   elapsed, the referendum enters its decision period, or it waits in its track's queue until
+//!   fewer than `max_deciding` referenda are being decided upon. A referendum which has not had its
+//!   decision deposit placed within `UndecidingTimeout` times out.
+//! - A referendum which is being decided upon is passing as long as its approval and support are
+//!   at least the values of its track's curves at the fraction of the decision period which has
+//!   elapsed. It is approved once it has kept passing for the confirmation period, and rejected if
+//!   it is not passing (or confirming) at the end of the decision period.
+//! - An approved proposal is scheduled for enactment. All deposits are returned once a referendum
+//!   concludes.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `submit` - Submit a proposal, to be dispatched from a given origin, for a referendum.
+//! - `place_decision_deposit` - Place the decision deposit of a referendum.
+//! - `vote` - Vote in a referendum.
+//! - `remove_vote` - Remove a vote from a referendum.
+//! - `unlock` - Redetermine the balance of an account locked due to voting.
+//!
+//! #### Cancellation Origin
+//!
+//! - `cancel` - Cancel an ongoing referendum.
+//!
+//! [`Call`]: ./enum.Call.html
+//! [`Trait`]: ./trait.Trait.html
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::{
+	DispatchError, Perbill,
+	traits::{Zero, Dispatchable, Saturating},
+};
+use codec::Encode;
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
+	weights::Weight,
+	traits::{
+		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
+		schedule::{Named as ScheduleNamed, DispatchTime}, EnsureOrigin,
+	},
+};
+use frame_system::{self as system, ensure_signed};
+use pallet_democracy::{AccountVote, PriorLock, Tally};
+
+mod types;
+mod default_weight;
+pub use types::{
+	TrackId, Curve, TrackInfo, TracksInfo, Deposit, DecidingStatus, ReferendumStatus, ReferendumInfo,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+const REFERENDA_ID: LockIdentifier = *b"referend";
+
+/// A referendum index.
+pub type ReferendumIndex = u32;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type TrackInfoOf<T> = TrackInfo<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+type ReferendumStatusOf<T> = ReferendumStatus<
+	<T as Trait>::PalletsOrigin,
+	<T as Trait>::Proposal,
+	<T as frame_system::Trait>::BlockNumber,
+	BalanceOf<T>,
+	<T as frame_system::Trait>::AccountId,
+>;
+type ReferendumInfoOf<T> = ReferendumInfo<
+	<T as Trait>::PalletsOrigin,
+	<T as Trait>::Proposal,
+	<T as frame_system::Trait>::BlockNumber,
+	BalanceOf<T>,
+	<T as frame_system::Trait>::AccountId,
+>;
+
+pub trait WeightInfo {
+	fn submit() -> Weight;
+	fn place_decision_deposit() -> Weight;
+	fn vote(r: u32, ) -> Weight;
+	fn remove_vote() -> Weight;
+	fn unlock(r: u32, ) -> Weight;
+	fn cancel() -> Weight;
+	fn on_initialize(a: u32, d: u32, ) -> Weight;
+}
+
+pub trait Trait: frame_system::Trait + Sized {
+	/// The proposals which are voted upon.
+	type Proposal: Parameter + Dispatchable<Origin=Self::Origin>;
+
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type for this module.
+	type Currency: ReservableCurrency<Self::AccountId>
+		+ LockableCurrency<Self::AccountId, Moment=Self::BlockNumber>;
+
+	/// The Scheduler, used to enact approved proposals.
+	type Scheduler: ScheduleNamed<Self::BlockNumber, Self::Proposal, Self::PalletsOrigin>;
+
+	/// Overarching type of all pallets origins. Proposals are dispatched from one of these.
+	type PalletsOrigin: Parameter + From<system::RawOrigin<Self::AccountId>>;
+
+	/// The tracks on which referenda are decided, by the origin of their proposal.
+	type Tracks: TracksInfo<BalanceOf<Self>, Self::BlockNumber, Origin=Self::PalletsOrigin>;
+
+	/// Origin from which any ongoing referendum may be cancelled.
+	type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The amount which must be placed on deposit to submit a referendum.
+	type SubmissionDeposit: Get<BalanceOf<Self>>;
+
+	/// The number of blocks after submission within which the decision deposit of a referendum
+	/// must be placed, or it times out.
+	type UndecidingTimeout: Get<Self::BlockNumber>;
+
+	/// The period for which votes on the winning side of a referendum stay locked after it
+	/// concluded, for every unit of conviction lock period.
+	type VoteLockingPeriod: Get<Self::BlockNumber>;
+
+	/// The maximum number of referenda in which an account may have a vote.
+	type MaxVotes: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Referenda {
+		/// The next free referendum index, aka the number of referenda submitted so far.
+		pub ReferendumCount get(fn referendum_count): ReferendumIndex;
+
+		/// Information concerning any given referendum.
+		pub ReferendumInfoFor get(fn referendum_info):
+			map hasher(twox_64_concat) ReferendumIndex => Option<ReferendumInfoOf<T>>;
+
+		/// The referenda which are being decided upon on each track.
+		pub Deciding get(fn deciding): map hasher(twox_64_concat) TrackId => Vec<ReferendumIndex>;
+
+		/// The referenda of each track which are ready to be decided upon, but are waiting for a
+		/// decision slot. In the order in which they became ready.
+		pub TrackQueue get(fn track_queue): map hasher(twox_64_concat) TrackId => Vec<ReferendumIndex>;
+
+		/// The referenda which need to be looked at in a given block, because their preparation
+		/// period ends or their decision deposit is due.
+		pub Alarms: map hasher(twox_64_concat) T::BlockNumber => Vec<ReferendumIndex>;
+
+		/// The votes of an account, by referendum.
+		pub VotingFor: double_map hasher(twox_64_concat) T::AccountId,
+			hasher(twox_64_concat) ReferendumIndex => Option<AccountVote<BalanceOf<T>>>;
+
+		/// The locks of an account which remain from the removed votes of concluded referenda.
+		pub PriorLocks get(fn prior_locks): map hasher(twox_64_concat) T::AccountId
+			=> PriorLock<T::BlockNumber, BalanceOf<T>>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		Balance = BalanceOf<T>,
+	{
+		/// A referendum has been submitted. \[index, track, submitter\]
+		Submitted(ReferendumIndex, TrackId, AccountId),
+		/// The decision deposit of a referendum has been placed. \[index, who, amount\]
+		DecisionDepositPlaced(ReferendumIndex, AccountId, Balance),
+		/// A referendum has entered its decision period. \[index, track\]
+		DecisionStarted(ReferendumIndex, TrackId),
+		/// A referendum is passing and its confirmation period has begun. \[index\]
+		ConfirmStarted(ReferendumIndex),
+		/// A referendum stopped passing during its confirmation period. \[index\]
+		ConfirmAborted(ReferendumIndex),
+		/// A referendum has been approved and its proposal scheduled for enactment. \[index\]
+		Approved(ReferendumIndex),
+		/// A referendum has been rejected. \[index\]
+		Rejected(ReferendumIndex),
+		/// A referendum has timed out without its decision deposit being placed. \[index\]
+		TimedOut(ReferendumIndex),
+		/// A referendum has been cancelled. \[index\]
+		Cancelled(ReferendumIndex),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// There is no track for the origin of the proposal.
+		NoTrack,
+		/// Referendum is not ongoing.
+		NotOngoing,
+		/// The decision deposit has already been placed.
+		HasDeposit,
+		/// The account has not voted in the referendum.
+		NotVoter,
+		/// Maximum number of votes reached.
+		MaxVotesReached,
+		/// Too high a balance was provided that the account cannot afford.
+		InsufficientFunds,
+		/// An unexpected integer overflow occurred.
+		Overflow,
+		/// An unexpected integer underflow occurred.
+		Underflow,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// The amount which must be placed on deposit to submit a referendum.
+		const SubmissionDeposit: BalanceOf<T> = T::SubmissionDeposit::get();
+
+		/// The number of blocks after submission within which the decision deposit of a referendum
+		/// must be placed.
+		const UndecidingTimeout: T::BlockNumber = T::UndecidingTimeout::get();
+
+		/// The period for which winning votes stay locked, per unit of conviction lock period.
+		const VoteLockingPeriod: T::BlockNumber = T::VoteLockingPeriod::get();
+
+		/// The maximum number of referenda in which an account may have a vote.
+		const MaxVotes: u32 = T::MaxVotes::get();
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::begin_block(now)
+		}
+
+		/// Submit a proposal for a referendum on the track of the origin it is to be dispatched
+		/// from.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have funds to cover
+		/// the `SubmissionDeposit`.
+		///
+		/// - `proposal_origin`: The origin from which the proposal is dispatched if approved. It
+		///   determines the track of the referendum.
+		/// - `proposal`: The proposal.
+		/// - `enactment_delay`: The number of blocks between approval and enactment. The track's
+		///   `min_enactment_period` is used if this is shorter.
+		///
+		/// Emits `Submitted`.
+		///
+		/// Weight: `O(1)`.
+		#[weight = T::WeightInfo::submit()]
+		fn submit(origin,
+			proposal_origin: Box<T::PalletsOrigin>,
+			proposal: Box<T::Proposal>,
+			enactment_delay: T::BlockNumber,
+		) {
+			let who = ensure_signed(origin)?;
+			let track = T::Tracks::track_for(&proposal_origin).map_err(|_| Error::<T>::NoTrack)?;
+			let info = Self::track_info(track)?;
+			let amount = T::SubmissionDeposit::get();
+			T::Currency::reserve(&who, amount)?;
+
+			let now = system::Module::<T>::block_number();
+			let index = ReferendumCount::mutate(|c| {
+				let index = *c;
+				*c += 1;
+				index
+			});
+			let status = ReferendumStatus {
+				track,
+				origin: *proposal_origin,
+				proposal: *proposal,
+				enactment: enactment_delay.max(info.min_enactment_period),
+				submitted: now,
+				submission_deposit: Deposit { who: who.clone(), amount },
+				decision_deposit: None,
+				deciding: None,
+				queued: false,
+				tally: Tally::default(),
+			};
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+			if !info.prepare_period.is_zero() {
+				Self::set_alarm(now.saturating_add(info.prepare_period), index);
+			}
+			Self::set_alarm(now.saturating_add(T::UndecidingTimeout::get()), index);
+			Self::deposit_event(RawEvent::Submitted(index, track, who));
+		}
+
+		/// Place the decision deposit of a referendum.
+		///
+		/// The referendum enters its decision period, or its track's queue, immediately if its
+		/// preparation period has elapsed, or otherwise once it has.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have funds to cover
+		/// the decision deposit of the referendum's track.
+		///
+		/// - `index`: The index of the referendum.
+		///
+		/// Emits `DecisionDepositPlaced`.
+		///
+		/// Weight: `O(1)`.
+		#[weight = T::WeightInfo::place_decision_deposit()]
+		fn place_decision_deposit(origin, #[compact] index: ReferendumIndex) {
+			let who = ensure_signed(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			ensure!(status.decision_deposit.is_none(), Error::<T>::HasDeposit);
+			let info = Self::track_info(status.track)?;
+			let amount = info.decision_deposit;
+			T::Currency::reserve(&who, amount)?;
+			status.decision_deposit = Some(Deposit { who: who.clone(), amount });
+
+			let now = system::Module::<T>::block_number();
+			if now >= status.submitted.saturating_add(info.prepare_period) {
+				Self::ready_for_decision(index, &mut status, now, &info);
+			}
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+			Self::deposit_event(RawEvent::DecisionDepositPlaced(index, who, amount));
+		}
+
+		/// Vote in a referendum, either to enact its proposal or to keep the status quo. A previous
+		/// vote of the sender in the referendum is replaced.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `index`: The index of the referendum to vote for.
+		/// - `vote`: The vote configuration.
+		///
+		/// The balance of the vote is locked until the vote is removed and the lock is
+		/// redetermined with `unlock`.
+		///
+		/// Weight: `O(R)` where R is the number of referenda the voter has voted on.
+		#[weight = T::WeightInfo::vote(T::MaxVotes::get())]
+		fn vote(origin,
+			#[compact] index: ReferendumIndex,
+			vote: AccountVote<BalanceOf<T>>,
+		) {
+			let who = ensure_signed(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			ensure!(vote.balance() <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
+			if let Some(old) = VotingFor::<T>::get(&who, index) {
+				status.tally.remove(old).ok_or(Error::<T>::Underflow)?;
+			} else {
+				let votes = VotingFor::<T>::iter_prefix_values(&who).count() as u32;
+				ensure!(votes < T::MaxVotes::get(), Error::<T>::MaxVotesReached);
+			}
+			status.tally.add(vote).ok_or(Error::<T>::Overflow)?;
+
+			VotingFor::<T>::insert(&who, index, vote);
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+			T::Currency::extend_lock(REFERENDA_ID, &who, vote.balance(), WithdrawReason::Transfer.into());
+		}
+
+		/// Remove a vote for a referendum.
+		///
+		/// If the referendum is ongoing, the vote is removed from its tally. If it was approved or
+		/// rejected and the vote was on the winning side, the balance of the vote stays locked for
+		/// the conviction's multiple of `VoteLockingPeriod` after the referendum concluded.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `index`: The index of the referendum.
+		///
+		/// Weight: `O(1)`.
+		#[weight = T::WeightInfo::remove_vote()]
+		fn remove_vote(origin, #[compact] index: ReferendumIndex) {
+			let who = ensure_signed(origin)?;
+			let vote = VotingFor::<T>::get(&who, index).ok_or(Error::<T>::NotVoter)?;
+			match ReferendumInfoFor::<T>::get(index) {
+				Some(ReferendumInfo::Ongoing(mut status)) => {
+					status.tally.remove(vote).ok_or(Error::<T>::Underflow)?;
+					ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+				}
+				Some(ReferendumInfo::Approved(end)) => Self::lock_prior(&who, vote, true, end),
+				Some(ReferendumInfo::Rejected(end)) => Self::lock_prior(&who, vote, false, end),
+				_ => {}
+			}
+			VotingFor::<T>::remove(&who, index);
+		}
+
+		/// Redetermine the balance of an account which is locked due to voting.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `target`: The account to remove the lock on.
+		///
+		/// Weight: `O(R)` with R number of votes of target.
+		#[weight = T::WeightInfo::unlock(T::MaxVotes::get())]
+		fn unlock(origin, target: T::AccountId) {
+			ensure_signed(origin)?;
+			Self::update_lock(&target);
+		}
+
+		/// Cancel an ongoing referendum, returning its deposits.
+		///
+		/// The dispatch origin of this call must be `CancelOrigin`.
+		///
+		/// - `index`: The index of the referendum to cancel.
+		///
+		/// Emits `Cancelled`.
+		///
+		/// Weight: `O(1)`.
+		#[weight = T::WeightInfo::cancel()]
+		fn cancel(origin, #[compact] index: ReferendumIndex) {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let status = Self::ensure_ongoing(index)?;
+			if status.deciding.is_some() {
+				Deciding::mutate(status.track, |d| d.retain(|i| *i != index));
+			}
+			if status.queued {
+				TrackQueue::mutate(status.track, |q| q.retain(|i| *i != index));
+			}
+			Self::refund_deposits(&status);
+			let now = system::Module::<T>::block_number();
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Cancelled(now));
+			Self::deposit_event(RawEvent::Cancelled(index));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Whether the `tally` of a referendum passes the curves of `track` once fraction `x` of its
+	/// decision period has elapsed.
+	pub fn is_passing(tally: &Tally<BalanceOf<T>>, x: Perbill, track: &TrackInfoOf<T>) -> bool {
+		let ayes = tally.ayes();
+		let votes = ayes.saturating_add(tally.nays());
+		let approval = if votes.is_zero() {
+			Perbill::zero()
+		} else {
+			Perbill::from_rational_approximation(ayes, votes)
+		};
+		let support = Perbill::from_rational_approximation(
+			tally.turnout(),
+			T::Currency::total_issuance().max(1u32.into()),
+		);
+		approval >= track.min_approval.threshold(x) && support >= track.min_support.threshold(x)
+	}
+
+	fn track_info(track: TrackId) -> Result<TrackInfoOf<T>, DispatchError> {
+		T::Tracks::info(track).ok_or_else(|| Error::<T>::NoTrack.into())
+	}
+
+	fn ensure_ongoing(index: ReferendumIndex) -> Result<ReferendumStatusOf<T>, DispatchError> {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) => Ok(status),
+			_ => Err(Error::<T>::NotOngoing.into()),
+		}
+	}
+
+	fn set_alarm(when: T::BlockNumber, index: ReferendumIndex) {
+		Alarms::<T>::append(when, index);
+	}
+
+	fn refund_deposits(status: &ReferendumStatusOf<T>) {
+		let Deposit { who, amount } = &status.submission_deposit;
+		T::Currency::unreserve(who, *amount);
+		if let Some(Deposit { who, amount }) = &status.decision_deposit {
+			T::Currency::unreserve(who, *amount);
+		}
+	}
+
+	/// Begin deciding upon the referendum `index` if there is a free decision slot on its track,
+	/// otherwise put it in the track's queue.
+	fn ready_for_decision(
+		index: ReferendumIndex,
+		status: &mut ReferendumStatusOf<T>,
+		now: T::BlockNumber,
+		track: &TrackInfoOf<T>,
+	) {
+		let has_slot = Deciding::try_mutate(status.track, |deciding| {
+			if (deciding.len() as u32) < track.max_deciding {
+				deciding.push(index);
+				Ok(())
+			} else {
+				Err(())
+			}
+		}).is_ok();
+		if has_slot {
+			Self::begin_deciding(index, status, now);
+		} else {
+			TrackQueue::append(status.track, index);
+			status.queued = true;
+		}
+	}
+
+	fn begin_deciding(index: ReferendumIndex, status: &mut ReferendumStatusOf<T>, now: T::BlockNumber) {
+		status.queued = false;
+		status.deciding = Some(DecidingStatus { since: now, confirming: None });
+		Self::deposit_event(RawEvent::DecisionStarted(index, status.track));
+	}
+
+	fn lock_prior(who: &T::AccountId, vote: AccountVote<BalanceOf<T>>, approved: bool, end: T::BlockNumber) {
+		if let Some((lock_periods, balance)) = vote.locked_if(approved) {
+			let unlock_at = end.saturating_add(
+				T::VoteLockingPeriod::get().saturating_mul(lock_periods.into())
+			);
+			if system::Module::<T>::block_number() < unlock_at {
+				PriorLocks::<T>::mutate(who, |prior| prior.accumulate(unlock_at, balance));
+			}
+		}
+	}
+
+	fn update_lock(who: &T::AccountId) {
+		let mut prior = PriorLocks::<T>::get(who);
+		prior.rejig(system::Module::<T>::block_number());
+		let locked = VotingFor::<T>::iter_prefix_values(who)
+			.map(|vote| vote.balance())
+			.fold(prior.locked(), |a, b| a.max(b));
+		if prior.locked().is_zero() {
+			PriorLocks::<T>::remove(who);
+		} else {
+			PriorLocks::<T>::insert(who, prior);
+		}
+		if locked.is_zero() {
+			T::Currency::remove_lock(REFERENDA_ID, who);
+		} else {
+			T::Currency::set_lock(REFERENDA_ID, who, locked, WithdrawReason::Transfer.into());
+		}
+	}
+
+	/// Look at a referendum whose alarm went off: it may have become ready to be decided upon, or
+	/// timed out.
+	fn service_alarm(index: ReferendumIndex, now: T::BlockNumber) {
+		let mut status = match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) => status,
+			_ => return,
+		};
+		if status.deciding.is_some() || status.queued {
+			return
+		}
+		let track = match T::Tracks::info(status.track) {
+			Some(track) => track,
+			None => return,
+		};
+		if status.decision_deposit.is_some() {
+			if now >= status.submitted.saturating_add(track.prepare_period) {
+				Self::ready_for_decision(index, &mut status, now, &track);
+				ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+			}
+		} else if now >= status.submitted.saturating_add(T::UndecidingTimeout::get()) {
+			Self::refund_deposits(&status);
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::TimedOut(now));
+			Self::deposit_event(RawEvent::TimedOut(index));
+		}
+	}
+
+	/// Advance the decision of a referendum which is being decided upon.
+	///
+	/// Returns `true` if the referendum is no longer being decided upon.
+	fn service_deciding(index: ReferendumIndex, now: T::BlockNumber, track: &TrackInfoOf<T>) -> bool {
+		let mut status = match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) => status,
+			_ => return true,
+		};
+		let mut deciding = match status.deciding.clone() {
+			Some(deciding) => deciding,
+			None => return true,
+		};
+
+		let elapsed = now.saturating_sub(deciding.since);
+		let x = if track.decision_period.is_zero() {
+			Perbill::one()
+		} else {
+			Perbill::from_rational_approximation(elapsed.min(track.decision_period), track.decision_period)
+		};
+		let mut approved = false;
+		if Self::is_passing(&status.tally, x, track) {
+			match deciding.confirming {
+				None if track.confirm_period.is_zero() => approved = true,
+				None => {
+					deciding.confirming = Some(now.saturating_add(track.confirm_period));
+					Self::deposit_event(RawEvent::ConfirmStarted(index));
+				}
+				Some(end) => approved = now >= end,
+			}
+		} else if deciding.confirming.take().is_some() {
+			Self::deposit_event(RawEvent::ConfirmAborted(index));
+		}
+
+		if approved {
+			Self::approve(index, status, now);
+			return true
+		}
+		if deciding.confirming.is_none() && elapsed >= track.decision_period {
+			Self::refund_deposits(&status);
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Rejected(now));
+			Self::deposit_event(RawEvent::Rejected(index));
+			return true
+		}
+		status.deciding = Some(deciding);
+		ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+		false
+	}
+
+	fn approve(index: ReferendumIndex, status: ReferendumStatusOf<T>, now: T::BlockNumber) {
+		if T::Scheduler::schedule_named(
+			(REFERENDA_ID, index).encode(),
+			DispatchTime::After(status.enactment),
+			None,
+			63,
+			status.origin.clone(),
+			status.proposal.clone(),
+		).is_err() {
+			frame_support::print("LOGIC ERROR: approve/schedule_named failed");
+		}
+		Self::refund_deposits(&status);
+		ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Approved(now));
+		Self::deposit_event(RawEvent::Approved(index));
+	}
+
+	/// Service the alarms of this block and the referenda being decided upon on every track, then
+	/// fill any free decision slots from the tracks' queues.
+	fn begin_block(now: T::BlockNumber) -> Weight {
+		let alarms = Alarms::<T>::take(now);
+		for index in alarms.iter() {
+			Self::service_alarm(*index, now);
+		}
+
+		let mut serviced = 0u32;
+		for (id, track) in T::Tracks::tracks() {
+			let mut deciding = Deciding::get(id);
+			if deciding.is_empty() && !TrackQueue::contains_key(id) {
+				continue
+			}
+			serviced = serviced.saturating_add(deciding.len() as u32);
+			deciding.retain(|index| !Self::service_deciding(*index, now, &track));
+
+			let mut queue = TrackQueue::get(id);
+			while (deciding.len() as u32) < track.max_deciding && !queue.is_empty() {
+				let index = queue.remove(0);
+				if let Some(ReferendumInfo::Ongoing(mut status)) = ReferendumInfoFor::<T>::get(index) {
+					Self::begin_deciding(index, &mut status, now);
+					ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+					deciding.push(index);
+				}
+			}
+			if queue.is_empty() {
+				TrackQueue::remove(id);
+			} else {
+				TrackQueue::insert(id, queue);
+			}
+			if deciding.is_empty() {
+				Deciding::remove(id);
+			} else {
+				Deciding::insert(id, deciding);
+			}
+		}
+
+		T::WeightInfo::on_initialize(alarms.len() as u32, serviced)
+	}
+}
@@ -0,0 +1,533 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use super::*;
+use frame_support::{
+	impl_outer_origin, impl_outer_dispatch, impl_outer_event, assert_noop, assert_ok,
+	parameter_types, ord_parameter_types, traits::{OnInitialize, Filter},
+	weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+	testing::Header, Perbill,
+};
+use pallet_balances::{BalanceLock, Error as BalancesError};
+use pallet_democracy::{Vote, Conviction};
+use frame_system::{EnsureSignedBy, EnsureRoot, RawOrigin};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		pallet_balances::Balances,
+		referenda::Referenda,
+	}
+}
+
+mod referenda {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		system<T>,
+		pallet_balances<T>,
+		pallet_scheduler<T>,
+		referenda<T>,
+	}
+}
+
+pub struct BaseFilter;
+impl Filter<Call> for BaseFilter {
+	fn filter(_call: &Call) -> bool {
+		true
+	}
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = BaseFilter;
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+}
+impl pallet_scheduler::Trait for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type MaxScheduledPerBlock = ();
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Proposals dispatched from root are decided upon on track 0, those dispatched from a signed
+/// origin on track 1.
+pub struct TestTracks;
+impl TracksInfo<u64, u64> for TestTracks {
+	type Origin = OriginCaller;
+	fn tracks() -> Vec<(TrackId, TrackInfo<u64, u64>)> {
+		vec![
+			(0, TrackInfo {
+				name: "root",
+				max_deciding: 1,
+				decision_deposit: 10,
+				prepare_period: 4,
+				decision_period: 4,
+				confirm_period: 2,
+				min_enactment_period: 4,
+				min_approval: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(80),
+					delta: Perbill::from_percent(30),
+				},
+				min_support: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(20),
+					delta: Perbill::from_percent(10),
+				},
+			}),
+			(1, TrackInfo {
+				name: "signed",
+				max_deciding: 2,
+				decision_deposit: 1,
+				prepare_period: 0,
+				decision_period: 10,
+				confirm_period: 0,
+				min_enactment_period: 1,
+				min_approval: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(50),
+					delta: Perbill::zero(),
+				},
+				min_support: Curve::LinearDecreasing {
+					begin: Perbill::zero(),
+					delta: Perbill::zero(),
+				},
+			}),
+		]
+	}
+	fn track_for(origin: &OriginCaller) -> Result<TrackId, ()> {
+		match origin {
+			OriginCaller::system(RawOrigin::Root) => Ok(0),
+			OriginCaller::system(RawOrigin::Signed(_)) => Ok(1),
+			_ => Err(()),
+		}
+	}
+}
+parameter_types! {
+	pub const SubmissionDeposit: u64 = 2;
+	pub const UndecidingTimeout: u64 = 20;
+	pub const VoteLockingPeriod: u64 = 3;
+	pub const MaxVotes: u32 = 10;
+}
+ord_parameter_types! {
+	pub const Four: u64 = 4;
+}
+impl super::Trait for Test {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = pallet_balances::Module<Self>;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type Tracks = TestTracks;
+	type CancelOrigin = EnsureSignedBy<Four, u64>;
+	type SubmissionDeposit = SubmissionDeposit;
+	type UndecidingTimeout = UndecidingTimeout;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Scheduler = pallet_scheduler::Module<Test>;
+type Referenda = Module<Test>;
+
+const AYE: Vote = Vote { aye: true, conviction: Conviction::Locked1x };
+const NAY: Vote = Vote { aye: false, conviction: Conviction::Locked1x };
+
+fn aye(balance: u64) -> AccountVote<u64> {
+	AccountVote::Standard { vote: AYE, balance }
+}
+
+fn nay(balance: u64) -> AccountVote<u64> {
+	AccountVote::Standard { vote: NAY, balance }
+}
+
+fn set_balance_proposal(value: u64) -> Box<Call> {
+	Box::new(Call::Balances(pallet_balances::Call::set_balance(42, value, 0)))
+}
+
+fn submit_root(who: u64, value: u64) -> ReferendumIndex {
+	assert_ok!(Referenda::submit(
+		Origin::signed(who),
+		Box::new(RawOrigin::Root.into()),
+		set_balance_proposal(value),
+		0,
+	));
+	Referenda::referendum_count() - 1
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let b = System::block_number() + 1;
+		System::set_block_number(b);
+		Scheduler::on_initialize(b);
+		Referenda::on_initialize(b);
+	}
+}
+
+fn referenda_events() -> Vec<RawEvent<u64, u64>> {
+	System::events().into_iter().filter_map(|r| match r.event {
+		Event::referenda(e) => Some(e),
+		_ => None,
+	}).collect()
+}
+
+fn status(index: ReferendumIndex) -> ReferendumStatusOf<Test> {
+	match Referenda::referendum_info(index) {
+		Some(ReferendumInfo::Ongoing(status)) => status,
+		_ => panic!("referendum not ongoing"),
+	}
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Referenda::referendum_count(), 0);
+		assert_eq!(Balances::free_balance(42), 0);
+		assert_eq!(Balances::total_issuance(), 210);
+	});
+}
+
+#[test]
+fn curves_should_decrease_over_decision_period() {
+	new_test_ext().execute_with(|| {
+		let track = TestTracks::info(0).unwrap();
+		let tally = |ayes: u64, nays: u64| {
+			let mut tally = Tally::default();
+			tally.add(aye(ayes)).unwrap();
+			tally.add(nay(nays)).unwrap();
+			tally
+		};
+		// 60% approval with 50% support passes only once half the decision period has elapsed.
+		assert!(!Referenda::is_passing(&tally(60, 40), Perbill::zero(), &track));
+		assert!(Referenda::is_passing(&tally(60, 40), Perbill::from_percent(80), &track));
+		// Full approval needs 20% support at first, and 10% at the end.
+		assert!(!Referenda::is_passing(&tally(30, 0), Perbill::zero(), &track));
+		assert!(Referenda::is_passing(&tally(30, 0), Perbill::one(), &track));
+	});
+}
+
+#[test]
+fn submit_should_work() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_eq!(index, 0);
+		assert_eq!(Balances::reserved_balance(1), 2);
+		let status = status(0);
+		assert_eq!(status.track, 0);
+		assert_eq!(status.enactment, 4);
+		assert_eq!(status.submitted, 1);
+		assert!(status.deciding.is_none());
+		assert_eq!(referenda_events(), vec![RawEvent::Submitted(0, 0, 1)]);
+
+		assert_noop!(
+			Referenda::submit(Origin::signed(1), Box::new(RawOrigin::None.into()), set_balance_proposal(2), 0),
+			Error::<Test>::NoTrack,
+		);
+		assert_noop!(
+			Referenda::submit(Origin::signed(42), Box::new(RawOrigin::Root.into()), set_balance_proposal(2), 0),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn approval_should_enact_proposal() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), index));
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_ok!(Referenda::vote(Origin::signed(6), index, aye(60)));
+
+		// Decision starts at the end of the preparation period, and as the referendum is passing
+		// it starts confirming straight away.
+		run_to_block(4);
+		assert!(status(index).deciding.is_none());
+		run_to_block(5);
+		assert_eq!(status(index).deciding, Some(DecidingStatus { since: 5, confirming: Some(7) }));
+		run_to_block(7);
+		assert_eq!(Referenda::referendum_info(index), Some(ReferendumInfo::Approved(7)));
+		assert_eq!(referenda_events(), vec![
+			RawEvent::Submitted(0, 0, 1),
+			RawEvent::DecisionDepositPlaced(0, 2, 10),
+			RawEvent::DecisionStarted(0, 0),
+			RawEvent::ConfirmStarted(0),
+			RawEvent::Approved(0),
+		]);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(42), 0);
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(42), 2);
+	});
+}
+
+#[test]
+fn confirmation_should_abort_when_not_passing() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), index));
+		assert_ok!(Referenda::vote(Origin::signed(6), index, aye(60)));
+		run_to_block(5);
+		assert_eq!(status(index).deciding.unwrap().confirming, Some(7));
+
+		assert_ok!(Referenda::vote(Origin::signed(5), index, nay(50)));
+		assert_ok!(Referenda::vote(Origin::signed(4), index, nay(40)));
+		run_to_block(6);
+		assert_eq!(status(index).deciding.unwrap().confirming, None);
+		assert_eq!(referenda_events().last(), Some(&RawEvent::ConfirmAborted(0)));
+
+		// Not passing at the end of the decision period.
+		run_to_block(9);
+		assert_eq!(Referenda::referendum_info(index), Some(ReferendumInfo::Rejected(9)));
+		assert_eq!(referenda_events().last(), Some(&RawEvent::Rejected(0)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		run_to_block(20);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn referendum_should_time_out_without_decision_deposit() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		run_to_block(20);
+		assert!(status(index).deciding.is_none());
+		run_to_block(21);
+		assert_eq!(Referenda::referendum_info(index), Some(ReferendumInfo::TimedOut(21)));
+		assert_eq!(referenda_events().last(), Some(&RawEvent::TimedOut(0)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(2), index), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn decision_deposit_should_only_be_placed_once() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), index));
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(3), index), Error::<Test>::HasDeposit);
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(2), 1), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn referenda_should_queue_for_decision_slots() {
+	new_test_ext().execute_with(|| {
+		let first = submit_root(1, 2);
+		let second = submit_root(1, 3);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), first));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(3), second));
+
+		run_to_block(5);
+		assert_eq!(Referenda::deciding(0), vec![first]);
+		assert_eq!(Referenda::track_queue(0), vec![second]);
+		assert!(status(second).queued);
+
+		// The first referendum is rejected at the end of its decision period, freeing its slot.
+		run_to_block(9);
+		assert_eq!(Referenda::referendum_info(first), Some(ReferendumInfo::Rejected(9)));
+		assert_eq!(Referenda::deciding(0), vec![second]);
+		assert!(Referenda::track_queue(0).is_empty());
+		let status = status(second);
+		assert!(!status.queued);
+		assert_eq!(status.deciding, Some(DecidingStatus { since: 9, confirming: None }));
+	});
+}
+
+#[test]
+fn tracks_should_decide_independently() {
+	new_test_ext().execute_with(|| {
+		let root = submit_root(1, 2);
+		assert_ok!(Referenda::submit(
+			Origin::signed(1),
+			Box::new(RawOrigin::Signed(1).into()),
+			Box::new(Call::System(frame_system::Call::remark(vec![]))),
+			0,
+		));
+		let signed = root + 1;
+		assert_eq!(status(signed).track, 1);
+
+		// There is no preparation period on track 1.
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), signed));
+		assert_eq!(Referenda::deciding(1), vec![signed]);
+		assert_ok!(Referenda::vote(Origin::signed(3), signed, aye(10)));
+		run_to_block(2);
+		assert_eq!(Referenda::referendum_info(signed), Some(ReferendumInfo::Approved(2)));
+		assert!(Referenda::deciding(1).is_empty());
+		assert_eq!(status(root).track, 0);
+	});
+}
+
+#[test]
+fn cancel_should_work() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), index));
+		run_to_block(5);
+		assert_eq!(Referenda::deciding(0), vec![index]);
+
+		assert_noop!(Referenda::cancel(Origin::signed(1), index), BadOrigin);
+		assert_ok!(Referenda::cancel(Origin::signed(4), index));
+		assert_eq!(Referenda::referendum_info(index), Some(ReferendumInfo::Cancelled(5)));
+		assert!(Referenda::deciding(0).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_noop!(Referenda::cancel(Origin::signed(4), index), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn vote_should_update_tally() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_ok!(Referenda::vote(Origin::signed(5), index, aye(20)));
+		assert_eq!(status(index).tally.ayes(), 20);
+		assert_ok!(Referenda::vote(Origin::signed(5), index, nay(30)));
+		assert_eq!(status(index).tally.ayes(), 0);
+		assert_eq!(status(index).tally.nays(), 30);
+		assert_eq!(status(index).tally.turnout(), 30);
+		assert_noop!(Referenda::vote(Origin::signed(1), index, aye(11)), Error::<Test>::InsufficientFunds);
+
+		assert_ok!(Referenda::remove_vote(Origin::signed(5), index));
+		assert_eq!(status(index).tally, Tally::default());
+		assert_noop!(Referenda::remove_vote(Origin::signed(5), index), Error::<Test>::NotVoter);
+	});
+}
+
+#[test]
+fn vote_should_respect_max_votes() {
+	new_test_ext().execute_with(|| {
+		for i in 0..MaxVotes::get() {
+			let index = submit_root(6, i as u64);
+			assert_ok!(Referenda::vote(Origin::signed(5), index, aye(1)));
+		}
+		let index = submit_root(6, 10);
+		assert_noop!(Referenda::vote(Origin::signed(5), index, aye(1)), Error::<Test>::MaxVotesReached);
+		// Changing an existing vote is still fine.
+		assert_ok!(Referenda::vote(Origin::signed(5), 0, aye(2)));
+	});
+}
+
+#[test]
+fn winning_votes_should_stay_locked() {
+	new_test_ext().execute_with(|| {
+		let index = submit_root(1, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), index));
+		assert_ok!(Referenda::vote(Origin::signed(6), index, aye(60)));
+		assert_ok!(Referenda::vote(Origin::signed(5), index, nay(10)));
+		assert_eq!(Balances::locks(6), vec![BalanceLock {
+			id: REFERENDA_ID,
+			amount: 60,
+			reasons: pallet_balances::Reasons::Misc,
+		}]);
+		run_to_block(7);
+		assert_eq!(Referenda::referendum_info(index), Some(ReferendumInfo::Approved(7)));
+
+		// The losing vote may be unlocked straight away.
+		assert_ok!(Referenda::remove_vote(Origin::signed(5), index));
+		assert_ok!(Referenda::unlock(Origin::signed(1), 5));
+		assert!(Balances::locks(5).is_empty());
+
+		// The winning vote stays locked for one `VoteLockingPeriod` after approval.
+		assert_ok!(Referenda::remove_vote(Origin::signed(6), index));
+		assert_ok!(Referenda::unlock(Origin::signed(1), 6));
+		assert_eq!(Balances::locks(6)[0].amount, 60);
+		assert_eq!(Referenda::prior_locks(6).locked(), 60);
+		run_to_block(10);
+		assert_ok!(Referenda::unlock(Origin::signed(1), 6));
+		assert!(Balances::locks(6).is_empty());
+		assert!(!PriorLocks::<Test>::contains_key(6));
+	});
+}
@@ -0,0 +1,165 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, Perbill};
+use pallet_democracy::Tally;
+
+/// The identifier of a track.
+pub type TrackId = u8;
+
+/// A curve of a threshold, over the fraction of the decision period which has elapsed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Curve {
+	/// Starts at `begin` and decreases linearly by `delta` over the decision period.
+	LinearDecreasing { begin: Perbill, delta: Perbill },
+}
+
+impl Curve {
+	/// The threshold once fraction `x` of the decision period has elapsed.
+	pub fn threshold(&self, x: Perbill) -> Perbill {
+		match self {
+			Curve::LinearDecreasing { begin, delta } => Perbill::from_parts(
+				begin.deconstruct().saturating_sub(x.mul_floor(delta.deconstruct()))
+			),
+		}
+	}
+}
+
+/// The parameters of a track.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TrackInfo<Balance, BlockNumber> {
+	/// A human-readable name of the track.
+	pub name: &'static str,
+	/// The maximum number of referenda which may be decided upon at once on this track.
+	pub max_deciding: u32,
+	/// The amount which must be placed on deposit before a referendum may be decided upon.
+	pub decision_deposit: Balance,
+	/// The minimum number of blocks between the submission of a referendum and the start of its
+	/// decision period.
+	pub prepare_period: BlockNumber,
+	/// The number of blocks within which a referendum must be approved, or it is rejected.
+	pub decision_period: BlockNumber,
+	/// The number of blocks for which a referendum must keep passing before it is approved.
+	pub confirm_period: BlockNumber,
+	/// The minimum number of blocks between the approval of a referendum and its enactment.
+	pub min_enactment_period: BlockNumber,
+	/// The minimum approval, i.e. the fraction of ayes out of all post-conviction votes.
+	pub min_approval: Curve,
+	/// The minimum support, i.e. the fraction of the total issuance which has voted.
+	pub min_support: Curve,
+}
+
+/// The set of tracks and the means of assigning a referendum to one by its origin.
+pub trait TracksInfo<Balance, BlockNumber> {
+	/// The origin of the proposals, by which their track is chosen.
+	type Origin;
+
+	/// All tracks, in any order.
+	fn tracks() -> Vec<(TrackId, TrackInfo<Balance, BlockNumber>)>;
+
+	/// The track on which a proposal to be dispatched from `origin` is decided, if any.
+	fn track_for(origin: &Self::Origin) -> Result<TrackId, ()>;
+
+	/// The parameters of track `id`.
+	fn info(id: TrackId) -> Option<TrackInfo<Balance, BlockNumber>> {
+		Self::tracks().into_iter().find(|x| x.0 == id).map(|x| x.1)
+	}
+}
+
+/// An amount held on deposit.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Deposit<AccountId, Balance> {
+	/// The account which placed the deposit.
+	pub who: AccountId,
+	/// The amount placed on deposit.
+	pub amount: Balance,
+}
+
+/// The status of a referendum which is being decided upon.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DecidingStatus<BlockNumber> {
+	/// The block at which the decision period began.
+	pub since: BlockNumber,
+	/// The block at which the referendum is approved, if it keeps passing until then.
+	pub confirming: Option<BlockNumber>,
+}
+
+/// Info regarding an ongoing referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReferendumStatus<Origin, Call, BlockNumber, Balance, AccountId> {
+	/// The track on which the referendum is decided.
+	pub track: TrackId,
+	/// The origin from which the proposal is dispatched if approved.
+	pub origin: Origin,
+	/// The proposal being voted on.
+	pub proposal: Call,
+	/// The delay (in blocks) to wait after approval before enacting the proposal.
+	pub enactment: BlockNumber,
+	/// The block at which the referendum was submitted.
+	pub submitted: BlockNumber,
+	/// The deposit placed by the submitter.
+	pub submission_deposit: Deposit<AccountId, Balance>,
+	/// The deposit needed for the referendum to be decided upon, if placed.
+	pub decision_deposit: Option<Deposit<AccountId, Balance>>,
+	/// The status of the decision, if the referendum is being decided upon.
+	pub deciding: Option<DecidingStatus<BlockNumber>>,
+	/// Whether the referendum is waiting in its track's queue for a decision slot.
+	pub queued: bool,
+	/// The current tally of votes in this referendum.
+	pub tally: Tally<Balance>,
+}
+
+/// Info regarding a referendum, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ReferendumInfo<Origin, Call, BlockNumber, Balance, AccountId> {
+	/// Referendum has been submitted and has not yet concluded.
+	Ongoing(ReferendumStatus<Origin, Call, BlockNumber, Balance, AccountId>),
+	/// Referendum was approved at the given block.
+	Approved(BlockNumber),
+	/// Referendum was rejected at the given block.
+	Rejected(BlockNumber),
+	/// Referendum was cancelled at the given block.
+	Cancelled(BlockNumber),
+	/// Referendum was not given a decision deposit in time and timed out at the given block.
+	TimedOut(BlockNumber),
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn linear_decreasing_curve_works() {
+		let c = Curve::LinearDecreasing {
+			begin: Perbill::from_percent(100),
+			delta: Perbill::from_percent(50),
+		};
+		assert_eq!(c.threshold(Perbill::zero()), Perbill::from_percent(100));
+		assert_eq!(c.threshold(Perbill::from_percent(50)), Perbill::from_percent(75));
+		assert_eq!(c.threshold(Perbill::one()), Perbill::from_percent(50));
+
+		let c = Curve::LinearDecreasing {
+			begin: Perbill::from_percent(10),
+			delta: Perbill::from_percent(50),
+		};
+		assert_eq!(c.threshold(Perbill::one()), Perbill::zero());
+	}
+}
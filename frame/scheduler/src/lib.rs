@@ -56,20 +56,33 @@ mod default_weights;
 
 use sp_std::{prelude::*, marker::PhantomData, borrow::Borrow};
 use codec::{Encode, Decode, Codec};
+use frame_support::type_info::TypeInfo;
 use sp_runtime::{RuntimeDebug, traits::{Zero, One, BadOrigin, Saturating}};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, IterableStorageMap,
 	dispatch::{Dispatchable, DispatchError, DispatchResult, Parameter},
 	traits::{Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType},
-	weights::{GetDispatchInfo, Weight},
+	weights::{GetDispatchInfo, Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
 };
 use frame_system::{self as system};
 
 pub trait WeightInfo {
 	fn schedule(s: u32, ) -> Weight;
+	fn schedule_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel(s: u32, ) -> Weight;
+	fn cancel_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn schedule_named(s: u32, ) -> Weight;
+	fn schedule_named_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel_named(s: u32, ) -> Weight;
+	fn cancel_named_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 /// Our pallet's configuration trait. All our types and constants go in here. If the
@@ -112,7 +125,7 @@ pub type PeriodicIndex = u32;
 pub type TaskAddress<BlockNumber> = (BlockNumber, u32);
 
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
-#[derive(Clone, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
 struct ScheduledV1<Call, BlockNumber> {
 	maybe_id: Option<Vec<u8>>,
 	priority: schedule::Priority,
@@ -122,7 +135,7 @@ struct ScheduledV1<Call, BlockNumber> {
 
 /// Information regarding an item to be executed in the future.
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
-#[derive(Clone, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	/// The unique identity for this task, if there is one.
 	maybe_id: Option<Vec<u8>>,
@@ -143,7 +156,7 @@ pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> = ScheduledV2<Ca
 // A value placed in storage that represents the current version of the Scheduler storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
 // storage migration logic.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	V1,
 	V2,
@@ -209,7 +222,10 @@ decl_module! {
 		///     - Write: Agenda
 		/// - Will use base weight of 25 which should be good for up to 30 scheduled calls
 		/// # </weight>
-		#[weight = T::WeightInfo::schedule(T::MaxScheduledPerBlock::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::schedule(T::MaxScheduledPerBlock::get()),
+			T::WeightInfo::schedule_proof_size(T::MaxScheduledPerBlock::get()),
+		)]
 		fn schedule(origin,
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
@@ -231,7 +247,10 @@ decl_module! {
 		///     - Write: Agenda, Lookup
 		/// - Will use base weight of 100 which should be good for up to 30 scheduled calls
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel(T::MaxScheduledPerBlock::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel(T::MaxScheduledPerBlock::get()),
+			T::WeightInfo::cancel_proof_size(T::MaxScheduledPerBlock::get()),
+		)]
 		fn cancel(origin, when: T::BlockNumber, index: u32) {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Trait>::Origin::from(origin);
@@ -248,7 +267,10 @@ decl_module! {
 		///     - Write: Agenda, Lookup
 		/// - Will use base weight of 35 which should be good for more than 30 scheduled calls
 		/// # </weight>
-		#[weight = T::WeightInfo::schedule_named(T::MaxScheduledPerBlock::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::schedule_named(T::MaxScheduledPerBlock::get()),
+			T::WeightInfo::schedule_named_proof_size(T::MaxScheduledPerBlock::get()),
+		)]
 		fn schedule_named(origin,
 			id: Vec<u8>,
 			when: T::BlockNumber,
@@ -273,7 +295,10 @@ decl_module! {
 		///     - Write: Agenda, Lookup
 		/// - Will use base weight of 100 which should be good for up to 30 scheduled calls
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel_named(T::MaxScheduledPerBlock::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel_named(T::MaxScheduledPerBlock::get()),
+			T::WeightInfo::cancel_named_proof_size(T::MaxScheduledPerBlock::get()),
+		)]
 		fn cancel_named(origin, id: Vec<u8>) {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Trait>::Origin::from(origin);
@@ -285,7 +310,10 @@ decl_module! {
 		/// # <weight>
 		/// Same as [`schedule`].
 		/// # </weight>
-		#[weight = T::WeightInfo::schedule(T::MaxScheduledPerBlock::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::schedule(T::MaxScheduledPerBlock::get()),
+			T::WeightInfo::schedule_proof_size(T::MaxScheduledPerBlock::get()),
+		)]
 		fn schedule_after(origin,
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
@@ -304,7 +332,10 @@ decl_module! {
 		/// # <weight>
 		/// Same as [`schedule_named`].
 		/// # </weight>
-		#[weight = T::WeightInfo::schedule_named(T::MaxScheduledPerBlock::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::schedule_named(T::MaxScheduledPerBlock::get()),
+			T::WeightInfo::schedule_named_proof_size(T::MaxScheduledPerBlock::get()),
+		)]
 		fn schedule_named_after(origin,
 			id: Vec<u8>,
 			after: T::BlockNumber,
@@ -752,6 +783,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type MaximumBlockLength = MaximumBlockLength;
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
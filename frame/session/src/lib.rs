@@ -111,7 +111,7 @@ use frame_support::{
 		Get, FindAuthor, ValidatorRegistration, EstimateNextSessionRotation, EstimateNextNewSession,
 	},
 	dispatch::{self, DispatchResult, DispatchError},
-	weights::Weight,
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
 };
 use frame_system::ensure_signed;
 
@@ -355,7 +355,13 @@ impl<T: Trait> ValidatorRegistration<T::ValidatorId> for Module<T> {
 
 pub trait WeightInfo {
 	fn set_keys() -> Weight;
+	fn set_keys_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn purge_keys() -> Weight;
+	fn purge_keys_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: frame_system::Trait {
@@ -521,7 +527,7 @@ decl_module! {
 		/// - DbReads per key id: `KeyOwner`
 		/// - DbWrites per key id: `KeyOwner`
 		/// # </weight>
-		#[weight = T::WeightInfo::set_keys()]
+		#[weight = WithProofSize(T::WeightInfo::set_keys(), T::WeightInfo::set_keys_proof_size())]
 		pub fn set_keys(origin, keys: T::Keys, proof: Vec<u8>) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -544,7 +550,10 @@ decl_module! {
 		/// - DbWrites: `NextKeys`, `origin account`
 		/// - DbWrites per key id: `KeyOwnder`
 		/// # </weight>
-		#[weight = T::WeightInfo::purge_keys()]
+		#[weight = WithProofSize(
+			T::WeightInfo::purge_keys(),
+			T::WeightInfo::purge_keys_proof_size(),
+		)]
 		pub fn purge_keys(origin) {
 			let who = ensure_signed(origin)?;
 			Self::do_purge_keys(&who)?;
@@ -285,14 +285,19 @@ use sp_std::{
 	result,
 	prelude::*,
 	collections::btree_map::BTreeMap,
-	convert::{TryInto, From},
+	convert::From,
 	mem::size_of,
 };
 use codec::{HasCompact, Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use frame_support::{
 	decl_module, decl_event, decl_storage, ensure, decl_error,
-	weights::{Weight, constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS}},
+	weights::{
+		Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize,
+		constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS},
+	},
 	storage::IterableStorageMap,
+	unsigned::{RateLimit, UnsignedValidity},
 	dispatch::{
 		IsSubType, DispatchResult, DispatchResultWithPostInfo, DispatchErrorWithPostInfo,
 		WithPostDispatchInfo,
@@ -311,7 +316,7 @@ use sp_runtime::{
 		AtLeast32BitUnsigned, Dispatchable,
 	},
 	transaction_validity::{
-		TransactionValidityError, TransactionValidity, ValidTransaction, InvalidTransaction,
+		TransactionValidityError, TransactionValidity, InvalidTransaction,
 		TransactionSource, TransactionPriority,
 	},
 };
@@ -392,7 +397,7 @@ type NegativeImbalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
 /// Information regarding the active era (era in used in session).
-#[derive(Encode, Decode, RuntimeDebug)]
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct ActiveEraInfo {
 	/// Index of era.
 	pub index: EraIndex,
@@ -406,7 +411,7 @@ pub struct ActiveEraInfo {
 /// Reward points of an era. Used to split era total payout between validators.
 ///
 /// This points will be used to reward validators and their respective nominators.
-#[derive(PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[derive(PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct EraRewardPoints<AccountId: Ord> {
 	/// Total number of points. Equals the sum of reward points for each validator.
 	total: RewardPoint,
@@ -427,7 +432,7 @@ pub enum StakerStatus<AccountId> {
 }
 
 /// A destination account for payment.
-#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum RewardDestination<AccountId> {
 	/// Pay into the stash account, increasing the amount at stake accordingly.
 	Staked,
@@ -446,7 +451,7 @@ impl<AccountId> Default for RewardDestination<AccountId> {
 }
 
 /// Preference of what happens regarding validation.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct ValidatorPrefs {
 	/// Reward that validator takes up-front; only the rest is split between themselves and
 	/// nominators.
@@ -463,7 +468,7 @@ impl Default for ValidatorPrefs {
 }
 
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct UnlockChunk<Balance: HasCompact> {
 	/// Amount of funds to be unlocked.
 	#[codec(compact)]
@@ -474,7 +479,7 @@ pub struct UnlockChunk<Balance: HasCompact> {
 }
 
 /// The ledger of a (bonded) stash.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct StakingLedger<AccountId, Balance: HasCompact> {
 	/// The stash account whose balance is actually locked and at stake.
 	pub stash: AccountId,
@@ -603,7 +608,7 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 }
 
 /// A record of the nominations made by a specific account.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct Nominations<AccountId> {
 	/// The targets of nomination.
 	pub targets: Vec<AccountId>,
@@ -619,7 +624,7 @@ pub struct Nominations<AccountId> {
 }
 
 /// The amount of exposure (to slashing) than an individual nominator has.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct IndividualExposure<AccountId, Balance: HasCompact> {
 	/// The stash account of the nominator in question.
 	pub who: AccountId,
@@ -629,7 +634,7 @@ pub struct IndividualExposure<AccountId, Balance: HasCompact> {
 }
 
 /// A snapshot of the stake backing a single validator in the system.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct Exposure<AccountId, Balance: HasCompact> {
 	/// The total balance backing this validator.
 	#[codec(compact)]
@@ -643,7 +648,7 @@ pub struct Exposure<AccountId, Balance: HasCompact> {
 
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
-#[derive(Encode, Decode, Default, RuntimeDebug)]
+#[derive(Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 	/// The stash ID of the offending validator.
 	validator: AccountId,
@@ -658,7 +663,7 @@ pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 }
 
 /// Indicate how an election round was computed.
-#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum ElectionCompute {
 	/// Result was forcefully computed on chain at the end of the session.
 	OnChain,
@@ -670,7 +675,7 @@ pub enum ElectionCompute {
 }
 
 /// The result of an election round.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct ElectionResult<AccountId, Balance: HasCompact> {
 	/// Flat list of validators who have been elected.
 	elected_stashes: Vec<AccountId>,
@@ -682,7 +687,7 @@ pub struct ElectionResult<AccountId, Balance: HasCompact> {
 }
 
 /// The status of the upcoming (offchain) election.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum ElectionStatus<BlockNumber> {
 	/// Nothing has and will happen for now. submission window is not open.
 	Closed,
@@ -695,7 +700,7 @@ pub enum ElectionStatus<BlockNumber> {
 /// Note that these values must reflect the __total__ number, not only those that are present in the
 /// solution. In short, these should be the same size as the size of the values dumped in
 /// `SnapshotValidators` and `SnapshotNominators`.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default, TypeInfo)]
 pub struct ElectionSize {
 	/// Number of validators in the snapshot of the current election round.
 	#[codec(compact)]
@@ -771,29 +776,101 @@ impl<T: Trait> SessionInterface<<T as frame_system::Trait>::AccountId> for T whe
 
 pub trait WeightInfo {
 	fn bond() -> Weight;
+	fn bond_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn bond_extra() -> Weight;
+	fn bond_extra_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn unbond() -> Weight;
+	fn unbond_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn withdraw_unbonded_update(s: u32, ) -> Weight;
+	fn withdraw_unbonded_update_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn withdraw_unbonded_kill(s: u32, ) -> Weight;
+	fn withdraw_unbonded_kill_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn validate() -> Weight;
+	fn validate_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn nominate(n: u32, ) -> Weight;
+	fn nominate_proof_size(_n: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn chill() -> Weight;
+	fn chill_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_payee() -> Weight;
+	fn set_payee_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_controller() -> Weight;
+	fn set_controller_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_validator_count() -> Weight;
+	fn set_validator_count_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_no_eras() -> Weight;
+	fn force_no_eras_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_new_era() -> Weight;
+	fn force_new_era_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_new_era_always() -> Weight;
+	fn force_new_era_always_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_invulnerables(v: u32, ) -> Weight;
+	fn set_invulnerables_proof_size(_v: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_unstake(s: u32, ) -> Weight;
+	fn force_unstake_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn cancel_deferred_slash(s: u32, ) -> Weight;
+	fn cancel_deferred_slash_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn payout_stakers_alive_staked(n: u32, ) -> Weight;
+	fn payout_stakers_alive_staked_proof_size(_n: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn payout_stakers_dead_controller(n: u32, ) -> Weight;
+	fn payout_stakers_dead_controller_proof_size(_n: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn rebond(l: u32, ) -> Weight;
+	fn rebond_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_history_depth(e: u32, ) -> Weight;
+	fn set_history_depth_proof_size(_e: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn reap_stash(s: u32, ) -> Weight;
+	fn reap_stash_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn new_era(v: u32, n: u32, ) -> Weight;
+	fn new_era_proof_size(_v: u32, _n: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn submit_solution_better(v: u32, n: u32, a: u32, w: u32, ) -> Weight;
+	fn submit_solution_better_proof_size(_v: u32, _n: u32, _a: u32, _w: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
@@ -883,12 +960,15 @@ pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
 	/// multiple pallets send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
 
+	/// The maximum number of unsigned election solutions accepted within an election window.
+	type MaxUnsignedSolutions: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
 
 /// Mode of era-forcing.
-#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Forcing {
 	/// Not forcing anything - just let whatever happen.
@@ -908,7 +988,7 @@ impl Default for Forcing {
 // A value placed in storage that represents the current version of the Staking storage. This value
 // is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
 // This should match directly with the semantic versions of the Rust crate.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	V1_0_0Ancient,
 	V2_0_0,
@@ -1091,6 +1171,10 @@ decl_storage! {
 		/// The score of the current [`QueuedElected`].
 		pub QueuedScore get(fn queued_score): Option<ElectionScore>;
 
+		/// The number of unsigned solutions accepted for an era, and the block number of the
+		/// first one.
+		UnsignedSolutions: map hasher(twox_64_concat) EraIndex => Option<(T::BlockNumber, u32)>;
+
 		/// Flag to control the execution of the offchain election. When `Open(_)`, we accept
 		/// solutions to be submitted.
 		pub EraElectionStatus get(fn era_election_status): ElectionStatus<T::BlockNumber>;
@@ -1273,6 +1357,9 @@ decl_module! {
 		/// The threshold of improvement that should be provided for a new solution to be accepted.
 		const MinSolutionScoreBump: Perbill = T::MinSolutionScoreBump::get();
 
+		/// The maximum number of unsigned election solutions accepted within an election window.
+		const MaxUnsignedSolutions: u32 = T::MaxUnsignedSolutions::get();
+
 		/// The maximum number of nominators rewarded for each validator.
 		///
 		/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can claim
@@ -1396,7 +1483,7 @@ decl_module! {
 		/// - Read: Bonded, Ledger, [Origin Account], Current Era, History Depth, Locks
 		/// - Write: Bonded, Payee, [Origin Account], Locks, Ledger
 		/// # </weight>
-		#[weight = T::WeightInfo::bond()]
+		#[weight = WithProofSize(T::WeightInfo::bond(), T::WeightInfo::bond_proof_size())]
 		pub fn bond(origin,
 			controller: <T::Lookup as StaticLookup>::Source,
 			#[compact] value: BalanceOf<T>,
@@ -1464,7 +1551,10 @@ decl_module! {
 		/// - Read: Era Election Status, Bonded, Ledger, [Origin Account], Locks
 		/// - Write: [Origin Account], Locks, Ledger
 		/// # </weight>
-		#[weight = T::WeightInfo::bond_extra()]
+		#[weight = WithProofSize(
+			T::WeightInfo::bond_extra(),
+			T::WeightInfo::bond_extra_proof_size(),
+		)]
 		fn bond_extra(origin, #[compact] max_additional: BalanceOf<T>) {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let stash = ensure_signed(origin)?;
@@ -1515,7 +1605,7 @@ decl_module! {
 		/// - Read: EraElectionStatus, Ledger, CurrentEra, Locks, BalanceOf Stash,
 		/// - Write: Locks, Ledger, BalanceOf Stash,
 		/// </weight>
-		#[weight = T::WeightInfo::unbond()]
+		#[weight = WithProofSize(T::WeightInfo::unbond(), T::WeightInfo::unbond_proof_size())]
 		fn unbond(origin, #[compact] value: BalanceOf<T>) {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -1575,7 +1665,10 @@ decl_module! {
 		/// - Writes Each: SpanSlash * S
 		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
 		/// # </weight>
-		#[weight = T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans)]
+		#[weight = WithProofSize(
+			T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans),
+			T::WeightInfo::withdraw_unbonded_kill_proof_size(*num_slashing_spans),
+		)]
 		fn withdraw_unbonded(origin, num_slashing_spans: u32) -> DispatchResultWithPostInfo {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -1630,7 +1723,7 @@ decl_module! {
 		/// - Read: Era Election Status, Ledger
 		/// - Write: Nominators, Validators
 		/// # </weight>
-		#[weight = T::WeightInfo::validate()]
+		#[weight = WithProofSize(T::WeightInfo::validate(), T::WeightInfo::validate_proof_size())]
 		pub fn validate(origin, prefs: ValidatorPrefs) {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -1659,7 +1752,10 @@ decl_module! {
 		/// - Reads: Era Election Status, Ledger, Current Era
 		/// - Writes: Validators, Nominators
 		/// # </weight>
-		#[weight = T::WeightInfo::nominate(targets.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::nominate(targets.len() as u32),
+			T::WeightInfo::nominate_proof_size(targets.len() as u32),
+		)]
 		pub fn nominate(origin, targets: Vec<<T::Lookup as StaticLookup>::Source>) {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -1699,7 +1795,7 @@ decl_module! {
 		/// - Read: EraElectionStatus, Ledger
 		/// - Write: Validators, Nominators
 		/// # </weight>
-		#[weight = T::WeightInfo::chill()]
+		#[weight = WithProofSize(T::WeightInfo::chill(), T::WeightInfo::chill_proof_size())]
 		fn chill(origin) {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -1723,7 +1819,7 @@ decl_module! {
 		///     - Read: Ledger
 		///     - Write: Payee
 		/// # </weight>
-		#[weight = T::WeightInfo::set_payee()]
+		#[weight = WithProofSize(T::WeightInfo::set_payee(), T::WeightInfo::set_payee_proof_size())]
 		fn set_payee(origin, payee: RewardDestination<T::AccountId>) {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
@@ -1747,7 +1843,10 @@ decl_module! {
 		/// - Read: Bonded, Ledger New Controller, Ledger Old Controller
 		/// - Write: Bonded, Ledger New Controller, Ledger Old Controller
 		/// # </weight>
-		#[weight = T::WeightInfo::set_controller()]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_controller(),
+			T::WeightInfo::set_controller_proof_size(),
+		)]
 		fn set_controller(origin, controller: <T::Lookup as StaticLookup>::Source) {
 			let stash = ensure_signed(origin)?;
 			let old_controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
@@ -1771,7 +1870,10 @@ decl_module! {
 		/// Weight: O(1)
 		/// Write: Validator Count
 		/// # </weight>
-		#[weight = T::WeightInfo::set_validator_count()]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_validator_count(),
+			T::WeightInfo::set_validator_count_proof_size(),
+		)]
 		fn set_validator_count(origin, #[compact] new: u32) {
 			ensure_root(origin)?;
 			ValidatorCount::put(new);
@@ -1784,7 +1886,10 @@ decl_module! {
 		/// # <weight>
 		/// Same as [`set_validator_count`].
 		/// # </weight>
-		#[weight = T::WeightInfo::set_validator_count()]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_validator_count(),
+			T::WeightInfo::set_validator_count_proof_size(),
+		)]
 		fn increase_validator_count(origin, #[compact] additional: u32) {
 			ensure_root(origin)?;
 			ValidatorCount::mutate(|n| *n += additional);
@@ -1797,7 +1902,10 @@ decl_module! {
 		/// # <weight>
 		/// Same as [`set_validator_count`].
 		/// # </weight>
-		#[weight = T::WeightInfo::set_validator_count()]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_validator_count(),
+			T::WeightInfo::set_validator_count_proof_size(),
+		)]
 		fn scale_validator_count(origin, factor: Percent) {
 			ensure_root(origin)?;
 			ValidatorCount::mutate(|n| *n += factor * *n);
@@ -1812,7 +1920,10 @@ decl_module! {
 		/// - Weight: O(1)
 		/// - Write: ForceEra
 		/// # </weight>
-		#[weight = T::WeightInfo::force_no_eras()]
+		#[weight = WithProofSize(
+			T::WeightInfo::force_no_eras(),
+			T::WeightInfo::force_no_eras_proof_size(),
+		)]
 		fn force_no_eras(origin) {
 			ensure_root(origin)?;
 			ForceEra::put(Forcing::ForceNone);
@@ -1828,7 +1939,10 @@ decl_module! {
 		/// - Weight: O(1)
 		/// - Write ForceEra
 		/// # </weight>
-		#[weight = T::WeightInfo::force_new_era()]
+		#[weight = WithProofSize(
+			T::WeightInfo::force_new_era(),
+			T::WeightInfo::force_new_era_proof_size(),
+		)]
 		fn force_new_era(origin) {
 			ensure_root(origin)?;
 			ForceEra::put(Forcing::ForceNew);
@@ -1842,7 +1956,10 @@ decl_module! {
 		/// - O(V)
 		/// - Write: Invulnerables
 		/// # </weight>
-		#[weight = T::WeightInfo::set_invulnerables(invulnerables.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_invulnerables(invulnerables.len() as u32),
+			T::WeightInfo::set_invulnerables_proof_size(invulnerables.len() as u32),
+		)]
 		fn set_invulnerables(origin, invulnerables: Vec<T::AccountId>) {
 			ensure_root(origin)?;
 			<Invulnerables<T>>::put(invulnerables);
@@ -1858,7 +1975,10 @@ decl_module! {
 		/// Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, Account, Locks
 		/// Writes Each: SpanSlash * S
 		/// # </weight>
-		#[weight = T::WeightInfo::force_unstake(*num_slashing_spans)]
+		#[weight = WithProofSize(
+			T::WeightInfo::force_unstake(*num_slashing_spans),
+			T::WeightInfo::force_unstake_proof_size(*num_slashing_spans),
+		)]
 		fn force_unstake(origin, stash: T::AccountId, num_slashing_spans: u32) {
 			ensure_root(origin)?;
 
@@ -1877,7 +1997,10 @@ decl_module! {
 		/// - Weight: O(1)
 		/// - Write: ForceEra
 		/// # </weight>
-		#[weight = T::WeightInfo::force_new_era_always()]
+		#[weight = WithProofSize(
+			T::WeightInfo::force_new_era_always(),
+			T::WeightInfo::force_new_era_always_proof_size(),
+		)]
 		fn force_new_era_always(origin) {
 			ensure_root(origin)?;
 			ForceEra::put(Forcing::ForceAlways);
@@ -1896,7 +2019,10 @@ decl_module! {
 		/// - Read: Unapplied Slashes
 		/// - Write: Unapplied Slashes
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32),
+			T::WeightInfo::cancel_deferred_slash_proof_size(slash_indices.len() as u32),
+		)]
 		fn cancel_deferred_slash(origin, era: EraIndex, slash_indices: Vec<u32>) {
 			T::SlashCancelOrigin::ensure_origin(origin)?;
 
@@ -1943,7 +2069,12 @@ decl_module! {
 		///   NOTE: weights are assuming that payouts are made to alive stash account (Staked).
 		///   Paying even a dead controller is cheaper weight-wise. We don't do any refunds here.
 		/// # </weight>
-		#[weight = T::WeightInfo::payout_stakers_alive_staked(T::MaxNominatorRewardedPerValidator::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::payout_stakers_alive_staked(T::MaxNominatorRewardedPerValidator::get()),
+			T::WeightInfo::payout_stakers_alive_staked_proof_size(
+				T::MaxNominatorRewardedPerValidator::get(),
+			),
+		)]
 		fn payout_stakers(origin, validator_stash: T::AccountId, era: EraIndex) -> DispatchResult {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			ensure_signed(origin)?;
@@ -1964,7 +2095,10 @@ decl_module! {
 		///     - Reads: EraElectionStatus, Ledger, Locks, [Origin Account]
 		///     - Writes: [Origin Account], Locks, Ledger
 		/// # </weight>
-		#[weight = T::WeightInfo::rebond(MAX_UNLOCKING_CHUNKS as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::rebond(MAX_UNLOCKING_CHUNKS as u32),
+			T::WeightInfo::rebond_proof_size(MAX_UNLOCKING_CHUNKS as u32),
+		)]
 		fn rebond(origin, #[compact] value: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
@@ -2001,7 +2135,10 @@ decl_module! {
 		///     - Clear Prefix Each: Era Stakers, EraStakersClipped, ErasValidatorPrefs
 		///     - Writes Each: ErasValidatorReward, ErasRewardPoints, ErasTotalStake, ErasStartSessionIndex
 		/// # </weight>
-		#[weight = T::WeightInfo::set_history_depth(*_era_items_deleted)]
+		#[weight = WithProofSize(
+			T::WeightInfo::set_history_depth(*_era_items_deleted),
+			T::WeightInfo::set_history_depth_proof_size(*_era_items_deleted),
+		)]
 		fn set_history_depth(origin,
 			#[compact] new_history_depth: EraIndex,
 			#[compact] _era_items_deleted: u32,
@@ -2034,7 +2171,10 @@ decl_module! {
 		/// - Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, Stash Account, Locks
 		/// - Writes Each: SpanSlash * S
 		/// # </weight>
-		#[weight = T::WeightInfo::reap_stash(*num_slashing_spans)]
+		#[weight = WithProofSize(
+			T::WeightInfo::reap_stash(*num_slashing_spans),
+			T::WeightInfo::reap_stash_proof_size(*num_slashing_spans),
+		)]
 		fn reap_stash(_origin, stash: T::AccountId, num_slashing_spans: u32) {
 			ensure!(T::Currency::total_balance(&stash).is_zero(), Error::<T>::FundedTarget);
 			Self::kill_stash(&stash, num_slashing_spans)?;
@@ -2090,11 +2230,19 @@ decl_module! {
 		///   - Initial solution is almost the same.
 		///   - Worse solution is retraced in pre-dispatch-checks which sets its own weight.
 		/// # </weight>
-		#[weight = T::WeightInfo::submit_solution_better(
-			size.validators.into(),
-			size.nominators.into(),
-			compact.len() as u32,
-			winners.len() as u32,
+		#[weight = WithProofSize(
+			T::WeightInfo::submit_solution_better(
+				size.validators.into(),
+				size.nominators.into(),
+				compact.len() as u32,
+				winners.len() as u32,
+			),
+			T::WeightInfo::submit_solution_better_proof_size(
+				size.validators.into(),
+				size.nominators.into(),
+				compact.len() as u32,
+				winners.len() as u32,
+			),
 		)]
 		pub fn submit_election_solution(
 			origin,
@@ -2124,11 +2272,19 @@ decl_module! {
 		/// # <weight>
 		/// See `crate::weight` module.
 		/// # </weight>
-		#[weight = T::WeightInfo::submit_solution_better(
-			size.validators.into(),
-			size.nominators.into(),
-			compact.len() as u32,
-			winners.len() as u32,
+		#[weight = WithProofSize(
+			T::WeightInfo::submit_solution_better(
+				size.validators.into(),
+				size.nominators.into(),
+				compact.len() as u32,
+				winners.len() as u32,
+			),
+			T::WeightInfo::submit_solution_better_proof_size(
+				size.validators.into(),
+				size.nominators.into(),
+				compact.len() as u32,
+				winners.len() as u32,
+			),
 		)]
 		pub fn submit_election_solution_unsigned(
 			origin,
@@ -2734,6 +2890,8 @@ impl<T: Trait> Module<T> {
 		<EraElectionStatus<T>>::put(ElectionStatus::Closed);
 		// Kill snapshots.
 		Self::kill_stakers_snapshot();
+		// Forget the unsigned solutions of the window.
+		<UnsignedSolutions<T>>::remove_all();
 		// Don't track final session.
 		IsCurrentSessionFinal::put(false);
 	}
@@ -3321,8 +3479,6 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 			era,
 			_,
 		) = call {
-			use offchain_election::DEFAULT_LONGEVITY;
-
 			// discard solution not coming from the local OCW.
 			match source {
 				TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ }
@@ -3342,11 +3498,20 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				return invalid .into();
 			}
 
+			if let Err(invalid) = UnsignedSolutionsLimit::<T>::check(
+				era,
+				<frame_system::Module<T>>::block_number(),
+			) {
+				log!(debug, "rejecting unsigned solution because too many were submitted for era {}.", era);
+				return Err(invalid);
+			}
+
 			log!(debug, "validateUnsigned succeeded for a solution at era {}.", era);
 
-			ValidTransaction::with_tag_prefix("StakingOffchain")
+			UnsignedValidity::new("StakingOffchain")
+				.priority(T::UnsignedPriority::get())
 				// The higher the score[0], the better a solution is.
-				.priority(T::UnsignedPriority::get().saturating_add(score[0].saturated_into()))
+				.bump_priority(score[0].saturated_into())
 				// Defensive only. A single solution can exist in the pool per era. Each validator
 				// will run OCW at most once per era, hence there should never exist more than one
 				// transaction anyhow.
@@ -3356,9 +3521,7 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 				// offchain workers now and the above should be same as `T::ElectionLookahead`
 				// without the need to query more storage in the validation phase. If we randomize
 				// offchain worker, then we might re-consider this.
-				.longevity(TryInto::<u64>::try_into(
-						T::ElectionLookahead::get()).unwrap_or(DEFAULT_LONGEVITY)
-				)
+				.longevity(T::ElectionLookahead::get(), offchain_election::DEFAULT_LONGEVITY)
 				// We don't propagate this. This can never the validated at a remote node.
 				.propagate(false)
 				.build()
@@ -3383,15 +3546,22 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 			// this duplicate check here so both signed and unsigned can use a singular
 			// `check_and_replace_solution`.
 			Self::pre_dispatch_checks(*score, *era)
-				.map(|_| ())
-				.map_err(to_invalid)
-				.map_err(Into::into)
+				.map_err(to_invalid)?;
+			UnsignedSolutionsLimit::<T>::note(era, <frame_system::Module<T>>::block_number())
 		} else {
 			Err(InvalidTransaction::Call.into())
 		}
 	}
 }
 
+/// Rate limit of the unsigned election solutions, at most `MaxUnsignedSolutions` of them are
+/// accepted per era within `ElectionLookahead` blocks.
+type UnsignedSolutionsLimit<T> = RateLimit<
+	UnsignedSolutions<T>,
+	<T as Trait>::MaxUnsignedSolutions,
+	<T as Trait>::ElectionLookahead,
+>;
+
 /// Check that list is sorted and has no duplicates.
 fn is_sorted_and_unique(list: &[u32]) -> bool {
 	list.windows(2).all(|w| w[0] < w[1])
@@ -3426,6 +3426,45 @@ mod offchain_election {
 		})
 	}
 
+	#[test]
+	fn unsigned_solutions_are_rate_limited_per_era() {
+		let mut ext = ExtBuilder::default()
+			.offchain_election_ext()
+			.validator_count(2)
+			.build();
+		let state = offchainify(&mut ext, 0);
+		ext.execute_with(|| {
+			run_to_block(12);
+			Staking::offchain_worker(12);
+			assert_eq!(state.read().transactions.len(), 1);
+
+			let encoded = state.read().transactions[0].clone();
+			let extrinsic: Extrinsic = Decode::decode(&mut &*encoded).unwrap();
+			let inner = match extrinsic.call {
+				mock::Call::Staking(inner) => inner,
+			};
+			let validate = || <Staking as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
+				TransactionSource::Local,
+				&inner,
+			);
+
+			assert!(validate().is_ok());
+			assert_ok!(<Staking as sp_runtime::traits::ValidateUnsigned>::pre_dispatch(&inner));
+			assert_eq!(UnsignedSolutions::<Test>::get(current_era()), Some((12, 1)));
+
+			// the limit is reached.
+			UnsignedSolutions::<Test>::insert(current_era(), (12, MaxUnsignedSolutions::get()));
+			assert_eq!(
+				validate(),
+				TransactionValidity::Err(InvalidTransaction::ExhaustsResources.into()),
+			);
+
+			// the counts are cleared when the window closes.
+			run_to_block(15);
+			assert_eq!(UnsignedSolutions::<Test>::iter().count(), 0);
+		})
+	}
+
 	#[test]
 	fn mediocre_submission_from_authority_is_early_rejected() {
 		let mut ext = ExtBuilder::default()
@@ -18,7 +18,9 @@
 mod parse;
 
 use frame_support_procedural_tools::syn_ext as ext;
-use frame_support_procedural_tools::{generate_crate_access, generate_hidden_includes};
+use frame_support_procedural_tools::{
+	generate_crate_access, generate_hidden_includes, clean_type_string,
+};
 use parse::{ModuleDeclaration, RuntimeDefinition, WhereSection, ModulePart};
 use proc_macro::TokenStream;
 use proc_macro2::{TokenStream as TokenStream2};
@@ -155,6 +157,7 @@ fn construct_runtime_parsed(definition: RuntimeDefinition) -> Result<TokenStream
 	let module_to_index = decl_pallet_runtime_setup(&modules, &scrate);
 
 	let dispatch = decl_outer_dispatch(&name, modules.iter(), &scrate);
+	let outer_type_info = decl_outer_type_info(&name, modules.iter(), &scrate);
 	let metadata = decl_runtime_metadata(&name, modules.iter(), &scrate, &unchecked_extrinsic);
 	let outer_config = decl_outer_config(&name, modules.iter(), &scrate);
 	let inherent = decl_outer_inherent(
@@ -188,6 +191,8 @@ fn construct_runtime_parsed(definition: RuntimeDefinition) -> Result<TokenStream
 
 		#dispatch
 
+		#outer_type_info
+
 		#metadata
 
 		#outer_config
@@ -275,7 +280,9 @@ fn decl_outer_config<'a>(
 		});
 	quote!(
 		#scrate::sp_runtime::impl_outer_config! {
-			pub struct GenesisConfig for #runtime {
+			pub struct GenesisConfig for #runtime
+				where OnGenesis = #scrate::traits::on_genesis_storage::<AllModulesWithSystem>
+			{
 				#(#modules_tokens)*
 			}
 		}
@@ -346,6 +353,101 @@ fn decl_outer_dispatch<'a>(
 	)
 }
 
+/// Implement `TypeInfo` for the outer `Event`, `Call` and `OriginCaller`, following the variants
+/// generated by `impl_outer_event`, `impl_outer_dispatch` and `impl_outer_origin`.
+fn decl_outer_type_info<'a>(
+	runtime: &'a Ident,
+	module_declarations: impl Iterator<Item = &'a Module>,
+	scrate: &'a TokenStream2,
+) -> TokenStream2 {
+	let variant = |name: String, ty: TokenStream2, index: u8| {
+		let type_name = clean_type_string(&ty.to_string());
+		quote!(
+			#scrate::type_info::Variant::new(
+				#name,
+				#scrate::sp_std::vec![
+					#scrate::type_info::Field::unnamed(
+						#scrate::type_info::MetaType::new::<#ty>(),
+						#type_name,
+					),
+				],
+				#index,
+			)
+		)
+	};
+
+	let mut events = Vec::new();
+	let mut calls = Vec::new();
+	let mut origins = Vec::new();
+	for module_declaration in module_declarations {
+		let module = &module_declaration.module;
+		let name = &module_declaration.name;
+		let index = module_declaration.index;
+
+		if name == SYSTEM_MODULE_NAME {
+			origins.push(variant("system".into(), quote!( #module::Origin<#runtime> ), index));
+		} else if let Some(part) = module_declaration.find_part("Origin") {
+			let (variant_name, instance) = match &module_declaration.instance {
+				Some(instance) => (format!("{}_{}", module, instance), Some(quote!(, #module::#instance))),
+				None => (module.to_string(), None),
+			};
+			let ty = if part.generics.params.is_empty() {
+				quote!( #module::Origin )
+			} else {
+				quote!( #module::Origin<#runtime #instance> )
+			};
+			origins.push(variant(variant_name, ty, index));
+		}
+
+		if let Some(part) = module_declaration.find_part("Event") {
+			let (variant_name, instance) = match &module_declaration.instance {
+				Some(instance) => (format!("{}_{}", module, instance), Some(quote!(, #module::#instance))),
+				None => (module.to_string(), None),
+			};
+			let ty = if part.generics.params.is_empty() {
+				quote!( #module::Event )
+			} else {
+				quote!( #module::Event<#runtime #instance> )
+			};
+			events.push(variant(variant_name, ty, index));
+		}
+
+		if module_declaration.exists_part("Call") {
+			let ty = quote!( #scrate::dispatch::CallableCallFor<#name, #runtime> );
+			calls.push(variant(name.to_string(), ty, index));
+		}
+	}
+
+	quote!(
+		impl #scrate::type_info::TypeInfo for Event {
+			fn type_info() -> #scrate::type_info::Type {
+				#scrate::type_info::Type::new(
+					#scrate::type_info::Path::new("Event", module_path!()),
+					#scrate::type_info::TypeDef::Variant(#scrate::sp_std::vec![ #( #events ),* ]),
+				)
+			}
+		}
+
+		impl #scrate::type_info::TypeInfo for Call {
+			fn type_info() -> #scrate::type_info::Type {
+				#scrate::type_info::Type::new(
+					#scrate::type_info::Path::new("Call", module_path!()),
+					#scrate::type_info::TypeDef::Variant(#scrate::sp_std::vec![ #( #calls ),* ]),
+				)
+			}
+		}
+
+		impl #scrate::type_info::TypeInfo for OriginCaller {
+			fn type_info() -> #scrate::type_info::Type {
+				#scrate::type_info::Type::new(
+					#scrate::type_info::Path::new("OriginCaller", module_path!()),
+					#scrate::type_info::TypeDef::Variant(#scrate::sp_std::vec![ #( #origins ),* ]),
+				)
+			}
+		}
+	)
+}
+
 fn decl_outer_origin<'a>(
 	runtime_name: &'a Ident,
 	modules_except_system: impl Iterator<Item = &'a Module>,
@@ -455,10 +557,17 @@ fn decl_all_modules<'a>(
 	let all_modules = names.iter()
 		.filter(|n| **n != SYSTEM_MODULE_NAME)
 		.fold(TokenStream2::default(), |combined, name| quote!((#name, #combined)));
+	let all_modules_with_system = names.iter()
+		.fold(TokenStream2::default(), |combined, name| quote!((#name, #combined)));
 
 	quote!(
 		#types
+		/// All modules included in the runtime as a nested tuple of types.
+		/// Excludes the System module.
 		type AllModules = ( #all_modules );
+		/// All modules included in the runtime as a nested tuple of types.
+		#[allow(dead_code)]
+		type AllModulesWithSystem = ( #all_modules_with_system );
 	)
 }
 
@@ -22,6 +22,11 @@
 mod storage;
 mod construct_runtime;
 mod transactional;
+mod pallet;
+mod clone_no_bound;
+mod partial_eq_no_bound;
+mod debug_no_bound;
+mod pallet_version;
 
 use proc_macro::TokenStream;
 
@@ -123,6 +128,25 @@ use proc_macro::TokenStream;
 ///   Twox128(module_prefix) ++ Twox128(storage_prefix) ++ Hasher1(encode(key1)) ++ Hasher2(encode(key2))
 ///   ```
 ///
+/// * N map: `Foo: nmap hasher($hash1) u32, hasher($hash2) u32, .., hasher($hashN) u32 => u32`:
+///   Implements the [`StorageNMap`](../frame_support/storage/trait.StorageNMap.html) trait using
+///   the [`StorageNMap generator`](../frame_support/storage/generator/trait.StorageNMap.html).
+///   And [`StoragePrefixedMap`](../frame_support/storage/trait.StoragePrefixedMap.html).
+///
+///   Keys are given as a tuple, e.g. `Foo::insert((k1, k2, k3), v)`, and any leading part of the
+///   keys can be used to iterate over or remove values, e.g. `Foo::remove_prefix((k1,))`. Each
+///   `$hashI` must be chosen with care, see generator documentation.
+///
+///   The generator is implemented with:
+///   * `module_prefix`: $module_prefix
+///   * `storage_prefix`: storage_name
+///   * `Key`: `(Key<$hash1, K1>, .., Key<$hashN, KN>)`, or `Key<$hash1, K1>` for a single key.
+///
+///   Thus keys are stored at:
+///   ```nocompile
+///   Twox128(module_prefix) ++ Twox128(storage_prefix) ++ Hasher1(encode(key1)) ++ .. ++ HasherN(encode(keyN))
+///   ```
+///
 /// Supported hashers (ordered from least to best security):
 ///
 /// * `identity` - Just the unrefined key material. Use only when it is known to be a secure hash
@@ -325,3 +349,48 @@ pub fn construct_runtime(input: TokenStream) -> TokenStream {
 pub fn transactional(attr: TokenStream, input: TokenStream) -> TokenStream {
 	transactional::transactional(attr, input).unwrap_or_else(|e| e.to_compile_error().into())
 }
+
+/// Declare a pallet, see the documentation of `frame_support::pallet`.
+#[proc_macro_attribute]
+pub fn pallet(attr: TokenStream, item: TokenStream) -> TokenStream {
+	pallet::pallet(attr, item)
+}
+
+/// Derive [`Clone`] but do not bound any generic. Docs are at `frame_support::CloneNoBound`.
+#[proc_macro_derive(CloneNoBound)]
+pub fn derive_clone_no_bound(input: TokenStream) -> TokenStream {
+	clone_no_bound::derive_clone_no_bound(input)
+}
+
+/// Derive [`PartialEq`] but do not bound any generic. Docs are at
+/// `frame_support::PartialEqNoBound`.
+#[proc_macro_derive(PartialEqNoBound)]
+pub fn derive_partial_eq_no_bound(input: TokenStream) -> TokenStream {
+	partial_eq_no_bound::derive_partial_eq_no_bound(input)
+}
+
+/// Derive [`Eq`] but do not bound any generic. Docs are at `frame_support::EqNoBound`.
+#[proc_macro_derive(EqNoBound)]
+pub fn derive_eq_no_bound(input: TokenStream) -> TokenStream {
+	partial_eq_no_bound::derive_eq_no_bound(input)
+}
+
+/// Derive [`Debug`] but do not bound any generic. Docs are at `frame_support::DebugNoBound`.
+#[proc_macro_derive(DebugNoBound)]
+pub fn derive_debug_no_bound(input: TokenStream) -> TokenStream {
+	debug_no_bound::derive_debug_no_bound(input)
+}
+
+/// Derive [`Debug`] but do not bound any generic, the implementation is stripped when the `std`
+/// feature is disabled. Docs are at `frame_support::RuntimeDebugNoBound`.
+#[proc_macro_derive(RuntimeDebugNoBound)]
+pub fn derive_runtime_debug_no_bound(input: TokenStream) -> TokenStream {
+	debug_no_bound::derive_runtime_debug_no_bound(input)
+}
+
+/// Convert the current crate version into a `PalletVersion`. Docs are at
+/// `frame_support::crate_to_pallet_version`.
+#[proc_macro]
+pub fn crate_to_pallet_version(input: TokenStream) -> TokenStream {
+	pallet_version::crate_to_pallet_version(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
@@ -0,0 +1,297 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+use frame_support_procedural_tools::clean_type_string;
+use syn::spanned::Spanned;
+
+/// * Generate enum call and implement various trait on it, including `TypeInfo`.
+/// * Implement Callable and call_function on `Pallet`
+pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
+	let frame_support = &def.frame_support;
+	let frame_system = &def.config.frame_system;
+	let pallet_ident = &def.pallet_struct.pallet;
+	let call_ident = syn::Ident::new("Call", def.call.attr_span);
+
+	let fn_name = def.call.methods.iter().map(|method| &method.name).collect::<Vec<_>>();
+
+	let fn_weight = def.call.methods.iter().map(|method| &method.weight);
+
+	let fn_doc = def.call.methods.iter().map(|method| &method.docs).collect::<Vec<_>>();
+
+	let args_name = def.call.methods.iter()
+		.map(|method| method.args.iter().map(|(_, name, _)| name.clone()).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	let args_name_other = def.call.methods.iter()
+		.map(|method| {
+			method.args.iter()
+				.map(|(_, name, _)| syn::Ident::new(&format!("{}_other", name), name.span()))
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+
+	let args_type = def.call.methods.iter()
+		.map(|method| method.args.iter().map(|(_, _, type_)| type_.clone()).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	let args_compact_attr = def.call.methods.iter().map(|method| {
+		method.args.iter()
+			.map(|(is_compact, _, type_)| {
+				if *is_compact {
+					quote::quote_spanned!(type_.span() => #[codec(compact)] )
+				} else {
+					quote::quote!()
+				}
+			})
+			.collect::<Vec<_>>()
+	});
+
+	let args_metadata_type = def.call.methods.iter().map(|method| {
+		method.args.iter()
+			.map(|(is_compact, _, type_)| {
+				let final_type = if *is_compact {
+					quote::quote!(Compact<#type_>)
+				} else {
+					quote::quote!(#type_)
+				};
+				clean_type_string(&final_type.to_string())
+			})
+			.collect::<Vec<_>>()
+	});
+
+	let args_meta_type = def.call.methods.iter().map(|method| {
+		method.args.iter()
+			.map(|(is_compact, _, type_)| {
+				if *is_compact {
+					quote::quote!(
+						#frame_support::type_info::MetaType::new::<#frame_support::codec::Compact<#type_>>()
+					)
+				} else {
+					quote::quote!( #frame_support::type_info::MetaType::new::<#type_>() )
+				}
+			})
+			.collect::<Vec<_>>()
+	});
+
+	let args_type_name = args_type.iter()
+		.map(|types| {
+			types.iter()
+				.map(|type_| clean_type_string(&quote::quote!(#type_).to_string()))
+				.collect::<Vec<_>>()
+		});
+
+	let all_args_type = args_type.iter().flatten().collect::<Vec<_>>();
+
+	let fn_index = (0..fn_name.len()).map(|i| i as u8);
+
+	quote::quote_spanned!(def.call.attr_span =>
+		/// Contains one variant per dispatchable that can be called by an extrinsic.
+		#[derive(#frame_support::codec::Encode, #frame_support::codec::Decode)]
+		#[allow(non_camel_case_types)]
+		pub enum #call_ident<T: Config> {
+			#[doc(hidden)]
+			#[codec(skip)]
+			__Ignore(
+				#frame_support::sp_std::marker::PhantomData<T>,
+				#frame_support::Never,
+			),
+			#(
+				#( #[doc = #fn_doc] )*
+				#fn_name( #( #args_compact_attr #args_type ),* ),
+			)*
+		}
+
+		// Manual implementation of clone/eq/partialeq/debug because using derive erroneously
+		// requires the traits from T.
+		impl<T: Config> #frame_support::dispatch::Clone for #call_ident<T> {
+			fn clone(&self) -> Self {
+				match *self {
+					#(
+						Self::#fn_name( #( ref #args_name, )* ) =>
+							Self::#fn_name( #( (*#args_name).clone(), )* ),
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+
+		impl<T: Config> #frame_support::dispatch::PartialEq for #call_ident<T> {
+			fn eq(&self, other: &Self) -> bool {
+				#[allow(unreachable_patterns)]
+				match (self, other) {
+					#(
+						(
+							Self::#fn_name( #( #args_name, )* ),
+							Self::#fn_name( #( #args_name_other, )* ),
+						) => true #( && #args_name == #args_name_other )*,
+					)*
+					(Self::__Ignore(_, _), _) | (_, Self::__Ignore(_, _)) =>
+						unreachable!("__Ignore cannot be used"),
+					_ => false,
+				}
+			}
+		}
+
+		impl<T: Config> #frame_support::dispatch::Eq for #call_ident<T> {}
+
+		impl<T: Config> #frame_support::dispatch::fmt::Debug for #call_ident<T> {
+			fn fmt(
+				&self,
+				_f: &mut #frame_support::dispatch::fmt::Formatter,
+			) -> #frame_support::dispatch::fmt::Result {
+				match *self {
+					#(
+						Self::#fn_name( #( ref #args_name, )* ) =>
+							_f.debug_tuple(stringify!(#fn_name))
+								#( .field(#args_name) )*
+								.finish(),
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+
+		impl<T: Config> #frame_support::dispatch::GetDispatchInfo for #call_ident<T> {
+			fn get_dispatch_info(&self) -> #frame_support::dispatch::DispatchInfo {
+				match *self {
+					#(
+						Self::#fn_name ( #( ref #args_name, )* ) => {
+							let base_weight = #fn_weight;
+
+							let weight = <
+								dyn #frame_support::dispatch::WeighData<( #( & #args_type, )* )>
+							>::weigh_data(&base_weight, ( #( #args_name, )* ));
+
+							let class = <
+								dyn #frame_support::dispatch::ClassifyDispatch<
+									( #( & #args_type, )* )
+								>
+							>::classify_dispatch(&base_weight, ( #( #args_name, )* ));
+
+							let pays_fee = <
+								dyn #frame_support::dispatch::PaysFee<( #( & #args_type, )* )>
+							>::pays_fee(&base_weight, ( #( #args_name, )* ));
+
+							let proof_size = <
+								dyn #frame_support::dispatch::WeighData<( #( & #args_type, )* )>
+							>::weigh_proof_size(&base_weight, ( #( #args_name, )* ));
+
+							#frame_support::dispatch::DispatchInfo {
+								weight,
+								proof_size,
+								class,
+								pays_fee,
+							}
+						},
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+
+		impl<T: Config> #frame_support::dispatch::GetCallName for #call_ident<T> {
+			fn get_call_name(&self) -> &'static str {
+				match *self {
+					#( Self::#fn_name(..) => stringify!(#fn_name), )*
+					Self::__Ignore(_, _) => unreachable!("__PhantomItem cannot be used."),
+				}
+			}
+
+			fn get_call_names() -> &'static [&'static str] {
+				&[ #( stringify!(#fn_name), )* ]
+			}
+		}
+
+		impl<T: Config> #frame_support::traits::UnfilteredDispatchable for #call_ident<T> {
+			type Origin = <T as #frame_system::Trait>::Origin;
+			fn dispatch_bypass_filter(
+				self,
+				origin: Self::Origin
+			) -> #frame_support::dispatch::DispatchResultWithPostInfo {
+				match self {
+					#(
+						Self::#fn_name( #( #args_name, )* ) =>
+							<#pallet_ident<T>>::#fn_name(origin, #( #args_name, )* )
+								.map(Into::into).map_err(Into::into),
+					)*
+					Self::__Ignore(_, _) => {
+						let _ = origin; // Use origin for empty Call enum
+						unreachable!("__PhantomItem cannot be used.");
+					},
+				}
+			}
+		}
+
+		impl<T: Config> #frame_support::type_info::TypeInfo for #call_ident<T>
+		where
+			T: 'static,
+			#( #all_args_type: #frame_support::type_info::TypeInfo + 'static, )*
+		{
+			fn type_info() -> #frame_support::type_info::Type {
+				#frame_support::type_info::Type::new(
+					#frame_support::type_info::Path::new(stringify!(#call_ident), module_path!()),
+					#frame_support::type_info::TypeDef::Variant(#frame_support::sp_std::vec![ #(
+						#frame_support::type_info::Variant::new(
+							stringify!(#fn_name),
+							#frame_support::sp_std::vec![ #(
+								#frame_support::type_info::Field::named(
+									stringify!(#args_name),
+									#args_meta_type,
+									#args_type_name,
+								),
+							)* ],
+							#fn_index,
+						).docs(&[ #( #fn_doc ),* ]),
+					)* ]),
+				)
+			}
+		}
+
+		impl<T: Config> #frame_support::dispatch::Callable<T> for #pallet_ident<T> {
+			type Call = #call_ident<T>;
+		}
+
+		impl<T: Config> #pallet_ident<T> {
+			#[doc(hidden)]
+			pub fn call_functions() -> &'static [#frame_support::dispatch::FunctionMetadata] {
+				&[ #(
+					#frame_support::dispatch::FunctionMetadata {
+						name: #frame_support::dispatch::DecodeDifferent::Encode(
+							stringify!(#fn_name)
+						),
+						arguments: #frame_support::dispatch::DecodeDifferent::Encode(
+							&[ #(
+								#frame_support::dispatch::FunctionArgumentMetadata {
+									name: #frame_support::dispatch::DecodeDifferent::Encode(
+										stringify!(#args_name)
+									),
+									ty: #frame_support::dispatch::DecodeDifferent::Encode(
+										#args_metadata_type
+									),
+								},
+							)* ]
+						),
+						documentation: #frame_support::dispatch::DecodeDifferent::Encode(
+							&[ #( #fn_doc ),* ]
+						),
+					},
+				)* ]
+			}
+		}
+	)
+}
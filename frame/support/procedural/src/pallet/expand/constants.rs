@@ -0,0 +1,115 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+use frame_support_procedural_tools::clean_type_string;
+use quote::ToTokens;
+
+/// Implement `module_constants_metadata` and `module_constants_metadata_v14` on `Pallet`, using
+/// the `#[pallet::constant]` associated types of the config trait.
+pub fn expand_constants(def: &mut Def) -> proc_macro2::TokenStream {
+	let frame_support = &def.frame_support;
+	let pallet_ident = &def.pallet_struct.pallet;
+
+	let mut getters = vec![];
+	let mut consts = vec![];
+	let mut consts_v14 = vec![];
+	let mut const_types = vec![];
+	for const_ in &def.config.consts_metadata {
+		let ident = &const_.ident;
+		let const_type = &const_.type_;
+		let const_type_str = clean_type_string(&const_type.to_token_stream().to_string());
+		let doc = &const_.doc;
+		let default_byte_getter = syn::Ident::new(
+			&format!("{}DefaultByteGetter", ident),
+			ident.span(),
+		);
+
+		getters.push(quote::quote!(
+			#[allow(non_upper_case_types)]
+			#[allow(non_camel_case_types)]
+			struct #default_byte_getter<T>(
+				#frame_support::sp_std::marker::PhantomData<T>
+			);
+
+			impl<T: Config> #frame_support::dispatch::DefaultByte for
+				#default_byte_getter<T>
+			{
+				fn default_byte(&self) -> #frame_support::sp_std::vec::Vec<u8> {
+					let value = <T::#ident as #frame_support::traits::Get<#const_type>>::get();
+					#frame_support::codec::Encode::encode(&value)
+				}
+			}
+
+			unsafe impl<T: Config> Send for #default_byte_getter<T> {}
+			unsafe impl<T: Config> Sync for #default_byte_getter<T> {}
+		));
+
+		consts.push(quote::quote!(
+			#frame_support::dispatch::ModuleConstantMetadata {
+				name: #frame_support::dispatch::DecodeDifferent::Encode(stringify!(#ident)),
+				ty: #frame_support::dispatch::DecodeDifferent::Encode(#const_type_str),
+				value: #frame_support::dispatch::DecodeDifferent::Encode(
+					#frame_support::dispatch::DefaultByteGetter(
+						&#default_byte_getter::<T>(
+							#frame_support::sp_std::marker::PhantomData
+						)
+					)
+				),
+				documentation: #frame_support::dispatch::DecodeDifferent::Encode(
+					&[ #( #doc ),* ]
+				),
+			}
+		));
+
+		consts_v14.push(quote::quote!(
+			#frame_support::metadata::ModuleConstantMetadataV14 {
+				name: stringify!(#ident),
+				ty: #frame_support::type_info::MetaType::new::<#const_type>(),
+				value: {
+					let value = <T::#ident as #frame_support::traits::Get<#const_type>>::get();
+					#frame_support::codec::Encode::encode(&value)
+				},
+				documentation: #frame_support::sp_std::vec![ #( #doc ),* ],
+			}
+		));
+		const_types.push(const_type);
+	}
+
+	quote::quote!(
+		impl<T: Config> #pallet_ident<T> {
+
+			#[doc(hidden)]
+			pub fn module_constants_metadata()
+				-> &'static [#frame_support::dispatch::ModuleConstantMetadata]
+			{
+				#( #getters )*
+
+				&[ #( #consts ),* ]
+			}
+
+			#[doc(hidden)]
+			pub fn module_constants_metadata_v14()
+				-> #frame_support::sp_std::vec::Vec<#frame_support::metadata::ModuleConstantMetadataV14>
+			where
+				#( #const_types: #frame_support::type_info::TypeInfo + 'static, )*
+			{
+				#frame_support::sp_std::vec![ #( #consts_v14 ),* ]
+			}
+		}
+	)
+}
@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+
+/// * impl various trait on Error
+/// * impl ModuleErrorMetadata for Error
+/// * impl TypeInfo for Error
+pub fn expand_error(def: &mut Def) -> proc_macro2::TokenStream {
+	let error = if let Some(error) = &def.error {
+		error
+	} else {
+		return Default::default()
+	};
+
+	let error_ident = &error.error;
+	let frame_support = &def.frame_support;
+	let frame_system = &def.config.frame_system;
+	let pallet_ident = &def.pallet_struct.pallet;
+
+	let phantom_variant: syn::Variant = syn::parse_quote!(
+		#[doc(hidden)]
+		__Ignore(
+			#frame_support::sp_std::marker::PhantomData<T>,
+			#frame_support::Never,
+		)
+	);
+
+	let as_u8_matches = error.variants.iter().enumerate()
+		.map(|(i, (variant, _))| {
+			let i = i as u8;
+			quote::quote_spanned!(error.attr_span => Self::#variant => #i,)
+		});
+
+	let as_str_matches = error.variants.iter()
+		.map(|(variant, _)| {
+			let variant_str = format!("{}", variant);
+			quote::quote_spanned!(error.attr_span => Self::#variant => #variant_str,)
+		});
+
+	let metadata = error.variants.iter()
+		.map(|(variant, doc)| {
+			let variant_str = format!("{}", variant);
+			quote::quote_spanned!(error.attr_span =>
+				#frame_support::error::ErrorMetadata {
+					name: #frame_support::error::DecodeDifferent::Encode(#variant_str),
+					documentation: #frame_support::error::DecodeDifferent::Encode(&[ #( #doc, )* ]),
+				},
+			)
+		});
+
+	let variants = error.variants.iter().enumerate()
+		.map(|(i, (variant, doc))| {
+			let i = i as u8;
+			let variant_str = format!("{}", variant);
+			quote::quote_spanned!(error.attr_span =>
+				#frame_support::type_info::Variant::new(
+					#variant_str,
+					#frame_support::sp_std::vec::Vec::new(),
+					#i,
+				).docs(&[ #( #doc, )* ]),
+			)
+		});
+
+	let error_item = {
+		let item = &mut def.item.content.as_mut().expect("Checked by def parser").1[error.index];
+		if let syn::Item::Enum(item) = item {
+			item
+		} else {
+			unreachable!("Checked by error parser")
+		}
+	};
+
+	error_item.variants.insert(0, phantom_variant);
+
+	quote::quote_spanned!(error.attr_span =>
+		impl<T: Config> #frame_support::sp_std::fmt::Debug for #error_ident<T> {
+			fn fmt(&self, f: &mut #frame_support::sp_std::fmt::Formatter<'_>)
+				-> #frame_support::sp_std::fmt::Result
+			{
+				f.write_str(self.as_str())
+			}
+		}
+
+		impl<T: Config> #error_ident<T> {
+			pub fn as_u8(&self) -> u8 {
+				match &self {
+					Self::__Ignore(_, _) => unreachable!("`__Ignore` can never be constructed"),
+					#( #as_u8_matches )*
+				}
+			}
+
+			pub fn as_str(&self) -> &'static str {
+				match &self {
+					Self::__Ignore(_, _) => unreachable!("`__Ignore` can never be constructed"),
+					#( #as_str_matches )*
+				}
+			}
+		}
+
+		impl<T: Config> From<#error_ident<T>> for &'static str {
+			fn from(err: #error_ident<T>) -> &'static str {
+				err.as_str()
+			}
+		}
+
+		impl<T: Config> From<#error_ident<T>> for #frame_support::sp_runtime::DispatchError {
+			fn from(err: #error_ident<T>) -> Self {
+				let index = <
+					<T as #frame_system::Trait>::PalletInfo
+					as #frame_support::traits::PalletInfo
+				>::index::<#pallet_ident<T>>()
+					.expect("Every active module has an index in the runtime; qed") as u8;
+
+				#frame_support::sp_runtime::DispatchError::Module {
+					index,
+					error: err.as_u8(),
+					message: Some(err.as_str()),
+				}
+			}
+		}
+
+		impl<T: Config> #frame_support::error::ModuleErrorMetadata for #error_ident<T> {
+			fn metadata() -> &'static [#frame_support::error::ErrorMetadata] {
+				&[ #( #metadata )* ]
+			}
+
+			fn error_type() -> Option<#frame_support::type_info::MetaType> {
+				Some(#frame_support::type_info::MetaType::new::<Self>())
+			}
+		}
+
+		impl<T: Config> #frame_support::type_info::TypeInfo for #error_ident<T> {
+			fn type_info() -> #frame_support::type_info::Type {
+				#frame_support::type_info::Type::new(
+					#frame_support::type_info::Path::new(stringify!(#error_ident), module_path!()),
+					#frame_support::type_info::TypeDef::Variant(
+						#frame_support::sp_std::vec![ #( #variants )* ]
+					),
+				)
+			}
+		}
+	)
+}
@@ -0,0 +1,149 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+
+/// * implement the individual traits using the Hooks trait
+/// * store the pallet version after `on_runtime_upgrade`
+pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
+	let frame_support = &def.frame_support;
+	let frame_system = &def.config.frame_system;
+	let pallet_ident = &def.pallet_struct.pallet;
+
+	quote::quote_spanned!(def.hooks.attr_span =>
+		impl<T: Config>
+			#frame_support::traits::OnFinalize<<T as #frame_system::Trait>::BlockNumber>
+			for #pallet_ident<T>
+		{
+			fn on_finalize(n: <T as #frame_system::Trait>::BlockNumber) {
+				#frame_support::sp_tracing::enter_span!(
+					#frame_support::sp_tracing::trace_span!("on_finalize")
+				);
+
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::on_finalize(n)
+			}
+		}
+
+		impl<T: Config>
+			#frame_support::traits::OnInitialize<<T as #frame_system::Trait>::BlockNumber>
+			for #pallet_ident<T>
+		{
+			fn on_initialize(
+				n: <T as #frame_system::Trait>::BlockNumber
+			) -> #frame_support::weights::Weight {
+				#frame_support::sp_tracing::enter_span!(
+					#frame_support::sp_tracing::trace_span!("on_initialize")
+				);
+
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::on_initialize(n)
+			}
+		}
+
+		impl<T: Config>
+			#frame_support::traits::OnIdle<<T as #frame_system::Trait>::BlockNumber>
+			for #pallet_ident<T>
+		{
+			fn on_idle(
+				n: <T as #frame_system::Trait>::BlockNumber,
+				remaining_weight: #frame_support::weights::Weight,
+			) -> #frame_support::weights::Weight {
+				#frame_support::sp_tracing::enter_span!(
+					#frame_support::sp_tracing::trace_span!("on_idle")
+				);
+
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::on_idle(n, remaining_weight)
+			}
+		}
+
+		impl<T: Config> #frame_support::traits::OnRuntimeUpgrade for #pallet_ident<T> {
+			fn on_runtime_upgrade() -> #frame_support::weights::Weight {
+				#frame_support::sp_tracing::enter_span!(
+					#frame_support::sp_tracing::trace_span!("on_runtime_upgrade")
+				);
+
+				let result = <
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::on_runtime_upgrade();
+
+				#frame_support::crate_to_pallet_version!()
+					.put_into_storage::<<T as #frame_system::Trait>::PalletInfo, Self>();
+
+				let additional_write = <
+					<T as #frame_system::Trait>::DbWeight as #frame_support::traits::Get<_>
+				>::get().writes(1);
+
+				result.saturating_add(additional_write)
+			}
+
+			#[cfg(feature = "try-runtime")]
+			fn pre_upgrade() -> Result<(), &'static str> {
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::pre_upgrade()
+			}
+
+			#[cfg(feature = "try-runtime")]
+			fn post_upgrade() -> Result<(), &'static str> {
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::post_upgrade()
+			}
+		}
+
+		impl<T: Config>
+			#frame_support::traits::OffchainWorker<<T as #frame_system::Trait>::BlockNumber>
+			for #pallet_ident<T>
+		{
+			fn offchain_worker(n: <T as #frame_system::Trait>::BlockNumber) {
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::offchain_worker(n)
+			}
+		}
+
+		#[cfg(feature = "std")]
+		impl<T: Config> #frame_support::traits::IntegrityTest for #pallet_ident<T> {
+			fn integrity_test() {
+				<
+					Self as #frame_support::traits::Hooks<
+						<T as #frame_system::Trait>::BlockNumber
+					>
+				>::integrity_test()
+			}
+		}
+	)
+}
@@ -0,0 +1,191 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use syn::spanned::Spanned;
+
+/// List of additional token to be used for parsing.
+mod keyword {
+	syn::custom_keyword!(Get);
+	syn::custom_keyword!(constant);
+	syn::custom_keyword!(pallet);
+}
+
+/// Input definition for the pallet config.
+pub struct ConfigDef {
+	/// The index of item in pallet module.
+	pub index: usize,
+	/// Whether the trait has the associated type `Event`, note that those bounds are checked:
+	/// * `IsType<Self as frame_system::Trait>::Event`
+	/// * `From<Event>` or `From<Event<T>>`
+	pub has_event_type: bool,
+	/// The path to the system crate, e.g. `frame_system`, as found in the supertrait.
+	pub frame_system: syn::Path,
+	/// Const associated type.
+	pub consts_metadata: Vec<ConstMetadataDef>,
+	/// The span of the pallet::config attribute.
+	pub attr_span: proc_macro2::Span,
+}
+
+/// Input definition for a constant in pallet config.
+pub struct ConstMetadataDef {
+	/// Name of the associated type.
+	pub ident: syn::Ident,
+	/// The type in Get, e.g. `u32` in `type Foo: Get<u32>;`, but `Self` is replaced by `T`.
+	pub type_: syn::Type,
+	/// The doc associated.
+	pub doc: Vec<syn::Lit>,
+}
+
+impl syn::parse::Parse for ConstMetadataDef {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let doc = helper::get_doc_literals(&syn::Attribute::parse_outer(input)?);
+		input.parse::<syn::Token![type]>()?;
+		let ident = input.parse::<syn::Ident>()?;
+		input.parse::<syn::Token![:]>()?;
+		input.parse::<keyword::Get>()?;
+		input.parse::<syn::Token![<]>()?;
+		let mut type_ = input.parse::<syn::Type>()?;
+		type_ = syn::parse2::<syn::Type>(replace_self_by_t(quote::quote!(#type_)))
+			.expect("Internal error: replacing `Self` by `T` should result in valid type");
+		input.parse::<syn::Token![>]>()?;
+		input.parse::<syn::Token![;]>()?;
+
+		Ok(Self { ident, type_, doc })
+	}
+}
+
+/// Parse for `#[pallet::constant]`.
+pub struct TypeAttrConst(proc_macro2::Span);
+
+impl syn::parse::Parse for TypeAttrConst {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+
+		Ok(TypeAttrConst(content.parse::<keyword::constant>()?.span()))
+	}
+}
+
+/// Replace ident `Self` by `T`.
+pub fn replace_self_by_t(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	input.into_iter()
+		.map(|token_tree| match token_tree {
+			proc_macro2::TokenTree::Group(group) =>
+				proc_macro2::Group::new(
+					group.delimiter(),
+					replace_self_by_t(group.stream())
+				).into(),
+			proc_macro2::TokenTree::Ident(ident) if ident == "Self" =>
+				proc_macro2::Ident::new("T", ident.span()).into(),
+			other => other
+		})
+		.collect()
+}
+
+impl ConfigDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Trait(item) = item {
+			item
+		} else {
+			let msg = "Invalid pallet::config, expected trait definition";
+			return Err(syn::Error::new(item.span(), msg));
+		};
+
+		if !matches!(item.vis, syn::Visibility::Public(_)) {
+			let msg = "Invalid pallet::config, trait must be public";
+			return Err(syn::Error::new(item.span(), msg));
+		}
+
+		if item.ident != "Config" {
+			let msg = "Invalid pallet::config, trait must be named `Config`";
+			return Err(syn::Error::new(item.ident.span(), msg));
+		}
+
+		if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+			let msg = "Invalid pallet::config, expected no generics, instantiable pallets are not \
+				supported yet";
+			return Err(syn::Error::new(item.generics.span(), msg));
+		}
+
+		let frame_system = item.supertraits.iter()
+			.find_map(|bound| match bound {
+				syn::TypeParamBound::Trait(bound) if bound.path.segments.len() >= 2 &&
+					bound.path.segments.last().map_or(false, |s| s.ident == "Trait") =>
+				{
+					let mut frame_system = bound.path.clone();
+					frame_system.segments.pop();
+					frame_system.segments.pop_punct();
+					Some(frame_system)
+				},
+				_ => None,
+			})
+			.ok_or_else(|| {
+				let msg = "Invalid pallet::config, expected explicit `frame_system::Trait` as \
+					supertrait, e.g. `pub trait Config: frame_system::Trait`";
+				syn::Error::new(item.span(), msg)
+			})?;
+
+		let mut has_event_type = false;
+		let mut consts_metadata = vec![];
+		for trait_item in &mut item.items {
+			if let syn::TraitItem::Type(type_) = trait_item {
+				if type_.ident == "Event" {
+					has_event_type = true;
+				}
+			}
+
+			let type_attrs_const: Vec<TypeAttrConst> = helper::take_item_attrs(trait_item)?;
+
+			if type_attrs_const.len() > 1 {
+				let msg = "Invalid attribute in pallet::config, only one attribute is expected";
+				return Err(syn::Error::new(type_attrs_const[1].0, msg));
+			}
+
+			if type_attrs_const.len() == 1 {
+				match trait_item {
+					syn::TraitItem::Type(type_) => {
+						let constant = syn::parse2::<ConstMetadataDef>(quote::quote!(#type_))
+							.map_err(|e| {
+								let error_msg = "Invalid usage of `#[pallet::constant]`, syntax \
+									must be `type $SomeIdent: Get<$SomeType>;`";
+								let mut err = syn::Error::new(type_.span(), error_msg);
+								err.combine(e);
+								err
+							})?;
+
+						consts_metadata.push(constant);
+					},
+					_ => {
+						let msg = "Invalid pallet::constant in pallet::config, expected type trait \
+							item";
+						return Err(syn::Error::new(trait_item.span(), msg));
+					},
+				}
+			}
+		}
+
+		Ok(Self { index, has_event_type, frame_system, consts_metadata, attr_span })
+	}
+}
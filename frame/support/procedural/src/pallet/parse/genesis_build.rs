@@ -0,0 +1,61 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use syn::spanned::Spanned;
+
+/// Definition for pallet genesis build implementation.
+pub struct GenesisBuildDef {
+	/// The index of item in pallet module.
+	pub index: usize,
+	/// The span of the pallet::genesis_build attribute.
+	pub attr_span: proc_macro2::Span,
+}
+
+impl GenesisBuildDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			let msg = "Invalid pallet::genesis_build, expected item impl";
+			return Err(syn::Error::new(item.span(), msg));
+		};
+
+		let item_trait = &item.trait_.as_ref()
+			.ok_or_else(|| {
+				let msg = "Invalid pallet::genesis_build, expected impl<..> GenesisBuild<..> \
+					for GenesisConfig<..>";
+				syn::Error::new(item.span(), msg)
+			})?.1;
+
+		let is_genesis_build = item_trait.segments.len() == 1 &&
+			item_trait.segments[0].ident == "GenesisBuild";
+		if !is_genesis_build {
+			let msg = "Invalid pallet::genesis_build, expected trait `GenesisBuild<T>`, you can \
+				import from `frame_support::pallet_prelude`";
+			return Err(syn::Error::new(item_trait.span(), msg));
+		}
+
+		helper::check_impl_gen(&item.generics, item.impl_token.span())?;
+
+		Ok(Self { attr_span, index })
+	}
+}
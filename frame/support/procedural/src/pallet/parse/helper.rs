@@ -0,0 +1,201 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+/// A type with attributes that can be taken out.
+pub trait MutItemAttrs {
+	fn mut_item_attrs(&mut self) -> Option<&mut Vec<syn::Attribute>>;
+}
+
+impl MutItemAttrs for syn::Item {
+	fn mut_item_attrs(&mut self) -> Option<&mut Vec<syn::Attribute>> {
+		match self {
+			Self::Const(item) => Some(item.attrs.as_mut()),
+			Self::Enum(item) => Some(item.attrs.as_mut()),
+			Self::ExternCrate(item) => Some(item.attrs.as_mut()),
+			Self::Fn(item) => Some(item.attrs.as_mut()),
+			Self::ForeignMod(item) => Some(item.attrs.as_mut()),
+			Self::Impl(item) => Some(item.attrs.as_mut()),
+			Self::Macro(item) => Some(item.attrs.as_mut()),
+			Self::Macro2(item) => Some(item.attrs.as_mut()),
+			Self::Mod(item) => Some(item.attrs.as_mut()),
+			Self::Static(item) => Some(item.attrs.as_mut()),
+			Self::Struct(item) => Some(item.attrs.as_mut()),
+			Self::Trait(item) => Some(item.attrs.as_mut()),
+			Self::TraitAlias(item) => Some(item.attrs.as_mut()),
+			Self::Type(item) => Some(item.attrs.as_mut()),
+			Self::Union(item) => Some(item.attrs.as_mut()),
+			Self::Use(item) => Some(item.attrs.as_mut()),
+			_ => None,
+		}
+	}
+}
+
+impl MutItemAttrs for syn::TraitItem {
+	fn mut_item_attrs(&mut self) -> Option<&mut Vec<syn::Attribute>> {
+		match self {
+			Self::Const(item) => Some(item.attrs.as_mut()),
+			Self::Method(item) => Some(item.attrs.as_mut()),
+			Self::Type(item) => Some(item.attrs.as_mut()),
+			Self::Macro(item) => Some(item.attrs.as_mut()),
+			_ => None,
+		}
+	}
+}
+
+impl MutItemAttrs for Vec<syn::Attribute> {
+	fn mut_item_attrs(&mut self) -> Option<&mut Vec<syn::Attribute>> {
+		Some(self)
+	}
+}
+
+impl MutItemAttrs for syn::ItemMod {
+	fn mut_item_attrs(&mut self) -> Option<&mut Vec<syn::Attribute>> {
+		Some(&mut self.attrs)
+	}
+}
+
+/// Take the first pallet attribute (e.g. attribute like `#[pallet..]`) and decode it to `Attr`.
+pub fn take_first_item_attr<Attr>(item: &mut impl MutItemAttrs) -> syn::Result<Option<Attr>> where
+	Attr: syn::parse::Parse,
+{
+	let attrs = if let Some(attrs) = item.mut_item_attrs() {
+		attrs
+	} else {
+		return Ok(None)
+	};
+
+	if let Some(index) = attrs.iter()
+		.position(|attr|
+			attr.path.segments.first().map_or(false, |segment| segment.ident == "pallet")
+		)
+	{
+		let pallet_attr = attrs.remove(index);
+		Ok(Some(syn::parse2(pallet_attr.into_token_stream())?))
+	} else {
+		Ok(None)
+	}
+}
+
+/// Take all the pallet attributes (e.g. attribute like `#[pallet..]`) and decode them to `Attr`.
+pub fn take_item_attrs<Attr>(item: &mut impl MutItemAttrs) -> syn::Result<Vec<Attr>> where
+	Attr: syn::parse::Parse,
+{
+	let mut pallet_attrs = Vec::new();
+
+	while let Some(attr) = take_first_item_attr(item)? {
+		pallet_attrs.push(attr)
+	}
+
+	Ok(pallet_attrs)
+}
+
+/// Get all the `#[doc = ".."]` literals of the attributes.
+pub fn get_doc_literals(attrs: &[syn::Attribute]) -> Vec<syn::Lit> {
+	attrs.iter()
+		.filter_map(|attr| {
+			if let Ok(syn::Meta::NameValue(meta)) = attr.parse_meta() {
+				if meta.path.get_ident().map_or(false, |ident| ident == "doc") {
+					Some(meta.lit)
+				} else {
+					None
+				}
+			} else {
+				None
+			}
+		})
+		.collect()
+}
+
+/// Check the generics are exactly `<T>`, an optional `Config` bound on `T` being accepted.
+///
+/// Instantiable pallets are not supported yet, so `I` gives a dedicated error.
+pub fn check_type_def_gen(
+	gen: &syn::Generics,
+	span: proc_macro2::Span,
+) -> syn::Result<()> {
+	let expected = "expected `<T>` or `<T: Config>`, instantiable pallets are not supported yet";
+
+	if gen.params.len() != 1 || gen.where_clause.is_some() {
+		return Err(syn::Error::new(span, format!("Invalid generics: {}", expected)))
+	}
+
+	match gen.params.first() {
+		Some(syn::GenericParam::Type(param)) if param.ident == "T" => {
+			let bound_ok = param.bounds.is_empty() || (
+				param.bounds.len() == 1 &&
+				param.bounds.to_token_stream().to_string() == "Config"
+			);
+			if bound_ok && param.default.is_none() {
+				return Ok(())
+			}
+		},
+		_ => (),
+	}
+
+	Err(syn::Error::new(span, format!("Invalid generics: {}", expected)))
+}
+
+/// Check the generics of a type are either empty or `<T>`, returns `true` if generic.
+pub fn check_type_def_optional_gen(
+	gen: &syn::Generics,
+	span: proc_macro2::Span,
+) -> syn::Result<bool> {
+	if gen.params.is_empty() && gen.where_clause.is_none() {
+		Ok(false)
+	} else {
+		check_type_def_gen(gen, span).map(|()| true)
+	}
+}
+
+/// Check the type is exactly `Pallet<T>`.
+pub fn check_pallet_struct_usage(type_: &syn::Type) -> syn::Result<()> {
+	let expected = "Invalid type: expected `Pallet<T>`";
+	let is_pallet = match type_ {
+		syn::Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+			let segment = &path.path.segments[0];
+			segment.ident == "Pallet" && segment.arguments.to_token_stream().to_string() == "< T >"
+		},
+		_ => false,
+	};
+
+	if is_pallet {
+		Ok(())
+	} else {
+		Err(syn::Error::new(type_.span(), expected))
+	}
+}
+
+/// Check the impl generics are exactly `<T: Config>`.
+pub fn check_impl_gen(gen: &syn::Generics, span: proc_macro2::Span) -> syn::Result<()> {
+	let expected = "Invalid generics: expected `impl<T: Config>`, instantiable pallets are not \
+		supported yet";
+
+	let is_ok = gen.params.len() == 1 && match gen.params.first() {
+		Some(syn::GenericParam::Type(param)) => param.ident == "T" &&
+			param.bounds.to_token_stream().to_string() == "Config",
+		_ => false,
+	};
+
+	if is_ok {
+		Ok(())
+	} else {
+		Err(syn::Error::new(span, expected))
+	}
+}
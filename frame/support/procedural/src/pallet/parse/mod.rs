@@ -0,0 +1,242 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parse for pallet macro.
+//!
+//! Parse the module into `Def` struct through `Def::try_from` function.
+
+pub mod config;
+pub mod pallet_struct;
+pub mod hooks;
+pub mod call;
+pub mod error;
+pub mod event;
+pub mod storage;
+pub mod genesis_config;
+pub mod genesis_build;
+pub mod helper;
+
+use syn::spanned::Spanned;
+use frame_support_procedural_tools::generate_crate_access_2018;
+
+/// Parsed definition of a pallet.
+pub struct Def {
+	/// The module items.
+	/// (their order must not be modified because they are registered in individual definitions).
+	pub item: syn::ItemMod,
+	pub config: config::ConfigDef,
+	pub pallet_struct: pallet_struct::PalletStructDef,
+	pub hooks: hooks::HooksDef,
+	pub call: call::CallDef,
+	pub storages: Vec<storage::StorageDef>,
+	pub error: Option<error::ErrorDef>,
+	pub event: Option<event::EventDef>,
+	pub genesis_config: Option<genesis_config::GenesisConfigDef>,
+	pub genesis_build: Option<genesis_build::GenesisBuildDef>,
+	pub frame_support: proc_macro2::TokenStream,
+}
+
+impl Def {
+	pub fn try_from(mut item: syn::ItemMod) -> syn::Result<Self> {
+		let frame_support = generate_crate_access_2018()?;
+
+		let item_span = item.span();
+		let items = &mut item.content.as_mut()
+			.ok_or_else(|| {
+				let msg = "Invalid pallet definition, expected mod to be inlined.";
+				syn::Error::new(item_span, msg)
+			})?.1;
+
+		let mut config = None;
+		let mut pallet_struct = None;
+		let mut hooks = None;
+		let mut call = None;
+		let mut error = None;
+		let mut event = None;
+		let mut genesis_config = None;
+		let mut genesis_build = None;
+		let mut storages = vec![];
+
+		for (index, item) in items.iter_mut().enumerate() {
+			let pallet_attr: Option<PalletAttr> = helper::take_first_item_attr(item)?;
+
+			match pallet_attr {
+				Some(PalletAttr::Config(span)) if config.is_none() =>
+					config = Some(config::ConfigDef::try_from(span, index, item)?),
+				Some(PalletAttr::Pallet(span)) if pallet_struct.is_none() => {
+					let p = pallet_struct::PalletStructDef::try_from(span, index, item)?;
+					pallet_struct = Some(p);
+				},
+				Some(PalletAttr::Hooks(span)) if hooks.is_none() => {
+					let m = hooks::HooksDef::try_from(span, index, item)?;
+					hooks = Some(m);
+				},
+				Some(PalletAttr::Call(span)) if call.is_none() =>
+					call = Some(call::CallDef::try_from(span, index, item)?),
+				Some(PalletAttr::Error(span)) if error.is_none() =>
+					error = Some(error::ErrorDef::try_from(span, index, item)?),
+				Some(PalletAttr::Event(span)) if event.is_none() =>
+					event = Some(event::EventDef::try_from(span, index, item)?),
+				Some(PalletAttr::GenesisConfig(_)) if genesis_config.is_none() => {
+					let g = genesis_config::GenesisConfigDef::try_from(index, item)?;
+					genesis_config = Some(g);
+				},
+				Some(PalletAttr::GenesisBuild(span)) if genesis_build.is_none() => {
+					let g = genesis_build::GenesisBuildDef::try_from(span, index, item)?;
+					genesis_build = Some(g);
+				},
+				Some(PalletAttr::Storage(span)) =>
+					storages.push(storage::StorageDef::try_from(span, index, item)?),
+				Some(attr) => {
+					let msg = "Invalid duplicated attribute";
+					return Err(syn::Error::new(attr.span(), msg));
+				},
+				None => (),
+			}
+		}
+
+		if genesis_config.is_some() != genesis_build.is_some() {
+			let (msg, span) = match (&genesis_config, &genesis_build) {
+				(Some(g), None) => (
+					"`#[pallet::genesis_config]` requires `#[pallet::genesis_build]`",
+					g.genesis_config.span(),
+				),
+				(None, Some(g)) => (
+					"`#[pallet::genesis_build]` requires `#[pallet::genesis_config]`",
+					g.attr_span,
+				),
+				_ => unreachable!("Checked by the condition above; qed"),
+			};
+			return Err(syn::Error::new(span, msg));
+		}
+
+		let def = Def {
+			item,
+			config: config.ok_or_else(|| syn::Error::new(item_span, "Missing `#[pallet::config]`"))?,
+			pallet_struct: pallet_struct
+				.ok_or_else(|| syn::Error::new(item_span, "Missing `#[pallet::pallet]`"))?,
+			hooks: hooks
+				.ok_or_else(|| syn::Error::new(item_span, "Missing `#[pallet::hooks]`"))?,
+			call: call.ok_or_else(|| syn::Error::new(item_span, "Missing `#[pallet::call]"))?,
+			genesis_config,
+			genesis_build,
+			error,
+			event,
+			storages,
+			frame_support,
+		};
+
+		def.check_event_usage()?;
+
+		Ok(def)
+	}
+
+	/// Check that usage of the `Event` type in config is consistent with the pallet event.
+	fn check_event_usage(&self) -> syn::Result<()> {
+		match (self.config.has_event_type, self.event.is_some()) {
+			(true, false) => {
+				let msg = "Invalid usage of Event, `Config` contains associated type `Event`, \
+					but enum `Event` is not declared (i.e. no use of `#[pallet::event]`). \
+					Note that type `Event` in trait is reserved to work alongside pallet event.";
+				Err(syn::Error::new(proc_macro2::Span::call_site(), msg))
+			},
+			(false, true) => {
+				let msg = "Invalid usage of Event, `Config` contains no associated type \
+					`Event`, but enum `Event` is declared (in use of `#[pallet::event]`). \
+					An Event associated type must be declare on trait `Config`.";
+				Err(syn::Error::new(proc_macro2::Span::call_site(), msg))
+			},
+			_ => Ok(())
+		}
+	}
+}
+
+/// List of additional token to be used for parsing.
+mod keyword {
+	syn::custom_keyword!(pallet);
+	syn::custom_keyword!(config);
+	syn::custom_keyword!(hooks);
+	syn::custom_keyword!(call);
+	syn::custom_keyword!(error);
+	syn::custom_keyword!(event);
+	syn::custom_keyword!(storage);
+	syn::custom_keyword!(genesis_build);
+	syn::custom_keyword!(genesis_config);
+}
+
+/// Parse attributes for item in pallet module
+/// syntax must be `pallet::` (e.g. `#[pallet::config]`)
+enum PalletAttr {
+	Config(proc_macro2::Span),
+	Pallet(proc_macro2::Span),
+	Hooks(proc_macro2::Span),
+	Call(proc_macro2::Span),
+	Error(proc_macro2::Span),
+	Event(proc_macro2::Span),
+	GenesisConfig(proc_macro2::Span),
+	GenesisBuild(proc_macro2::Span),
+	Storage(proc_macro2::Span),
+}
+
+impl PalletAttr {
+	fn span(&self) -> proc_macro2::Span {
+		match self {
+			Self::Config(span) => *span,
+			Self::Pallet(span) => *span,
+			Self::Hooks(span) => *span,
+			Self::Call(span) => *span,
+			Self::Error(span) => *span,
+			Self::Event(span) => *span,
+			Self::GenesisConfig(span) => *span,
+			Self::GenesisBuild(span) => *span,
+			Self::Storage(span) => *span,
+		}
+	}
+}
+
+impl syn::parse::Parse for PalletAttr {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::config) {
+			Ok(PalletAttr::Config(content.parse::<keyword::config>()?.span()))
+		} else if lookahead.peek(keyword::pallet) {
+			Ok(PalletAttr::Pallet(content.parse::<keyword::pallet>()?.span()))
+		} else if lookahead.peek(keyword::hooks) {
+			Ok(PalletAttr::Hooks(content.parse::<keyword::hooks>()?.span()))
+		} else if lookahead.peek(keyword::call) {
+			Ok(PalletAttr::Call(content.parse::<keyword::call>()?.span()))
+		} else if lookahead.peek(keyword::error) {
+			Ok(PalletAttr::Error(content.parse::<keyword::error>()?.span()))
+		} else if lookahead.peek(keyword::event) {
+			Ok(PalletAttr::Event(content.parse::<keyword::event>()?.span()))
+		} else if lookahead.peek(keyword::genesis_config) {
+			Ok(PalletAttr::GenesisConfig(content.parse::<keyword::genesis_config>()?.span()))
+		} else if lookahead.peek(keyword::genesis_build) {
+			Ok(PalletAttr::GenesisBuild(content.parse::<keyword::genesis_build>()?.span()))
+		} else if lookahead.peek(keyword::storage) {
+			Ok(PalletAttr::Storage(content.parse::<keyword::storage>()?.span()))
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
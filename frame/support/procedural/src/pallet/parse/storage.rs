@@ -0,0 +1,264 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use syn::spanned::Spanned;
+
+/// List of additional token to be used for parsing.
+mod keyword {
+	syn::custom_keyword!(pallet);
+	syn::custom_keyword!(getter);
+}
+
+/// Parse for `#[pallet::getter(fn dummy)]`
+pub struct PalletStorageAttr {
+	getter: syn::Ident,
+}
+
+impl syn::parse::Parse for PalletStorageAttr {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+		content.parse::<keyword::getter>()?;
+
+		let generate_content;
+		syn::parenthesized!(generate_content in content);
+		generate_content.parse::<syn::Token![fn]>()?;
+		Ok(Self { getter: generate_content.parse::<syn::Ident>()? })
+	}
+}
+
+/// The value and key types used by storages. Needed to expand metadata.
+pub enum Metadata {
+	Value { value: syn::Type },
+	Map { value: syn::Type, key: syn::Type },
+	DoubleMap {
+		value: syn::Type,
+		key1: syn::Type,
+		key2: syn::Type
+	},
+	NMap {
+		value: syn::Type,
+		/// The key generator, i.e. `Key<Hasher, K>` or a tuple of those.
+		keygen: syn::Type,
+		/// The key types, in order.
+		keys: Vec<syn::Type>,
+	},
+}
+
+pub enum QueryKind {
+	OptionQuery,
+	ValueQuery,
+}
+
+/// Definition of a storage, storage is a storage type like
+/// `type MyStorage = StorageValue<MyStorageP, u32>`
+/// The keys and values types are parsed in order to get metadata
+pub struct StorageDef {
+	/// The index of storage item in pallet module.
+	pub index: usize,
+	/// Visibility of the storage type.
+	pub vis: syn::Visibility,
+	/// The type ident, to generate the StoragePrefix for.
+	pub ident: syn::Ident,
+	/// The keys and value metadata of the storage.
+	pub metadata: Metadata,
+	/// The doc associated to the storage.
+	pub docs: Vec<syn::Lit>,
+	/// The span of the pallet::storage attribute.
+	pub attr_span: proc_macro2::Span,
+	/// The `QueryKind` of the storage, `None` if it can't be determined from the syntax.
+	pub query_kind: Option<QueryKind>,
+	/// The ident of the getter to generate, if any.
+	pub getter: Option<syn::Ident>,
+}
+
+/// In `Foo<A, B, C>` retrieve the argument at given position, i.e. A is argument at position 0.
+fn retrieve_arg(
+	segment: &syn::PathSegment,
+	arg_pos: usize,
+) -> syn::Result<syn::Type> {
+	if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+		if arg_pos < args.args.len() {
+			if let syn::GenericArgument::Type(arg) = &args.args[arg_pos] {
+				Ok(arg.clone())
+			} else {
+				let msg = "Invalid pallet::storage, unexpected generic argument kind, expected a \
+					type";
+				Err(syn::Error::new(args.args[arg_pos].span(), msg))
+			}
+		} else {
+			let msg = format!("Invalid pallet::storage, unexpected number of generic argument, \
+				expected at least {} args, found {}", arg_pos + 1, args.args.len());
+			Err(syn::Error::new(args.span(), msg))
+		}
+	} else {
+		let msg = format!("Invalid pallet::storage, unexpected number of generic argument, \
+			expected at least {} args, found none", arg_pos + 1);
+		Err(syn::Error::new(segment.span(), msg))
+	}
+}
+
+/// Collect the key types of a `StorageNMap` key generator, i.e. the second generic argument of
+/// `Key<Hasher, K>` or of each element of a tuple of those.
+fn collect_keys(keygen: &syn::Type) -> syn::Result<Vec<syn::Type>> {
+	let single_key = |ty: &syn::Type| -> syn::Result<syn::Type> {
+		match ty {
+			syn::Type::Path(path) if path.path.segments.last().map_or(false, |s| {
+				s.ident == "Key" || s.ident == "NMapKey"
+			}) => retrieve_arg(path.path.segments.last().expect("Checked above; qed"), 1),
+			_ => {
+				let msg = "Invalid pallet::storage, expected `Key<Hasher, KeyType>` or \
+					`NMapKey<Hasher, KeyType>`";
+				Err(syn::Error::new(ty.span(), msg))
+			},
+		}
+	};
+
+	match keygen {
+		syn::Type::Tuple(tuple) => tuple.elems.iter().map(single_key).collect(),
+		syn::Type::Paren(paren) => Ok(vec![single_key(&paren.elem)?]),
+		ty => Ok(vec![single_key(ty)?]),
+	}
+}
+
+impl StorageDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+	) -> syn::Result<Self> {
+		let item = if let syn::Item::Type(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::storage, expect item type."));
+		};
+
+		let mut attrs: Vec<PalletStorageAttr> = helper::take_item_attrs(&mut item.attrs)?;
+		if attrs.len() > 1 {
+			let msg = "Invalid pallet::storage, multiple argument pallet::getter found";
+			return Err(syn::Error::new(attrs[1].getter.span(), msg));
+		}
+		let getter = attrs.pop().map(|attr| attr.getter);
+
+		let vis = item.vis.clone();
+		let ident = item.ident.clone();
+		let docs = helper::get_doc_literals(&item.attrs);
+
+		if item.generics.where_clause.is_some() {
+			let msg = "Invalid pallet::storage, where clause not supported on storage type";
+			return Err(syn::Error::new(item.generics.where_clause.span(), msg));
+		}
+		helper::check_type_def_gen(&item.generics, item.ident.span())?;
+
+		let typ = if let syn::Type::Path(typ) = &*item.ty {
+			typ
+		} else {
+			let msg = "Invalid pallet::storage, expected type path";
+			return Err(syn::Error::new(item.ty.span(), msg));
+		};
+
+		if typ.path.segments.len() != 1 {
+			let msg = "Invalid pallet::storage, expected type path with one segment";
+			return Err(syn::Error::new(item.ty.span(), msg));
+		}
+
+		let query_kind;
+		let metadata = match &*typ.path.segments[0].ident.to_string() {
+			"StorageValue" => {
+				query_kind = retrieve_arg(&typ.path.segments[0], 2);
+				Metadata::Value {
+					value: retrieve_arg(&typ.path.segments[0], 1)?,
+				}
+			}
+			"StorageMap" => {
+				query_kind = retrieve_arg(&typ.path.segments[0], 4);
+				Metadata::Map {
+					key: retrieve_arg(&typ.path.segments[0], 2)?,
+					value: retrieve_arg(&typ.path.segments[0], 3)?,
+				}
+			}
+			"StorageDoubleMap" => {
+				query_kind = retrieve_arg(&typ.path.segments[0], 6);
+				Metadata::DoubleMap {
+					key1: retrieve_arg(&typ.path.segments[0], 2)?,
+					key2: retrieve_arg(&typ.path.segments[0], 4)?,
+					value: retrieve_arg(&typ.path.segments[0], 5)?,
+				}
+			}
+			"StorageNMap" => {
+				query_kind = retrieve_arg(&typ.path.segments[0], 3);
+				let keygen = retrieve_arg(&typ.path.segments[0], 1)?;
+				Metadata::NMap {
+					keys: collect_keys(&keygen)?,
+					keygen,
+					value: retrieve_arg(&typ.path.segments[0], 2)?,
+				}
+			}
+			found => {
+				let msg = format!(
+					"Invalid pallet::storage, expected ident: `StorageValue` or \
+					`StorageMap` or `StorageDoubleMap` or `StorageNMap` in order to expand \
+					metadata, found `{}`",
+					found,
+				);
+				return Err(syn::Error::new(item.ty.span(), msg));
+			}
+		};
+
+		let prefix_arg = retrieve_arg(&typ.path.segments[0], 0)?;
+		if !matches!(prefix_arg, syn::Type::Infer(_)) {
+			let msg = "Invalid pallet::storage, for unnamed generic arguments the type \
+				first generic argument must be `_`, the final argument is automatically set by \
+				macro.";
+			return Err(syn::Error::new(prefix_arg.span(), msg));
+		}
+
+		let query_kind = query_kind
+			.map(|query_kind| match query_kind {
+				syn::Type::Path(path)
+					if path.path.segments.last().map_or(false, |s| s.ident == "OptionQuery")
+				=> Some(QueryKind::OptionQuery),
+				syn::Type::Path(path)
+					if path.path.segments.last().map_or(false, |s| s.ident == "ValueQuery")
+				=> Some(QueryKind::ValueQuery),
+				_ => None,
+			})
+			.unwrap_or(Some(QueryKind::OptionQuery)); // This value must match the default generic.
+
+		if query_kind.is_none() && getter.is_some() {
+			let msg = "Invalid pallet::storage, cannot generate getter because QueryKind is not \
+				identifiable. QueryKind must be `OptionQuery`, `ValueQuery`, or default one to be \
+				identifiable.";
+			return Err(syn::Error::new(getter.unwrap().span(), msg));
+		}
+
+		Ok(StorageDef {
+			attr_span,
+			index,
+			vis,
+			ident,
+			metadata,
+			docs,
+			getter,
+			query_kind,
+		})
+	}
+}
@@ -18,7 +18,7 @@
 //! Builder logic definition used to build genesis storage.
 
 use frame_support_procedural_tools::syn_ext as ext;
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, Span};
 use syn::spanned::Spanned;
 use quote::{quote, quote_spanned};
 use super::super::{DeclStorageDefExt, StorageLineTypeDef};
@@ -120,6 +120,24 @@ impl BuilderDef {
 							});
 						}}
 					},
+					StorageLineTypeDef::NMap(map) => {
+						let keys = &map.keys;
+						let key_arg = map.keys.iter().map(|key| quote!( &#key ));
+						let key_idents = (0..map.keys.len())
+							.map(|i| syn::Ident::new(&format!("k{}", i), Span::call_site()))
+							.collect::<Vec<_>>();
+						quote!{{
+							#data
+							let data: &#scrate::sp_std::vec::Vec<
+								(#( #keys, )* #value_type)
+							> = data;
+							data.iter().for_each(|( #( #key_idents, )* v )| {
+								<#storage_struct as #scrate::#storage_trait>::insert::<
+									( #( #key_arg, )* ), &#value_type
+								>(( #( #key_idents, )* ), v);
+							});
+						}}
+					},
 				});
 			}
 		}
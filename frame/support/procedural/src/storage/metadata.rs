@@ -15,7 +15,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Implementation of `storage_metadata` on module structure, used by construct_runtime.
+//! Implementation of `storage_metadata` and `storage_metadata_v14` on module structure, used by
+//! construct_runtime.
 
 use frame_support_procedural_tools::clean_type_string;
 use proc_macro2::TokenStream;
@@ -63,6 +64,88 @@ fn storage_line_metadata_type(scrate: &TokenStream, line: &StorageLineDefExt) ->
 				}
 			}
 		},
+		StorageLineTypeDef::NMap(map) => {
+			let keys = map.keys
+				.iter()
+				.map(|key| clean_type_string(&quote!(#key).to_string()))
+				.collect::<Vec<_>>();
+			let hashers = map.hashers
+				.iter()
+				.map(|hasher| hasher.into_metadata())
+				.collect::<Vec<_>>();
+			quote!{
+				#scrate::metadata::StorageEntryType::NMap {
+					keys: #scrate::metadata::DecodeDifferent::Encode(&[
+						#( #keys, )*
+					]),
+					hashers: #scrate::metadata::DecodeDifferent::Encode(&[
+						#( #scrate::metadata::#hashers, )*
+					]),
+					value: #scrate::metadata::DecodeDifferent::Encode(#value_type),
+				}
+			}
+		},
+	}
+}
+
+/// The entry type of a storage line in the metadata v14, collects the types to register.
+fn storage_line_metadata_type_v14(
+	scrate: &TokenStream,
+	line: &StorageLineDefExt,
+	types: &mut Vec<syn::Type>,
+) -> TokenStream {
+	let value_type = &line.value_type;
+	types.push(value_type.clone());
+	let meta_type = |ty: &syn::Type| quote!( #scrate::type_info::MetaType::new::<#ty>() );
+	let value = meta_type(value_type);
+	match &line.storage_type {
+		StorageLineTypeDef::Simple(_) => {
+			quote!( #scrate::metadata::StorageEntryTypeV14::Plain(#value) )
+		},
+		StorageLineTypeDef::Map(map) => {
+			let hasher = map.hasher.into_metadata();
+			types.push(map.key.clone());
+			let key = meta_type(&map.key);
+			quote!{
+				#scrate::metadata::StorageEntryTypeV14::Map {
+					hasher: #scrate::metadata::#hasher,
+					key: #key,
+					value: #value,
+				}
+			}
+		},
+		StorageLineTypeDef::DoubleMap(map) => {
+			let hasher1 = map.hasher1.into_metadata();
+			let hasher2 = map.hasher2.into_metadata();
+			types.push(map.key1.clone());
+			types.push(map.key2.clone());
+			let key1 = meta_type(&map.key1);
+			let key2 = meta_type(&map.key2);
+			quote!{
+				#scrate::metadata::StorageEntryTypeV14::DoubleMap {
+					hasher: #scrate::metadata::#hasher1,
+					key1: #key1,
+					key2: #key2,
+					value: #value,
+					key2_hasher: #scrate::metadata::#hasher2,
+				}
+			}
+		},
+		StorageLineTypeDef::NMap(map) => {
+			types.extend(map.keys.iter().cloned());
+			let keys = map.keys.iter().map(meta_type).collect::<Vec<_>>();
+			let hashers = map.hashers
+				.iter()
+				.map(|hasher| hasher.into_metadata())
+				.collect::<Vec<_>>();
+			quote!{
+				#scrate::metadata::StorageEntryTypeV14::NMap {
+					keys: #scrate::sp_std::vec![ #( #keys, )* ],
+					hashers: #scrate::sp_std::vec![ #( #scrate::metadata::#hashers, )* ],
+					value: #value,
+				}
+			}
+		},
 	}
 }
 
@@ -142,6 +225,8 @@ fn default_byte_getter(
 
 pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStream {
 	let mut entries = TokenStream::new();
+	let mut entries_v14 = TokenStream::new();
+	let mut types = Vec::new();
 	let mut default_byte_getter_struct_defs = TokenStream::new();
 
 	for line in def.storage_lines.iter() {
@@ -182,8 +267,22 @@ pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStre
 			},
 		};
 
+		let ty_v14 = storage_line_metadata_type_v14(scrate, line, &mut types);
+		let entry_v14 = quote! {
+			#scrate::metadata::StorageEntryMetadataV14 {
+				name: #str_name,
+				modifier: #modifier,
+				ty: #ty_v14,
+				default: #scrate::metadata::DefaultByte::default_byte(
+					&#default_byte_getter_struct_instance
+				),
+				documentation: #scrate::sp_std::vec![ #docs ],
+			},
+		};
+
 		default_byte_getter_struct_defs.extend(default_byte_getter_struct_def);
 		entries.extend(entry);
+		entries_v14.extend(entry_v14);
 	}
 
 	let prefix = if let Some(instance) = &def.module_instance {
@@ -201,6 +300,13 @@ pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStre
 		}
 	);
 
+	let store_metadata_v14 = quote!(
+		#scrate::metadata::StorageMetadataV14 {
+			prefix: #prefix,
+			entries: #scrate::sp_std::vec![ #entries_v14 ],
+		}
+	);
+
 	let module_struct = &def.module_struct;
 	let module_impl = &def.module_impl;
 	let where_clause = &def.where_clause;
@@ -213,6 +319,14 @@ pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStre
 			pub fn storage_metadata() -> #scrate::metadata::StorageMetadata {
 				#store_metadata
 			}
+
+			#[doc(hidden)]
+			pub fn storage_metadata_v14() -> #scrate::metadata::StorageMetadataV14
+			where
+				#( #types: #scrate::type_info::TypeInfo + 'static, )*
+			{
+				#store_metadata_v14
+			}
 		}
 	)
 }
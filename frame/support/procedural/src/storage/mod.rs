@@ -241,12 +241,17 @@ impl StorageLineDefExt {
 					|| ext::type_contains_ident(&map.key2, &def.module_runtime_generic)
 					|| ext::type_contains_ident(&map.value, &def.module_runtime_generic)
 			}
+			StorageLineTypeDef::NMap(map) => {
+				map.keys.iter().any(|key| ext::type_contains_ident(key, &def.module_runtime_generic))
+					|| ext::type_contains_ident(&map.value, &def.module_runtime_generic)
+			}
 		};
 
 		let query_type = match &storage_def.storage_type {
 			StorageLineTypeDef::Simple(value) => value.clone(),
 			StorageLineTypeDef::Map(map) => map.value.clone(),
 			StorageLineTypeDef::DoubleMap(map) => map.value.clone(),
+			StorageLineTypeDef::NMap(map) => map.value.clone(),
 		};
 		let is_option = ext::extract_type_option(&query_type).is_some();
 		let value_type = ext::extract_type_option(&query_type).unwrap_or_else(|| query_type.clone());
@@ -292,6 +297,13 @@ impl StorageLineDefExt {
 				let key2 = &map.key2;
 				quote!( StorageDoubleMap<#key1, #key2, #value_type> )
 			},
+			StorageLineTypeDef::NMap(map) => {
+				let hidden_crate_name = def.hidden_crate.as_ref().map(|i| i.to_string())
+					.unwrap_or_else(|| "decl_storage".to_string());
+				let scrate = generate_crate_access(&hidden_crate_name, "frame-support");
+				let keygen = map.to_keygen_struct(&scrate);
+				quote!( StorageNMap<#keygen, #value_type> )
+			},
 		};
 
 		let storage_trait = quote!( storage::#storage_trait_truncated );
@@ -329,6 +341,7 @@ impl StorageLineDefExt {
 pub enum StorageLineTypeDef {
 	Map(MapDef),
 	DoubleMap(Box<DoubleMapDef>),
+	NMap(NMapDef),
 	Simple(syn::Type),
 }
 
@@ -348,6 +361,31 @@ pub struct DoubleMapDef {
 	pub value: syn::Type,
 }
 
+pub struct NMapDef {
+	pub hashers: Vec<HasherKind>,
+	pub keys: Vec<syn::Type>,
+	/// This is the query value not the inner value used in storage trait implementation.
+	pub value: syn::Type,
+}
+
+impl NMapDef {
+	/// The key generator type, i.e. `Key<Hasher, Key>` for a single key, or a tuple of those
+	/// otherwise.
+	fn to_keygen_struct(&self, scrate: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+		let key_hasher = self.keys.iter().zip(&self.hashers).map(|(key, hasher)| {
+			let hasher = hasher.to_storage_hasher_struct();
+			quote!( #scrate::storage::types::Key<#scrate::#hasher, #key> )
+		}).collect::<Vec<_>>();
+
+		if key_hasher.len() == 1 {
+			let key_hasher = &key_hasher[0];
+			quote!( #key_hasher )
+		} else {
+			quote!( ( #( #key_hasher, )* ) )
+		}
+	}
+}
+
 pub struct ExtraGenesisLineDef {
 	attrs: Vec<syn::Attribute>,
 	name: syn::Ident,
@@ -415,9 +453,11 @@ pub fn decl_storage_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 			StorageValue as _,
 			StorageMap as _,
 			StorageDoubleMap as _,
+			StorageNMap as _,
 			StoragePrefixedMap as _,
 			IterableStorageMap as _,
 			IterableStorageDoubleMap as _,
+			IterableStorageNMap as _,
 		};
 
 		#scrate_decl
@@ -29,6 +29,7 @@ mod keyword {
 	syn::custom_keyword!(get);
 	syn::custom_keyword!(map);
 	syn::custom_keyword!(double_map);
+	syn::custom_keyword!(nmap);
 	syn::custom_keyword!(opaque_blake2_256);
 	syn::custom_keyword!(opaque_blake2_128);
 	syn::custom_keyword!(blake2_128_concat);
@@ -199,6 +200,7 @@ impl_parse_for_opt!(DeclStorageBuild => keyword::build);
 enum DeclStorageType {
 	Map(DeclStorageMap),
 	DoubleMap(Box<DeclStorageDoubleMap>),
+	NMap(DeclStorageNMap),
 	Simple(syn::Type),
 }
 
@@ -208,6 +210,8 @@ impl syn::parse::Parse for DeclStorageType {
 			Ok(Self::Map(input.parse()?))
 		} else if input.peek(keyword::double_map) {
 			Ok(Self::DoubleMap(input.parse()?))
+		} else if input.peek(keyword::nmap) {
+			Ok(Self::NMap(input.parse()?))
 		} else {
 			Ok(Self::Simple(input.parse()?))
 		}
@@ -235,6 +239,42 @@ struct DeclStorageDoubleMap {
 	pub value: syn::Type,
 }
 
+#[derive(ToTokens, Debug)]
+struct DeclStorageNMap {
+	pub map_keyword: keyword::nmap,
+	pub storage_keys: syn::punctuated::Punctuated<DeclStorageKey, Token![,]>,
+	pub ass_keyword: Token![=>],
+	pub value: syn::Type,
+}
+
+impl syn::parse::Parse for DeclStorageNMap {
+	fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
+		let map_keyword = input.parse()?;
+
+		let mut storage_keys = syn::punctuated::Punctuated::new();
+		loop {
+			storage_keys.push_value(input.parse()?);
+			if input.peek(Token![=>]) {
+				break
+			}
+			storage_keys.push_punct(input.parse()?);
+		}
+
+		Ok(Self {
+			map_keyword,
+			storage_keys,
+			ass_keyword: input.parse()?,
+			value: input.parse()?,
+		})
+	}
+}
+
+#[derive(Parse, ToTokens, Debug)]
+struct DeclStorageKey {
+	pub hasher: Opt<SetHasher>,
+	pub key: syn::Type,
+}
+
 #[derive(ToTokens, Debug)]
 enum Hasher {
 	Blake2_256(keyword::opaque_blake2_256),
@@ -495,6 +535,19 @@ fn parse_storage_line_defs(
 					value: map.value,
 				})
 			),
+			DeclStorageType::NMap(map) => {
+				let mut hashers = Vec::with_capacity(map.storage_keys.len());
+				let mut keys = Vec::with_capacity(map.storage_keys.len());
+				for storage_key in map.storage_keys.into_iter() {
+					hashers.push(storage_key.hasher.inner.ok_or_else(no_hasher_error)?.into());
+					keys.push(storage_key.key);
+				}
+				super::StorageLineTypeDef::NMap(super::NMapDef {
+					hashers,
+					keys,
+					value: map.value,
+				})
+			},
 			DeclStorageType::Simple(expr) => super::StorageLineTypeDef::Simple(expr),
 		};
 
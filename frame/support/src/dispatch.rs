@@ -26,7 +26,7 @@ pub use frame_metadata::{
 };
 pub use crate::weights::{
 	GetDispatchInfo, DispatchInfo, WeighData, ClassifyDispatch, TransactionPriority, Weight,
-	PaysFee, PostDispatchInfo, WithPostDispatchInfo,
+	PaysFee, PostDispatchInfo, WithPostDispatchInfo, ProofSize, WithProofSize, FeelessIf,
 };
 pub use sp_runtime::{traits::Dispatchable, DispatchError};
 pub use crate::traits::{CallMetadata, GetCallMetadata, GetCallName, UnfilteredDispatchable};
@@ -285,6 +285,15 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 /// As such, **calling other modules must be avoided**!! Using this function will implement the
 /// [`OnRuntimeUpgrade`](../sp_runtime/traits/trait.OnRuntimeUpgrade.html) trait.
 /// Function signature must be `fn on_runtime_upgrade() -> frame_support::weights::Weight`.
+/// After the function has been executed, the [`PalletVersion`](crate::traits::PalletVersion)
+/// of the module stored in state is set to the current crate version, whether the function is
+/// declared or not.
+///
+/// * `pre_upgrade` and `post_upgrade`: Check the state before and after `on_runtime_upgrade`,
+/// which must be declared before them. These checks are only compiled with the `try-runtime`
+/// feature of the module and never executed on-chain, see
+/// [`OnRuntimeUpgrade::pre_upgrade`](./traits/trait.OnRuntimeUpgrade.html#method.pre_upgrade).
+/// Function signature must be `fn pre_upgrade() -> Result<(), &'static str>`.
 ///
 /// * `on_initialize`: Executes at the beginning of a block. Using this function will
 /// implement the [`OnInitialize`](./trait.OnInitialize.html) trait.
@@ -298,6 +307,13 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 ///   * `fn on_finalize(n: BlockNumber) -> frame_support::weights::Weight` or
 ///   * `fn on_finalize() -> frame_support::weights::Weight`
 ///
+/// * `on_idle`: Executes at the end of a block, after all extrinsics and before `on_finalize`,
+/// with the weight left unused by the block. Using this function will implement the
+/// [`OnIdle`](./traits/trait.OnIdle.html) trait.
+/// Function signature can be either:
+///   * `fn on_idle(n: BlockNumber, remaining_weight: Weight) -> frame_support::weights::Weight` or
+///   * `fn on_idle(remaining_weight: Weight) -> frame_support::weights::Weight`
+///
 /// * `offchain_worker`: Executes at the beginning of a block and produces extrinsics for a future block
 ///   upon completion. Using this function will implement the
 ///   [`OffchainWorker`](./traits/trait.OffchainWorker.html) trait.
@@ -332,6 +348,7 @@ macro_rules! decl_module {
 			{}
 			{}
 			{}
+			{}
 			[]
 			$($t)*
 		);
@@ -367,6 +384,7 @@ macro_rules! decl_module {
 			{}
 			{}
 			{}
+			{}
 			[]
 			$($t)*
 		);
@@ -386,6 +404,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$vis:vis fn deposit_event() = default;
@@ -404,6 +423,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -421,6 +441,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$vis:vis fn deposit_event
@@ -446,6 +467,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$vis:vis fn deposit_event() = default;
@@ -467,6 +489,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_finalize( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -487,6 +510,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -505,6 +529,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -532,6 +557,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -556,6 +582,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_runtime_upgrade( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -581,6 +608,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -608,6 +636,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_runtime_upgrade( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -628,6 +657,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -648,6 +678,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_runtime_upgrade( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -655,6 +686,144 @@ macro_rules! decl_module {
 	) => {
 		compile_error!("`on_runtime_upgrade` can only be passed once as input.");
 	};
+	// Add pre_upgrade, next to on_runtime_upgrade
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ fn on_runtime_upgrade $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn pre_upgrade() -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		$crate::decl_module!(@normalize
+			$(#[$attr])*
+			pub struct $mod_type<$trait_instance: $trait_name$(<I>, I: $instantiable $(= $module_default_instance)?)?>
+			for enum $call_type where origin: $origin_type, system = $system
+			{ $( $other_where_bounds )* }
+			{ $( $deposit_event )* }
+			{ $( $on_initialize )* }
+			{
+				fn on_runtime_upgrade $( $on_runtime_upgrade )*
+				fn pre_upgrade() -> $return { $( $impl )* }
+			}
+			{ $( $on_finalize )* }
+			{ $( $offchain )* }
+			{ $( $constants )* }
+			{ $( $error_type )* }
+			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
+			[ $( $dispatchables )* ]
+			$($rest)*
+		);
+	};
+	// Compile error on `pre_upgrade` without an `on_runtime_upgrade` declared before.
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{}
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn pre_upgrade() -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		compile_error!(
+			"`pre_upgrade` checks the `on_runtime_upgrade` declared before it, which is missing."
+		);
+	};
+	// Add post_upgrade, next to on_runtime_upgrade
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ fn on_runtime_upgrade $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn post_upgrade() -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		$crate::decl_module!(@normalize
+			$(#[$attr])*
+			pub struct $mod_type<$trait_instance: $trait_name$(<I>, I: $instantiable $(= $module_default_instance)?)?>
+			for enum $call_type where origin: $origin_type, system = $system
+			{ $( $other_where_bounds )* }
+			{ $( $deposit_event )* }
+			{ $( $on_initialize )* }
+			{
+				fn on_runtime_upgrade $( $on_runtime_upgrade )*
+				fn post_upgrade() -> $return { $( $impl )* }
+			}
+			{ $( $on_finalize )* }
+			{ $( $offchain )* }
+			{ $( $constants )* }
+			{ $( $error_type )* }
+			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
+			[ $( $dispatchables )* ]
+			$($rest)*
+		);
+	};
+	// Compile error on `post_upgrade` without an `on_runtime_upgrade` declared before.
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{}
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn post_upgrade() -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		compile_error!(
+			"`post_upgrade` checks the `on_runtime_upgrade` declared before it, which is missing."
+		);
+	};
 	// Add integrity_test
 	(@normalize
 		$(#[$attr:meta])*
@@ -671,6 +840,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{}
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn integrity_test() { $( $impl:tt )* }
@@ -692,6 +862,7 @@ macro_rules! decl_module {
 				$(#[doc = $doc_attr])*
 				fn integrity_test() { $( $impl)* }
 			}
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -712,6 +883,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )+ }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn integrity_test() { $( $impl:tt )* }
@@ -719,6 +891,96 @@ macro_rules! decl_module {
 	) => {
 		compile_error!("`integrity_test` can only be passed once as input.");
 	};
+	// Add on_idle
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{}
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn on_idle( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		$crate::decl_module!(@normalize
+			$(#[$attr])*
+			pub struct $mod_type<$trait_instance: $trait_name$(<I>, I: $instantiable $(= $module_default_instance)?)?>
+			for enum $call_type where origin: $origin_type, system = $system
+			{ $( $other_where_bounds )* }
+			{ $( $deposit_event )* }
+			{ $( $on_initialize )* }
+			{ $( $on_runtime_upgrade )* }
+			{ $( $on_finalize )* }
+			{ $( $offchain )* }
+			{ $( $constants )* }
+			{ $( $error_type )* }
+			{ $( $integrity_test)* }
+			{
+				fn on_idle( $( $param_name : $param ),* ) -> $return { $( $impl )* }
+			}
+			[ $( $dispatchables )* ]
+			$($rest)*
+		);
+	};
+	// compile_error on_idle, without a returned weight.
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{}
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn on_idle( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		compile_error!("`on_idle` must return the weight it consumed.");
+	};
+	// Compile error on `on_idle` being added a second time.
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )+ }
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn on_idle( $( $param_name:ident : $param:ty ),* $(,)? ) $( -> $return:ty )? { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		compile_error!("`on_idle` can only be passed once as input.");
+	};
 	// compile_error on_initialize, without a given weight removed syntax.
 	(@normalize
 		$(#[$attr:meta])*
@@ -735,6 +997,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_initialize( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -760,6 +1023,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -787,6 +1051,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_initialize( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -807,6 +1072,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -827,6 +1093,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_initialize( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -850,6 +1117,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn offchain_worker( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -870,6 +1138,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -890,6 +1159,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn offchain_worker( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -914,6 +1184,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$( #[doc = $doc_attr:tt] )*
 		const $name:ident: $ty:ty = $value:expr;
@@ -939,6 +1210,7 @@ macro_rules! decl_module {
 			}
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -961,6 +1233,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		type Error = $error_type:ty;
@@ -981,6 +1254,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $error_type }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -1002,6 +1276,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $($t:tt)* ]
 		$($rest:tt)*
 	) => {
@@ -1020,6 +1295,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ &'static str }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $($t)* ]
 			$($rest)*
 		);
@@ -1042,6 +1318,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $error_type:ty }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -1066,6 +1343,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $error_type }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[
 				$( $dispatchables )*
 				$(#[doc = $doc_attr])*
@@ -1096,6 +1374,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[$fn_attr:meta])*
@@ -1124,6 +1403,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[weight = $weight:expr])?
@@ -1152,6 +1432,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[weight = $weight:expr])?
@@ -1180,6 +1461,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[weight = $weight:expr])?
@@ -1209,6 +1491,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 	) => {
 		$crate::decl_module!(@imp
@@ -1226,6 +1509,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 		);
 	};
 
@@ -1310,29 +1594,59 @@ macro_rules! decl_module {
 	};
 
 	(@impl_on_runtime_upgrade
+		{ $system:ident }
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
 		{ $( $other_where_bounds:tt )* }
 		fn on_runtime_upgrade() -> $return:ty { $( $impl:tt )* }
+		$( fn $check:ident() -> $check_return:ty { $( $check_impl:tt )* } )*
 	) => {
-		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+		impl<$trait_instance: $system::Trait + $trait_name$(<I>, $instance: $instantiable)?>
 			$crate::traits::OnRuntimeUpgrade
 			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
 		{
+			$(
+				#[cfg(feature = "try-runtime")]
+				fn $check() -> $check_return { $( $check_impl )* }
+			)*
+
 			fn on_runtime_upgrade() -> $return {
 				$crate::sp_tracing::enter_span!($crate::sp_tracing::trace_span!("on_runtime_upgrade"));
-				{ $( $impl )* }
+				let result: $return = (|| { $( $impl )* })();
+
+				let new_storage_version = $crate::crate_to_pallet_version!();
+				new_storage_version
+					.put_into_storage::<<$trait_instance as $system::Trait>::PalletInfo, Self>();
+
+				let additional_write = <
+					<$trait_instance as $system::Trait>::DbWeight as $crate::traits::Get<_>
+				>::get().writes(1);
+
+				result.saturating_add(additional_write)
 			}
 		}
 	};
 
 	(@impl_on_runtime_upgrade
+		{ $system:ident }
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
 		{ $( $other_where_bounds:tt )* }
 	) => {
-		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+		impl<$trait_instance: $system::Trait + $trait_name$(<I>, $instance: $instantiable)?>
 			$crate::traits::OnRuntimeUpgrade
 			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
-		{}
+		{
+			fn on_runtime_upgrade() -> $crate::dispatch::Weight {
+				$crate::sp_tracing::enter_span!($crate::sp_tracing::trace_span!("on_runtime_upgrade"));
+
+				let new_storage_version = $crate::crate_to_pallet_version!();
+				new_storage_version
+					.put_into_storage::<<$trait_instance as $system::Trait>::PalletInfo, Self>();
+
+				<
+					<$trait_instance as $system::Trait>::DbWeight as $crate::traits::Get<_>
+				>::get().writes(1)
+			}
+		}
 	};
 
 	(@impl_integrity_test
@@ -1410,6 +1724,56 @@ macro_rules! decl_module {
 		}
 	};
 
+	(@impl_on_idle
+		{ $system:ident }
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		{ $( $other_where_bounds:tt )* }
+		fn on_idle($remaining:ident : $remaining_ty:ty) -> $return:ty { $( $impl:tt )* }
+	) => {
+		impl<$trait_instance: $system::Trait + $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnIdle<<$trait_instance as $system::Trait>::BlockNumber>
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn on_idle(
+				_block_number_not_used: <$trait_instance as $system::Trait>::BlockNumber,
+				$remaining: $remaining_ty,
+			) -> $return {
+				$crate::sp_tracing::enter_span!($crate::sp_tracing::trace_span!("on_idle"));
+				{ $( $impl )* }
+			}
+		}
+	};
+
+	(@impl_on_idle
+		{ $system:ident }
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		{ $( $other_where_bounds:tt )* }
+		fn on_idle($param:ident : $param_ty:ty, $remaining:ident : $remaining_ty:ty) -> $return:ty {
+			$( $impl:tt )*
+		}
+	) => {
+		impl<$trait_instance: $system::Trait + $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnIdle<<$trait_instance as $system::Trait>::BlockNumber>
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn on_idle($param: $param_ty, $remaining: $remaining_ty) -> $return {
+				$crate::sp_tracing::enter_span!($crate::sp_tracing::trace_span!("on_idle"));
+				{ $( $impl )* }
+			}
+		}
+	};
+
+	(@impl_on_idle
+		{ $system:ident }
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		{ $( $other_where_bounds:tt )* }
+	) => {
+		impl<$trait_instance: $system::Trait + $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnIdle<<$trait_instance as $system::Trait>::BlockNumber>
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{}
+	};
+
 	(@impl_offchain
 		{ $system:ident }
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
@@ -1630,6 +1994,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $error_type:ty }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 	) => {
 		$crate::__check_reserved_fn_name! { $( $fn_name )* }
 
@@ -1652,6 +2017,7 @@ macro_rules! decl_module {
 
 		$crate::decl_module! {
 			@impl_on_runtime_upgrade
+			{ $system }
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
 			{ $( $other_where_bounds )* }
 			$( $on_runtime_upgrade )*
@@ -1665,6 +2031,14 @@ macro_rules! decl_module {
 			$( $on_finalize )*
 		}
 
+		$crate::decl_module! {
+			@impl_on_idle
+			{ $system }
+			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
+			{ $( $other_where_bounds )* }
+			$( $on_idle )*
+		}
+
 		$crate::decl_module! {
 			@impl_offchain
 			{ $system }
@@ -1687,6 +2061,35 @@ macro_rules! decl_module {
 			$( $integrity_test )*
 		}
 
+		// Implement `GetPalletVersion` for `Module`
+		impl<$trait_instance: $system::Trait + $trait_name $(<I>, $instance: $instantiable)?>
+			$crate::traits::GetPalletVersion for $mod_type<$trait_instance $(, $instance)?>
+			where $( $other_where_bounds )*
+		{
+			fn current_version() -> $crate::traits::PalletVersion {
+				$crate::crate_to_pallet_version!()
+			}
+
+			fn storage_version() -> Option<$crate::traits::PalletVersion> {
+				let key = $crate::traits::PalletVersion::storage_key::<
+						<$trait_instance as $system::Trait>::PalletInfo, Self
+					>().expect("Every active pallet has a name in the runtime; qed");
+
+				$crate::storage::unhashed::get(&key)
+			}
+		}
+
+		// Implement `OnGenesis` for `Module`
+		impl<$trait_instance: $system::Trait + $trait_name $(<I>, $instance: $instantiable)?>
+			$crate::traits::OnGenesis for $mod_type<$trait_instance $(, $instance)?>
+			where $( $other_where_bounds )*
+		{
+			fn on_genesis() {
+				$crate::crate_to_pallet_version!()
+					.put_into_storage::<<$trait_instance as $system::Trait>::PalletInfo, Self>();
+			}
+		}
+
 		/// Can also be called using [`Call`].
 		///
 		/// [`Call`]: enum.Call.html
@@ -1749,8 +2152,13 @@ macro_rules! decl_module {
 								&base_weight,
 								($( $param_name, )*)
 							);
+							let proof_size = <dyn $crate::dispatch::WeighData<( $( & $param, )* )>>::weigh_proof_size(
+								&base_weight,
+								($( $param_name, )*)
+							);
 							$crate::dispatch::DispatchInfo {
 								weight,
+								proof_size,
 								class,
 								pays_fee,
 							}
@@ -1896,6 +2304,10 @@ macro_rules! decl_module {
 			fn metadata() -> &'static [$crate::dispatch::ErrorMetadata] {
 				<$error_type as $crate::dispatch::ModuleErrorMetadata>::metadata()
 			}
+
+			fn error_type() -> Option<$crate::type_info::MetaType> {
+				<$error_type as $crate::dispatch::ModuleErrorMetadata>::error_type()
+			}
 		}
 	}
 }
@@ -2062,7 +2474,17 @@ macro_rules! __dispatch_impl_metadata {
 	(
 		$mod_type:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>
 		{ $( $other_where_bounds:tt )* }
-		$($rest:tt)*
+		$call_type:ident $origin_type:ty
+		{
+			$(
+				$(#[doc = $doc_attr:tt])*
+				fn $fn_name:ident($from:ident
+					$(
+						, $(#[$codec_attr:ident])* $param_name:ident : $param:ty
+					)*
+				);
+			)*
+		}
 	) => {
 		impl<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?> $mod_type<$trait_instance $(, $instance)?>
 			where $( $other_where_bounds )*
@@ -2070,7 +2492,60 @@ macro_rules! __dispatch_impl_metadata {
 			#[doc(hidden)]
 			#[allow(dead_code)]
 			pub fn call_functions() -> &'static [$crate::dispatch::FunctionMetadata] {
-				$crate::__call_to_functions!($($rest)*)
+				$crate::__call_to_functions!(
+					$call_type $origin_type
+					{
+						$(
+							$(#[doc = $doc_attr])*
+							fn $fn_name($from $(, $(#[$codec_attr])* $param_name : $param )*);
+						)*
+					}
+				)
+			}
+		}
+
+		// Every variant is a dispatchable, encoded with the index of the function. The
+		// `__PhantomItem` variant is skipped by the encoding.
+		impl<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?> $crate::type_info::TypeInfo
+			for $call_type<$trait_instance $(, $instance)?>
+		where
+			$trait_instance: 'static,
+			$( $( $param: $crate::type_info::TypeInfo + 'static, )* )*
+			$( $other_where_bounds )*
+		{
+			fn type_info() -> $crate::type_info::Type {
+				let functions: $crate::dispatch::Vec<(&'static str, $crate::dispatch::Vec<_>, &[&'static str])> =
+					$crate::sp_std::vec![
+						$(
+							(
+								stringify!($fn_name),
+								$crate::sp_std::vec![
+									$(
+										$crate::type_info::Field::named(
+											stringify!($param_name),
+											$crate::__function_to_metadata!(@meta_type
+												$(#[$codec_attr])* $param
+											),
+											stringify!($param),
+										)
+									),*
+								],
+								&[ $( $doc_attr ),* ] as &[&'static str],
+							)
+						),*
+					];
+
+				$crate::type_info::Type::new(
+					$crate::type_info::Path::new(stringify!($call_type), module_path!()),
+					$crate::type_info::TypeDef::Variant(
+						functions.into_iter()
+							.enumerate()
+							.map(|(index, (name, fields, docs))| {
+								$crate::type_info::Variant::new(name, fields, index as u8).docs(docs)
+							})
+							.collect()
+					),
+				)
 			}
 		}
 	}
@@ -2195,6 +2670,28 @@ macro_rules! __impl_module_constants_metadata {
 					),*
 				]
 			}
+
+			#[doc(hidden)]
+			#[allow(dead_code)]
+			pub fn module_constants_metadata_v14()
+				-> $crate::dispatch::Vec<$crate::metadata::ModuleConstantMetadataV14>
+			where
+				$( $type: $crate::type_info::TypeInfo + 'static, )*
+			{
+				$crate::sp_std::vec![
+					$(
+						$crate::metadata::ModuleConstantMetadataV14 {
+							name: stringify!($name),
+							ty: $crate::type_info::MetaType::new::<$type>(),
+							value: {
+								let value: $type = $value;
+								$crate::dispatch::Encode::encode(&value)
+							},
+							documentation: $crate::sp_std::vec![ $( $doc_attr ),* ],
+						}
+					),*
+				]
+			}
 		}
 	}
 }
@@ -2292,6 +2789,15 @@ macro_rules! __function_to_metadata {
 
 	(@stringify_expand_attr $param_name:ident : $param:ty) => { stringify!($param) };
 
+	(@meta_type #[compact] $param:ty) => {
+		$crate::type_info::MetaType::new::<$crate::codec::Compact<$param>>()
+	};
+
+	// Invalid attributes are reported by `@stringify_expand_attr`.
+	(@meta_type $(#[$codec_attr:ident])* $param:ty) => {
+		$crate::type_info::MetaType::new::<$param>()
+	};
+
 	(@stringify_expand_attr $(#[codec_attr:ident])* $param_name:ident : $param:ty) => {
 		compile_error!(concat!(
 			"Invalid attribute for parameter `", stringify!($param_name),
@@ -2315,6 +2821,9 @@ macro_rules! __check_reserved_fn_name {
 	(on_finalize $( $rest:ident )*) => {
 		$crate::__check_reserved_fn_name!(@compile_error on_finalize);
 	};
+	(on_idle $( $rest:ident )*) => {
+		$crate::__check_reserved_fn_name!(@compile_error on_idle);
+	};
 	(offchain_worker $( $rest:ident )*) => {
 		$crate::__check_reserved_fn_name!(@compile_error offchain_worker);
 	};
@@ -2357,7 +2866,7 @@ mod tests {
 	use crate::weights::{DispatchInfo, DispatchClass, Pays};
 	use crate::traits::{
 		CallMetadata, GetCallMetadata, GetCallName, OnInitialize, OnFinalize, OnRuntimeUpgrade,
-		IntegrityTest,
+		IntegrityTest, OnIdle,
 	};
 
 	pub trait Trait: system::Trait + Sized where Self::AccountId: From<u32> { }
@@ -2371,6 +2880,8 @@ mod tests {
 			type BaseCallFilter;
 			type Origin: crate::traits::OriginTrait<Call = Self::Call>;
 			type BlockNumber: Into<u32>;
+			type PalletInfo: crate::traits::PalletInfo;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
@@ -2418,7 +2929,10 @@ mod tests {
 
 			fn on_initialize(n: T::BlockNumber,) -> Weight { if n.into() == 42 { panic!("on_initialize") } 7 }
 			fn on_finalize(n: T::BlockNumber,) { if n.into() == 42 { panic!("on_finalize") } }
+			fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight { remaining_weight / 2 }
 			fn on_runtime_upgrade() -> Weight { 10 }
+			fn pre_upgrade() -> Result<(), &'static str> { Ok(()) }
+			fn post_upgrade() -> Result<(), &'static str> { Err("post_upgrade") }
 			fn offchain_worker() {}
 			/// Some doc
 			fn integrity_test() { panic!("integrity_test") }
@@ -2514,6 +3028,8 @@ mod tests {
 		type Call = OuterCall;
 		type BaseCallFilter = ();
 		type BlockNumber = u32;
+		type PalletInfo = ();
+		type DbWeight = ();
 	}
 
 	#[test]
@@ -2567,9 +3083,23 @@ mod tests {
 		<Module<TraitImpl> as OnFinalize<u32>>::on_finalize(42);
 	}
 
+	#[test]
+	fn on_idle_should_work() {
+		assert_eq!(<Module<TraitImpl> as OnIdle<u32>>::on_idle(10, 100), 50);
+	}
+
 	#[test]
 	fn on_runtime_upgrade_should_work() {
-		assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::on_runtime_upgrade(), 10);
+		sp_io::TestExternalities::default().execute_with(||
+			assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::on_runtime_upgrade(), 10)
+		);
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn pre_and_post_upgrade_should_work() {
+		assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::pre_upgrade(), Ok(()));
+		assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::post_upgrade(), Err("post_upgrade"));
 	}
 
 	#[test]
@@ -2577,12 +3107,22 @@ mod tests {
 		// operational.
 		assert_eq!(
 			Call::<TraitImpl>::operational().get_dispatch_info(),
-			DispatchInfo { weight: 5, class: DispatchClass::Operational, pays_fee: Pays::Yes },
+			DispatchInfo {
+				weight: 5,
+				proof_size: 0,
+				class: DispatchClass::Operational,
+				pays_fee: Pays::Yes,
+			},
 		);
 		// custom basic
 		assert_eq!(
 			Call::<TraitImpl>::aux_3().get_dispatch_info(),
-			DispatchInfo { weight: 3, class: DispatchClass::Normal, pays_fee: Pays::Yes },
+			DispatchInfo {
+				weight: 3,
+				proof_size: 0,
+				class: DispatchClass::Normal,
+				pays_fee: Pays::Yes,
+			},
 		);
 	}
 
@@ -167,6 +167,34 @@ macro_rules! decl_error {
 					),*
 				]
 			}
+
+			fn error_type() -> Option<$crate::type_info::MetaType> {
+				Some($crate::type_info::MetaType::new::<Self>())
+			}
+		}
+
+		// The variants are described with the index used in `DispatchError::Module`.
+		impl<$generic: $trait $(, $inst_generic: $instance)?> $crate::type_info::TypeInfo
+			for $error<$generic $(, $inst_generic)?>
+		{
+			fn type_info() -> $crate::type_info::Type {
+				let errors: &[(&'static str, &[&'static str])] = &[
+					$( (stringify!($name), &[ $( $doc_attr ),* ]) ),*
+				];
+
+				$crate::type_info::Type::new(
+					$crate::type_info::Path::new(stringify!($error), module_path!()),
+					$crate::type_info::TypeDef::Variant(
+						errors.iter()
+							.enumerate()
+							.map(|(index, &(name, docs))| {
+								$crate::type_info::Variant::new(name, Default::default(), index as u8)
+									.docs(docs)
+							})
+							.collect()
+					),
+				)
+			}
 		}
 	};
 	(@GENERATE_AS_U8
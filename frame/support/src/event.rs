@@ -145,6 +145,16 @@ macro_rules! decl_event {
 				$crate::__events_to_metadata!(; $( $events )* )
 			}
 		}
+		impl $crate::type_info::TypeInfo for Event {
+			fn type_info() -> $crate::type_info::Type {
+				$crate::type_info::Type::new(
+					$crate::type_info::Path::new("Event", module_path!()),
+					$crate::type_info::TypeDef::Variant(
+						$crate::__events_to_type_info!(0;; $( $events )* )
+					),
+				)
+			}
+		}
 	}
 }
 
@@ -293,12 +303,84 @@ macro_rules! __decl_generic_event {
 				$crate::__events_to_metadata!(; $( $events )* )
 			}
 		}
+		$crate::__impl_generic_event_type_info! {
+			{ $( $generic_param ),* $(, $instance)? }
+			$( $events )*
+		}
 	};
 	(@cannot_parse $ty:ty) => {
 		compile_error!(concat!("The type `", stringify!($ty), "` can't be parsed as an unnamed one, please name it `Name = ", stringify!($ty), "`"));
 	}
 }
 
+#[macro_export]
+#[doc(hidden)]
+// Implements `TypeInfo` for `RawEvent`, requiring it for every parameter of the events.
+macro_rules! __impl_generic_event_type_info {
+	(
+		{ $( $generic_param:ident ),* }
+		$(
+			$( #[doc = $doc_attr:tt] )*
+			$event:ident $( ( $( $param:path ),* $(,)? ) )*,
+		)*
+	) => {
+		impl<$( $generic_param ),*> $crate::type_info::TypeInfo for RawEvent<$( $generic_param ),*>
+		where
+			$( $generic_param: 'static, )*
+			$( $( $( $param: $crate::type_info::TypeInfo + 'static, )* )* )*
+		{
+			fn type_info() -> $crate::type_info::Type {
+				$crate::type_info::Type::new(
+					$crate::type_info::Path::new("RawEvent", module_path!()),
+					$crate::type_info::TypeDef::Variant(
+						$crate::__events_to_type_info!(0;; $(
+							$( #[doc = $doc_attr] )*
+							$event $( ( $( $param ),* ) )*,
+						)* )
+					),
+				)
+			}
+		}
+	}
+}
+
+#[macro_export]
+#[doc(hidden)]
+// The variants of an event enum, the index is the position of the event.
+macro_rules! __events_to_type_info {
+	(
+		$index:expr;
+		$( $variant:expr ),*;
+		$( #[doc = $doc_attr:tt] )*
+		$event:ident $( ( $( $param:path ),* $(,)? ) )*,
+		$( $rest:tt )*
+	) => {
+		$crate::__events_to_type_info!(
+			$index + 1;
+			$( $variant, )*
+			$crate::type_info::Variant::new(
+				stringify!($event),
+				$crate::sp_std::vec![
+					$( $(
+						$crate::type_info::Field::unnamed(
+							$crate::type_info::MetaType::new::<$param>(),
+							stringify!($param),
+						)
+					),* )*
+				],
+				$index,
+			).docs(&[ $( $doc_attr ),* ]);
+			$( $rest )*
+		)
+	};
+	(
+		$index:expr;
+		$( $variant:expr ),*;
+	) => {
+		$crate::sp_std::vec![ $( $variant ),* ]
+	}
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __events_to_metadata {
@@ -523,6 +605,15 @@ macro_rules! __impl_outer_event_json_metadata {
 				$( $module_name < $( $generic_params ),* > $( $instance )? ; )*
 			}
 		}
+
+		// The event types of the modules, used for the type information in the metadata.
+		$crate::paste::item! {
+			$(
+				#[allow(dead_code, non_camel_case_types)]
+				type [< __ModuleEvent_ $runtime _ $module_name $( _ $instance )? >] =
+					$module_name::Event ::< $( $generic_params ),* >;
+			)*
+		}
 	};
 
 	(@DECL_MODULE_EVENT_FNS
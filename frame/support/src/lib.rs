@@ -47,6 +47,11 @@ pub use sp_state_machine::BasicExternalities;
 pub use sp_io::{storage::root as storage_root, self};
 #[doc(hidden)]
 pub use sp_runtime::RuntimeDebug;
+/// Type information of the types used by modules, exposed in the runtime metadata.
+///
+/// Pallets derive [`TypeInfo`](type_info::TypeInfo) through this re-export for every type
+/// used in a call, event, storage item or constant.
+pub use sp_type_info as type_info;
 
 #[macro_use]
 pub mod debug;
@@ -74,8 +79,10 @@ pub use self::hash::{
 	StorageHasher, ReversibleStorageHasher
 };
 pub use self::storage::{
-	StorageValue, StorageMap, StorageDoubleMap, StoragePrefixedMap, IterableStorageMap,
-	IterableStorageDoubleMap, migration
+	StorageValue, StorageMap, StorageDoubleMap, StorageNMap, StoragePrefixedMap,
+	IterableStorageMap, IterableStorageDoubleMap, IterableStorageNMap, migration,
+	bounded_vec::BoundedVec, bounded_btree_set::BoundedBTreeSet,
+	bounded_btree_map::BoundedBTreeMap,
 };
 pub use self::dispatch::{Parameter, Callable, IsSubType};
 pub use sp_runtime::{self, ConsensusEngineId, print, traits::Printable};
@@ -269,6 +276,98 @@ macro_rules! ord_parameter_types {
 #[doc(inline)]
 pub use frame_support_procedural::{decl_storage, construct_runtime, transactional};
 
+/// Convert the current crate version into a [`PalletVersion`](crate::traits::PalletVersion).
+///
+/// It uses the `CARGO_PKG_VERSION_MAJOR`, `CARGO_PKG_VERSION_MINOR` and
+/// `CARGO_PKG_VERSION_PATCH` environment variables to fetch the crate version.
+/// This means that the [`PalletVersion`](crate::traits::PalletVersion)
+/// object will correspond to the version of the crate the macro is called in!
+///
+/// # Example
+///
+/// ```
+/// # use frame_support::{traits::PalletVersion, crate_to_pallet_version};
+/// const VERSION: PalletVersion = crate_to_pallet_version!();
+/// ```
+pub use frame_support_procedural::crate_to_pallet_version;
+
+/// Derive [`Clone`] but do not bound any generic.
+///
+/// This is useful for type generic over runtime:
+/// ```
+/// # use frame_support::CloneNoBound;
+/// trait Config {
+/// 	type C: Clone;
+/// }
+///
+/// // Foo implements [`Clone`] because `C` bounds [`Clone`].
+/// // Otherwise compilation will fail with an output telling `c` doesn't implement [`Clone`].
+/// #[derive(CloneNoBound)]
+/// struct Foo<T: Config> {
+/// 	c: T::C,
+/// }
+/// ```
+pub use frame_support_procedural::CloneNoBound;
+
+/// Derive [`Eq`] but do not bound any generic.
+///
+/// This is useful for type generic over runtime:
+/// ```
+/// # use frame_support::{EqNoBound, PartialEqNoBound};
+/// trait Config {
+/// 	type C: Eq;
+/// }
+///
+/// // Foo implements [`Eq`] because `C` bounds [`Eq`].
+/// // Otherwise compilation will fail with an output telling `c` doesn't implement [`Eq`].
+/// #[derive(PartialEqNoBound, EqNoBound)]
+/// struct Foo<T: Config> {
+/// 	c: T::C,
+/// }
+/// ```
+pub use frame_support_procedural::EqNoBound;
+
+/// Derive [`PartialEq`] but do not bound any generic.
+///
+/// This is useful for type generic over runtime:
+/// ```
+/// # use frame_support::PartialEqNoBound;
+/// trait Config {
+/// 	type C: PartialEq;
+/// }
+///
+/// // Foo implements [`PartialEq`] because `C` bounds [`PartialEq`].
+/// // Otherwise compilation will fail with an output telling `c` doesn't implement [`PartialEq`].
+/// #[derive(PartialEqNoBound)]
+/// struct Foo<T: Config> {
+/// 	c: T::C,
+/// }
+/// ```
+pub use frame_support_procedural::PartialEqNoBound;
+
+/// Derive [`Debug`] but do not bound any generic.
+///
+/// This is useful for type generic over runtime:
+/// ```
+/// # use frame_support::DebugNoBound;
+/// # use core::fmt::Debug;
+/// trait Config {
+/// 	type C: Debug;
+/// }
+///
+/// // Foo implements [`Debug`] because `C` bounds [`Debug`].
+/// // Otherwise compilation will fail with an output telling `c` doesn't implement [`Debug`].
+/// #[derive(DebugNoBound)]
+/// struct Foo<T: Config> {
+/// 	c: T::C,
+/// }
+/// ```
+pub use frame_support_procedural::DebugNoBound;
+
+/// Derive [`Debug`] but do not bound any generic, and strip the implementation when the `std`
+/// feature is disabled, as [`RuntimeDebug`] does.
+pub use frame_support_procedural::RuntimeDebugNoBound;
+
 /// Return Err of the expression: `return Err($expression);`.
 ///
 /// Used as `fail!(expression)`.
@@ -368,6 +467,165 @@ macro_rules! assert_ok {
 #[doc(hidden)]
 pub use serde::{Serialize, Deserialize};
 
+/// Prelude to be used alongside pallet macro, for ease of use.
+pub mod pallet_prelude {
+	pub use sp_std::marker::PhantomData;
+	pub use frame_support::{
+		EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, DebugNoBound, CloneNoBound, Twox256,
+		Twox128, Blake2_256, Blake2_128, Identity, Twox64Concat, Blake2_128Concat, debug, ensure,
+		RuntimeDebug, storage,
+		traits::{Get, Hooks, IsType, EnsureOrigin, GenesisBuild},
+		dispatch::{DispatchResultWithPostInfo, Parameter, DispatchError},
+		weights::{DispatchClass, Pays, Weight},
+		storage::types::{
+			StorageValue, StorageMap, StorageDoubleMap, StorageNMap, Key as NMapKey, ValueQuery,
+			OptionQuery,
+		},
+		storage::{
+			bounded_vec::{BoundedVec, TryAppendValue, TryAppendMap, TryAppendDoubleMap},
+			bounded_btree_set::BoundedBTreeSet,
+			bounded_btree_map::BoundedBTreeMap,
+		},
+	};
+	pub use codec::{Encode, Decode};
+	pub use sp_inherents::{InherentData, InherentIdentifier, ProvideInherent};
+	pub use sp_runtime::{
+		traits::{MaybeSerializeDeserialize, Member, ValidateUnsigned},
+		transaction_validity::{
+			TransactionSource, TransactionValidity, ValidTransaction, TransactionPriority,
+			TransactionTag, TransactionLongevity, TransactionValidityError, InvalidTransaction,
+			UnknownTransaction,
+		},
+	};
+}
+
+/// `pallet` attribute macro allows to define a pallet to be used in `construct_runtime!`.
+///
+/// It is an alternative to `decl_module!`, `decl_storage!`, `decl_event!` and `decl_error!`:
+/// the whole pallet is declared in a single module, and each part of the pallet is an item of
+/// this module annotated with a `#[pallet::*]` attribute. The macro then generates the same
+/// code as the `decl_*` macros, so the pallet can be used in `construct_runtime!` like any
+/// other pallet.
+///
+/// Instantiable pallets are not supported yet.
+///
+/// ## Attributes
+///
+/// * `#[pallet::config]` (mandatory): the configuration trait, it must be declared as
+///   `pub trait Config: frame_system::Trait`. Associated types annotated with
+///   `#[pallet::constant]` must be declared as `type Foo: Get<$Type>;` and are exposed in the
+///   module constants metadata. If the pallet declares an event, the trait must contain
+///   `type Event: From<Event<Self>> + IsType<<Self as frame_system::Trait>::Event>;`.
+/// * `#[pallet::pallet]` (mandatory): the pallet struct, declared as
+///   `pub struct Pallet<T>(PhantomData<T>);`. A type alias `Module<T>` is generated for
+///   `construct_runtime!`. `#[pallet::generate_store($vis trait Store)]` can be added to
+///   generate a `Store` trait giving access to storages as `<Self as Store>::Foo`.
+/// * `#[pallet::hooks]` (mandatory): `impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>`,
+///   used to implement `OnInitialize`, `OnIdle`, `OnFinalize`, `OnRuntimeUpgrade`,
+///   `OffchainWorker` and `IntegrityTest` on the pallet.
+/// * `#[pallet::call]` (mandatory): `impl<T: Config> Pallet<T> { .. }` containing the
+///   dispatchables. Each dispatchable must be public, have `origin: OriginFor<T>` as first
+///   argument, return `DispatchResultWithPostInfo` and be annotated with
+///   `#[pallet::weight($expr)]`. Arguments can be annotated with `#[pallet::compact]`.
+///   The `Call` enum is generated from those functions.
+/// * `#[pallet::error]` (optional): `pub enum Error<T> { .. }` with unit variants only.
+/// * `#[pallet::event]` (optional): `pub enum Event<T: Config> { .. }` or `pub enum Event`.
+///   `#[pallet::metadata(T::AccountId = "AccountId")]` gives the name of types in the event
+///   metadata, and `#[pallet::generate_deposit($vis fn deposit_event)]` generates a function
+///   on `Pallet` to deposit the event through `frame_system`.
+/// * `#[pallet::storage]` (optional): `$vis type $Name<T> = $StorageType<_, ..>;` where
+///   `$StorageType` is one of `StorageValue`, `StorageMap`, `StorageDoubleMap` or `StorageNMap`
+///   (see [`storage::types`]). The keys of a `StorageNMap` are given as
+///   `(NMapKey<$Hasher1, $Key1>, NMapKey<$Hasher2, $Key2>, ..)`. The first generic `_` is
+///   replaced by a generated prefix: the name of the pallet in `construct_runtime!` and the name
+///   of the type. `#[pallet::getter(fn $name)]` generates a getter on `Pallet`.
+/// * `#[pallet::genesis_config]` (optional): `pub struct GenesisConfig` or
+///   `pub struct GenesisConfig<T: Config>`, it must implement `Default`.
+/// * `#[pallet::genesis_build]` (mandatory if there is a genesis config):
+///   `impl<T: Config> GenesisBuild<T> for GenesisConfig { .. }`, the `build` function is
+///   executed with the genesis storage.
+///
+/// Other items of the module are left untouched, so origins, inherents and unsigned
+/// validation are implemented manually on `Pallet`.
+///
+/// ## Example
+///
+/// ```ignore
+/// pub use pallet::*;
+///
+/// #[frame_support::pallet]
+/// pub mod pallet {
+/// 	use frame_support::pallet_prelude::*;
+/// 	use frame_system::pallet_prelude::*;
+///
+/// 	#[pallet::config]
+/// 	pub trait Config: frame_system::Trait {
+/// 		#[pallet::constant]
+/// 		type MyGetParam: Get<u32>;
+/// 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Trait>::Event>;
+/// 	}
+///
+/// 	#[pallet::pallet]
+/// 	#[pallet::generate_store(pub(super) trait Store)]
+/// 	pub struct Pallet<T>(PhantomData<T>);
+///
+/// 	#[pallet::hooks]
+/// 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+///
+/// 	#[pallet::call]
+/// 	impl<T: Config> Pallet<T> {
+/// 		/// Doc comment put in metadata
+/// 		#[pallet::weight(0)]
+/// 		pub fn toto(
+/// 			origin: OriginFor<T>,
+/// 			#[pallet::compact] _foo: u32,
+/// 		) -> DispatchResultWithPostInfo {
+/// 			let who = ensure_signed(origin)?;
+/// 			<MyStorage<T>>::put(3);
+/// 			Self::deposit_event(Event::Something(who));
+/// 			Ok(().into())
+/// 		}
+/// 	}
+///
+/// 	#[pallet::error]
+/// 	pub enum Error<T> {
+/// 		/// Doc comment put into metadata
+/// 		InsufficientProposersBalance,
+/// 	}
+///
+/// 	#[pallet::event]
+/// 	#[pallet::metadata(T::AccountId = "AccountId")]
+/// 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+/// 	pub enum Event<T: Config> {
+/// 		/// Doc comment put in metadata
+/// 		Something(T::AccountId),
+/// 	}
+///
+/// 	#[pallet::storage]
+/// 	#[pallet::getter(fn my_storage)]
+/// 	pub(super) type MyStorage<T> = StorageValue<_, u32, ValueQuery>;
+///
+/// 	#[pallet::genesis_config]
+/// 	#[derive(Default)]
+/// 	pub struct GenesisConfig {
+/// 		_myfield: u32,
+/// 	}
+///
+/// 	#[pallet::genesis_build]
+/// 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+/// 		fn build(&self) {}
+/// 	}
+/// }
+/// ```
+///
+/// ## Upgrade guidelines
+///
+/// A pallet declared with `decl_*` macros can be moved to this macro without changing its
+/// storage layout: storages keep the same prefix as long as the pallet keeps the same name in
+/// `construct_runtime!` as the one given to `decl_storage!`. Calls, events and errors keep their
+/// encoding as long as the declaration order of dispatchables and variants is kept.
+pub use frame_support_procedural::pallet;
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -414,6 +672,12 @@ mod tests {
 				=> Option<T::BlockNumber>;
 			pub AppendableDM:
 				double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::BlockNumber => Vec<u32>;
+			pub DataNM get(fn data_nm) build(|_| vec![(15u32, 16u32, 17u32, 42u64)]):
+				nmap hasher(twox_64_concat) u32, hasher(blake2_128_concat) u32, hasher(identity) u32
+				=> u64;
+			pub GenericDataNM:
+				nmap hasher(blake2_128_concat) T::BlockNumber, hasher(twox_64_concat) T::BlockNumber
+				=> Option<T::BlockNumber>;
 		}
 	}
 
@@ -676,6 +940,75 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn n_map_basic_insert_remove_remove_prefix_should_work() {
+		new_test_ext().execute_with(|| {
+			type NMap = DataNM;
+			// initialized during genesis
+			assert_eq!(NMap::get((15u32, 16u32, 17u32)), 42u64);
+			assert_eq!(Module::<Test>::data_nm((15u32, 16u32, 17u32)), 42u64);
+
+			// get / insert / take
+			let (key1, key2, key3) = (17u32, 18u32, 19u32);
+			assert_eq!(NMap::get((key1, key2, key3)), 0u64);
+			NMap::insert((&key1, &key2, &key3), &4u64);
+			assert_eq!(NMap::get((key1, key2, key3)), 4u64);
+			assert_eq!(NMap::take((key1, key2, key3)), 4u64);
+			assert_eq!(NMap::get((key1, key2, key3)), 0u64);
+
+			// mutate
+			NMap::mutate((key1, key2, key3), |val| {
+				*val = 15;
+			});
+			assert_eq!(NMap::get((key1, key2, key3)), 15u64);
+
+			// remove
+			NMap::remove((key1, key2, key3));
+			assert_eq!(NMap::get((key1, key2, key3)), 0u64);
+
+			// remove prefix
+			NMap::insert((key1, key2, key3), 4u64);
+			NMap::insert((key1, key2, key3 + 1), 4u64);
+			NMap::insert((key1, key2 + 1, key3), 4u64);
+			NMap::insert((key1 + 1, key2, key3), 4u64);
+			NMap::remove_prefix((key1, key2));
+			assert_eq!(NMap::get((key1, key2, key3)), 0u64);
+			assert_eq!(NMap::get((key1, key2, key3 + 1)), 0u64);
+			assert_eq!(NMap::get((key1, key2 + 1, key3)), 4u64);
+			assert_eq!(NMap::get((key1 + 1, key2, key3)), 4u64);
+			NMap::remove_prefix((key1,));
+			assert_eq!(NMap::get((key1, key2 + 1, key3)), 0u64);
+			assert_eq!(NMap::get((key1 + 1, key2, key3)), 4u64);
+		});
+	}
+
+	#[test]
+	fn n_map_iteration_should_work() {
+		new_test_ext().execute_with(|| {
+			type NMap = GenericDataNM<Test>;
+
+			NMap::insert((1, 1), 11);
+			NMap::insert((1, 2), 12);
+			NMap::insert((2, 1), 21);
+
+			assert_eq!(
+				NMap::iter().collect::<Vec<_>>().sorted(),
+				vec![((1, 1), 11), ((1, 2), 12), ((2, 1), 21)],
+			);
+			assert_eq!(
+				NMap::iter_prefix((1,)).collect::<Vec<_>>().sorted(),
+				vec![((1,), 11), ((2,), 12)],
+			);
+			assert_eq!(NMap::iter_prefix_values((2,)).collect::<Vec<_>>(), vec![21]);
+
+			assert_eq!(
+				NMap::drain_prefix((1,)).collect::<Vec<_>>().sorted(),
+				vec![((1,), 11), ((2,), 12)],
+			);
+			assert_eq!(NMap::iter().collect::<Vec<_>>(), vec![((2, 1), 21)]);
+		});
+	}
+
 	const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
 		prefix: DecodeDifferent::Encode("Test"),
 		entries: DecodeDifferent::Encode(
@@ -796,6 +1129,39 @@ mod tests {
 					),
 					documentation: DecodeDifferent::Encode(&[]),
 				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Encode("DataNM"),
+					modifier: StorageEntryModifier::Default,
+					ty: StorageEntryType::NMap {
+						keys: DecodeDifferent::Encode(&["u32", "u32", "u32"]),
+						hashers: DecodeDifferent::Encode(&[
+							StorageHasher::Twox64Concat,
+							StorageHasher::Blake2_128Concat,
+							StorageHasher::Identity,
+						]),
+						value: DecodeDifferent::Encode("u64"),
+					},
+					default: DecodeDifferent::Encode(
+						DefaultByteGetter(&__GetByteStructDataNM(PhantomData::<Test>))
+					),
+					documentation: DecodeDifferent::Encode(&[]),
+				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Encode("GenericDataNM"),
+					modifier: StorageEntryModifier::Optional,
+					ty: StorageEntryType::NMap {
+						keys: DecodeDifferent::Encode(&["T::BlockNumber", "T::BlockNumber"]),
+						hashers: DecodeDifferent::Encode(&[
+							StorageHasher::Blake2_128Concat,
+							StorageHasher::Twox64Concat,
+						]),
+						value: DecodeDifferent::Encode("T::BlockNumber"),
+					},
+					default: DecodeDifferent::Encode(
+						DefaultByteGetter(&__GetByteStructGenericDataNM(PhantomData::<Test>))
+					),
+					documentation: DecodeDifferent::Encode(&[]),
+				},
 			]
 		),
 	};
@@ -19,11 +19,16 @@ pub use frame_metadata::{
 	DecodeDifferent, FnEncode, RuntimeMetadata, ModuleMetadata, RuntimeMetadataLastVersion,
 	DefaultByteGetter, RuntimeMetadataPrefixed, StorageEntryMetadata, StorageMetadata,
 	StorageEntryType, StorageEntryModifier, DefaultByte, StorageHasher, ModuleErrorMetadata,
-	ExtrinsicMetadata,
+	ExtrinsicMetadata, RuntimeMetadataV14, ModuleMetadataV14, StorageMetadataV14,
+	StorageEntryMetadataV14, StorageEntryTypeV14, ModuleConstantMetadataV14,
 };
 
 /// Implements the metadata support for the given runtime and all its modules.
 ///
+/// The metadata describes every type used by the calls, events, storage items, constants and
+/// errors of the modules in its type registry. Thus all of them must implement
+/// [`TypeInfo`](crate::type_info::TypeInfo).
+///
 /// Example:
 /// ```
 ///# mod module0 {
@@ -66,20 +71,20 @@ macro_rules! impl_runtime_metadata {
 	) => {
 		impl $runtime {
 			pub fn metadata() -> $crate::metadata::RuntimeMetadataPrefixed {
-				$crate::metadata::RuntimeMetadataLastVersion {
-						modules: $crate::__runtime_modules_to_metadata!($runtime;; $( $rest )*),
-						extrinsic: $crate::metadata::ExtrinsicMetadata {
-							version: <$ext as $crate::sp_runtime::traits::ExtrinsicMetadata>::VERSION,
-							signed_extensions: <
-									<
-										$ext as $crate::sp_runtime::traits::ExtrinsicMetadata
-									>::SignedExtensions as $crate::sp_runtime::traits::SignedExtension
-								>::identifier()
-									.into_iter()
-									.map($crate::metadata::DecodeDifferent::Encode)
-									.collect(),
-						},
-				}.into()
+				$crate::metadata::RuntimeMetadataLastVersion::new(
+					$crate::__runtime_modules_to_metadata!($runtime;; $( $rest )*),
+					$crate::metadata::ExtrinsicMetadata {
+						version: <$ext as $crate::sp_runtime::traits::ExtrinsicMetadata>::VERSION,
+						signed_extensions: <
+								<
+									$ext as $crate::sp_runtime::traits::ExtrinsicMetadata
+								>::SignedExtensions as $crate::sp_runtime::traits::SignedExtension
+							>::identifier()
+								.into_iter()
+								.map($crate::metadata::DecodeDifferent::Encode)
+								.collect(),
+					},
+				).into()
 			}
 		}
 	}
@@ -99,8 +104,8 @@ macro_rules! __runtime_modules_to_metadata {
 	) => {
 		$crate::__runtime_modules_to_metadata!(
 			$runtime;
-			$( $metadata, )* $crate::metadata::ModuleMetadata {
-				name: $crate::metadata::DecodeDifferent::Encode(stringify!($name)),
+			$( $metadata, )* $crate::metadata::ModuleMetadataV14 {
+				name: stringify!($name),
 				index: $index,
 				storage: $crate::__runtime_modules_to_metadata_calls_storage!(
 					$mod, $module $( <$instance> )?, $runtime, $(with $kw)*
@@ -111,16 +116,9 @@ macro_rules! __runtime_modules_to_metadata {
 				event: $crate::__runtime_modules_to_metadata_calls_event!(
 					$mod, $module $( <$instance> )?, $runtime, $(with $kw)*
 				),
-				constants: $crate::metadata::DecodeDifferent::Encode(
-					$crate::metadata::FnEncode(
-						$mod::$module::<$runtime $(, $mod::$instance )?>::module_constants_metadata
-					)
-				),
-				errors: $crate::metadata::DecodeDifferent::Encode(
-					$crate::metadata::FnEncode(
-						<$mod::$module::<$runtime $(, $mod::$instance )?> as $crate::metadata::ModuleErrorMetadata>::metadata
-					)
-				)
+				constants: $mod::$module::<$runtime $(, $mod::$instance )?>::module_constants_metadata_v14(),
+				error: <$mod::$module::<$runtime $(, $mod::$instance )?> as $crate::metadata::ModuleErrorMetadata>
+					::error_type(),
 			};
 			$( $rest )*
 		)
@@ -129,7 +127,7 @@ macro_rules! __runtime_modules_to_metadata {
 		$runtime:ident;
 		$( $metadata:expr ),*;
 	) => {
-		$crate::metadata::DecodeDifferent::Encode(&[ $( $metadata ),* ])
+		$crate::sp_std::vec![ $( $metadata ),* ]
 	};
 }
 
@@ -143,11 +141,9 @@ macro_rules! __runtime_modules_to_metadata_calls_call {
 		with Call
 		$(with $kws:ident)*
 	) => {
-		Some($crate::metadata::DecodeDifferent::Encode(
-			$crate::metadata::FnEncode(
-				$mod::$module::<$runtime $(, $mod::$instance )?>::call_functions
-			)
-		))
+		Some($crate::type_info::MetaType::new::<
+			$crate::dispatch::CallableCallFor<$mod::$module<$runtime $(, $mod::$instance )?>, $runtime>
+		>())
 	};
 	(
 		$mod: ident,
@@ -180,13 +176,9 @@ macro_rules! __runtime_modules_to_metadata_calls_event {
 		with Event
 		$(with $kws:ident)*
 	) => {
-		Some($crate::metadata::DecodeDifferent::Encode(
-			$crate::metadata::FnEncode(
-				$crate::paste::expr!{
-					$runtime:: [< __module_events_ $mod $(_ $instance)?>]
-				}
-			)
-		))
+		Some($crate::paste::expr!{
+			$crate::type_info::MetaType::new::<[< __ModuleEvent_ $runtime _ $mod $(_ $instance)? >]>()
+		})
 	};
 	(
 		$mod: ident,
@@ -216,11 +208,7 @@ macro_rules! __runtime_modules_to_metadata_calls_storage {
 		with Storage
 		$(with $kws:ident)*
 	) => {
-		Some($crate::metadata::DecodeDifferent::Encode(
-			$crate::metadata::FnEncode(
-				$mod::$module::<$runtime $(, $mod::$instance )?>::storage_metadata
-			)
-		))
+		Some($mod::$module::<$runtime $(, $mod::$instance )?>::storage_metadata_v14())
 	};
 	(
 		$mod: ident,
@@ -248,11 +236,8 @@ macro_rules! __runtime_modules_to_metadata_calls_storage {
 #[allow(dead_code)]
 mod tests {
 	use super::*;
-	use frame_metadata::{
-		EventMetadata, StorageEntryModifier, StorageEntryType, FunctionMetadata, StorageEntryMetadata,
-		ModuleMetadata, RuntimeMetadataPrefixed, DefaultByte, ModuleConstantMetadata, DefaultByteGetter,
-		ErrorMetadata, ExtrinsicMetadata,
-	};
+	use frame_metadata::META_RESERVED;
+	use crate::type_info::{PortableRegistry, PortableForm, TypeDef, TypeDefPrimitive};
 	use codec::{Encode, Decode};
 	use crate::traits::Get;
 	use sp_runtime::transaction_validity::TransactionValidityError;
@@ -458,163 +443,97 @@ mod tests {
 			event_module2::Module as Module2 { index 2 } with Event Storage Call,
 	);
 
-	struct ConstantBlockNumberByteGetter;
-	impl DefaultByte for ConstantBlockNumberByteGetter {
-		fn default_byte(&self) -> Vec<u8> {
-			100u32.encode()
-		}
-	}
-
-	struct ConstantGetTypeByteGetter;
-	impl DefaultByte for ConstantGetTypeByteGetter {
-		fn default_byte(&self) -> Vec<u8> {
-			SystemValue::get().encode()
-		}
+	fn resolve(registry: &PortableRegistry, id: u32) -> &TypeDef<PortableForm> {
+		registry.resolve(id).expect("Every type of the metadata is registered").type_def()
 	}
 
-	struct ConstantAssociatedConstByteGetter;
-	impl DefaultByte for ConstantAssociatedConstByteGetter {
-		fn default_byte(&self) -> Vec<u8> {
-			<TestRuntime as system::Trait>::ASSOCIATED_CONST.encode()
+	fn variant_names(registry: &PortableRegistry, id: u32) -> Vec<(String, u8)> {
+		match resolve(registry, id) {
+			TypeDef::Variant(variants) => variants.iter()
+				.map(|v| (v.name().clone(), v.index()))
+				.collect(),
+			def => panic!("Expected a variant, got {:?}", def),
 		}
 	}
 
 	#[test]
 	fn runtime_metadata() {
-		let expected_metadata: RuntimeMetadataLastVersion = RuntimeMetadataLastVersion {
-			modules: DecodeDifferent::Encode(&[
-				ModuleMetadata {
-					name: DecodeDifferent::Encode("System"),
-					index: 0,
-					storage: None,
-					calls: None,
-					event: Some(DecodeDifferent::Encode(
-						FnEncode(||&[
-							EventMetadata {
-								name: DecodeDifferent::Encode("SystemEvent"),
-								arguments: DecodeDifferent::Encode(&[]),
-								documentation: DecodeDifferent::Encode(&[])
-							}
-						])
-					)),
-					constants: DecodeDifferent::Encode(
-						FnEncode(|| &[
-							ModuleConstantMetadata {
-								name: DecodeDifferent::Encode("BlockNumber"),
-								ty: DecodeDifferent::Encode("T::BlockNumber"),
-								value: DecodeDifferent::Encode(
-									DefaultByteGetter(&ConstantBlockNumberByteGetter)
-								),
-								documentation: DecodeDifferent::Encode(&[" Hi, I am a comment."]),
-							},
-							ModuleConstantMetadata {
-								name: DecodeDifferent::Encode("GetType"),
-								ty: DecodeDifferent::Encode("T::AccountId"),
-								value: DecodeDifferent::Encode(
-									DefaultByteGetter(&ConstantGetTypeByteGetter)
-								),
-								documentation: DecodeDifferent::Encode(&[]),
-							},
-							ModuleConstantMetadata {
-								name: DecodeDifferent::Encode("ASSOCIATED_CONST"),
-								ty: DecodeDifferent::Encode("u64"),
-								value: DecodeDifferent::Encode(
-									DefaultByteGetter(&ConstantAssociatedConstByteGetter)
-								),
-								documentation: DecodeDifferent::Encode(&[]),
-							}
-						])
-					),
-					errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				},
-				ModuleMetadata {
-					name: DecodeDifferent::Encode("Module"),
-					index: 1,
-					storage: None,
-					calls: Some(
-						DecodeDifferent::Encode(FnEncode(|| &[
-							FunctionMetadata {
-								name: DecodeDifferent::Encode("aux_0"),
-								arguments: DecodeDifferent::Encode(&[]),
-								documentation: DecodeDifferent::Encode(&[]),
-							}
-						]))),
-					event: Some(DecodeDifferent::Encode(
-						FnEncode(||&[
-							EventMetadata {
-								name: DecodeDifferent::Encode("TestEvent"),
-								arguments: DecodeDifferent::Encode(&["Balance"]),
-								documentation: DecodeDifferent::Encode(&[" Hi, I am a comment."])
-							}
-						])
-					)),
-					constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-					errors: DecodeDifferent::Encode(FnEncode(|| &[
-						ErrorMetadata {
-							name: DecodeDifferent::Encode("UserInputError"),
-							documentation: DecodeDifferent::Encode(&[" Some user input error"]),
-						},
-						ErrorMetadata {
-							name: DecodeDifferent::Encode("BadThingHappened"),
-							documentation: DecodeDifferent::Encode(&[
-								" Something bad happened",
-								" this could be due to many reasons",
-							]),
-						},
-					])),
-				},
-				ModuleMetadata {
-					name: DecodeDifferent::Encode("Module2"),
-					index: 2,
-					storage: Some(DecodeDifferent::Encode(
-						FnEncode(|| StorageMetadata {
-							prefix: DecodeDifferent::Encode("TestStorage"),
-							entries: DecodeDifferent::Encode(
-								&[
-									StorageEntryMetadata {
-										name: DecodeDifferent::Encode("StorageMethod"),
-										modifier: StorageEntryModifier::Optional,
-										ty: StorageEntryType::Plain(DecodeDifferent::Encode("u32")),
-										default: DecodeDifferent::Encode(
-											DefaultByteGetter(
-												&event_module2::__GetByteStructStorageMethod(
-													std::marker::PhantomData::<TestRuntime>
-												)
-											)
-										),
-										documentation: DecodeDifferent::Encode(&[]),
-									}
-								]
-							)
-						}),
-					)),
-					calls: Some(DecodeDifferent::Encode(FnEncode(|| &[]))),
-					event: Some(DecodeDifferent::Encode(
-						FnEncode(||&[
-							EventMetadata {
-								name: DecodeDifferent::Encode("TestEvent"),
-								arguments: DecodeDifferent::Encode(&["Balance"]),
-								documentation: DecodeDifferent::Encode(&[])
-							}
-						])
-					)),
-					constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-					errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				},
-			]),
-			extrinsic: ExtrinsicMetadata {
+		let metadata_encoded = TestRuntime::metadata().encode();
+		let metadata = match RuntimeMetadataPrefixed::decode(&mut &metadata_encoded[..]).unwrap() {
+			RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(metadata)) => metadata,
+			metadata => panic!("Unexpected metadata {:?}", metadata),
+		};
+		let types = &metadata.types;
+
+		assert_eq!(
+			metadata.extrinsic,
+			ExtrinsicMetadata {
 				version: 1,
 				signed_extensions: vec![
-					DecodeDifferent::Encode("testextension"),
-					DecodeDifferent::Encode("testextension2"),
+					DecodeDifferent::Decoded("testextension".into()),
+					DecodeDifferent::Decoded("testextension2".into()),
 				],
-			}
-		};
+			},
+		);
+		assert_eq!(
+			metadata.modules.iter().map(|m| (m.name.as_str(), m.index)).collect::<Vec<_>>(),
+			vec![("System", 0), ("Module", 1), ("Module2", 2)],
+		);
 
-		let metadata_encoded = TestRuntime::metadata().encode();
-		let metadata_decoded = RuntimeMetadataPrefixed::decode(&mut &metadata_encoded[..]);
-		let expected_metadata: RuntimeMetadataPrefixed = expected_metadata.into();
+		// System
+		let system = &metadata.modules[0];
+		assert!(system.storage.is_none());
+		assert!(system.calls.is_none());
+		assert!(system.error.is_none());
+		assert_eq!(variant_names(types, system.event.unwrap()), vec![("SystemEvent".into(), 0)]);
+
+		let constants = system.constants.iter()
+			.map(|c| (c.name.as_str(), resolve(types, c.ty).clone(), c.value.clone()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			constants,
+			vec![
+				("BlockNumber", TypeDef::Primitive(TypeDefPrimitive::U32), 100u32.encode()),
+				("GetType", TypeDef::Primitive(TypeDefPrimitive::U32), 600u32.encode()),
+				("ASSOCIATED_CONST", TypeDef::Primitive(TypeDefPrimitive::U64), 500u64.encode()),
+			],
+		);
+		assert_eq!(system.constants[0].documentation, vec![" Hi, I am a comment.".to_string()]);
+
+		// Module
+		let module = &metadata.modules[1];
+		assert_eq!(variant_names(types, module.calls.unwrap()), vec![("aux_0".into(), 0)]);
+		assert_eq!(
+			variant_names(types, module.error.unwrap()),
+			vec![("UserInputError".into(), 0), ("BadThingHappened".into(), 1)],
+		);
+		match resolve(types, module.event.unwrap()) {
+			TypeDef::Variant(variants) => {
+				assert_eq!(variants[0].name(), "TestEvent");
+				assert_eq!(variants[0].docs(), &[" Hi, I am a comment.".to_string()]);
+				assert_eq!(variants[0].fields()[0].type_name().map(|s| s.as_str()), Some("Balance"));
+				assert_eq!(
+					resolve(types, *variants[0].fields()[0].ty()),
+					&TypeDef::Primitive(TypeDefPrimitive::U32),
+				);
+			},
+			def => panic!("Expected a variant, got {:?}", def),
+		}
 
-		pretty_assertions::assert_eq!(expected_metadata, metadata_decoded.unwrap());
+		// Module2
+		let module2 = &metadata.modules[2];
+		assert_eq!(variant_names(types, module2.calls.unwrap()), vec![]);
+		assert!(module2.error.is_none());
+		let storage = module2.storage.as_ref().unwrap();
+		assert_eq!(storage.prefix, "TestStorage");
+		assert_eq!(storage.entries.len(), 1);
+		assert_eq!(storage.entries[0].name, "StorageMethod");
+		assert_eq!(storage.entries[0].modifier, StorageEntryModifier::Optional);
+		assert_eq!(storage.entries[0].default, Option::<u32>::None.encode());
+		match &storage.entries[0].ty {
+			StorageEntryTypeV14::Plain(ty) =>
+				assert_eq!(resolve(types, *ty), &TypeDef::Primitive(TypeDefPrimitive::U32)),
+			ty => panic!("Expected a plain storage entry, got {:?}", ty),
+		}
 	}
 }
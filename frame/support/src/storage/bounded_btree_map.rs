@@ -0,0 +1,307 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits, types and structs to support a bounded `BTreeMap`.
+
+use sp_std::{
+	borrow::Borrow, collections::btree_map::BTreeMap, convert::TryFrom, fmt, marker::PhantomData,
+};
+use codec::{Encode, EncodeLike, Decode};
+use crate::type_info::{TypeInfo, Type};
+use crate::{traits::Get, storage::StorageDecodeLength};
+
+/// A bounded map based on a B-Tree.
+///
+/// B-Trees represent a fundamental compromise between cache-efficiency and actually minimizing
+/// the amount of work performed in a search. See [`BTreeMap`] for more details.
+///
+/// Unlike a standard `BTreeMap`, there is a static, enforced upper limit to the number of items
+/// in the map. All internal operations ensure this bound is respected, and decoding a value that
+/// is longer than the bound fails.
+pub struct BoundedBTreeMap<K, V, S>(BTreeMap<K, V>, PhantomData<S>);
+
+impl<K: Encode, V: Encode, S> Encode for BoundedBTreeMap<K, V, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<K: Encode, V: Encode, S> EncodeLike for BoundedBTreeMap<K, V, S> {}
+
+impl<K: TypeInfo + 'static, V: TypeInfo + 'static, S: 'static> TypeInfo for BoundedBTreeMap<K, V, S> {
+	fn type_info() -> Type {
+		<BTreeMap<K, V> as TypeInfo>::type_info()
+	}
+}
+
+impl<K: Decode + Ord, V: Decode, S: Get<u32>> Decode for BoundedBTreeMap<K, V, S> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let inner = BTreeMap::<K, V>::decode(input)?;
+		if inner.len() > S::get() as usize {
+			return Err("BoundedBTreeMap exceeds its limit".into());
+		}
+		Ok(Self(inner, PhantomData))
+	}
+}
+
+impl<K: Ord, V, S> BoundedBTreeMap<K, V, S> {
+	/// Create a new `BoundedBTreeMap`.
+	///
+	/// Does not allocate.
+	pub fn new() -> Self {
+		Self(BTreeMap::new(), PhantomData)
+	}
+
+	/// Consume self, and return the inner `BTreeMap`.
+	///
+	/// This is useful when a mutating API of the inner type is desired, and closure-based mutation
+	/// such as provided by [`try_mutate`][Self::try_mutate] is inconvenient.
+	pub fn into_inner(self) -> BTreeMap<K, V> {
+		self.0
+	}
+
+	/// Return a mutable reference to the value corresponding to the key.
+	///
+	/// The key may be any borrowed form of the map's key type, but the ordering on the borrowed
+	/// form _must_ match the ordering on the key type.
+	pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+	where
+		K: Borrow<Q>,
+		Q: Ord + ?Sized,
+	{
+		self.0.get_mut(key)
+	}
+
+	/// Remove a key from the map, returning the value at the key if the key was previously in the
+	/// map.
+	///
+	/// The key may be any borrowed form of the map's key type, but the ordering on the borrowed
+	/// form _must_ match the ordering on the key type.
+	pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+	where
+		K: Borrow<Q>,
+		Q: Ord + ?Sized,
+	{
+		self.0.remove(key)
+	}
+
+	/// Retains only the elements specified by the predicate.
+	pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+		// `BTreeMap` has no `retain` in the toolchain we support, rebuild the map instead.
+		let inner = sp_std::mem::replace(&mut self.0, BTreeMap::new());
+		self.0 = inner.into_iter().filter_map(|(k, mut v)| {
+			if f(&k, &mut v) { Some((k, v)) } else { None }
+		}).collect();
+	}
+}
+
+impl<K: Ord, V, S: Get<u32>> BoundedBTreeMap<K, V, S> {
+	/// Get the bound of the type in `usize`.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Consumes self and mutates self via the given `mutate` function.
+	///
+	/// If the outcome of mutation is within bounds, `Some(Self)` is returned. Else, `None` is
+	/// returned.
+	///
+	/// This is essentially a *consuming* shorthand [`Self::into_inner`] -> `...` ->
+	/// [`Self::try_from`].
+	pub fn try_mutate(mut self, mut mutate: impl FnMut(&mut BTreeMap<K, V>)) -> Option<Self> {
+		mutate(&mut self.0);
+		if self.0.len() <= Self::bound() {
+			Some(self)
+		} else {
+			None
+		}
+	}
+
+	/// Exactly the same semantics as [`BTreeMap::insert`], but returns an `Err` (and is a noop)
+	/// if the new length of the map exceeds `S`.
+	///
+	/// Overwriting the value of a key which is already in the map never fails.
+	pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, ()> {
+		if self.0.contains_key(&key) || self.len() < Self::bound() {
+			Ok(self.0.insert(key, value))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<K: Ord, V, S> Default for BoundedBTreeMap<K, V, S> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K: Clone, V: Clone, S> Clone for BoundedBTreeMap<K, V, S> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone(), PhantomData)
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S: Get<u32>> fmt::Debug for BoundedBTreeMap<K, V, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("BoundedBTreeMap").field(&self.0).field(&S::get()).finish()
+	}
+}
+
+impl<K: PartialEq, V: PartialEq, S> PartialEq for BoundedBTreeMap<K, V, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<K: PartialEq, V: PartialEq, S> PartialEq<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	fn eq(&self, other: &BTreeMap<K, V>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<K: Eq, V: Eq, S> Eq for BoundedBTreeMap<K, V, S> {}
+
+impl<K: Ord, V, S: Get<u32>> TryFrom<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	type Error = ();
+
+	fn try_from(value: BTreeMap<K, V>) -> Result<Self, Self::Error> {
+		if value.len() <= Self::bound() {
+			Ok(Self(value, PhantomData))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<K, V, S> AsRef<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	fn as_ref(&self) -> &BTreeMap<K, V> {
+		&self.0
+	}
+}
+
+// Allows for all immutable operations of `BTreeMap<K, V>` on `BoundedBTreeMap<K, V>`.
+impl<K, V, S> sp_std::ops::Deref for BoundedBTreeMap<K, V, S> {
+	type Target = BTreeMap<K, V>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<K, V, S> IntoIterator for BoundedBTreeMap<K, V, S> {
+	type Item = (K, V);
+	type IntoIter = sp_std::collections::btree_map::IntoIter<K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<K, V, S> codec::DecodeLength for BoundedBTreeMap<K, V, S> {
+	fn len(self_encoded: &[u8]) -> Result<usize, codec::Error> {
+		// `BoundedBTreeMap<K, V, _>` is stored as a `BTreeMap<K, V>`, which is encoded as a
+		// sequence of pairs with a `Compact` length prefix, exactly like a `Vec<(K, V)>`.
+		<sp_std::vec::Vec<(K, V)> as codec::DecodeLength>::len(self_encoded)
+	}
+}
+
+impl<K, V, S> StorageDecodeLength for BoundedBTreeMap<K, V, S> {}
+
+#[cfg(test)]
+pub mod test {
+	use super::*;
+	use sp_io::TestExternalities;
+	use sp_std::convert::TryInto;
+	use crate::{Twox128, traits::StorageInstance};
+	use crate::storage::types::{self, OptionQuery};
+
+	crate::parameter_types! {
+		pub const Seven: u32 = 7;
+		pub const Four: u32 = 4;
+	}
+
+	struct Prefix;
+	impl StorageInstance for Prefix {
+		fn pallet_prefix() -> &'static str { "test" }
+		const STORAGE_PREFIX: &'static str = "foo";
+	}
+
+	type Foo = types::StorageValue<Prefix, BoundedBTreeMap<u32, (), Seven>, OptionQuery>;
+	type FooMap = types::StorageMap<
+		Prefix, Twox128, u32, BoundedBTreeMap<u32, (), Seven>, OptionQuery
+	>;
+
+	fn map_from_keys<K: Ord + Clone>(keys: &[K]) -> BTreeMap<K, ()> {
+		keys.iter().cloned().map(|k| (k, ())).collect()
+	}
+
+	fn boundedmap_from_keys<K: Ord + Clone, S: Get<u32>>(keys: &[K]) -> BoundedBTreeMap<K, (), S> {
+		map_from_keys(keys).try_into().unwrap()
+	}
+
+	#[test]
+	fn decode_len_works() {
+		TestExternalities::default().execute_with(|| {
+			let bounded = boundedmap_from_keys::<u32, Seven>(&[1, 2, 3]);
+			Foo::put(bounded);
+			assert_eq!(Foo::decode_len().unwrap(), 3);
+		});
+
+		TestExternalities::default().execute_with(|| {
+			let bounded = boundedmap_from_keys::<u32, Seven>(&[1, 2, 3]);
+			FooMap::insert(1, bounded);
+			assert_eq!(FooMap::decode_len(1).unwrap(), 3);
+			assert!(FooMap::decode_len(0).is_none());
+			assert!(FooMap::decode_len(2).is_none());
+		});
+	}
+
+	#[test]
+	fn try_insert_works() {
+		let mut bounded = boundedmap_from_keys::<u32, Four>(&[1, 2, 3]);
+		assert_eq!(bounded.try_insert(0, ()), Ok(None));
+		assert_eq!(*bounded, map_from_keys(&[1, 0, 2, 3]));
+
+		// overwriting an existing key does not hit the bound
+		assert_eq!(bounded.try_insert(2, ()), Ok(Some(())));
+		assert!(bounded.try_insert(9, ()).is_err());
+		assert_eq!(*bounded, map_from_keys(&[1, 0, 2, 3]));
+	}
+
+	#[test]
+	fn try_mutate_works() {
+		let bounded = boundedmap_from_keys::<u32, Seven>(&[1, 2, 3, 4, 5, 6]);
+		let bounded = bounded.try_mutate(|v| { v.insert(7, ()); }).unwrap();
+		assert_eq!(bounded.len(), 7);
+		assert!(bounded.try_mutate(|v| { v.insert(8, ()); }).is_none());
+	}
+
+	#[test]
+	fn decode_fails_past_bound() {
+		let v = map_from_keys(&[1u32, 2, 3, 4, 5]);
+		assert!(BoundedBTreeMap::<u32, (), Four>::decode(&mut &v.encode()[..]).is_err());
+
+		let v = map_from_keys(&[1u32, 2, 3, 4]);
+		let bounded = BoundedBTreeMap::<u32, (), Four>::decode(&mut &v.encode()[..]).unwrap();
+		assert_eq!(bounded, v);
+	}
+}
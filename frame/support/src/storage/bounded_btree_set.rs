@@ -0,0 +1,305 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits, types and structs to support a bounded `BTreeSet`.
+
+use sp_std::{
+	borrow::Borrow, collections::btree_set::BTreeSet, convert::TryFrom, fmt, marker::PhantomData,
+};
+use codec::{Encode, EncodeLike, Decode};
+use crate::type_info::{TypeInfo, Type};
+use crate::{traits::Get, storage::StorageDecodeLength};
+
+/// A bounded set based on a B-Tree.
+///
+/// B-Trees represent a fundamental compromise between cache-efficiency and actually minimizing
+/// the amount of work performed in a search. See [`BTreeSet`] for more details.
+///
+/// Unlike a standard `BTreeSet`, there is a static, enforced upper limit to the number of items
+/// in the set. All internal operations ensure this bound is respected, and decoding a value that
+/// is longer than the bound fails.
+pub struct BoundedBTreeSet<T, S>(BTreeSet<T>, PhantomData<S>);
+
+impl<T: Encode, S> Encode for BoundedBTreeSet<T, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<T: Encode, S> EncodeLike for BoundedBTreeSet<T, S> {}
+
+impl<T: TypeInfo + 'static, S: 'static> TypeInfo for BoundedBTreeSet<T, S> {
+	fn type_info() -> Type {
+		<BTreeSet<T> as TypeInfo>::type_info()
+	}
+}
+
+impl<T: Decode + Ord, S: Get<u32>> Decode for BoundedBTreeSet<T, S> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let inner = BTreeSet::<T>::decode(input)?;
+		if inner.len() > S::get() as usize {
+			return Err("BoundedBTreeSet exceeds its limit".into());
+		}
+		Ok(Self(inner, PhantomData))
+	}
+}
+
+impl<T: Ord, S> BoundedBTreeSet<T, S> {
+	/// Create a new `BoundedBTreeSet`.
+	///
+	/// Does not allocate.
+	pub fn new() -> Self {
+		Self(BTreeSet::new(), PhantomData)
+	}
+
+	/// Consume self, and return the inner `BTreeSet`.
+	///
+	/// This is useful when a mutating API of the inner type is desired, and closure-based mutation
+	/// such as provided by [`try_mutate`][Self::try_mutate] is inconvenient.
+	pub fn into_inner(self) -> BTreeSet<T> {
+		self.0
+	}
+
+	/// Remove an item from the set, returning whether it was previously in the set.
+	///
+	/// The item may be any borrowed form of the set's item type, but the ordering on the borrowed
+	/// form _must_ match the ordering on the item type.
+	pub fn remove<Q>(&mut self, item: &Q) -> bool
+	where
+		T: Borrow<Q>,
+		Q: Ord + ?Sized,
+	{
+		self.0.remove(item)
+	}
+
+	/// Retains only the elements specified by the predicate.
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+		// `BTreeSet` has no `retain` in the toolchain we support, rebuild the set instead.
+		let inner = sp_std::mem::replace(&mut self.0, BTreeSet::new());
+		self.0 = inner.into_iter().filter(|item| f(item)).collect();
+	}
+}
+
+impl<T: Ord, S: Get<u32>> BoundedBTreeSet<T, S> {
+	/// Get the bound of the type in `usize`.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Consumes self and mutates self via the given `mutate` function.
+	///
+	/// If the outcome of mutation is within bounds, `Some(Self)` is returned. Else, `None` is
+	/// returned.
+	///
+	/// This is essentially a *consuming* shorthand [`Self::into_inner`] -> `...` ->
+	/// [`Self::try_from`].
+	pub fn try_mutate(mut self, mut mutate: impl FnMut(&mut BTreeSet<T>)) -> Option<Self> {
+		mutate(&mut self.0);
+		if self.0.len() <= Self::bound() {
+			Some(self)
+		} else {
+			None
+		}
+	}
+
+	/// Exactly the same semantics as [`BTreeSet::insert`], but returns an `Err` (and is a noop)
+	/// if the new length of the set exceeds `S`.
+	///
+	/// Inserting an item which is already in the set never fails, and returns `Ok(false)`.
+	pub fn try_insert(&mut self, item: T) -> Result<bool, ()> {
+		if self.0.contains(&item) {
+			return Ok(false);
+		}
+		if self.len() < Self::bound() {
+			Ok(self.0.insert(item))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T: Ord, S> Default for BoundedBTreeSet<T, S> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Clone, S> Clone for BoundedBTreeSet<T, S> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone(), PhantomData)
+	}
+}
+
+impl<T: fmt::Debug, S: Get<u32>> fmt::Debug for BoundedBTreeSet<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("BoundedBTreeSet").field(&self.0).field(&S::get()).finish()
+	}
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedBTreeSet<T, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<T: PartialEq, S> PartialEq<BTreeSet<T>> for BoundedBTreeSet<T, S> {
+	fn eq(&self, other: &BTreeSet<T>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<T: Eq, S> Eq for BoundedBTreeSet<T, S> {}
+
+impl<T: Ord, S: Get<u32>> TryFrom<BTreeSet<T>> for BoundedBTreeSet<T, S> {
+	type Error = ();
+
+	fn try_from(value: BTreeSet<T>) -> Result<Self, Self::Error> {
+		if value.len() <= Self::bound() {
+			Ok(Self(value, PhantomData))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T, S> AsRef<BTreeSet<T>> for BoundedBTreeSet<T, S> {
+	fn as_ref(&self) -> &BTreeSet<T> {
+		&self.0
+	}
+}
+
+// Allows for all immutable operations of `BTreeSet<T>` on `BoundedBTreeSet<T>`.
+impl<T, S> sp_std::ops::Deref for BoundedBTreeSet<T, S> {
+	type Target = BTreeSet<T>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T, S> IntoIterator for BoundedBTreeSet<T, S> {
+	type Item = T;
+	type IntoIter = sp_std::collections::btree_set::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<T, S> codec::DecodeLength for BoundedBTreeSet<T, S> {
+	fn len(self_encoded: &[u8]) -> Result<usize, codec::Error> {
+		// `BoundedBTreeSet<T, _>` is stored as a `BTreeSet<T>`, which is encoded as a sequence
+		// with a `Compact` length prefix, exactly like a `Vec<T>`.
+		<sp_std::vec::Vec<T> as codec::DecodeLength>::len(self_encoded)
+	}
+}
+
+impl<T, S> StorageDecodeLength for BoundedBTreeSet<T, S> {}
+
+#[cfg(test)]
+pub mod test {
+	use super::*;
+	use sp_io::TestExternalities;
+	use sp_std::convert::TryInto;
+	use crate::{Twox128, traits::StorageInstance};
+	use crate::storage::types::{self, OptionQuery};
+
+	crate::parameter_types! {
+		pub const Seven: u32 = 7;
+		pub const Four: u32 = 4;
+	}
+
+	struct Prefix;
+	impl StorageInstance for Prefix {
+		fn pallet_prefix() -> &'static str { "test" }
+		const STORAGE_PREFIX: &'static str = "foo";
+	}
+
+	type Foo = types::StorageValue<Prefix, BoundedBTreeSet<u32, Seven>, OptionQuery>;
+	type FooMap = types::StorageMap<Prefix, Twox128, u32, BoundedBTreeSet<u32, Seven>, OptionQuery>;
+
+	fn set_from_keys<T: Ord>(keys: &[T]) -> BTreeSet<T> where T: Clone {
+		keys.iter().cloned().collect()
+	}
+
+	fn boundedset_from_keys<T: Ord, S: Get<u32>>(keys: &[T]) -> BoundedBTreeSet<T, S>
+	where
+		T: Clone,
+	{
+		set_from_keys(keys).try_into().unwrap()
+	}
+
+	#[test]
+	fn decode_len_works() {
+		TestExternalities::default().execute_with(|| {
+			let bounded = boundedset_from_keys::<u32, Seven>(&[1, 2, 3]);
+			Foo::put(bounded);
+			assert_eq!(Foo::decode_len().unwrap(), 3);
+		});
+
+		TestExternalities::default().execute_with(|| {
+			let bounded = boundedset_from_keys::<u32, Seven>(&[1, 2, 3]);
+			FooMap::insert(1, bounded);
+			assert_eq!(FooMap::decode_len(1).unwrap(), 3);
+			assert!(FooMap::decode_len(0).is_none());
+			assert!(FooMap::decode_len(2).is_none());
+		});
+	}
+
+	#[test]
+	fn try_insert_works() {
+		let mut bounded = boundedset_from_keys::<u32, Four>(&[1, 2, 3]);
+		assert_eq!(bounded.try_insert(0), Ok(true));
+		assert_eq!(*bounded, set_from_keys(&[1, 0, 2, 3]));
+
+		// already present, so the bound is not hit
+		assert_eq!(bounded.try_insert(2), Ok(false));
+		assert!(bounded.try_insert(9).is_err());
+		assert_eq!(*bounded, set_from_keys(&[1, 0, 2, 3]));
+	}
+
+	#[test]
+	fn try_mutate_works() {
+		let bounded = boundedset_from_keys::<u32, Seven>(&[1, 2, 3, 4, 5, 6]);
+		let bounded = bounded.try_mutate(|v| { v.insert(7); }).unwrap();
+		assert_eq!(bounded.len(), 7);
+		assert!(bounded.try_mutate(|v| { v.insert(8); }).is_none());
+	}
+
+	#[test]
+	fn deref_coercion_works() {
+		let bounded = boundedset_from_keys::<u32, Seven>(&[1, 2, 3]);
+		// these methods come from deref-ed set.
+		assert_eq!(bounded.len(), 3);
+		assert!(bounded.iter().next().is_some());
+		assert!(!bounded.is_empty());
+	}
+
+	#[test]
+	fn decode_fails_past_bound() {
+		let v = set_from_keys(&[1u32, 2, 3, 4, 5]);
+		assert!(BoundedBTreeSet::<u32, Four>::decode(&mut &v.encode()[..]).is_err());
+
+		let v = set_from_keys(&[1u32, 2, 3, 4]);
+		let bounded = BoundedBTreeSet::<u32, Four>::decode(&mut &v.encode()[..]).unwrap();
+		assert_eq!(bounded, v);
+	}
+}
@@ -15,12 +15,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Some utilities for helping access storage with arbitrary key types.
+//! Some utilities for helping access storage with arbitrary key types and for running
+//! versioned migrations.
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use crate::{StorageHasher, Twox128};
 use crate::hash::ReversibleStorageHasher;
+use crate::traits::{GetPalletVersion, PalletVersion};
+use crate::weights::Weight;
 
 /// Utility to iterate through raw items in storage.
 pub struct StorageIterator<T> {
@@ -195,3 +198,51 @@ pub fn take_storage_item<K: Encode + Sized, T: Decode + Sized, H: StorageHasher>
 ) -> Option<T> {
 	take_storage_value(module, item, key.using_encoded(H::hash).as_ref())
 }
+
+/// Count the values stored under `prefix`, including those that don't decode.
+///
+/// This is meant for the `pre_upgrade` and `post_upgrade` checks of migrations, e.g. to ensure
+/// that a `translate` didn't remove any value. It reads all the keys, so don't use it on-chain.
+pub fn count_keys_with_prefix(prefix: &[u8]) -> u32 {
+	let mut key = prefix.to_vec();
+	let mut count = 0;
+	while let Some(next) = sp_io::storage::next_key(&key).filter(|next| next.starts_with(prefix)) {
+		count += 1;
+		key = next;
+	}
+	count
+}
+
+/// Execute `migration` iff the version of `Pallet` found in storage is below `version`.
+///
+/// A pallet without a version in storage is considered to be below any version, as this is
+/// the state of pallets that were deployed before they started to track their version.
+///
+/// The new version doesn't need to be written by `migration`, this is done by the pallet itself
+/// after its `on_runtime_upgrade` has been executed.
+///
+/// Returns the weight returned by `migration`, or `0` if it wasn't executed. The weight of
+/// reading the version from storage is not included.
+///
+/// # Example
+///
+/// ```nocompile
+/// fn on_runtime_upgrade() -> Weight {
+/// 	migration::migrate_to_version::<Self>(PalletVersion::new(2, 0, 1), || {
+/// 		Values::translate::<u32, _>(|_, old| Some(old as u64));
+/// 		T::MaximumBlockWeight::get()
+/// 	})
+/// }
+/// ```
+pub fn migrate_to_version<Pallet: GetPalletVersion>(
+	version: PalletVersion,
+	migration: impl FnOnce() -> Weight,
+) -> Weight {
+	let below = Pallet::storage_version().map_or(true, |stored| stored < version);
+
+	if below {
+		migration()
+	} else {
+		0
+	}
+}
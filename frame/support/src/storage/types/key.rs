@@ -0,0 +1,250 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage key type, used by `StorageNMap` to describe its keys and their hashers.
+
+use crate::hash::{ReversibleStorageHasher, StorageHasher};
+use codec::{Encode, EncodeLike, FullCodec};
+use sp_std::prelude::*;
+
+/// A type used exclusively by storage maps as their key type.
+///
+/// The final key generated is the concatenation of the hash of the encoded key, using the
+/// given hasher.
+pub struct Key<Hasher, KeyType>(core::marker::PhantomData<(Hasher, KeyType)>);
+
+/// A trait that contains the current key as an associated type.
+///
+/// It is implemented by a single [`Key`] and by tuples of [`Key`]s.
+pub trait KeyGenerator {
+	/// The full key, a tuple of all the key types.
+	type Key: FullCodec;
+	/// The tuple of owned key types expected when generating the final key.
+	type KArg: Encode;
+
+	/// The hashers of the keys, in order.
+	const HASHER_METADATA: &'static [frame_metadata::StorageHasher];
+
+	/// Given a `key` tuple, calculate the final key by encoding each element individually and
+	/// hashing them using the corresponding hasher in the `KeyGenerator`.
+	fn final_key<KArg: EncodeLikeTuple<Self::KArg> + TupleToEncodedIter>(key: KArg) -> Vec<u8>;
+}
+
+/// A `KeyGenerator` whose hashers are all reversible, thus allowing the keys to be decoded back
+/// from the final key.
+pub trait ReversibleKeyGenerator: KeyGenerator {
+	/// Decode the full key from the given key material, i.e. the final key without the storage
+	/// prefix.
+	///
+	/// Returns the decoded key and the remaining key material.
+	fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error>;
+}
+
+/// Trait indicating whether a `KeyGenerator` has the prefix `P`.
+pub trait HasKeyPrefix<P>: KeyGenerator {
+	/// The remaining keys once the prefix is given.
+	type Suffix;
+
+	/// Generate the partial key (without the storage prefix) for the given prefix.
+	fn partial_key(prefix: P) -> Vec<u8>;
+}
+
+/// Trait indicating whether a `ReversibleKeyGenerator` has the prefix `P`.
+pub trait HasReversibleKeyPrefix<P>: ReversibleKeyGenerator + HasKeyPrefix<P> {
+	/// Decode the suffix keys from the key material following the partial key.
+	fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error>;
+}
+
+/// Marker trait to indicate that each element in the tuple encodes like the corresponding element
+/// in another tuple.
+///
+/// This trait is sealed.
+pub trait EncodeLikeTuple<T>: private::Sealed {}
+
+/// Trait to indicate that a tuple can be converted into an iterator of a vector of encoded bytes.
+pub trait TupleToEncodedIter {
+	/// Encode each element of the tuple individually.
+	fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>>;
+}
+
+impl<T: TupleToEncodedIter> TupleToEncodedIter for &T {
+	fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>> {
+		(*self).to_encoded_iter()
+	}
+}
+
+impl<T: EncodeLikeTuple<U>, U> EncodeLikeTuple<U> for &T {}
+
+mod private {
+	pub trait Sealed {}
+
+	impl<T: Sealed> Sealed for &T {}
+}
+
+impl<H: StorageHasher, K: FullCodec> KeyGenerator for Key<H, K> {
+	type Key = K;
+	type KArg = (K,);
+
+	const HASHER_METADATA: &'static [frame_metadata::StorageHasher] = &[H::METADATA];
+
+	fn final_key<KArg: EncodeLikeTuple<Self::KArg> + TupleToEncodedIter>(key: KArg) -> Vec<u8> {
+		H::hash(
+			&key.to_encoded_iter().next().expect("should have at least one element!")
+		).as_ref().to_vec()
+	}
+}
+
+impl<H: ReversibleStorageHasher, K: FullCodec> ReversibleKeyGenerator for Key<H, K> {
+	fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error> {
+		let mut current_key_material = H::reverse(key_material);
+		let key = K::decode(&mut current_key_material)?;
+		Ok((key, current_key_material))
+	}
+}
+
+/// Implement `EncodeLikeTuple` and `TupleToEncodedIter` for a tuple of the given arity.
+macro_rules! impl_encode_like_tuple {
+	( $( ($Arg:ident, $Target:ident) ),+ ) => {
+		impl<$( $Arg: Encode ),+> private::Sealed for ( $( $Arg, )+ ) {}
+
+		impl<$( $Arg: EncodeLike<$Target>, $Target: Encode ),+>
+			EncodeLikeTuple<( $( $Target, )+ )> for ( $( $Arg, )+ ) {}
+
+		impl<$( $Arg: Encode ),+> TupleToEncodedIter for ( $( $Arg, )+ ) {
+			#[allow(non_snake_case)]
+			fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>> {
+				let ( $( $Arg, )+ ) = self;
+				vec![ $( $Arg.encode() ),+ ].into_iter()
+			}
+		}
+	};
+}
+
+/// Implement `KeyGenerator` and `ReversibleKeyGenerator` for a tuple of `Key`s of the given arity.
+macro_rules! impl_key_generator_for_tuple {
+	( $( ($Hasher:ident, $KeyType:ident) ),+ ) => {
+		impl<$( $Hasher: StorageHasher, $KeyType: FullCodec ),+> KeyGenerator
+			for ( $( Key<$Hasher, $KeyType>, )+ )
+		{
+			type Key = ( $( $KeyType, )+ );
+			type KArg = ( $( $KeyType, )+ );
+
+			const HASHER_METADATA: &'static [frame_metadata::StorageHasher] =
+				&[ $( $Hasher::METADATA ),+ ];
+
+			fn final_key<KArg: EncodeLikeTuple<Self::KArg> + TupleToEncodedIter>(
+				key: KArg,
+			) -> Vec<u8> {
+				let mut final_key = Vec::new();
+				let mut iter = key.to_encoded_iter();
+				$(
+					let next_encoded = iter.next().expect("KArg number should be equal to Key number");
+					final_key.extend_from_slice($Hasher::hash(&next_encoded).as_ref());
+				)+
+				final_key
+			}
+		}
+
+		impl<$( $Hasher: ReversibleStorageHasher, $KeyType: FullCodec ),+> ReversibleKeyGenerator
+			for ( $( Key<$Hasher, $KeyType>, )+ )
+		{
+			fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error> {
+				let mut current_key_material = key_material;
+				Ok((
+					( $( {
+						let (key, material) =
+							Key::<$Hasher, $KeyType>::decode_final_key(current_key_material)?;
+						current_key_material = material;
+						key
+					}, )+ ),
+					current_key_material,
+				))
+			}
+		}
+	};
+}
+
+/// Implement `HasKeyPrefix` and `HasReversibleKeyPrefix` for every prefix of a tuple of `Key`s.
+///
+/// The first list is the prefix, the second the suffix; the first element of the suffix is moved
+/// to the prefix on each recursion until the suffix is empty.
+macro_rules! impl_key_prefix_for_tuple {
+	( [ $( ($PH:ident, $PK:ident) ),+ ] [] ) => {};
+	(
+		[ $( ($PH:ident, $PK:ident) ),+ ]
+		[ ($SH1:ident, $SK1:ident) $(, ($SH:ident, $SK:ident) )* ]
+	) => {
+		impl<
+			$( $PH: StorageHasher, $PK: FullCodec, )+
+			$SH1: StorageHasher, $SK1: FullCodec,
+			$( $SH: StorageHasher, $SK: FullCodec, )*
+		> HasKeyPrefix<( $( $PK, )+ )>
+			for ( $( Key<$PH, $PK>, )+ Key<$SH1, $SK1>, $( Key<$SH, $SK>, )* )
+		{
+			type Suffix = ( $SK1, $( $SK, )* );
+
+			fn partial_key(prefix: ( $( $PK, )+ )) -> Vec<u8> {
+				<( $( Key<$PH, $PK>, )+ ) as KeyGenerator>::final_key(prefix)
+			}
+		}
+
+		impl<
+			$( $PH: ReversibleStorageHasher, $PK: FullCodec, )+
+			$SH1: ReversibleStorageHasher, $SK1: FullCodec,
+			$( $SH: ReversibleStorageHasher, $SK: FullCodec, )*
+		> HasReversibleKeyPrefix<( $( $PK, )+ )>
+			for ( $( Key<$PH, $PK>, )+ Key<$SH1, $SK1>, $( Key<$SH, $SK>, )* )
+		{
+			fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error> {
+				<( Key<$SH1, $SK1>, $( Key<$SH, $SK>, )* ) as ReversibleKeyGenerator>
+					::decode_final_key(key_material)
+					.map(|(suffix, _)| suffix)
+			}
+		}
+
+		impl_key_prefix_for_tuple!(
+			[ $( ($PH, $PK), )+ ($SH1, $SK1) ]
+			[ $( ($SH, $SK) ),* ]
+		);
+	};
+}
+
+/// Implement all the key traits for tuples of the given arity.
+macro_rules! impl_for_tuple {
+	( ($H1:ident, $K1:ident, $A1:ident) $(, ($H:ident, $K:ident, $A:ident) )* ) => {
+		impl_encode_like_tuple!( ($A1, $K1) $(, ($A, $K) )* );
+		impl_key_generator_for_tuple!( ($H1, $K1) $(, ($H, $K) )* );
+		impl_key_prefix_for_tuple!( [ ($H1, $K1) ] [ $( ($H, $K) ),* ] );
+	};
+}
+
+impl_for_tuple!((H1, K1, A1));
+impl_for_tuple!((H1, K1, A1), (H2, K2, A2));
+impl_for_tuple!((H1, K1, A1), (H2, K2, A2), (H3, K3, A3));
+impl_for_tuple!((H1, K1, A1), (H2, K2, A2), (H3, K3, A3), (H4, K4, A4));
+impl_for_tuple!((H1, K1, A1), (H2, K2, A2), (H3, K3, A3), (H4, K4, A4), (H5, K5, A5));
+impl_for_tuple!(
+	(H1, K1, A1), (H2, K2, A2), (H3, K3, A3), (H4, K4, A4), (H5, K5, A5), (H6, K6, A6)
+);
+impl_for_tuple!(
+	(H1, K1, A1), (H2, K2, A2), (H3, K3, A3), (H4, K4, A4), (H5, K5, A5), (H6, K6, A6),
+	(H7, K7, A7)
+);
+impl_for_tuple!(
+	(H1, K1, A1), (H2, K2, A2), (H3, K3, A3), (H4, K4, A4), (H5, K5, A5), (H6, K6, A6),
+	(H7, K7, A7), (H8, K8, A8)
+);
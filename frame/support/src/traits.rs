@@ -21,6 +21,7 @@
 
 use sp_std::{prelude::*, result, marker::PhantomData, ops::Div, fmt::Debug};
 use codec::{FullCodec, Codec, Encode, Decode, EncodeLike};
+use crate::type_info::TypeInfo;
 use sp_core::u32_trait::Value as U32;
 use sp_runtime::{
 	RuntimeDebug, ConsensusEngineId, DispatchResult, DispatchError, traits::{
@@ -477,6 +478,12 @@ impl<T: Default> Get<T> for () {
 	fn get() -> T { T::default() }
 }
 
+/// Implement `Get` by returning `Default` for any type that implements `Default`.
+pub struct GetDefault;
+impl<T: Default> Get<T> for GetDefault {
+	fn get() -> T { T::default() }
+}
+
 /// A trait for querying whether a type can be said to "contain" a value.
 pub trait Contains<T: Ord> {
 	/// Return `true` if this "contains" the given value `t`.
@@ -1032,7 +1039,7 @@ pub trait Currency<AccountId> {
 }
 
 /// Status of funds.
-#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum BalanceStatus {
 	/// Funds are free, as corresponding to `free` item in Balances.
 	Free,
@@ -1182,6 +1189,39 @@ pub trait VestingSchedule<AccountId> {
 	fn remove_vesting_schedule(who: &AccountId);
 }
 
+/// Abstraction over a collection of fungible assets, each identified by an asset id.
+pub trait MultiAsset<AccountId> {
+	/// The identifier of an asset.
+	type AssetId: Parameter + Default + Copy;
+
+	/// The balance of an account in any one asset.
+	type Balance: Parameter + AtLeast32BitUnsigned + Default + Copy;
+
+	/// The balance of asset `id` held by `who`.
+	fn balance(id: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+	/// Transfer `amount` of asset `id` from `source` to `dest`.
+	fn transfer(
+		id: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+}
+
+impl<AccountId> MultiAsset<AccountId> for () {
+	type AssetId = u32;
+	type Balance = u32;
+
+	fn balance(_: u32, _: &AccountId) -> u32 {
+		0
+	}
+
+	fn transfer(_: u32, _: &AccountId, _: &AccountId, _: u32) -> DispatchResult {
+		Err(DispatchError::Other("no assets"))
+	}
+}
+
 bitmask! {
 	/// Reasons for moving funds out of an account.
 	#[derive(Encode, Decode)]
@@ -1346,6 +1386,18 @@ pub trait Randomness<Output> {
 	}
 }
 
+/// A source of [`Randomness`] which also tells since when its value is known.
+///
+/// A random value is only useful for a decision if nobody could know it at the time the decision
+/// was committed to. Consumers should therefore remember the block number at which e.g. a bet
+/// was placed and reject any value whose `known_since` is not greater than it, waiting for a
+/// fresher value instead.
+pub trait RandomnessWithFreshness<Output, BlockNumber>: Randomness<Output> {
+	/// Get a "random" value for `subject` along with the number of the block since which it
+	/// could be known, at the latest, to anyone observing the chain.
+	fn random_with_freshness(subject: &[u8]) -> (Output, BlockNumber);
+}
+
 /// Provides an implementation of [`Randomness`] that should only be used in tests!
 pub struct TestRandomness;
 
@@ -1355,6 +1407,14 @@ impl<Output: Decode + Default> Randomness<Output> for TestRandomness {
 	}
 }
 
+impl<Output: Decode + Default, BlockNumber: Default> RandomnessWithFreshness<Output, BlockNumber>
+	for TestRandomness
+{
+	fn random_with_freshness(subject: &[u8]) -> (Output, BlockNumber) {
+		(Self::random(subject), Default::default())
+	}
+}
+
 /// Trait to be used by block producing consensus engine modules to determine
 /// how late the current block is (e.g. in a slot-based proposal mechanism how
 /// many slots were skipped since the previous block).
@@ -1429,6 +1489,32 @@ pub trait OnFinalize<BlockNumber> {
 	fn on_finalize(_n: BlockNumber) {}
 }
 
+/// The block idle trait. Implementing this lets you express what should happen for your module
+/// with the weight left over once all extrinsics of the block have been applied.
+///
+/// It is called right before `on_finalize`.
+pub trait OnIdle<BlockNumber> {
+	/// The block is about to be finalized and `remaining_weight` of the block's maximum weight
+	/// has not been used. Implement to have some optional work happen.
+	///
+	/// Return the weight consumed, which must not exceed `remaining_weight`.
+	fn on_idle(_n: BlockNumber, _remaining_weight: crate::weights::Weight) -> crate::weights::Weight {
+		0
+	}
+}
+
+#[impl_for_tuples(30)]
+impl<BlockNumber: Clone> OnIdle<BlockNumber> for Tuple {
+	fn on_idle(n: BlockNumber, remaining_weight: crate::weights::Weight) -> crate::weights::Weight {
+		let mut weight: crate::weights::Weight = 0;
+		for_tuples!( #(
+			let adjusted_remaining_weight = remaining_weight.saturating_sub(weight);
+			weight = weight.saturating_add(Tuple::on_idle(n.clone(), adjusted_remaining_weight));
+		)* );
+		weight
+	}
+}
+
 /// The block initialization trait. Implementing this lets you express what should happen
 /// for your module when the block is beginning (right before the first extrinsic is executed).
 pub trait OnInitialize<BlockNumber> {
@@ -1462,6 +1548,18 @@ pub trait OnRuntimeUpgrade {
 	///
 	/// Return the non-negotiable weight consumed for runtime upgrade.
 	fn on_runtime_upgrade() -> crate::weights::Weight { 0 }
+
+	/// Execute some pre-checks prior to a runtime upgrade.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> { Ok(()) }
+
+	/// Execute some post-checks after a runtime upgrade.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> { Ok(()) }
 }
 
 #[impl_for_tuples(30)]
@@ -1471,6 +1569,182 @@ impl OnRuntimeUpgrade for Tuple {
 		for_tuples!( #( weight = weight.saturating_add(Tuple::on_runtime_upgrade()); )* );
 		weight
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		for_tuples!( #( Tuple::pre_upgrade()?; )* );
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		for_tuples!( #( Tuple::post_upgrade()?; )* );
+		Ok(())
+	}
+}
+
+/// A storage migration which may be too heavy for a single block and is therefore executed in
+/// steps, spread over as many blocks as needed.
+///
+/// Migrations are given to `frame_executive::Executive`, which starts them after a runtime upgrade
+/// and steps them at the beginning of every block until they are complete. No normal
+/// transactions are accepted meanwhile. Like [`OnRuntimeUpgrade`], the migrations are started
+/// again on every runtime upgrade, so they should be removed once they have been executed.
+///
+/// A tuple of migrations executes them one after the other.
+pub trait SteppedMigration {
+	/// Execute the next step of the migration, using at most `limit` weight.
+	///
+	/// `cursor` is the cursor returned by the previous step, or `None` for the first step.
+	///
+	/// Return the cursor to continue from, or `None` if the migration is complete, together
+	/// with the weight consumed. A step that doesn't advance the cursor is retried in the next
+	/// block.
+	fn step(
+		cursor: Option<Vec<u8>>,
+		limit: crate::weights::Weight,
+	) -> (Option<Vec<u8>>, crate::weights::Weight);
+}
+
+#[impl_for_tuples(30)]
+impl SteppedMigration for Tuple {
+	// The cursor of a tuple is the index of the current migration and its own cursor.
+	#[allow(unused_mut, unused_variables)]
+	fn step(
+		cursor: Option<Vec<u8>>,
+		limit: crate::weights::Weight,
+	) -> (Option<Vec<u8>>, crate::weights::Weight) {
+		let (index, inner) = cursor
+			.and_then(|cursor| <(u32, Option<Vec<u8>>)>::decode(&mut &cursor[..]).ok())
+			.unwrap_or((0, None));
+		let mut current = 0u32;
+		for_tuples!( #(
+			if current == index {
+				let (next, weight) = Tuple::step(inner, limit);
+				let next_index = if next.is_some() { index } else { index.saturating_add(1) };
+				return (Some((next_index, next).encode()), weight);
+			}
+			current += 1;
+		)* );
+		(None, 0)
+	}
+}
+
+/// A trait that will be called at genesis.
+///
+/// Implementing this trait for a pallet lets you express operations that should
+/// happen at genesis. It will be called in an externalities provided environment and
+/// will see the genesis state after all pallets have written their genesis state.
+#[impl_for_tuples(30)]
+pub trait OnGenesis {
+	/// Something that should happen at genesis.
+	fn on_genesis() {}
+}
+
+/// Call [`OnGenesis::on_genesis`] of `T` on top of the given genesis `storage`.
+///
+/// Used by the `GenesisConfig` generated by [`construct_runtime!`](crate::construct_runtime),
+/// after all pallets have written their genesis state.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub fn on_genesis_storage<T: OnGenesis>(storage: &mut sp_runtime::Storage) {
+	crate::BasicExternalities::execute_with_storage(storage, T::on_genesis);
+}
+
+/// The storage key postfix that is used to store the [`PalletVersion`] per pallet.
+///
+/// The full storage key is built by using:
+/// Twox128([`PalletInfo::name`]) ++ Twox128([`PALLET_VERSION_STORAGE_KEY_POSTFIX`])
+pub const PALLET_VERSION_STORAGE_KEY_POSTFIX: &[u8] = b":__PALLET_VERSION__:";
+
+/// The version of a pallet.
+///
+/// Each pallet version is stored in the state under a fixed key. See
+/// [`PALLET_VERSION_STORAGE_KEY_POSTFIX`] for how this key is built.
+/// Versions are ordered by `major`, then `minor` and then `patch`.
+#[derive(RuntimeDebug, Eq, PartialEq, Encode, Decode, Ord, PartialOrd, Clone, Copy, TypeInfo)]
+pub struct PalletVersion {
+	/// The major version of the pallet.
+	pub major: u16,
+	/// The minor version of the pallet.
+	pub minor: u8,
+	/// The patch version of the pallet.
+	pub patch: u8,
+}
+
+impl PalletVersion {
+	/// Creates a new instance of `Self`.
+	pub fn new(major: u16, minor: u8, patch: u8) -> Self {
+		Self {
+			major,
+			minor,
+			patch,
+		}
+	}
+
+	/// Returns the storage key for a pallet version.
+	///
+	/// See [`PALLET_VERSION_STORAGE_KEY_POSTFIX`] on how this key is built.
+	///
+	/// Returns `None` if the given `PI` returned a `None` as name for the given
+	/// `Pallet`.
+	pub fn storage_key<PI: PalletInfo, Pallet: 'static>() -> Option<[u8; 32]> {
+		let pallet_name = PI::name::<Pallet>()?;
+
+		let pallet_name = sp_io::hashing::twox_128(pallet_name.as_bytes());
+		let postfix = sp_io::hashing::twox_128(PALLET_VERSION_STORAGE_KEY_POSTFIX);
+
+		let mut final_key = [0u8; 32];
+		final_key[..16].copy_from_slice(&pallet_name);
+		final_key[16..].copy_from_slice(&postfix);
+
+		Some(final_key)
+	}
+
+	/// Put this pallet version into the storage.
+	///
+	/// It will use the storage key that is associated with the given `Pallet`.
+	///
+	/// # Panics
+	///
+	/// This function will panic iff `Pallet` can not be found by `PalletInfo`.
+	/// In a runtime that is put together using
+	/// [`construct_runtime!`](crate::construct_runtime) this should never happen.
+	///
+	/// It will also panic if this function isn't executed in an externalities
+	/// provided environment.
+	pub fn put_into_storage<PI: PalletInfo, Pallet: 'static>(&self) {
+		let key = Self::storage_key::<PI, Pallet>()
+			.expect("Every active pallet has a name in the runtime; qed");
+
+		crate::storage::unhashed::put(&key, self);
+	}
+}
+
+/// Provides version information about a pallet.
+///
+/// This trait provides two functions for returning the version of a
+/// pallet. There is a state where both functions can return distinct versions.
+/// See [`GetPalletVersion::storage_version`] for more information about this.
+pub trait GetPalletVersion {
+	/// Returns the current version of the pallet.
+	fn current_version() -> PalletVersion;
+
+	/// Returns the version of the pallet that is stored in storage.
+	///
+	/// Most of the time this will return the exact same version as
+	/// [`GetPalletVersion::current_version`]. Only when being in
+	/// a state after a runtime upgrade happened and the pallet did
+	/// not yet updated its version in storage, this will return a
+	/// different (the previous, seen from the time of calling) version.
+	///
+	/// See [`PalletVersion`] for more information.
+	///
+	/// # Note
+	///
+	/// If there was no previous version of the pallet stored in the state,
+	/// this function returns `None`.
+	fn storage_version() -> Option<PalletVersion>;
 }
 
 /// Off-chain computation trait.
@@ -1507,7 +1781,7 @@ pub mod schedule {
 	pub type Priority = u8;
 
 	/// The dispatch time of a scheduled task.
-	#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+	#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 	pub enum DispatchTime<BlockNumber> {
 		/// At specified block.
 		At(BlockNumber),
@@ -1661,6 +1935,95 @@ impl<T> IsType<T> for T {
 	fn into_mut(&mut self) -> &mut T { self }
 }
 
+/// The storage key prefix of a storage item declared with `#[pallet::storage]`.
+///
+/// The final key of the storage item is built from the prefix of the pallet, which is the name
+/// of the pallet in the runtime, and the prefix of the storage item, which is the name of the
+/// storage item.
+pub trait StorageInstance {
+	/// Prefix of the pallet, i.e. the name of the pallet in the runtime.
+	fn pallet_prefix() -> &'static str;
+
+	/// Prefix given to the storage item.
+	const STORAGE_PREFIX: &'static str;
+}
+
+/// The hooks of a pallet declared with `#[pallet::hooks]`.
+///
+/// `#[pallet::hooks]` implements `OnInitialize`, `OnFinalize`, `OnRuntimeUpgrade`,
+/// `OffchainWorker` and `IntegrityTest` for the pallet from this trait.
+pub trait Hooks<BlockNumber> {
+	/// The block is being finalized. Implement to have something happen.
+	fn on_finalize(_n: BlockNumber) {}
+
+	/// The block is being initialized. Implement to have something happen.
+	///
+	/// Return the non-negotiable weight consumed in the block.
+	fn on_initialize(_n: BlockNumber) -> crate::weights::Weight { 0 }
+
+	/// The block is about to be finalized and `remaining_weight` is left unused.
+	///
+	/// See [`OnIdle`] for more information.
+	///
+	/// Return the weight consumed.
+	fn on_idle(_n: BlockNumber, _remaining_weight: crate::weights::Weight) -> crate::weights::Weight {
+		0
+	}
+
+	/// Perform a module upgrade.
+	///
+	/// See [`OnRuntimeUpgrade`] for the caveats of this hook.
+	///
+	/// Return the non-negotiable weight consumed for runtime upgrade.
+	fn on_runtime_upgrade() -> crate::weights::Weight { 0 }
+
+	/// Execute some pre-checks prior to a runtime upgrade.
+	///
+	/// See [`OnRuntimeUpgrade::pre_upgrade`] for more information.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> { Ok(()) }
+
+	/// Execute some post-checks after a runtime upgrade.
+	///
+	/// See [`OnRuntimeUpgrade::post_upgrade`] for more information.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> { Ok(()) }
+
+	/// This function is being called after every block import (when fully synced).
+	///
+	/// See [`OffchainWorker`] for more information.
+	fn offchain_worker(_n: BlockNumber) {}
+
+	/// Run integrity test.
+	///
+	/// The test is not executed in a externalities provided environment.
+	fn integrity_test() {}
+}
+
+/// The genesis build of a pallet declared with `#[pallet::genesis_build]`.
+pub trait GenesisBuild<T>: Default + MaybeSerializeDeserialize {
+	/// The build function is called within an externalities allowing storage APIs.
+	/// Thus one can write to storage using regular pallet storages.
+	fn build(&self);
+
+	/// Build the storage using `build` inside default storage.
+	#[cfg(feature = "std")]
+	fn build_storage(&self) -> Result<sp_runtime::Storage, String> {
+		let mut storage = Default::default();
+		self.assimilate_storage(&mut storage)?;
+		Ok(storage)
+	}
+
+	/// Assimilate the storage for this module into pre-existing overlays.
+	#[cfg(feature = "std")]
+	fn assimilate_storage(&self, storage: &mut sp_runtime::Storage) -> Result<(), String> {
+		crate::BasicExternalities::execute_with_storage(storage, || {
+			self.build();
+			Ok(())
+		})
+	}
+}
+
 /// An instance of a pallet in the storage.
 ///
 /// It is required that these instances are unique, to support multiple instances per pallet in the same runtime!
@@ -22,6 +22,153 @@ pub use crate::sp_runtime::transaction_validity::{
 	TransactionValidity, UnknownTransaction, TransactionValidityError, TransactionSource,
 };
 
+use codec::{Encode, FullCodec, FullEncode};
+use sp_std::convert::TryInto;
+use crate::sp_runtime::traits::{AtLeast32BitUnsigned, Saturating};
+use crate::sp_runtime::transaction_validity::{
+	InvalidTransaction, TransactionLongevity, TransactionPriority, ValidTransaction,
+	ValidTransactionBuilder,
+};
+use crate::storage::StorageMap;
+use crate::traits::Get;
+
+/// Rate limit of unsigned transactions.
+///
+/// Counts the transactions submitted under each key within a window of `Period` blocks, starting
+/// at the first transaction of the key, and allows at most `MaxCount` of them. Transactions over
+/// the limit are `InvalidTransaction::ExhaustsResources`.
+///
+/// The counts are kept in `Counts`, a storage map declared by the pallet as
+/// `map hasher(...) Key => Option<(BlockNumber, u32)>`. Since the state changes of
+/// `validate_unsigned` are discarded, a transaction should be checked with `check` when it is
+/// validated and recorded with `note` in `pre_dispatch`.
+pub struct RateLimit<Counts, MaxCount, Period>(
+	sp_std::marker::PhantomData<(Counts, MaxCount, Period)>
+);
+
+impl<Counts, MaxCount: Get<u32>, Period> RateLimit<Counts, MaxCount, Period> {
+	/// Returns the window start and transaction count of `key` after another transaction at
+	/// block `now`, if it is within the limit.
+	pub fn check<K, BlockNumber>(
+		key: &K,
+		now: BlockNumber,
+	) -> Result<(BlockNumber, u32), TransactionValidityError> where
+		K: FullEncode,
+		BlockNumber: AtLeast32BitUnsigned + FullCodec + Copy,
+		Counts: StorageMap<K, (BlockNumber, u32), Query = Option<(BlockNumber, u32)>>,
+		Period: Get<BlockNumber>,
+	{
+		let (start, count) = match Counts::get(key) {
+			Some((start, count)) if now < start.saturating_add(Period::get()) => (start, count),
+			_ => (now, 0),
+		};
+		if count >= MaxCount::get() {
+			Err(InvalidTransaction::ExhaustsResources.into())
+		} else {
+			Ok((start, count + 1))
+		}
+	}
+
+	/// Checks another transaction of `key` at block `now` and records it.
+	pub fn note<K, BlockNumber>(
+		key: &K,
+		now: BlockNumber,
+	) -> Result<(), TransactionValidityError> where
+		K: FullEncode,
+		BlockNumber: AtLeast32BitUnsigned + FullCodec + Copy,
+		Counts: StorageMap<K, (BlockNumber, u32), Query = Option<(BlockNumber, u32)>>,
+		Period: Get<BlockNumber>,
+	{
+		let next = Self::check(key, now)?;
+		Counts::insert(key, next);
+		Ok(())
+	}
+}
+
+/// Builder of the `ValidTransaction` of an unsigned transaction.
+///
+/// Works like `ValidTransaction::with_tag_prefix`, but caps the priority and the longevity of the
+/// transaction, so that they can be derived from runtime values (e.g. the score of a solution or
+/// a number of blocks) without exceeding the bounds set by the pallet. Transactions are propagated
+/// unless stated otherwise.
+pub struct UnsignedValidity {
+	builder: ValidTransactionBuilder,
+	priority: TransactionPriority,
+	max_priority: TransactionPriority,
+	longevity: TransactionLongevity,
+	max_longevity: TransactionLongevity,
+	propagate: bool,
+}
+
+impl UnsignedValidity {
+	/// Start building the validity of an unsigned transaction whose tags are prefixed by `prefix`.
+	pub fn new(prefix: &'static str) -> Self {
+		Self {
+			builder: ValidTransaction::with_tag_prefix(prefix),
+			priority: 0,
+			max_priority: TransactionPriority::max_value(),
+			longevity: TransactionLongevity::max_value(),
+			max_longevity: TransactionLongevity::max_value(),
+			propagate: true,
+		}
+	}
+
+	/// Set the priority of the transaction.
+	pub fn priority(mut self, priority: TransactionPriority) -> Self {
+		self.priority = priority;
+		self
+	}
+
+	/// Bump the priority of the transaction by `bump`.
+	pub fn bump_priority(mut self, bump: TransactionPriority) -> Self {
+		self.priority = self.priority.saturating_add(bump);
+		self
+	}
+
+	/// Cap the priority of the transaction at `max_priority`.
+	pub fn max_priority(mut self, max_priority: TransactionPriority) -> Self {
+		self.max_priority = max_priority;
+		self
+	}
+
+	/// Make the transaction valid for `blocks` blocks, or for `fallback` blocks if `blocks` does
+	/// not fit in a `TransactionLongevity`.
+	pub fn longevity<N: TryInto<TransactionLongevity>>(
+		mut self,
+		blocks: N,
+		fallback: TransactionLongevity,
+	) -> Self {
+		self.longevity = blocks.try_into().unwrap_or(fallback);
+		self
+	}
+
+	/// Cap the longevity of the transaction at `max_longevity`.
+	pub fn max_longevity(mut self, max_longevity: TransactionLongevity) -> Self {
+		self.max_longevity = max_longevity;
+		self
+	}
+
+	/// Add a tag provided by the transaction, prefixed by the tag prefix.
+	pub fn and_provides(mut self, tag: impl Encode) -> Self {
+		self.builder = self.builder.and_provides(tag);
+		self
+	}
+
+	/// Set whether the transaction should be propagated to other peers.
+	pub fn propagate(mut self, propagate: bool) -> Self {
+		self.propagate = propagate;
+		self
+	}
+
+	/// Build the `ValidTransaction`.
+	pub fn build(self) -> TransactionValidity {
+		self.builder
+			.priority(self.priority.min(self.max_priority))
+			.longevity(self.longevity.min(self.max_longevity))
+			.propagate(self.propagate)
+			.build()
+	}
+}
 
 /// Implement `ValidateUnsigned` for `Runtime`.
 /// All given modules need to implement `ValidateUnsigned`.
@@ -95,6 +242,101 @@ macro_rules! impl_outer_validate_unsigned {
 	};
 }
 
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{decl_module, decl_storage, parameter_types};
+	use sp_io::TestExternalities;
+
+	pub trait Trait {
+		type Origin;
+		type BlockNumber;
+	}
+
+	struct Runtime;
+
+	impl Trait for Runtime {
+		type Origin = u32;
+		type BlockNumber = u32;
+	}
+
+	decl_module! {
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Trait> as Test {
+			Counts: map hasher(identity) u32 => Option<(u32, u32)>;
+		}
+	}
+
+	parameter_types! {
+		pub const MaxCount: u32 = 2;
+		pub const Period: u32 = 10;
+	}
+
+	type Limit = RateLimit<Counts, MaxCount, Period>;
+
+	#[test]
+	fn rate_limit_works() {
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(Limit::check(&1u32, 1u32), Ok((1, 1)));
+			// checking doesn't record the transaction.
+			assert_eq!(Limit::check(&1u32, 1u32), Ok((1, 1)));
+
+			assert_eq!(Limit::note(&1u32, 1u32), Ok(()));
+			assert_eq!(Limit::note(&1u32, 5u32), Ok(()));
+			assert_eq!(Counts::get(1), Some((1, 2)));
+			assert_eq!(
+				Limit::check(&1u32, 10u32),
+				Err(InvalidTransaction::ExhaustsResources.into()),
+			);
+
+			// other keys are limited separately.
+			assert_eq!(Limit::note(&2u32, 10u32), Ok(()));
+
+			// a new window starts after `Period` blocks.
+			assert_eq!(Limit::note(&1u32, 11u32), Ok(()));
+			assert_eq!(Counts::get(1), Some((11, 1)));
+		});
+	}
+
+	#[test]
+	fn unsigned_validity_caps_priority_and_longevity() {
+		let validity = UnsignedValidity::new("Test")
+			.priority(10)
+			.bump_priority(u64::max_value())
+			.max_priority(100)
+			.longevity(5u32, 32)
+			.max_longevity(4)
+			.and_provides(1u32)
+			.propagate(false)
+			.build()
+			.unwrap();
+
+		assert_eq!(validity.priority, 100);
+		assert_eq!(validity.longevity, 4);
+		assert_eq!(validity.provides, vec![("Test", 1u32).encode()]);
+		assert!(!validity.propagate);
+
+		let validity = UnsignedValidity::new("Test")
+			.priority(10)
+			.longevity(5u32, 32)
+			.build()
+			.unwrap();
+		assert_eq!(validity.priority, 10);
+		assert_eq!(validity.longevity, 5);
+		assert!(validity.propagate);
+
+		// blocks that don't fit in a `TransactionLongevity` fall back to the given longevity.
+		let validity = UnsignedValidity::new("Test")
+			.longevity(u128::max_value(), 32)
+			.build()
+			.unwrap();
+		assert_eq!(validity.longevity, 32);
+	}
+}
+
 #[cfg(test)]
 mod test_empty_call {
 	pub enum Call {}
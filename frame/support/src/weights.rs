@@ -117,6 +117,50 @@
 //! }
 //! # fn main() {}
 //! ```
+//!
+//! ### 3. Storage proof size
+//!
+//! Besides the compute time described by [`Weight`], a dispatch consumes some amount of the block's
+//! storage proof, which light clients and parachain validators need to re-execute the block. Any of
+//! the annotations above can be wrapped in [`WithProofSize`] to declare the worst case proof size
+//! of the dispatch, in bytes. The proof size of dispatches that don't declare one is zero.
+//! Benchmarked pallets expose the measured proof size next to each weight in their `WeightInfo`,
+//! e.g. `WithProofSize(T::WeightInfo::transfer(), T::WeightInfo::transfer_proof_size())`. Weights
+//! generated before the benchmark CLI measured proof sizes report [`UNBENCHMARKED_PROOF_SIZE`].
+//!
+//! ```
+//! # use frame_system::Trait;
+//! # use frame_support::weights::{DispatchClass, WithProofSize};
+//! frame_support::decl_module! {
+//!     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+//!         #[weight = WithProofSize((1000, DispatchClass::Operational), 4 * 1024)]
+//!         fn dispatching(origin) { unimplemented!() }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ### 4. Feeless dispatches
+//!
+//! A static `Pays::No` makes every call of a dispatch free, while a `Pays::No` returned in the
+//! `PostDispatchInfo` is only a refund: the sender still needs the funds to pay the fee up front.
+//! Wrapping any of the annotations above in [`FeelessIf`] makes a dispatch free whenever a
+//! condition on its arguments holds. The condition is evaluated with the dispatch info, hence
+//! already when the transaction is validated. Feeless transactions should be rate limited, e.g.
+//! with `frame_system::CheckFeelessRateLimit`.
+//!
+//! ```
+//! # use frame_system::Trait;
+//! # use frame_support::weights::FeelessIf;
+//! frame_support::decl_module! {
+//!     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+//!         #[weight = FeelessIf(1000, |(a,): (&u32,)| *a < 10)]
+//!         fn dispatching(origin, a: u32) { unimplemented!() }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
 //! FRAME assumes a weight of `1_000_000_000_000` equals 1 second of compute on a standard machine.
 //!
 //! Latest machine specification used to benchmark are:
@@ -129,6 +173,7 @@
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use codec::{Encode, Decode};
+use crate::type_info::TypeInfo;
 use sp_runtime::{
 	RuntimeDebug,
 	traits::SignedExtension,
@@ -145,6 +190,15 @@ pub use sp_runtime::transaction_validity::TransactionPriority;
 /// Numeric range of a transaction weight.
 pub type Weight = u64;
 
+/// Numeric range of the storage proof size of a transaction, in bytes.
+pub type ProofSize = u64;
+
+/// The proof size of the dispatches whose `WeightInfo` was not benchmarked for it yet.
+///
+/// Like for dispatches that don't declare a proof size, it is zero: such dispatches are not limited
+/// by the maximum proof size of a block until their weights are regenerated.
+pub const UNBENCHMARKED_PROOF_SIZE: ProofSize = 0;
+
 /// These constants are specific to FRAME, and the current implementation of its various components.
 /// For example: FRAME System, FRAME Executive, our FRAME support libraries, etc...
 pub mod constants {
@@ -181,6 +235,12 @@ pub trait WeighData<T> {
 	/// Weigh the data `T` given by `target`. When implementing this for a dispatchable, `T` will be
 	/// a tuple of all arguments given to the function (except origin).
 	fn weigh_data(&self, target: T) -> Weight;
+
+	/// Weigh the size of the storage proof, in bytes, the data `T` given by `target` will at most
+	/// consume. Defaults to zero, see [`WithProofSize`] to declare it.
+	fn weigh_proof_size(&self, _target: T) -> ProofSize {
+		0
+	}
 }
 
 /// Means of classifying a dispatchable function.
@@ -198,7 +258,7 @@ pub trait PaysFee<T> {
 }
 
 /// Explicit enum to denote if a transaction pays fee or not.
-#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub enum Pays {
 	/// Transactor will pay related fees.
 	Yes,
@@ -215,7 +275,7 @@ impl Default for Pays {
 /// A generalized group of dispatch types.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum DispatchClass {
 	/// A normal dispatch.
 	Normal,
@@ -267,10 +327,12 @@ pub mod priority {
 }
 
 /// A bundle of static information collected from the `#[weight = $x]` attributes.
-#[derive(Clone, Copy, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, Copy, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct DispatchInfo {
 	/// Weight of this transaction.
 	pub weight: Weight,
+	/// Worst case size of the storage proof of this transaction, in bytes.
+	pub proof_size: ProofSize,
 	/// Class of this transaction.
 	pub class: DispatchClass,
 	/// Does this transaction pay fees.
@@ -288,7 +350,7 @@ pub trait GetDispatchInfo {
 
 /// Weight information that is only available post dispatch.
 /// NOTE: This can only be used to reduce the weight or fee, not increase it.
-#[derive(Clone, Copy, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, Copy, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct PostDispatchInfo {
 	/// Actual weight consumed by a call or `None` which stands for the worst case static weight.
 	pub actual_weight: Option<Weight>,
@@ -486,6 +548,66 @@ impl<T> PaysFee<T> for (Weight, Pays) {
 	}
 }
 
+/// A weight annotation which declares the worst case storage proof size of a dispatch on top of
+/// any other weight annotation `W`, e.g. `WithProofSize(1000, 4096)` or
+/// `WithProofSize((1000, DispatchClass::Operational, Pays::No), 4096)`.
+pub struct WithProofSize<W>(pub W, pub ProofSize);
+
+impl<T, W: WeighData<T>> WeighData<T> for WithProofSize<W> {
+	fn weigh_data(&self, target: T) -> Weight {
+		self.0.weigh_data(target)
+	}
+
+	fn weigh_proof_size(&self, _: T) -> ProofSize {
+		self.1
+	}
+}
+
+impl<T, W: ClassifyDispatch<T>> ClassifyDispatch<T> for WithProofSize<W> {
+	fn classify_dispatch(&self, target: T) -> DispatchClass {
+		self.0.classify_dispatch(target)
+	}
+}
+
+impl<T, W: PaysFee<T>> PaysFee<T> for WithProofSize<W> {
+	fn pays_fee(&self, target: T) -> Pays {
+		self.0.pays_fee(target)
+	}
+}
+
+/// A weight annotation which makes a dispatch feeless if the condition `F` holds for its arguments,
+/// e.g. `FeelessIf(1000, |(who,): (&T::AccountId,)| Module::<T>::can_claim(who))`. Otherwise the
+/// fees are paid as declared by the weight annotation `W`.
+///
+/// The arguments are passed to `F` as a tuple of references.
+pub struct FeelessIf<W, F>(pub W, pub F);
+
+impl<T, W: WeighData<T>, F> WeighData<T> for FeelessIf<W, F> {
+	fn weigh_data(&self, target: T) -> Weight {
+		self.0.weigh_data(target)
+	}
+
+	fn weigh_proof_size(&self, target: T) -> ProofSize {
+		self.0.weigh_proof_size(target)
+	}
+}
+
+impl<T, W: ClassifyDispatch<T>, F> ClassifyDispatch<T> for FeelessIf<W, F> {
+	fn classify_dispatch(&self, target: T) -> DispatchClass {
+		self.0.classify_dispatch(target)
+	}
+}
+
+impl<T: Copy, W: PaysFee<T>, F: Fn(T) -> bool> PaysFee<T> for FeelessIf<W, F> {
+	fn pays_fee(&self, target: T) -> Pays {
+		if (self.1)(target) {
+			Pays::No
+		} else {
+			self.0.pays_fee(target)
+		}
+	}
+}
+
 /// A struct to represent a weight which is a function of the input arguments. The given items have
 /// the following types:
 ///
@@ -589,7 +711,7 @@ impl<Call: Encode, Extra: Encode> GetDispatchInfo for sp_runtime::testing::TestX
 }
 
 /// The weight of database operations that the runtime can invoke.
-#[derive(Clone, Copy, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, Copy, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct RuntimeDbWeight {
 	pub read: Weight,
 	pub write: Weight,
@@ -621,7 +743,7 @@ impl RuntimeDbWeight {
 ///
 /// The `negative` value encodes whether the term is added or substracted from the
 /// overall polynomial result.
-#[derive(Clone, Encode, Decode)]
+#[derive(Clone, Encode, Decode, TypeInfo)]
 pub struct WeightToFeeCoefficient<Balance> {
 	/// The integral part of the coefficient.
 	pub coeff_integer: Balance,
@@ -752,6 +874,18 @@ mod tests {
 			#[weight = T::DbWeight::get().reads_writes(6, 5) + 40_000]
 			fn f21(_origin) { unimplemented!(); }
 
+			#[weight = WithProofSize(1000, 2048)]
+			fn f3(_origin) { unimplemented!(); }
+
+			#[weight = WithProofSize(((_a * 10) as Weight, DispatchClass::Operational, Pays::No), 512)]
+			fn f31(_origin, _a: u32) { unimplemented!(); }
+
+			#[weight = FeelessIf(1000, |(a,): (&u32,)| *a < 10)]
+			fn f4(_origin, _a: u32) { unimplemented!(); }
+
+			#[weight = FeelessIf(WithProofSize((1000, DispatchClass::Operational), 256), |_: ()| false)]
+			fn f41(_origin) { unimplemented!(); }
+
 		}
 	}
 
@@ -788,6 +922,42 @@ mod tests {
 		assert_eq!(Call::<TraitImpl>::f2().get_dispatch_info().weight, 12300);
 		assert_eq!(Call::<TraitImpl>::f21().get_dispatch_info().weight, 45600);
 		assert_eq!(Call::<TraitImpl>::f2().get_dispatch_info().class, DispatchClass::Normal);
+		assert_eq!(Call::<TraitImpl>::f2().get_dispatch_info().proof_size, 0);
+	}
+
+	#[test]
+	fn proof_size_is_correct() {
+		// #[weight = WithProofSize(1000, 2048)]
+		let info = Call::<TraitImpl>::f3().get_dispatch_info();
+		assert_eq!(info.weight, 1000);
+		assert_eq!(info.proof_size, 2048);
+		assert_eq!(info.class, DispatchClass::Normal);
+		assert_eq!(info.pays_fee, Pays::Yes);
+
+		// #[weight = WithProofSize(((_a * 10) as Weight, DispatchClass::Operational, Pays::No), 512)]
+		let info = Call::<TraitImpl>::f31(7).get_dispatch_info();
+		assert_eq!(info.weight, 70);
+		assert_eq!(info.proof_size, 512);
+		assert_eq!(info.class, DispatchClass::Operational);
+		assert_eq!(info.pays_fee, Pays::No);
+	}
+
+	#[test]
+	fn feeless_if_is_correct() {
+		// #[weight = FeelessIf(1000, |(a,): (&u32,)| *a < 10)]
+		let info = Call::<TraitImpl>::f4(9).get_dispatch_info();
+		assert_eq!(info.weight, 1000);
+		assert_eq!(info.pays_fee, Pays::No);
+		let info = Call::<TraitImpl>::f4(10).get_dispatch_info();
+		assert_eq!(info.weight, 1000);
+		assert_eq!(info.pays_fee, Pays::Yes);
+
+		// #[weight = FeelessIf(WithProofSize((1000, DispatchClass::Operational), 256), |_: ()| false)]
+		let info = Call::<TraitImpl>::f41().get_dispatch_info();
+		assert_eq!(info.weight, 1000);
+		assert_eq!(info.proof_size, 256);
+		assert_eq!(info.class, DispatchClass::Operational);
+		assert_eq!(info.pays_fee, Pays::Yes);
 	}
 
 	#[test]
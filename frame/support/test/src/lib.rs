@@ -28,6 +28,10 @@ pub trait Trait {
 	type Origin;
 	/// The block number type.
 	type BlockNumber;
+	/// The information about the pallet setup in the runtime.
+	type PalletInfo: frame_support::traits::PalletInfo;
+	/// The db weights.
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
@@ -50,7 +50,7 @@ mod module1 {
 		}
 	}
 
-	#[derive(Clone, PartialEq, Eq, Debug, codec::Encode, codec::Decode)]
+	#[derive(Clone, PartialEq, Eq, Debug, codec::Encode, codec::Decode, frame_support::type_info::TypeInfo)]
 	pub struct Origin<T, I: Instance = DefaultInstance>(pub core::marker::PhantomData::<(T, I)>);
 
 	frame_support::decl_event! {
@@ -92,7 +92,7 @@ mod module2 {
 		}
 	}
 
-	#[derive(Clone, PartialEq, Eq, Debug, codec::Encode, codec::Decode)]
+	#[derive(Clone, PartialEq, Eq, Debug, codec::Encode, codec::Decode, frame_support::type_info::TypeInfo)]
 	pub struct Origin;
 
 	frame_support::decl_event! {
@@ -128,6 +128,7 @@ impl system::Trait for Runtime {
 	type AccountId = AccountId;
 	type Event = Event;
 	type PalletInfo = PalletInfo;
+	type DbWeight = ();
 	type Call = Call;
 }
 
@@ -282,229 +283,125 @@ fn call_codec() {
 
 #[test]
 fn test_metadata() {
+	use codec::{Encode, Decode};
 	use frame_metadata::*;
-	let expected_metadata: RuntimeMetadataLastVersion = RuntimeMetadataLastVersion {
-		modules: DecodeDifferent::Encode(&[
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("System"),
-				storage: None,
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("noop"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[
-					EventMetadata {
-						name: DecodeDifferent::Encode("ExtrinsicSuccess"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-					EventMetadata {
-						name: DecodeDifferent::Encode("ExtrinsicFailed"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-					EventMetadata {
-						name: DecodeDifferent::Encode("Ignore"),
-						arguments: DecodeDifferent::Encode(&["BlockNumber"]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-				]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 30,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_1"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance1Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[
-					FunctionMetadata {
-						name: DecodeDifferent::Encode("fail"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-				]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 31,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module2"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[
-					FunctionMetadata {
-						name: DecodeDifferent::Encode("fail"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-				]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[
-					EventMetadata {
-						name: DecodeDifferent::Encode("A"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-				]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 32,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_2"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance2Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("fail"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 33,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_3"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance3Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: None,
-				event: None,
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 6,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_4"),
-				storage: None,
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("fail"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: None,
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 3,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_5"),
-				storage: None,
-				calls: None,
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 4,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_6"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance6Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("fail"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 1,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_7"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance7Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("fail"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 2,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_8"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance8Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("fail"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 12,
-			},
-			ModuleMetadata {
-				name: DecodeDifferent::Encode("Module1_9"),
-				storage: Some(DecodeDifferent::Encode(FnEncode(|| StorageMetadata {
-					prefix: DecodeDifferent::Encode("Instance9Module"),
-					entries: DecodeDifferent::Encode(&[]),
-				}))),
-				calls: Some(DecodeDifferent::Encode(FnEncode(|| &[FunctionMetadata {
-					name: DecodeDifferent::Encode("fail"),
-					arguments: DecodeDifferent::Encode(&[]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				event: Some(DecodeDifferent::Encode(FnEncode(|| &[EventMetadata {
-					name: DecodeDifferent::Encode("A"),
-					arguments: DecodeDifferent::Encode(&["AccountId"]),
-					documentation: DecodeDifferent::Encode(&[]),
-				}]))),
-				constants: DecodeDifferent::Encode(FnEncode(|| &[])),
-				errors: DecodeDifferent::Encode(FnEncode(|| &[])),
-				index: 13,
-			},
-		]),
-		extrinsic: ExtrinsicMetadata {
+	use frame_support::type_info::{PortableRegistry, TypeDef};
+
+	fn variant_names(types: &PortableRegistry, id: u32) -> Vec<(String, u8)> {
+		match types.resolve(id).expect("Type is in the registry").type_def() {
+			TypeDef::Variant(variants) => variants.iter()
+				.map(|v| (v.name().clone(), v.index()))
+				.collect(),
+			def => panic!("Expected a variant, got {:?}", def),
+		}
+	}
+
+	let metadata_encoded = Runtime::metadata().encode();
+	let metadata = match RuntimeMetadataPrefixed::decode(&mut &metadata_encoded[..]).unwrap() {
+		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(metadata)) => metadata,
+		metadata => panic!("Unexpected metadata {:?}", metadata),
+	};
+	let types = &metadata.types;
+
+	pretty_assertions::assert_eq!(
+		metadata.extrinsic,
+		ExtrinsicMetadata {
 			version: 4,
-			signed_extensions: vec![DecodeDifferent::Encode("UnitSignedExtension")],
+			signed_extensions: vec![DecodeDifferent::Decoded("UnitSignedExtension".into())],
 		},
+	);
+
+	let modules = metadata.modules.iter()
+		.map(|m| (
+			m.name.as_str(),
+			m.index,
+			m.storage.as_ref().map(|s| (s.prefix.as_str(), s.entries.len())),
+			m.calls.is_some(),
+			m.event.is_some(),
+		))
+		.collect::<Vec<_>>();
+	pretty_assertions::assert_eq!(
+		modules,
+		vec![
+			("System", 30, None, true, true),
+			("Module1_1", 31, Some(("Instance1Module", 0)), true, true),
+			("Module2", 32, Some(("Module", 0)), true, true),
+			("Module1_2", 33, Some(("Instance2Module", 0)), true, true),
+			("Module1_3", 6, Some(("Instance3Module", 0)), false, false),
+			("Module1_4", 3, None, true, false),
+			("Module1_5", 4, None, false, true),
+			("Module1_6", 1, Some(("Instance6Module", 0)), true, true),
+			("Module1_7", 2, Some(("Instance7Module", 0)), true, true),
+			("Module1_8", 12, Some(("Instance8Module", 0)), true, true),
+			("Module1_9", 13, Some(("Instance9Module", 0)), true, true),
+		],
+	);
+
+	let system = &metadata.modules[0];
+	assert_eq!(variant_names(types, system.calls.unwrap()), vec![("noop".into(), 0)]);
+	assert_eq!(
+		variant_names(types, system.event.unwrap()),
+		vec![("ExtrinsicSuccess".into(), 0), ("ExtrinsicFailed".into(), 1), ("Ignore".into(), 2)],
+	);
+	// None of the modules declare `type Error` in `decl_module!`.
+	assert!(system.error.is_none());
+
+	for module in metadata.modules.iter().skip(1) {
+		if let Some(calls) = module.calls {
+			assert_eq!(variant_names(types, calls), vec![("fail".into(), 0)]);
+		}
+		if let Some(event) = module.event {
+			assert_eq!(variant_names(types, event), vec![("A".into(), 0)]);
+		}
+		assert!(module.error.is_none());
+		assert!(module.constants.is_empty());
+	}
+}
+
+#[test]
+fn outer_call_and_event_type_info() {
+	use frame_support::type_info::{Registry, PortableRegistry, MetaType, TypeDef};
+
+	let mut registry = Registry::new();
+	let call = registry.register_type(&MetaType::new::<Call>());
+	let event = registry.register_type(&MetaType::new::<Event>());
+	let registry = PortableRegistry::from(registry);
+
+	let variants = |id| match registry.resolve(id).unwrap().type_def() {
+		TypeDef::Variant(variants) => variants.iter()
+			.map(|v| (v.name().clone(), v.index()))
+			.collect::<Vec<_>>(),
+		def => panic!("Expected a variant, got {:?}", def),
 	};
-	pretty_assertions::assert_eq!(Runtime::metadata().1, RuntimeMetadata::V12(expected_metadata));
+
+	assert_eq!(
+		variants(call),
+		vec![
+			("System".into(), 30),
+			("Module1_1".into(), 31),
+			("Module2".into(), 32),
+			("Module1_2".into(), 33),
+			("Module1_4".into(), 3),
+			("Module1_6".into(), 1),
+			("Module1_7".into(), 2),
+			("Module1_8".into(), 12),
+			("Module1_9".into(), 13),
+		],
+	);
+	assert_eq!(
+		variants(event),
+		vec![
+			("system".into(), 30),
+			("module1_Instance1".into(), 31),
+			("module2".into(), 32),
+			("module1_Instance2".into(), 33),
+			("module1_Instance5".into(), 4),
+			("module1_Instance6".into(), 1),
+			("module1_Instance7".into(), 2),
+			("module1_Instance8".into(), 12),
+			("module1_Instance9".into(), 13),
+		],
+	);
 }
 
 #[test]
@@ -31,6 +31,8 @@ mod tests {
 	pub trait Trait {
 		type Origin: Encode + Decode + EncodeLike + std::default::Default;
 		type BlockNumber;
+		type PalletInfo: frame_support::traits::PalletInfo;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_storage! {
@@ -88,6 +90,8 @@ mod tests {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type PalletInfo = ();
+		type DbWeight = ();
 	}
 
 	const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
@@ -417,6 +421,8 @@ mod test2 {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type PalletInfo: frame_support::traits::PalletInfo;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
@@ -444,6 +450,8 @@ mod test2 {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type PalletInfo = ();
+		type DbWeight = ();
 	}
 }
 
@@ -453,6 +461,8 @@ mod test3 {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type PalletInfo: frame_support::traits::PalletInfo;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 	frame_support::decl_module! {
 		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
@@ -470,6 +480,8 @@ mod test3 {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type PalletInfo = ();
+		type DbWeight = ();
 	}
 }
 
@@ -482,6 +494,8 @@ mod test_append_and_len {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type PalletInfo: frame_support::traits::PalletInfo;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
@@ -514,6 +528,8 @@ mod test_append_and_len {
 	impl Trait for Test {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type PalletInfo = ();
+		type DbWeight = ();
 	}
 
 	#[test]
@@ -18,6 +18,8 @@
 pub trait Trait {
 	type Origin;
 	type BlockNumber: codec::Codec + codec::EncodeLike + Default + Clone;
+	type PalletInfo: frame_support::traits::PalletInfo;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
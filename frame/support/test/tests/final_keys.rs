@@ -26,6 +26,8 @@ mod no_instance {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber: Encode + Decode + EncodeLike + Default + Clone;
+		type PalletInfo: frame_support::traits::PalletInfo;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
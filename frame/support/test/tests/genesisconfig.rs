@@ -17,6 +17,8 @@
 
 pub trait Trait {
 	type BlockNumber: codec::Codec + codec::EncodeLike + Default;
+	type PalletInfo: frame_support::traits::PalletInfo;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	type Origin;
 }
 
@@ -34,6 +36,8 @@ struct Test;
 
 impl Trait for Test {
 	type BlockNumber = u32;
+	type PalletInfo = ();
+	type DbWeight = ();
 	type Origin = ();
 }
 
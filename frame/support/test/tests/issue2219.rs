@@ -18,6 +18,7 @@
 use frame_support::sp_runtime::generic;
 use frame_support::sp_runtime::traits::{BlakeTwo256, Block as _, Verify};
 use frame_support::codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_core::{H256, sr25519};
 use serde::{Serialize, Deserialize};
 
@@ -33,12 +34,12 @@ mod module {
 	);
 	pub type Requests<T> = Vec<Request<T>>;
 
-	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug)]
+	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug, TypeInfo)]
 	pub enum Role {
 		Storage,
 	}
 
-	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug)]
+	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug, TypeInfo)]
 	pub struct RoleParameters<T: Trait> {
 		// minimum actors to maintain - if role is unstaking
 		// and remaining actors would be less that this value - prevent or punish for unstaking
@@ -87,7 +88,7 @@ mod module {
 		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=system {}
 	}
 
-	#[derive(Encode, Decode, Copy, Clone, Serialize, Deserialize)]
+	#[derive(Encode, Decode, Copy, Clone, Serialize, Deserialize, TypeInfo)]
 	pub struct Data<T: Trait> {
 		pub	data: T::BlockNumber,
 	}
@@ -165,6 +166,7 @@ impl system::Trait for Runtime {
 	type AccountId = AccountId;
 	type Event = Event;
 	type PalletInfo = ();
+	type DbWeight = ();
 	type Call = Call;
 }
 
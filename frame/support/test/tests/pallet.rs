@@ -0,0 +1,388 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! General tests for the pallet attribute macro, test for:
+//! * storages use the pallet name given in `construct_runtime` as prefix
+//! * calls, errors, events, constants and storages are exposed in the metadata
+//! * hooks and genesis build are called
+
+use frame_support::{
+	dispatch::{DispatchInfo, GetDispatchInfo, UnfilteredDispatchable},
+	traits::{OnInitialize, OnIdle, OnFinalize, GetCallName},
+	weights::{DispatchClass, Pays},
+	storage::unhashed,
+};
+use sp_runtime::{generic, traits::{BlakeTwo256, Verify}, BuildStorage, DispatchError};
+use sp_core::{H256, sr25519};
+use sp_io::hashing::{twox_64, twox_128, blake2_128};
+
+mod system;
+
+pub type OriginFor<T> = <T as system::Trait>::Origin;
+pub type BlockNumberFor<T> = <T as system::Trait>::BlockNumber;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use super::{OriginFor, BlockNumberFor};
+
+	#[pallet::config]
+	pub trait Config: crate::system::Trait {
+		/// Some comment
+		/// Some comment
+		#[pallet::constant]
+		type MyGetParam: Get<u32>;
+
+		type Event: From<Event<Self>> + IsType<<Self as crate::system::Trait>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			<<Self as Store>::Value>::put(10);
+			10
+		}
+
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			remaining_weight / 2
+		}
+
+		fn on_finalize(_: BlockNumberFor<T>) {
+			<Map<T>>::insert(1, 11);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Doc comment put in metadata
+		#[pallet::weight(Weight::from(*_foo))]
+		pub fn foo(
+			origin: OriginFor<T>,
+			#[pallet::compact] _foo: u32,
+			_bar: u32,
+		) -> DispatchResultWithPostInfo {
+			crate::system::ensure_root(origin)?;
+			Self::deposit_event(Event::Spending(3));
+			Ok(().into())
+		}
+
+		#[pallet::weight(1)]
+		pub fn fail(_origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			Err(Error::<T>::InsufficientProposersBalance.into())
+		}
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Doc comment put into metadata
+		InsufficientProposersBalance,
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(<T as crate::system::Trait>::AccountId = "AccountId")]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Doc comment put in metadata
+		Proposed(<T as crate::system::Trait>::AccountId),
+		/// Doc
+		Spending(u32),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn value)]
+	pub type Value<T> = StorageValue<_, u32>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn map)]
+	pub type Map<T> = StorageMap<_, Blake2_128Concat, u8, u16, ValueQuery>;
+
+	#[pallet::storage]
+	pub type DoubleMap<T> = StorageDoubleMap<_, Blake2_128Concat, u8, Twox64Concat, u16, u32>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {
+		pub initial_value: u32,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			<Value<T>>::put(self.initial_value);
+		}
+	}
+}
+
+frame_support::parameter_types!(
+	pub const MyGetParam: u32 = 10;
+);
+
+pub type Signature = sr25519::Signature;
+pub type AccountId = <Signature as Verify>::Signer;
+pub type BlockNumber = u64;
+
+impl system::Trait for Runtime {
+	type BaseCallFilter = ();
+	type Hash = H256;
+	type Origin = Origin;
+	type BlockNumber = BlockNumber;
+	type AccountId = AccountId;
+	type Event = Event;
+	type PalletInfo = PalletInfo;
+	type DbWeight = ();
+	type Call = Call;
+}
+
+impl pallet::Config for Runtime {
+	type MyGetParam = MyGetParam;
+	type Event = Event;
+}
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: system::{Module, Call, Event<T>},
+		Example: pallet::{Module, Call, Storage, Event<T>, Config},
+	}
+);
+
+pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
+pub type Block = generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<u32, Call, Signature, ()>;
+
+#[test]
+fn call_expand() {
+	let call_foo = pallet::Call::<Runtime>::foo(3, 0);
+	assert_eq!(
+		call_foo.get_dispatch_info(),
+		DispatchInfo { weight: 3, proof_size: 0, class: DispatchClass::Normal, pays_fee: Pays::Yes },
+	);
+	assert_eq!(call_foo.get_call_name(), "foo");
+	assert_eq!(pallet::Call::<Runtime>::get_call_names(), &["foo", "fail"]);
+	assert_eq!(call_foo.clone(), call_foo);
+	assert_eq!(format!("{:?}", call_foo), "foo(3, 0)");
+	assert!(call_foo != pallet::Call::<Runtime>::fail());
+}
+
+#[test]
+fn call_dispatch_and_error_expand() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		assert_eq!(
+			pallet::Call::<Runtime>::foo(3, 0).dispatch_bypass_filter(Origin::root()),
+			Ok(().into()),
+		);
+		assert!(
+			pallet::Call::<Runtime>::foo(3, 0)
+				.dispatch_bypass_filter(Origin::signed(Default::default()))
+				.is_err()
+		);
+		assert_eq!(
+			Example::fail(Origin::root()).map_err(|e| e.error),
+			Err(DispatchError::Module { index: 1, error: 0, message: Some("InsufficientProposersBalance") }),
+		);
+	})
+}
+
+#[test]
+fn storage_expand() {
+	use frame_support::pallet_prelude::*;
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet::Value::<Runtime>::put(1);
+		let k = [twox_128(b"Example"), twox_128(b"Value")].concat();
+		assert_eq!(unhashed::get::<u32>(&k), Some(1u32));
+		assert_eq!(Example::value(), Some(1u32));
+
+		pallet::Map::<Runtime>::insert(1, 2);
+		let mut k = [twox_128(b"Example"), twox_128(b"Map")].concat();
+		k.extend(1u8.using_encoded(blake2_128).iter());
+		k.extend(1u8.encode());
+		assert_eq!(unhashed::get::<u16>(&k), Some(2u16));
+		assert_eq!(Example::map(1), 2);
+		assert_eq!(Example::map(2), 0);
+
+		pallet::DoubleMap::<Runtime>::insert(&1, &2, &3);
+		let mut k = [twox_128(b"Example"), twox_128(b"DoubleMap")].concat();
+		k.extend(1u8.using_encoded(blake2_128).iter());
+		k.extend(1u8.encode());
+		k.extend(2u16.using_encoded(twox_64).iter());
+		k.extend(2u16.encode());
+		assert_eq!(unhashed::get::<u32>(&k), Some(3u32));
+	})
+}
+
+#[test]
+fn hooks_expand() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		assert_eq!(Example::on_initialize(1), 10);
+		assert_eq!(Example::value(), Some(10));
+
+		assert_eq!(Example::on_idle(1, 100), 50);
+
+		Example::on_finalize(1);
+		assert_eq!(Example::map(1), 11);
+	})
+}
+
+#[test]
+fn genesis_config_expand() {
+	let mut t = GenesisConfig {
+		pallet: Some(pallet::GenesisConfig { initial_value: 4 }),
+	}.build_storage().unwrap();
+
+	sp_io::TestExternalities::new(t.clone()).execute_with(|| {
+		assert_eq!(Example::value(), Some(4));
+	});
+
+	<pallet::GenesisConfig as frame_support::traits::GenesisBuild<Runtime>>::assimilate_storage(
+		&pallet::GenesisConfig { initial_value: 5 },
+		&mut t,
+	).unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(Example::value(), Some(5));
+	});
+}
+
+#[test]
+fn event_expand() {
+	use codec::Encode;
+
+	let event = pallet::Event::<Runtime>::Spending(3);
+	assert_eq!(Event::from(event.clone()).encode()[0], 1);
+	assert_eq!(event.clone(), pallet::Event::<Runtime>::Spending(3));
+	assert!(event != pallet::Event::<Runtime>::Proposed(Default::default()));
+}
+
+#[test]
+fn metadata() {
+	use frame_metadata::*;
+	use codec::{Decode, Encode};
+	use frame_support::type_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+
+	fn resolve(types: &PortableRegistry, id: u32) -> &TypeDef<frame_support::type_info::PortableForm> {
+		types.resolve(id).expect("Type is in the registry").type_def()
+	}
+
+	let metadata_encoded = Runtime::metadata().encode();
+	let metadata = match RuntimeMetadataPrefixed::decode(&mut &metadata_encoded[..]).unwrap() {
+		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(metadata)) => metadata,
+		_ => panic!("metadata has been bump, test needs to be updated"),
+	};
+	let types = &metadata.types;
+	let u8_ty = TypeDef::Primitive(TypeDefPrimitive::U8);
+	let u16_ty = TypeDef::Primitive(TypeDefPrimitive::U16);
+	let u32_ty = TypeDef::Primitive(TypeDefPrimitive::U32);
+
+	let pallet_metadata = &metadata.modules[1];
+	assert_eq!(pallet_metadata.name, "Example");
+	assert_eq!(pallet_metadata.index, 1);
+
+	// Storage
+	let storage = pallet_metadata.storage.as_ref().unwrap();
+	assert_eq!(storage.prefix, "Example");
+	let entries = storage.entries.iter()
+		.map(|e| (e.name.as_str(), e.modifier.clone(), e.default.clone()))
+		.collect::<Vec<_>>();
+	assert_eq!(
+		entries,
+		vec![
+			("Value", StorageEntryModifier::Optional, vec![0]),
+			("Map", StorageEntryModifier::Default, vec![0, 0]),
+			("DoubleMap", StorageEntryModifier::Optional, vec![0]),
+		],
+	);
+	match &storage.entries[0].ty {
+		StorageEntryTypeV14::Plain(value) => assert_eq!(resolve(types, *value), &u32_ty),
+		ty => panic!("Unexpected storage type {:?}", ty),
+	}
+	match &storage.entries[1].ty {
+		StorageEntryTypeV14::Map { hasher, key, value } => {
+			assert_eq!(hasher, &StorageHasher::Blake2_128Concat);
+			assert_eq!(resolve(types, *key), &u8_ty);
+			assert_eq!(resolve(types, *value), &u16_ty);
+		},
+		ty => panic!("Unexpected storage type {:?}", ty),
+	}
+	match &storage.entries[2].ty {
+		StorageEntryTypeV14::DoubleMap { hasher, key1, key2, value, key2_hasher } => {
+			assert_eq!(hasher, &StorageHasher::Blake2_128Concat);
+			assert_eq!(key2_hasher, &StorageHasher::Twox64Concat);
+			assert_eq!(resolve(types, *key1), &u8_ty);
+			assert_eq!(resolve(types, *key2), &u16_ty);
+			assert_eq!(resolve(types, *value), &u32_ty);
+		},
+		ty => panic!("Unexpected storage type {:?}", ty),
+	}
+
+	// Calls
+	let calls = match resolve(types, pallet_metadata.calls.unwrap()) {
+		TypeDef::Variant(variants) => variants.clone(),
+		def => panic!("Unexpected call type {:?}", def),
+	};
+	assert_eq!(
+		calls.iter().map(|v| (v.name().as_str(), v.index())).collect::<Vec<_>>(),
+		vec![("foo", 0), ("fail", 1)],
+	);
+	assert_eq!(calls[0].docs(), &[" Doc comment put in metadata".to_string()]);
+	let foo_args = calls[0].fields().iter()
+		.map(|f| (f.name().unwrap().as_str(), f.type_name().unwrap().as_str()))
+		.collect::<Vec<_>>();
+	assert_eq!(foo_args, vec![("_foo", "u32"), ("_bar", "u32")]);
+	assert!(matches!(resolve(types, *calls[0].fields()[0].ty()), TypeDef::Compact(_)));
+	assert_eq!(resolve(types, *calls[0].fields()[1].ty()), &u32_ty);
+
+	// Event
+	let events = match resolve(types, pallet_metadata.event.unwrap()) {
+		TypeDef::Variant(variants) => variants.clone(),
+		def => panic!("Unexpected event type {:?}", def),
+	};
+	assert_eq!(
+		events.iter().map(|v| (v.name().as_str(), v.index())).collect::<Vec<_>>(),
+		vec![("Proposed", 0), ("Spending", 1)],
+	);
+	assert_eq!(events[0].docs(), &[" Doc comment put in metadata".to_string()]);
+	assert_eq!(resolve(types, *events[1].fields()[0].ty()), &u32_ty);
+
+	// Constants
+	assert_eq!(pallet_metadata.constants.len(), 1);
+	let constant = &pallet_metadata.constants[0];
+	assert_eq!(constant.name, "MyGetParam");
+	assert_eq!(resolve(types, constant.ty), &u32_ty);
+	assert_eq!(constant.value, vec![10, 0, 0, 0]);
+	assert_eq!(
+		constant.documentation,
+		vec![" Some comment".to_string(), " Some comment".to_string()],
+	);
+
+	// Errors
+	match resolve(types, pallet_metadata.error.unwrap()) {
+		TypeDef::Variant(variants) => {
+			assert_eq!(variants.len(), 1);
+			assert_eq!(variants[0].name(), "InsufficientProposersBalance");
+			assert_eq!(variants[0].docs(), &[" Doc comment put into metadata".to_string()]);
+		},
+		def => panic!("Unexpected error type {:?}", def),
+	}
+}
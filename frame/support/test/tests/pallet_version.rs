@@ -0,0 +1,237 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests related to the pallet version.
+
+#![recursion_limit="128"]
+
+use codec::{Decode, Encode};
+use sp_runtime::{generic, traits::{BlakeTwo256, Verify}, BuildStorage};
+use frame_support::{
+	traits::{PALLET_VERSION_STORAGE_KEY_POSTFIX, PalletVersion, OnRuntimeUpgrade, GetPalletVersion},
+	crate_to_pallet_version, weights::Weight,
+};
+use sp_core::{H256, sr25519};
+
+mod system;
+
+/// A version that we will check for in the tests
+const SOME_TEST_VERSION: PalletVersion = PalletVersion { major: 3000, minor: 30, patch: 13 };
+
+/// Checks that `on_runtime_upgrade` sets the latest pallet version when being called without
+/// being provided by the user.
+mod module1 {
+	use super::*;
+
+	pub trait Trait: system::Trait {}
+
+	frame_support::decl_module! {
+		pub struct Module<T: Trait> for enum Call where
+			origin: <T as system::Trait>::Origin,
+			system = system,
+		{}
+	}
+}
+
+/// Checks that `on_runtime_upgrade` sets the latest pallet version when being called and also
+/// that the migration helper only runs the migration while the stored version is below the
+/// target version.
+mod module2 {
+	use super::*;
+
+	pub trait Trait<I=DefaultInstance>: system::Trait {}
+
+	frame_support::decl_module! {
+		pub struct Module<T: Trait<I>, I: Instance=DefaultInstance> for enum Call where
+			origin: <T as system::Trait>::Origin,
+			system = system
+		{
+			fn on_runtime_upgrade() -> Weight {
+				assert_eq!(crate_to_pallet_version!(), Self::current_version());
+
+				frame_support::migration::migrate_to_version::<Self>(
+					crate_to_pallet_version!(),
+					|| {
+						Migrations::<I>::mutate(|m| *m += 1);
+						10
+					},
+				)
+			}
+		}
+	}
+
+	frame_support::decl_storage! {
+		trait Store for Module<T: Trait<I>, I: Instance=DefaultInstance> as Module2 {
+			pub Migrations get(fn migrations): u32;
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet3 {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: crate::system::Trait {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl module1::Trait for Runtime {}
+impl module2::Trait for Runtime {}
+impl module2::Trait<module2::Instance1> for Runtime {}
+impl module2::Trait<module2::Instance2> for Runtime {}
+
+impl pallet3::Config for Runtime {}
+
+pub type Signature = sr25519::Signature;
+pub type AccountId = <Signature as Verify>::Signer;
+pub type BlockNumber = u64;
+pub type Index = u64;
+
+impl system::Trait for Runtime {
+	type BaseCallFilter = ();
+	type Hash = H256;
+	type Origin = Origin;
+	type BlockNumber = BlockNumber;
+	type AccountId = AccountId;
+	type Event = Event;
+	type PalletInfo = PalletInfo;
+	type DbWeight = ();
+	type Call = Call;
+}
+
+frame_support::construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: system::{Module, Call, Event<T>},
+		Module1: module1::{Module, Call},
+		Module2: module2::{Module, Call, Storage},
+		Module2_1: module2::<Instance1>::{Module, Call, Storage},
+		Module2_2: module2::<Instance2>::{Module, Call, Storage},
+		Pallet3: pallet3::{Module, Call},
+	}
+);
+
+pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
+pub type Block = generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<u32, Call, Signature, ()>;
+
+/// Returns the storage key for `PalletVersion` for the given `pallet`.
+fn get_pallet_version_storage_key_for_pallet(pallet: &str) -> [u8; 32] {
+	let pallet_name = sp_io::hashing::twox_128(pallet.as_bytes());
+	let postfix = sp_io::hashing::twox_128(PALLET_VERSION_STORAGE_KEY_POSTFIX);
+
+	let mut final_key = [0u8; 32];
+	final_key[..16].copy_from_slice(&pallet_name);
+	final_key[16..].copy_from_slice(&postfix);
+
+	final_key
+}
+
+/// Checks the version of the given `pallet`.
+///
+/// It is expected that the pallet version can be found in the storage and equals the
+/// current crate version.
+fn check_pallet_version(pallet: &str) {
+	let key = get_pallet_version_storage_key_for_pallet(pallet);
+	let value = sp_io::storage::get(&key).expect("Pallet version exists");
+	let version = PalletVersion::decode(&mut &value[..])
+		.expect("Pallet version is encoded correctly");
+
+	assert_eq!(crate_to_pallet_version!(), version);
+}
+
+/// The names of all pallets in the runtime.
+const PALLETS: &[&str] = &["System", "Module1", "Module2", "Module2_1", "Module2_2", "Pallet3"];
+
+#[test]
+fn on_runtime_upgrade_sets_the_pallet_versions_in_storage() {
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		AllModules::on_runtime_upgrade();
+
+		PALLETS.iter().filter(|p| **p != "System").for_each(|p| check_pallet_version(p));
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_overwrites_old_version() {
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		let key = get_pallet_version_storage_key_for_pallet("Module2");
+		sp_io::storage::set(&key, &SOME_TEST_VERSION.encode());
+
+		AllModules::on_runtime_upgrade();
+
+		PALLETS.iter().filter(|p| **p != "System").for_each(|p| check_pallet_version(p));
+	});
+}
+
+#[test]
+fn migration_only_runs_while_the_stored_version_is_below() {
+	sp_io::TestExternalities::new_empty().execute_with(|| {
+		// No version in storage, the migration needs to run.
+		assert_eq!(Module2::storage_version(), None);
+		assert_eq!(Module2::on_runtime_upgrade(), 10);
+		assert_eq!(Module2::migrations(), 1);
+		assert_eq!(Module2::storage_version(), Some(Module2::current_version()));
+
+		// The version got bumped, running the upgrade again is a noop.
+		assert_eq!(Module2::on_runtime_upgrade(), 0);
+		assert_eq!(Module2::migrations(), 1);
+
+		// A newer version in storage doesn't trigger the migration either.
+		let key = get_pallet_version_storage_key_for_pallet("Module2");
+		sp_io::storage::set(&key, &SOME_TEST_VERSION.encode());
+		assert_eq!(Module2::on_runtime_upgrade(), 0);
+		assert_eq!(Module2::migrations(), 1);
+
+		// Instances are versioned independently.
+		assert_eq!(Module2_1::storage_version(), None);
+		assert_eq!(Module2_1::on_runtime_upgrade(), 10);
+		assert_eq!(module2::Migrations::<module2::Instance1>::get(), 1);
+	});
+}
+
+#[test]
+fn genesis_init_puts_pallet_version_into_storage() {
+	let storage = GenesisConfig {}.build_storage().expect("Builds genesis storage");
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		PALLETS.iter().for_each(|p| check_pallet_version(p));
+
+		let system_version = System::storage_version().expect("System version should be set");
+		assert_eq!(System::current_version(), system_version);
+	});
+}
+
+#[test]
+fn pallet_versions_are_ordered() {
+	assert!(PalletVersion::new(1, 0, 0) < PalletVersion::new(1, 0, 1));
+	assert!(PalletVersion::new(1, 0, 9) < PalletVersion::new(1, 1, 0));
+	assert!(PalletVersion::new(1, 9, 9) < PalletVersion::new(2, 0, 0));
+	assert!(crate_to_pallet_version!() < SOME_TEST_VERSION);
+}
@@ -7,6 +7,8 @@ macro_rules! reserved {
 				pub trait Trait {
 					type Origin;
 					type BlockNumber: Into<u32>;
+					type PalletInfo: frame_support::traits::PalletInfo;
+					type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 				}
 
 				pub mod system {
@@ -26,6 +26,8 @@ use sp_std::result;
 pub trait Trait {
 	type Origin;
 	type BlockNumber: Encode + Decode + EncodeLike + Default + Clone;
+	type PalletInfo: frame_support::traits::PalletInfo;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
@@ -56,6 +58,8 @@ struct Runtime;
 impl Trait for Runtime {
 	type Origin = u32;
 	type BlockNumber = u32;
+	type PalletInfo = ();
+	type DbWeight = ();
 }
 
 #[test]
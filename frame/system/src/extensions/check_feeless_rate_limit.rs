@@ -0,0 +1,199 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Encode, Decode};
+use crate::{Trait, Module};
+use frame_support::{
+	traits::Get,
+	weights::{DispatchInfo, Pays},
+	StorageMap,
+};
+use sp_runtime::{
+	traits::{SignedExtension, DispatchInfoOf, Dispatchable, Saturating},
+	transaction_validity::{
+		ValidTransaction, TransactionValidityError, InvalidTransaction, TransactionValidity,
+	},
+};
+
+/// The `InvalidTransaction::Custom` code of a feeless transaction exceeding the allowance of its
+/// sender.
+pub const FEELESS_RATE_LIMIT_EXCEEDED: u8 = 200;
+
+/// The `InvalidTransaction::Custom` code of a feeless transaction sent by an account that doesn't
+/// exist.
+pub const FEELESS_SENDER_UNKNOWN: u8 = 201;
+
+/// Rate limit transactions which don't pay fees.
+///
+/// A signed transaction is feeless if its dispatch info says it doesn't pay fees, e.g. because of
+/// a `FeelessIf` weight annotation that holds. An account can make at most
+/// `MaxFeelessTransactions` of them within a period of `FeelessRateLimitPeriod` blocks, starting
+/// at its first feeless transaction of the period. Transactions that pay fees are not limited.
+///
+/// Only existing accounts can send feeless transactions, so that the allowance can't be renewed by
+/// switching to fresh accounts for free. This makes each allowance cost at least the existential
+/// deposit of an account. This extension must come before `CheckNonce`, which creates the account
+/// of a sender that doesn't exist.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default)]
+pub struct CheckFeelessRateLimit<T: Trait + Send + Sync>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckFeelessRateLimit<T> {
+	/// Creates new `SignedExtension` to rate limit feeless transactions.
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+
+	/// Returns the rate limit period and feeless transaction count of `who` after another feeless
+	/// transaction, if it is within the allowance.
+	fn next_count(
+		who: &T::AccountId,
+	) -> Result<(T::BlockNumber, u32), TransactionValidityError> {
+		if !crate::Account::<T>::contains_key(who) {
+			return Err(InvalidTransaction::Custom(FEELESS_SENDER_UNKNOWN).into())
+		}
+		let now = Module::<T>::block_number();
+		let (start, count) = match Module::<T>::feeless_transactions(who) {
+			Some((start, count)) if now < start.saturating_add(T::FeelessRateLimitPeriod::get()) =>
+				(start, count),
+			_ => (now, 0),
+		};
+		if count >= T::MaxFeelessTransactions::get() {
+			Err(InvalidTransaction::Custom(FEELESS_RATE_LIMIT_EXCEEDED).into())
+		} else {
+			Ok((start, count + 1))
+		}
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckFeelessRateLimit<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckFeelessRateLimit")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckFeelessRateLimit<T> where
+	T::Call: Dispatchable<Info=DispatchInfo>
+{
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+	const IDENTIFIER: &'static str = "CheckFeelessRateLimit";
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		if info.pays_fee == Pays::No {
+			let next = Self::next_count(who)?;
+			crate::FeelessTransactions::<T>::insert(who, next);
+		}
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if info.pays_fee == Pays::No {
+			Self::next_count(who)?;
+		}
+		Ok(ValidTransaction::default())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{Test, new_test_ext, CALL, System};
+
+	#[test]
+	fn signed_ext_check_feeless_rate_limit_works() {
+		new_test_ext().execute_with(|| {
+			let feeless = DispatchInfo { pays_fee: Pays::No, ..Default::default() };
+			let paying = DispatchInfo::default();
+			let len = 0_usize;
+			let validate = |who: u64, info: &DispatchInfo| {
+				CheckFeelessRateLimit::<Test>::new().validate(&who, CALL, info, len)
+			};
+			let pre_dispatch = |who: u64, info: &DispatchInfo| {
+				CheckFeelessRateLimit::<Test>::new().pre_dispatch(&who, CALL, info, len)
+			};
+			let max = <Test as Trait>::MaxFeelessTransactions::get();
+			let period = <Test as Trait>::FeelessRateLimitPeriod::get();
+			System::set_block_number(1);
+			crate::Account::<Test>::insert(1, crate::AccountInfo::default());
+			crate::Account::<Test>::insert(2, crate::AccountInfo::default());
+
+			for _ in 0..max {
+				assert!(validate(1, &feeless).is_ok());
+				assert!(pre_dispatch(1, &feeless).is_ok());
+			}
+			assert_eq!(System::feeless_transactions(1), Some((1, max)));
+
+			// the allowance is used up, but not for other accounts or for paying transactions.
+			let exceeded: TransactionValidityError =
+				InvalidTransaction::Custom(FEELESS_RATE_LIMIT_EXCEEDED).into();
+			assert_eq!(validate(1, &feeless), Err(exceeded));
+			assert_eq!(pre_dispatch(1, &feeless), Err(exceeded));
+			assert!(pre_dispatch(2, &feeless).is_ok());
+			assert!(pre_dispatch(1, &paying).is_ok());
+
+			// the allowance is renewed with the next period.
+			System::set_block_number(period);
+			assert!(validate(1, &feeless).is_err());
+			System::set_block_number(1 + period);
+			assert!(validate(1, &feeless).is_ok());
+			assert!(pre_dispatch(1, &feeless).is_ok());
+			assert_eq!(System::feeless_transactions(1), Some((1 + period, 1)));
+		})
+	}
+
+	#[test]
+	fn signed_ext_check_feeless_rate_limit_requires_existing_sender() {
+		new_test_ext().execute_with(|| {
+			let feeless = DispatchInfo { pays_fee: Pays::No, ..Default::default() };
+			let paying = DispatchInfo::default();
+			let len = 0_usize;
+			let unknown: TransactionValidityError =
+				InvalidTransaction::Custom(FEELESS_SENDER_UNKNOWN).into();
+			let ext = CheckFeelessRateLimit::<Test>::new();
+			assert_eq!(ext.validate(&1, CALL, &feeless, len), Err(unknown));
+			assert_eq!(ext.clone().pre_dispatch(&1, CALL, &feeless, len), Err(unknown));
+			assert!(ext.validate(&1, CALL, &paying, len).is_ok());
+			assert_eq!(System::feeless_transactions(1), None);
+
+			crate::Account::<Test>::insert(1, crate::AccountInfo::default());
+			assert!(ext.validate(&1, CALL, &feeless, len).is_ok());
+			assert!(ext.pre_dispatch(&1, CALL, &feeless, len).is_ok());
+		})
+	}
+}
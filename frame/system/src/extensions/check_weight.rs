@@ -27,7 +27,9 @@ use sp_runtime::{
 };
 use frame_support::{
 	traits::{Get},
-	weights::{PostDispatchInfo, DispatchInfo, DispatchClass, priority::FrameTransactionPriority},
+	weights::{
+		PostDispatchInfo, DispatchInfo, DispatchClass, ProofSize, priority::FrameTransactionPriority,
+	},
 	StorageValue,
 };
 
@@ -156,6 +158,47 @@ impl<T: Trait + Send + Sync> CheckWeight<T> where
 		}
 	}
 
+	/// Checks if the current extrinsic can fit into the block with respect to block proof size
+	/// limits.
+	///
+	/// Upon successes, it returns the new block proof size as a `Result`.
+	fn check_block_proof_size(
+		info: &DispatchInfoOf<T::Call>,
+	) -> Result<ProofSize, TransactionValidityError> {
+		let current_size = Module::<T>::block_proof_size();
+		match info.class {
+			// Mandatory dispatches are included unconditionally, we only account for them.
+			DispatchClass::Mandatory => Ok(current_size.saturating_add(info.proof_size)),
+			DispatchClass::Normal | DispatchClass::Operational => {
+				let maximum_size = T::MaximumBlockProofSize::get();
+				let limit = Self::get_dispatch_limit_ratio(info.class) * maximum_size;
+				let next_size = current_size.checked_add(info.proof_size)
+					.ok_or(InvalidTransaction::ExhaustsResources)?;
+				if next_size > limit {
+					Err(InvalidTransaction::ExhaustsResources.into())
+				} else {
+					Ok(next_size)
+				}
+			},
+		}
+	}
+
+	/// Checks if the current extrinsic alone does not exceed the proof size available to its
+	/// dispatch class.
+	fn check_extrinsic_proof_size(
+		info: &DispatchInfoOf<T::Call>,
+	) -> Result<(), TransactionValidityError> {
+		if info.class == DispatchClass::Mandatory {
+			return Ok(())
+		}
+		let limit = Self::get_dispatch_limit_ratio(info.class) * T::MaximumBlockProofSize::get();
+		if info.proof_size > limit {
+			Err(InvalidTransaction::ExhaustsResources.into())
+		} else {
+			Ok(())
+		}
+	}
+
 	/// get the priority of an extrinsic denoted by `info`.
 	///
 	/// Operational transaction will be given a fixed initial amount to be fairly distinguished from
@@ -181,23 +224,25 @@ impl<T: Trait + Send + Sync> CheckWeight<T> where
 
 	/// Do the pre-dispatch checks. This can be applied to both signed and unsigned.
 	///
-	/// It checks and notes the new weight and length.
+	/// It checks and notes the new weight, length and proof size.
 	fn do_pre_dispatch(
 		info: &DispatchInfoOf<T::Call>,
 		len: usize,
 	) -> Result<(), TransactionValidityError> {
 		let next_len = Self::check_block_length(info, len)?;
 		let next_weight = Self::check_block_weight(info)?;
+		let next_proof_size = Self::check_block_proof_size(info)?;
 		Self::check_extrinsic_weight(info)?;
 
 		crate::AllExtrinsicsLen::put(next_len);
 		crate::BlockWeight::put(next_weight);
+		crate::BlockProofSize::put(next_proof_size);
 		Ok(())
 	}
 
 	/// Do the validate checks. This can be applied to both signed and unsigned.
 	///
-	/// It only checks that the block weight and length limit will not exceed.
+	/// It only checks that the block weight, length and proof size limits will not exceed.
 	fn do_validate(
 		info: &DispatchInfoOf<T::Call>,
 		len: usize,
@@ -208,6 +253,7 @@ impl<T: Trait + Send + Sync> CheckWeight<T> where
 		// call runs on an empty block anyway, by this we prevent `on_initialize` weight
 		// consumption from causing false negatives.
 		Self::check_extrinsic_weight(info)?;
+		Self::check_extrinsic_proof_size(info)?;
 
 		Ok(ValidTransaction { priority: Self::get_priority(info), ..Default::default() })
 	}
@@ -309,7 +355,7 @@ impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckWeight<T> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{BlockWeight, AllExtrinsicsLen};
+	use crate::{BlockWeight, AllExtrinsicsLen, BlockProofSize};
 	use crate::mock::{Test, CALL, new_test_ext, System};
 	use sp_std::marker::PhantomData;
 	use frame_support::{assert_ok, assert_noop};
@@ -323,6 +369,10 @@ mod tests {
 		<Test as Trait>::AvailableBlockRatio::get() * <Test as Trait>::MaximumBlockLength::get()
 	}
 
+	fn normal_proof_size_limit() -> ProofSize {
+		<Test as Trait>::AvailableBlockRatio::get() * <Test as Trait>::MaximumBlockProofSize::get()
+	}
+
 	#[test]
 	fn mandatory_extrinsic_doesnt_care_about_limits() {
 		fn check(call: impl FnOnce(&DispatchInfo, usize)) {
@@ -480,7 +530,12 @@ mod tests {
 	fn signed_ext_check_weight_works_operational_tx() {
 		new_test_ext().execute_with(|| {
 			let normal = DispatchInfo { weight: 100, ..Default::default() };
-			let op = DispatchInfo { weight: 100, class: DispatchClass::Operational, pays_fee: Pays::Yes };
+			let op = DispatchInfo {
+				weight: 100,
+				proof_size: 0,
+				class: DispatchClass::Operational,
+				pays_fee: Pays::Yes,
+			};
 			let len = 0_usize;
 			let normal_limit = normal_weight_limit();
 
@@ -504,8 +559,18 @@ mod tests {
 	#[test]
 	fn signed_ext_check_weight_works() {
 		new_test_ext().execute_with(|| {
-			let normal = DispatchInfo { weight: 100, class: DispatchClass::Normal, pays_fee: Pays::Yes };
-			let op = DispatchInfo { weight: 100, class: DispatchClass::Operational, pays_fee: Pays::Yes };
+			let normal = DispatchInfo {
+				weight: 100,
+				proof_size: 0,
+				class: DispatchClass::Normal,
+				pays_fee: Pays::Yes,
+			};
+			let op = DispatchInfo {
+				weight: 100,
+				proof_size: 0,
+				class: DispatchClass::Operational,
+				pays_fee: Pays::Yes,
+			};
 			let len = 0_usize;
 
 			let priority = CheckWeight::<Test>(PhantomData)
@@ -538,7 +603,12 @@ mod tests {
 			reset_check_weight(&normal, normal_limit + 1, true);
 
 			// Operational ones don't have this limit.
-			let op = DispatchInfo { weight: 0, class: DispatchClass::Operational, pays_fee: Pays::Yes };
+			let op = DispatchInfo {
+				weight: 0,
+				proof_size: 0,
+				class: DispatchClass::Operational,
+				pays_fee: Pays::Yes,
+			};
 			reset_check_weight(&op, normal_limit, false);
 			reset_check_weight(&op, normal_limit + 100, false);
 			reset_check_weight(&op, 1024, false);
@@ -653,4 +723,78 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn signed_ext_accounts_for_block_proof_size() {
+		new_test_ext().execute_with(|| {
+			let normal = DispatchInfo { proof_size: 100, ..Default::default() };
+			let op = DispatchInfo {
+				proof_size: 100,
+				class: DispatchClass::Operational,
+				..Default::default()
+			};
+			let len = 0_usize;
+			let reset_check_weight = |i, f, s| {
+				BlockProofSize::put(s);
+				let r = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, i, len);
+				if f { assert!(r.is_err()) } else { assert!(r.is_ok()) }
+			};
+
+			reset_check_weight(&normal, false, 0);
+			assert_eq!(System::block_proof_size(), 100);
+			reset_check_weight(&normal, false, normal_proof_size_limit() - 100);
+			assert_eq!(System::block_proof_size(), normal_proof_size_limit());
+			reset_check_weight(&normal, true, normal_proof_size_limit() - 99);
+
+			// operational ones can use the remaining space.
+			let max = <Test as Trait>::MaximumBlockProofSize::get();
+			reset_check_weight(&op, false, normal_proof_size_limit());
+			reset_check_weight(&op, false, max - 100);
+			reset_check_weight(&op, true, max - 99);
+		})
+	}
+
+	#[test]
+	fn signed_ext_rejects_extrinsic_exceeding_proof_size_limit() {
+		new_test_ext().execute_with(|| {
+			let normal_limit = normal_proof_size_limit();
+			let max = <Test as Trait>::MaximumBlockProofSize::get();
+			let len = 0_usize;
+
+			let normal = DispatchInfo { proof_size: normal_limit, ..Default::default() };
+			assert_ok!(CheckWeight::<Test>(PhantomData).validate(&1, CALL, &normal, len));
+			let normal = DispatchInfo { proof_size: normal_limit + 1, ..Default::default() };
+			assert_noop!(
+				CheckWeight::<Test>(PhantomData).validate(&1, CALL, &normal, len),
+				InvalidTransaction::ExhaustsResources,
+			);
+
+			let op = DispatchInfo {
+				proof_size: max,
+				class: DispatchClass::Operational,
+				..Default::default()
+			};
+			assert_ok!(CheckWeight::<Test>(PhantomData).validate(&1, CALL, &op, len));
+			let op = DispatchInfo { proof_size: max + 1, ..op };
+			assert_noop!(
+				CheckWeight::<Test>(PhantomData).validate(&1, CALL, &op, len),
+				InvalidTransaction::ExhaustsResources,
+			);
+		})
+	}
+
+	#[test]
+	fn mandatory_extrinsic_ignores_proof_size_limit() {
+		new_test_ext().execute_with(|| {
+			let max = <Test as Trait>::MaximumBlockProofSize::get();
+			let mandatory = DispatchInfo {
+				proof_size: max + 1,
+				class: DispatchClass::Mandatory,
+				..Default::default()
+			};
+			BlockProofSize::put(max);
+			assert_ok!(CheckWeight::<Test>::do_pre_dispatch(&mandatory, 0));
+			assert_eq!(System::block_proof_size(), 2 * max + 1);
+		})
+	}
 }
@@ -15,6 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod check_feeless_rate_limit;
 pub mod check_genesis;
 pub mod check_mortality;
 pub mod check_nonce;
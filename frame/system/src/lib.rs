@@ -121,12 +121,13 @@ use frame_support::{
 		StoredMap, EnsureOrigin, OriginTrait, Filter,
 	},
 	weights::{
-		Weight, RuntimeDbWeight, DispatchInfo, DispatchClass,
-		extract_actual_weight,
+		Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, RuntimeDbWeight, DispatchInfo,
+		DispatchClass, extract_actual_weight,
 	},
 	dispatch::DispatchResultWithPostInfo,
 };
 use codec::{Encode, Decode, FullCodec, EncodeLike};
+use frame_support::type_info::TypeInfo;
 
 #[cfg(any(feature = "std", test))]
 use sp_io::TestExternalities;
@@ -144,7 +145,7 @@ mod default_weights;
 pub use extensions::{
 	check_mortality::CheckMortality, check_genesis::CheckGenesis, check_nonce::CheckNonce,
 	check_spec_version::CheckSpecVersion, check_tx_version::CheckTxVersion,
-	check_weight::CheckWeight,
+	check_weight::CheckWeight, check_feeless_rate_limit::CheckFeelessRateLimit,
 };
 // Backward compatible re-export.
 pub use extensions::check_mortality::CheckMortality as CheckEra;
@@ -161,12 +162,33 @@ pub fn extrinsics_data_root<H: Hash>(xts: Vec<Vec<u8>>) -> H::Output {
 
 pub trait WeightInfo {
 	fn remark() -> Weight;
+	fn remark_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_heap_pages() -> Weight;
+	fn set_heap_pages_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_changes_trie_config() -> Weight;
+	fn set_changes_trie_config_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn set_storage(i: u32, ) -> Weight;
+	fn set_storage_proof_size(_i: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn kill_storage(i: u32, ) -> Weight;
+	fn kill_storage_proof_size(_i: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn kill_prefix(p: u32, ) -> Weight;
+	fn kill_prefix_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn suicide() -> Weight;
+	fn suicide_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: 'static + Eq + Clone {
@@ -245,6 +267,18 @@ pub trait Trait: 'static + Eq + Clone {
 	/// containing `Normal` dispatch class calls.
 	type MaximumExtrinsicWeight: Get<Weight>;
 
+	/// The maximum size (in bytes) of the storage proof of a block. This bounds the amount of
+	/// state a light client or a relay chain validator has to fetch to re-execute the block.
+	type MaximumBlockProofSize: Get<ProofSize>;
+
+	/// The maximum number of transactions an account can make without paying fees within
+	/// `FeelessRateLimitPeriod` blocks, enforced by the [`CheckFeelessRateLimit`] extension.
+	type MaxFeelessTransactions: Get<u32>;
+
+	/// The number of blocks after which the feeless transaction allowance of an account is
+	/// renewed.
+	type FeelessRateLimitPeriod: Get<Self::BlockNumber>;
+
 	/// The maximum length of a block (in bytes).
 	type MaximumBlockLength: Get<u32>;
 
@@ -286,7 +320,7 @@ pub type Key = Vec<u8>;
 pub type KeyValue = (Vec<u8>, Vec<u8>);
 
 /// A phase of a block's execution.
-#[derive(Encode, Decode, RuntimeDebug)]
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, PartialEq, Eq, Clone))]
 pub enum Phase {
 	/// Applying an extrinsic.
@@ -304,7 +338,7 @@ impl Default for Phase {
 }
 
 /// Record of an event happening.
-#[derive(Encode, Decode, RuntimeDebug)]
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, PartialEq, Eq, Clone))]
 pub struct EventRecord<E: Parameter + Member, T> {
 	/// The phase of the block it happened in.
@@ -316,7 +350,7 @@ pub struct EventRecord<E: Parameter + Member, T> {
 }
 
 /// Origin for the System module.
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub enum RawOrigin<AccountId> {
 	/// The system itself ordained this dispatch to happen: this is the highest privilege level.
 	Root,
@@ -359,7 +393,7 @@ type EventIndex = u32;
 pub type RefCount = u32;
 
 /// Information of an account.
-#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct AccountInfo<Index, AccountData> {
 	/// The number of transactions this account has sent.
 	pub nonce: Index,
@@ -373,7 +407,7 @@ pub struct AccountInfo<Index, AccountData> {
 
 /// Stores the `spec_version` and `spec_name` of when the last runtime upgrade
 /// happened.
-#[derive(sp_runtime::RuntimeDebug, Encode, Decode)]
+#[derive(sp_runtime::RuntimeDebug, Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(PartialEq))]
 pub struct LastRuntimeUpgradeInfo {
 	pub spec_version: codec::Compact<u32>,
@@ -413,6 +447,18 @@ decl_storage! {
 		/// Total length (in bytes) for all extrinsics put together, for the current block.
 		AllExtrinsicsLen: Option<u32>;
 
+		/// Total storage proof size (in bytes) declared by all extrinsics, for the current block.
+		BlockProofSize get(fn block_proof_size): ProofSize;
+
+		/// The number of feeless transactions made by an account in its current rate limit period,
+		/// along with the block number the period started at.
+		///
+		/// An entry is reset by the next feeless transaction of its account and removed when the
+		/// account is reaped after its period ended. Hence there is at most one entry per account
+		/// which made a feeless transaction.
+		pub FeelessTransactions get(fn feeless_transactions):
+			map hasher(blake2_128_concat) T::AccountId => Option<(T::BlockNumber, u32)>;
+
 		/// Map of block numbers to block hashes.
 		pub BlockHash get(fn block_hash) build(|_| vec![(T::BlockNumber::zero(), hash69())]):
 			map hasher(twox_64_concat) T::BlockNumber => T::Hash;
@@ -463,6 +509,12 @@ decl_storage! {
 
 		/// The execution phase of the block.
 		ExecutionPhase: Option<Phase>;
+
+		/// The cursor of the ongoing multi-block migrations, see `SteppedMigration`.
+		///
+		/// Not set if no migration is ongoing, `None` if the migrations have not made their first
+		/// step yet.
+		pub MigrationCursor get(fn migration_cursor): Option<Option<Vec<u8>>>;
 	}
 	add_extra_genesis {
 		config(changes_trie_config): Option<ChangesTrieConfiguration>;
@@ -543,6 +595,15 @@ decl_module! {
 		/// The maximum length of a block (in bytes).
 		const MaximumBlockLength: u32 = T::MaximumBlockLength::get();
 
+		/// The maximum storage proof size of a block (in bytes).
+		const MaximumBlockProofSize: ProofSize = T::MaximumBlockProofSize::get();
+
+		/// The maximum number of feeless transactions of an account per rate limit period.
+		const MaxFeelessTransactions: u32 = T::MaxFeelessTransactions::get();
+
+		/// The length of a feeless transaction rate limit period (in blocks).
+		const FeelessRateLimitPeriod: T::BlockNumber = T::FeelessRateLimitPeriod::get();
+
 		fn on_runtime_upgrade() -> frame_support::weights::Weight {
 			if !UpgradedToU32RefCount::get() {
 				Account::<T>::translate::<(T::Index, u8, T::AccountData), _>(|_key, (nonce, rc, data)|
@@ -555,6 +616,15 @@ decl_module! {
 			}
 		}
 
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(UpgradedToU32RefCount::get(), "accounts were not upgraded to u32 ref counts");
+			ensure!(
+				Account::<T>::iter().count() == Self::raw_accounts_count(),
+				"accounts don't decode with u32 ref counts after the upgrade",
+			);
+			Ok(())
+		}
+
 		/// A dispatch that will fill the block weight up to the given ratio.
 		// TODO: This should only be available for testing, rather than in general usage, but
 		// that's not possible at present (since it's within the decl_module macro).
@@ -570,7 +640,10 @@ decl_module! {
 		/// - Base Weight: 0.665 µs, independent of remark length.
 		/// - No DB operations.
 		/// # </weight>
-		#[weight = T::SystemWeightInfo::remark()]
+		#[weight = WithProofSize(
+			T::SystemWeightInfo::remark(),
+			T::SystemWeightInfo::remark_proof_size(),
+		)]
 		fn remark(origin, _remark: Vec<u8>) {
 			ensure_signed(origin)?;
 		}
@@ -583,7 +656,10 @@ decl_module! {
 		/// - Base Weight: 1.405 µs
 		/// - 1 write to HEAP_PAGES
 		/// # </weight>
-		#[weight = (T::SystemWeightInfo::set_heap_pages(), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::SystemWeightInfo::set_heap_pages(), DispatchClass::Operational),
+			T::SystemWeightInfo::set_heap_pages_proof_size(),
+		)]
 		fn set_heap_pages(origin, pages: u64) {
 			ensure_root(origin)?;
 			storage::unhashed::put_raw(well_known_keys::HEAP_PAGES, &pages.encode());
@@ -633,7 +709,10 @@ decl_module! {
 		/// - DB Weight:
 		///     - Writes: Changes Trie, System Digest
 		/// # </weight>
-		#[weight = (T::SystemWeightInfo::set_changes_trie_config(), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::SystemWeightInfo::set_changes_trie_config(), DispatchClass::Operational),
+			T::SystemWeightInfo::set_changes_trie_config_proof_size(),
+		)]
 		pub fn set_changes_trie_config(origin, changes_trie_config: Option<ChangesTrieConfiguration>) {
 			ensure_root(origin)?;
 			match changes_trie_config.clone() {
@@ -658,9 +737,9 @@ decl_module! {
 		/// - Base Weight: 0.568 * i µs
 		/// - Writes: Number of items
 		/// # </weight>
-		#[weight = (
-			T::SystemWeightInfo::set_storage(items.len() as u32),
-			DispatchClass::Operational,
+		#[weight = WithProofSize(
+			(T::SystemWeightInfo::set_storage(items.len() as u32), DispatchClass::Operational),
+			T::SystemWeightInfo::set_storage_proof_size(items.len() as u32),
 		)]
 		fn set_storage(origin, items: Vec<KeyValue>) {
 			ensure_root(origin)?;
@@ -677,9 +756,9 @@ decl_module! {
 		/// - Base Weight: .378 * i µs
 		/// - Writes: Number of items
 		/// # </weight>
-		#[weight = (
-			T::SystemWeightInfo::kill_storage(keys.len() as u32),
-			DispatchClass::Operational,
+		#[weight = WithProofSize(
+			(T::SystemWeightInfo::kill_storage(keys.len() as u32), DispatchClass::Operational),
+			T::SystemWeightInfo::kill_storage_proof_size(keys.len() as u32),
 		)]
 		fn kill_storage(origin, keys: Vec<Key>) {
 			ensure_root(origin)?;
@@ -699,9 +778,9 @@ decl_module! {
 		/// - Base Weight: 0.834 * P µs
 		/// - Writes: Number of subkeys + 1
 		/// # </weight>
-		#[weight = (
-			T::SystemWeightInfo::kill_prefix(_subkeys.saturating_add(1)),
-			DispatchClass::Operational,
+		#[weight = WithProofSize(
+			(T::SystemWeightInfo::kill_prefix(_subkeys.saturating_add(1)), DispatchClass::Operational),
+			T::SystemWeightInfo::kill_prefix_proof_size(_subkeys.saturating_add(1)),
 		)]
 		fn kill_prefix(origin, prefix: Key, _subkeys: u32) {
 			ensure_root(origin)?;
@@ -718,7 +797,10 @@ decl_module! {
 		/// Base Weight: 8.626 µs
 		/// No DB Read or Write operations because caller is already in overlay
 		/// # </weight>
-		#[weight = (T::SystemWeightInfo::suicide(), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::SystemWeightInfo::suicide(), DispatchClass::Operational),
+			T::SystemWeightInfo::suicide_proof_size(),
+		)]
 		pub fn suicide(origin) {
 			let who = ensure_signed(origin)?;
 			let account = Account::<T>::get(&who);
@@ -879,6 +961,17 @@ pub fn ensure_none<OuterOrigin, AccountId>(o: OuterOrigin) -> Result<(), BadOrig
 	}
 }
 
+/// Prelude to be used alongside pallet macro, for ease of use.
+pub mod pallet_prelude {
+	pub use crate::{ensure_signed, ensure_none, ensure_root};
+
+	/// Type alias for the `Origin` associated type of system config.
+	pub type OriginFor<T> = <T as crate::Trait>::Origin;
+
+	/// Type alias for the `BlockNumber` associated type of system config.
+	pub type BlockNumberFor<T> = <T as crate::Trait>::BlockNumber;
+}
+
 /// A type of block initialization to perform.
 pub enum InitKind {
 	/// Leave inspectable storage entries in state.
@@ -906,6 +999,15 @@ pub enum RefStatus {
 	Unreferenced,
 }
 
+#[cfg(feature = "try-runtime")]
+impl<T: Trait> Module<T> {
+	/// The number of entries of `Account`, including those that don't decode.
+	fn raw_accounts_count() -> usize {
+		use frame_support::storage::{migration::count_keys_with_prefix, StoragePrefixedMap};
+		count_keys_with_prefix(&Account::<T>::final_prefix()) as usize
+	}
+}
+
 impl<T: Trait> Module<T> {
 	/// Deposits an event into this block's event record.
 	pub fn deposit_event(event: impl Into<T::Event>) {
@@ -1005,6 +1107,12 @@ impl<T: Trait> Module<T> {
 		});
 	}
 
+	/// Whether multi-block migrations are ongoing, in which case no normal transactions are
+	/// accepted.
+	pub fn migrations_ongoing() -> bool {
+		MigrationCursor::exists()
+	}
+
 	/// Start the execution of a particular block.
 	pub fn initialize(
 		number: &T::BlockNumber,
@@ -1038,6 +1146,7 @@ impl<T: Trait> Module<T> {
 		ExecutionPhase::kill();
 		ExtrinsicCount::kill();
 		AllExtrinsicsLen::kill();
+		BlockProofSize::kill();
 
 		let number = <Number<T>>::take();
 		let parent_hash = <ParentHash<T>>::take();
@@ -1233,6 +1342,12 @@ impl<T: Trait> Module<T> {
 				);
 			}
 		}
+		// Reaping an account must not renew its allowance of feeless transactions.
+		if let Some((start, _)) = FeelessTransactions::<T>::get(who) {
+			if Self::block_number() >= start.saturating_add(T::FeelessRateLimitPeriod::get()) {
+				FeelessTransactions::<T>::remove(who);
+			}
+		}
 		Module::<T>::on_killed_account(who.clone());
 	}
 
@@ -40,6 +40,9 @@ parameter_types! {
 	pub const MaximumExtrinsicWeight: Weight = 768;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
 	pub const MaximumBlockLength: u32 = 1024;
+	pub const MaximumBlockProofSize: ProofSize = 1024;
+	pub const MaxFeelessTransactions: u32 = 2;
+	pub const FeelessRateLimitPeriod: u64 = 10;
 	pub Version: RuntimeVersion = RuntimeVersion {
 		spec_name: sp_version::create_runtime_str!("test"),
 		impl_name: sp_version::create_runtime_str!("system-test"),
@@ -98,6 +101,9 @@ impl Trait for Test {
 	type BlockExecutionWeight = BlockExecutionWeight;
 	type ExtrinsicBaseWeight = ExtrinsicBaseWeight;
 	type MaximumExtrinsicWeight = MaximumExtrinsicWeight;
+	type MaximumBlockProofSize = MaximumBlockProofSize;
+	type MaxFeelessTransactions = MaxFeelessTransactions;
+	type FeelessRateLimitPeriod = FeelessRateLimitPeriod;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type MaximumBlockLength = MaximumBlockLength;
 	type Version = Version;
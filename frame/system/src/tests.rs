@@ -49,6 +49,24 @@ fn stored_map_works() {
 	});
 }
 
+#[test]
+fn kill_account_forgets_expired_feeless_transactions() {
+	new_test_ext().execute_with(|| {
+		let period = <Test as Trait>::FeelessRateLimitPeriod::get();
+		System::set_block_number(1);
+		FeelessTransactions::<Test>::insert(&0, (1, 2));
+		FeelessTransactions::<Test>::insert(&1, (1, 2));
+
+		// the period of a reaped account is still running.
+		System::kill_account(&0);
+		assert_eq!(System::feeless_transactions(0), Some((1, 2)));
+
+		System::set_block_number(1 + period);
+		System::kill_account(&1);
+		assert_eq!(System::feeless_transactions(1), None);
+	});
+}
+
 #[test]
 fn deposit_event_should_work() {
 	new_test_ext().execute_with(|| {
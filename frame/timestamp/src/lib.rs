@@ -102,7 +102,7 @@ use frame_support::debug;
 use frame_support::{
 	Parameter, decl_storage, decl_module,
 	traits::{Time, UnixTime, Get},
-	weights::{DispatchClass, Weight},
+	weights::{DispatchClass, Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
 };
 use sp_runtime::{
 	RuntimeString,
@@ -118,7 +118,13 @@ use sp_timestamp::{
 
 pub trait WeightInfo {
 	fn set() -> Weight;
+	fn set_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn on_finalize() -> Weight;
+	fn on_finalize_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 /// The module configuration trait
@@ -163,9 +169,9 @@ decl_module! {
 		/// - 1 storage read and 1 storage mutation (codec `O(1)`). (because of `DidUpdate::take` in `on_finalize`)
 		/// - 1 event handler `on_timestamp_set` `O(T)`.
 		/// # </weight>
-		#[weight = (
-			T::WeightInfo::set(),
-			DispatchClass::Mandatory
+		#[weight = WithProofSize(
+			(T::WeightInfo::set(), DispatchClass::Mandatory),
+			T::WeightInfo::set_proof_size(),
 		)]
 		fn set(origin, #[compact] now: T::Moment) {
 			ensure_none(origin)?;
@@ -336,6 +342,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = ();
@@ -44,6 +44,23 @@ pub struct RuntimeDispatchInfo<Balance> {
 	pub partial_fee: Balance,
 }
 
+/// Fee information of a dispatchable together with the willingness of a sponsor to pay it.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SponsoredDispatchInfo<Balance> {
+	/// Weight, class and inclusion fee of this dispatch.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	pub info: RuntimeDispatchInfo<Balance>,
+	/// Whether the sponsor currently accepts to pay the inclusion fee.
+	pub accepted: bool,
+	/// The amount of fees the sponsor is still willing to pay.
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub remaining: Balance,
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
 	serializer.serialize_str(&t.to_string())
@@ -61,6 +78,18 @@ sp_api::decl_runtime_apis! {
 	{
 		fn query_info(uxt: Block::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance>;
 	}
+
+	pub trait SponsoredTransactionPaymentApi<Balance, AccountId> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		AccountId: Codec,
+	{
+		fn query_sponsored_info(
+			uxt: Block::Extrinsic,
+			len: u32,
+			sponsor: AccountId,
+			who: AccountId,
+		) -> SponsoredDispatchInfo<Balance>;
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,89 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmarks for the sponsorship extrinsics of the Transaction Payment Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+
+const SEED: u32 = 0;
+
+// Open a sponsorship for `sponsor`, paying the fees of one account.
+fn setup_sponsorship<T: Trait>(sponsor: &T::AccountId) -> Result<T::AccountId, &'static str> {
+	Module::<T>::set_sponsorship(RawOrigin::Signed(sponsor.clone()).into(), 100u32.into())?;
+	let sponsored: T::AccountId = account("sponsored", 0, SEED);
+	Module::<T>::sponsor_account(RawOrigin::Signed(sponsor.clone()).into(), sponsored.clone())?;
+	Ok(sponsored)
+}
+
+benchmarks! {
+	_ { }
+
+	set_sponsorship {
+		let caller: T::AccountId = whitelisted_caller();
+		let cap: BalanceOf<T> = 100u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), cap)
+	verify {
+		assert_eq!(Sponsorships::<T>::get(&caller).map(|s| s.cap), Some(cap));
+	}
+
+	remove_sponsorship {
+		let caller: T::AccountId = whitelisted_caller();
+		let sponsored = setup_sponsorship::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Sponsorships::<T>::contains_key(&caller));
+		assert!(!SponsoredAccounts::<T>::get(&caller, &sponsored));
+	}
+
+	sponsor_account {
+		let caller: T::AccountId = whitelisted_caller();
+		Module::<T>::set_sponsorship(RawOrigin::Signed(caller.clone()).into(), 100u32.into())?;
+		let sponsored: T::AccountId = account("sponsored", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), sponsored.clone())
+	verify {
+		assert!(SponsoredAccounts::<T>::get(&caller, &sponsored));
+	}
+
+	unsponsor_account {
+		let caller: T::AccountId = whitelisted_caller();
+		let sponsored = setup_sponsorship::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), sponsored.clone())
+	verify {
+		assert!(!SponsoredAccounts::<T>::get(&caller, &sponsored));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{ExtBuilder, Runtime};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_sponsorship::<Runtime>());
+			assert_ok!(test_benchmark_remove_sponsorship::<Runtime>());
+			assert_ok!(test_benchmark_sponsor_account::<Runtime>());
+			assert_ok!(test_benchmark_unsponsor_account::<Runtime>());
+		});
+	}
+}
@@ -29,32 +29,65 @@
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Trait::FeeMultiplierUpdate`]
+//!
+//! ## Sponsored transactions
+//!
+//! Instead of [`ChargeTransactionPayment`], a runtime can use [`ChargeSponsoredTransactionPayment`]
+//! which lets a sponsor pay the fees of a transaction. A sponsor opens a [`Sponsorship`] with a
+//! spending cap through [`Module::set_sponsorship`] and allows accounts through
+//! [`Module::sponsor_account`]. It then approves each transaction by signing its
+//! [`Module::sponsor_payload`]. The remaining budget of a sponsor can be queried together with the
+//! fee of a transaction through [`Module::query_sponsored_info`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use frame_support::{
-	decl_storage, decl_module,
+	decl_storage, decl_module, decl_error, ensure, Parameter, StorageMap,
 	traits::{Currency, Get, OnUnbalanced, ExistenceRequirement, WithdrawReason, Imbalance},
 	weights::{
-		Weight, DispatchInfo, PostDispatchInfo, GetDispatchInfo, Pays, WeightToFeePolynomial,
-		WeightToFeeCoefficient,
+		Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, DispatchInfo, PostDispatchInfo,
+		GetDispatchInfo, Pays, WeightToFeePolynomial, WeightToFeeCoefficient,
 	},
 	dispatch::DispatchResult,
 };
+use frame_system::ensure_signed;
 use sp_runtime::{
-	FixedU128, FixedPointNumber, FixedPointOperand, Perquintill, RuntimeDebug,
+	FixedU128, FixedPointNumber, FixedPointOperand, Perquintill, RuntimeDebug, generic::Era,
 	transaction_validity::{
 		TransactionPriority, ValidTransaction, InvalidTransaction, TransactionValidityError,
 		TransactionValidity,
 	},
 	traits::{
 		Zero, Saturating, SignedExtension, SaturatedConversion, Convert, Dispatchable,
-		DispatchInfoOf, PostDispatchInfoOf,
+		DispatchInfoOf, PostDispatchInfoOf, Verify, IdentifyAccount,
 	},
 };
-use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+use pallet_transaction_payment_rpc_runtime_api::{RuntimeDispatchInfo, SponsoredDispatchInfo};
+
+mod benchmarking;
+mod default_weights;
+
+pub trait WeightInfo {
+	fn set_sponsorship() -> Weight;
+	fn set_sponsorship_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn remove_sponsorship() -> Weight;
+	fn remove_sponsorship_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn sponsor_account() -> Weight;
+	fn sponsor_account_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn unsponsor_account() -> Weight;
+	fn unsponsor_account_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+}
 
 /// Fee multiplier.
 pub type Multiplier = FixedU128;
@@ -198,7 +231,7 @@ impl<T, S, V, M> Convert<Multiplier, Multiplier> for TargetedFeeAdjustment<T, S,
 }
 
 /// Storage releases of the module.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	/// Original version of the module.
 	V1Ancient,
@@ -212,6 +245,36 @@ impl Default for Releases {
 	}
 }
 
+/// The context prepended to the payload signed by a sponsor, see [`Module::sponsor_payload`].
+pub const SPONSOR_PAYLOAD_CONTEXT: &[u8] = b"sponsored_transaction";
+
+/// The fee budget of a sponsor.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Sponsorship<Balance> {
+	/// The total amount of fees the sponsor is willing to pay.
+	pub cap: Balance,
+	/// The amount of fees paid so far.
+	pub spent: Balance,
+}
+
+impl<Balance: Saturating + Copy> Sponsorship<Balance> {
+	/// The amount of fees the sponsor is still willing to pay.
+	pub fn remaining(&self) -> Balance {
+		self.cap.saturating_sub(self.spent)
+	}
+}
+
+/// The approval of a sponsor to pay the fees of a transaction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SponsorApproval<AccountId, Signature> {
+	/// The account paying the fees.
+	pub sponsor: AccountId,
+	/// The period in which the approval can be used.
+	pub era: Era,
+	/// The signature of the sponsor over the [`Module::sponsor_payload`] of the transaction.
+	pub signature: Signature,
+}
+
 pub trait Trait: frame_system::Trait {
 	/// The currency type in which fees will be paid.
 	type Currency: Currency<Self::AccountId> + Send + Sync;
@@ -229,6 +292,15 @@ pub trait Trait: frame_system::Trait {
 
 	/// Update the multiplier of the next block, based on the previous block's weight.
 	type FeeMultiplierUpdate: MultiplierUpdate;
+
+	/// The signature with which a sponsor approves paying the fees of a transaction.
+	type SponsorSignature: Parameter + Verify<Signer = Self::SponsorPublic>;
+
+	/// The public key of a sponsor, identifying the account paying the fees.
+	type SponsorPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
@@ -236,11 +308,29 @@ decl_storage! {
 		pub NextFeeMultiplier get(fn next_fee_multiplier): Multiplier = Multiplier::saturating_from_integer(1);
 
 		StorageVersion build(|_: &GenesisConfig| Releases::V2): Releases;
+
+		/// The fee budget of each sponsor.
+		pub Sponsorships get(fn sponsorship):
+			map hasher(blake2_128_concat) T::AccountId => Option<Sponsorship<BalanceOf<T>>>;
+
+		/// The accounts whose transaction fees a sponsor is willing to pay, keyed by the sponsor.
+		pub SponsoredAccounts get(fn is_sponsored):
+			double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId
+			=> bool;
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The sender has no sponsorship.
+		NotSponsor,
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
 		/// The fee to be paid for making a transaction; the per-byte portion.
 		const TransactionByteFee: BalanceOf<T> = T::TransactionByteFee::get();
 
@@ -292,6 +382,56 @@ decl_module! {
 				);
 			})
 		}
+
+		/// Open a sponsorship paying up to `cap` in transaction fees, or update the cap of the
+		/// existing sponsorship of the sender.
+		///
+		/// The fees already paid are kept, hence a cap below them stops any further sponsoring.
+		#[weight = WithProofSize(
+			T::WeightInfo::set_sponsorship(),
+			T::WeightInfo::set_sponsorship_proof_size(),
+		)]
+		fn set_sponsorship(origin, #[compact] cap: BalanceOf<T>) {
+			let sponsor = ensure_signed(origin)?;
+			<Sponsorships<T>>::mutate(&sponsor, |sponsorship| {
+				sponsorship.get_or_insert_with(Default::default).cap = cap;
+			});
+		}
+
+		/// Close the sponsorship of the sender and forget all the accounts it sponsors.
+		#[weight = WithProofSize(
+			T::WeightInfo::remove_sponsorship(),
+			T::WeightInfo::remove_sponsorship_proof_size(),
+		)]
+		fn remove_sponsorship(origin) {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(<Sponsorships<T>>::contains_key(&sponsor), Error::<T>::NotSponsor);
+			<Sponsorships<T>>::remove(&sponsor);
+			<SponsoredAccounts<T>>::remove_prefix(&sponsor);
+		}
+
+		/// Allow the sender to pay the transaction fees of `who`.
+		///
+		/// The sender must have a sponsorship.
+		#[weight = WithProofSize(
+			T::WeightInfo::sponsor_account(),
+			T::WeightInfo::sponsor_account_proof_size(),
+		)]
+		fn sponsor_account(origin, who: T::AccountId) {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(<Sponsorships<T>>::contains_key(&sponsor), Error::<T>::NotSponsor);
+			<SponsoredAccounts<T>>::insert(&sponsor, &who, true);
+		}
+
+		/// Stop paying the transaction fees of `who`.
+		#[weight = WithProofSize(
+			T::WeightInfo::unsponsor_account(),
+			T::WeightInfo::unsponsor_account_proof_size(),
+		)]
+		fn unsponsor_account(origin, who: T::AccountId) {
+			let sponsor = ensure_signed(origin)?;
+			<SponsoredAccounts<T>>::remove(&sponsor, &who);
+		}
 	}
 }
 
@@ -328,6 +468,65 @@ impl<T: Trait> Module<T> where
 		RuntimeDispatchInfo { weight, class, partial_fee }
 	}
 
+	/// Query the fee information of a given `call` like [`Self::query_info`], along with whether
+	/// `sponsor` currently accepts to pay it on behalf of `who`.
+	///
+	/// Like [`Self::query_info`], this does not account for a tip.
+	pub fn query_sponsored_info<Extrinsic: GetDispatchInfo>(
+		unchecked_extrinsic: Extrinsic,
+		len: u32,
+		sponsor: T::AccountId,
+		who: T::AccountId,
+	) -> SponsoredDispatchInfo<BalanceOf<T>>
+	where
+		T: Send + Sync,
+		BalanceOf<T>: Send + Sync,
+		T::Call: Dispatchable<Info=DispatchInfo>,
+	{
+		let info = Self::query_info(unchecked_extrinsic, len);
+		let remaining = Self::sponsorship(&sponsor)
+			.map(|sponsorship| sponsorship.remaining())
+			.unwrap_or_else(Zero::zero);
+		let accepted = Self::is_sponsored(&sponsor, &who) && remaining >= info.partial_fee;
+
+		SponsoredDispatchInfo { info, accepted, remaining }
+	}
+
+	/// The payload a sponsor signs to pay the fees of the transaction of `who` with `nonce`,
+	/// dispatching `call` and adding `tip`.
+	///
+	/// Like a transaction, the payload commits to the genesis hash, the runtime version and the
+	/// hash of the birth block of `era`, so an approval can't be replayed on another chain, after
+	/// a runtime upgrade, on another fork or once `era` is over.
+	pub fn sponsor_payload(
+		who: &T::AccountId,
+		nonce: T::Index,
+		call: &T::Call,
+		tip: BalanceOf<T>,
+		era: Era,
+	) -> Result<[u8; 32], TransactionValidityError> {
+		let current = <frame_system::Module<T>>::block_number().saturated_into::<u64>();
+		let birth = era.birth(current).saturated_into::<T::BlockNumber>();
+		if !<frame_system::BlockHash<T>>::contains_key(birth) {
+			return Err(InvalidTransaction::AncientBirthBlock.into());
+		}
+		let genesis_hash = <frame_system::Module<T>>::block_hash(T::BlockNumber::zero());
+		let birth_hash = <frame_system::Module<T>>::block_hash(birth);
+		let version = T::Version::get();
+		Ok((
+			SPONSOR_PAYLOAD_CONTEXT,
+			genesis_hash,
+			version.spec_version,
+			version.transaction_version,
+			era,
+			birth_hash,
+			who,
+			nonce,
+			call,
+			tip,
+		).using_encoded(sp_io::hashing::blake2_256))
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	///
 	/// The final fee is composed of:
@@ -427,7 +626,7 @@ impl<T> Convert<Weight, BalanceOf<T>> for Module<T> where
 
 /// Require the transactor pay for themselves and maybe include a tip to gain additional priority
 /// in the queue.
-#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 pub struct ChargeTransactionPayment<T: Trait + Send + Sync>(#[codec(compact)] BalanceOf<T>);
 
 impl<T: Trait + Send + Sync> ChargeTransactionPayment<T> where
@@ -570,12 +769,158 @@ impl<T: Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T> whe
 	}
 }
 
+/// Pay the fees of a transaction either as the transactor or, if approved, from a sponsor.
+///
+/// Without a [`SponsorApproval`] this behaves exactly like [`ChargeTransactionPayment`]. With one,
+/// the sponsor must have a [`Sponsorship`] with enough budget left for the fee, must sponsor the
+/// transactor and must have signed the [`Module::sponsor_payload`] of the transaction. The fee
+/// and the tip are then withdrawn from the sponsor, and only the actual fee, without the tip, is
+/// counted against its budget.
+///
+/// The approval is bound to the current nonce of the transactor, hence this extension must come
+/// before `CheckNonce` in the `SignedExtra` of the runtime.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub struct ChargeSponsoredTransactionPayment<T: Trait + Send + Sync> {
+	payment: ChargeTransactionPayment<T>,
+	sponsor: Option<SponsorApproval<T::AccountId, T::SponsorSignature>>,
+}
+
+impl<T: Trait + Send + Sync> ChargeSponsoredTransactionPayment<T> where
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync + FixedPointOperand,
+{
+	/// Pay the fees, including `tip`, as the transactor.
+	pub fn from(tip: BalanceOf<T>) -> Self {
+		Self { payment: ChargeTransactionPayment::from(tip), sponsor: None }
+	}
+
+	/// Pay the fees, including `tip`, from the sponsor which gave `approval`.
+	pub fn sponsored(
+		tip: BalanceOf<T>,
+		approval: SponsorApproval<T::AccountId, T::SponsorSignature>,
+	) -> Self {
+		Self { payment: ChargeTransactionPayment::from(tip), sponsor: Some(approval) }
+	}
+
+	/// Returns the account paying the fees of the transaction of `who`.
+	fn payer(
+		&self,
+		who: &T::AccountId,
+		call: &T::Call,
+		info: &DispatchInfoOf<T::Call>,
+		len: usize,
+	) -> Result<T::AccountId, TransactionValidityError> {
+		let approval = match self.sponsor {
+			Some(ref approval) => approval,
+			None => return Ok(who.clone()),
+		};
+
+		let tip = self.payment.0;
+		let fee = Module::<T>::compute_fee(len as u32, info, tip).saturating_sub(tip);
+		let sponsorship = Module::<T>::sponsorship(&approval.sponsor)
+			.ok_or(InvalidTransaction::Payment)?;
+		if !Module::<T>::is_sponsored(&approval.sponsor, who) || sponsorship.remaining() < fee {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		let nonce = frame_system::Module::<T>::account_nonce(who);
+		let payload = Module::<T>::sponsor_payload(who, nonce, call, tip, approval.era)?;
+		if !approval.signature.verify(&payload[..], &approval.sponsor) {
+			return Err(InvalidTransaction::BadProof.into());
+		}
+
+		Ok(approval.sponsor.clone())
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeSponsoredTransactionPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(
+			f,
+			"ChargeSponsoredTransactionPayment<{:?}, {:?}>",
+			self.payment.0,
+			self.sponsor.as_ref().map(|approval| &approval.sponsor),
+		)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeSponsoredTransactionPayment<T> where
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeSponsoredTransactionPayment";
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	type Pre = (<ChargeTransactionPayment<T> as SignedExtension>::Pre, Option<Self::AccountId>);
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		let payer = self.payer(who, call, info, len)?;
+		self.payment.validate(&payer, call, info, len)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let payer = self.payer(who, call, info, len)?;
+		let sponsor = self.sponsor.map(|approval| approval.sponsor);
+		let pre = self.payment.pre_dispatch(&payer, call, info, len)?;
+		if let Some(ref sponsor) = sponsor {
+			let fee = pre.3.saturating_sub(pre.0);
+			<Sponsorships<T>>::mutate(sponsor, |sponsorship| {
+				if let Some(sponsorship) = sponsorship {
+					sponsorship.spent = sponsorship.spent.saturating_add(fee);
+				}
+			});
+		}
+		Ok((pre, sponsor))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let (pre, sponsor) = pre;
+		// Only the actual fee is counted against the budget of the sponsor.
+		if let (Some(sponsor), (tip, _, Some(_), fee)) = (sponsor, &pre) {
+			let actual_fee = Module::<T>::compute_actual_fee(len as u32, info, post_info, *tip);
+			let refund = (*fee).saturating_sub(actual_fee);
+			<Sponsorships<T>>::mutate(sponsor, |sponsorship| {
+				if let Some(sponsorship) = sponsorship {
+					sponsorship.spent = sponsorship.spent.saturating_sub(refund);
+				}
+			});
+		}
+		ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use codec::Encode;
 	use frame_support::{
 		impl_outer_dispatch, impl_outer_origin, impl_outer_event, parameter_types,
+		assert_ok, assert_noop,
 		weights::{
 			DispatchClass, DispatchInfo, PostDispatchInfo, GetDispatchInfo, Weight,
 			WeightToFeePolynomial, WeightToFeeCoefficients, WeightToFeeCoefficient,
@@ -585,7 +930,7 @@ mod tests {
 	use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 	use sp_core::H256;
 	use sp_runtime::{
-		testing::{Header, TestXt},
+		testing::{Header, TestXt, TestSignature, UintAuthorityId},
 		traits::{BlakeTwo256, IdentityLookup},
 		Perbill,
 	};
@@ -651,6 +996,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ExtrinsicBaseWeight;
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type MaximumBlockLength = MaximumBlockLength;
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
@@ -704,6 +1052,9 @@ mod tests {
 		type TransactionByteFee = TransactionByteFee;
 		type WeightToFee = WeightToFee;
 		type FeeMultiplierUpdate = ();
+		type SponsorSignature = TestSignature;
+		type SponsorPublic = UintAuthorityId;
+		type WeightInfo = ();
 	}
 
 	type Balances = pallet_balances::Module<Runtime>;
@@ -898,6 +1249,7 @@ mod tests {
 			// This is a completely free (and thus wholly insecure/DoS-ridden) transaction.
 			let operational_transaction = DispatchInfo {
 				weight: 0,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::No,
 			};
@@ -910,6 +1262,7 @@ mod tests {
 			// like a InsecureFreeNormal
 			let free_transaction = DispatchInfo {
 				weight: 0,
+				proof_size: 0,
 				class: DispatchClass::Normal,
 				pays_fee: Pays::Yes,
 			};
@@ -949,6 +1302,185 @@ mod tests {
 		})
 	}
 
+	fn sponsor_approval(sponsor: u64, who: u64, tip: u64) -> SponsorApproval<u64, TestSignature> {
+		mortal_sponsor_approval(sponsor, who, tip, Era::Immortal)
+	}
+
+	fn mortal_sponsor_approval(
+		sponsor: u64,
+		who: u64,
+		tip: u64,
+		era: Era,
+	) -> SponsorApproval<u64, TestSignature> {
+		let nonce = System::account_nonce(who);
+		let payload = TransactionPayment::sponsor_payload(&who, nonce, CALL, tip, era).unwrap();
+		SponsorApproval { sponsor, era, signature: TestSignature(sponsor, payload.to_vec()) }
+	}
+
+	#[test]
+	fn sponsorship_management_works() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_noop!(
+				TransactionPayment::sponsor_account(Origin::signed(3), 1),
+				Error::<Runtime>::NotSponsor,
+			);
+
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), 50));
+			assert_ok!(TransactionPayment::sponsor_account(Origin::signed(3), 1));
+			assert_ok!(TransactionPayment::sponsor_account(Origin::signed(3), 2));
+			assert_eq!(TransactionPayment::sponsorship(3), Some(Sponsorship { cap: 50, spent: 0 }));
+			assert!(TransactionPayment::is_sponsored(3, 1));
+
+			assert_ok!(TransactionPayment::unsponsor_account(Origin::signed(3), 1));
+			assert!(!TransactionPayment::is_sponsored(3, 1));
+
+			assert_ok!(TransactionPayment::remove_sponsorship(Origin::signed(3)));
+			assert_eq!(TransactionPayment::sponsorship(3), None);
+			assert!(!TransactionPayment::is_sponsored(3, 2));
+		});
+	}
+
+	#[test]
+	fn signed_extension_sponsored_payment_works() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			let len = 10;
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), 50));
+			assert_ok!(TransactionPayment::sponsor_account(Origin::signed(3), 1));
+
+			// without an approval the transactor pays.
+			let pre = ChargeSponsoredTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&1, CALL, &info_from_weight(5), len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 5 - 10);
+			assert!(
+				ChargeSponsoredTransactionPayment::<Runtime>
+					::post_dispatch(pre, &info_from_weight(5), &default_post_info(), len, &Ok(()))
+					.is_ok()
+			);
+
+			let pre = ChargeSponsoredTransactionPayment::<Runtime>::sponsored(5, sponsor_approval(3, 1, 5))
+				.pre_dispatch(&1, CALL, &info_from_weight(20), len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 5 - 10);
+			assert_eq!(Balances::free_balance(3), 300 - 5 - 10 - 20 - 5);
+			// the tip is paid by the sponsor, but not counted against its budget.
+			assert_eq!(TransactionPayment::sponsorship(3).unwrap().spent, 35);
+
+			assert!(
+				ChargeSponsoredTransactionPayment::<Runtime>
+					::post_dispatch(pre, &info_from_weight(20), &post_info_from_weight(10), len, &Ok(()))
+					.is_ok()
+			);
+			// only the actual fee is paid by, and counted against the budget of, the sponsor.
+			assert_eq!(Balances::free_balance(3), 300 - 5 - 10 - 10 - 5);
+			assert_eq!(TransactionPayment::sponsorship(3).unwrap().spent, 25);
+		});
+	}
+
+	#[test]
+	fn signed_extension_sponsored_payment_checks_approval() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			let len = 10;
+			let validate = |who: u64, approval| {
+				ChargeSponsoredTransactionPayment::<Runtime>::sponsored(0, approval)
+					.validate(&who, CALL, &info_from_weight(20), len)
+			};
+
+			// no sponsorship.
+			assert_eq!(validate(1, sponsor_approval(3, 1, 0)), Err(InvalidTransaction::Payment.into()));
+
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), 35));
+			assert_ok!(TransactionPayment::sponsor_account(Origin::signed(3), 1));
+
+			// not sponsored.
+			assert_eq!(validate(2, sponsor_approval(3, 2, 0)), Err(InvalidTransaction::Payment.into()));
+			// signed for another transactor or nonce.
+			assert_eq!(validate(1, sponsor_approval(3, 2, 0)), Err(InvalidTransaction::BadProof.into()));
+			let approval = sponsor_approval(3, 1, 0);
+			System::inc_account_nonce(1);
+			assert_eq!(validate(1, approval), Err(InvalidTransaction::BadProof.into()));
+			// signed with another tip.
+			assert_eq!(validate(1, sponsor_approval(3, 1, 1)), Err(InvalidTransaction::BadProof.into()));
+			// signed for another era.
+			let mut approval = sponsor_approval(3, 1, 0);
+			approval.era = Era::mortal(4, 0);
+			assert_eq!(validate(1, approval), Err(InvalidTransaction::BadProof.into()));
+
+			assert!(validate(1, sponsor_approval(3, 1, 0)).is_ok());
+
+			// the fee of 35 exceeds the remaining budget.
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), 34));
+			assert_eq!(validate(1, sponsor_approval(3, 1, 0)), Err(InvalidTransaction::Payment.into()));
+		});
+	}
+
+	#[test]
+	fn signed_extension_sponsored_payment_expires_with_era() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			let len = 10;
+			let validate = |approval| {
+				ChargeSponsoredTransactionPayment::<Runtime>::sponsored(0, approval)
+					.validate(&1, CALL, &info_from_weight(20), len)
+			};
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), 50));
+			assert_ok!(TransactionPayment::sponsor_account(Origin::signed(3), 1));
+
+			System::set_block_number(13);
+			<frame_system::BlockHash<Runtime>>::insert(12, H256::repeat_byte(1));
+			let approval = mortal_sponsor_approval(3, 1, 0, Era::mortal(4, 12));
+			assert!(validate(approval.clone()).is_ok());
+
+			// the birth block of the era moved on.
+			System::set_block_number(17);
+			assert_eq!(validate(approval.clone()), Err(InvalidTransaction::AncientBirthBlock.into()));
+			<frame_system::BlockHash<Runtime>>::insert(16, H256::repeat_byte(2));
+			assert_eq!(validate(approval), Err(InvalidTransaction::BadProof.into()));
+		});
+	}
+
+	#[test]
+	fn query_sponsored_info_works() {
+		let xt = TestXt::new(CALL.clone(), Some((1, ())));
+		let info = xt.get_dispatch_info();
+		let len = xt.encode().len() as u32;
+		ExtBuilder::default()
+			.base_weight(5)
+			.build()
+			.execute_with(||
+		{
+			let fee = 5 + len as u64 + info.weight.min(MaximumBlockWeight::get());
+			let query = || TransactionPayment::query_sponsored_info(xt.clone(), len, 3, 1);
+
+			assert_eq!(query().remaining, 0);
+			assert!(!query().accepted);
+
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), fee));
+			assert_ok!(TransactionPayment::sponsor_account(Origin::signed(3), 1));
+			let sponsored = query();
+			assert_eq!(sponsored.info, TransactionPayment::query_info(xt.clone(), len));
+			assert_eq!(sponsored.remaining, fee);
+			assert!(sponsored.accepted);
+
+			assert_ok!(TransactionPayment::set_sponsorship(Origin::signed(3), fee - 1));
+			assert!(!query().accepted);
+		});
+	}
+
 	#[test]
 	fn query_info_works() {
 		let call = Call::Balances(BalancesCall::transfer(2, 69));
@@ -997,6 +1529,7 @@ mod tests {
 			// Tip only, no fees works
 			let dispatch_info = DispatchInfo {
 				weight: 0,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::No,
 			};
@@ -1004,6 +1537,7 @@ mod tests {
 			// No tip, only base fee works
 			let dispatch_info = DispatchInfo {
 				weight: 0,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1015,6 +1549,7 @@ mod tests {
 			// Weight fee + base fee works
 			let dispatch_info = DispatchInfo {
 				weight: 1000,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1036,6 +1571,7 @@ mod tests {
 			// Base fee is unaffected by multiplier
 			let dispatch_info = DispatchInfo {
 				weight: 0,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1044,6 +1580,7 @@ mod tests {
 			// Everything works together :)
 			let dispatch_info = DispatchInfo {
 				weight: 123,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1070,6 +1607,7 @@ mod tests {
 			// Base fee is unaffected by multiplier.
 			let dispatch_info = DispatchInfo {
 				weight: 0,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1078,6 +1616,7 @@ mod tests {
 			// Everything works together.
 			let dispatch_info = DispatchInfo {
 				weight: 123,
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1101,6 +1640,7 @@ mod tests {
 			// Overflow is handled
 			let dispatch_info = DispatchInfo {
 				weight: Weight::max_value(),
+				proof_size: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
@@ -1188,6 +1728,7 @@ mod tests {
 			let len = 10;
 			let dispatch_info = DispatchInfo {
 				weight: 100,
+				proof_size: 0,
 				pays_fee: Pays::No,
 				class: DispatchClass::Normal,
 			};
@@ -155,6 +155,44 @@ fn create_bounty<T: Trait<I>, I: Instance>() -> Result<(
 	Ok((curator_lookup, bounty_id))
 }
 
+// Add a child bounty to a fresh active bounty, with an active child curator if `accept`, or a
+// proposed one otherwise.
+fn create_child_bounty<T: Trait<I>, I: Instance>(accept: bool) -> Result<(
+	T::AccountId,
+	T::AccountId,
+	BountyIndex,
+	BountyIndex,
+), &'static str> {
+	let (curator_lookup, bounty_id) = create_bounty::<T, I>()?;
+	let curator = T::Lookup::lookup(curator_lookup)?;
+	let value = T::ChildBountyValueMinimum::get().max(T::Currency::minimum_balance().saturating_mul(10.into()));
+	let fee = T::Currency::minimum_balance().saturating_mul(2.into());
+	Treasury::<T, I>::add_child_bounty(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounty_id,
+		value,
+		vec![0; MAX_BYTES as usize],
+	)?;
+	let child_bounty_id = ChildBountyCount::<I>::get() - 1;
+	let child_curator: T::AccountId = account("child_curator", 0, SEED);
+	let _ = T::Currency::make_free_balance_be(&child_curator, fee);
+	Treasury::<T, I>::propose_child_bounty_curator(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounty_id,
+		child_bounty_id,
+		T::Lookup::unlookup(child_curator.clone()),
+		fee,
+	)?;
+	if accept {
+		Treasury::<T, I>::accept_child_bounty_curator(
+			RawOrigin::Signed(child_curator.clone()).into(),
+			bounty_id,
+			child_bounty_id,
+		)?;
+	}
+	Ok((curator, child_curator, bounty_id, child_bounty_id))
+}
+
 fn setup_pod_account<T: Trait<I>, I: Instance>() {
 	let pot_account = Treasury::<T, I>::account_id();
 	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000.into());
@@ -164,6 +202,25 @@ fn setup_pod_account<T: Trait<I>, I: Instance>() {
 const MAX_BYTES: u32 = 16384;
 const MAX_TIPPERS: u32 = 100;
 
+// Insert `n` asset spends, all of which are due in the next block. They are inserted directly, as
+// the treasury may not hold any asset.
+fn create_asset_spends<T: Trait<I>, I: Instance>(n: u32) {
+	let next = frame_system::Module::<T>::block_number() + 1.into();
+	for i in 0 .. n {
+		let spend = AssetSpend {
+			asset_id: Default::default(),
+			beneficiary: account("beneficiary", i, SEED),
+			amount: 100.into(),
+			installments: 12,
+			period: 10.into(),
+			failures: 0,
+		};
+		AssetSpends::<T, I>::insert(i, spend);
+		AssetPayouts::<T, I>::append(next, i);
+	}
+	AssetSpendCount::<I>::put(n);
+}
+
 benchmarks_instance! {
 	_ { }
 
@@ -366,6 +423,57 @@ benchmarks_instance! {
 		let curator = T::Lookup::lookup(curator_lookup)?;
 	}: _(RawOrigin::Signed(curator), bounty_id, Vec::new())
 
+	add_child_bounty {
+		let d in 0 .. MAX_BYTES;
+		setup_pod_account::<T, _>();
+		let (curator_lookup, bounty_id) = create_bounty::<T, _>()?;
+		let curator = T::Lookup::lookup(curator_lookup)?;
+		let value = T::ChildBountyValueMinimum::get().max(T::Currency::minimum_balance().saturating_mul(10.into()));
+		let description = vec![0; d as usize];
+	}: _(RawOrigin::Signed(curator), bounty_id, value, description)
+
+	propose_child_bounty_curator {
+		setup_pod_account::<T, _>();
+		let (curator, child_curator, bounty_id, child_bounty_id) = create_child_bounty::<T, _>(false)?;
+		let child_curator_lookup = T::Lookup::unlookup(child_curator);
+		let fee = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(curator), bounty_id, child_bounty_id, child_curator_lookup, fee)
+
+	accept_child_bounty_curator {
+		setup_pod_account::<T, _>();
+		let (curator, child_curator, bounty_id, child_bounty_id) = create_child_bounty::<T, _>(false)?;
+	}: _(RawOrigin::Signed(child_curator), bounty_id, child_bounty_id)
+
+	// Worst case when the parent curator slashes an active child curator.
+	unassign_child_bounty_curator {
+		setup_pod_account::<T, _>();
+		let (curator, child_curator, bounty_id, child_bounty_id) = create_child_bounty::<T, _>(true)?;
+	}: _(RawOrigin::Signed(curator), bounty_id, child_bounty_id)
+
+	award_child_bounty {
+		setup_pod_account::<T, _>();
+		let (curator, child_curator, bounty_id, child_bounty_id) = create_child_bounty::<T, _>(true)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", 0, SEED));
+	}: _(RawOrigin::Signed(child_curator), bounty_id, child_bounty_id, beneficiary)
+
+	claim_child_bounty {
+		setup_pod_account::<T, _>();
+		let (curator, child_curator, bounty_id, child_bounty_id) = create_child_bounty::<T, _>(true)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", 0, SEED));
+		Treasury::<T, _>::award_child_bounty(
+			RawOrigin::Signed(child_curator.clone()).into(),
+			bounty_id,
+			child_bounty_id,
+			beneficiary,
+		)?;
+		frame_system::Module::<T>::set_block_number(T::BountyDepositPayoutDelay::get());
+	}: _(RawOrigin::Signed(child_curator), bounty_id, child_bounty_id)
+
+	close_child_bounty {
+		setup_pod_account::<T, _>();
+		let (curator, child_curator, bounty_id, child_bounty_id) = create_child_bounty::<T, _>(true)?;
+	}: _(RawOrigin::Root, bounty_id, child_bounty_id)
+
 	on_initialize_proposals {
 		let p in 0 .. 100;
 		setup_pod_account::<T, _>();
@@ -381,6 +489,32 @@ benchmarks_instance! {
 	}: {
 		Treasury::<T, _>::on_initialize(T::BlockNumber::zero());
 	}
+
+	// The spend is only approved if the treasury holds the asset, which the benchmark cannot
+	// ensure for any `Assets`, so the result is ignored.
+	spend_asset {
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", 0, SEED));
+		let amount: AssetBalanceOf<T, I> = 100.into();
+	}: {
+		let _ = Treasury::<T, I>::spend_asset(
+			RawOrigin::Root.into(), Default::default(), amount, beneficiary, 12, 10.into(),
+		);
+	}
+
+	cancel_asset_spend {
+		create_asset_spends::<T, _>(1);
+	}: _(RawOrigin::Root, 0)
+	verify {
+		ensure!(!AssetSpends::<T, I>::contains_key(0), "asset spend not cancelled");
+	}
+
+	on_initialize_asset_spends {
+		let s in 0 .. 100;
+		create_asset_spends::<T, _>(s);
+		let next = frame_system::Module::<T>::block_number() + 1.into();
+	}: {
+		Treasury::<T, _>::pay_asset_installments(next);
+	}
 }
 
 #[cfg(test)]
@@ -410,8 +544,18 @@ mod tests {
 			assert_ok!(test_benchmark_close_bounty_proposed::<Test>());
 			assert_ok!(test_benchmark_close_bounty_active::<Test>());
 			assert_ok!(test_benchmark_extend_bounty_expiry::<Test>());
+			assert_ok!(test_benchmark_add_child_bounty::<Test>());
+			assert_ok!(test_benchmark_propose_child_bounty_curator::<Test>());
+			assert_ok!(test_benchmark_accept_child_bounty_curator::<Test>());
+			assert_ok!(test_benchmark_unassign_child_bounty_curator::<Test>());
+			assert_ok!(test_benchmark_award_child_bounty::<Test>());
+			assert_ok!(test_benchmark_claim_child_bounty::<Test>());
+			assert_ok!(test_benchmark_close_child_bounty::<Test>());
 			assert_ok!(test_benchmark_on_initialize_proposals::<Test>());
 			assert_ok!(test_benchmark_on_initialize_bounties::<Test>());
+			assert_ok!(test_benchmark_spend_asset::<Test>());
+			assert_ok!(test_benchmark_cancel_asset_spend::<Test>());
+			assert_ok!(test_benchmark_on_initialize_asset_spends::<Test>());
 		});
 	}
 }
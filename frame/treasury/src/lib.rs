@@ -44,6 +44,17 @@
 //! countdown period, the median of all declared tips is paid to the reported beneficiary, along
 //! with any finders fee, in case of a public (and bonded) original report.
 //!
+//! ### Asset Spends
+//!
+//! Besides the native currency, the treasury account may hold assets of the `Assets` type of its
+//! configuration, such as those of the Assets module. The `ApproveOrigin` may approve a spend of
+//! such an asset, paid to a beneficiary in one or more installments of a fixed amount each, a fixed
+//! period apart. The treasury must hold enough of the asset to pay the first installment when the
+//! spend is approved. The first installment is paid at the start of the next block. Any installment
+//! which cannot be paid, for lack of the asset in the treasury, is retried one period later, and
+//! the spend is dropped once `MaxAssetPaymentFailures` installments in a row could not be paid. The
+//! `ApproveOrigin` may cancel the installments which remain to be paid of an asset spend.
+//!
 //! ### Bounty
 //!
 //! A Bounty Spending is a reward for a specified body of work - or specified set of objectives - that
@@ -61,6 +72,15 @@
 //! is pending, resulting in the slash of the curator's deposit.
 //!
 //!
+//! ### Child Bounty
+//!
+//! The curator of an active bounty may carve child bounties out of the bounty's funds, so that the
+//! work of a large bounty can be split among many contributors. Each child bounty has its own
+//! curator, proposed by the curator of the parent bounty, with its own fee and deposit, and is
+//! awarded and claimed like a bounty. A bounty cannot be awarded or closed while it has child
+//! bounties which are yet to be claimed or closed. Closing a child bounty returns its funds to the
+//! parent bounty.
+//!
 //! ### Terminology
 //!
 //! - **Proposal:** A suggestion to allocate funds from the pot to a beneficiary.
@@ -81,6 +101,11 @@
 //! - **Finders Fee:** Some proportion of the tip amount that is paid to the reporter of the tip,
 //!   rather than the main beneficiary.
 //!
+//! Asset spends:
+//! - **Asset spend:** An approved spend of some asset of the `Assets` held by the treasury, paid to a
+//!   beneficiary in one or more installments.
+//! - **Installment:** One of the equal payments of an asset spend, made a fixed period apart.
+//!
 //! Bounty:
 //! - **Bounty spending proposal:** A proposal to reward a predefined body of work upon completion by
 //! the Treasury.
@@ -96,6 +121,8 @@
 //! - **Payout address:** The account to which the total or part of the bounty is assigned to.
 //! - **Payout Delay:** The delay period for which a bounty beneficiary needs to wait before claiming.
 //! - **Curator fee:** The reserved upfront payment for a curator for work related to the bounty.
+//! - **Child bounty:** A part of the funds of an active bounty, with its own curator, fee and award.
+//! - **Parent bounty:** The bounty out of whose funds a child bounty is carved.
 //!
 //! ## Interface
 //!
@@ -108,6 +135,10 @@
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
 //!
+//! Asset spends:
+//! - `spend_asset` - Approve a spend of an asset held by the treasury, paid in installments.
+//! - `cancel_asset_spend` - Cancel the remaining installments of an asset spend.
+//!
 //! Tipping protocol:
 //! - `report_awesome` - Report something worthy of a tip and register for a finders fee.
 //! - `retract_tip` - Retract a previous (finders fee registered) report.
@@ -127,6 +158,15 @@
 //! - `unassign_curator` - Unassign an accepted curator from a specific earmark.
 //! - `close_bounty` - Cancel the earmark for a specific treasury amount and close the bounty.
 //!
+//! Child bounty protocol:
+//! - `add_child_bounty` - Carve a child bounty out of the funds of an active bounty.
+//! - `propose_child_bounty_curator` - Assign an account to a child bounty as candidate curator.
+//! - `accept_child_bounty_curator` - Accept a child bounty assignment, setting a curator deposit.
+//! - `unassign_child_bounty_curator` - Unassign the curator of a child bounty.
+//! - `award_child_bounty` - Close and pay out the child bounty for the completed work.
+//! - `claim_child_bounty` - Claim the payout of an awarded child bounty.
+//! - `close_child_bounty` - Close a child bounty, returning its funds to the parent bounty.
+//!
 //!
 //! ## GenesisConfig
 //!
@@ -142,13 +182,16 @@ use frame_support::traits::{
 	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::{KeepAlive, AllowDeath},
 	ReservableCurrency, WithdrawReason
 };
-use sp_runtime::{Permill, ModuleId, Percent, RuntimeDebug, DispatchResult, traits::{
+use sp_runtime::{Permill, ModuleId, Percent, RuntimeDebug, DispatchResult, DispatchError, traits::{
 	Zero, StaticLookup, AccountIdConversion, Saturating, Hash, BadOrigin
 }};
 use frame_support::dispatch::DispatchResultWithPostInfo;
-use frame_support::weights::{Weight, DispatchClass};
-use frame_support::traits::{Contains, ContainsLengthBound, EnsureOrigin};
+use frame_support::weights::{
+	Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, DispatchClass,
+};
+use frame_support::traits::{Contains, ContainsLengthBound, EnsureOrigin, MultiAsset};
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use frame_system::{self as system, ensure_signed};
 
 mod tests;
@@ -161,28 +204,132 @@ type PositiveImbalanceOf<T, I> =
 	<<T as Trait<I>>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
 type NegativeImbalanceOf<T, I> =
 	<<T as Trait<I>>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+type AssetIdOf<T, I> =
+	<<T as Trait<I>>::Assets as MultiAsset<<T as frame_system::Trait>::AccountId>>::AssetId;
+type AssetBalanceOf<T, I> =
+	<<T as Trait<I>>::Assets as MultiAsset<<T as frame_system::Trait>::AccountId>>::Balance;
 
 pub trait WeightInfo {
 	fn propose_spend() -> Weight;
+	fn propose_spend_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn reject_proposal() -> Weight;
+	fn reject_proposal_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn approve_proposal() -> Weight;
+	fn approve_proposal_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn report_awesome(r: u32, ) -> Weight;
+	fn report_awesome_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn retract_tip() -> Weight;
+	fn retract_tip_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn tip_new(r: u32, t: u32, ) -> Weight;
+	fn tip_new_proof_size(_r: u32, _t: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn tip(t: u32, ) -> Weight;
+	fn tip_proof_size(_t: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_tip(t: u32, ) -> Weight;
+	fn close_tip_proof_size(_t: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn propose_bounty(r: u32, ) -> Weight;
+	fn propose_bounty_proof_size(_r: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn approve_bounty() -> Weight;
+	fn approve_bounty_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn propose_curator() -> Weight;
+	fn propose_curator_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn unassign_curator() -> Weight;
+	fn unassign_curator_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn accept_curator() -> Weight;
+	fn accept_curator_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn award_bounty() -> Weight;
+	fn award_bounty_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn claim_bounty() -> Weight;
+	fn claim_bounty_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_bounty_proposed() -> Weight;
+	fn close_bounty_proposed_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn close_bounty_active() -> Weight;
+	fn close_bounty_active_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn extend_bounty_expiry() -> Weight;
+	fn extend_bounty_expiry_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn add_child_bounty(d: u32, ) -> Weight;
+	fn add_child_bounty_proof_size(_d: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn propose_child_bounty_curator() -> Weight;
+	fn propose_child_bounty_curator_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn accept_child_bounty_curator() -> Weight;
+	fn accept_child_bounty_curator_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn unassign_child_bounty_curator() -> Weight;
+	fn unassign_child_bounty_curator_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn award_child_bounty() -> Weight;
+	fn award_child_bounty_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn claim_child_bounty() -> Weight;
+	fn claim_child_bounty_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn close_child_bounty() -> Weight;
+	fn close_child_bounty_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn on_initialize_proposals(p: u32, ) -> Weight;
+	fn on_initialize_proposals_proof_size(_p: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn on_initialize_bounties(b: u32, ) -> Weight;
+	fn on_initialize_bounties_proof_size(_b: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn spend_asset() -> Weight;
+	fn spend_asset_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn cancel_asset_spend() -> Weight;
+	fn cancel_asset_spend_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
+	fn on_initialize_asset_spends(s: u32, ) -> Weight;
+	fn on_initialize_asset_spends_proof_size(_s: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait<I=DefaultInstance>: frame_system::Trait {
@@ -252,9 +399,22 @@ pub trait Trait<I=DefaultInstance>: frame_system::Trait {
 	/// Maximum acceptable reason length.
 	type MaximumReasonLength: Get<u32>;
 
+	/// Minimum value for a child bounty.
+	type ChildBountyValueMinimum: Get<BalanceOf<Self, I>>;
+
+	/// Maximum number of child bounties of a bounty which are yet to be claimed or closed.
+	type MaxActiveChildBountyCount: Get<u32>;
+
 	/// Handler for the unbalanced decrease when treasury funds are burned.
 	type BurnDestination: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
 
+	/// The assets, other than the native currency, which the treasury may hold and spend.
+	type Assets: MultiAsset<Self::AccountId>;
+
+	/// The number of consecutive installments of an asset spend which may fail to be paid before
+	/// the spend is dropped.
+	type MaxAssetPaymentFailures: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -264,7 +424,7 @@ pub type ProposalIndex = u32;
 
 /// A spending proposal.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct Proposal<AccountId, Balance> {
 	/// The account proposing it.
 	proposer: AccountId,
@@ -278,7 +438,7 @@ pub struct Proposal<AccountId, Balance> {
 
 /// An open tipping "motion". Retains all details of a tip including information on the finder
 /// and the members who have voted.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct OpenTip<
 	AccountId: Parameter,
 	Balance: Parameter,
@@ -303,11 +463,73 @@ pub struct OpenTip<
 	finders_fee: bool,
 }
 
+/// A child bounty, carved out of the funds of an active parent bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ChildBounty<AccountId, Balance, BlockNumber> {
+	/// The index of the parent bounty.
+	parent_bounty: BountyIndex,
+	/// The (total) amount that should be paid if the child bounty is rewarded.
+	value: Balance,
+	/// The curator fee. Included in value.
+	fee: Balance,
+	/// The deposit of curator.
+	curator_deposit: Balance,
+	/// The status of this child bounty.
+	status: ChildBountyStatus<AccountId, BlockNumber>,
+}
+
+/// The status of a child bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ChildBountyStatus<AccountId, BlockNumber> {
+	/// The child bounty is funded and waiting for curator assignment.
+	Added,
+	/// A curator has been proposed by the curator of the parent bounty. Waiting for acceptance
+	/// from the curator.
+	CuratorProposed {
+		/// The assigned curator of this child bounty.
+		curator: AccountId,
+	},
+	/// The child bounty is active and waiting to be awarded.
+	Active {
+		/// The curator of this child bounty.
+		curator: AccountId,
+	},
+	/// The child bounty is awarded and waiting to released after a delay.
+	PendingPayout {
+		/// The curator of this child bounty.
+		curator: AccountId,
+		/// The beneficiary of the child bounty.
+		beneficiary: AccountId,
+		/// When the child bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
+/// An index of an asset spend. Just a `u32`.
+pub type AssetSpendIndex = u32;
+
+/// An approved spend of an asset, paid out in installments.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetSpend<AssetId, AccountId, Balance, BlockNumber> {
+	/// The asset which is paid.
+	asset_id: AssetId,
+	/// The account to which the installments are paid.
+	beneficiary: AccountId,
+	/// The amount paid in each installment.
+	amount: Balance,
+	/// The number of installments which remain to be paid.
+	installments: u32,
+	/// The number of blocks between successive installments.
+	period: BlockNumber,
+	/// The number of consecutive installments which could not be paid.
+	failures: u32,
+}
+
 /// An index of a bounty. Just a `u32`.
 pub type BountyIndex = u32;
 
 /// A bounty proposal.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct Bounty<AccountId, Balance, BlockNumber> {
 	/// The account proposing it.
 	proposer: AccountId,
@@ -324,7 +546,7 @@ pub struct Bounty<AccountId, Balance, BlockNumber> {
 }
 
 /// The status of a bounty proposal.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub enum BountyStatus<AccountId, BlockNumber> {
 	/// The bounty is proposed and waiting for approval.
 	Proposed,
@@ -392,6 +614,34 @@ decl_storage! {
 
 		/// Bounty indices that have been approved but not yet funded.
 		pub BountyApprovals get(fn bounty_approvals): Vec<BountyIndex>;
+
+		/// Number of child bounties that have been added.
+		pub ChildBountyCount get(fn child_bounty_count): BountyIndex;
+
+		/// Number of child bounties of each bounty which are yet to be claimed or closed.
+		pub ParentChildBounties get(fn parent_child_bounties):
+			map hasher(twox_64_concat) BountyIndex => u32;
+
+		/// Child bounties that have been added, by parent bounty.
+		pub ChildBounties get(fn child_bounties):
+			double_map hasher(twox_64_concat) BountyIndex, hasher(twox_64_concat) BountyIndex
+			=> Option<ChildBounty<T::AccountId, BalanceOf<T, I>, T::BlockNumber>>;
+
+		/// The description of each child bounty.
+		pub ChildBountyDescriptions get(fn child_bounty_descriptions):
+			map hasher(twox_64_concat) BountyIndex => Option<Vec<u8>>;
+
+		/// Number of asset spends that have been approved.
+		pub AssetSpendCount get(fn asset_spend_count): AssetSpendIndex;
+
+		/// Asset spends which have installments remaining to be paid.
+		pub AssetSpends get(fn asset_spends):
+			map hasher(twox_64_concat) AssetSpendIndex
+			=> Option<AssetSpend<AssetIdOf<T, I>, T::AccountId, AssetBalanceOf<T, I>, T::BlockNumber>>;
+
+		/// The asset spends whose next installment is due at the start of a given block.
+		pub AssetPayouts get(fn asset_payouts):
+			map hasher(twox_64_concat) T::BlockNumber => Vec<AssetSpendIndex>;
 	}
 	add_extra_genesis {
 		build(|_config| {
@@ -410,6 +660,8 @@ decl_event!(
 		Balance = BalanceOf<T, I>,
 		<T as frame_system::Trait>::AccountId,
 		<T as frame_system::Trait>::Hash,
+		AssetId = AssetIdOf<T, I>,
+		AssetBalance = AssetBalanceOf<T, I>,
 	{
 		/// New proposal. \[proposal_index\]
 		Proposed(ProposalIndex),
@@ -448,6 +700,27 @@ decl_event!(
 		BountyCanceled(BountyIndex),
 		/// A bounty expiry is extended. [index]
 		BountyExtended(BountyIndex),
+		/// A child bounty has been added. [parent_index, child_index]
+		ChildBountyAdded(BountyIndex, BountyIndex),
+		/// A child bounty is awarded to a beneficiary. [parent_index, child_index, beneficiary]
+		ChildBountyAwarded(BountyIndex, BountyIndex, AccountId),
+		/// A child bounty is claimed by beneficiary.
+		/// [parent_index, child_index, payout, beneficiary]
+		ChildBountyClaimed(BountyIndex, BountyIndex, Balance, AccountId),
+		/// A child bounty is cancelled. [parent_index, child_index]
+		ChildBountyCanceled(BountyIndex, BountyIndex),
+		/// An asset spend has been approved.
+		/// \[index, asset_id, amount, installments, beneficiary\]
+		AssetSpendApproved(AssetSpendIndex, AssetId, AssetBalance, u32, AccountId),
+		/// An installment of an asset spend has been paid. \[index, asset_id, amount, beneficiary\]
+		AssetPaid(AssetSpendIndex, AssetId, AssetBalance, AccountId),
+		/// An installment of an asset spend could not be paid and will be retried. \[index\]
+		AssetPaymentFailed(AssetSpendIndex),
+		/// The remaining installments of an asset spend have been cancelled. \[index\]
+		AssetSpendCancelled(AssetSpendIndex),
+		/// An asset spend has been dropped after too many of its installments in a row could not be
+		/// paid. \[index\]
+		AssetSpendDropped(AssetSpendIndex),
 	}
 );
 
@@ -481,6 +754,17 @@ decl_error! {
 		/// A bounty payout is pending.
 		/// To cancel the bounty, you must unassign and slash the curator.
 		PendingPayout,
+		/// The parent bounty is not active.
+		ParentBountyNotActive,
+		/// The bounty has child bounties which are yet to be claimed or closed.
+		HasActiveChildBounty,
+		/// The bounty has the maximum number of child bounties which are yet to be claimed or
+		/// closed.
+		TooManyChildBounties,
+		/// The amount, number of installments or period of an asset spend is zero.
+		InvalidSchedule,
+		/// The treasury does not hold enough of the asset to pay an installment.
+		InsufficientAssets,
 	}
 }
 
@@ -531,6 +815,16 @@ decl_module! {
 		/// Maximum acceptable reason length.
 		const MaximumReasonLength: u32 = T::MaximumReasonLength::get();
 
+		/// Minimum value for a child bounty.
+		const ChildBountyValueMinimum: BalanceOf<T, I> = T::ChildBountyValueMinimum::get();
+
+		/// Maximum number of child bounties of a bounty which are yet to be claimed or closed.
+		const MaxActiveChildBountyCount: u32 = T::MaxActiveChildBountyCount::get();
+
+		/// The number of consecutive installments of an asset spend which may fail to be paid
+		/// before the spend is dropped.
+		const MaxAssetPaymentFailures: u32 = T::MaxAssetPaymentFailures::get();
+
 		type Error = Error<T, I>;
 
 		fn deposit_event() = default;
@@ -544,7 +838,10 @@ decl_module! {
 		/// - DbReads: `ProposalCount`, `origin account`
 		/// - DbWrites: `ProposalCount`, `Proposals`, `origin account`
 		/// # </weight>
-		#[weight = T::WeightInfo::propose_spend()]
+		#[weight = WithProofSize(
+			T::WeightInfo::propose_spend(),
+			T::WeightInfo::propose_spend_proof_size(),
+		)]
 		fn propose_spend(
 			origin,
 			#[compact] value: BalanceOf<T, I>,
@@ -573,7 +870,10 @@ decl_module! {
 		/// - DbReads: `Proposals`, `rejected proposer account`
 		/// - DbWrites: `Proposals`, `rejected proposer account`
 		/// # </weight>
-		#[weight = (T::WeightInfo::reject_proposal(), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::reject_proposal(), DispatchClass::Operational),
+			T::WeightInfo::reject_proposal_proof_size(),
+		)]
 		fn reject_proposal(origin, #[compact] proposal_id: ProposalIndex) {
 			T::RejectOrigin::ensure_origin(origin)?;
 
@@ -595,7 +895,10 @@ decl_module! {
 		/// - DbReads: `Proposals`, `Approvals`
 		/// - DbWrite: `Approvals`
 		/// # </weight>
-		#[weight = (T::WeightInfo::approve_proposal(), DispatchClass::Operational)]
+		#[weight = WithProofSize(
+			(T::WeightInfo::approve_proposal(), DispatchClass::Operational),
+			T::WeightInfo::approve_proposal_proof_size(),
+		)]
 		fn approve_proposal(origin, #[compact] proposal_id: ProposalIndex) {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
@@ -622,7 +925,10 @@ decl_module! {
 		/// - DbReads: `Reasons`, `Tips`
 		/// - DbWrites: `Reasons`, `Tips`
 		/// # </weight>
-		#[weight = T::WeightInfo::report_awesome(reason.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::report_awesome(reason.len() as u32),
+			T::WeightInfo::report_awesome_proof_size(reason.len() as u32),
+		)]
 		fn report_awesome(origin, reason: Vec<u8>, who: T::AccountId) {
 			let finder = ensure_signed(origin)?;
 
@@ -670,7 +976,10 @@ decl_module! {
 		/// - DbReads: `Tips`, `origin account`
 		/// - DbWrites: `Reasons`, `Tips`, `origin account`
 		/// # </weight>
-		#[weight = T::WeightInfo::retract_tip()]
+		#[weight = WithProofSize(
+			T::WeightInfo::retract_tip(),
+			T::WeightInfo::retract_tip_proof_size(),
+		)]
 		fn retract_tip(origin, hash: T::Hash) {
 			let who = ensure_signed(origin)?;
 			let tip = Tips::<T, I>::get(&hash).ok_or(Error::<T, I>::UnknownTip)?;
@@ -706,7 +1015,10 @@ decl_module! {
 		/// - DbReads: `Tippers`, `Reasons`
 		/// - DbWrites: `Reasons`, `Tips`
 		/// # </weight>
-		#[weight = T::WeightInfo::tip_new(reason.len() as u32, T::Tippers::max_len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::tip_new(reason.len() as u32, T::Tippers::max_len() as u32),
+			T::WeightInfo::tip_new_proof_size(reason.len() as u32, T::Tippers::max_len() as u32),
+		)]
 		fn tip_new(origin, reason: Vec<u8>, who: T::AccountId, #[compact] tip_value: BalanceOf<T, I>) {
 			let tipper = ensure_signed(origin)?;
 			ensure!(T::Tippers::contains(&tipper), BadOrigin);
@@ -754,7 +1066,10 @@ decl_module! {
 		/// - DbReads: `Tippers`, `Tips`
 		/// - DbWrites: `Tips`
 		/// # </weight>
-		#[weight = T::WeightInfo::tip(T::Tippers::max_len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::tip(T::Tippers::max_len() as u32),
+			T::WeightInfo::tip_proof_size(T::Tippers::max_len() as u32),
+		)]
 		fn tip(origin, hash: T::Hash, #[compact] tip_value: BalanceOf<T, I>) {
 			let tipper = ensure_signed(origin)?;
 			ensure!(T::Tippers::contains(&tipper), BadOrigin);
@@ -783,7 +1098,10 @@ decl_module! {
 		/// - DbReads: `Tips`, `Tippers`, `tip finder`
 		/// - DbWrites: `Reasons`, `Tips`, `Tippers`, `tip finder`
 		/// # </weight>
-		#[weight = T::WeightInfo::close_tip(T::Tippers::max_len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::close_tip(T::Tippers::max_len() as u32),
+			T::WeightInfo::close_tip_proof_size(T::Tippers::max_len() as u32),
+		)]
 		fn close_tip(origin, hash: T::Hash) {
 			ensure_signed(origin)?;
 
@@ -808,7 +1126,10 @@ decl_module! {
 		/// - `fee`: The curator fee.
 		/// - `value`: The total payment amount of this bounty, curator fee included.
 		/// - `description`: The description of this bounty.
-		#[weight = T::WeightInfo::propose_bounty(description.len() as u32)]
+		#[weight = WithProofSize(
+			T::WeightInfo::propose_bounty(description.len() as u32),
+			T::WeightInfo::propose_bounty_proof_size(description.len() as u32),
+		)]
 		fn propose_bounty(
 			origin,
 			#[compact] value: BalanceOf<T, I>,
@@ -828,7 +1149,10 @@ decl_module! {
 		/// - Limited storage reads.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = T::WeightInfo::approve_bounty()]
+		#[weight = WithProofSize(
+			T::WeightInfo::approve_bounty(),
+			T::WeightInfo::approve_bounty_proof_size(),
+		)]
 		fn approve_bounty(origin, #[compact] bounty_id: ProposalIndex) {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
@@ -853,7 +1177,10 @@ decl_module! {
 		/// - Limited storage reads.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = T::WeightInfo::propose_curator()]
+		#[weight = WithProofSize(
+			T::WeightInfo::propose_curator(),
+			T::WeightInfo::propose_curator_proof_size(),
+		)]
 		fn propose_curator(
 			origin,
 			#[compact] bounty_id: ProposalIndex,
@@ -899,7 +1226,10 @@ decl_module! {
 		/// - Limited storage reads.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = T::WeightInfo::unassign_curator()]
+		#[weight = WithProofSize(
+			T::WeightInfo::unassign_curator(),
+			T::WeightInfo::unassign_curator_proof_size(),
+		)]
 		fn unassign_curator(
 			origin,
 			#[compact] bounty_id: ProposalIndex,
@@ -981,7 +1311,10 @@ decl_module! {
 		/// - Limited storage reads.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = T::WeightInfo::accept_curator()]
+		#[weight = WithProofSize(
+			T::WeightInfo::accept_curator(),
+			T::WeightInfo::accept_curator_proof_size(),
+		)]
 		fn accept_curator(origin, #[compact] bounty_id: ProposalIndex) {
 			let signer = ensure_signed(origin)?;
 
@@ -1012,7 +1345,10 @@ decl_module! {
 		///
 		/// - `bounty_id`: Bounty ID to award.
 		/// - `beneficiary`: The beneficiary account whom will receive the payout.
-		#[weight = T::WeightInfo::award_bounty()]
+		#[weight = WithProofSize(
+			T::WeightInfo::award_bounty(),
+			T::WeightInfo::award_bounty_proof_size(),
+		)]
 		fn award_bounty(origin, #[compact] bounty_id: ProposalIndex, beneficiary: <T::Lookup as StaticLookup>::Source) {
 			let signer = ensure_signed(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
@@ -1025,6 +1361,10 @@ decl_module! {
 						..
 					} => {
 						ensure!(signer == *curator, Error::<T, I>::RequireCurator);
+						ensure!(
+							ParentChildBounties::<I>::get(bounty_id) == 0,
+							Error::<T, I>::HasActiveChildBounty,
+						);
 					},
 					_ => return Err(Error::<T, I>::UnexpectedStatus.into()),
 				}
@@ -1045,7 +1385,10 @@ decl_module! {
 		/// The dispatch origin for this call must be the beneficiary of this bounty.
 		///
 		/// - `bounty_id`: Bounty ID to claim.
-		#[weight = T::WeightInfo::claim_bounty()]
+		#[weight = WithProofSize(
+			T::WeightInfo::claim_bounty(),
+			T::WeightInfo::claim_bounty_proof_size(),
+		)]
 		fn claim_bounty(origin, #[compact] bounty_id: BountyIndex) {
 			let _ = ensure_signed(origin)?; // anyone can trigger claim
 
@@ -1078,12 +1421,20 @@ decl_module! {
 		/// Only `T::RejectOrigin` is able to cancel a bounty.
 		///
 		/// - `bounty_id`: Bounty ID to cancel.
-		#[weight = T::WeightInfo::close_bounty_proposed().max(T::WeightInfo::close_bounty_active())]
+		#[weight = WithProofSize(
+			T::WeightInfo::close_bounty_proposed().max(T::WeightInfo::close_bounty_active()),
+			T::WeightInfo::close_bounty_proposed_proof_size()
+				.max(T::WeightInfo::close_bounty_active_proof_size()),
+		)]
 		fn close_bounty(origin, #[compact] bounty_id: BountyIndex) -> DispatchResultWithPostInfo {
 			T::RejectOrigin::ensure_origin(origin)?;
 
 			Bounties::<T, I>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResultWithPostInfo {
 				let bounty = maybe_bounty.as_ref().ok_or(Error::<T, I>::InvalidIndex)?;
+				ensure!(
+					ParentChildBounties::<I>::get(bounty_id) == 0,
+					Error::<T, I>::HasActiveChildBounty,
+				);
 
 				match &bounty.status {
 					BountyStatus::Proposed => {
@@ -1140,7 +1491,10 @@ decl_module! {
 		///
 		/// - `bounty_id`: Bounty ID to extend.
 		/// - `remark`: additional information.
-		#[weight = T::WeightInfo::extend_bounty_expiry()]
+		#[weight = WithProofSize(
+			T::WeightInfo::extend_bounty_expiry(),
+			T::WeightInfo::extend_bounty_expiry_proof_size(),
+		)]
 		fn extend_bounty_expiry(origin, #[compact] bounty_id: BountyIndex, _remark: Vec<u8>) {
 			let signer = ensure_signed(origin)?;
 
@@ -1161,6 +1515,471 @@ decl_module! {
 			Self::deposit_event(Event::<T, I>::BountyExtended(bounty_id));
 		}
 
+		/// Add a child bounty, carved out of the funds of an active bounty.
+		///
+		/// The dispatch origin for this call must be the curator of the parent bounty.
+		///
+		/// The value of the child bounty is transferred from the account of the parent bounty to
+		/// the account of the child bounty, and deducted from the value of the parent bounty. The
+		/// parent bounty must be left with more than its curator fee.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `value`: The total payment amount of this child bounty, curator fee included.
+		/// - `description`: The description of this child bounty.
+		///
+		/// Emits `ChildBountyAdded`.
+		#[weight = WithProofSize(
+			T::WeightInfo::add_child_bounty(description.len() as u32),
+			T::WeightInfo::add_child_bounty_proof_size(description.len() as u32),
+		)]
+		fn add_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] value: BalanceOf<T, I>,
+			description: Vec<u8>,
+		) {
+			let signer = ensure_signed(origin)?;
+			let parent_curator = Self::parent_curator(parent_bounty_id)?;
+			ensure!(signer == parent_curator, Error::<T, I>::RequireCurator);
+			ensure!(description.len() <= T::MaximumReasonLength::get() as usize, Error::<T, I>::ReasonTooBig);
+			ensure!(value >= T::ChildBountyValueMinimum::get(), Error::<T, I>::InvalidValue);
+			ensure!(
+				ParentChildBounties::<I>::get(parent_bounty_id) < T::MaxActiveChildBountyCount::get(),
+				Error::<T, I>::TooManyChildBounties,
+			);
+			let mut parent_bounty = Self::bounties(parent_bounty_id).ok_or(Error::<T, I>::InvalidIndex)?;
+			parent_bounty.value = parent_bounty.value.saturating_sub(value);
+			ensure!(parent_bounty.fee < parent_bounty.value, Error::<T, I>::InvalidValue);
+
+			let child_bounty_id = Self::child_bounty_count();
+			T::Currency::transfer(
+				&Self::bounty_account_id(parent_bounty_id),
+				&Self::child_bounty_account_id(child_bounty_id),
+				value,
+				KeepAlive,
+			)?;
+			Bounties::<T, I>::insert(parent_bounty_id, &parent_bounty);
+
+			ChildBountyCount::<I>::put(child_bounty_id + 1);
+			ParentChildBounties::<I>::mutate(parent_bounty_id, |count| *count += 1);
+			let child_bounty = ChildBounty {
+				parent_bounty: parent_bounty_id,
+				value,
+				fee: Zero::zero(),
+				curator_deposit: Zero::zero(),
+				status: ChildBountyStatus::Added,
+			};
+			ChildBounties::<T, I>::insert(parent_bounty_id, child_bounty_id, &child_bounty);
+			ChildBountyDescriptions::<I>::insert(child_bounty_id, description);
+
+			Self::deposit_event(RawEvent::ChildBountyAdded(parent_bounty_id, child_bounty_id));
+		}
+
+		/// Propose a curator for a child bounty.
+		///
+		/// The dispatch origin for this call must be the curator of the parent bounty.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `child_bounty_id`: Index of the child bounty.
+		/// - `curator`: The account proposed as curator of the child bounty.
+		/// - `fee`: The curator fee, included in the value of the child bounty.
+		#[weight = WithProofSize(
+			T::WeightInfo::propose_child_bounty_curator(),
+			T::WeightInfo::propose_child_bounty_curator_proof_size(),
+		)]
+		fn propose_child_bounty_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			curator: <T::Lookup as StaticLookup>::Source,
+			#[compact] fee: BalanceOf<T, I>,
+		) {
+			let signer = ensure_signed(origin)?;
+			let curator = T::Lookup::lookup(curator)?;
+			let parent_curator = Self::parent_curator(parent_bounty_id)?;
+			ensure!(signer == parent_curator, Error::<T, I>::RequireCurator);
+
+			ChildBounties::<T, I>::try_mutate_exists(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T, I>::InvalidIndex)?;
+					match child_bounty.status {
+						ChildBountyStatus::Added | ChildBountyStatus::CuratorProposed { .. } => {},
+						_ => return Err(Error::<T, I>::UnexpectedStatus.into()),
+					};
+
+					ensure!(fee < child_bounty.value, Error::<T, I>::InvalidFee);
+
+					child_bounty.status = ChildBountyStatus::CuratorProposed { curator };
+					child_bounty.fee = fee;
+
+					Ok(())
+				},
+			)?;
+		}
+
+		/// Accept the curator role for a child bounty.
+		/// A deposit will be reserved from curator and refund upon successful payout.
+		///
+		/// The dispatch origin for this call must be the proposed curator of the child bounty.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `child_bounty_id`: Index of the child bounty.
+		#[weight = WithProofSize(
+			T::WeightInfo::accept_child_bounty_curator(),
+			T::WeightInfo::accept_child_bounty_curator_proof_size(),
+		)]
+		fn accept_child_bounty_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let signer = ensure_signed(origin)?;
+			Self::parent_curator(parent_bounty_id)?;
+
+			ChildBounties::<T, I>::try_mutate_exists(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T, I>::InvalidIndex)?;
+					match child_bounty.status {
+						ChildBountyStatus::CuratorProposed { ref curator } => {
+							ensure!(signer == *curator, Error::<T, I>::RequireCurator);
+
+							let deposit = T::BountyCuratorDeposit::get() * child_bounty.fee;
+							T::Currency::reserve(curator, deposit)?;
+							child_bounty.curator_deposit = deposit;
+							child_bounty.status = ChildBountyStatus::Active { curator: curator.clone() };
+
+							Ok(())
+						},
+						_ => Err(Error::<T, I>::UnexpectedStatus.into()),
+					}
+				},
+			)?;
+		}
+
+		/// Unassign the curator of a child bounty.
+		///
+		/// The dispatch origin for this call must be either `T::RejectOrigin`, the curator of the
+		/// parent bounty or the curator of the child bounty.
+		///
+		/// If called by `T::RejectOrigin` or the curator of the parent bounty, an active curator
+		/// of the child bounty is slashed. If called by the curator of the child bounty, they give
+		/// up the role and their deposit is returned.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `child_bounty_id`: Index of the child bounty.
+		#[weight = WithProofSize(
+			T::WeightInfo::unassign_child_bounty_curator(),
+			T::WeightInfo::unassign_child_bounty_curator_proof_size(),
+		)]
+		fn unassign_child_bounty_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+			let maybe_parent_curator = Self::parent_curator(parent_bounty_id).ok();
+
+			ChildBounties::<T, I>::try_mutate_exists(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T, I>::InvalidIndex)?;
+					let curator = match &child_bounty.status {
+						ChildBountyStatus::Added => return Err(Error::<T, I>::UnexpectedStatus.into()),
+						ChildBountyStatus::CuratorProposed { curator } |
+						ChildBountyStatus::Active { curator } |
+						ChildBountyStatus::PendingPayout { curator, .. } => curator.clone(),
+					};
+					let is_curator = maybe_sender.as_ref() == Some(&curator);
+					let is_privileged = match &maybe_sender {
+						None => true,
+						Some(sender) => maybe_parent_curator.as_ref() == Some(sender),
+					};
+					ensure!(is_curator || is_privileged, BadOrigin);
+					if let ChildBountyStatus::PendingPayout { .. } = child_bounty.status {
+						// Only a privileged origin may claim that an awarding curator acted
+						// maliciously.
+						ensure!(is_privileged, BadOrigin);
+					}
+
+					if !child_bounty.curator_deposit.is_zero() {
+						if is_curator {
+							let _ = T::Currency::unreserve(&curator, child_bounty.curator_deposit);
+						} else {
+							let imbalance = T::Currency::slash_reserved(&curator, child_bounty.curator_deposit).0;
+							T::OnSlash::on_unbalanced(imbalance);
+						}
+						child_bounty.curator_deposit = Zero::zero();
+					}
+
+					child_bounty.status = ChildBountyStatus::Added;
+					Ok(())
+				},
+			)?;
+		}
+
+		/// Award a child bounty to a beneficiary account. The beneficiary will be able to claim
+		/// the funds after a delay.
+		///
+		/// The dispatch origin for this call must be the curator of the child bounty.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `child_bounty_id`: Index of the child bounty.
+		/// - `beneficiary`: The beneficiary account whom will receive the payout.
+		///
+		/// Emits `ChildBountyAwarded`.
+		#[weight = WithProofSize(
+			T::WeightInfo::award_child_bounty(),
+			T::WeightInfo::award_child_bounty_proof_size(),
+		)]
+		fn award_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) {
+			let signer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			Self::parent_curator(parent_bounty_id)?;
+
+			ChildBounties::<T, I>::try_mutate_exists(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T, I>::InvalidIndex)?;
+					match &child_bounty.status {
+						ChildBountyStatus::Active { curator } => {
+							ensure!(signer == *curator, Error::<T, I>::RequireCurator);
+						},
+						_ => return Err(Error::<T, I>::UnexpectedStatus.into()),
+					}
+					child_bounty.status = ChildBountyStatus::PendingPayout {
+						curator: signer,
+						beneficiary: beneficiary.clone(),
+						unlock_at: system::Module::<T>::block_number() + T::BountyDepositPayoutDelay::get(),
+					};
+
+					Ok(())
+				},
+			)?;
+
+			Self::deposit_event(RawEvent::ChildBountyAwarded(parent_bounty_id, child_bounty_id, beneficiary));
+		}
+
+		/// Claim the payout from an awarded child bounty after payout delay.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `child_bounty_id`: Index of the child bounty.
+		///
+		/// Emits `ChildBountyClaimed`.
+		#[weight = WithProofSize(
+			T::WeightInfo::claim_child_bounty(),
+			T::WeightInfo::claim_child_bounty_proof_size(),
+		)]
+		fn claim_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let _ = ensure_signed(origin)?; // anyone can trigger claim
+
+			ChildBounties::<T, I>::try_mutate_exists(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_ref().ok_or(Error::<T, I>::InvalidIndex)?;
+					if let ChildBountyStatus::PendingPayout { curator, beneficiary, unlock_at } = &child_bounty.status {
+						ensure!(system::Module::<T>::block_number() >= *unlock_at, Error::<T, I>::Premature);
+						let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+						let balance = T::Currency::free_balance(&child_bounty_account);
+						let fee = child_bounty.fee.min(balance); // just to be safe
+						let payout = balance.saturating_sub(fee);
+						let _ = T::Currency::unreserve(&curator, child_bounty.curator_deposit);
+						let _ = T::Currency::transfer(&child_bounty_account, &curator, fee, AllowDeath); // should not fail
+						let _ = T::Currency::transfer(&child_bounty_account, &beneficiary, payout, AllowDeath); // should not fail
+
+						Self::deposit_event(RawEvent::ChildBountyClaimed(
+							parent_bounty_id, child_bounty_id, payout, beneficiary.clone(),
+						));
+						Self::remove_child_bounty(parent_bounty_id, child_bounty_id);
+						*maybe_child_bounty = None;
+						Ok(())
+					} else {
+						Err(Error::<T, I>::UnexpectedStatus.into())
+					}
+				},
+			)?;
+		}
+
+		/// Close a child bounty which has not been awarded, returning its funds to the parent
+		/// bounty and adding them back to its value. The curator deposit will be unreserved if
+		/// possible.
+		///
+		/// The dispatch origin for this call must be either `T::RejectOrigin` or the curator of
+		/// the parent bounty.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `child_bounty_id`: Index of the child bounty.
+		///
+		/// Emits `ChildBountyCanceled`.
+		#[weight = WithProofSize(
+			T::WeightInfo::close_child_bounty(),
+			T::WeightInfo::close_child_bounty_proof_size(),
+		)]
+		fn close_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+			if let Some(sender) = maybe_sender {
+				let parent_curator = Self::parent_curator(parent_bounty_id)?;
+				ensure!(sender == parent_curator, Error::<T, I>::RequireCurator);
+			}
+
+			ChildBounties::<T, I>::try_mutate_exists(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_ref().ok_or(Error::<T, I>::InvalidIndex)?;
+					match &child_bounty.status {
+						ChildBountyStatus::Added |
+						ChildBountyStatus::CuratorProposed { .. } => {
+							// Nothing extra to do besides the removal of the child bounty below.
+						},
+						ChildBountyStatus::Active { curator } => {
+							// Cancelled by the parent curator, refund deposit of the working curator.
+							let _ = T::Currency::unreserve(&curator, child_bounty.curator_deposit);
+						},
+						ChildBountyStatus::PendingPayout { .. } => {
+							// The curator should first be unassigned, slashing their deposit.
+							return Err(Error::<T, I>::PendingPayout.into())
+						},
+					}
+
+					let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+					let balance = T::Currency::free_balance(&child_bounty_account);
+					let _ = T::Currency::transfer(
+						&child_bounty_account,
+						&Self::bounty_account_id(parent_bounty_id),
+						balance,
+						AllowDeath,
+					); // should not fail
+					Bounties::<T, I>::mutate(parent_bounty_id, |maybe_bounty| {
+						if let Some(bounty) = maybe_bounty {
+							bounty.value = bounty.value.saturating_add(balance);
+						}
+					});
+
+					Self::remove_child_bounty(parent_bounty_id, child_bounty_id);
+					*maybe_child_bounty = None;
+
+					Self::deposit_event(RawEvent::ChildBountyCanceled(parent_bounty_id, child_bounty_id));
+					Ok(())
+				},
+			)?;
+		}
+
+		/// Approve a spend of an asset held by the treasury, paid to `beneficiary` in
+		/// `installments` installments of `amount` each, `period` blocks apart.
+		///
+		/// The treasury must hold at least `amount` of the asset. The first installment is paid at
+		/// the start of the next block. An installment which cannot be paid is retried `period`
+		/// blocks later, until `MaxAssetPaymentFailures` installments in a row could not be paid.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// - `asset_id`: The asset to be paid.
+		/// - `amount`: The amount paid in each installment.
+		/// - `beneficiary`: The account to which the installments are paid.
+		/// - `installments`: The number of installments.
+		/// - `period`: The number of blocks between successive installments.
+		///
+		/// Emits `AssetSpendApproved`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `AssetSpendCount`, `treasury asset balance`
+		/// - DbWrites: `AssetSpendCount`, `AssetSpends`, `AssetPayouts`
+		/// # </weight>
+		#[weight = WithProofSize(
+			(T::WeightInfo::spend_asset(), DispatchClass::Operational),
+			T::WeightInfo::spend_asset_proof_size(),
+		)]
+		fn spend_asset(
+			origin,
+			asset_id: AssetIdOf<T, I>,
+			#[compact] amount: AssetBalanceOf<T, I>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] installments: u32,
+			period: T::BlockNumber,
+		) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(
+				!amount.is_zero() && installments > 0 && !period.is_zero(),
+				Error::<T, I>::InvalidSchedule,
+			);
+			ensure!(
+				T::Assets::balance(asset_id, &Self::account_id()) >= amount,
+				Error::<T, I>::InsufficientAssets,
+			);
+
+			let index = Self::asset_spend_count();
+			AssetSpendCount::<I>::put(index + 1);
+			let spend = AssetSpend {
+				asset_id,
+				beneficiary: beneficiary.clone(),
+				amount,
+				installments,
+				period,
+				failures: 0,
+			};
+			AssetSpends::<T, I>::insert(index, spend);
+			let next = system::Module::<T>::block_number() + 1.into();
+			AssetPayouts::<T, I>::append(next, index);
+
+			Self::deposit_event(RawEvent::AssetSpendApproved(
+				index, asset_id, amount, installments, beneficiary,
+			));
+		}
+
+		/// Cancel the installments which remain to be paid of an asset spend.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// - `index`: The index of the asset spend.
+		///
+		/// Emits `AssetSpendCancelled`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `AssetSpends`
+		/// - DbWrites: `AssetSpends`
+		/// # </weight>
+		#[weight = WithProofSize(
+			(T::WeightInfo::cancel_asset_spend(), DispatchClass::Operational),
+			T::WeightInfo::cancel_asset_spend_proof_size(),
+		)]
+		fn cancel_asset_spend(origin, #[compact] index: AssetSpendIndex) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			// Any installment still queued in `AssetPayouts` is skipped once it is due.
+			AssetSpends::<T, I>::take(index).ok_or(Error::<T, I>::InvalidIndex)?;
+
+			Self::deposit_event(RawEvent::AssetSpendCancelled(index));
+		}
+
 		/// # <weight>
 		/// - Complexity: `O(A)` where `A` is the number of approvals
 		/// - Db reads and writes: `Approvals`, `pot account data`
@@ -1169,12 +1988,14 @@ decl_module! {
 		/// - The weight is overestimated if some approvals got missed.
 		/// # </weight>
 		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = Self::pay_asset_installments(n);
+
 			// Check to see if we should spend some funds!
 			if (n % T::SpendPeriod::get()).is_zero() {
-				Self::spend_funds()
-			} else {
-				0
+				weight = weight.saturating_add(Self::spend_funds());
 			}
+
+			weight
 		}
 	}
 }
@@ -1197,6 +2018,33 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 		T::ModuleId::get().into_sub_account(("bt", id))
 	}
 
+	/// The account ID holding the funds of a child bounty, carved out of its parent bounty.
+	///
+	/// Child bounties are indexed globally, not per parent bounty, so the index alone identifies
+	/// the account.
+	pub fn child_bounty_account_id(id: BountyIndex) -> T::AccountId {
+		// "cb" is the two byte prefix of child bounties, as "bt" is for bounties, leaving two
+		// bytes of a 16 byte account id (used by test) for the child bounty index
+		T::ModuleId::get().into_sub_account(("cb", id))
+	}
+
+	/// The curator of bounty `id`, which must be active.
+	fn parent_curator(id: BountyIndex) -> Result<T::AccountId, DispatchError> {
+		let bounty = Self::bounties(id).ok_or(Error::<T, I>::InvalidIndex)?;
+		match bounty.status {
+			BountyStatus::Active { curator, .. } => Ok(curator),
+			_ => Err(Error::<T, I>::ParentBountyNotActive.into()),
+		}
+	}
+
+	/// Remove the description of a child bounty and its count against its parent bounty.
+	fn remove_child_bounty(parent_bounty_id: BountyIndex, child_bounty_id: BountyIndex) {
+		ChildBountyDescriptions::<I>::remove(child_bounty_id);
+		ParentChildBounties::<I>::mutate_exists(parent_bounty_id, |maybe_count| {
+			*maybe_count = maybe_count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+		});
+	}
+
 	/// The needed bond for a proposal whose spend is `value`.
 	fn calculate_bond(value: BalanceOf<T, I>) -> BalanceOf<T, I> {
 		T::ProposalBondMinimum::get().max(T::ProposalBond::get() * value)
@@ -1379,6 +2227,50 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 		total_weight
 	}
 
+	/// Pay the installments of asset spends which are due at the start of block `now`.
+	fn pay_asset_installments(now: T::BlockNumber) -> Weight {
+		let due = AssetPayouts::<T, I>::take(now);
+		if due.is_empty() {
+			return T::WeightInfo::on_initialize_asset_spends(0)
+		}
+
+		let account_id = Self::account_id();
+		for &index in due.iter() {
+			AssetSpends::<T, I>::mutate_exists(index, |maybe_spend| {
+				// The spend has been cancelled.
+				let spend = match maybe_spend {
+					Some(spend) => spend,
+					None => return,
+				};
+
+				match T::Assets::transfer(spend.asset_id, &account_id, &spend.beneficiary, spend.amount) {
+					Ok(()) => {
+						spend.installments = spend.installments.saturating_sub(1);
+						spend.failures = 0;
+						Self::deposit_event(RawEvent::AssetPaid(
+							index, spend.asset_id, spend.amount, spend.beneficiary.clone(),
+						));
+					},
+					Err(_) => {
+						spend.failures = spend.failures.saturating_add(1);
+						Self::deposit_event(RawEvent::AssetPaymentFailed(index));
+					},
+				}
+
+				if spend.failures >= T::MaxAssetPaymentFailures::get() {
+					*maybe_spend = None;
+					Self::deposit_event(RawEvent::AssetSpendDropped(index));
+				} else if spend.installments == 0 {
+					*maybe_spend = None;
+				} else {
+					AssetPayouts::<T, I>::append(now + spend.period, index);
+				}
+			});
+		}
+
+		T::WeightInfo::on_initialize_asset_spends(due.len() as u32)
+	}
+
 	/// Return the amount of money in the pot.
 	// The existential deposit is not part of the pot so treasury account never gets deleted.
 	fn pot() -> BalanceOf<T, I> {
@@ -1420,7 +2312,7 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 	pub fn migrate_retract_tip_for_tip_new() {
 		/// An open tipping "motion". Retains all details of a tip including information on the finder
 		/// and the members who have voted.
-		#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+		#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 		pub struct OldOpenTip<
 			AccountId: Parameter,
 			Balance: Parameter,
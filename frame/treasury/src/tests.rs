@@ -46,6 +46,7 @@ impl_outer_event! {
 	pub enum Event for Test {
 		system<T>,
 		pallet_balances<T>,
+		pallet_assets<T>,
 		treasury<T>,
 	}
 }
@@ -77,6 +78,9 @@ impl frame_system::Trait for Test {
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockProofSize = ();
+	type MaxFeelessTransactions = ();
+	type FeelessRateLimitPeriod = ();
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type MaximumBlockLength = MaximumBlockLength;
 	type Version = ();
@@ -98,6 +102,11 @@ impl pallet_balances::Trait for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+impl pallet_assets::Trait for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+}
 thread_local! {
 	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
 }
@@ -139,6 +148,9 @@ parameter_types! {
 	pub const MaximumReasonLength: u32 = 16384;
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: u64 = 1;
+	pub const ChildBountyValueMinimum: u64 = 1;
+	pub const MaxActiveChildBountyCount: u32 = 2;
+	pub const MaxAssetPaymentFailures: u32 = 2;
 }
 impl Trait for Test {
 	type ModuleId = TreasuryModuleId;
@@ -162,11 +174,16 @@ impl Trait for Test {
 	type BountyCuratorDeposit = BountyCuratorDeposit;
 	type BountyValueMinimum = BountyValueMinimum;
 	type MaximumReasonLength = MaximumReasonLength;
+	type ChildBountyValueMinimum = ChildBountyValueMinimum;
+	type MaxActiveChildBountyCount = MaxActiveChildBountyCount;
 	type BurnDestination = ();  // Just gets burned.
+	type Assets = Assets;
+	type MaxAssetPaymentFailures = MaxAssetPaymentFailures;
 	type WeightInfo = ();
 }
 type System = frame_system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
+type Assets = pallet_assets::Module<Test>;
 type Treasury = Module<Test>;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -179,7 +196,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	t.into()
 }
 
-fn last_event() -> RawEvent<u64, u128, H256, DefaultInstance> {
+fn last_event() -> RawEvent<u64, u128, H256, u32, u64, DefaultInstance> {
 	System::events().into_iter().map(|r| r.event)
 		.filter_map(|e| {
 			if let Event::treasury(inner) = e { Some(inner) } else { None }
@@ -1148,3 +1165,304 @@ fn test_last_reward_migration() {
 		);
 	});
 }
+
+/// Create bounty 0 of value 50, with account 4 as its active curator.
+fn create_active_bounty() {
+	System::set_block_number(1);
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	Balances::make_free_balance_be(&4, 10);
+	assert_ok!(Treasury::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+	assert_ok!(Treasury::approve_bounty(Origin::root(), 0));
+
+	System::set_block_number(2);
+	<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+	assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 4));
+	assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+}
+
+#[test]
+fn add_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Treasury::add_child_bounty(Origin::signed(4), 0, 10, b"child".to_vec()),
+			Error::<Test, _>::InvalidIndex,
+		);
+		create_active_bounty();
+
+		assert_noop!(
+			Treasury::add_child_bounty(Origin::signed(1), 0, 10, b"child".to_vec()),
+			Error::<Test, _>::RequireCurator,
+		);
+		assert_noop!(
+			Treasury::add_child_bounty(Origin::signed(4), 0, 0, b"child".to_vec()),
+			Error::<Test, _>::InvalidValue,
+		);
+		// The parent bounty must be left with more than its curator fee of 4.
+		assert_noop!(
+			Treasury::add_child_bounty(Origin::signed(4), 0, 46, b"child".to_vec()),
+			Error::<Test, _>::InvalidValue,
+		);
+
+		assert_ok!(Treasury::add_child_bounty(Origin::signed(4), 0, 10, b"child".to_vec()));
+		assert_eq!(last_event(), RawEvent::ChildBountyAdded(0, 0));
+
+		assert_eq!(Treasury::child_bounties(0, 0).unwrap(), ChildBounty {
+			parent_bounty: 0,
+			value: 10,
+			fee: 0,
+			curator_deposit: 0,
+			status: ChildBountyStatus::Added,
+		});
+		assert_eq!(Treasury::child_bounty_descriptions(0), Some(b"child".to_vec()));
+		assert_eq!(Treasury::parent_child_bounties(0), 1);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 40);
+		assert_eq!(Balances::free_balance(Treasury::child_bounty_account_id(0)), 10);
+		assert_eq!(Treasury::bounties(0).unwrap().value, 40);
+
+		assert_ok!(Treasury::add_child_bounty(Origin::signed(4), 0, 10, b"child".to_vec()));
+		assert_eq!(Treasury::bounties(0).unwrap().value, 30);
+		assert_noop!(
+			Treasury::add_child_bounty(Origin::signed(4), 0, 10, b"child".to_vec()),
+			Error::<Test, _>::TooManyChildBounties,
+		);
+
+		// The parent bounty cannot be awarded or closed while it has child bounties.
+		assert_noop!(Treasury::award_bounty(Origin::signed(4), 0, 3), Error::<Test, _>::HasActiveChildBounty);
+		assert_noop!(Treasury::close_bounty(Origin::root(), 0), Error::<Test, _>::HasActiveChildBounty);
+	});
+}
+
+#[test]
+fn award_and_claim_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_bounty();
+		Balances::make_free_balance_be(&5, 10);
+		assert_ok!(Treasury::add_child_bounty(Origin::signed(4), 0, 10, b"child".to_vec()));
+
+		assert_noop!(
+			Treasury::propose_child_bounty_curator(Origin::signed(5), 0, 0, 5, 6),
+			Error::<Test, _>::RequireCurator,
+		);
+		assert_noop!(
+			Treasury::propose_child_bounty_curator(Origin::signed(4), 0, 0, 5, 10),
+			Error::<Test, _>::InvalidFee,
+		);
+		assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), 0, 0, 5, 6));
+
+		assert_noop!(
+			Treasury::accept_child_bounty_curator(Origin::signed(4), 0, 0),
+			Error::<Test, _>::RequireCurator,
+		);
+		assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), 0, 0));
+		assert_eq!(Balances::free_balance(5), 7); // initial 10 - 3 deposit
+		assert_eq!(Balances::reserved_balance(5), 3);
+
+		assert_noop!(
+			Treasury::award_child_bounty(Origin::signed(4), 0, 0, 3),
+			Error::<Test, _>::RequireCurator,
+		);
+		assert_ok!(Treasury::award_child_bounty(Origin::signed(5), 0, 0, 3));
+		assert_eq!(last_event(), RawEvent::ChildBountyAwarded(0, 0, 3));
+		assert_eq!(Treasury::child_bounties(0, 0).unwrap(), ChildBounty {
+			parent_bounty: 0,
+			value: 10,
+			fee: 6,
+			curator_deposit: 3,
+			status: ChildBountyStatus::PendingPayout {
+				curator: 5,
+				beneficiary: 3,
+				unlock_at: 5,
+			},
+		});
+		assert_noop!(Treasury::close_child_bounty(Origin::root(), 0, 0), Error::<Test, _>::PendingPayout);
+
+		assert_noop!(Treasury::claim_child_bounty(Origin::signed(1), 0, 0), Error::<Test, _>::Premature);
+
+		System::set_block_number(5);
+		assert_ok!(Treasury::claim_child_bounty(Origin::signed(1), 0, 0));
+		assert_eq!(last_event(), RawEvent::ChildBountyClaimed(0, 0, 4, 3));
+
+		assert_eq!(Balances::free_balance(5), 16); // initial 10 + fee 6
+		assert_eq!(Balances::reserved_balance(5), 0);
+		assert_eq!(Balances::free_balance(3), 4);
+		assert_eq!(Balances::free_balance(Treasury::child_bounty_account_id(0)), 0);
+		assert_eq!(Treasury::child_bounties(0, 0), None);
+		assert_eq!(Treasury::child_bounty_descriptions(0), None);
+		assert_eq!(Treasury::parent_child_bounties(0), 0);
+
+		// With no child bounties left, the parent bounty can be awarded.
+		assert_ok!(Treasury::award_bounty(Origin::signed(4), 0, 3));
+	});
+}
+
+#[test]
+fn unassign_and_close_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_bounty();
+		Balances::make_free_balance_be(&5, 10);
+		assert_ok!(Treasury::add_child_bounty(Origin::signed(4), 0, 10, b"child".to_vec()));
+
+		assert_noop!(
+			Treasury::unassign_child_bounty_curator(Origin::signed(4), 0, 0),
+			Error::<Test, _>::UnexpectedStatus,
+		);
+
+		// The child curator can give up the role, getting back their deposit.
+		assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), 0, 0, 5, 4));
+		assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), 0, 0));
+		assert_eq!(Balances::free_balance(5), 8);
+		assert_noop!(Treasury::unassign_child_bounty_curator(Origin::signed(1), 0, 0), BadOrigin);
+		assert_ok!(Treasury::unassign_child_bounty_curator(Origin::signed(5), 0, 0));
+		assert_eq!(Balances::free_balance(5), 10);
+		assert_eq!(Treasury::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+
+		// The parent curator unassigning an active child curator slashes their deposit.
+		assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), 0, 0, 5, 4));
+		assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), 0, 0));
+		assert_ok!(Treasury::unassign_child_bounty_curator(Origin::signed(4), 0, 0));
+		assert_eq!(Balances::free_balance(5), 8);
+		assert_eq!(Balances::reserved_balance(5), 0);
+
+		// Closing returns the funds to the parent bounty and the deposit to the child curator.
+		assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), 0, 0, 5, 4));
+		assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), 0, 0));
+		assert_eq!(Balances::free_balance(5), 6);
+		assert_noop!(
+			Treasury::close_child_bounty(Origin::signed(5), 0, 0),
+			Error::<Test, _>::RequireCurator,
+		);
+		assert_ok!(Treasury::close_child_bounty(Origin::root(), 0, 0));
+		assert_eq!(last_event(), RawEvent::ChildBountyCanceled(0, 0));
+
+		assert_eq!(Balances::free_balance(5), 8);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 50);
+		assert_eq!(Treasury::bounties(0).unwrap().value, 50);
+		assert_eq!(Balances::free_balance(Treasury::child_bounty_account_id(0)), 0);
+		assert_eq!(Treasury::child_bounties(0, 0), None);
+		assert_eq!(Treasury::parent_child_bounties(0), 0);
+
+		assert_ok!(Treasury::close_bounty(Origin::root(), 0));
+	});
+}
+
+fn has_event(event: RawEvent<u64, u128, H256, u32, u64, DefaultInstance>) -> bool {
+	System::events().into_iter().any(|r| r.event == Event::treasury(event.clone()))
+}
+
+/// Issue asset 0 to account 0 and move `amount` of it into the treasury.
+fn fund_treasury_with_asset(amount: u64) {
+	assert_ok!(Assets::issue(Origin::signed(0), 1000));
+	assert_ok!(Assets::transfer(Origin::signed(0), 0, Treasury::account_id(), amount));
+}
+
+#[test]
+fn spend_asset_checks_origin_and_schedule() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Treasury::spend_asset(Origin::signed(0), 0, 100, 3, 3, 5), BadOrigin);
+		assert_noop!(
+			Treasury::spend_asset(Origin::root(), 0, 0, 3, 3, 5),
+			Error::<Test, _>::InvalidSchedule,
+		);
+		assert_noop!(
+			Treasury::spend_asset(Origin::root(), 0, 100, 3, 0, 5),
+			Error::<Test, _>::InvalidSchedule,
+		);
+		assert_noop!(
+			Treasury::spend_asset(Origin::root(), 0, 100, 3, 3, 0),
+			Error::<Test, _>::InvalidSchedule,
+		);
+	});
+}
+
+#[test]
+fn spend_asset_requires_the_treasury_to_hold_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Treasury::spend_asset(Origin::root(), 0, 100, 3, 3, 5),
+			Error::<Test, _>::InsufficientAssets,
+		);
+		fund_treasury_with_asset(99);
+		assert_noop!(
+			Treasury::spend_asset(Origin::root(), 0, 100, 3, 3, 5),
+			Error::<Test, _>::InsufficientAssets,
+		);
+	});
+}
+
+#[test]
+fn asset_spend_is_dropped_after_too_many_failures() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		fund_treasury_with_asset(100);
+		assert_ok!(Treasury::spend_asset(Origin::root(), 0, 100, 3, 3, 5));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Assets::balance(0, 3), 100);
+
+		// The treasury is empty, the next installment fails once and is retried.
+		<Treasury as OnInitialize<u64>>::on_initialize(7);
+		assert!(has_event(RawEvent::AssetPaymentFailed(0)));
+		assert_eq!(Treasury::asset_payouts(12), vec![0]);
+
+		// The second failure in a row drops the spend.
+		<Treasury as OnInitialize<u64>>::on_initialize(12);
+		assert_eq!(last_event(), RawEvent::AssetSpendDropped(0));
+		assert_eq!(Treasury::asset_spends(0), None);
+		assert!(Treasury::asset_payouts(17).is_empty());
+	});
+}
+
+#[test]
+fn asset_spend_pays_installments() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		fund_treasury_with_asset(250);
+
+		assert_ok!(Treasury::spend_asset(Origin::root(), 0, 100, 3, 3, 5));
+		assert_eq!(last_event(), RawEvent::AssetSpendApproved(0, 0, 100, 3, 3));
+		assert_eq!(Treasury::asset_payouts(2), vec![0]);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Assets::balance(0, 3), 100);
+		assert!(has_event(RawEvent::AssetPaid(0, 0, 100, 3)));
+		assert_eq!(Treasury::asset_payouts(7), vec![0]);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(7);
+		assert_eq!(Assets::balance(0, 3), 200);
+
+		// Only 50 of the asset is left in the treasury, so the last installment is retried.
+		System::set_block_number(12);
+		<Treasury as OnInitialize<u64>>::on_initialize(12);
+		assert_eq!(Assets::balance(0, 3), 200);
+		assert!(has_event(RawEvent::AssetPaymentFailed(0)));
+		assert_eq!(Treasury::asset_payouts(17), vec![0]);
+
+		assert_ok!(Assets::transfer(Origin::signed(0), 0, Treasury::account_id(), 50));
+		<Treasury as OnInitialize<u64>>::on_initialize(17);
+		assert_eq!(Assets::balance(0, 3), 300);
+		assert_eq!(Assets::balance(0, Treasury::account_id()), 0);
+		assert_eq!(Treasury::asset_spends(0), None);
+		assert!(Treasury::asset_payouts(22).is_empty());
+	});
+}
+
+#[test]
+fn cancel_asset_spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		fund_treasury_with_asset(250);
+
+		assert_ok!(Treasury::spend_asset(Origin::root(), 0, 100, 3, 3, 5));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Assets::balance(0, 3), 100);
+
+		assert_noop!(Treasury::cancel_asset_spend(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Treasury::cancel_asset_spend(Origin::root(), 0));
+		assert_eq!(last_event(), RawEvent::AssetSpendCancelled(0));
+		assert_noop!(Treasury::cancel_asset_spend(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(7);
+		assert_eq!(Assets::balance(0, 3), 100);
+		assert_eq!(Assets::balance(0, Treasury::account_id()), 150);
+	});
+}
@@ -0,0 +1,44 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Supporting types for try-runtime, testing and dry-running commands.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::weights::Weight;
+use sp_runtime::RuntimeString;
+
+sp_api::decl_runtime_apis! {
+	/// Runtime api for testing the execution of a runtime upgrade.
+	pub trait TryRuntime {
+		/// Dry run the runtime upgrade, including the `pre_upgrade` and `post_upgrade` checks.
+		///
+		/// Returns the consumed weight of the migration and the maximum weight of a block, or the
+		/// reason one of the checks failed.
+		///
+		/// This is only meant to be called on a local copy of some existing state, never on-chain.
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), RuntimeString>;
+
+		/// Execute an existing block without checking its state root, e.g. with a runtime other
+		/// than the one the block was authored with.
+		///
+		/// Returns the weight consumed by the block, including the runtime upgrade if the runtime
+		/// changed since the parent block. The `pre_upgrade` and `post_upgrade` checks are not
+		/// executed.
+		fn execute_block_no_check(block: Block) -> Weight;
+	}
+}
@@ -57,12 +57,16 @@
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_core::TypeId;
 use sp_io::hashing::blake2_256;
 use frame_support::{decl_module, decl_event, decl_storage, Parameter};
 use frame_support::{
 	traits::{OriginTrait, UnfilteredDispatchable},
-	weights::{Weight, GetDispatchInfo, DispatchClass}, dispatch::PostDispatchInfo,
+	weights::{
+		Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize, GetDispatchInfo, DispatchClass,
+	},
+	dispatch::PostDispatchInfo,
 };
 use frame_system::{ensure_signed, ensure_root};
 use sp_runtime::{DispatchError, DispatchResult, traits::Dispatchable};
@@ -73,7 +77,13 @@ mod default_weights;
 
 pub trait WeightInfo {
 	fn batch(c: u32, ) -> Weight;
+	fn batch_proof_size(_c: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn as_derivative() -> Weight;
+	fn as_derivative_proof_size() -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 /// Configuration trait.
@@ -106,7 +116,7 @@ decl_event! {
 }
 
 /// A module identifier. These are per module and should be stored in a registry somewhere.
-#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode)]
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo)]
 struct IndexedUtilityModuleId(u16);
 
 impl TypeId for IndexedUtilityModuleId {
@@ -138,21 +148,27 @@ decl_module! {
 		/// `BatchInterrupted` event is deposited, along with the number of successful calls made
 		/// and the error of the failed call. If all were successful, then the `BatchCompleted`
 		/// event is deposited.
-		#[weight = (
+		#[weight = WithProofSize(
+			(
+				calls.iter()
+					.map(|call| call.get_dispatch_info().weight)
+					.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
+					.saturating_add(T::WeightInfo::batch(calls.len() as u32)),
+				{
+					let all_operational = calls.iter()
+						.map(|call| call.get_dispatch_info().class)
+						.all(|class| class == DispatchClass::Operational);
+					if all_operational {
+						DispatchClass::Operational
+					} else {
+						DispatchClass::Normal
+					}
+				},
+			),
 			calls.iter()
-				.map(|call| call.get_dispatch_info().weight)
-				.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
-				.saturating_add(T::WeightInfo::batch(calls.len() as u32)),
-			{
-				let all_operational = calls.iter()
-					.map(|call| call.get_dispatch_info().class)
-					.all(|class| class == DispatchClass::Operational);
-				if all_operational {
-					DispatchClass::Operational
-				} else {
-					DispatchClass::Normal
-				}
-			},
+				.map(|call| call.get_dispatch_info().proof_size)
+				.fold(0, |total: ProofSize, size: ProofSize| total.saturating_add(size))
+				.saturating_add(T::WeightInfo::batch_proof_size(calls.len() as u32)),
 		)]
 		fn batch(origin, calls: Vec<<T as Trait>::Call>) {
 			let is_root = ensure_root(origin.clone()).is_ok();
@@ -183,10 +199,14 @@ decl_module! {
 		/// NOTE: Prior to version *12, this was called `as_limited_sub`.
 		///
 		/// The dispatch origin for this call must be _Signed_.
-		#[weight = (
-			T::WeightInfo::as_derivative()
-				.saturating_add(call.get_dispatch_info().weight),
-			call.get_dispatch_info().class,
+		#[weight = WithProofSize(
+			(
+				T::WeightInfo::as_derivative()
+					.saturating_add(call.get_dispatch_info().weight),
+				call.get_dispatch_info().class,
+			),
+			T::WeightInfo::as_derivative_proof_size()
+				.saturating_add(call.get_dispatch_info().proof_size),
 		)]
 		fn as_derivative(origin, index: u16, call: Box<<T as Trait>::Call>) -> DispatchResult {
 			let mut origin = origin;
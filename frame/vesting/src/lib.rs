@@ -50,10 +50,14 @@
 use sp_std::prelude::*;
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode};
+use frame_support::type_info::TypeInfo;
 use sp_runtime::{DispatchResult, RuntimeDebug, traits::{
 	StaticLookup, Zero, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert
 }};
-use frame_support::{decl_module, decl_event, decl_storage, decl_error, ensure, weights::Weight};
+use frame_support::{
+	decl_module, decl_event, decl_storage, decl_error, ensure,
+	weights::{Weight, ProofSize, UNBENCHMARKED_PROOF_SIZE, WithProofSize},
+};
 use frame_support::traits::{
 	Currency, LockableCurrency, VestingSchedule, WithdrawReason, LockIdentifier,
 	ExistenceRequirement, Get,
@@ -68,11 +72,29 @@ type MaxLocksOf<T> = <<T as Trait>::Currency as LockableCurrency<<T as frame_sys
 
 pub trait WeightInfo {
 	fn vest_locked(l: u32, ) -> Weight;
+	fn vest_locked_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vest_unlocked(l: u32, ) -> Weight;
+	fn vest_unlocked_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vest_other_locked(l: u32, ) -> Weight;
+	fn vest_other_locked_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vest_other_unlocked(l: u32, ) -> Weight;
+	fn vest_other_unlocked_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn vested_transfer(l: u32, ) -> Weight;
+	fn vested_transfer_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 	fn force_vested_transfer(l: u32, ) -> Weight;
+	fn force_vested_transfer_proof_size(_l: u32, ) -> ProofSize {
+		UNBENCHMARKED_PROOF_SIZE
+	}
 }
 
 pub trait Trait: frame_system::Trait {
@@ -95,7 +117,7 @@ pub trait Trait: frame_system::Trait {
 const VESTING_ID: LockIdentifier = *b"vesting ";
 
 /// Struct to encode the vesting schedule of an individual account.
-#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct VestingInfo<Balance, BlockNumber> {
 	/// Locked amount at genesis.
 	pub locked: Balance,
@@ -209,9 +231,12 @@ decl_module! {
 		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
 		/// # </weight>
-		#[weight = T::WeightInfo::vest_locked(MaxLocksOf::<T>::get())
-			.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get()))
-		]
+		#[weight = WithProofSize(
+			T::WeightInfo::vest_locked(MaxLocksOf::<T>::get())
+				.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get())),
+			T::WeightInfo::vest_locked_proof_size(MaxLocksOf::<T>::get())
+				.max(T::WeightInfo::vest_unlocked_proof_size(MaxLocksOf::<T>::get())),
+		)]
 		fn vest(origin) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::update_lock(who)
@@ -232,9 +257,12 @@ decl_module! {
 		///     - Reads: Vesting Storage, Balances Locks, Target Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account
 		/// # </weight>
-		#[weight = T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get())
-			.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get()))
-		]
+		#[weight = WithProofSize(
+			T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get())
+				.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get())),
+			T::WeightInfo::vest_other_locked_proof_size(MaxLocksOf::<T>::get())
+				.max(T::WeightInfo::vest_other_unlocked_proof_size(MaxLocksOf::<T>::get())),
+		)]
 		fn vest_other(origin, target: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			ensure_signed(origin)?;
 			Self::update_lock(T::Lookup::lookup(target)?)
@@ -256,7 +284,10 @@ decl_module! {
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		/// # </weight>
-		#[weight = T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get()),
+			T::WeightInfo::vested_transfer_proof_size(MaxLocksOf::<T>::get()),
+		)]
 		pub fn vested_transfer(
 			origin,
 			target: <T::Lookup as StaticLookup>::Source,
@@ -293,7 +324,10 @@ decl_module! {
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, Source Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, Source Account
 		/// # </weight>
-		#[weight = T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get())]
+		#[weight = WithProofSize(
+			T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get()),
+			T::WeightInfo::force_vested_transfer_proof_size(MaxLocksOf::<T>::get()),
+		)]
 		pub fn force_vested_transfer(
 			origin,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -441,6 +475,9 @@ mod tests {
 		type BlockExecutionWeight = ();
 		type ExtrinsicBaseWeight = ();
 		type MaximumExtrinsicWeight = MaximumBlockWeight;
+		type MaximumBlockProofSize = ();
+		type MaxFeelessTransactions = ();
+		type FeelessRateLimitPeriod = ();
 		type MaximumBlockLength = MaximumBlockLength;
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
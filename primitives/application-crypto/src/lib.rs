@@ -31,6 +31,8 @@ pub use sp_core::crypto::{KeyTypeId, CryptoTypeId, key_types};
 #[doc(hidden)]
 pub use codec;
 #[doc(hidden)]
+pub use sp_type_info;
+#[doc(hidden)]
 #[cfg(feature = "std")]
 pub use serde;
 #[doc(hidden)]
@@ -203,6 +205,8 @@ macro_rules! app_crypto_public_full_crypto {
 			pub struct Public($public);
 		}
 
+		$crate::app_crypto_type_info!(Public, $public);
+
 		impl $crate::CryptoType for Public {
 			type Pair = Pair;
 		}
@@ -218,6 +222,28 @@ macro_rules! app_crypto_public_full_crypto {
 	}
 }
 
+/// Implements `TypeInfo` for an application-specific wrapper type, describing it as a composite
+/// with the wrapped type as its only field.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! app_crypto_type_info {
+	($outer:ident, $inner:ty) => {
+		impl $crate::sp_type_info::TypeInfo for $outer {
+			fn type_info() -> $crate::sp_type_info::Type {
+				$crate::sp_type_info::Type::new(
+					$crate::sp_type_info::Path::new(stringify!($outer), module_path!()),
+					$crate::sp_type_info::TypeDef::Composite($crate::sp_type_info::sp_std::vec![
+						$crate::sp_type_info::Field::unnamed(
+							$crate::sp_type_info::MetaType::new::<$inner>(),
+							stringify!($inner),
+						),
+					]),
+				)
+			}
+		}
+	}
+}
+
 /// Declares Public type which is functionally equivalent to `$public`, but is new
 /// Application-specific type whose identifier is `$key_type`.
 /// can only be used without `full_crypto` feature
@@ -237,6 +263,8 @@ macro_rules! app_crypto_public_not_full_crypto {
 			pub struct Public($public);
 		}
 
+		$crate::app_crypto_type_info!(Public, $public);
+
 		impl $crate::CryptoType for Public {}
 
 		impl $crate::AppKey for Public {
@@ -397,6 +425,8 @@ macro_rules! app_crypto_signature_full_crypto {
 			pub struct Signature($sig);
 		}
 
+		$crate::app_crypto_type_info!(Signature, $sig);
+
 		impl $crate::CryptoType for Signature {
 			type Pair = Pair;
 		}
@@ -430,6 +460,8 @@ macro_rules! app_crypto_signature_not_full_crypto {
 			pub struct Signature($sig);
 		}
 
+		$crate::app_crypto_type_info!(Signature, $sig);
+
 		impl $crate::CryptoType for Signature {}
 
 		impl $crate::AppKey for Signature {
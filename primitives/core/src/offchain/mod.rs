@@ -18,6 +18,7 @@
 //! Offchain workers types
 
 use codec::{Encode, Decode};
+use sp_type_info::TypeInfo;
 use sp_std::{prelude::{Vec, Box}, convert::TryFrom};
 use crate::{OpaquePeerId, RuntimeDebug};
 use sp_runtime_interface::pass_by::{PassByCodec, PassByInner, PassByEnum};
@@ -181,7 +182,7 @@ impl TryFrom<u32> for HttpRequestStatus {
 
 /// A blob to hold information about the local node's network state
 /// without committing to its format.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, PassByCodec)]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, PassByCodec, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Default))]
 pub struct OpaqueNetworkState {
 	/// PeerId of the local node in SCALE encoded.
@@ -191,7 +192,7 @@ pub struct OpaqueNetworkState {
 }
 
 /// Simple blob to hold a `Multiaddr` without committing to its format.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, PassByInner)]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, PassByInner, TypeInfo)]
 pub struct OpaqueMultiaddr(pub Vec<u8>);
 
 impl OpaqueMultiaddr {
@@ -495,8 +496,8 @@ pub trait Externalities: Send {
 	/// - `nodes`: a set of nodes which are allowed to connect for the local node.
 	/// each one is identified with an `OpaquePeerId`, here it just use plain bytes
 	/// without any encoding. Invalid `OpaquePeerId`s are silently ignored.
-	/// - `authorized_only`: if true, only the authorized nodes are allowed to connect,
-	/// otherwise unauthorized nodes can also be connected through other mechanism.
+	/// - `authorized_only`: ignored, whether only the authorized nodes are allowed to
+	/// connect is decided by the node operator with `--permissioned`.
 	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool);
 }
 
@@ -26,6 +26,7 @@ extern crate alloc;
 use serde::Serialize;
 
 use codec::{Encode, Decode, Input, Codec};
+use sp_type_info::TypeInfo;
 use sp_runtime::{ConsensusEngineId, RuntimeDebug, traits::NumberFor};
 use sp_std::borrow::Cow;
 use sp_std::vec::Vec;
@@ -171,7 +172,7 @@ impl<N: Codec> ConsensusLog<N> {
 /// GRANDPA happens when a voter votes on the same round (either at prevote or
 /// precommit stage) for different blocks. Proving is achieved by collecting the
 /// signed messages of conflicting votes.
-#[derive(Clone, Debug, Decode, Encode, PartialEq)]
+#[derive(Clone, Debug, Decode, Encode, PartialEq, TypeInfo)]
 pub struct EquivocationProof<H, N> {
 	set_id: SetId,
 	equivocation: Equivocation<H, N>,
@@ -244,6 +245,80 @@ impl<H, N> From<grandpa::Equivocation<AuthorityId, grandpa::Precommit<H, N>, Aut
 	}
 }
 
+/// Mirrors of the `finality-grandpa` vote types, they only exist to describe the encoding of
+/// [`Equivocation`] since `TypeInfo` can't be implemented for foreign types.
+#[allow(dead_code)]
+mod grandpa_type_info {
+	use super::{AuthorityId, AuthoritySignature, TypeInfo};
+
+	/// An equivocation (double-vote) in a given round.
+	#[derive(TypeInfo)]
+	pub struct Equivocation<V> {
+		/// The round number equivocated in.
+		round_number: u64,
+		/// The identity of the equivocator.
+		identity: AuthorityId,
+		/// The first vote in the equivocation.
+		first: (V, AuthoritySignature),
+		/// The second vote in the equivocation.
+		second: (V, AuthoritySignature),
+	}
+
+	/// A prevote for a block and its ancestors.
+	#[derive(TypeInfo)]
+	pub struct Prevote<H, N> {
+		/// The target block's hash.
+		target_hash: H,
+		/// The target block's number.
+		target_number: N,
+	}
+
+	/// A precommit for a block and its ancestors.
+	#[derive(TypeInfo)]
+	pub struct Precommit<H, N> {
+		/// The target block's hash.
+		target_hash: H,
+		/// The target block's number.
+		target_number: N,
+	}
+}
+
+impl<H, N> TypeInfo for Equivocation<H, N>
+where
+	H: TypeInfo + 'static,
+	N: TypeInfo + 'static,
+{
+	fn type_info() -> sp_type_info::Type {
+		use grandpa_type_info::{Equivocation as GrandpaEquivocation, Prevote, Precommit};
+		use sp_type_info::{Type, TypeDef, Path, Field, Variant, MetaType};
+
+		Type::new(
+			Path::new("Equivocation", module_path!()),
+			TypeDef::Variant(sp_std::vec![
+				Variant::new(
+					"Prevote",
+					sp_std::vec![Field::unnamed(
+						MetaType::new::<GrandpaEquivocation<Prevote<H, N>>>(),
+						"grandpa::Equivocation<AuthorityId, grandpa::Prevote<H, N>, AuthoritySignature>",
+					)],
+					0,
+				).docs(&[" Proof of equivocation at prevote stage."]),
+				Variant::new(
+					"Precommit",
+					sp_std::vec![Field::unnamed(
+						MetaType::new::<GrandpaEquivocation<Precommit<H, N>>>(),
+						"grandpa::Equivocation<AuthorityId, grandpa::Precommit<H, N>, AuthoritySignature>",
+					)],
+					1,
+				).docs(&[" Proof of equivocation at precommit stage."]),
+			]),
+		).docs(&[
+			" Wrapper object for GRANDPA equivocation proofs, useful for unifying prevote",
+			" and precommit equivocations under a common type.",
+		])
+	}
+}
+
 impl<H, N> Equivocation<H, N> {
 	/// Returns the authority id of the equivocator.
 	pub fn offender(&self) -> &AuthorityId {
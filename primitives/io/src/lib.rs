@@ -965,7 +965,10 @@ pub trait Offchain {
 			.map(|r| r as u32)
 	}
 
-	/// Set the authorized nodes and authorized_only flag.
+	/// Set the authorized nodes.
+	///
+	/// The `authorized_only` flag is ignored, the permissioned mode is switched by
+	/// `--permissioned` only.
 	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
 		self.extension::<OffchainExt>()
 			.expect("set_authorized_nodes can be called only in the offchain worker context")
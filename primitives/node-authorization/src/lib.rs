@@ -0,0 +1,35 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime Api to retrieve the nodes a node is allowed to connect to in a permissioned network.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::vec::Vec;
+
+pub use sp_core::OpaquePeerId;
+
+sp_api::decl_runtime_apis! {
+	/// The node authorization api.
+	///
+	/// This api is used by the client to keep the peers allowed by a network running in
+	/// permissioned mode in sync with the runtime.
+	pub trait NodeAuthorizationApi {
+		/// Retrieve the nodes the given node is allowed to be connected to.
+		fn authorized_nodes(node: OpaquePeerId) -> Vec<OpaquePeerId>;
+	}
+}
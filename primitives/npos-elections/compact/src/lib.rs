@@ -25,6 +25,7 @@ use syn::{parse::{Parse, ParseStream, Result}};
 
 mod assignment;
 mod codec;
+mod type_info;
 
 // prefix used for struct fields in compact.
 const PREFIX: &'static str = "votes";
@@ -153,6 +154,15 @@ fn struct_def(
 	let edge_count_impl = edge_count_impl(count);
 	let unique_targets_impl = unique_targets_impl(count);
 
+	let type_info_impl = type_info::type_info_impl(
+		ident.clone(),
+		voter_type.clone(),
+		target_type.clone(),
+		weight_type.clone(),
+		count,
+		compact_encoding,
+	);
+
 	let derives_and_maybe_compact_encoding = if compact_encoding {
 		// custom compact encoding.
 		let compact_impl = codec::codec_impl(
@@ -176,6 +186,8 @@ fn struct_def(
 		#derives_and_maybe_compact_encoding
 		#vis struct #ident { #singles #doubles #rest }
 
+		#type_info_impl
+
 		impl _npos::VotingLimit for #ident {
 			const LIMIT: usize = #count;
 		}
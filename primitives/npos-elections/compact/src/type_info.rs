@@ -0,0 +1,74 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Code generation for the ratio assignment type' `TypeInfo` impl.
+
+use crate::field_name_for;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+pub(crate) fn type_info_impl(
+	ident: syn::Ident,
+	voter_type: syn::Type,
+	target_type: syn::Type,
+	weight_type: syn::Type,
+	count: usize,
+	compact_encoding: bool,
+) -> TokenStream2 {
+	// with the custom compact encoding every number is described as `Compact<_>`.
+	let maybe_compact = |ty: &syn::Type| if compact_encoding {
+		quote!(_npos::sp_type_info::__Compact<#ty>)
+	} else {
+		quote!(#ty)
+	};
+	let voter = maybe_compact(&voter_type);
+	let target = maybe_compact(&target_type);
+	let weight = maybe_compact(&weight_type);
+
+	let field = |c: usize, ty: TokenStream2| {
+		let name = field_name_for(c).to_string();
+		let type_name = ty.to_string();
+		quote! {
+			_npos::sp_type_info::Field::named(
+				#name,
+				_npos::sp_type_info::MetaType::new::<#ty>(),
+				#type_name,
+			),
+		}
+	};
+
+	let single = field(1, quote!(Vec<(#voter, #target)>));
+	let double = field(2, quote!(Vec<(#voter, (#target, #weight), #target)>));
+	let rest = (3..=count).map(|c| {
+		let array_len = c - 1;
+		field(c, quote!(Vec<(#voter, [(#target, #weight); #array_len], #target)>))
+	}).collect::<TokenStream2>();
+
+	let ident_str = ident.to_string();
+	quote! {
+		impl _npos::sp_type_info::TypeInfo for #ident {
+			fn type_info() -> _npos::sp_type_info::Type {
+				_npos::sp_type_info::Type::new(
+					_npos::sp_type_info::Path::new(#ident_str, module_path!()),
+					_npos::sp_type_info::TypeDef::Composite(
+						_npos::sp_type_info::sp_std::vec![ #single #double #rest ]
+					),
+				).docs(&[" A struct to encode a election assignment in a compact way."])
+			}
+		}
+	}
+}
@@ -59,6 +59,8 @@ pub use helpers::*;
 pub use codec;
 #[doc(hidden)]
 pub use sp_arithmetic;
+#[doc(hidden)]
+pub use sp_type_info;
 
 /// Simple Extension trait to easily convert `None` from index closures to `Err`.
 ///
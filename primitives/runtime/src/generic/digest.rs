@@ -25,9 +25,10 @@ use sp_std::prelude::*;
 use crate::ConsensusEngineId;
 use crate::codec::{Decode, Encode, Input, Error};
 use sp_core::{ChangesTrieConfiguration, RuntimeDebug};
+use sp_type_info::{TypeInfo, Type, TypeDef, Path, Field, Variant, MetaType};
 
 /// Generic header digest.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, parity_util_mem::MallocSizeOf))]
 pub struct Digest<Hash> {
 	/// A list of logs in the digest.
@@ -118,7 +119,7 @@ pub enum DigestItem<Hash> {
 }
 
 /// Available changes trie signals.
-#[derive(PartialEq, Eq, Clone, Encode, Decode)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug, parity_util_mem::MallocSizeOf))]
 pub enum ChangesTrieSignal {
 	/// New changes trie configuration is enacted, starting from **next block**.
@@ -277,6 +278,41 @@ impl<Hash: Encode> Encode for DigestItem<Hash> {
 
 impl<Hash: Encode> codec::EncodeLike for DigestItem<Hash> {}
 
+impl<Hash: TypeInfo + 'static> TypeInfo for DigestItem<Hash> {
+	fn type_info() -> Type {
+		let engine_and_data = || sp_std::vec![
+			Field::unnamed(MetaType::new::<ConsensusEngineId>(), "ConsensusEngineId"),
+			Field::unnamed(MetaType::new::<Vec<u8>>(), "Vec<u8>"),
+		];
+
+		Type::new(
+			Path::new("DigestItem", module_path!()),
+			TypeDef::Variant(sp_std::vec![
+				Variant::new(
+					"ChangesTrieRoot",
+					sp_std::vec![Field::unnamed(MetaType::new::<Hash>(), "Hash")],
+					DigestItemType::ChangesTrieRoot as u8,
+				),
+				Variant::new("PreRuntime", engine_and_data(), DigestItemType::PreRuntime as u8),
+				Variant::new("Consensus", engine_and_data(), DigestItemType::Consensus as u8),
+				Variant::new("Seal", engine_and_data(), DigestItemType::Seal as u8),
+				Variant::new(
+					"ChangesTrieSignal",
+					sp_std::vec![
+						Field::unnamed(MetaType::new::<ChangesTrieSignal>(), "ChangesTrieSignal"),
+					],
+					DigestItemType::ChangesTrieSignal as u8,
+				),
+				Variant::new(
+					"Other",
+					sp_std::vec![Field::unnamed(MetaType::new::<Vec<u8>>(), "Vec<u8>")],
+					DigestItemType::Other as u8,
+				),
+			]),
+		)
+	}
+}
+
 impl<Hash: Decode> Decode for DigestItem<Hash> {
 	#[allow(deprecated)]
 	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
@@ -27,6 +27,7 @@ use crate::traits::{
 };
 use crate::generic::Digest;
 use sp_core::U256;
+use sp_type_info::{TypeInfo, Type, TypeDef, Path, Field, MetaType};
 use sp_std::{
 	convert::TryFrom,
 	fmt::Debug,
@@ -115,6 +116,25 @@ impl<Number, Hash> Encode for Header<Number, Hash> where
 	}
 }
 
+impl<Number, Hash> TypeInfo for Header<Number, Hash> where
+	Number: TypeInfo + Copy + Into<U256> + TryFrom<U256> + 'static,
+	Hash: HashT + 'static,
+	Hash::Output: TypeInfo + 'static,
+{
+	fn type_info() -> Type {
+		Type::new(
+			Path::new("Header", module_path!()),
+			TypeDef::Composite(sp_std::vec![
+				Field::named("parent_hash", MetaType::new::<Hash::Output>(), "Hash::Output"),
+				Field::named("number", MetaType::new::<codec::Compact<Number>>(), "Number"),
+				Field::named("state_root", MetaType::new::<Hash::Output>(), "Hash::Output"),
+				Field::named("extrinsics_root", MetaType::new::<Hash::Output>(), "Hash::Output"),
+				Field::named("digest", MetaType::new::<Digest<Hash::Output>>(), "Digest<Hash::Output>"),
+			]),
+		).docs(&[" Abstraction over a block header for a substrate chain."])
+	}
+}
+
 impl<Number, Hash> codec::EncodeLike for Header<Number, Hash> where
 	Number: HasCompact + Copy + Into<U256> + TryFrom<U256>,
 	Hash: HashT,
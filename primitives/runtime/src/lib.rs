@@ -32,6 +32,9 @@ pub use serde;
 #[doc(hidden)]
 pub use sp_std;
 
+#[doc(hidden)]
+pub use sp_type_info;
+
 #[doc(hidden)]
 pub use paste;
 
@@ -46,6 +49,7 @@ use sp_std::convert::TryFrom;
 use sp_core::{crypto::{self, Public}, ed25519, sr25519, ecdsa, hash::{H256, H512}};
 
 use codec::{Encode, Decode};
+use sp_type_info::TypeInfo;
 
 pub mod curve;
 pub mod generic;
@@ -95,7 +99,7 @@ pub type Justification = Vec<u8>;
 use traits::{Verify, Lazy};
 
 /// A module identifier. These are per module and should be stored in a registry somewhere.
-#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode)]
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, TypeInfo)]
 pub struct ModuleId(pub [u8; 8]);
 
 impl TypeId for ModuleId {
@@ -169,7 +173,7 @@ pub type ConsensusEngineId = [u8; 4];
 
 /// Signature verify that can work with any known signature types..
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(Eq, PartialEq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum MultiSignature {
 	/// An Ed25519 signature.
 	Ed25519(ed25519::Signature),
@@ -225,7 +229,7 @@ impl Default for MultiSignature {
 }
 
 /// Public key for any known crypto algorithm.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum MultiSigner {
 	/// An Ed25519 identity.
@@ -341,7 +345,7 @@ impl Verify for MultiSignature {
 }
 
 /// Signature verify that can work with any known signature types..
-#[derive(Eq, PartialEq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[derive(Eq, PartialEq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct AnySignature(H512);
 
@@ -386,7 +390,7 @@ pub type DispatchResult = sp_std::result::Result<(), DispatchError>;
 pub type DispatchResultWithInfo<T> = sp_std::result::Result<T, DispatchErrorWithPostInfo<T>>;
 
 /// Reason why a dispatch call failed.
-#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize))]
 pub enum DispatchError {
 	/// Some error occurred.
@@ -609,10 +613,13 @@ macro_rules! __impl_outer_config_types {
 /// 	rust_module_one: Option<ModuleOneConfig>,
 /// 	...
 /// }
+///
+/// The optional `where OnGenesis = path` clause names a function that is called with the
+/// genesis storage after every module has built its own genesis storage.
 #[macro_export]
 macro_rules! impl_outer_config {
 	(
-		pub struct $main:ident for $concrete:ident {
+		pub struct $main:ident for $concrete:ident $( where OnGenesis = $on_genesis:path )? {
 			$( $config:ident =>
 				$snake:ident $( $instance:ident )? $( <$generic:ident> )*, )*
 		}
@@ -649,6 +656,7 @@ macro_rules! impl_outer_config {
 							}
 						}
 					)*
+					$( $on_genesis(storage); )?
 					Ok(())
 				}
 			}
@@ -18,6 +18,7 @@
 use codec::{Encode, Decode};
 use sp_core::RuntimeDebug;
 use sp_std::vec::Vec;
+use sp_type_info::{TypeInfo, Type};
 
 /// A string that wraps a `&'static str` in the runtime and `String`/`Vec<u8>` on decode.
 #[derive(Eq, RuntimeDebug, Clone)]
@@ -78,6 +79,12 @@ impl Encode for RuntimeString {
 	}
 }
 
+impl TypeInfo for RuntimeString {
+	fn type_info() -> Type {
+		<str as TypeInfo>::type_info()
+	}
+}
+
 impl Decode for RuntimeString {
 	fn decode<I: codec::Input>(value: &mut I) -> Result<Self, codec::Error> {
 		Decode::decode(value).map(Self::Owned)
@@ -1176,6 +1176,23 @@ macro_rules! impl_opaque_keys {
 			)*
 		}
 
+		impl $crate::sp_type_info::TypeInfo for $name {
+			fn type_info() -> $crate::sp_type_info::Type {
+				$crate::sp_type_info::Type::new(
+					$crate::sp_type_info::Path::new(stringify!($name), module_path!()),
+					$crate::sp_type_info::TypeDef::Composite($crate::sp_std::vec![ $(
+						$crate::sp_type_info::Field::named(
+							stringify!($field),
+							$crate::sp_type_info::MetaType::new::<
+								<$type as $crate::BoundToRuntimeAppPublic>::Public
+							>(),
+							stringify!($type),
+						),
+					)* ]),
+				)
+			}
+		}
+
 		impl $name {
 			/// Generate a set of keys with optionally using the given seed.
 			///
@@ -237,6 +237,21 @@ pub trait Backend<H: Hasher>: sp_std::fmt::Debug {
 		unimplemented!()
 	}
 
+	/// Get the size (in bytes) of the storage proof recorded by the db
+	fn proof_size(&self) -> u32 {
+		unimplemented!()
+	}
+
+	/// Get the number of unique trie nodes in the storage proof recorded by the db
+	fn proof_nodes(&self) -> u32 {
+		unimplemented!()
+	}
+
+	/// Reset the storage proof recorded by the db
+	fn reset_proof_size(&self) {
+		unimplemented!()
+	}
+
 	/// Get the whitelist for tracking db reads/writes
 	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
 		Default::default()
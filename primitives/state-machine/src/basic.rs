@@ -325,6 +325,18 @@ impl Externalities for BasicExternalities {
 		unimplemented!("reset_read_write_count is not supported in Basic")
 	}
 
+	fn proof_size(&self) -> u32 {
+		unimplemented!("proof_size is not supported in Basic")
+	}
+
+	fn proof_nodes(&self) -> u32 {
+		unimplemented!("proof_nodes is not supported in Basic")
+	}
+
+	fn reset_proof_size(&mut self) {
+		unimplemented!("reset_proof_size is not supported in Basic")
+	}
+
 	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
 		unimplemented!("get_whitelist is not supported in Basic")
 	}
@@ -660,6 +660,18 @@ where
 		self.backend.reset_read_write_count()
 	}
 
+	fn proof_size(&self) -> u32 {
+		self.backend.proof_size()
+	}
+
+	fn proof_nodes(&self) -> u32 {
+		self.backend.proof_nodes()
+	}
+
+	fn reset_proof_size(&mut self) {
+		self.backend.reset_proof_size()
+	}
+
 	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
 		self.backend.get_whitelist()
 	}
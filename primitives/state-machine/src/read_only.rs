@@ -194,6 +194,18 @@ impl<'a, H: Hasher, B: 'a + Backend<H>> Externalities for ReadOnlyExternalities<
 		unimplemented!("reset_read_write_count is not supported in ReadOnlyExternalities")
 	}
 
+	fn proof_size(&self) -> u32 {
+		unimplemented!("proof_size is not supported in ReadOnlyExternalities")
+	}
+
+	fn proof_nodes(&self) -> u32 {
+		unimplemented!("proof_nodes is not supported in ReadOnlyExternalities")
+	}
+
+	fn reset_proof_size(&mut self) {
+		unimplemented!("reset_proof_size is not supported in ReadOnlyExternalities")
+	}
+
 	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
 		unimplemented!("get_whitelist is not supported in ReadOnlyExternalities")
 	}
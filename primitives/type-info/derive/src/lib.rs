@@ -0,0 +1,237 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Derive macro for the `TypeInfo` trait of `sp-type-info`.
+//!
+//! The generated type information follows the SCALE encoding produced by the codec derive
+//! macros: fields and variants marked `#[codec(skip)]` are omitted, `#[codec(compact)]` fields
+//! are described as compact and variants use their `#[codec(index = ..)]` or discriminant.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use proc_macro_crate::crate_name;
+use quote::{quote, ToTokens};
+use syn::{
+	Data, DeriveInput, Error, Fields, Ident, Lit, Meta, NestedMeta, Result, Type, parse_quote,
+	spanned::Spanned, punctuated::Punctuated, token::Comma,
+};
+
+/// Derive `TypeInfo`.
+///
+/// The crate is found through `sp-type-info` or, for pallets, the re-export in `frame-support`.
+#[proc_macro_derive(TypeInfo)]
+pub fn type_info(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	let input = syn::parse_macro_input!(input as DeriveInput);
+	match derive(input) {
+		Ok(tokens) => tokens.into(),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
+
+fn derive(mut input: DeriveInput) -> Result<TokenStream> {
+	let import = import()?;
+	let ident = &input.ident;
+	let docs = docs(&input.attrs);
+
+	let type_params = input.generics.type_params().map(|p| p.ident.clone()).collect::<Vec<_>>();
+	let mut bounded = Vec::new();
+
+	let type_def = match &input.data {
+		Data::Struct(data) => {
+			let fields = fields(&data.fields, &type_params, &mut bounded);
+			quote!( _type_info::TypeDef::Composite(#fields) )
+		},
+		Data::Enum(data) => {
+			let mut variants = Vec::new();
+			let encoded = data.variants.iter().filter(|v| !has_codec_attr(&v.attrs, "skip"));
+			for (position, variant) in encoded.enumerate() {
+				// Same as the codec derive: the explicit index, the discriminant or the position
+				// among the encoded variants.
+				let index = match codec_index(&variant.attrs)? {
+					Some(index) => index,
+					None => match &variant.discriminant {
+						Some((_, syn::Expr::Lit(syn::ExprLit { lit: Lit::Int(lit), .. }))) =>
+							lit.base10_parse::<u8>()?,
+						_ => position as u8,
+					},
+				};
+
+				let name = variant.ident.to_string();
+				let fields = fields(&variant.fields, &type_params, &mut bounded);
+				let docs = docs(&variant.attrs);
+				variants.push(quote! {
+					_type_info::Variant::new(#name, #fields, #index).docs(&[ #( #docs ),* ])
+				});
+			}
+			quote!( _type_info::TypeDef::Variant(_type_info::sp_std::vec![ #( #variants ),* ]) )
+		},
+		Data::Union(_) => return Err(Error::new(input.span(), "`TypeInfo` can not be derived for unions")),
+	};
+
+	let where_clause = input.generics.make_where_clause();
+	for param in &type_params {
+		where_clause.predicates.push(parse_quote!( #param: 'static ));
+	}
+	for ty in bounded {
+		where_clause.predicates.push(parse_quote!( #ty: _type_info::TypeInfo + 'static ));
+	}
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let name = ident.to_string();
+
+	Ok(quote! {
+		const _: () = {
+			#import
+
+			impl #impl_generics _type_info::TypeInfo for #ident #ty_generics #where_clause {
+				fn type_info() -> _type_info::Type {
+					_type_info::Type::new(
+						_type_info::Path::new(#name, module_path!()),
+						#type_def,
+					).docs(&[ #( #docs ),* ])
+				}
+			}
+		};
+	})
+}
+
+/// Describe the encoded fields, collecting the field types that depend on type parameters.
+fn fields(fields: &Fields, type_params: &[Ident], bounded: &mut Vec<Type>) -> TokenStream {
+	let fields = match fields {
+		Fields::Named(fields) => &fields.named,
+		Fields::Unnamed(fields) => &fields.unnamed,
+		Fields::Unit => return quote!( _type_info::sp_std::vec::Vec::new() ),
+	};
+
+	let fields = fields.iter()
+		.filter(|field| !has_codec_attr(&field.attrs, "skip"))
+		.map(|field| {
+			let ty = &field.ty;
+			if contains_ident(ty.to_token_stream(), type_params) {
+				bounded.push(ty.clone());
+			}
+
+			let meta_type = if has_codec_attr(&field.attrs, "compact") {
+				quote!( _type_info::MetaType::new::<_type_info::__Compact<#ty>>() )
+			} else {
+				quote!( _type_info::MetaType::new::<#ty>() )
+			};
+			let type_name = type_name(ty);
+			let docs = docs(&field.attrs);
+			let field = match &field.ident {
+				Some(ident) => {
+					let name = ident.to_string();
+					quote!( _type_info::Field::named(#name, #meta_type, #type_name) )
+				},
+				None => quote!( _type_info::Field::unnamed(#meta_type, #type_name) ),
+			};
+			quote!( #field.docs(&[ #( #docs ),* ]) )
+		})
+		.collect::<Vec<_>>();
+
+	quote!( _type_info::sp_std::vec![ #( #fields ),* ] )
+}
+
+/// Import the `sp-type-info` crate as `_type_info`.
+fn import() -> Result<TokenStream> {
+	if std::env::var("CARGO_PKG_NAME").unwrap() == "sp-type-info" {
+		return Ok(quote!( use ::sp_type_info as _type_info; ))
+	}
+
+	match crate_name("sp-type-info") {
+		Ok(name) => {
+			let ident = Ident::new(&name, Span::call_site());
+			Ok(quote!( extern crate #ident as _type_info; ))
+		},
+		Err(_) => match crate_name("frame-support") {
+			Ok(name) => {
+				let ident = Ident::new(&name, Span::call_site());
+				Ok(quote!( use #ident::type_info as _type_info; ))
+			},
+			Err(e) => Err(Error::new(Span::call_site(), &e)),
+		},
+	}
+}
+
+/// The documentation of an item, one string per line.
+fn docs(attrs: &[syn::Attribute]) -> Vec<TokenStream> {
+	attrs.iter()
+		.filter_map(|attr| match attr.parse_meta() {
+			Ok(Meta::NameValue(meta)) if meta.path.is_ident("doc") => Some(meta.lit.to_token_stream()),
+			_ => None,
+		})
+		.collect()
+}
+
+fn codec_attrs(attrs: &[syn::Attribute]) -> impl Iterator<Item = NestedMeta> + '_ {
+	attrs.iter()
+		.filter_map(|attr| match attr.parse_meta() {
+			Ok(Meta::List(list)) if list.path.is_ident("codec") => Some(list.nested),
+			_ => None,
+		})
+		.flat_map(|nested: Punctuated<NestedMeta, Comma>| nested.into_iter())
+}
+
+fn has_codec_attr(attrs: &[syn::Attribute], name: &str) -> bool {
+	codec_attrs(attrs).any(|meta| match meta {
+		NestedMeta::Meta(Meta::Path(path)) => path.is_ident(name),
+		_ => false,
+	})
+}
+
+/// The explicit `#[codec(index = ..)]` of a variant.
+fn codec_index(attrs: &[syn::Attribute]) -> Result<Option<u8>> {
+	for meta in codec_attrs(attrs) {
+		if let NestedMeta::Meta(Meta::NameValue(meta)) = meta {
+			if meta.path.is_ident("index") {
+				return match &meta.lit {
+					Lit::Str(lit) => lit.value().parse::<u8>()
+						.map(Some)
+						.map_err(|_| Error::new(lit.span(), "Invalid variant index")),
+					Lit::Int(lit) => lit.base10_parse::<u8>().map(Some),
+					lit => Err(Error::new(lit.span(), "Invalid variant index")),
+				}
+			}
+		}
+	}
+
+	Ok(None)
+}
+
+fn contains_ident(tokens: TokenStream, idents: &[Ident]) -> bool {
+	tokens.into_iter().any(|token| match token {
+		TokenTree::Ident(ident) => idents.contains(&ident),
+		TokenTree::Group(group) => contains_ident(group.stream(), idents),
+		_ => false,
+	})
+}
+
+/// The name of the type as written in the source, without the spacing added by the tokenizer.
+fn type_name(ty: &Type) -> String {
+	ty.to_token_stream().to_string()
+		.replace(" ::", "::")
+		.replace(":: ", "::")
+		.replace(" ,", ",")
+		.replace(" ;", ";")
+		.replace(" [", "[")
+		.replace("[ ", "[")
+		.replace(" ]", "]")
+		.replace(" (", "(")
+		.replace("( ", "(")
+		.replace(" )", ")")
+		.replace(" <", "<")
+		.replace("< ", "<")
+		.replace(" >", ">")
+		.replace("& ", "&")
+}
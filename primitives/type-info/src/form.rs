@@ -0,0 +1,59 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The forms in which type information is represented.
+//!
+//! Type information is first gathered in the [`MetaForm`], where referenced types are
+//! [`MetaType`]s and strings are static. Registering it in a [`Registry`](crate::Registry)
+//! turns it into the [`PortableForm`], where types are referenced by their identifier in the
+//! registry and which can be encoded and decoded.
+
+use crate::MetaType;
+use sp_std::fmt::Debug;
+
+/// On `no_std` decoding is not supported, thus strings of the portable form stay static.
+#[cfg(not(feature = "std"))]
+pub type StringBuf = &'static str;
+
+/// The string type of the [`PortableForm`].
+#[cfg(feature = "std")]
+pub type StringBuf = String;
+
+/// The representation of type references and strings within type information.
+pub trait Form {
+	/// The type used to reference other types.
+	type Type: PartialEq + Eq + Clone + Debug;
+	/// The string type.
+	type String: PartialEq + Eq + Clone + Debug;
+}
+
+/// The form of type information as it is provided by [`TypeInfo`](crate::TypeInfo).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum MetaForm {}
+
+impl Form for MetaForm {
+	type Type = MetaType;
+	type String = &'static str;
+}
+
+/// The form of type information stored in a [`PortableRegistry`](crate::PortableRegistry).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PortableForm {}
+
+impl Form for PortableForm {
+	type Type = u32;
+	type String = StringBuf;
+}
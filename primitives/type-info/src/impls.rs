@@ -0,0 +1,201 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Implementations of [`TypeInfo`] for primitive and standard library types.
+
+use crate::{TypeInfo, Type, TypeDef, TypeDefPrimitive, Path, Field, Variant, MetaType};
+use codec::Compact;
+use primitive_types::{H160, H256, H512, U256};
+use sp_std::{
+	boxed::Box, collections::{btree_map::BTreeMap, btree_set::BTreeSet, vec_deque::VecDeque},
+	marker::PhantomData, vec, vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+macro_rules! impl_primitives {
+	( $( $ty:ty => $primitive:ident ),* $(,)? ) => {
+		$(
+			impl TypeInfo for $ty {
+				fn type_info() -> Type {
+					Type::unnamed(TypeDef::Primitive(TypeDefPrimitive::$primitive))
+				}
+			}
+		)*
+	}
+}
+
+impl_primitives!(
+	bool => Bool,
+	char => Char,
+	str => Str,
+	String => Str,
+	u8 => U8,
+	u16 => U16,
+	u32 => U32,
+	u64 => U64,
+	u128 => U128,
+	U256 => U256,
+	i8 => I8,
+	i16 => I16,
+	i32 => I32,
+	i64 => I64,
+	i128 => I128,
+);
+
+macro_rules! impl_arrays {
+	( $( $n:expr ),* $(,)? ) => {
+		$(
+			impl<T: TypeInfo + 'static> TypeInfo for [T; $n] {
+				fn type_info() -> Type {
+					Type::unnamed(TypeDef::Array { len: $n, type_param: MetaType::new::<T>() })
+				}
+			}
+		)*
+	}
+}
+
+impl_arrays!(
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+	17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+	33, 36, 40, 48, 56, 64, 65, 72, 80, 96, 128, 256, 512, 1024, 2048,
+);
+
+macro_rules! impl_tuples {
+	( $first:ident $(, $rest:ident )* ) => {
+		impl<$first: TypeInfo + 'static, $( $rest: TypeInfo + 'static ),*> TypeInfo
+			for ($first, $( $rest, )*)
+		{
+			fn type_info() -> Type {
+				Type::unnamed(TypeDef::Tuple(vec![
+					MetaType::new::<$first>(),
+					$( MetaType::new::<$rest>(), )*
+				]))
+			}
+		}
+
+		impl_tuples!($( $rest ),*);
+	};
+	() => {
+		impl TypeInfo for () {
+			fn type_info() -> Type {
+				Type::unnamed(TypeDef::Tuple(Vec::new()))
+			}
+		}
+	};
+}
+
+impl_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, U);
+
+impl<T: TypeInfo + 'static> TypeInfo for [T] {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Sequence(MetaType::new::<T>()))
+	}
+}
+
+impl<T: TypeInfo + 'static> TypeInfo for Vec<T> {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Sequence(MetaType::new::<T>()))
+	}
+}
+
+impl<T: TypeInfo + 'static> TypeInfo for VecDeque<T> {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Sequence(MetaType::new::<T>()))
+	}
+}
+
+impl<T: TypeInfo + 'static> TypeInfo for BTreeSet<T> {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Sequence(MetaType::new::<T>()))
+	}
+}
+
+impl<K: TypeInfo + 'static, V: TypeInfo + 'static> TypeInfo for BTreeMap<K, V> {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Sequence(MetaType::new::<(K, V)>()))
+	}
+}
+
+impl<T: TypeInfo + 'static> TypeInfo for Option<T> {
+	fn type_info() -> Type {
+		Type::new(
+			Path::new("Option", ""),
+			TypeDef::Variant(vec![
+				Variant::new("None", Vec::new(), 0),
+				Variant::new("Some", vec![Field::unnamed(MetaType::new::<T>(), "T")], 1),
+			]),
+		)
+	}
+}
+
+impl<T: TypeInfo + 'static, E: TypeInfo + 'static> TypeInfo for Result<T, E> {
+	fn type_info() -> Type {
+		Type::new(
+			Path::new("Result", ""),
+			TypeDef::Variant(vec![
+				Variant::new("Ok", vec![Field::unnamed(MetaType::new::<T>(), "T")], 0),
+				Variant::new("Err", vec![Field::unnamed(MetaType::new::<E>(), "E")], 1),
+			]),
+		)
+	}
+}
+
+/// Indirections are transparent in the encoding, they share the type information of `T`.
+macro_rules! impl_transparent {
+	( $( $ty:ty ),* $(,)? ) => {
+		$(
+			impl<T: TypeInfo + ?Sized + 'static> TypeInfo for $ty {
+				fn type_info() -> Type {
+					T::type_info()
+				}
+			}
+		)*
+	}
+}
+
+impl_transparent!(Box<T>, &'static T);
+
+impl<T: ?Sized> TypeInfo for PhantomData<T> {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Tuple(Vec::new()))
+	}
+}
+
+impl<T: TypeInfo + 'static> TypeInfo for Compact<T> {
+	fn type_info() -> Type {
+		Type::unnamed(TypeDef::Compact(MetaType::new::<T>()))
+	}
+}
+
+macro_rules! impl_fixed_hashes {
+	( $( $ty:ident => $len:literal ),* $(,)? ) => {
+		$(
+			impl TypeInfo for $ty {
+				fn type_info() -> Type {
+					Type::new(
+						Path::new(stringify!($ty), "primitive_types"),
+						TypeDef::Composite(vec![
+							Field::unnamed(MetaType::new::<[u8; $len]>(), concat!("[u8; ", $len, "]")),
+						]),
+					)
+				}
+			}
+		)*
+	}
+}
+
+impl_fixed_hashes!(H160 => 20, H256 => 32, H512 => 64);
@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Type information for SCALE encoded types.
+//!
+//! Every type that appears in the runtime metadata implements [`TypeInfo`], which describes the
+//! shape of its SCALE encoding: composites (structs), variants (enums), sequences, fixed size
+//! arrays, tuples, primitives and compact encoded values.
+//!
+//! Types are collected in a [`Registry`] which assigns each of them a unique `u32` identifier
+//! and replaces all type references by these identifiers. The resulting [`PortableRegistry`] is
+//! self contained and can be encoded into the metadata, allowing a client to decode any value
+//! of the runtime without hand written type definitions.
+//!
+//! `TypeInfo` is usually implemented through its derive macro, which understands the
+//! `#[codec(skip)]`, `#[codec(compact)]` and `#[codec(index = ..)]` attributes:
+//!
+//! ```
+//! use codec::{Encode, Decode};
+//! use sp_type_info::{TypeInfo, Registry, MetaType, TypeDef};
+//!
+//! #[derive(Encode, Decode, TypeInfo)]
+//! struct Transfer {
+//! 	/// The receiver of the transfer.
+//! 	to: u64,
+//! 	#[codec(compact)]
+//! 	value: u128,
+//! }
+//!
+//! let mut registry = Registry::new();
+//! let id = registry.register_type(&MetaType::new::<Transfer>());
+//! let registry = sp_type_info::PortableRegistry::from(registry);
+//!
+//! match registry.resolve(id).unwrap().type_def() {
+//! 	TypeDef::Composite(fields) => assert_eq!(fields.len(), 2),
+//! 	_ => panic!("`Transfer` is a composite"),
+//! }
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+// Allows the derive macro to be used inside of this crate.
+extern crate self as sp_type_info;
+
+mod form;
+mod impls;
+mod meta_type;
+mod registry;
+mod ty;
+
+pub use form::{Form, MetaForm, PortableForm, StringBuf};
+pub use meta_type::MetaType;
+pub use registry::{IntoPortable, Registry, PortableRegistry};
+pub use ty::{Type, Path, TypeDef, TypeDefPrimitive, Field, Variant};
+
+/// Derive macro for [`TypeInfo`].
+pub use sp_type_info_derive::TypeInfo;
+
+#[doc(hidden)]
+pub use sp_std;
+#[doc(hidden)]
+pub use codec::Compact as __Compact;
+
+/// Implementors provide a description of the SCALE encoding of `Self`.
+pub trait TypeInfo {
+	/// Returns the type information of `Self`.
+	///
+	/// Other types are referenced through [`MetaType`], they are resolved when the type is
+	/// registered in a [`Registry`].
+	fn type_info() -> Type;
+}
+
+/// Returns the [`MetaType`] of the given type.
+pub fn meta_type<T: TypeInfo + ?Sized + 'static>() -> MetaType {
+	MetaType::new::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::{Encode, Compact};
+	use sp_std::marker::PhantomData;
+
+	/// A documented struct.
+	#[derive(Encode, TypeInfo)]
+	#[allow(dead_code)]
+	struct Unit;
+
+	#[derive(Encode, TypeInfo)]
+	#[allow(dead_code)]
+	struct Named<T> {
+		/// First field.
+		a: T,
+		#[codec(compact)]
+		b: u64,
+		#[codec(skip)]
+		c: PhantomData<T>,
+	}
+
+	#[derive(Encode, TypeInfo)]
+	#[allow(dead_code)]
+	enum Enum {
+		A,
+		#[codec(skip)]
+		Skipped,
+		B(u32, Vec<u8>),
+		#[codec(index = "7")]
+		C { recursive: Option<Box<Enum>> },
+	}
+
+	fn portable<T: TypeInfo + 'static>() -> (u32, PortableRegistry) {
+		let mut registry = Registry::new();
+		let id = registry.register_type(&meta_type::<T>());
+		(id, registry.into())
+	}
+
+	#[test]
+	fn primitives_and_containers_are_described() {
+		let (id, registry) = portable::<Vec<(u32, bool)>>();
+		let element = match registry.resolve(id).unwrap().type_def() {
+			TypeDef::Sequence(element) => *element,
+			_ => panic!("expected a sequence"),
+		};
+		match registry.resolve(element).unwrap().type_def() {
+			TypeDef::Tuple(fields) => {
+				assert_eq!(
+					registry.resolve(fields[0]).unwrap().type_def(),
+					&TypeDef::Primitive(TypeDefPrimitive::U32),
+				);
+				assert_eq!(
+					registry.resolve(fields[1]).unwrap().type_def(),
+					&TypeDef::Primitive(TypeDefPrimitive::Bool),
+				);
+			},
+			_ => panic!("expected a tuple"),
+		}
+
+		let (id, registry) = portable::<[u8; 32]>();
+		match registry.resolve(id).unwrap().type_def() {
+			TypeDef::Array { len, .. } => assert_eq!(*len, 32),
+			_ => panic!("expected an array"),
+		}
+
+		let (id, registry) = portable::<Compact<u128>>();
+		assert!(matches!(registry.resolve(id).unwrap().type_def(), TypeDef::Compact(_)));
+	}
+
+	#[test]
+	fn derive_describes_structs() {
+		let (id, registry) = portable::<Unit>();
+		let ty = registry.resolve(id).unwrap();
+		assert_eq!(ty.path().segments().last().map(|s| s.as_str()), Some("Unit"));
+		assert_eq!(ty.docs(), &[" A documented struct.".to_string()]);
+		assert_eq!(ty.type_def(), &TypeDef::Composite(vec![]));
+
+		let (id, registry) = portable::<Named<u32>>();
+		let fields = match registry.resolve(id).unwrap().type_def() {
+			TypeDef::Composite(fields) => fields.clone(),
+			_ => panic!("expected a composite"),
+		};
+		assert_eq!(fields.len(), 2);
+		assert_eq!(fields[0].name().map(|s| s.as_str()), Some("a"));
+		assert_eq!(fields[0].type_name().map(|s| s.as_str()), Some("T"));
+		assert_eq!(fields[0].docs(), &[" First field.".to_string()]);
+		assert_eq!(fields[1].type_name().map(|s| s.as_str()), Some("u64"));
+		assert!(matches!(registry.resolve(*fields[1].ty()).unwrap().type_def(), TypeDef::Compact(_)));
+	}
+
+	#[test]
+	fn derive_describes_enums_with_codec_indices() {
+		let (id, registry) = portable::<Enum>();
+		let variants = match registry.resolve(id).unwrap().type_def() {
+			TypeDef::Variant(variants) => variants.clone(),
+			_ => panic!("expected a variant"),
+		};
+
+		let described = variants.iter()
+			.map(|v| (v.name().as_str(), v.index(), v.fields().len()))
+			.collect::<Vec<_>>();
+		assert_eq!(described, vec![("A", 0, 0), ("B", 1, 2), ("C", 7, 1)]);
+		assert_eq!(Enum::B(0, vec![]).encode()[0], 1);
+		assert_eq!(Enum::C { recursive: None }.encode()[0], 7);
+
+		// The recursive reference resolves to the enum itself.
+		let option = registry.resolve(*variants[2].fields()[0].ty()).unwrap();
+		let boxed = match option.type_def() {
+			TypeDef::Variant(variants) => *variants[1].fields()[0].ty(),
+			_ => panic!("expected an option"),
+		};
+		assert_eq!(registry.resolve(boxed).unwrap().path(), registry.resolve(id).unwrap().path());
+	}
+
+	#[test]
+	fn registry_deduplicates_types() {
+		let mut registry = Registry::new();
+		let first = registry.register_type(&meta_type::<Vec<u8>>());
+		let second = registry.register_type(&meta_type::<Vec<u8>>());
+		let u8_id = registry.register_type(&meta_type::<u8>());
+		assert_eq!(first, second);
+
+		let registry = PortableRegistry::from(registry);
+		assert_eq!(registry.types().len(), 2);
+		assert_eq!(registry.resolve(first).unwrap().type_def(), &TypeDef::Sequence(u8_id));
+	}
+
+	#[test]
+	fn portable_registry_encoding_round_trips() {
+		use codec::Decode;
+
+		let (_, registry) = portable::<Enum>();
+		let encoded = registry.encode();
+		assert_eq!(PortableRegistry::decode(&mut &encoded[..]).unwrap(), registry);
+	}
+}
@@ -0,0 +1,79 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{TypeInfo, Type};
+use sp_std::{any::TypeId, cmp::Ordering, fmt, hash::{Hash, Hasher}};
+
+/// A reference to a type implementing [`TypeInfo`].
+///
+/// Two meta types are equal if they reference the same type.
+#[derive(Clone, Copy)]
+pub struct MetaType {
+	fn_type_info: fn() -> Type,
+	type_id: TypeId,
+}
+
+impl MetaType {
+	/// Creates the meta type of `T`.
+	pub fn new<T: TypeInfo + ?Sized + 'static>() -> Self {
+		Self {
+			fn_type_info: <T as TypeInfo>::type_info,
+			type_id: TypeId::of::<T>(),
+		}
+	}
+
+	/// Returns the type information of the referenced type.
+	pub fn type_info(&self) -> Type {
+		(self.fn_type_info)()
+	}
+
+	/// Returns the type id of the referenced type.
+	pub fn type_id(&self) -> TypeId {
+		self.type_id
+	}
+}
+
+impl PartialEq for MetaType {
+	fn eq(&self, other: &Self) -> bool {
+		self.type_id == other.type_id
+	}
+}
+
+impl Eq for MetaType {}
+
+impl PartialOrd for MetaType {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for MetaType {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.type_id.cmp(&other.type_id)
+	}
+}
+
+impl Hash for MetaType {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.type_id.hash(state)
+	}
+}
+
+impl fmt::Debug for MetaType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.type_id.fmt(f)
+	}
+}
@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The registry collecting type information into a [`PortableRegistry`].
+
+use crate::{MetaType, Type, PortableForm, StringBuf};
+use codec::Encode;
+#[cfg(feature = "std")]
+use codec::Decode;
+#[cfg(feature = "std")]
+use serde::Serialize;
+use sp_std::{any::TypeId, collections::btree_map::BTreeMap, vec::Vec};
+
+/// Convert type information from the [`MetaForm`](crate::MetaForm) into the [`PortableForm`].
+pub trait IntoPortable {
+	/// The portable representation of `Self`.
+	type Output;
+
+	/// Convert `self` into its portable representation, registering all referenced types.
+	fn into_portable(self, registry: &mut Registry) -> Self::Output;
+}
+
+impl IntoPortable for &'static str {
+	type Output = StringBuf;
+
+	fn into_portable(self, _registry: &mut Registry) -> Self::Output {
+		self.into()
+	}
+}
+
+/// Collects types and assigns each of them a unique identifier.
+///
+/// A type is only registered once, further registrations return the same identifier. The
+/// identifier is assigned before the type information is converted, so recursive types are
+/// supported.
+#[derive(Default)]
+pub struct Registry {
+	type_table: BTreeMap<TypeId, u32>,
+	types: Vec<Option<Type<PortableForm>>>,
+}
+
+impl Registry {
+	/// Create a new empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register the given type and all types it references, returns its identifier.
+	pub fn register_type(&mut self, ty: &MetaType) -> u32 {
+		if let Some(id) = self.type_table.get(&ty.type_id()) {
+			return *id
+		}
+
+		let id = self.types.len() as u32;
+		self.type_table.insert(ty.type_id(), id);
+		self.types.push(None);
+
+		let portable = ty.type_info().into_portable(self);
+		self.types[id as usize] = Some(portable);
+		id
+	}
+
+	/// Register all the given types, returns their identifiers.
+	pub fn register_types<I>(&mut self, types: I) -> Vec<u32>
+	where
+		I: IntoIterator<Item = MetaType>,
+	{
+		types.into_iter().map(|ty| self.register_type(&ty)).collect()
+	}
+
+	/// Convert all the given items into their portable representation.
+	pub fn map_into_portable<I, T>(&mut self, items: I) -> Vec<T::Output>
+	where
+		I: IntoIterator<Item = T>,
+		T: IntoPortable,
+	{
+		items.into_iter().map(|item| item.into_portable(self)).collect()
+	}
+}
+
+/// The registry of all types in their portable form, the identifier of a type is its index.
+#[derive(PartialEq, Eq, Clone, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+pub struct PortableRegistry {
+	types: Vec<Type<PortableForm>>,
+}
+
+impl PortableRegistry {
+	/// Returns the type with the given identifier.
+	pub fn resolve(&self, id: u32) -> Option<&Type<PortableForm>> {
+		self.types.get(id as usize)
+	}
+
+	/// All the types of the registry, ordered by their identifier.
+	pub fn types(&self) -> &[Type<PortableForm>] {
+		&self.types
+	}
+}
+
+impl From<Registry> for PortableRegistry {
+	fn from(registry: Registry) -> Self {
+		Self {
+			types: registry.types.into_iter()
+				.map(|ty| ty.expect("Every registered type is converted before registration returns; qed"))
+				.collect(),
+		}
+	}
+}
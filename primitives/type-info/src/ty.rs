@@ -0,0 +1,337 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The type information data structures.
+
+use crate::{Form, MetaForm, MetaType, PortableForm, IntoPortable, Registry};
+use codec::Encode;
+#[cfg(feature = "std")]
+use codec::Decode;
+#[cfg(feature = "std")]
+use serde::Serialize;
+use sp_std::vec::Vec;
+
+/// The description of a type.
+#[derive(PartialEq, Eq, Clone, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct Type<F: Form = MetaForm> {
+	/// The unique path to the type, empty for types without a name (e.g. tuples).
+	path: Path<F>,
+	/// The actual definition of the type.
+	type_def: TypeDef<F>,
+	/// The documentation of the type.
+	docs: Vec<F::String>,
+}
+
+impl Type {
+	/// Create a new type with the given path and definition.
+	pub fn new(path: Path, type_def: TypeDef) -> Self {
+		Self { path, type_def, docs: Vec::new() }
+	}
+
+	/// Create a new type without a path.
+	pub fn unnamed(type_def: TypeDef) -> Self {
+		Self::new(Path::default(), type_def)
+	}
+
+	/// Set the documentation of the type.
+	pub fn docs(mut self, docs: &[&'static str]) -> Self {
+		self.docs = docs.to_vec();
+		self
+	}
+}
+
+impl<F: Form> Type<F> {
+	/// The path of the type.
+	pub fn path(&self) -> &Path<F> {
+		&self.path
+	}
+
+	/// The definition of the type.
+	pub fn type_def(&self) -> &TypeDef<F> {
+		&self.type_def
+	}
+
+	/// The documentation of the type.
+	pub fn docs(&self) -> &[F::String] {
+		&self.docs
+	}
+}
+
+impl IntoPortable for Type {
+	type Output = Type<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		Type {
+			path: self.path.into_portable(registry),
+			type_def: self.type_def.into_portable(registry),
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
+
+/// The path of a type, consisting of the module path and the name of the type.
+#[derive(PartialEq, Eq, Clone, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::String: Serialize")))]
+pub struct Path<F: Form = MetaForm> {
+	segments: Vec<F::String>,
+}
+
+impl<F: Form> Default for Path<F> {
+	fn default() -> Self {
+		Self { segments: Vec::new() }
+	}
+}
+
+impl Path {
+	/// Create the path of the type `ident`, declared in the module `module_path`.
+	///
+	/// `module_path` is usually obtained through `module_path!()`.
+	pub fn new(ident: &'static str, module_path: &'static str) -> Self {
+		let segments = module_path.split("::")
+			.filter(|s| !s.is_empty())
+			.chain(sp_std::iter::once(ident))
+			.collect();
+		Self { segments }
+	}
+}
+
+impl<F: Form> Path<F> {
+	/// The segments of the path, the last one is the name of the type.
+	pub fn segments(&self) -> &[F::String] {
+		&self.segments
+	}
+}
+
+impl IntoPortable for Path {
+	type Output = Path<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		Path { segments: registry.map_into_portable(self.segments) }
+	}
+}
+
+/// The definition of a type, following its SCALE encoding.
+#[derive(PartialEq, Eq, Clone, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub enum TypeDef<F: Form = MetaForm> {
+	/// A struct, its fields are encoded one after another.
+	Composite(Vec<Field<F>>),
+	/// An enum, encoded as the index of the variant followed by its fields.
+	Variant(Vec<Variant<F>>),
+	/// A sequence of elements prefixed by its compact encoded length.
+	Sequence(F::Type),
+	/// A fixed size array of elements.
+	Array {
+		/// The number of elements.
+		len: u32,
+		/// The type of the elements.
+		type_param: F::Type,
+	},
+	/// A tuple, its elements are encoded one after another.
+	Tuple(Vec<F::Type>),
+	/// A primitive type.
+	Primitive(TypeDefPrimitive),
+	/// A compact encoded value of the given type.
+	Compact(F::Type),
+}
+
+impl IntoPortable for TypeDef {
+	type Output = TypeDef<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		match self {
+			TypeDef::Composite(fields) => TypeDef::Composite(registry.map_into_portable(fields)),
+			TypeDef::Variant(variants) => TypeDef::Variant(registry.map_into_portable(variants)),
+			TypeDef::Sequence(ty) => TypeDef::Sequence(registry.register_type(&ty)),
+			TypeDef::Array { len, type_param } => TypeDef::Array {
+				len,
+				type_param: registry.register_type(&type_param),
+			},
+			TypeDef::Tuple(types) => TypeDef::Tuple(registry.register_types(types)),
+			TypeDef::Primitive(primitive) => TypeDef::Primitive(primitive),
+			TypeDef::Compact(ty) => TypeDef::Compact(registry.register_type(&ty)),
+		}
+	}
+}
+
+/// A primitive type.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+pub enum TypeDefPrimitive {
+	/// `bool`, encoded as a single byte.
+	Bool,
+	/// `char`, encoded as `u32`.
+	Char,
+	/// `str`, encoded as a sequence of UTF-8 bytes.
+	Str,
+	/// `u8`
+	U8,
+	/// `u16`
+	U16,
+	/// `u32`
+	U32,
+	/// `u64`
+	U64,
+	/// `u128`
+	U128,
+	/// 256 bits unsigned integer.
+	U256,
+	/// `i8`
+	I8,
+	/// `i16`
+	I16,
+	/// `i32`
+	I32,
+	/// `i64`
+	I64,
+	/// `i128`
+	I128,
+}
+
+/// A field of a composite or of a variant.
+#[derive(PartialEq, Eq, Clone, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct Field<F: Form = MetaForm> {
+	/// The name of the field, `None` for unnamed fields.
+	name: Option<F::String>,
+	/// The type of the field.
+	ty: F::Type,
+	/// The name of the type as written in the source, e.g. `T::AccountId`.
+	type_name: Option<F::String>,
+	/// The documentation of the field.
+	docs: Vec<F::String>,
+}
+
+impl Field {
+	/// Create a named field.
+	pub fn named(name: &'static str, ty: MetaType, type_name: &'static str) -> Self {
+		Self { name: Some(name), ty, type_name: Some(type_name), docs: Vec::new() }
+	}
+
+	/// Create an unnamed field.
+	pub fn unnamed(ty: MetaType, type_name: &'static str) -> Self {
+		Self { name: None, ty, type_name: Some(type_name), docs: Vec::new() }
+	}
+
+	/// Set the documentation of the field.
+	pub fn docs(mut self, docs: &[&'static str]) -> Self {
+		self.docs = docs.to_vec();
+		self
+	}
+}
+
+impl<F: Form> Field<F> {
+	/// The name of the field, `None` for unnamed fields.
+	pub fn name(&self) -> Option<&F::String> {
+		self.name.as_ref()
+	}
+
+	/// The type of the field.
+	pub fn ty(&self) -> &F::Type {
+		&self.ty
+	}
+
+	/// The name of the type as written in the source.
+	pub fn type_name(&self) -> Option<&F::String> {
+		self.type_name.as_ref()
+	}
+
+	/// The documentation of the field.
+	pub fn docs(&self) -> &[F::String] {
+		&self.docs
+	}
+}
+
+impl IntoPortable for Field {
+	type Output = Field<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		Field {
+			name: self.name.map(|name| name.into_portable(registry)),
+			ty: registry.register_type(&self.ty),
+			type_name: self.type_name.map(|name| name.into_portable(registry)),
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
+
+/// A variant of an enum.
+#[derive(PartialEq, Eq, Clone, Debug, Encode)]
+#[cfg_attr(feature = "std", derive(Decode, Serialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "F::Type: Serialize, F::String: Serialize")))]
+pub struct Variant<F: Form = MetaForm> {
+	/// The name of the variant.
+	name: F::String,
+	/// The fields of the variant.
+	fields: Vec<Field<F>>,
+	/// The index of the variant in the encoding.
+	index: u8,
+	/// The documentation of the variant.
+	docs: Vec<F::String>,
+}
+
+impl Variant {
+	/// Create a variant encoded with the given index.
+	pub fn new(name: &'static str, fields: Vec<Field>, index: u8) -> Self {
+		Self { name, fields, index, docs: Vec::new() }
+	}
+
+	/// Set the documentation of the variant.
+	pub fn docs(mut self, docs: &[&'static str]) -> Self {
+		self.docs = docs.to_vec();
+		self
+	}
+}
+
+impl<F: Form> Variant<F> {
+	/// The name of the variant.
+	pub fn name(&self) -> &F::String {
+		&self.name
+	}
+
+	/// The fields of the variant.
+	pub fn fields(&self) -> &[Field<F>] {
+		&self.fields
+	}
+
+	/// The index of the variant in the encoding.
+	pub fn index(&self) -> u8 {
+		self.index
+	}
+
+	/// The documentation of the variant.
+	pub fn docs(&self) -> &[F::String] {
+		&self.docs
+	}
+}
+
+impl IntoPortable for Variant {
+	type Output = Variant<PortableForm>;
+
+	fn into_portable(self, registry: &mut Registry) -> Self::Output {
+		Variant {
+			name: self.name.into_portable(registry),
+			fields: registry.map_into_portable(self.fields),
+			index: self.index,
+			docs: registry.map_into_portable(self.docs),
+		}
+	}
+}
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::BenchmarkCmd;
+use crate::{BenchmarkCmd, BenchmarkSubCmd};
 use codec::{Decode, Encode};
 use frame_benchmarking::{Analysis, BenchmarkBatch, BenchmarkSelector};
 use sc_cli::{SharedParams, CliConfiguration, ExecutionStrategy, Result};
@@ -41,6 +41,16 @@ impl BenchmarkCmd {
 		<BB as BlockT>::Hash: std::str::FromStr,
 		ExecDispatch: NativeExecutionDispatch + 'static,
 	{
+		if let Some(BenchmarkSubCmd::Compare(cmd)) = &self.subcommand {
+			return cmd.run()
+		}
+
+		// Both are required by the argument parser unless all benchmarks are listed.
+		let pallet = self.pallet.clone().unwrap_or_else(|| "*".into());
+		let extrinsic = self.extrinsic.clone().unwrap_or_else(|| "*".into());
+		// Benchmarks are not executed if they are not repeated.
+		let repeat = if self.list { 0 } else { self.repeat };
+
 		let spec = config.chain_spec;
 		let wasm_method = self.wasm_method.into();
 		let strategy = self.execution.unwrap_or(ExecutionStrategy::Native);
@@ -69,12 +79,12 @@ impl BenchmarkCmd {
 			&executor,
 			"Benchmark_dispatch_benchmark",
 			&(
-				&self.pallet,
-				&self.extrinsic,
+				&pallet,
+				&extrinsic,
 				self.lowest_range_values.clone(),
 				self.highest_range_values.clone(),
 				self.steps.clone(),
-				self.repeat,
+				repeat,
 				!self.no_verify,
 				self.extra,
 			).encode(),
@@ -90,16 +100,34 @@ impl BenchmarkCmd {
 
 		match results {
 			Ok(batches) => {
+				if self.list {
+					for batch in batches.into_iter() {
+						println!(
+							"{}, {}",
+							String::from_utf8(batch.pallet).expect("Encoded from String; qed"),
+							String::from_utf8(batch.benchmark).expect("Encoded from String; qed"),
+						);
+					}
+					return Ok(())
+				}
+
 				// If we are going to output results to a file...
-				if self.output {
+				if self.output || self.output_dir.is_some() {
+					let output_dir = self.output_dir.clone().unwrap_or_default();
+					std::fs::create_dir_all(&output_dir)?;
 					if self.weight_trait {
-						let mut file = crate::writer::open_file("traits.rs")?;
+						let mut file = crate::writer::open_file(&output_dir.join("traits.rs"))?;
 						crate::writer::write_trait(&mut file, batches.clone())?;
 					} else {
-						crate::writer::write_results(&batches)?;
+						crate::writer::write_results(&batches, &output_dir, self)?;
 					}
 				}
 
+				if self.json {
+					println!("{}", crate::writer::batches_to_json(&batches)?);
+					return Ok(())
+				}
+
 				for batch in batches.into_iter() {
 					// Print benchmark metadata
 					println!(
@@ -119,19 +147,21 @@ impl BenchmarkCmd {
 						// Print the table header
 						batch.results[0].components.iter().for_each(|param| print!("{:?},", param.0));
 
-						print!("extrinsic_time,storage_root_time,reads,repeat_reads,writes,repeat_writes\n");
+						print!("extrinsic_time,storage_root_time,reads,repeat_reads,writes,repeat_writes,proof_size,proof_nodes\n");
 						// Print the values
 						batch.results.iter().for_each(|result| {
 							let parameters = &result.components;
 							parameters.iter().for_each(|param| print!("{:?},", param.1));
 							// Print extrinsic time and storage root time
-							print!("{:?},{:?},{:?},{:?},{:?},{:?}\n",
+							print!("{:?},{:?},{:?},{:?},{:?},{:?},{:?},{:?}\n",
 								result.extrinsic_time,
 								result.storage_root_time,
 								result.reads,
 								result.repeat_reads,
 								result.writes,
 								result.repeat_writes,
+								result.proof_size,
+								result.proof_nodes,
 							);
 						});
 
@@ -150,6 +180,12 @@ impl BenchmarkCmd {
 						if let Some(analysis) = Analysis::median_slopes(&batch.results, BenchmarkSelector::Writes) {
 							println!("Writes = {:?}", analysis);
 						}
+						if let Some(analysis) = Analysis::median_slopes(&batch.results, BenchmarkSelector::ProofSize) {
+							println!("Proof Size = {:?}", analysis);
+						}
+						if let Some(analysis) = Analysis::median_slopes(&batch.results, BenchmarkSelector::ProofNodes) {
+							println!("Proof Nodes = {:?}", analysis);
+						}
 					}
 					if !self.no_min_squares {
 						println!("Min Squares Analysis\n========");
@@ -162,6 +198,12 @@ impl BenchmarkCmd {
 						if let Some(analysis) = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::Writes) {
 							println!("Writes = {:?}", analysis);
 						}
+						if let Some(analysis) = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::ProofSize) {
+							println!("Proof Size = {:?}", analysis);
+						}
+						if let Some(analysis) = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::ProofNodes) {
+							println!("Proof Nodes = {:?}", analysis);
+						}
 					}
 				}
 			},
@@ -0,0 +1,356 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Compares the results of two benchmark runs to catch weight regressions.
+
+use crate::BenchmarkCompareCmd;
+use crate::writer::{JsonAnalyses, JsonAnalysis, JsonBatch};
+use sc_cli::Result;
+
+// The kind of weight a value measures, which decides how a regression is detected.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+	Time,
+	Db,
+	Proof,
+}
+
+// The benchmarks of two runs, matched by pallet and name.
+struct Matched<'a> {
+	// The benchmarks found in both runs, as `(old, new)`.
+	both: Vec<(&'a JsonBatch, &'a JsonBatch)>,
+	// The benchmarks only found in the new run.
+	added: Vec<&'a JsonBatch>,
+	// The benchmarks only found in the old run.
+	removed: Vec<&'a JsonBatch>,
+}
+
+// A single value of a benchmark, e.g. the base weight or a component slope, in both runs.
+struct Delta {
+	label: String,
+	old: u128,
+	new: u128,
+	regression: bool,
+}
+
+impl BenchmarkCompareCmd {
+	/// Compares the two result sets and fails if any weight regressed beyond the thresholds.
+	pub fn run(&self) -> Result<()> {
+		let old = read_batches(&self.old)?;
+		let new = read_batches(&self.new)?;
+
+		let matched = match_batches(&old, &new);
+
+		let mut regressions = 0;
+		for (old_batch, new_batch) in matched.both {
+			let name = format!("{}::{}", new_batch.pallet, new_batch.benchmark);
+			let (old_analyses, new_analyses) = if self.median_slopes {
+				(&old_batch.median_slopes, &new_batch.median_slopes)
+			} else {
+				(&old_batch.min_squares, &new_batch.min_squares)
+			};
+
+			let deltas = self.compare(old_analyses, new_analyses);
+			println!("{}", name);
+			for delta in &deltas {
+				println!(
+					"\t{}: {} -> {} ({}){}",
+					delta.label,
+					delta.old,
+					delta.new,
+					percentage(delta.old, delta.new),
+					if delta.regression { " REGRESSION" } else { "" },
+				);
+			}
+			println!();
+			regressions += deltas.iter().filter(|d| d.regression).count();
+		}
+
+		for batch in matched.added {
+			println!("{}::{}: new benchmark\n", batch.pallet, batch.benchmark);
+		}
+		for batch in matched.removed {
+			println!("{}::{}: removed benchmark\n", batch.pallet, batch.benchmark);
+		}
+
+		if regressions > 0 {
+			Err(format!("Found {} weight regressions.", regressions).into())
+		} else {
+			println!("No weight regressions found.");
+			Ok(())
+		}
+	}
+
+	// Compare the extrinsic time, reads, writes and storage proof of a benchmark.
+	fn compare(&self, old: &JsonAnalyses, new: &JsonAnalyses) -> Vec<Delta> {
+		let mut deltas = Vec::new();
+		self.compare_analysis(
+			"extrinsic time",
+			Kind::Time,
+			&old.extrinsic_time,
+			&new.extrinsic_time,
+			&mut deltas,
+		);
+		self.compare_analysis("reads", Kind::Db, &old.reads, &new.reads, &mut deltas);
+		self.compare_analysis("writes", Kind::Db, &old.writes, &new.writes, &mut deltas);
+		self.compare_analysis(
+			"proof size",
+			Kind::Proof,
+			&old.proof_size,
+			&new.proof_size,
+			&mut deltas,
+		);
+		self.compare_analysis(
+			"proof nodes",
+			Kind::Proof,
+			&old.proof_nodes,
+			&new.proof_nodes,
+			&mut deltas,
+		);
+		deltas
+	}
+
+	// Compare the base value and the slope of every component of an analysis.
+	fn compare_analysis(
+		&self,
+		what: &str,
+		kind: Kind,
+		old: &Option<JsonAnalysis>,
+		new: &Option<JsonAnalysis>,
+		deltas: &mut Vec<Delta>,
+	) {
+		let (old, new) = match (old, new) {
+			(Some(old), Some(new)) => (old, new),
+			_ => return,
+		};
+
+		deltas.push(self.delta(format!("{} base", what), kind, old.base, new.base));
+
+		let mut names = old.slopes.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+		new.slopes.iter().for_each(|s| if !names.contains(&s.name) { names.push(s.name.clone()) });
+
+		let slope = |analysis: &JsonAnalysis, name: &str| analysis.slopes.iter()
+			.find(|s| s.name == name)
+			.map(|s| s.slope)
+			.unwrap_or_default();
+
+		for name in names {
+			let (old_slope, new_slope) = (slope(old, &name), slope(new, &name));
+			// Components without influence in both runs are not worth reporting.
+			if old_slope == 0 && new_slope == 0 { continue }
+			deltas.push(self.delta(format!("{} per {}", what, name), kind, old_slope, new_slope));
+		}
+	}
+
+	fn delta(&self, label: String, kind: Kind, old: u128, new: u128) -> Delta {
+		let increase = new.saturating_sub(old);
+		let regression = match kind {
+			Kind::Time => increase.saturating_mul(100) > old.saturating_mul(self.time_threshold.into()),
+			Kind::Db => increase > self.db_threshold.into(),
+			Kind::Proof =>
+				increase.saturating_mul(100) > old.saturating_mul(self.proof_threshold.into()),
+		};
+		Delta { label, old, new, regression }
+	}
+}
+
+// Match the benchmarks of the old and the new run by pallet and name.
+fn match_batches<'a>(old: &'a [JsonBatch], new: &'a [JsonBatch]) -> Matched<'a> {
+	let same = |a: &JsonBatch, b: &JsonBatch| a.pallet == b.pallet && a.benchmark == b.benchmark;
+	let mut both = Vec::new();
+	let mut added = Vec::new();
+	for new_batch in new {
+		match old.iter().find(|old_batch| same(old_batch, new_batch)) {
+			Some(old_batch) => both.push((old_batch, new_batch)),
+			None => added.push(new_batch),
+		}
+	}
+	let removed = old.iter()
+		.filter(|old_batch| !new.iter().any(|new_batch| same(old_batch, new_batch)))
+		.collect();
+	Matched { both, added, removed }
+}
+
+fn read_batches(path: &std::path::Path) -> Result<Vec<JsonBatch>> {
+	let file = std::fs::File::open(path)?;
+	serde_json::from_reader(std::io::BufReader::new(file))
+		.map_err(|e| format!("Failed to decode benchmark results {:?}: {}", path, e).into())
+}
+
+fn percentage(old: u128, new: u128) -> String {
+	if old == 0 {
+		return if new == 0 { "+0.00%".into() } else { "new".into() }
+	}
+	format!("{:+.2}%", (new as f64 - old as f64) * 100.0 / old as f64)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::writer::ComponentSlope;
+
+	fn cmd(time_threshold: u32, db_threshold: u32) -> BenchmarkCompareCmd {
+		BenchmarkCompareCmd {
+			old: Default::default(),
+			new: Default::default(),
+			time_threshold,
+			db_threshold,
+			proof_threshold: 10,
+			median_slopes: false,
+		}
+	}
+
+	fn analysis(base: u128, slopes: &[(&str, u128)]) -> Option<JsonAnalysis> {
+		Some(JsonAnalysis {
+			base,
+			slopes: slopes.iter()
+				.map(|(name, slope)| ComponentSlope { name: name.to_string(), slope: *slope, error: 0 })
+				.collect(),
+		})
+	}
+
+	fn analyses(time: u128, reads: u128, writes: u128) -> JsonAnalyses {
+		JsonAnalyses {
+			extrinsic_time: analysis(time, &[]),
+			reads: analysis(reads, &[]),
+			writes: analysis(writes, &[]),
+			proof_size: None,
+			proof_nodes: None,
+		}
+	}
+
+	fn batch(pallet: &str, benchmark: &str) -> JsonBatch {
+		JsonBatch {
+			pallet: pallet.into(),
+			benchmark: benchmark.into(),
+			results: Vec::new(),
+			median_slopes: analyses(0, 0, 0),
+			min_squares: analyses(0, 0, 0),
+		}
+	}
+
+	fn regressions(deltas: &[Delta]) -> Vec<&str> {
+		deltas.iter().filter(|d| d.regression).map(|d| d.label.as_str()).collect()
+	}
+
+	#[test]
+	fn any_increase_of_a_zero_old_base_is_a_regression() {
+		let cmd = cmd(10, 0);
+		let deltas = cmd.compare(&analyses(0, 0, 0), &analyses(1, 0, 0));
+		assert_eq!(regressions(&deltas), vec!["extrinsic time base"]);
+		assert_eq!(percentage(0, 1), "new");
+
+		let deltas = cmd.compare(&analyses(0, 0, 0), &analyses(0, 0, 0));
+		assert!(regressions(&deltas).is_empty());
+		assert_eq!(percentage(0, 0), "+0.00%");
+	}
+
+	#[test]
+	fn time_threshold_is_relative_and_db_threshold_absolute() {
+		let cmd = cmd(10, 1);
+		// 10% more time and one more read are within the thresholds.
+		let deltas = cmd.compare(&analyses(1000, 100, 3), &analyses(1100, 101, 3));
+		assert!(regressions(&deltas).is_empty());
+
+		// just over 10% more time, and two more reads although that is only 2% more.
+		let deltas = cmd.compare(&analyses(1000, 100, 3), &analyses(1101, 102, 4));
+		assert_eq!(regressions(&deltas), vec!["extrinsic time base", "reads base"]);
+
+		// decreases are never regressions.
+		let deltas = cmd.compare(&analyses(1000, 100, 3), &analyses(1, 0, 0));
+		assert!(regressions(&deltas).is_empty());
+	}
+
+	#[test]
+	fn proof_threshold_is_relative() {
+		let cmd = cmd(10, 0);
+		let old = JsonAnalyses {
+			proof_size: analysis(1000, &[]),
+			proof_nodes: analysis(10, &[]),
+			..analyses(0, 0, 0)
+		};
+		// 10% more is within the threshold.
+		let new = JsonAnalyses {
+			proof_size: analysis(1100, &[]),
+			proof_nodes: analysis(11, &[]),
+			..analyses(0, 0, 0)
+		};
+		assert!(regressions(&cmd.compare(&old, &new)).is_empty());
+
+		let new = JsonAnalyses {
+			proof_size: analysis(1101, &[]),
+			proof_nodes: analysis(12, &[]),
+			..analyses(0, 0, 0)
+		};
+		assert_eq!(
+			regressions(&cmd.compare(&old, &new)),
+			vec!["proof size base", "proof nodes base"],
+		);
+	}
+
+	#[test]
+	fn slopes_are_matched_by_component() {
+		let cmd = cmd(10, 0);
+		let old = JsonAnalyses {
+			extrinsic_time: analysis(1000, &[("a", 100), ("b", 0), ("removed", 10)]),
+			..analyses(0, 0, 0)
+		};
+		let new = JsonAnalyses {
+			extrinsic_time: analysis(1000, &[("a", 105), ("b", 0), ("added", 10)]),
+			..analyses(0, 0, 0)
+		};
+		let deltas = cmd.compare(&old, &new);
+
+		let labels = deltas.iter().map(|d| d.label.as_str()).collect::<Vec<_>>();
+		assert_eq!(labels, vec![
+			"extrinsic time base",
+			"extrinsic time per a",
+			"extrinsic time per removed",
+			"extrinsic time per added",
+			"reads base",
+			"writes base",
+		]);
+		// a component without influence in the old run regresses by any increase.
+		assert_eq!(regressions(&deltas), vec!["extrinsic time per added"]);
+	}
+
+	#[test]
+	fn benchmarks_are_matched_by_pallet_and_name() {
+		let old = vec![
+			batch("balances", "transfer"),
+			batch("balances", "removed"),
+			batch("staking", "bond"),
+		];
+		let new = vec![
+			batch("staking", "bond"),
+			batch("balances", "transfer"),
+			batch("staking", "transfer"),
+		];
+		let matched = match_batches(&old, &new);
+
+		let name = |b: &JsonBatch| format!("{}::{}", b.pallet, b.benchmark);
+		assert_eq!(
+			matched.both.iter().map(|&(o, n)| (name(o), name(n))).collect::<Vec<_>>(),
+			vec![
+				("staking::bond".to_string(), "staking::bond".to_string()),
+				("balances::transfer".to_string(), "balances::transfer".to_string()),
+			],
+		);
+		assert_eq!(matched.added.into_iter().map(name).collect::<Vec<_>>(), vec!["staking::transfer"]);
+		assert_eq!(matched.removed.into_iter().map(name).collect::<Vec<_>>(), vec!["balances::removed"]);
+	}
+}
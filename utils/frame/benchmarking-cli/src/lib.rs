@@ -16,21 +16,35 @@
 // limitations under the License.
 
 mod command;
+mod compare;
 mod writer;
 
-use sc_cli::{ExecutionStrategy, WasmExecutionMethod};
+use sc_cli::{Database, ExecutionStrategy, WasmExecutionMethod};
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 /// The `benchmark` command used to benchmark FRAME Pallets.
 #[derive(Debug, structopt::StructOpt)]
+#[structopt(setting = structopt::clap::AppSettings::SubcommandsNegateReqs)]
 pub struct BenchmarkCmd {
-	/// Select a FRAME Pallet to benchmark, or `*` for all (in which case `extrinsic` must be `*`).
-	#[structopt(short, long)]
-	pub pallet: String,
+	#[allow(missing_docs)]
+	#[structopt(subcommand)]
+	pub subcommand: Option<BenchmarkSubCmd>,
+
+	/// Select the FRAME Pallets to benchmark. `*` matches any sequence of characters, e.g.
+	/// `pallet_*` or `*` for all.
+	#[structopt(short, long, required_unless = "list")]
+	pub pallet: Option<String>,
 
-	/// Select an extrinsic inside the pallet to benchmark, or `*` for all.
-	#[structopt(short, long)]
-	pub extrinsic: String,
+	/// Select the extrinsics inside the pallets to benchmark. `*` matches any sequence of
+	/// characters, e.g. `*` for all.
+	#[structopt(short, long, required_unless = "list")]
+	pub extrinsic: Option<String>,
+
+	/// List the benchmarks selected by `pallet` and `extrinsic`, all of them by default, instead
+	/// of running them.
+	#[structopt(long)]
+	pub list: bool,
 
 	/// Select how many samples we should take across the variable components.
 	#[structopt(short, long, use_delimiter = true)]
@@ -60,14 +74,45 @@ pub struct BenchmarkCmd {
 	#[structopt(long)]
 	pub no_min_squares: bool,
 
-	/// Output the benchmarks to a Rust file.
+	/// Output the benchmarks to a Rust file per pallet.
 	#[structopt(long)]
 	pub output: bool,
 
+	/// The directory the files written with `--output` are put in, instead of the current
+	/// directory. Implies `--output`.
+	#[structopt(long, parse(from_os_str))]
+	pub output_dir: Option<PathBuf>,
+
 	/// Output the trait definition to a Rust file.
 	#[structopt(long)]
 	pub weight_trait: bool,
 
+	/// A Handlebars template used to render the weight files written with `--output`.
+	///
+	/// The template is given the data of each benchmark of the pallet, see the default
+	/// `template.hbs` of this crate for an example.
+	#[structopt(long, parse(from_os_str))]
+	pub template: Option<PathBuf>,
+
+	/// A file whose content is added as `header` to the template data, e.g. a license.
+	#[structopt(long, parse(from_os_str))]
+	pub header: Option<PathBuf>,
+
+	/// The database whose read and write weights are used by the weight files written with
+	/// `--output`. Added as `db_weight` to the template data.
+	#[structopt(
+		long,
+		value_name = "DB",
+		possible_values = &Database::variants(),
+		case_insensitive = true,
+		default_value = "RocksDb",
+	)]
+	pub db_weight: Database,
+
+	/// Print the raw results and their analysis as JSON instead of human-readable text.
+	#[structopt(long)]
+	pub json: bool,
+
 	/// Set the heap pages while running benchmarks.
 	#[structopt(long)]
 	pub heap_pages: Option<u64>,
@@ -107,3 +152,42 @@ pub struct BenchmarkCmd {
 	#[structopt(long = "db-cache", value_name = "MiB", default_value = "128")]
 	pub database_cache_size: u32,
 }
+
+/// The subcommands of the `benchmark` command.
+#[derive(Debug, structopt::StructOpt)]
+pub enum BenchmarkSubCmd {
+	/// Compare the results of two benchmark runs, as output with `--json`.
+	Compare(BenchmarkCompareCmd),
+}
+
+/// The `benchmark compare` command used to catch weight regressions between two benchmark runs.
+///
+/// The extrinsic time, reads, writes and storage proof of every benchmark found in both runs are
+/// compared, both the base values and the slope of each component.
+#[derive(Debug, structopt::StructOpt)]
+pub struct BenchmarkCompareCmd {
+	/// The results of the old benchmark run.
+	#[structopt(parse(from_os_str))]
+	pub old: PathBuf,
+
+	/// The results of the new benchmark run.
+	#[structopt(parse(from_os_str))]
+	pub new: PathBuf,
+
+	/// Fail if an extrinsic time increased by more than this percentage.
+	#[structopt(long, default_value = "10")]
+	pub time_threshold: u32,
+
+	/// Fail if a number of database reads or writes increased by more than this.
+	#[structopt(long, default_value = "0")]
+	pub db_threshold: u32,
+
+	/// Fail if a storage proof size or number of proof nodes increased by more than this
+	/// percentage.
+	#[structopt(long, default_value = "10")]
+	pub proof_threshold: u32,
+
+	/// Compare the median slopes analysis instead of the min-squares analysis.
+	#[structopt(long)]
+	pub median_slopes: bool,
+}
@@ -19,12 +19,125 @@
 
 use std::fs::{File, OpenOptions};
 use std::io::prelude::*;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
 use frame_benchmarking::{BenchmarkBatch, BenchmarkSelector, Analysis};
+use sc_cli::Database;
 use sp_runtime::traits::Zero;
 
+use crate::BenchmarkCmd;
+
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+const TEMPLATE: &str = include_str!("./template.hbs");
+
+// This is the final structure we will pass to the Handlebars template.
+#[derive(Serialize, Default, Debug, Clone)]
+struct TemplateData {
+	args: Vec<String>,
+	date: String,
+	version: String,
+	pallet: String,
+	header: String,
+	db_weight: String,
+	cmd: CmdData,
+	benchmarks: Vec<BenchmarkData>,
+}
+
+// This was the final data we have about each benchmark.
+#[derive(Serialize, Default, Debug, Clone)]
+struct BenchmarkData {
+	name: String,
+	components: Vec<String>,
+	component_ranges: Vec<ComponentRange>,
+	base_weight: u128,
+	base_reads: u128,
+	base_writes: u128,
+	component_weight: Vec<ComponentSlope>,
+	component_reads: Vec<ComponentSlope>,
+	component_writes: Vec<ComponentSlope>,
+	base_proof_size: u128,
+	base_proof_nodes: u128,
+	component_proof_size: Vec<ComponentSlope>,
+	component_proof_nodes: Vec<ComponentSlope>,
+	comments: Vec<String>,
+}
+
+// This forwards some specific metadata from the `BenchmarkCmd`.
+#[derive(Serialize, Default, Debug, Clone)]
+struct CmdData {
+	steps: Vec<u32>,
+	repeat: u32,
+	lowest_range_values: Vec<u32>,
+	highest_range_values: Vec<u32>,
+	execution: String,
+	wasm_execution: String,
+	chain: String,
+	db_cache: u32,
+}
+
+// The minimum and maximum value a component was benchmarked with.
+#[derive(Serialize, Default, Debug, Clone)]
+struct ComponentRange {
+	name: String,
+	min: u32,
+	max: u32,
+}
+
+// The slope of a component, along with the standard error of the regression if known.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub(crate) struct ComponentSlope {
+	pub name: String,
+	pub slope: u128,
+	pub error: u128,
+}
+
+// The raw results and analysis of a benchmark, as output by `--json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct JsonBatch {
+	pub pallet: String,
+	pub benchmark: String,
+	pub results: Vec<JsonResult>,
+	pub median_slopes: JsonAnalyses,
+	pub min_squares: JsonAnalyses,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct JsonResult {
+	pub components: Vec<(String, u32)>,
+	pub extrinsic_time: u128,
+	pub storage_root_time: u128,
+	pub reads: u32,
+	pub repeat_reads: u32,
+	pub writes: u32,
+	pub repeat_writes: u32,
+	pub proof_size: u32,
+	#[serde(default)]
+	pub proof_nodes: u32,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub(crate) struct JsonAnalyses {
+	pub extrinsic_time: Option<JsonAnalysis>,
+	pub reads: Option<JsonAnalysis>,
+	pub writes: Option<JsonAnalysis>,
+	#[serde(default)]
+	pub proof_size: Option<JsonAnalysis>,
+	#[serde(default)]
+	pub proof_nodes: Option<JsonAnalysis>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct JsonAnalysis {
+	pub base: u128,
+	pub slopes: Vec<ComponentSlope>,
+}
+
+pub(crate) fn io_error(s: &str) -> std::io::Error {
+	use std::io::{Error, ErrorKind};
+	Error::new(ErrorKind::Other, s)
+}
 
-pub fn open_file(path: &str) -> Result<File, std::io::Error> {
+pub fn open_file(path: &Path) -> Result<File, std::io::Error> {
 	OpenOptions::new()
 		.create(true)
 		.write(true)
@@ -74,16 +187,15 @@ pub fn write_trait(file: &mut File, batches: Vec<BenchmarkBatch>) -> Result<(),
 			current_pallet = batch.pallet.clone()
 		}
 
-		// function name
-		write!(file, "\tfn {}(", benchmark_string)?;
-
 		// params
 		let components = &batch.results[0].components;
-		for component in components {
-			write!(file, "{:?}: u32, ", component.0)?;
-		}
-		// return value
-		write!(file, ") -> Weight;\n")?;
+		let params = components.iter()
+			.map(|component| format!("{:?}: u32, ", component.0))
+			.collect::<String>();
+
+		// the weight function and its proof size counterpart
+		write!(file, "\tfn {}({}) -> Weight;\n", benchmark_string, params)?;
+		write!(file, "\tfn {}_proof_size({}) -> ProofSize;\n", benchmark_string, params)?;
 	}
 
 	// final close trait
@@ -92,149 +204,280 @@ pub fn write_trait(file: &mut File, batches: Vec<BenchmarkBatch>) -> Result<(),
 	Ok(())
 }
 
-pub fn write_results(batches: &[BenchmarkBatch]) -> Result<(), std::io::Error> {
-	let mut current_pallet = Vec::<u8>::new();
-
-	// Skip writing if there are no batches
-	if batches.is_empty() { return Ok(()) }
-
-	let mut batches_iter = batches.iter().peekable();
+// Create the weight file data of a single benchmark.
+fn benchmark_data(batch: &BenchmarkBatch) -> BenchmarkData {
+	// Analysis results
+	let extrinsic_time = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::ExtrinsicTime)
+		.expect("analysis of non-empty results is always possible; qed");
+	let reads = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::Reads)
+		.expect("analysis of non-empty results is always possible; qed");
+	let writes = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::Writes)
+		.expect("analysis of non-empty results is always possible; qed");
+	let proof_size = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::ProofSize)
+		.expect("analysis of non-empty results is always possible; qed");
+	let proof_nodes = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::ProofNodes)
+		.expect("analysis of non-empty results is always possible; qed");
+
+	// Analysis data may include components that are not used, this filters out anything whose
+	// value is zero.
+	let mut used_components = Vec::new();
+	let mut used_extrinsic_time = Vec::new();
+	let mut used_reads = Vec::new();
+	let mut used_writes = Vec::new();
+	let mut used_proof_size = Vec::new();
+	let mut used_proof_nodes = Vec::new();
+
+	extrinsic_time.slopes.iter().zip(extrinsic_time.names.iter()).enumerate()
+		.for_each(|(idx, (slope, name))| {
+			if !slope.is_zero() {
+				if !used_components.contains(name) { used_components.push(name.clone()); }
+				let error = extrinsic_time.model.as_ref()
+					.and_then(|model| model.se.regressor_values.get(idx))
+					.map(|error| (*error as u128).saturating_mul(1000))
+					.unwrap_or_default();
+				used_extrinsic_time.push(ComponentSlope {
+					name: name.clone(),
+					slope: slope.saturating_mul(1000),
+					error,
+				});
+			}
+		});
+	reads.slopes.iter().zip(reads.names.iter()).for_each(|(slope, name)| {
+		if !slope.is_zero() {
+			if !used_components.contains(name) { used_components.push(name.clone()); }
+			used_reads.push(ComponentSlope { name: name.clone(), slope: *slope, error: 0 });
+		}
+	});
+	writes.slopes.iter().zip(writes.names.iter()).for_each(|(slope, name)| {
+		if !slope.is_zero() {
+			if !used_components.contains(name) { used_components.push(name.clone()); }
+			used_writes.push(ComponentSlope { name: name.clone(), slope: *slope, error: 0 });
+		}
+	});
+	proof_size.slopes.iter().zip(proof_size.names.iter()).for_each(|(slope, name)| {
+		if !slope.is_zero() {
+			if !used_components.contains(name) { used_components.push(name.clone()); }
+			used_proof_size.push(ComponentSlope { name: name.clone(), slope: *slope, error: 0 });
+		}
+	});
+	proof_nodes.slopes.iter().zip(proof_nodes.names.iter()).for_each(|(slope, name)| {
+		if !slope.is_zero() {
+			used_proof_nodes.push(ComponentSlope { name: name.clone(), slope: *slope, error: 0 });
+		}
+	});
+
+	// The range each component was benchmarked over.
+	let mut component_ranges: Vec<ComponentRange> = Vec::new();
+	for result in &batch.results {
+		for (name, value) in &result.components {
+			let name = name.to_string();
+			match component_ranges.iter_mut().find(|range| range.name == name) {
+				Some(range) => {
+					range.min = range.min.min(*value);
+					range.max = range.max.max(*value);
+				},
+				None => component_ranges.push(ComponentRange { name, min: *value, max: *value }),
+			}
+		}
+	}
 
-	let first_pallet = String::from_utf8(
-		batches_iter.peek().expect("we checked that batches is not empty").pallet.clone()
-	).unwrap();
-	let mut file = open_file(&(first_pallet + ".rs"))?;
+	let mut comments = Vec::new();
+	let unused_components = component_ranges.iter()
+		.map(|range| range.name.clone())
+		.filter(|name| !used_components.contains(name))
+		.collect::<Vec<String>>();
+	if !unused_components.is_empty() {
+		comments.push(format!("WARNING! Some components were not used: {:?}", unused_components));
+	}
 
+	BenchmarkData {
+		name: String::from_utf8(batch.benchmark.clone()).unwrap(),
+		components: used_components,
+		component_ranges,
+		base_weight: extrinsic_time.base.saturating_mul(1000),
+		base_reads: reads.base,
+		base_writes: writes.base,
+		component_weight: used_extrinsic_time,
+		component_reads: used_reads,
+		component_writes: used_writes,
+		base_proof_size: proof_size.base,
+		base_proof_nodes: proof_nodes.base,
+		component_proof_size: used_proof_size,
+		component_proof_nodes: used_proof_nodes,
+		comments,
+	}
+}
 
-	while let Some(batch) = batches_iter.next() {
-		// Skip writing if there are no results
+// Organize the benchmark batches by pallet, skipping those without results.
+fn batches_by_pallet(batches: &[BenchmarkBatch]) -> Vec<(String, Vec<BenchmarkData>)> {
+	let mut all_benchmarks: Vec<(String, Vec<BenchmarkData>)> = Vec::new();
+	for batch in batches {
 		if batch.results.is_empty() { continue }
 
-		let pallet_string = String::from_utf8(batch.pallet.clone()).unwrap();
-		let benchmark_string = String::from_utf8(batch.benchmark.clone()).unwrap();
+		let pallet = String::from_utf8(batch.pallet.clone()).unwrap();
+		let data = benchmark_data(batch);
+		match all_benchmarks.iter_mut().find(|(name, _)| *name == pallet) {
+			Some((_, benchmarks)) => benchmarks.push(data),
+			None => all_benchmarks.push((pallet, vec![data])),
+		}
+	}
+	all_benchmarks
+}
 
-		// only create new trait definitions when we go to a new pallet
-		if batch.pallet != current_pallet {
-			// auto-generation note
-			write!(
-				file,
-				"//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION {}\n\n",
-				 VERSION,
-			)?;
-
-			// allow statements
-			write!(
-				file,
-				"#![allow(unused_parens)]\n#![allow(unused_imports)]\n\n",
-			)?;
-
-			// general imports
-			write!(
-				file,
-				"use frame_support::weights::{{Weight, constants::RocksDbWeight as DbWeight}};\n\n"
-			)?;
-
-			// struct for weights
-			write!(file, "pub struct WeightInfo;\n")?;
+/// Write a weight file for each benchmarked pallet into `output_dir`, named after the pallet.
+///
+/// The files are rendered from the Handlebars template given with `--template`, or from the
+/// default template otherwise.
+pub fn write_results(
+	batches: &[BenchmarkBatch],
+	output_dir: &Path,
+	cmd: &BenchmarkCmd,
+) -> Result<(), std::io::Error> {
+	let template: String = match &cmd.template {
+		Some(template_file) => std::fs::read_to_string(template_file)?,
+		None => TEMPLATE.to_string(),
+	};
+
+	let header_text = match &cmd.header {
+		Some(header_file) => std::fs::read_to_string(header_file)?,
+		None => String::new(),
+	};
+
+	let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+	let args = std::env::args().collect::<Vec<String>>();
+	// The name of the `RuntimeDbWeight` constant in `frame_support::weights::constants`.
+	let db_weight = match cmd.db_weight {
+		Database::RocksDb => "RocksDbWeight",
+		Database::ParityDb => "ParityDbWeight",
+	}.to_string();
+
+	let cmd_data = CmdData {
+		steps: cmd.steps.clone(),
+		repeat: cmd.repeat,
+		lowest_range_values: cmd.lowest_range_values.clone(),
+		highest_range_values: cmd.highest_range_values.clone(),
+		execution: format!("{:?}", cmd.execution),
+		wasm_execution: format!("{:?}", cmd.wasm_method),
+		chain: format!("{:?}", cmd.shared_params.chain),
+		db_cache: cmd.database_cache_size,
+	};
+
+	let mut handlebars = handlebars::Handlebars::new();
+	handlebars.register_helper("underscore", Box::new(UnderscoreHelper));
+	handlebars.register_helper("join", Box::new(JoinHelper));
+	// Don't HTML escape any characters.
+	handlebars.register_escape_fn(|s| -> String { s.to_string() });
+
+	for (pallet, benchmarks) in batches_by_pallet(batches) {
+		let hbs_data = TemplateData {
+			args: args.clone(),
+			date: date.clone(),
+			version: VERSION.to_string(),
+			pallet: pallet.clone(),
+			header: header_text.clone(),
+			db_weight: db_weight.clone(),
+			cmd: cmd_data.clone(),
+			benchmarks,
+		};
+
+		let mut output_file = open_file(&output_dir.join(pallet + ".rs"))?;
+		handlebars.render_template_to_write(&template, &hbs_data, &mut output_file)
+			.map_err(|e| io_error(&e.to_string()))?;
+	}
 
-			// trait wrapper
-			write!(file, "impl {}::WeightInfo for WeightInfo {{\n", pallet_string)?;
+	Ok(())
+}
 
-			current_pallet = batch.pallet.clone()
+/// Serialize the raw results and their analysis to JSON.
+pub fn batches_to_json(batches: &[BenchmarkBatch]) -> Result<String, std::io::Error> {
+	fn analysis(
+		results: &Vec<frame_benchmarking::BenchmarkResults>,
+		analyze: fn(&Vec<frame_benchmarking::BenchmarkResults>, BenchmarkSelector) -> Option<Analysis>,
+	) -> JsonAnalyses {
+		let to_json = |analysis: Analysis| JsonAnalysis {
+			base: analysis.base,
+			slopes: analysis.slopes.iter().zip(analysis.names.iter())
+				.map(|(slope, name)| ComponentSlope { name: name.clone(), slope: *slope, error: 0 })
+				.collect(),
+		};
+
+		JsonAnalyses {
+			extrinsic_time: analyze(results, BenchmarkSelector::ExtrinsicTime).map(to_json),
+			reads: analyze(results, BenchmarkSelector::Reads).map(to_json),
+			writes: analyze(results, BenchmarkSelector::Writes).map(to_json),
+			proof_size: analyze(results, BenchmarkSelector::ProofSize).map(to_json),
+			proof_nodes: analyze(results, BenchmarkSelector::ProofNodes).map(to_json),
 		}
+	}
 
-		// Analysis results
-		let extrinsic_time = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::ExtrinsicTime).unwrap();
-		let reads = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::Reads).unwrap();
-		let writes = Analysis::min_squares_iqr(&batch.results, BenchmarkSelector::Writes).unwrap();
-
-		// Analysis data may include components that are not used, this filters out anything whose value is zero.
-		let mut used_components = Vec::new();
-		let mut used_extrinsic_time = Vec::new();
-		let mut used_reads = Vec::new();
-		let mut used_writes = Vec::new();
-		extrinsic_time.slopes.iter().zip(extrinsic_time.names.iter()).for_each(|(slope, name)| {
-			if !slope.is_zero() {
-				if !used_components.contains(&name) { used_components.push(name); }
-				used_extrinsic_time.push((slope, name));
-			}
-		});
-		reads.slopes.iter().zip(reads.names.iter()).for_each(|(slope, name)| {
-			if !slope.is_zero() {
-				if !used_components.contains(&name) { used_components.push(name); }
-				used_reads.push((slope, name));
-			}
-		});
-		writes.slopes.iter().zip(writes.names.iter()).for_each(|(slope, name)| {
-			if !slope.is_zero() {
-				if !used_components.contains(&name) { used_components.push(name); }
-				used_writes.push((slope, name));
-			}
-		});
+	let json_batches = batches.iter().map(|batch| JsonBatch {
+		pallet: String::from_utf8(batch.pallet.clone()).unwrap(),
+		benchmark: String::from_utf8(batch.benchmark.clone()).unwrap(),
+		results: batch.results.iter().map(|result| JsonResult {
+			components: result.components.iter()
+				.map(|(name, value)| (name.to_string(), *value))
+				.collect(),
+			extrinsic_time: result.extrinsic_time,
+			storage_root_time: result.storage_root_time,
+			reads: result.reads,
+			repeat_reads: result.repeat_reads,
+			writes: result.writes,
+			repeat_writes: result.repeat_writes,
+			proof_size: result.proof_size,
+			proof_nodes: result.proof_nodes,
+		}).collect(),
+		median_slopes: analysis(&batch.results, Analysis::median_slopes),
+		min_squares: analysis(&batch.results, Analysis::min_squares_iqr),
+	}).collect::<Vec<_>>();
+
+	serde_json::to_string_pretty(&json_batches).map_err(|e| io_error(&e.to_string()))
+}
 
-		let all_components = batch.results[0].components
-			.iter()
-			.map(|(name, _)| -> String { return name.to_string() })
-			.collect::<Vec<String>>();
-		if all_components.len() != used_components.len() {
-			let mut unused_components = all_components;
-			unused_components.retain(|x| !used_components.contains(&x));
-			write!(file, "\t// WARNING! Some components were not used: {:?}\n", unused_components)?;
-		}
+// Add an underscore after every 3rd character, i.e. a separator for large numbers.
+#[derive(Clone, Copy)]
+struct UnderscoreHelper;
+impl handlebars::HelperDef for UnderscoreHelper {
+	fn call<'reg: 'rc, 'rc>(
+		&self, h: &handlebars::Helper,
+		_: &handlebars::Handlebars,
+		_: &handlebars::Context,
+		_rc: &mut handlebars::RenderContext,
+		out: &mut dyn handlebars::Output,
+	) -> handlebars::HelperResult {
+		use handlebars::JsonRender;
+		let param = h.param(0)
+			.ok_or_else(|| handlebars::RenderError::new("`underscore` expects a parameter"))?;
+		let underscore_param = underscore(param.value().render());
+		out.write(&underscore_param)?;
+		Ok(())
+	}
+}
 
-		// function name
-		write!(file, "\tfn {}(", benchmark_string)?;
-		// params
-		for component in used_components {
-			write!(file, "{}: u32, ", component)?;
-		}
-		// return value
-		write!(file, ") -> Weight {{\n")?;
-
-		write!(file, "\t\t({} as Weight)\n", underscore(extrinsic_time.base.saturating_mul(1000)))?;
-		used_extrinsic_time.iter().try_for_each(|(slope, name)| -> Result<(), std::io::Error> {
-			write!(file, "\t\t\t.saturating_add(({} as Weight).saturating_mul({} as Weight))\n",
-				underscore(slope.saturating_mul(1000)),
-				name,
-			)
-		})?;
-
-		if !reads.base.is_zero() {
-			write!(file, "\t\t\t.saturating_add(DbWeight::get().reads({} as Weight))\n", reads.base)?;
-		}
-		used_reads.iter().try_for_each(|(slope, name)| -> Result<(), std::io::Error> {
-			write!(file, "\t\t\t.saturating_add(DbWeight::get().reads(({} as Weight).saturating_mul({} as Weight)))\n",
-				slope,
-				name,
-			)
-		})?;
-
-		if !writes.base.is_zero() {
-			write!(file, "\t\t\t.saturating_add(DbWeight::get().writes({} as Weight))\n", writes.base)?;
-		}
-		used_writes.iter().try_for_each(|(slope, name)| -> Result<(), std::io::Error> {
-			write!(file, "\t\t\t.saturating_add(DbWeight::get().writes(({} as Weight).saturating_mul({} as Weight)))\n",
-				slope,
-				name,
-			)
-		})?;
-
-		// close function
-		write!(file, "\t}}\n")?;
-
-		// Check if this is the end of the iterator
-		if let Some(next) = batches_iter.peek() {
-			// Next pallet is different than current pallet, so we close up the file and open a new one.
-			if next.pallet != current_pallet {
-				write!(file, "}}\n")?;
-				let next_pallet = String::from_utf8(next.pallet.clone()).unwrap();
-				file = open_file(&(next_pallet + ".rs"))?;
-			}
+// A Handlebars helper to join a string of vectors.
+#[derive(Clone, Copy)]
+struct JoinHelper;
+impl handlebars::HelperDef for JoinHelper {
+	fn call<'reg: 'rc, 'rc>(
+		&self, h: &handlebars::Helper,
+		_: &handlebars::Handlebars,
+		_: &handlebars::Context,
+		_rc: &mut handlebars::RenderContext,
+		out: &mut dyn handlebars::Output,
+	) -> handlebars::HelperResult {
+		use handlebars::JsonRender;
+		let param = h.param(0)
+			.ok_or_else(|| handlebars::RenderError::new("`join` expects a parameter"))?;
+		let value = param.value();
+		let joined = if value.is_array() {
+			value.as_array().unwrap()
+				.iter()
+				.map(|v| v.render())
+				.collect::<Vec<String>>()
+				.join(" ")
 		} else {
-			// This is the end of the iterator, so we close up the final file.
-			write!(file, "}}\n")?;
-		}
+			value.render()
+		};
+		out.write(&joined)?;
+		Ok(())
 	}
-
-	Ok(())
 }
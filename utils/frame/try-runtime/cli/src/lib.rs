@@ -0,0 +1,364 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `Structopt`-ready struct for `try-runtime`.
+
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use sc_cli::{
+	BlockNumberOrHash, CliConfiguration, DatabaseParams, ExecutionStrategy, PruningParams,
+	SharedParams, WasmExecutionMethod,
+};
+use sc_client_api::backend::Backend as _;
+use sc_executor::NativeExecutor;
+use sc_service::{Configuration, NativeExecutionDispatch};
+use sp_blockchain::{Backend as _, HeaderBackend};
+use sp_core::storage::{
+	well_known_keys, ChildInfo, Storage, StorageChild, StorageData, StorageKey, StorageMap,
+};
+use sp_externalities::Extensions;
+use sp_runtime::{
+	generic::BlockId, traits::{Block as BlockT, Header as HeaderT, HashFor, NumberFor},
+};
+use sp_state_machine::{Backend as _, InMemoryBackend, StateMachine};
+use std::{collections::HashMap, fmt::Debug, fs::File, path::PathBuf, str::FromStr};
+
+/// Same delay as the one used by the service when opening the database of a full node.
+const CANONICALIZATION_DELAY: u64 = 4096;
+
+/// The `try-runtime` command used to test the runtime upgrade against some existing state.
+///
+/// With a local node's database, it can also execute an existing block with the runtime.
+#[derive(Debug, structopt::StructOpt)]
+pub struct TryRuntimeCmd {
+	/// The state to execute the runtime upgrade on.
+	#[structopt(subcommand)]
+	pub state: State,
+
+	/// Replace the runtime code found in the state with the given wasm blob.
+	///
+	/// This is needed to test a runtime upgrade when executing with the wasm runtime, since the
+	/// state still contains the code of the runtime being upgraded from.
+	#[structopt(long, parse(from_os_str))]
+	pub code: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+
+	/// The execution strategy that should be used.
+	#[structopt(
+		long = "execution",
+		value_name = "STRATEGY",
+		possible_values = &ExecutionStrategy::variants(),
+		case_insensitive = true,
+		default_value = "Native",
+	)]
+	pub execution: ExecutionStrategy,
+
+	/// Method for executing Wasm runtime code.
+	#[structopt(
+		long = "wasm-execution",
+		value_name = "METHOD",
+		possible_values = &WasmExecutionMethod::enabled_variants(),
+		case_insensitive = true,
+		default_value = "Interpreted"
+	)]
+	pub wasm_method: WasmExecutionMethod,
+
+	/// Set the heap pages while executing the runtime upgrade.
+	#[structopt(long)]
+	pub heap_pages: Option<u64>,
+}
+
+/// The source of the state the runtime upgrade is executed on.
+#[derive(Debug, Clone, structopt::StructOpt)]
+pub enum State {
+	/// Use a state snapshot file, as produced by the `export-state` command.
+	Snap {
+		/// Path to the snapshot file.
+		#[structopt(parse(from_os_str))]
+		snapshot_path: PathBuf,
+	},
+
+	/// Use the state of a block in the database of the local node.
+	Db {
+		/// Block hash or number at which to read the state. Defaults to the best block.
+		#[structopt(long, value_name = "HASH or NUMBER")]
+		at: Option<BlockNumberOrHash>,
+
+		/// Execute the block `at` on the state of its parent instead of the runtime upgrade.
+		///
+		/// If `--code` replaces the runtime, the runtime upgrade is executed as part of the block,
+		/// but without its `pre_upgrade` and `post_upgrade` checks. The state root of the block is
+		/// not checked, as it depends on the runtime.
+		#[structopt(long)]
+		execute_block: bool,
+	},
+}
+
+/// The part of a chain spec we care about: the raw storage, as written by `export-state`.
+#[derive(serde::Deserialize)]
+struct Snapshot {
+	genesis: SnapshotGenesis,
+}
+
+#[derive(serde::Deserialize)]
+struct SnapshotGenesis {
+	raw: RawState,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawState {
+	top: HashMap<StorageKey, StorageData>,
+	children_default: HashMap<StorageKey, HashMap<StorageKey, StorageData>>,
+}
+
+impl From<RawState> for Storage {
+	fn from(raw: RawState) -> Self {
+		Storage {
+			top: raw.top.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
+			children_default: raw.children_default.into_iter().map(|(storage_key, child)| {
+				let child_info = ChildInfo::new_default(&storage_key.0);
+				(
+					storage_key.0,
+					StorageChild {
+						data: child.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
+						child_info,
+					},
+				)
+			}).collect(),
+		}
+	}
+}
+
+impl TryRuntimeCmd {
+	/// Execute the runtime upgrade on the selected state and report the outcome.
+	pub fn run<B, ExecDispatch>(&self, config: Configuration) -> sc_cli::Result<()>
+	where
+		B: BlockT,
+		B::Hash: FromStr,
+		<B::Hash as FromStr>::Err: Debug,
+		NumberFor<B>: FromStr,
+		<NumberFor<B> as FromStr>::Err: Debug,
+		ExecDispatch: NativeExecutionDispatch + 'static,
+	{
+		let (mut storage, block) = match &self.state {
+			State::Snap { snapshot_path } => {
+				log::info!("Loading state from snapshot {:?}", snapshot_path);
+				(state_from_snapshot(snapshot_path)?, None)
+			},
+			State::Db { at, execute_block } => {
+				let at = at.as_ref().map(|at| at.parse::<B>()).transpose()?;
+				let backend = open_db::<B>(&config)?;
+				let at = at.unwrap_or_else(|| BlockId::Hash(backend.blockchain().info().best_hash));
+				if *execute_block {
+					let block = block_from_db(&backend, at)?;
+					let parent = BlockId::Hash(*block.header().parent_hash());
+					log::info!("Loading state from the database at the parent of {:?}", at);
+					(state_from_db(&backend, parent)?, Some(block))
+				} else {
+					log::info!("Loading state from the database at {:?}", at);
+					(state_from_db(&backend, at)?, None)
+				}
+			},
+		};
+
+		if let Some(code_path) = &self.code {
+			let code = std::fs::read(code_path)
+				.map_err(|e| format!("Failed to read runtime code {:?}: {}", code_path, e))?;
+			storage.top.insert(well_known_keys::CODE.to_vec(), code);
+		}
+
+		log::info!(
+			"Loaded {} top keys and {} child tries",
+			storage.top.len(),
+			storage.children_default.len(),
+		);
+
+		let state = InMemoryBackend::<HashFor<B>>::from(storage);
+
+		if let Some(block) = block {
+			let number = *block.header().number();
+			let encoded_weight = self.execute::<B, ExecDispatch>(
+				&state,
+				"TryRuntime_execute_block_no_check",
+				&block.encode(),
+			)?;
+			let weight = <Weight as Decode>::decode(&mut &*encoded_weight)
+				.map_err(|e| format!("Failed to decode the weight of the block: {:?}", e))?;
+			log::info!("Block #{} executed without errors. Consumed weight = {}", number, weight);
+			return Ok(())
+		}
+
+		let encoded_result = self.execute::<B, ExecDispatch>(
+			&state,
+			"TryRuntime_on_runtime_upgrade",
+			&[],
+		)?;
+
+		let result = <Result<(Weight, Weight), String> as Decode>::decode(&mut &*encoded_result)
+			.map_err(|e| format!("Failed to decode the result of the runtime upgrade: {:?}", e))?;
+
+		match result {
+			Ok((weight, total_weight)) => {
+				log::info!(
+					"Runtime upgrade executed without errors. Consumed weight = {}, total weight = {} ({:.2} %)",
+					weight,
+					total_weight,
+					weight as f64 / total_weight as f64 * 100.0,
+				);
+				Ok(())
+			},
+			Err(reason) => Err(format!("Runtime upgrade checks failed: {}", reason).into()),
+		}
+	}
+
+	/// Call the runtime api `method` with the encoded arguments `data` on top of `state`.
+	fn execute<B, ExecDispatch>(
+		&self,
+		state: &InMemoryBackend<HashFor<B>>,
+		method: &str,
+		data: &[u8],
+	) -> Result<Vec<u8>, String>
+	where
+		B: BlockT,
+		ExecDispatch: NativeExecutionDispatch + 'static,
+	{
+		let executor = NativeExecutor::<ExecDispatch>::new(
+			self.wasm_method.into(),
+			self.heap_pages,
+			2, // The runtime instances cache size.
+		);
+
+		let mut changes = Default::default();
+		let mut offchain_changes = Default::default();
+		StateMachine::<_, _, NumberFor<B>, _>::new(
+			state,
+			None,
+			&mut changes,
+			&mut offchain_changes,
+			&executor,
+			method,
+			data,
+			Extensions::default(),
+			&sp_state_machine::backend::BackendRuntimeCode::new(state).runtime_code()?,
+			sp_core::testing::TaskExecutor::new(),
+		)
+		.execute(self.execution.into())
+		.map_err(|e| format!("Failed to execute `{}`: {:?}", method, e))
+	}
+}
+
+/// Read the raw state of a snapshot file produced by `export-state`.
+fn state_from_snapshot(path: &PathBuf) -> Result<Storage, String> {
+	let file = File::open(path)
+		.map_err(|e| format!("Error opening snapshot file {:?}: {}", path, e))?;
+	let snapshot: Snapshot = serde_json::from_reader(file)
+		.map_err(|e| format!("Error parsing snapshot file {:?}: {}", path, e))?;
+
+	Ok(snapshot.genesis.raw.into())
+}
+
+/// Open the database of the local node.
+fn open_db<B: BlockT>(config: &Configuration) -> Result<sc_client_db::Backend<B>, String> {
+	let settings = sc_client_db::DatabaseSettings {
+		state_cache_size: config.state_cache_size,
+		state_cache_child_ratio: config.state_cache_child_ratio.map(|v| (v, 100)),
+		pruning: config.pruning.clone(),
+		source: config.database.clone(),
+	};
+	sc_client_db::Backend::<B>::new(settings, CANONICALIZATION_DELAY)
+		.map_err(|e| format!("Failed to open the database: {:?}", e))
+}
+
+/// Read the block `at` from the database of the local node.
+fn block_from_db<B: BlockT>(
+	backend: &sc_client_db::Backend<B>,
+	at: BlockId<B>,
+) -> Result<B, String> {
+	let header = backend.blockchain().header(at)
+		.map_err(|e| format!("Failed to get the header of {:?}: {:?}", at, e))?
+		.ok_or_else(|| format!("Block {:?} not found", at))?;
+	let body = backend.blockchain().body(at)
+		.map_err(|e| format!("Failed to get the body of {:?}: {:?}", at, e))?
+		.ok_or_else(|| format!("Body of block {:?} not found", at))?;
+	Ok(B::new(header, body))
+}
+
+/// Read the whole state at the given block from the database of the local node.
+fn state_from_db<B: BlockT>(
+	backend: &sc_client_db::Backend<B>,
+	at: BlockId<B>,
+) -> Result<Storage, String> {
+	let state = backend.state_at(at)
+		.map_err(|e| format!("Failed to get the state at {:?}: {:?}", at, e))?;
+
+	let mut top = StorageMap::new();
+	let mut children_default = HashMap::new();
+	for (key, value) in state.pairs() {
+		if key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			let storage_key = key[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..].to_vec();
+			let child_info = ChildInfo::new_default(&storage_key);
+
+			let mut data = StorageMap::new();
+			for child_key in state.child_keys(&child_info, &[]) {
+				let child_value = state.child_storage(&child_info, &child_key)
+					.map_err(|e| format!("Failed to read child storage: {:?}", e))?;
+				if let Some(child_value) = child_value {
+					data.insert(child_key, child_value);
+				}
+			}
+
+			children_default.insert(storage_key, StorageChild { data, child_info });
+		} else {
+			top.insert(key, value);
+		}
+	}
+
+	Ok(Storage { top, children_default })
+}
+
+impl CliConfiguration for TryRuntimeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+
+	fn chain_id(&self, _is_dev: bool) -> sc_cli::Result<String> {
+		Ok(match self.shared_params.chain {
+			Some(ref chain) => chain.clone(),
+			None => "dev".into(),
+		})
+	}
+}